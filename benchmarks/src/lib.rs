@@ -3,5 +3,6 @@
 extern crate test;
 
 mod reactive;
+mod router;
 mod ssr;
 mod todomvc;
@@ -0,0 +1,206 @@
+use leptos::tachys::view::iterators::StaticVec;
+use leptos_router::{
+    MatchNestedRoutes, NestedRoute, ParamSegment, RouteDefs, StaticSegment,
+    WildcardSegment,
+};
+use test::Bencher;
+
+macro_rules! siblings {
+    ($($path:literal),*) => {
+        ($(NestedRoute::new(StaticSegment($path), || ()),)*)
+    };
+}
+
+#[bench]
+fn router_miss_among_16_siblings(b: &mut Bencher) {
+    let routes = RouteDefs::<_>::new(siblings!(
+        "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "r10",
+        "r11", "r12", "r13", "r14", "r15"
+    ));
+    b.iter(|| assert!(routes.match_route(test::black_box("/miss")).is_none()));
+}
+
+#[bench]
+fn router_miss_among_32_siblings(b: &mut Bencher) {
+    let routes = RouteDefs::<_>::new(siblings!(
+        "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "r10",
+        "r11", "r12", "r13", "r14", "r15", "r16", "r17", "r18", "r19", "r20",
+        "r21", "r22", "r23", "r24", "r25", "r26", "r27", "r28", "r29", "r30",
+        "r31"
+    ));
+    b.iter(|| assert!(routes.match_route(test::black_box("/miss")).is_none()));
+}
+
+#[bench]
+fn router_miss_among_32_nested_siblings(b: &mut Bencher) {
+    let routes = RouteDefs::<_>::new((
+        siblings!(
+            "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "r10",
+            "r11", "r12", "r13", "r14", "r15"
+        ),
+        siblings!(
+            "r16", "r17", "r18", "r19", "r20", "r21", "r22", "r23", "r24",
+            "r25", "r26", "r27", "r28", "r29", "r30", "r31"
+        ),
+    ));
+    b.iter(|| assert!(routes.match_route(test::black_box("/miss")).is_none()));
+}
+
+fn static_siblings(
+    count: usize,
+) -> StaticVec<impl MatchNestedRoutes + Clone> {
+    StaticVec::from(
+        (0..count)
+            .map(|i| {
+                let path: &'static str = format!("r{i}").leak();
+                NestedRoute::new(StaticSegment(path), || ())
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn static_routes(
+    count: usize,
+) -> RouteDefs<StaticVec<impl MatchNestedRoutes + Clone>> {
+    RouteDefs::new(static_siblings(count))
+}
+
+#[bench]
+fn router_last_of_2000_static_routes_tree_walk(b: &mut Bencher) {
+    let routes = static_routes(2000);
+    b.iter(|| assert!(routes.match_route(test::black_box("/r1999")).is_some()));
+}
+
+#[bench]
+fn router_last_of_2000_static_routes_compiled(b: &mut Bencher) {
+    let routes = static_routes(2000).compile();
+    b.iter(|| assert!(routes.match_route(test::black_box("/r1999")).is_some()));
+}
+
+#[bench]
+fn router_last_of_50_static_routes_tree_walk(b: &mut Bencher) {
+    let routes = static_routes(50);
+    b.iter(|| assert!(routes.match_route(test::black_box("/r49")).is_some()));
+}
+
+#[bench]
+fn router_last_of_50_static_routes_compiled(b: &mut Bencher) {
+    let routes = static_routes(50).compile();
+    b.iter(|| assert!(routes.match_route(test::black_box("/r49")).is_some()));
+}
+
+#[bench]
+fn router_last_of_200_static_routes_tree_walk(b: &mut Bencher) {
+    let routes = static_routes(200);
+    b.iter(|| assert!(routes.match_route(test::black_box("/r199")).is_some()));
+}
+
+#[bench]
+fn router_last_of_200_static_routes_compiled(b: &mut Bencher) {
+    let routes = static_routes(200).compile();
+    b.iter(|| assert!(routes.match_route(test::black_box("/r199")).is_some()));
+}
+
+macro_rules! nested {
+    ($path:literal) => {
+        NestedRoute::new(StaticSegment($path), || ())
+    };
+    ($path:literal, $($rest:literal),+) => {
+        NestedRoute::new(StaticSegment($path), || ()).child(nested!($($rest),+))
+    };
+}
+
+#[bench]
+fn router_10_levels_nested_tree_walk(b: &mut Bencher) {
+    let routes = RouteDefs::<_>::new(nested!(
+        "l0", "l1", "l2", "l3", "l4", "l5", "l6", "l7", "l8", "l9"
+    ));
+    let path = "/l0/l1/l2/l3/l4/l5/l6/l7/l8/l9";
+    b.iter(|| assert!(routes.match_route(test::black_box(path)).is_some()));
+}
+
+#[bench]
+fn router_10_levels_nested_compiled(b: &mut Bencher) {
+    let routes = RouteDefs::<_>::new(nested!(
+        "l0", "l1", "l2", "l3", "l4", "l5", "l6", "l7", "l8", "l9"
+    ))
+    .compile();
+    let path = "/l0/l1/l2/l3/l4/l5/l6/l7/l8/l9";
+    b.iter(|| assert!(routes.match_route(test::black_box(path)).is_some()));
+}
+
+#[bench]
+fn router_last_of_50_param_routes(b: &mut Bencher) {
+    let routes = RouteDefs::<_>::new(StaticVec::from(
+        (0..50)
+            .map(|i| {
+                let path: &'static str = format!("r{i}").leak();
+                NestedRoute::new(
+                    (
+                        StaticSegment(path),
+                        ParamSegment("id"),
+                        StaticSegment("posts"),
+                        ParamSegment("post"),
+                    ),
+                    || (),
+                )
+            })
+            .collect::<Vec<_>>(),
+    ));
+    b.iter(|| {
+        assert!(routes
+            .match_route(test::black_box("/r49/42/posts/7"))
+            .is_some())
+    });
+}
+
+#[bench]
+fn router_wildcard_fallback_after_50_static_routes(b: &mut Bencher) {
+    let routes = RouteDefs::<_>::new((
+        static_siblings(50),
+        NestedRoute::new(WildcardSegment("any"), || ()),
+    ));
+    b.iter(|| {
+        assert!(routes
+            .match_route(test::black_box("/missing/page"))
+            .is_some())
+    });
+}
+
+#[bench]
+fn router_wildcard_fallback_after_50_static_routes_compiled(b: &mut Bencher) {
+    let routes = RouteDefs::<_>::new((
+        static_siblings(50),
+        NestedRoute::new(WildcardSegment("any"), || ()),
+    ))
+    .compile();
+    b.iter(|| {
+        assert!(routes
+            .match_route(test::black_box("/missing/page"))
+            .is_some())
+    });
+}
+
+/// A view that captures a large table, which is cloned with it.
+fn large_view() -> impl Fn() + Send + Sync + Clone + 'static {
+    let table = (0..1000).map(|i| format!("row {i}")).collect::<Vec<_>>();
+    move || {
+        test::black_box(&table);
+    }
+}
+
+#[bench]
+fn router_match_large_view(b: &mut Bencher) {
+    let routes =
+        RouteDefs::<_>::new(NestedRoute::new(StaticSegment("table"), large_view()));
+    b.iter(|| assert!(routes.match_route(test::black_box("/table")).is_some()));
+}
+
+#[bench]
+fn router_match_large_shared_view(b: &mut Bencher) {
+    let routes = RouteDefs::<_>::new(NestedRoute::new_shared(
+        StaticSegment("table"),
+        large_view(),
+    ));
+    b.iter(|| assert!(routes.match_route(test::black_box("/table")).is_some()));
+}
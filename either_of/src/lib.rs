@@ -751,6 +751,430 @@ tuples!(EitherOf16 + EitherOf16Future + EitherOf16FutureProj {
     O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P>,
     P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1>,
 });
+tuples!(EitherOf17 + EitherOf17Future + EitherOf17FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1>,
+});
+tuples!(EitherOf18 + EitherOf18Future + EitherOf18FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q, R) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q, R>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q, R) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q, R>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q, R) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q, R>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q, R) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q, R>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q, R) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q, R>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q, R) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q, R>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q, R) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q, R>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q, R) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q, R>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q, R) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q, R>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q, R) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q, R>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q, R) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q, R>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q, R) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q, R>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q, R) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q, R>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, R) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1, R>,
+    R => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R1>,
+});
+tuples!(EitherOf19 + EitherOf19Future + EitherOf19FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q, R, S) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q, R, S>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q, R, S) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q, R, S>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q, R, S) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q, R, S>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q, R, S) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q, R, S>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q, R, S) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q, R, S>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q, R, S) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q, R, S>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q, R, S) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q, R, S>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q, R, S) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q, R, S>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q, R, S) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q, R, S>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q, R, S) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q, R, S>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q, R, S) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q, R, S>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q, R, S) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q, R, S>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, R, S) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1, R, S>,
+    R => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, S) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R1, S>,
+    S => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S1>,
+});
+tuples!(EitherOf20 + EitherOf20Future + EitherOf20FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q, R, S, T) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q, R, S, T>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q, R, S, T) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q, R, S, T>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q, R, S, T) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q, R, S, T>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q, R, S, T) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q, R, S, T>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q, R, S, T) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q, R, S, T>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q, R, S, T) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q, R, S, T>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q, R, S, T) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q, R, S, T>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q, R, S, T) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q, R, S, T>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q, R, S, T) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q, R, S, T>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q, R, S, T) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q, R, S, T>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q, R, S, T) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q, R, S, T>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, R, S, T) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1, R, S, T>,
+    R => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, S, T) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R1, S, T>,
+    S => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, T) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S1, T>,
+    T => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T1>,
+});
+tuples!(EitherOf21 + EitherOf21Future + EitherOf21FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q, R, S, T, U) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q, R, S, T, U>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q, R, S, T, U) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q, R, S, T, U>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q, R, S, T, U) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q, R, S, T, U>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q, R, S, T, U) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q, R, S, T, U>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q, R, S, T, U) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q, R, S, T, U>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q, R, S, T, U) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q, R, S, T, U>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q, R, S, T, U) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q, R, S, T, U>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q, R, S, T, U) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q, R, S, T, U>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q, R, S, T, U) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q, R, S, T, U>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q, R, S, T, U) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q, R, S, T, U>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, R, S, T, U) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1, R, S, T, U>,
+    R => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, S, T, U) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R1, S, T, U>,
+    S => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, T, U) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S1, T, U>,
+    T => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, U) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T1, U>,
+    U => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U1>,
+});
+tuples!(EitherOf22 + EitherOf22Future + EitherOf22FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q, R, S, T, U, V) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q, R, S, T, U, V>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q, R, S, T, U, V) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q, R, S, T, U, V>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q, R, S, T, U, V) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q, R, S, T, U, V>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q, R, S, T, U, V) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q, R, S, T, U, V>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q, R, S, T, U, V) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q, R, S, T, U, V>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q, R, S, T, U, V) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q, R, S, T, U, V>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q, R, S, T, U, V) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q, R, S, T, U, V>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q, R, S, T, U, V) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q, R, S, T, U, V>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q, R, S, T, U, V) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q, R, S, T, U, V>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, R, S, T, U, V) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1, R, S, T, U, V>,
+    R => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, S, T, U, V) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R1, S, T, U, V>,
+    S => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, T, U, V) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S1, T, U, V>,
+    T => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, U, V) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T1, U, V>,
+    U => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, V) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U1, V>,
+    V => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V1>,
+});
+tuples!(EitherOf23 + EitherOf23Future + EitherOf23FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q, R, S, T, U, V, W) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q, R, S, T, U, V, W>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q, R, S, T, U, V, W) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q, R, S, T, U, V, W>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q, R, S, T, U, V, W) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q, R, S, T, U, V, W>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q, R, S, T, U, V, W) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q, R, S, T, U, V, W>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q, R, S, T, U, V, W) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q, R, S, T, U, V, W>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q, R, S, T, U, V, W) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q, R, S, T, U, V, W>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q, R, S, T, U, V, W) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q, R, S, T, U, V, W>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q, R, S, T, U, V, W) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q, R, S, T, U, V, W>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, R, S, T, U, V, W) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1, R, S, T, U, V, W>,
+    R => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, S, T, U, V, W) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R1, S, T, U, V, W>,
+    S => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, T, U, V, W) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S1, T, U, V, W>,
+    T => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, U, V, W) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T1, U, V, W>,
+    U => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, V, W) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U1, V, W>,
+    V => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, W) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V1, W>,
+    W => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W1>,
+});
+tuples!(EitherOf24 + EitherOf24Future + EitherOf24FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q, R, S, T, U, V, W, X) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q, R, S, T, U, V, W, X>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q, R, S, T, U, V, W, X) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q, R, S, T, U, V, W, X>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q, R, S, T, U, V, W, X) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q, R, S, T, U, V, W, X>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q, R, S, T, U, V, W, X) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q, R, S, T, U, V, W, X>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q, R, S, T, U, V, W, X) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q, R, S, T, U, V, W, X>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q, R, S, T, U, V, W, X) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q, R, S, T, U, V, W, X>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q, R, S, T, U, V, W, X) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q, R, S, T, U, V, W, X>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, R, S, T, U, V, W, X) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1, R, S, T, U, V, W, X>,
+    R => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, S, T, U, V, W, X) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R1, S, T, U, V, W, X>,
+    S => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, T, U, V, W, X) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S1, T, U, V, W, X>,
+    T => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, U, V, W, X) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T1, U, V, W, X>,
+    U => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, V, W, X) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U1, V, W, X>,
+    V => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, W, X) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V1, W, X>,
+    W => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, X) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W1, X>,
+    X => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X1>,
+});
+tuples!(EitherOf25 + EitherOf25Future + EitherOf25FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q, R, S, T, U, V, W, X, Y) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q, R, S, T, U, V, W, X, Y>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q, R, S, T, U, V, W, X, Y) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q, R, S, T, U, V, W, X, Y>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q, R, S, T, U, V, W, X, Y) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q, R, S, T, U, V, W, X, Y>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q, R, S, T, U, V, W, X, Y) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q, R, S, T, U, V, W, X, Y>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q, R, S, T, U, V, W, X, Y) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q, R, S, T, U, V, W, X, Y>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, R, S, T, U, V, W, X, Y) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1, R, S, T, U, V, W, X, Y>,
+    R => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, S, T, U, V, W, X, Y) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R1, S, T, U, V, W, X, Y>,
+    S => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, T, U, V, W, X, Y) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S1, T, U, V, W, X, Y>,
+    T => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, U, V, W, X, Y) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T1, U, V, W, X, Y>,
+    U => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, V, W, X, Y) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U1, V, W, X, Y>,
+    V => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, W, X, Y) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V1, W, X, Y>,
+    W => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, X, Y) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W1, X, Y>,
+    X => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, Y) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X1, Y>,
+    Y => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y1>,
+});
+tuples!(EitherOf26 + EitherOf26Future + EitherOf26FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q, R, S, T, U, V, W, X, Y, Z) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q, R, S, T, U, V, W, X, Y, Z>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q, R, S, T, U, V, W, X, Y, Z) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q, R, S, T, U, V, W, X, Y, Z>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q, R, S, T, U, V, W, X, Y, Z) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q, R, S, T, U, V, W, X, Y, Z>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q, R, S, T, U, V, W, X, Y, Z) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q, R, S, T, U, V, W, X, Y, Z>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, R, S, T, U, V, W, X, Y, Z) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1, R, S, T, U, V, W, X, Y, Z>,
+    R => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, S, T, U, V, W, X, Y, Z) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R1, S, T, U, V, W, X, Y, Z>,
+    S => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, T, U, V, W, X, Y, Z) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S1, T, U, V, W, X, Y, Z>,
+    T => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, U, V, W, X, Y, Z) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T1, U, V, W, X, Y, Z>,
+    U => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, V, W, X, Y, Z) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U1, V, W, X, Y, Z>,
+    V => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, W, X, Y, Z) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V1, W, X, Y, Z>,
+    W => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, X, Y, Z) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W1, X, Y, Z>,
+    X => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, Y, Z) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X1, Y, Z>,
+    Y => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Z) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y1, Z>,
+    Z => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z1>,
+});
+tuples!(EitherOf27 + EitherOf27Future + EitherOf27FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q, R, S, T, U, V, W, X, Y, Z, AA>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1, R, S, T, U, V, W, X, Y, Z, AA>,
+    R => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R1, S, T, U, V, W, X, Y, Z, AA>,
+    S => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S1, T, U, V, W, X, Y, Z, AA>,
+    T => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T1, U, V, W, X, Y, Z, AA>,
+    U => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U1, V, W, X, Y, Z, AA>,
+    V => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V1, W, X, Y, Z, AA>,
+    W => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W1, X, Y, Z, AA>,
+    X => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X1, Y, Z, AA>,
+    Y => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y1, Z, AA>,
+    Z => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, AA) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z1, AA>,
+    AA => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA1>,
+});
+tuples!(EitherOf28 + EitherOf28Future + EitherOf28FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1, R, S, T, U, V, W, X, Y, Z, AA, AB>,
+    R => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R1, S, T, U, V, W, X, Y, Z, AA, AB>,
+    S => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S1, T, U, V, W, X, Y, Z, AA, AB>,
+    T => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T1, U, V, W, X, Y, Z, AA, AB>,
+    U => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U1, V, W, X, Y, Z, AA, AB>,
+    V => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V1, W, X, Y, Z, AA, AB>,
+    W => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W1, X, Y, Z, AA, AB>,
+    X => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X1, Y, Z, AA, AB>,
+    Y => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y1, Z, AA, AB>,
+    Z => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z1, AA, AB>,
+    AA => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA1, AB>,
+    AB => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB1>,
+});
+tuples!(EitherOf29 + EitherOf29Future + EitherOf29FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1, R, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    R => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R1, S, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    S => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S1, T, U, V, W, X, Y, Z, AA, AB, AC>,
+    T => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T1, U, V, W, X, Y, Z, AA, AB, AC>,
+    U => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U1, V, W, X, Y, Z, AA, AB, AC>,
+    V => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V1, W, X, Y, Z, AA, AB, AC>,
+    W => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W1, X, Y, Z, AA, AB, AC>,
+    X => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X1, Y, Z, AA, AB, AC>,
+    Y => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y1, Z, AA, AB, AC>,
+    Z => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z1, AA, AB, AC>,
+    AA => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA1, AB, AC>,
+    AB => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB1, AC>,
+    AC => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC1>,
+});
+tuples!(EitherOf30 + EitherOf30Future + EitherOf30FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    R => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R1, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    S => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S1, T, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    T => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T1, U, V, W, X, Y, Z, AA, AB, AC, AD>,
+    U => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U1, V, W, X, Y, Z, AA, AB, AC, AD>,
+    V => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V1, W, X, Y, Z, AA, AB, AC, AD>,
+    W => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W1, X, Y, Z, AA, AB, AC, AD>,
+    X => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X1, Y, Z, AA, AB, AC, AD>,
+    Y => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y1, Z, AA, AB, AC, AD>,
+    Z => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z1, AA, AB, AC, AD>,
+    AA => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA1, AB, AC, AD>,
+    AB => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB1, AC, AD>,
+    AC => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC1, AD>,
+    AD => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD1>,
+});
+tuples!(EitherOf31 + EitherOf31Future + EitherOf31FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    R => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R1, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    S => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S1, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    T => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T1, U, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    U => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U1, V, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    V => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V1, W, X, Y, Z, AA, AB, AC, AD, AE>,
+    W => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W1, X, Y, Z, AA, AB, AC, AD, AE>,
+    X => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X1, Y, Z, AA, AB, AC, AD, AE>,
+    Y => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y1, Z, AA, AB, AC, AD, AE>,
+    Z => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z1, AA, AB, AC, AD, AE>,
+    AA => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA1, AB, AC, AD, AE>,
+    AB => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB1, AC, AD, AE>,
+    AC => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC1, AD, AE>,
+    AD => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD1, AE>,
+    AE => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE1>,
+});
+tuples!(EitherOf32 + EitherOf32Future + EitherOf32FutureProj {
+    A => (B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A1, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    B => (A, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B1, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    C => (A, B, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C1, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    D => (A, B, C, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D1, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    E => (A, B, C, D, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E1, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    F => (A, B, C, D, E, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F1, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    G => (A, B, C, D, E, F, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G1, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    H => (A, B, C, D, E, F, G, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H1, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    I => (A, B, C, D, E, F, G, H, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I1, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    J => (A, B, C, D, E, F, G, H, I, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J1, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    K => (A, B, C, D, E, F, G, H, I, J, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K1, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    L => (A, B, C, D, E, F, G, H, I, J, K, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L1, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    M => (A, B, C, D, E, F, G, H, I, J, K, L, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M1, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    N => (A, B, C, D, E, F, G, H, I, J, K, L, M, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N1, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    O => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O1, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    P => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P1, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    Q => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q1, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    R => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R1, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    S => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S1, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    T => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T1, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    U => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U1, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    V => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, W, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V1, W, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    W => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, X, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W1, X, Y, Z, AA, AB, AC, AD, AE, AF>,
+    X => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, Y, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X1, Y, Z, AA, AB, AC, AD, AE, AF>,
+    Y => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Z, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y1, Z, AA, AB, AC, AD, AE, AF>,
+    Z => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, AA, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z1, AA, AB, AC, AD, AE, AF>,
+    AA => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AB, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA1, AB, AC, AD, AE, AF>,
+    AB => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AC, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB1, AC, AD, AE, AF>,
+    AC => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AD, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC1, AD, AE, AF>,
+    AD => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AE, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD1, AE, AF>,
+    AE => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AF) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE1, AF>,
+    AF => (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE) + <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF1>,
+});
 
 /// Matches over the first expression and returns an either ([`Either`], [`EitherOf3`], ... [`EitherOf8`])
 /// composed of the values returned by the match arms.
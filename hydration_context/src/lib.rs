@@ -18,15 +18,17 @@ mod csr;
 #[cfg(feature = "browser")]
 #[cfg_attr(docsrs, doc(cfg(feature = "browser")))]
 mod hydrate;
+mod metrics;
 mod ssr;
 #[cfg(feature = "browser")]
 pub use csr::*;
 use futures::Stream;
 #[cfg(feature = "browser")]
 pub use hydrate::*;
+pub use metrics::*;
 use serde::{Deserialize, Serialize};
 pub use ssr::*;
-use std::{fmt::Debug, future::Future, pin::Pin};
+use std::{borrow::Cow, fmt::Debug, future::Future, pin::Pin};
 use throw_error::{Error, ErrorId};
 
 /// Type alias for a boxed [`Future`].
@@ -165,4 +167,29 @@ pub trait SharedContext: Debug {
 
     /// Checks whether this chunk is being sent from the server before all its data have loaded.
     fn get_incomplete_chunk(&self, id: &SerializedDataId) -> bool;
+
+    /// Names a piece of data that will be serialized, like after the place
+    /// where the resource that loads it was defined, for the
+    /// [`ResponseMetrics`] of the response.
+    ///
+    /// By default, names are ignored.
+    fn name_data(&self, id: &SerializedDataId, name: Cow<'static, str>) {
+        _ = (id, name);
+    }
+
+    /// Adds the size of the serialized props of an island, in bytes, to the
+    /// [`ResponseMetrics`] of the response.
+    ///
+    /// By default, sizes are ignored.
+    fn record_island_props(&self, island: &'static str, bytes: usize) {
+        _ = (island, bytes);
+    }
+
+    /// The sizes of the data that have been written to the response for
+    /// hydration so far.
+    ///
+    /// By default, sizes are not tracked, and this returns `None`.
+    fn response_metrics(&self) -> Option<ResponseMetrics> {
+        None
+    }
 }
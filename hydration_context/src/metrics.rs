@@ -0,0 +1,118 @@
+use crate::SerializedDataId;
+use std::{borrow::Cow, fmt::Write};
+
+/// The sizes of the data that a response sent from the server to the client for
+/// hydration, as it was written to the response, to keep track of how much each
+/// page costs to hydrate.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResponseMetrics {
+    /// The serialized resources, in the order they were sent.
+    pub resources: Vec<ResourcePayload>,
+    /// The serialized props of the islands, in the order they were rendered.
+    pub islands: Vec<IslandPayload>,
+}
+
+/// The size of a resource that was serialized from the server to the client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourcePayload {
+    /// The ID of the resource's data.
+    pub id: SerializedDataId,
+    /// The name of the resource, which is where it was defined in debug builds,
+    /// if it is known.
+    pub name: Option<Cow<'static, str>>,
+    /// The number of bytes written to the response for the resource.
+    pub bytes: usize,
+}
+
+/// The size of the props of an island that were serialized from the server to
+/// the client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IslandPayload {
+    /// The name of the island component.
+    pub name: Cow<'static, str>,
+    /// The number of bytes of its serialized props.
+    pub bytes: usize,
+}
+
+impl ResponseMetrics {
+    /// The number of bytes of all the serialized resources.
+    pub fn resource_bytes(&self) -> usize {
+        self.resources.iter().map(|resource| resource.bytes).sum()
+    }
+
+    /// The number of bytes of the serialized props of all the islands.
+    pub fn island_bytes(&self) -> usize {
+        self.islands.iter().map(|island| island.bytes).sum()
+    }
+
+    /// The number of bytes of all the data sent for hydration.
+    pub fn total_bytes(&self) -> usize {
+        self.resource_bytes() + self.island_bytes()
+    }
+
+    /// Formats the sizes like a `Server-Timing` header, like
+    /// `hydration;desc="total bytes";dur=1520, hydration-resources;desc="resource bytes";dur=1400, ...`,
+    /// with the number of bytes as the duration of each metric, so that they
+    /// are shown by the network panel of the browser's developer tools.
+    ///
+    /// Each resource is listed with its name, if it has one, or its ID.
+    pub fn server_timing(&self) -> String {
+        let mut header = format!(
+            "hydration;desc=\"total bytes\";dur={}, \
+             hydration-resources;desc=\"resource bytes\";dur={}, \
+             hydration-islands;desc=\"island props bytes\";dur={}",
+            self.total_bytes(),
+            self.resource_bytes(),
+            self.island_bytes()
+        );
+        for (index, resource) in self.resources.iter().enumerate() {
+            let name = resource.name.as_deref().map_or_else(
+                || format!("resource {}", resource.id.0),
+                // the description is a quoted string, so quotes are left out
+                |name| name.replace(['"', '\\'], ""),
+            );
+            _ = write!(
+                header,
+                ", hydration-resource-{index};desc=\"{name}\";dur={}",
+                resource.bytes
+            );
+        }
+        header
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IslandPayload, ResourcePayload, ResponseMetrics};
+    use crate::SerializedDataId;
+
+    #[test]
+    fn the_metrics_are_sent_like_server_timing() {
+        let metrics = ResponseMetrics {
+            resources: vec![
+                ResourcePayload {
+                    id: SerializedDataId(0),
+                    name: Some("\"user\"".into()),
+                    bytes: 300,
+                },
+                ResourcePayload {
+                    id: SerializedDataId(3),
+                    name: None,
+                    bytes: 1100,
+                },
+            ],
+            islands: vec![IslandPayload {
+                name: "Counter".into(),
+                bytes: 120,
+            }],
+        };
+        assert_eq!(
+            metrics.server_timing(),
+            "hydration;desc=\"total bytes\";dur=1520, \
+             hydration-resources;desc=\"resource bytes\";dur=1400, \
+             hydration-islands;desc=\"island props bytes\";dur=120, \
+             hydration-resource-0;desc=\"user\";dur=300, \
+             hydration-resource-1;desc=\"resource 3\";dur=1100"
+        );
+    }
+}
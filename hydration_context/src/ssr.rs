@@ -1,5 +1,7 @@
 use super::{SerializedDataId, SharedContext};
-use crate::{PinnedFuture, PinnedStream};
+use crate::{
+    IslandPayload, PinnedFuture, PinnedStream, ResourcePayload, ResponseMetrics,
+};
 use futures::{
     future::join_all,
     stream::{self, once},
@@ -7,7 +9,8 @@ use futures::{
 };
 use or_poisoned::OrPoisoned;
 use std::{
-    collections::HashSet,
+    borrow::Cow,
+    collections::{HashMap, HashSet},
     fmt::{Debug, Write},
     mem,
     pin::Pin,
@@ -23,6 +26,28 @@ type AsyncDataBuf = Arc<RwLock<Vec<(SerializedDataId, PinnedFuture<String>)>>>;
 type ErrorBuf = Arc<RwLock<Vec<(SerializedDataId, ErrorId, Error)>>>;
 type SealedErrors = Arc<RwLock<HashSet<SerializedDataId>>>;
 
+/// Tallies the sizes of the data written to the response, as they are written.
+#[derive(Debug, Default)]
+struct PayloadTally {
+    names: Mutex<HashMap<SerializedDataId, Cow<'static, str>>>,
+    metrics: Mutex<ResponseMetrics>,
+}
+
+impl PayloadTally {
+    fn resource(&self, id: &SerializedDataId, bytes: usize) {
+        let name = self.names.lock().or_poisoned().remove(id);
+        self.metrics
+            .lock()
+            .or_poisoned()
+            .resources
+            .push(ResourcePayload {
+                id: id.clone(),
+                name,
+                bytes,
+            });
+    }
+}
+
 #[derive(Default)]
 /// The shared context that should be used on the server side.
 pub struct SsrSharedContext {
@@ -35,6 +60,7 @@ pub struct SsrSharedContext {
     sealed_error_boundaries: SealedErrors,
     deferred: Mutex<Vec<PinnedFuture<()>>>,
     incomplete: Arc<Mutex<Vec<SerializedDataId>>>,
+    tally: Arc<PayloadTally>,
 }
 
 impl SsrSharedContext {
@@ -175,7 +201,10 @@ impl SharedContext for SsrSharedContext {
         // resolved synchronous resources and errors
         initial_chunk.push_str("__RESOLVED_RESOURCES=[");
         for resolved in sync_data {
+            let start = initial_chunk.len();
             resolved.write_to_buf(&mut initial_chunk);
+            self.tally
+                .resource(&resolved.0, initial_chunk.len() - start);
             initial_chunk.push(',');
         }
         initial_chunk.push_str("];");
@@ -206,6 +235,7 @@ impl SharedContext for SsrSharedContext {
             async_buf: Arc::clone(&self.async_buf),
             errors: Arc::clone(&self.errors),
             sealed_error_boundaries: Arc::clone(&self.sealed_error_boundaries),
+            tally: Arc::clone(&self.tally),
         };
 
         let incomplete = Arc::clone(&self.incomplete);
@@ -256,12 +286,37 @@ impl SharedContext for SsrSharedContext {
             .iter()
             .any(|entry| entry == id)
     }
+
+    fn name_data(&self, id: &SerializedDataId, name: Cow<'static, str>) {
+        self.tally
+            .names
+            .lock()
+            .or_poisoned()
+            .insert(id.clone(), name);
+    }
+
+    fn record_island_props(&self, island: &'static str, bytes: usize) {
+        self.tally
+            .metrics
+            .lock()
+            .or_poisoned()
+            .islands
+            .push(IslandPayload {
+                name: Cow::Borrowed(island),
+                bytes,
+            });
+    }
+
+    fn response_metrics(&self) -> Option<ResponseMetrics> {
+        Some(self.tally.metrics.lock().or_poisoned().clone())
+    }
 }
 
 struct AsyncDataStream {
     async_buf: AsyncDataBuf,
     errors: ErrorBuf,
     sealed_error_boundaries: SealedErrors,
+    tally: Arc<PayloadTally>,
 }
 
 impl Stream for AsyncDataStream {
@@ -282,11 +337,13 @@ impl Stream for AsyncDataStream {
                 }
                 Poll::Ready(data) => {
                     let data = data.replace('<', "\\u003c");
+                    let start = resolved.len();
                     _ = write!(
                         resolved,
                         "__RESOLVED_RESOURCES[{}] = {:?};",
                         id.0, data
                     );
+                    self.tally.resource(&id, resolved.len() - start);
                 }
             }
         }
@@ -325,3 +382,57 @@ impl ResolvedData {
         write!(buf, "{}: {:?}", id.0, ser).unwrap();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::SsrSharedContext;
+    use crate::{IslandPayload, ResourcePayload, SharedContext};
+    use futures::{executor::block_on, StreamExt};
+    use std::borrow::Cow;
+
+    #[test]
+    fn the_payload_is_tallied_as_it_is_written() {
+        let sc = SsrSharedContext::new();
+        let user = sc.next_id();
+        let posts = sc.next_id();
+        sc.name_data(&user, "src/app.rs:12:16".into());
+        sc.write_async(user.clone(), Box::pin(async { "{\"id\":1}".into() }));
+        sc.write_async(posts.clone(), Box::pin(async { "[<p>]".into() }));
+        sc.record_island_props("Counter", 12);
+
+        let written = block_on(sc.pending_data().unwrap().collect::<String>());
+        let metrics = sc.response_metrics().unwrap();
+        // the sizes are those of the scripts that were written, escapes
+        // included
+        let user_script = r#"__RESOLVED_RESOURCES[0] = "{\"id\":1}";"#;
+        let posts_script = r#"__RESOLVED_RESOURCES[1] = "[\\u003cp>]";"#;
+        assert!(written.contains(user_script), "{written}");
+        assert!(written.contains(posts_script), "{written}");
+        assert_eq!(
+            metrics.resources,
+            [
+                ResourcePayload {
+                    id: user,
+                    name: Some(Cow::Borrowed("src/app.rs:12:16")),
+                    bytes: user_script.len(),
+                },
+                ResourcePayload {
+                    id: posts,
+                    name: None,
+                    bytes: posts_script.len(),
+                },
+            ]
+        );
+        assert_eq!(
+            metrics.islands,
+            [IslandPayload {
+                name: "Counter".into(),
+                bytes: 12
+            }]
+        );
+        assert_eq!(
+            metrics.total_bytes(),
+            user_script.len() + posts_script.len() + 12
+        );
+    }
+}
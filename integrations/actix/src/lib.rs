@@ -17,32 +17,56 @@ use actix_web::{
     *,
 };
 use dashmap::DashMap;
-use futures::{stream::once, Stream, StreamExt};
+use futures::{future::BoxFuture, stream::once, Stream, StreamExt};
 use http::StatusCode;
-use hydration_context::SsrSharedContext;
+use hydration_context::{SharedContext, SsrSharedContext};
 use leptos::{
     config::LeptosOptions,
     context::{provide_context, use_context},
     hydration::IslandsRouterNavigation,
     prelude::expect_context,
     reactive::{computed::ScopedFuture, owner::Owner},
+    server::{RequestDeadline, RequestTimeout, REQUEST_TIMEOUT_HEADER},
     IntoView,
 };
+pub use leptos_integration_utils::{
+    on_response_metrics, IslandPayload, ResourcePayload, ResponseMetrics,
+};
 use leptos_integration_utils::{
     BoxedFnOnce, ExtendResponse, PinnedFuture, PinnedStream,
 };
 use leptos_meta::ServerMetaContext;
+#[cfg(feature = "minify_html")]
+use leptos_router::HtmlMinifier;
+#[cfg(feature = "static_dir")]
+use leptos_router::StaticAssetRequest;
 use leptos_router::{
+    accept_ch_header, buffer_body,
     components::provide_server_redirect,
     location::RequestUrl,
+    params::ParamsMap,
+    preload_link_header, segments_to_pattern,
     static_routes::{RegenerationFn, ResolvedStaticPath},
-    ExpandOptionals, Method, PathSegment, RouteList, RouteListing, SsrMode,
+    x_robots_tag_header, ApiKey, ApiKeyConfig, Batch, BatchRequest,
+    BatchResponse, BodySchemas, BufferedBody, ClientCert, ClientCertConfig,
+    ClientHint, ClientHints, ContentTypes, Deduplicated, DeduplicatedResponse,
+    DeduplicationConfig, EnvelopeConfig, ErrorFormat, ExcludeFromAccessLog,
+    ExpandOptionals, GuardResult, HeadInjection, HtmlMinifyConfig,
+    LanguageNegotiation, LanguageRedirect, MatchRequest, Method,
+    NegotiatedLanguage, NotFoundResponse, Passthrough, PathSegment,
+    PreloadLink, ProxyError, ProxyRequest, QuerySchema, RequestDetails,
+    ResponseSigningConfig, RouteConfig, RouteError, RouteGuard, RouteList,
+    RouteListing, RouteMiddleware, ShutdownSignal, SlowRequestConfig, SsrMode,
+    StickySessionPolicy, SurrogateKeys, ValidatedBody, ValidatedQuery,
+    WebSocketHandler, WebSocketMessage, WebhookBody, WebhookSignatureConfig,
+    XRobotsDirective, BUFFERED_BODY_LIMIT, REQUEST_ID_HEADER,
 };
 use parking_lot::RwLock;
 use send_wrapper::SendWrapper;
 use server_fn::{
-    error::ServerFnErrorErr, redirect::REDIRECT_HEADER,
-    request::actix::ActixRequest,
+    error::ServerFnErrorErr, middleware::BoxedService,
+    redirect::REDIRECT_HEADER, request::actix::ActixRequest,
+    response::actix::ActixResponse as ServerFnActixResponse,
 };
 use std::{
     collections::HashSet,
@@ -50,7 +74,9 @@ use std::{
     future::Future,
     ops::{Deref, DerefMut},
     path::Path,
+    pin::Pin,
     sync::{Arc, LazyLock},
+    time::{Duration, Instant, SystemTime},
 };
 
 /// This struct lets you define headers and override the status of the Response from an Element or a Server Function
@@ -194,6 +220,14 @@ impl ExtendResponse for ActixResponse {
             );
         }
     }
+
+    fn insert_header(&mut self, name: &'static str, value: &str) {
+        if let Ok(value) = HeaderValue::from_str(value) {
+            self.0
+                .headers_mut()
+                .insert(HeaderName::from_static(name), value);
+        }
+    }
 }
 
 /// Provides an easy way to redirect the user from within a server function.
@@ -339,90 +373,96 @@ pub fn handle_server_fns_with_context(
 ) -> Route {
     web::to(move |req: HttpRequest, payload: Payload| {
         let additional_context = additional_context.clone();
-        async move {
-            let additional_context = additional_context.clone();
-
-            let path = req.path();
-            let method = req.method();
-            if let Some(mut service) =
-                server_fn::actix::get_server_fn_service(path, method)
-            {
-                let owner = Owner::new();
-                owner
-                    .with(|| {
-                        ScopedFuture::new(async move {
-                            provide_context(Request::new(&req));
-                            let res_options = ResponseOptions::default();
-                            provide_context(res_options.clone());
-                            additional_context();
-
-                            // store Accepts and Referer in case we need them for redirect (below)
-                            let accepts_html = req
-                                .headers()
-                                .get(ACCEPT)
-                                .and_then(|v| v.to_str().ok())
-                                .map(|v| v.contains("text/html"))
-                                .unwrap_or(false);
-                            let referrer = req.headers().get(REFERER).cloned();
-
-                            // actually run the server fn
-                            let mut res = ActixResponse(
-                                service
-                                    .run(ActixRequest::from((req, payload)))
-                                    .await
-                                    .take(),
-                            );
+        handle_server_fn(req, payload, additional_context)
+    })
+}
 
-                            // if it accepts text/html (i.e., is a plain form post) and doesn't already have a
-                            // Location set, then redirect to the Referer
-                            if accepts_html {
-                                if let Some(referrer) = referrer {
-                                    let has_location =
-                                        res.0.headers().get(LOCATION).is_some();
-                                    if !has_location {
-                                        *res.0.status_mut() = StatusCode::FOUND;
-                                        res.0
-                                            .headers_mut()
-                                            .insert(LOCATION, referrer);
-                                    }
-                                }
-                            }
+/// Runs the server function at the path of `req`, with the context that
+/// [`handle_server_fns_with_context`] provides.
+async fn handle_server_fn(
+    req: HttpRequest,
+    payload: Payload,
+    additional_context: impl Fn() + 'static + Clone + Send,
+) -> HttpResponse {
+    let path = req.path();
+    let method = req.method();
+    if let Some(mut service) =
+        server_fn::actix::get_server_fn_service(path, method)
+    {
+        let owner = Owner::new();
+        owner
+            .with(|| {
+                ScopedFuture::new(async move {
+                    provide_context(Request::new(&req));
+                    let res_options = ResponseOptions::default();
+                    provide_context(res_options.clone());
+                    additional_context();
 
-                            // the Location header may have been set to Referer, so any redirection by the
-                            // user must overwrite it
-                            {
-                                let mut res_options = res_options.0.write();
-                                let headers = res.0.headers_mut();
+                    // store Accepts and Referer in case we need them for
+                    // redirect (below)
+                    let accepts_html = req
+                        .headers()
+                        .get(ACCEPT)
+                        .and_then(|v| v.to_str().ok())
+                        .map(|v| v.contains("text/html"))
+                        .unwrap_or(false);
+                    let referrer = req.headers().get(REFERER).cloned();
+
+                    // actually run the server fn
+                    let mut res = ActixResponse(
+                        service
+                            .run(ActixRequest::from((req, payload)))
+                            .await
+                            .take(),
+                    );
 
-                                for location in
-                                    res_options.headers.remove(header::LOCATION)
-                                {
-                                    headers.insert(header::LOCATION, location);
-                                }
+                    // if it accepts text/html (i.e., is a plain form post) and
+                    // doesn't already have a Location set, then redirect to the
+                    // Referer
+                    if accepts_html {
+                        if let Some(referrer) = referrer {
+                            let has_location =
+                                res.0.headers().get(LOCATION).is_some();
+                            if !has_location {
+                                *res.0.status_mut() = StatusCode::FOUND;
+                                res.0.headers_mut().insert(LOCATION, referrer);
                             }
+                        }
+                    }
 
-                            // apply status code and headers if user changed them
-                            res.extend_response(&res_options);
-                            res.0
-                        })
-                    })
-                    .await
-            } else {
-                HttpResponse::BadRequest().body(format!(
-                    "Could not find a server function at the route {:?}. \
-                     \n\nIt's likely that either
-                         1. The API prefix you specify in the `#[server]` \
-                     macro doesn't match the prefix at which your server \
-                     function handler is mounted, or \n2. You are on a \
-                     platform that doesn't support automatic server function \
-                     registration and you need to call \
-                     ServerFn::register_explicit() on the server function \
-                     type, somewhere in your `main` function.",
-                    req.path()
-                ))
-            }
-        }
-    })
+                    // the Location header may have been set to Referer, so any
+                    // redirection by the user must overwrite it
+                    {
+                        let mut res_options = res_options.0.write();
+                        let headers = res.0.headers_mut();
+
+                        for location in
+                            res_options.headers.remove(header::LOCATION)
+                        {
+                            headers.insert(header::LOCATION, location);
+                        }
+                    }
+
+                    // apply status code and headers if user changed them
+                    res.extend_response(&res_options);
+                    res.0
+                })
+            })
+            .await
+    } else {
+        HttpResponse::BadRequest().body(format!(
+            "Could not find a server function at the route {:?}. \
+             \n\nIt's likely that either
+                 1. The API prefix you specify in the `#[server]` \
+             macro doesn't match the prefix at which your server \
+             function handler is mounted, or \n2. You are on a \
+             platform that doesn't support automatic server function \
+             registration and you need to call \
+             ServerFn::register_explicit() on the server function \
+             type, somewhere in your `main` function.",
+            req.path()
+        ))
+    }
 }
 
 /// Returns an Actix [struct@Route](actix_web::Route) that listens for a `GET` request and tries
@@ -768,6 +808,23 @@ where
     feature = "tracing",
     tracing::instrument(level = "trace", fields(error), skip_all)
 )]
+/// The method and headers of the request, which the router matches its routes
+/// against.
+fn request_details(req: &HttpRequest) -> RequestDetails {
+    // a `HEAD` request is answered by the handler of the route's `GET` requests
+    let method = if req.method() == actix_web::http::Method::HEAD {
+        Method::Get
+    } else {
+        Method::from_name(req.method().as_str().to_owned())
+    };
+    req.headers()
+        .iter()
+        .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)))
+        .fold(RequestDetails::new(method), |details, (name, value)| {
+            details.with_header(name, value)
+        })
+}
+
 fn provide_contexts(
     req: Request,
     meta_context: &ServerMetaContext,
@@ -776,8 +833,41 @@ fn provide_contexts(
     let path = leptos_corrected_path(&req);
 
     provide_context(RequestUrl::new(&path));
+    provide_context(request_details(&req));
     provide_context(meta_context.clone());
     provide_context(res_options.clone());
+    if let Some(body) = req.extensions().get::<WebhookBody>() {
+        provide_context(body.clone());
+    }
+    if let Some(key) = req.extensions().get::<ApiKey>() {
+        provide_context(key.clone());
+    }
+    if let Some(language) = req.extensions().get::<NegotiatedLanguage>() {
+        provide_context(*language);
+    }
+    if let Some(cert) = req.extensions().get::<ClientCert>() {
+        provide_context(cert.clone());
+    }
+    if let Some(hints) = req.extensions().get::<ClientHints>() {
+        provide_context(hints.clone());
+    }
+    if let Some(query) = req.extensions().get::<ValidatedQuery>() {
+        provide_context(query.clone());
+    }
+    if let Some(body) = req.extensions().get::<ValidatedBody>() {
+        provide_context(body.clone());
+    }
+    if let Some(errors) = req.extensions().get::<RouteErrors>() {
+        errors.watch();
+    }
+    if let Some(deadline) = RequestDeadline::for_request(
+        req.headers()
+            .get(REQUEST_TIMEOUT_HEADER)
+            .and_then(|value| value.to_str().ok()),
+        req.app_data::<RequestTimeout>().copied(),
+    ) {
+        provide_context(deadline);
+    }
     provide_context(req);
     provide_server_redirect(redirect);
     leptos::nonce::provide_nonce();
@@ -846,11 +936,18 @@ where
         }
     };
     match method {
-        Method::Get => web::get().to(handler),
+        Method::Get => get_or_head().to(handler),
         Method::Post => web::post().to(handler),
         Method::Put => web::put().to(handler),
         Method::Delete => web::delete().to(handler),
         Method::Patch => web::patch().to(handler),
+        // actix-web compares the raw method of the request with the custom
+        // method's name
+        Method::Custom(name) => web::method(
+            actix_web::http::Method::from_bytes(name.as_bytes())
+                .expect("the methods of a listing are valid method names"),
+        )
+        .to(handler),
     }
 }
 
@@ -954,9 +1051,14 @@ impl ActixPath for Vec<PathSegment> {
 /// A route that this application can serve.
 pub struct ActixRouteListing {
     path: String,
+    /// The pattern of the path, as it is written in the route definitions, like
+    /// `/blog/:slug`.
+    pattern: String,
     mode: SsrMode,
     methods: Vec<leptos_router::Method>,
     regenerate: Vec<RegenerationFn>,
+    config: RouteConfig,
+    language_redirect: Option<LanguageRedirect>,
     exclude: bool,
 }
 
@@ -971,6 +1073,7 @@ impl IntoRouteListing for RouteListing {
             .expand_optionals()
             .into_iter()
             .map(|path| {
+                let pattern = segments_to_pattern(&path);
                 let path = path.to_actix_path();
                 let path = if path.is_empty() {
                     "/".to_string()
@@ -978,13 +1081,34 @@ impl IntoRouteListing for RouteListing {
                     path
                 };
                 let mode = self.mode();
-                let methods = self.methods().collect();
+                let methods = self
+                    .methods()
+                    .filter(|method| {
+                        let valid = method.is_valid();
+                        if !valid {
+                            let msg = format!(
+                                "{method:?} is not a valid HTTP method name, \
+                                 so the route for {path} does not handle it."
+                            );
+
+                            #[cfg(feature = "tracing")]
+                            tracing::warn!("{}", &msg);
+
+                            #[cfg(not(feature = "tracing"))]
+                            eprintln!("{}", &msg);
+                        }
+                        valid
+                    })
+                    .collect();
                 let regenerate = self.regenerate().into();
                 ActixRouteListing {
                     path,
+                    pattern,
                     mode: mode.clone(),
                     methods,
                     regenerate,
+                    config: self.config().clone(),
+                    language_redirect: self.language_redirect().cloned(),
                     exclude: false,
                 }
             })
@@ -1001,14 +1125,33 @@ impl ActixRouteListing {
         regenerate: impl Into<Vec<RegenerationFn>>,
     ) -> Self {
         Self {
+            pattern: path.clone(),
             path,
             mode,
             methods: methods.into_iter().collect(),
             regenerate: regenerate.into(),
+            config: RouteConfig::default(),
+            language_redirect: None,
             exclude: false,
         }
     }
 
+    /// Sets the options the server integrations apply to the requests to this
+    /// route.
+    pub fn with_config(mut self, config: RouteConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets how requests to this route are redirected to a path with a locale.
+    pub fn with_language_redirect(
+        mut self,
+        redirect: Option<LanguageRedirect>,
+    ) -> Self {
+        self.language_redirect = redirect;
+        self
+    }
+
     /// The path this route handles.
     pub fn path(&self) -> &str {
         &self.path
@@ -1021,7 +1164,19 @@ impl ActixRouteListing {
 
     /// The HTTP request methods this path can handle.
     pub fn methods(&self) -> impl Iterator<Item = leptos_router::Method> + '_ {
-        self.methods.iter().copied()
+        self.methods.iter().cloned()
+    }
+
+    /// The options the server integrations apply to the requests to this
+    /// route.
+    pub fn config(&self) -> &RouteConfig {
+        &self.config
+    }
+
+    /// How requests to this route are redirected to a path with a locale, if
+    /// they are.
+    pub fn language_redirect(&self) -> Option<&LanguageRedirect> {
+        self.language_redirect.as_ref()
     }
 }
 
@@ -1087,9 +1242,12 @@ where
             .flatten()
             .map(|path| ActixRouteListing {
                 path,
+                pattern: String::new(),
                 mode: Default::default(),
                 methods: Vec::new(),
                 regenerate: Vec::new(),
+                config: RouteConfig::default(),
+                language_redirect: None,
                 exclude: true,
             });
 
@@ -1259,6 +1417,8 @@ fn handle_static_route<IV>(
     additional_context: impl Fn() + 'static + Clone + Send,
     app_fn: impl Fn() -> IV + Clone + Send + 'static,
     regenerate: Vec<RegenerationFn>,
+    incremental: bool,
+    head: HeadInjection,
 ) -> Route
 where
     IV: IntoView + 'static,
@@ -1268,6 +1428,7 @@ where
             let app_fn = app_fn.clone();
             let additional_context = additional_context.clone();
             let regenerate = regenerate.clone();
+            let head = head.clone();
             async move {
                 let options = data.into_inner();
                 let orig_path = req.uri().path();
@@ -1275,17 +1436,35 @@ where
                 let path = Path::new(&path);
                 let exists = tokio::fs::try_exists(path).await.unwrap_or(false);
 
-                let (response_options, html) = if !exists {
+                let (response_options, html) = if !exists && !incremental {
+                    // this path was not prerendered, and should not be written,
+                    // so just render it
+                    let (owner, html) = StaticRouteGenerator::render_route(
+                        orig_path.to_string(),
+                        app_fn,
+                        additional_context,
+                    )
+                    .await;
+                    (
+                        owner.with(use_context::<ResponseOptions>),
+                        Some(head.inject(html)),
+                    )
+                } else if !exists {
                     let path = ResolvedStaticPath::new(orig_path);
 
                     let (owner, html) = path
                         .build(
                             move |path: &ResolvedStaticPath| {
-                                StaticRouteGenerator::render_route(
+                                let render = StaticRouteGenerator::render_route(
                                     path.to_string(),
                                     app_fn.clone(),
                                     additional_context.clone(),
-                                )
+                                );
+                                let head = head.clone();
+                                async move {
+                                    let (owner, html) = render.await;
+                                    (owner, head.inject(html))
+                                }
                             },
                             move |path: &ResolvedStaticPath,
                                   owner: &Owner,
@@ -1338,11 +1517,130 @@ where
             }
         })
     };
-    web::get().to(handler)
+    get_or_head().to(handler)
+}
+
+/// A route for `GET` requests that also handles `HEAD` requests, for which
+/// actix-web removes the body of the response.
+fn get_or_head() -> Route {
+    web::route().guard(guard::Any(guard::Get()).or(guard::Head()))
+}
+
+/// A route that responds to `OPTIONS` requests with `204 No Content` and an
+/// `Allow` header listing `methods`, or `None` if `OPTIONS` is one of them.
+///
+/// This is not a CORS preflight response: it does not include any
+/// `Access-Control-*` headers. To allow cross-origin requests, wrap the app in
+/// a CORS middleware (like `actix_cors::Cors`), which answers preflight
+/// requests before they reach this route.
+fn options_route(methods: impl IntoIterator<Item = Method>) -> Option<Route> {
+    let methods = methods.into_iter().collect::<Vec<_>>();
+    if methods.contains(&Method::from_name("OPTIONS")) {
+        return None;
+    }
+    let allow = leptos_router::allow_header(&methods);
+    Some(web::route().guard(guard::Options()).to(move || {
+        let allow = allow.clone();
+        async move {
+            HttpResponse::NoContent()
+                .insert_header((header::ALLOW, allow))
+                .finish()
+        }
+    }))
+}
+
+/// What the [`Route`] of a route does with its requests, which decides which of
+/// the layers of [`apply_route_layers`] it gets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RouteHandler {
+    /// Renders the app.
+    Rendered,
+    /// Serves the prerendered page of a static route.
+    Static,
+    /// Serves a file of a static directory.
+    File,
+    /// Hands requests to a WebSocket, passthrough, batch or `not_found`
+    /// handler.
+    Proxied,
+}
+
+/// Wraps `route` in the layers for everything `listing` sets up for its route,
+/// which `handler` says how `route` handles.
+///
+/// Each layer wraps the ones before it, so the last one runs first.
+fn apply_route_layers(
+    mut route: Route,
+    listing: &ActixRouteListing,
+    handler: RouteHandler,
+) -> Route {
+    // The responses of proxied routes do not come from the app and may be
+    // upgraded WebSocket connections or streamed from upstream, so they are
+    // passed on as they are, rather than buffered to be shared, rewritten or
+    // checked, or given the status of the route.
+    if handler != RouteHandler::Proxied {
+        route = with_route_status(listing.config().status, route);
+        route = wrap_response_envelope(
+            listing.config().response_envelope.as_ref(),
+            route,
+        );
+        route =
+            format_route_errors(listing.config().error_format.as_ref(), route);
+        route = validate_response_body(
+            listing.config().response_schemas.get(&200),
+            route,
+        );
+        route = minify_html_responses(listing.config().minify_html, route);
+        // static routes inject the head content into their pages before they
+        // are written to disk, so it would be injected twice
+        if handler == RouteHandler::Rendered {
+            route = inject_head_content(&listing.config().head, route);
+        }
+        route = deduplicate_requests(
+            listing.config().deduplication.as_ref(),
+            route,
+        );
+    }
+    route = verify_webhook_signature(
+        listing.config().webhook_signature.as_ref(),
+        route,
+    );
+    route = check_guards(&listing.config().guards, route);
+    route = sign_responses(listing.config().response_signing.as_ref(), route);
+    route = verify_body(listing.config().body_schemas.as_ref(), route);
+    route = verify_content_type(listing.config().consumes.as_ref(), route);
+    route = verify_query(listing.config().query_schema.as_ref(), route);
+    route = verify_api_key(listing.config().api_key.as_ref(), route);
+    route = negotiate_language(listing.config().language_negotiation, route);
+    route = verify_client_cert(listing.config().client_cert.as_ref(), route);
+    route = mark_access_log_exclusion(
+        listing.config().exclude_from_access_log.unwrap_or(false),
+        route,
+    );
+    route = set_sticky_session(listing.config().sticky_session, route);
+    route = set_x_robots_tag(&listing.config().x_robots_tag, route);
+    route = set_surrogate_keys(listing.config().surrogate_keys.as_ref(), route);
+    route = set_preload_links(&listing.config().preload, route);
+    route = request_client_hints(&listing.config().client_hints, route);
+    route = redirect_to_language(listing.language_redirect(), route);
+    route = apply_route_middleware(listing.config().middleware.as_ref(), route);
+    route = warn_on_slow_requests(
+        listing.config().slow_request,
+        &listing.pattern,
+        route,
+    );
+    reject_after_shutdown(listing.config().shutdown.as_ref(), route)
 }
 
 /// This trait allows one to pass a list of routes and a render function to Actix's router, letting us avoid
 /// having to use wildcards or manually define all routes in multiple places.
+///
+/// Every route that handles `GET` also handles `HEAD`, with the response body
+/// removed. Every route that does not handle `OPTIONS` itself responds to
+/// `OPTIONS` with `204 No Content` and an `Allow` header listing its methods.
+/// That response has no `Access-Control-*` headers, so it does not allow CORS
+/// preflight requests; to do that, add a CORS middleware like
+/// [`actix_cors::Cors`](https://docs.rs/actix-cors), which answers preflight
+/// requests before they reach these routes.
 pub trait LeptosRoutes {
     /// Adds routes to the Axum router that have either
     /// 1) been generated by `leptos_router`, or
@@ -1433,61 +1731,122 @@ where
             let path = listing.path();
             let mode = listing.mode();
 
+            let proxied = listing
+                .config()
+                .websocket
+                .as_ref()
+                .map(|handler| websocket_route(handler.clone()))
+                .or_else(|| {
+                    listing
+                        .config()
+                        .passthrough
+                        .as_ref()
+                        .cloned()
+                        .map(passthrough_route)
+                })
+                .or_else(|| {
+                    listing.config().batch.as_ref().map(|batch| {
+                        batch_route(batch.clone(), additional_context.clone())
+                    })
+                })
+                .or_else(|| {
+                    listing
+                        .config()
+                        .not_found
+                        .as_ref()
+                        .cloned()
+                        .map(not_found_route)
+                });
+            if let Some(proxied) = proxied {
+                router = router.route(
+                    path,
+                    apply_route_layers(proxied, listing, RouteHandler::Proxied),
+                );
+                continue;
+            }
+
+            if let Some(route) = options_route(listing.methods()) {
+                router = router.route(path, route);
+            }
+
+            if let Some(file) = static_file_route(listing) {
+                router = router.route(
+                    path,
+                    apply_route_layers(file, listing, RouteHandler::File),
+                );
+                continue;
+            }
+
             for method in listing.methods() {
                 let additional_context = additional_context.clone();
-                let additional_context_and_method = move || {
-                    provide_context(method);
-                    additional_context();
+                let additional_context_and_method = {
+                    let method = method.clone();
+                    move || {
+                        provide_context(method.clone());
+                        additional_context();
+                    }
                 };
-                router = if matches!(listing.mode(), SsrMode::Static(_)) {
+                router = if let SsrMode::Static(route) = listing.mode() {
                     router.route(
                         path,
-                        handle_static_route(
+                        apply_route_layers(
+                            handle_static_route(
+                                additional_context_and_method.clone(),
+                                app_fn.clone(),
+                                listing.regenerate.clone(),
+                                route.is_incremental(),
+                                listing.config().head.clone(),
+                            ),
+                            listing,
+                            RouteHandler::Static,
+                        ),
+                    )
+                } else {
+                    let rendered = match mode {
+                        SsrMode::OutOfOrder => {
+                            render_app_to_stream_with_context(
+                                additional_context_and_method.clone(),
+                                app_fn.clone(),
+                                method,
+                            )
+                        }
+                        SsrMode::PartiallyBlocked => {
+                            render_app_to_stream_with_context_and_replace_blocks(
+                                additional_context_and_method.clone(),
+                                app_fn.clone(),
+                                method,
+                                true,
+                            )
+                        }
+                        SsrMode::InOrder => {
+                            render_app_to_stream_in_order_with_context(
+                                additional_context_and_method.clone(),
+                                app_fn.clone(),
+                                method,
+                            )
+                        }
+                        SsrMode::Async => render_app_async_with_context(
                             additional_context_and_method.clone(),
                             app_fn.clone(),
-                            listing.regenerate.clone(),
+                            method,
+                        ),
+                        _ => unreachable!(),
+                    };
+                    router.route(
+                        path,
+                        apply_route_layers(
+                            rendered,
+                            listing,
+                            RouteHandler::Rendered,
                         ),
                     )
-                } else {
-                    router
-                        .route(path, web::head().to(HttpResponse::Ok))
-                        .route(
-                            path,
-                            match mode {
-                                SsrMode::OutOfOrder => {
-                                    render_app_to_stream_with_context(
-                                        additional_context_and_method.clone(),
-                                        app_fn.clone(),
-                                        method,
-                                    )
-                                }
-                                SsrMode::PartiallyBlocked => {
-                                    render_app_to_stream_with_context_and_replace_blocks(
-                                        additional_context_and_method.clone(),
-                                        app_fn.clone(),
-                                        method,
-                                        true,
-                                    )
-                                }
-                                SsrMode::InOrder => {
-                                    render_app_to_stream_in_order_with_context(
-                                        additional_context_and_method.clone(),
-                                        app_fn.clone(),
-                                        method,
-                                    )
-                                }
-                                SsrMode::Async => render_app_async_with_context(
-                                    additional_context_and_method.clone(),
-                                    app_fn.clone(),
-                                    method,
-                                ),
-                                _ => unreachable!()
-                            },
-                        )
                 };
             }
         }
 
+        if let Some(route) = jwks_route(&paths) {
+            router = router.route(leptos_router::JWKS_PATH, route);
+        }
         router
     }
 }
@@ -1546,58 +1905,1666 @@ impl LeptosRoutes for &mut ServiceConfig {
             let path = listing.path();
             let mode = listing.mode();
 
+            let proxied = listing
+                .config()
+                .websocket
+                .as_ref()
+                .map(|handler| websocket_route(handler.clone()))
+                .or_else(|| {
+                    listing
+                        .config()
+                        .passthrough
+                        .as_ref()
+                        .cloned()
+                        .map(passthrough_route)
+                })
+                .or_else(|| {
+                    listing.config().batch.as_ref().map(|batch| {
+                        batch_route(batch.clone(), additional_context.clone())
+                    })
+                })
+                .or_else(|| {
+                    listing
+                        .config()
+                        .not_found
+                        .as_ref()
+                        .cloned()
+                        .map(not_found_route)
+                });
+            if let Some(proxied) = proxied {
+                router = router.route(
+                    path,
+                    apply_route_layers(proxied, listing, RouteHandler::Proxied),
+                );
+                continue;
+            }
+
+            if let Some(route) = options_route(listing.methods()) {
+                router = router.route(path, route);
+            }
+
+            if let Some(file) = static_file_route(listing) {
+                router = router.route(
+                    path,
+                    apply_route_layers(file, listing, RouteHandler::File),
+                );
+                continue;
+            }
+
             for method in listing.methods() {
-                if matches!(listing.mode(), SsrMode::Static(_)) {
+                if let SsrMode::Static(route) = listing.mode() {
                     router = router.route(
                         path,
-                        handle_static_route(
-                            additional_context.clone(),
-                            app_fn.clone(),
-                            listing.regenerate.clone(),
+                        apply_route_layers(
+                            handle_static_route(
+                                additional_context.clone(),
+                                app_fn.clone(),
+                                listing.regenerate.clone(),
+                                route.is_incremental(),
+                                listing.config().head.clone(),
+                            ),
+                            listing,
+                            RouteHandler::Static,
                         ),
                     )
                 } else {
+                    let rendered = match mode {
+                        SsrMode::OutOfOrder => {
+                            render_app_to_stream_with_context(
+                                additional_context.clone(),
+                                app_fn.clone(),
+                                method,
+                            )
+                        }
+                        SsrMode::PartiallyBlocked => {
+                            render_app_to_stream_with_context_and_replace_blocks(
+                                additional_context.clone(),
+                                app_fn.clone(),
+                                method,
+                                true,
+                            )
+                        }
+                        SsrMode::InOrder => {
+                            render_app_to_stream_in_order_with_context(
+                                additional_context.clone(),
+                                app_fn.clone(),
+                                method,
+                            )
+                        }
+                        SsrMode::Async => render_app_async_with_context(
+                            additional_context.clone(),
+                            app_fn.clone(),
+                            method,
+                        ),
+                        _ => unreachable!(),
+                    };
                     router = router.route(
-                            path,
-                            match mode {
-                                SsrMode::OutOfOrder => {
-                                    render_app_to_stream_with_context(
-                                        additional_context.clone(),
-                                        app_fn.clone(),
-                                        method,
-                                    )
+                        path,
+                        apply_route_layers(
+                            rendered,
+                            listing,
+                            RouteHandler::Rendered,
+                        ),
+                    );
+                }
+            }
+        }
+
+        if let Some(route) = jwks_route(&paths) {
+            router = router.route(leptos_router::JWKS_PATH, route);
+        }
+        router
+    }
+}
+
+/// Rejects requests to `route` that are not signed according to `config`, if
+/// there is one.
+///
+/// The body has to be read in full to validate it, so it is stored as a
+/// [`WebhookBody`] in the request extensions (and provided via context by the
+/// Leptos handlers) for the handler to use.
+fn verify_webhook_signature(
+    config: Option<&WebhookSignatureConfig>,
+    route: Route,
+) -> Route {
+    let Some(config) = config.cloned() else {
+        return route;
+    };
+    route.wrap(middleware::from_fn(
+        move |mut req: ServiceRequest,
+              next: middleware::Next<body::BoxBody>| {
+            let config = config.clone();
+            async move {
+                let body = req.extract::<web::Bytes>().await?;
+                let valid = req
+                    .headers()
+                    .get(config.header)
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|signature| config.verify(&body, signature));
+                if !valid {
+                    return Err(error::ErrorUnauthorized(
+                        "invalid webhook signature",
+                    ));
+                }
+
+                req.extensions_mut().insert(WebhookBody::new(body.to_vec()));
+                req.set_payload(dev::Payload::from(body));
+                next.call(req).await
+            }
+        },
+    ))
+}
+
+/// A route that upgrades `GET` requests to WebSocket connections, which are
+/// handled by `handler`.
+fn websocket_route(handler: WebSocketHandler) -> Route {
+    web::get().to(move |req: HttpRequest, payload: Payload| {
+        let handler = handler.clone();
+        async move {
+            let (response, mut session, mut messages) =
+                actix_ws::handle(&req, payload)?;
+            rt::spawn(async move {
+                use actix_ws::Message;
+                use futures::{channel::mpsc, FutureExt};
+
+                let (incoming_tx, incoming_rx) = mpsc::unbounded();
+                let mut outgoing = handler.handle(incoming_rx);
+                loop {
+                    futures::select! {
+                        message = messages.next().fuse() => {
+                            let message = match message {
+                                Some(Ok(Message::Text(text))) => {
+                                    WebSocketMessage::Text(text.to_string())
                                 }
-                                SsrMode::PartiallyBlocked => {
-                                    render_app_to_stream_with_context_and_replace_blocks(
-                                        additional_context.clone(),
-                                        app_fn.clone(),
-                                        method,
-                                        true,
-                                    )
+                                Some(Ok(Message::Binary(bytes))) => {
+                                    WebSocketMessage::Binary(bytes.to_vec())
                                 }
-                                SsrMode::InOrder => {
-                                    render_app_to_stream_in_order_with_context(
-                                        additional_context.clone(),
-                                        app_fn.clone(),
-                                        method,
-                                    )
+                                Some(Ok(Message::Ping(bytes))) => {
+                                    if session.pong(&bytes).await.is_err() {
+                                        break;
+                                    }
+                                    continue;
                                 }
-                                SsrMode::Async => render_app_async_with_context(
-                                    additional_context.clone(),
-                                    app_fn.clone(),
-                                    method,
-                                ),
-                                _ => unreachable!()
-                            },
-                        );
+                                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                                Some(Ok(_)) => continue,
+                            };
+                            if incoming_tx.unbounded_send(message).is_err() {
+                                break;
+                            }
+                        },
+                        message = outgoing.next().fuse() => {
+                            let sent = match message {
+                                Some(WebSocketMessage::Text(text)) => {
+                                    session.text(text).await
+                                }
+                                Some(WebSocketMessage::Binary(bytes)) => {
+                                    session.binary(bytes).await
+                                }
+                                None => break,
+                            };
+                            if sent.is_err() {
+                                break;
+                            }
+                        },
+                    }
                 }
-            }
+                _ = session.close(None).await;
+            });
+            Ok::<_, Error>(response)
         }
+    })
+}
 
-        router
+/// A route that responds to requests of every method with `404 Not Found` and
+/// the body of `response`.
+fn not_found_route(response: NotFoundResponse) -> Route {
+    web::route().to(move || {
+        let response = response.clone();
+        async move {
+            HttpResponse::NotFound()
+                .content_type(response.content_type())
+                .body(response.body().to_string())
+        }
+    })
+}
+
+/// A route that serves the file of `listing`, if it is one of the routes
+/// generated for the files of a
+/// [static directory](leptos_router::NestedRoute::static_dir), with support for
+/// conditional and range requests.
+fn static_file_route(listing: &ActixRouteListing) -> Option<Route> {
+    #[cfg(feature = "static_dir")]
+    {
+        let file = listing.config().static_file.clone()?;
+        Some(get_or_head().to(move |req: HttpRequest| {
+            let header_value = |name| {
+                req.headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string)
+            };
+            let res = file.respond(&StaticAssetRequest {
+                range: header_value(header::RANGE),
+                if_none_match: header_value(header::IF_NONE_MATCH),
+            });
+            async move {
+                let status = StatusCode::from_u16(res.status)
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                let mut builder = HttpResponse::build(status);
+                for (name, value) in res.headers {
+                    builder.insert_header((name, value));
+                }
+                builder.body(res.body)
+            }
+        }))
+    }
+    #[cfg(not(feature = "static_dir"))]
+    {
+        _ = listing;
+        None
     }
 }
 
+/// A route that runs the requests in the body of each `POST` as a batch, by
+/// calling the server function at the path of each of them.
+///
+/// Each request has the headers and peer address of the batch, and a JSON body,
+/// so the server functions it calls should take their arguments as JSON, or in
+/// the query of a `GET`. The `app_data` of the app is not visible to them.
+fn batch_route(
+    batch: Batch,
+    additional_context: impl Fn() + 'static + Clone + Send,
+) -> Route {
+    web::post().to(move |req: HttpRequest, body: web::Bytes| {
+        let batch = batch.clone();
+        let additional_context = additional_context.clone();
+        async move {
+            let executor = |request: BatchRequest| {
+                let mut sub = test::TestRequest::default()
+                    .method(
+                        actix_web::http::Method::from_bytes(
+                            request.method.as_str().as_bytes(),
+                        )
+                        .unwrap_or(actix_web::http::Method::GET),
+                    )
+                    .uri(&request.path);
+                for (name, value) in req.headers() {
+                    if name != header::CONTENT_TYPE
+                        && name != header::CONTENT_LENGTH
+                    {
+                        sub = sub.append_header((name.clone(), value.clone()));
+                    }
+                }
+                if let Some(addr) = req.peer_addr() {
+                    sub = sub.peer_addr(addr);
+                }
+                let (sub, mut payload) = sub
+                    .insert_header((header::CONTENT_TYPE, "application/json"))
+                    .set_payload(
+                        request
+                            .body
+                            .map(|body| body.to_string())
+                            .unwrap_or_default(),
+                    )
+                    .to_http_parts();
+                let additional_context = additional_context.clone();
+                async move {
+                    use actix_web::FromRequest;
+
+                    let res = match Payload::from_request(&sub, &mut payload)
+                        .await
+                    {
+                        Ok(payload) => {
+                            handle_server_fn(sub, payload, additional_context)
+                                .await
+                        }
+                        Err(error) => error.error_response(),
+                    };
+                    let status = res.status().as_u16();
+                    let body = actix_web::body::to_bytes(res.into_body())
+                        .await
+                        .unwrap_or_default();
+                    BatchResponse::from_body(status, &body)
+                }
+            };
+            match batch.execute(&body, &executor).await {
+                Ok(responses) => HttpResponse::Ok()
+                    .content_type("application/json")
+                    .body(responses.to_string()),
+                Err(error) => HttpResponse::build(
+                    StatusCode::from_u16(error.status_code())
+                        .unwrap_or(StatusCode::BAD_REQUEST),
+                )
+                .body(error.to_string()),
+            }
+        }
+    })
+}
+
+/// A route that forwards requests of every method to the upstream of
+/// `passthrough`, streaming its response back to the client.
+fn passthrough_route(passthrough: Passthrough) -> Route {
+    web::route().to(move |req: HttpRequest, mut payload: Payload| {
+        let passthrough = passthrough.clone();
+        async move {
+            use futures::{channel::mpsc, SinkExt};
+
+            // the payload is tied to this thread, so it is sent on to the
+            // upstream through a channel, which also holds back the client
+            // while the upstream is reading slowly
+            let (mut body_tx, body_rx) = mpsc::channel(16);
+            rt::spawn(async move {
+                while let Some(chunk) = payload.next().await {
+                    let chunk =
+                        chunk.map(|bytes| bytes.to_vec()).map_err(|error| {
+                            ProxyError::Request(error.to_string())
+                        });
+                    if body_tx.send(chunk).await.is_err() {
+                        break;
+                    }
+                }
+            });
+
+            let headers = req
+                .headers()
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect();
+            let request = ProxyRequest {
+                method: req.method().to_string(),
+                path_and_query: req
+                    .uri()
+                    .path_and_query()
+                    .map_or("/", |path| path.as_str())
+                    .to_string(),
+                headers,
+                client_ip: req.peer_addr().map(|addr| addr.ip()),
+                body: body_rx.boxed(),
+            };
+            match passthrough.forward(request).await {
+                Ok(response) => {
+                    let status = StatusCode::from_u16(response.status)
+                        .unwrap_or(StatusCode::BAD_GATEWAY);
+                    let mut res = HttpResponse::build(status);
+                    for (name, value) in response.headers {
+                        res.append_header((name, value));
+                    }
+                    res.streaming(
+                        response.body.map(|chunk| chunk.map(web::Bytes::from)),
+                    )
+                }
+                Err(error) => HttpResponse::build(
+                    StatusCode::from_u16(error.status())
+                        .unwrap_or(StatusCode::BAD_GATEWAY),
+                )
+                .body(error.to_string()),
+            }
+        }
+    })
+}
+
+/// Measures a request to the route with the path `pattern`, and warns, as set
+/// by `config`, if it was slow when it is dropped, once its response body has
+/// been sent.
+struct SlowRequestTimer {
+    config: SlowRequestConfig,
+    pattern: Arc<str>,
+    path: String,
+    params: Vec<String>,
+    start: Instant,
+}
+
+impl Drop for SlowRequestTimer {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        if !self.config.should_warn(elapsed) {
+            return;
+        }
+        let bucket = SlowRequestConfig::histogram_bucket(elapsed);
+        let msg = format!(
+            "Slow request to route {}: {} [{}] took {elapsed:?}, more than \
+             {:?} (histogram bucket le=\"{bucket}\")",
+            self.pattern,
+            self.path,
+            self.params.join(", "),
+            self.config.threshold
+        );
+
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            route = %self.pattern,
+            path = %self.path,
+            params = ?self.params,
+            elapsed_ms = elapsed.as_millis() as u64,
+            threshold_ms = self.config.threshold.as_millis() as u64,
+            histogram_bucket = bucket,
+            "{}",
+            &msg
+        );
+
+        #[cfg(not(feature = "tracing"))]
+        eprintln!("{}", &msg);
+    }
+}
+
+/// Warns about the requests to `route`, the route with the path `pattern`, that
+/// are slower than the threshold of `config`, if there is one, until their
+/// response bodies have been sent.
+fn warn_on_slow_requests(
+    config: Option<SlowRequestConfig>,
+    pattern: &str,
+    route: Route,
+) -> Route {
+    let Some(config) = config else {
+        return route;
+    };
+    let pattern = Arc::<str>::from(pattern);
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let timer = SlowRequestTimer {
+                config,
+                pattern: Arc::clone(&pattern),
+                path: req.path().to_string(),
+                params: req
+                    .match_info()
+                    .iter()
+                    .map(|(key, value)| format!("{key}={value}"))
+                    .collect(),
+                start: Instant::now(),
+            };
+            async move {
+                let res = next.call(req).await?;
+                let (req, res) = res.into_parts();
+                let (res, body) = res.into_parts();
+                let mut body = Box::pin(body);
+                let body = futures::stream::poll_fn(move |cx| {
+                    let _ = &timer;
+                    body::MessageBody::poll_next(body.as_mut(), cx)
+                });
+                Ok(dev::ServiceResponse::new(
+                    req,
+                    res.set_body(body::BoxBody::new(body::BodyStream::new(
+                        body,
+                    ))),
+                ))
+            }
+        },
+    ))
+}
+
+/// Answers requests to `route` with `503 Service Unavailable` once `shutdown`
+/// is set, if there is one, as set with `NestedRoute::graceful_shutdown`, and
+/// otherwise counts them as in flight until their response bodies have been
+/// sent, for [`drain_routes`].
+fn reject_after_shutdown(
+    shutdown: Option<&ShutdownSignal>,
+    route: Route,
+) -> Route {
+    let Some(shutdown) = shutdown.cloned() else {
+        return route;
+    };
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let shutdown = shutdown.clone();
+            async move {
+                if shutdown.is_shutting_down() {
+                    return Ok(req.into_response(
+                        HttpResponse::ServiceUnavailable()
+                            .insert_header((header::CONNECTION, "close"))
+                            .finish(),
+                    ));
+                }
+                let in_flight = shutdown.track_request();
+                let res = next.call(req).await?;
+                let (req, res) = res.into_parts();
+                let (res, body) = res.into_parts();
+                let mut body = Box::pin(body);
+                let body = futures::stream::poll_fn(move |cx| {
+                    let _ = &in_flight;
+                    body::MessageBody::poll_next(body.as_mut(), cx)
+                });
+                Ok(dev::ServiceResponse::new(
+                    req,
+                    res.set_body(body::BoxBody::new(body::BodyStream::new(
+                        body,
+                    ))),
+                ))
+            }
+        },
+    ))
+}
+
+/// The rest of the middleware of a route, and its handler, as the innermost
+/// service of the layers of its `NestedRoute::middleware`.
+struct NextService(SendWrapper<Option<middleware::Next<body::BoxBody>>>);
+
+impl server_fn::middleware::Service<ActixRequest, ServerFnActixResponse>
+    for NextService
+{
+    fn run(
+        &mut self,
+        req: ActixRequest,
+        _ser: fn(ServerFnErrorErr) -> web::Bytes,
+    ) -> Pin<Box<dyn Future<Output = ServerFnActixResponse> + Send>> {
+        let next = (*self.0).take();
+        let (req, payload) = req.take();
+        Box::pin(SendWrapper::new(async move {
+            let res = match next {
+                Some(next) => match next
+                    .call(ServiceRequest::from_parts(req, payload.into_inner()))
+                    .await
+                {
+                    Ok(res) => res.into_parts().1,
+                    Err(error) => error.error_response(),
+                },
+                None => HttpResponse::InternalServerError()
+                    .body("the route middleware ran the route more than once"),
+            };
+            ServerFnActixResponse::from(res)
+        }))
+    }
+}
+
+/// Runs every request to `route` through the layers of `route_middleware` for
+/// Actix, if there are any, as set with `NestedRoute::middleware`.
+fn apply_route_middleware(
+    route_middleware: Option<&RouteMiddleware>,
+    route: Route,
+) -> Route {
+    let Some(route_middleware) = route_middleware
+        .filter(|route_middleware| {
+            route_middleware
+                .layers::<ActixRequest, ServerFnActixResponse>()
+                .count()
+                > 0
+        })
+        .cloned()
+    else {
+        return route;
+    };
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let mut service = route_middleware.apply(BoxedService::new(
+                |error: ServerFnErrorErr| web::Bytes::from(error.to_string()),
+                NextService(SendWrapper::new(Some(next))),
+            ));
+            async move {
+                let (req, mut payload) = req.into_parts();
+                let payload = Payload::from_request(&req, &mut payload).await?;
+                let res = service
+                    .run(ActixRequest::from((req.clone(), payload)))
+                    .await
+                    .take();
+                Ok(dev::ServiceResponse::new(req, res))
+            }
+        },
+    ))
+}
+
+/// Waits for the requests to the routes that use `shutdown` to complete, or for
+/// `timeout` to pass, whichever is first, once it has been set, so that the
+/// server can exit without cutting off any response in the middle.
+///
+/// A request is in flight from when one of the routes accepts it until its
+/// response body has been sent in full, so streamed responses are waited for to
+/// the end.
+pub fn drain_routes(
+    shutdown: &ShutdownSignal,
+    timeout: Duration,
+) -> BoxFuture<'static, ()> {
+    let shutdown = shutdown.clone();
+    Box::pin(async move {
+        _ = tokio::time::timeout(timeout, shutdown.drained()).await;
+    })
+}
+
+/// Redirects every request to `route` to the same path with a locale, as chosen
+/// by `redirect`, if there is one, which is the case for the path without a
+/// locale of a route set with `NestedRoute::language_redirect`.
+fn redirect_to_language(
+    redirect: Option<&LanguageRedirect>,
+    route: Route,
+) -> Route {
+    let Some(redirect) = redirect.cloned() else {
+        return route;
+    };
+    let status = StatusCode::from_u16(redirect.config.status())
+        .expect("invalid language redirect status");
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, _next: middleware::Next<body::BoxBody>| {
+            let header_value = |name| {
+                req.headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+            };
+            let query = req.query_string();
+            let (location, set_cookie) = redirect.redirect(
+                req.path(),
+                (!query.is_empty()).then_some(query),
+                header_value(header::COOKIE),
+                header_value(header::ACCEPT_LANGUAGE),
+            );
+            let mut res = HttpResponse::build(status);
+            res.insert_header((LOCATION, location))
+                .insert_header((header::VARY, "Accept-Language, Cookie"));
+            if let Some(cookie) = set_cookie {
+                res.insert_header((header::SET_COOKIE, cookie));
+            }
+            let res = req.into_response(res.finish());
+            async move { Ok(res) }
+        },
+    ))
+}
+
+/// Asks browsers for `hints` with the `Accept-CH` and `Critical-CH` headers of
+/// every response from `route`, if there are any, and stores the hints a
+/// request was sent with as [`ClientHints`] in its extensions (and in the
+/// context of the rendered route).
+fn request_client_hints(hints: &[ClientHint], route: Route) -> Route {
+    let Some(value) = accept_ch_header(hints) else {
+        return route;
+    };
+    let value = HeaderValue::from_str(&value)
+        .unwrap_or_else(|_| panic!("invalid Accept-CH header: {value}"));
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let value = value.clone();
+            async move {
+                let hints = ClientHints::from_headers(|name| {
+                    req.headers().get(name)?.to_str().ok()
+                });
+                req.extensions_mut().insert(hints);
+                let mut res = next.call(req).await?;
+                let headers = res.response_mut().headers_mut();
+                headers.insert(
+                    HeaderName::from_static("accept-ch"),
+                    value.clone(),
+                );
+                headers.insert(HeaderName::from_static("critical-ch"), value);
+                Ok(res)
+            }
+        },
+    ))
+}
+
+/// Preloads `assets` with a `Link` header on every response from `route`, if
+/// there are any.
+fn set_preload_links(assets: &[PreloadLink], route: Route) -> Route {
+    let Some(value) = preload_link_header(assets) else {
+        return route;
+    };
+    let value = HeaderValue::from_str(&value)
+        .unwrap_or_else(|_| panic!("invalid Link header: {value}"));
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let value = value.clone();
+            async move {
+                let mut res = next.call(req).await?;
+                res.response_mut().headers_mut().append(header::LINK, value);
+                Ok(res)
+            }
+        },
+    ))
+}
+
+/// Sets the `X-Robots-Tag` header of every response from `route` to
+/// `directives`, if there are any.
+fn set_x_robots_tag(directives: &[XRobotsDirective], route: Route) -> Route {
+    let Some(value) = x_robots_tag_header(directives) else {
+        return route;
+    };
+    let value = HeaderValue::from_str(&value)
+        .unwrap_or_else(|_| panic!("invalid X-Robots-Tag header: {value}"));
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let value = value.clone();
+            async move {
+                let mut res = next.call(req).await?;
+                res.response_mut()
+                    .headers_mut()
+                    .insert(HeaderName::from_static("x-robots-tag"), value);
+                Ok(res)
+            }
+        },
+    ))
+}
+
+/// Checks every request to `route` against `guards`, the
+/// [guards](leptos_router::NestedRoute::guard) of its route, with the path
+/// params of the request, before it is handled.
+///
+/// A request that a guard redirects is answered with the redirect, and one that
+/// a guard forbids with `403 Forbidden`.
+fn check_guards(guards: &[RouteGuard], route: Route) -> Route {
+    if guards.is_empty() {
+        return route;
+    }
+    let guards = Arc::<[RouteGuard]>::from(guards);
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let guards = Arc::clone(&guards);
+            let params = req
+                .match_info()
+                .iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect::<ParamsMap>();
+            async move {
+                match RouteGuard::check_all(&guards, &params).await {
+                    GuardResult::Allow => next.call(req).await,
+                    GuardResult::Redirect(redirect) => {
+                        let status = StatusCode::from_u16(redirect.status)
+                            .unwrap_or(StatusCode::FOUND);
+                        Ok(req.into_response(
+                            HttpResponse::build(status)
+                                .insert_header((LOCATION, redirect.path))
+                                .finish(),
+                        ))
+                    }
+                    GuardResult::Forbid => {
+                        Ok(req
+                            .into_response(HttpResponse::Forbidden().finish()))
+                    }
+                }
+            }
+        },
+    ))
+}
+
+/// Sets the `Surrogate-Key` and `Cache-Tag` headers of every response from
+/// `route` to the surrogate keys of `keys`, for the path params of its request,
+/// if there are any.
+fn set_surrogate_keys(keys: Option<&SurrogateKeys>, route: Route) -> Route {
+    let Some(keys) = keys.cloned() else {
+        return route;
+    };
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let params = req
+                .match_info()
+                .iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect::<ParamsMap>();
+            let headers = keys.headers(&params);
+            async move {
+                let mut res = next.call(req).await?;
+                for (name, value) in headers {
+                    if let Ok(value) = HeaderValue::from_str(&value) {
+                        res.response_mut()
+                            .headers_mut()
+                            .insert(HeaderName::from_static(name), value);
+                    }
+                }
+                Ok(res)
+            }
+        },
+    ))
+}
+
+/// Identifies this server instance with the cookie and header of `policy`, if
+/// there is one, in the responses from `route` to clients that do not send the
+/// cookie yet.
+fn set_sticky_session(
+    policy: Option<StickySessionPolicy>,
+    route: Route,
+) -> Route {
+    let Some(policy) = policy else {
+        return route;
+    };
+    let header_name =
+        HeaderName::try_from(policy.header).unwrap_or_else(|_| {
+            panic!("invalid sticky session header name: {}", policy.header)
+        });
+    let instance_id = HeaderValue::from_str(StickySessionPolicy::instance_id())
+        .expect("invalid sticky session instance ID");
+    let cookie = HeaderValue::from_str(&policy.set_cookie())
+        .expect("invalid sticky session cookie");
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let (header_name, instance_id, cookie) =
+                (header_name.clone(), instance_id.clone(), cookie.clone());
+            async move {
+                let first_contact = !req
+                    .headers()
+                    .get_all(header::COOKIE)
+                    .filter_map(|value| value.to_str().ok())
+                    .any(|cookies| policy.is_set_in(cookies));
+                let mut res = next.call(req).await?;
+                if first_contact {
+                    let headers = res.response_mut().headers_mut();
+                    headers.append(header::SET_COOKIE, cookie);
+                    headers.insert(header_name, instance_id);
+                }
+                Ok(res)
+            }
+        },
+    ))
+}
+
+/// Marks every response from `route` with [`ExcludeFromAccessLog`], if it is
+/// `excluded`.
+fn mark_access_log_exclusion(excluded: bool, route: Route) -> Route {
+    if !excluded {
+        return route;
+    }
+    route.wrap(middleware::from_fn(
+        |req: ServiceRequest, next: middleware::Next<body::BoxBody>| async move {
+            let mut res = next.call(req).await?;
+            res.response_mut().extensions_mut().insert(ExcludeFromAccessLog);
+            Ok(res)
+        },
+    ))
+}
+
+/// Responds to every request to `route` with `status`, if there is one,
+/// whatever status its handler set.
+fn with_route_status(status: Option<u16>, route: Route) -> Route {
+    let Some(status) = status else {
+        return route;
+    };
+    let status = StatusCode::from_u16(status)
+        .unwrap_or_else(|_| panic!("invalid route status code: {status}"));
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            async move {
+                let mut res = next.call(req).await?;
+                *res.response_mut().status_mut() = status;
+                Ok(res)
+            }
+        },
+    ))
+}
+
+/// Wraps the JSON responses of `route` in the envelope of `config`, if there is
+/// one.
+///
+/// Responses that are not JSON, like the HTML of a page, whose bodies do not
+/// parse as JSON, or that are larger than the [`BUFFERED_BODY_LIMIT`], are left
+/// as they are.
+fn wrap_response_envelope(
+    config: Option<&EnvelopeConfig>,
+    route: Route,
+) -> Route {
+    let Some(config) = config.cloned() else {
+        return route;
+    };
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let config = config.clone();
+            async move {
+                let res = next.call(req).await?;
+                let is_json = res
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|mime| mime.split(';').next())
+                    .is_some_and(|mime| {
+                        let mime = mime.trim();
+                        mime == "application/json" || mime.ends_with("+json")
+                    });
+                if !is_json {
+                    return Ok(res);
+                }
+                let (req, res) = res.into_parts();
+                let (mut res, body) = res.into_parts();
+                let mut body = Box::pin(body);
+                let body = futures::stream::poll_fn(move |cx| {
+                    body::MessageBody::poll_next(body.as_mut(), cx)
+                });
+                let body = match buffer_body(body, BUFFERED_BODY_LIMIT)
+                    .await
+                    .map_err(error::ErrorInternalServerError)?
+                {
+                    BufferedBody::Whole(body) => body,
+                    BufferedBody::TooLarge(read, rest) => {
+                        let body =
+                            futures::stream::iter(read.into_iter().map(Ok))
+                                .chain(rest);
+                        return Ok(dev::ServiceResponse::new(
+                            req,
+                            res.set_body(body::BoxBody::new(
+                                body::BodyStream::new(body),
+                            )),
+                        ));
+                    }
+                };
+                let Ok(data) =
+                    serde_json::from_slice::<serde_json::Value>(&body)
+                else {
+                    return Ok(dev::ServiceResponse::new(
+                        req,
+                        res.set_body(body::BoxBody::new(body)),
+                    ));
+                };
+                let wrapped = {
+                    let headers = req
+                        .headers()
+                        .iter()
+                        .filter_map(|(name, value)| {
+                            Some((name.as_str(), value.to_str().ok()?))
+                        })
+                        .collect::<Vec<_>>();
+                    let connection_info = req.connection_info();
+                    let mut match_req = MatchRequest::new(req.path())
+                        .host(connection_info.host())
+                        .headers(&headers);
+                    if !req.query_string().is_empty() {
+                        match_req = match_req.query(req.query_string());
+                    }
+                    let request_id = match_req.header(REQUEST_ID_HEADER);
+                    config.wrap(data, &match_req, request_id, SystemTime::now())
+                };
+                res.headers_mut().remove(header::CONTENT_LENGTH);
+                let res = res.set_body(body::BoxBody::new(wrapped.to_string()));
+                Ok(dev::ServiceResponse::new(req, res))
+            }
+        },
+    ))
+}
+
+/// The errors caught by the `<ErrorBoundary/>`s of the app while a route with
+/// an [`ErrorFormat`] is rendered, which are registered with the shared context
+/// of its response.
+#[derive(Clone, Default)]
+struct RouteErrors(
+    Arc<parking_lot::Mutex<Option<Arc<dyn SharedContext + Send + Sync>>>>,
+);
+
+impl RouteErrors {
+    /// Collects the errors of the shared context of the current owner.
+    fn watch(&self) {
+        *self.0.lock() = Owner::current_shared_context();
+    }
+
+    fn take(&self) -> Vec<leptos::error::Error> {
+        self.0
+            .lock()
+            .as_ref()
+            .map(|sc| {
+                sc.take_errors()
+                    .into_iter()
+                    .map(|(_, _, error)| error)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Responds with the errors caught while `route` renders a route, encoded with
+/// `format`, instead of the page, if the format is not HTML and there are any.
+///
+/// The body of the response has to be buffered whole, as errors can be caught
+/// until its last chunk is rendered.
+fn format_route_errors(format: Option<&ErrorFormat>, route: Route) -> Route {
+    let Some(format) = format.filter(|format| !format.is_html()).cloned()
+    else {
+        return route;
+    };
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let format = format.clone();
+            async move {
+                let errors = RouteErrors::default();
+                req.extensions_mut().insert(errors.clone());
+                let res = next.call(req).await?;
+                let (req, res) = res.into_parts();
+                let (res, body) = res.into_parts();
+                let body = body::to_bytes(body)
+                    .await
+                    .map_err(error::ErrorInternalServerError)?;
+                let caught = errors.take();
+                if caught.is_empty() {
+                    return Ok(dev::ServiceResponse::new(
+                        req,
+                        res.set_body(body::BoxBody::new(body)),
+                    ));
+                }
+                let status = if res.status().is_client_error()
+                    || res.status().is_server_error()
+                {
+                    res.status()
+                } else {
+                    StatusCode::INTERNAL_SERVER_ERROR
+                };
+                let error = RouteError::new(status.as_u16(), caught);
+                let res = match format.format(&error) {
+                    Some((error_body, mime)) => HttpResponse::build(status)
+                        .content_type(mime)
+                        .body(error_body),
+                    None => res.set_body(body::BoxBody::new(body)),
+                };
+                Ok(dev::ServiceResponse::new(req, res))
+            }
+        },
+    ))
+}
+
+/// Inserts `head` into the `<head>` of the HTML responses of `route`, if it is
+/// not empty, as they are streamed.
+fn inject_head_content(head: &HeadInjection, route: Route) -> Route {
+    if head.is_empty() {
+        return route;
+    }
+    let head = head.clone();
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let head = head.clone();
+            async move {
+                let res = next.call(req).await?;
+                let is_html = res
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|value| value.starts_with("text/html"));
+                if !is_html {
+                    return Ok(res);
+                }
+                let (req, res) = res.into_parts();
+                let (mut res, body) = res.into_parts();
+                res.headers_mut().remove(header::CONTENT_LENGTH);
+                let injector =
+                    Arc::new(parking_lot::Mutex::new(head.injector()));
+                let mut body = Box::pin(body);
+                let body = futures::stream::poll_fn(move |cx| {
+                    body::MessageBody::poll_next(body.as_mut(), cx)
+                })
+                .map({
+                    let injector = Arc::clone(&injector);
+                    move |chunk| {
+                        chunk.map(|chunk| {
+                            web::Bytes::from(injector.lock().push(&chunk))
+                        })
+                    }
+                })
+                .chain(once(async move {
+                    Ok(web::Bytes::from(injector.lock().finish()))
+                }));
+                Ok(dev::ServiceResponse::new(
+                    req,
+                    res.set_body(body::BoxBody::new(body::BodyStream::new(
+                        body,
+                    ))),
+                ))
+            }
+        },
+    ))
+}
+
+/// Minifies the HTML responses of `route` with `config`, if there is one, a
+/// chunk at a time as they are streamed.
+fn minify_html_responses(
+    config: Option<HtmlMinifyConfig>,
+    route: Route,
+) -> Route {
+    #[cfg(feature = "minify_html")]
+    if let Some(config) = config {
+        return route.wrap(middleware::from_fn(
+            move |req: ServiceRequest,
+                  next: middleware::Next<body::BoxBody>| async move {
+                let res = next.call(req).await?;
+                let is_html = res
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|value| value.starts_with("text/html"));
+                if !is_html {
+                    return Ok(res);
+                }
+                let (req, res) = res.into_parts();
+                let (mut res, body) = res.into_parts();
+                res.headers_mut().remove(header::CONTENT_LENGTH);
+                let minifier = Arc::new(parking_lot::Mutex::new(
+                    HtmlMinifier::new(config),
+                ));
+                let mut body = Box::pin(body);
+                let body = futures::stream::poll_fn(move |cx| {
+                    body::MessageBody::poll_next(body.as_mut(), cx)
+                })
+                .map({
+                    let minifier = Arc::clone(&minifier);
+                    move |chunk| {
+                        chunk.map(|chunk| {
+                            web::Bytes::from(minifier.lock().push(&chunk))
+                        })
+                    }
+                })
+                .chain(once(async move {
+                    Ok(web::Bytes::from(minifier.lock().finish()))
+                }));
+                Ok(dev::ServiceResponse::new(
+                    req,
+                    res.set_body(body::BoxBody::new(body::BodyStream::new(
+                        body,
+                    ))),
+                ))
+            },
+        ));
+    }
+    _ = config;
+    route
+}
+
+/// Checks the JSON bodies of the `200 OK` responses of `route` against
+/// `schema`, if there is one, with the `response_schema` feature, and logs each
+/// way in which they do not match it.
+///
+/// The body of a checked response has to be buffered whole, but it is sent on
+/// as it is, whether or not it matches. Bodies larger than the
+/// [`BUFFERED_BODY_LIMIT`] are not checked.
+fn validate_response_body(
+    schema: Option<&serde_json::Value>,
+    route: Route,
+) -> Route {
+    #[cfg(feature = "response_schema")]
+    if let Some(schema) = schema.cloned() {
+        return route.wrap(middleware::from_fn(
+            move |req: ServiceRequest,
+                  next: middleware::Next<body::BoxBody>| {
+                let schema = schema.clone();
+                async move {
+                    let path = req.path().to_owned();
+                    let res = next.call(req).await?;
+                    let is_json = res
+                        .headers()
+                        .get(header::CONTENT_TYPE)
+                        .and_then(|value| value.to_str().ok())
+                        .is_some_and(|value| {
+                            value.starts_with("application/json")
+                        });
+                    if res.status() != StatusCode::OK || !is_json {
+                        return Ok(res);
+                    }
+                    let (req, res) = res.into_parts();
+                    let (res, body) = res.into_parts();
+                    let mut body = Box::pin(body);
+                    let body = futures::stream::poll_fn(move |cx| {
+                        body::MessageBody::poll_next(body.as_mut(), cx)
+                    });
+                    let body = match buffer_body(body, BUFFERED_BODY_LIMIT)
+                        .await
+                        .map_err(error::ErrorInternalServerError)?
+                    {
+                        BufferedBody::Whole(body) => body,
+                        BufferedBody::TooLarge(read, rest) => {
+                            let body =
+                                futures::stream::iter(read.into_iter().map(Ok))
+                                    .chain(rest);
+                            return Ok(dev::ServiceResponse::new(
+                                req,
+                                res.set_body(body::BoxBody::new(
+                                    body::BodyStream::new(body),
+                                )),
+                            ));
+                        }
+                    };
+                    let mismatches = match serde_json::from_slice(&body) {
+                        Ok(value) => leptos_router::validate_response_schema(
+                            &schema, &value,
+                        )
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                        Err(e) => vec![format!("the body is not JSON: {e}")],
+                    };
+                    for mismatch in mismatches {
+                        let msg = format!(
+                            "The response to {path} does not match its schema \
+                             {mismatch}"
+                        );
+
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!("{}", &msg);
+
+                        #[cfg(not(feature = "tracing"))]
+                        eprintln!("{}", &msg);
+                    }
+                    Ok(dev::ServiceResponse::new(
+                        req,
+                        res.set_body(body::BoxBody::new(body)),
+                    ))
+                }
+            },
+        ));
+    }
+    _ = schema;
+    route
+}
+
+/// Signs the body of each response of `route` according to `config`, if there
+/// is one, in the [`SIGNATURE_HEADER`](leptos_router::SIGNATURE_HEADER).
+///
+/// The body is buffered whole to sign it. A response that cannot be signed is
+/// replaced with `500 Internal Server Error`, rather than being sent without
+/// its signature.
+fn sign_responses(
+    config: Option<&ResponseSigningConfig>,
+    route: Route,
+) -> Route {
+    #[cfg(feature = "response_signing")]
+    if let Some(config) = config.cloned() {
+        return route.wrap(middleware::from_fn(
+            move |req: ServiceRequest,
+                  next: middleware::Next<body::BoxBody>| {
+                let config = config.clone();
+                async move {
+                    let res = next.call(req).await?;
+                    let (req, res) = res.into_parts();
+                    let (mut res, body) = res.into_parts();
+                    let body = body::to_bytes(body)
+                        .await
+                        .map_err(error::ErrorInternalServerError)?;
+                    let signature = config
+                        .sign(&body)
+                        .map_err(|e| e.to_string())
+                        .and_then(|signature| {
+                            HeaderValue::try_from(signature)
+                                .map_err(|e| e.to_string())
+                        })
+                        .map_err(|e| {
+                            #[cfg(feature = "tracing")]
+                            tracing::error!("{}", &e);
+
+                            #[cfg(not(feature = "tracing"))]
+                            eprintln!("{}", &e);
+
+                            error::ErrorInternalServerError(e)
+                        })?;
+                    res.headers_mut().insert(
+                        HeaderName::from_static(
+                            leptos_router::SIGNATURE_HEADER,
+                        ),
+                        signature,
+                    );
+                    Ok(dev::ServiceResponse::new(
+                        req,
+                        res.set_body(body::BoxBody::new(body)),
+                    ))
+                }
+            },
+        ));
+    }
+    _ = config;
+    route
+}
+
+/// The route that serves the public keys of the routes in `paths` whose
+/// responses are signed with RSA or ECDSA, if there are any, as a JSON Web Key
+/// Set at the [`JWKS_PATH`](leptos_router::JWKS_PATH).
+fn jwks_route(paths: &[ActixRouteListing]) -> Option<Route> {
+    #[cfg(feature = "response_signing")]
+    {
+        let configs = paths
+            .iter()
+            .filter_map(|listing| listing.config().response_signing.as_ref())
+            .filter(|config| config.algorithm.is_asymmetric())
+            .collect::<Vec<_>>();
+        if !configs.is_empty() {
+            let jwks = leptos_router::jwks(configs).to_string();
+            return Some(web::get().to(move || {
+                let jwks = jwks.clone();
+                async move {
+                    HttpResponse::Ok()
+                        .content_type("application/json")
+                        .body(jwks)
+                }
+            }));
+        }
+    }
+    _ = paths;
+    None
+}
+
+/// Makes concurrent identical `GET` requests to `route` share the response of
+/// the first of them, if `config` is set.
+///
+/// The response of the first request is buffered whole, unless it is larger
+/// than the body limit of `config`. It is only shared if it succeeded and was
+/// buffered; otherwise, the requests that waited on it are handled on their
+/// own.
+fn deduplicate_requests(
+    config: Option<&DeduplicationConfig>,
+    route: Route,
+) -> Route {
+    let Some(config) = config.cloned() else {
+        return route;
+    };
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let config = config.clone();
+            async move {
+                if req.method() != http::Method::GET {
+                    return next.call(req).await;
+                }
+                let headers = req.headers().clone();
+                let header_value = |name| {
+                    headers.get(name).and_then(|value| value.to_str().ok())
+                };
+                let key = config.key(
+                    req.path(),
+                    Some(req.query_string()),
+                    header_value(header::COOKIE),
+                    header_value(header::AUTHORIZATION),
+                );
+                let request_header = |name: &str| {
+                    headers.get(name).map(|value| value.as_bytes().to_vec())
+                };
+                let guard = match config.join(key, request_header).await {
+                    Deduplicated::First(guard) => guard,
+                    Deduplicated::Shared(shared) => {
+                        let mut res = HttpResponse::build(
+                            StatusCode::from_u16(shared.status)
+                                .unwrap_or(StatusCode::OK),
+                        );
+                        for (name, value) in shared.headers {
+                            if let (Ok(name), Ok(value)) = (
+                                HeaderName::try_from(name),
+                                HeaderValue::try_from(value),
+                            ) {
+                                res.append_header((name, value));
+                            }
+                        }
+                        return Ok(req.into_response(res.body(shared.body)));
+                    }
+                    Deduplicated::Alone => return next.call(req).await,
+                };
+                let res = next.call(req).await?;
+                if !res.status().is_success() {
+                    guard.finish(None);
+                    return Ok(res);
+                }
+                let (req, res) = res.into_parts();
+                let (res, body) = res.into_parts();
+                let mut body = Box::pin(body);
+                let body = futures::stream::poll_fn(move |cx| {
+                    body::MessageBody::poll_next(body.as_mut(), cx)
+                });
+                match config.buffer_body(body).await {
+                    Ok(BufferedBody::Whole(body)) => {
+                        guard.finish(DeduplicatedResponse::new(
+                            res.status().as_u16(),
+                            res.headers()
+                                .iter()
+                                .map(|(name, value)| {
+                                    (
+                                        name.to_string(),
+                                        value.as_bytes().to_vec(),
+                                    )
+                                })
+                                .collect(),
+                            body.clone(),
+                            request_header,
+                        ));
+                        Ok(dev::ServiceResponse::new(
+                            req,
+                            res.set_body(body::BoxBody::new(body)),
+                        ))
+                    }
+                    Ok(BufferedBody::TooLarge(read, rest)) => {
+                        guard.finish(None);
+                        let body =
+                            futures::stream::iter(read.into_iter().map(Ok))
+                                .chain(rest);
+                        Ok(dev::ServiceResponse::new(
+                            req,
+                            res.set_body(body::BoxBody::new(
+                                body::BodyStream::new(body),
+                            )),
+                        ))
+                    }
+                    Err(e) => {
+                        guard.finish(None);
+                        Err(error::ErrorInternalServerError(e))
+                    }
+                }
+            }
+        },
+    ))
+}
+
+/// Negotiates the language of every response from `route` with `negotiation`,
+/// if there is one, storing it as a [`NegotiatedLanguage`] in the extensions of
+/// the request (and in the context of the rendered route), and sending it in
+/// the `Content-Language` header, along with a `Set-Cookie` header that stores
+/// it if the request did not send it in its cookie.
+fn negotiate_language(
+    negotiation: Option<LanguageNegotiation>,
+    route: Route,
+) -> Route {
+    let Some(negotiation) = negotiation else {
+        return route;
+    };
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let header_value = |name| {
+                req.headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+            };
+            let (language, set_cookie) = negotiation.select(
+                header_value(header::COOKIE),
+                header_value(header::ACCEPT_LANGUAGE),
+            );
+            req.extensions_mut().insert(NegotiatedLanguage(language));
+            async move {
+                let mut res = next.call(req).await?;
+                let headers = res.response_mut().headers_mut();
+                headers.insert(
+                    header::CONTENT_LANGUAGE,
+                    HeaderValue::from_static(language),
+                );
+                headers.append(
+                    header::VARY,
+                    HeaderValue::from_static("Accept-Language, Cookie"),
+                );
+                if let Some(cookie) =
+                    set_cookie.and_then(|cookie| cookie.try_into().ok())
+                {
+                    headers.append(header::SET_COOKIE, cookie);
+                }
+                Ok(res)
+            }
+        },
+    ))
+}
+
+/// Rejects requests to `route` that do not carry an API key accepted by
+/// `config`, if there is one.
+///
+/// The key of a valid request is stored as an [`ApiKey`] in the request
+/// extensions (and provided via context by the Leptos handlers) for the handler
+/// to use.
+fn verify_api_key(config: Option<&ApiKeyConfig>, route: Route) -> Route {
+    let Some(config) = config.cloned() else {
+        return route;
+    };
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let config = config.clone();
+            async move {
+                let key = config
+                    .source
+                    .find(
+                        |name| {
+                            req.headers()
+                                .get(name)
+                                .and_then(|value| value.to_str().ok())
+                        },
+                        Some(req.query_string()),
+                    )
+                    .map(|key| key.into_owned());
+                let valid = match &key {
+                    Some(key) => config.validator.validate(key).await,
+                    None => false,
+                };
+                match key {
+                    Some(key) if valid => {
+                        req.extensions_mut().insert(ApiKey::new(key));
+                        next.call(req).await
+                    }
+                    _ => {
+                        let res = HttpResponse::Unauthorized()
+                            .insert_header((
+                                header::WWW_AUTHENTICATE,
+                                config.source.www_authenticate(),
+                            ))
+                            .finish();
+                        Err(error::InternalError::from_response(
+                            "missing or invalid API key",
+                            res,
+                        )
+                        .into())
+                    }
+                }
+            }
+        },
+    ))
+}
+
+/// Rejects requests to `route` that do not present a TLS client certificate
+/// accepted by `config`, if there is one.
+///
+/// The certificate must have been stored as a [`ClientCert`] in the request
+/// extensions by the server that terminates TLS. Requests without one are
+/// rejected with `401 Unauthorized`, and those with a certificate that is not
+/// valid with `403 Forbidden`.
+fn verify_client_cert(
+    config: Option<&ClientCertConfig>,
+    route: Route,
+) -> Route {
+    let Some(config) = config.cloned() else {
+        return route;
+    };
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let config = config.clone();
+            async move {
+                let Some(cert) = req.extensions().get::<ClientCert>().cloned()
+                else {
+                    return Err(error::InternalError::from_response(
+                        "missing client certificate",
+                        HttpResponse::Unauthorized().finish(),
+                    )
+                    .into());
+                };
+                if !config.validator.validate(&cert).await {
+                    return Err(error::InternalError::from_response(
+                        "invalid client certificate",
+                        HttpResponse::Forbidden().finish(),
+                    )
+                    .into());
+                }
+                next.call(req).await
+            }
+        },
+    ))
+}
+
+/// Rejects requests to `route` whose query does not match `schema`, if there is
+/// one, with `400 Bad Request` and a JSON body that lists the errors.
+///
+/// The params of a valid query are stored as a [`ValidatedQuery`] in the
+/// request extensions (and provided via context by the Leptos handlers) for the
+/// handler to use.
+fn verify_query(schema: Option<&QuerySchema>, route: Route) -> Route {
+    let Some(schema) = schema.cloned() else {
+        return route;
+    };
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let query = schema.validate(req.query_string());
+            async move {
+                match query {
+                    Ok(query) => {
+                        req.extensions_mut().insert(query);
+                        next.call(req).await
+                    }
+                    Err(errors) => {
+                        let res = HttpResponse::BadRequest()
+                            .content_type("application/json")
+                            .body(errors.to_json());
+                        Err(error::InternalError::from_response(errors, res)
+                            .into())
+                    }
+                }
+            }
+        },
+    ))
+}
+
+/// Rejects requests to `route` with a body whose content type is not one of
+/// `consumes`, if they are restricted, with `415 Unsupported Media Type`.
+fn verify_content_type(consumes: Option<&ContentTypes>, route: Route) -> Route {
+    let Some(consumes) = consumes.cloned() else {
+        return route;
+    };
+    route.wrap(middleware::from_fn(
+        move |req: ServiceRequest, next: middleware::Next<body::BoxBody>| {
+            let unsupported = consumes.applies_to(req.method().as_str())
+                && !consumes.accepts(
+                    req.headers()
+                        .get(header::CONTENT_TYPE)
+                        .and_then(|value| value.to_str().ok()),
+                );
+            async move {
+                if unsupported {
+                    Ok(req.into_response(HttpResponse::UnsupportedMediaType()))
+                } else {
+                    next.call(req).await
+                }
+            }
+        },
+    ))
+}
+
+/// Rejects requests to `route` whose body does not match the schema in
+/// `schemas` for its content type, if there are any, with
+/// `422 Unprocessable Entity` (or `400 Bad Request` or
+/// `415 Unsupported Media Type` for a body that cannot be checked) and a JSON
+/// body that lists the errors.
+///
+/// The body has to be read in full to validate it, so its parsed value is
+/// stored as a [`ValidatedBody`] in the request extensions (and provided via
+/// context by the Leptos handlers) for the handler to use.
+fn verify_body(schemas: Option<&BodySchemas>, route: Route) -> Route {
+    let Some(schemas) = schemas.cloned() else {
+        return route;
+    };
+    route.wrap(middleware::from_fn(
+        move |mut req: ServiceRequest,
+              next: middleware::Next<body::BoxBody>| {
+            let schemas = schemas.clone();
+            async move {
+                if !schemas.applies_to(req.method().as_str()) {
+                    return next.call(req).await;
+                }
+                let body = req.extract::<web::Bytes>().await?;
+                let content_type = req
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok());
+                match schemas.validate(content_type, &body) {
+                    Ok(validated) => {
+                        req.extensions_mut().insert(validated);
+                        req.set_payload(dev::Payload::from(body));
+                        next.call(req).await
+                    }
+                    Err(rejection) => {
+                        let status =
+                            StatusCode::from_u16(rejection.status_code())
+                                .unwrap_or(StatusCode::UNPROCESSABLE_ENTITY);
+                        let res = HttpResponse::build(status)
+                            .content_type("application/json")
+                            .body(rejection.to_json());
+                        Err(error::InternalError::from_response(rejection, res)
+                            .into())
+                    }
+                }
+            }
+        },
+    ))
+}
+
 /// A helper to make it easier to use Actix extractors in server functions.
 ///
 /// It is generic over some type `T` that implements [`FromRequest`] and can
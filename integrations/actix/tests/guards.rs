@@ -0,0 +1,73 @@
+use actix_web::{
+    http::{header, StatusCode},
+    test, web, App,
+};
+use leptos::prelude::*;
+use leptos_actix::{generate_route_list, LeptosRoutes};
+use leptos_router::{
+    components::{Router, Routes},
+    params::ParamsMap,
+    GuardResult, MatchNestedRoutes, NestedRoute, ParamSegment, RouteRedirect,
+    StaticSegment,
+};
+
+/// The routes of the app, with a guard that redirects every request and one
+/// that forbids those for a secret project.
+#[component(transparent)]
+fn GuardedRoutes() -> impl MatchNestedRoutes + Clone {
+    (
+        NestedRoute::new(StaticSegment("home"), || "home"),
+        NestedRoute::new(StaticSegment("account"), || "account").guard(
+            |_: &ParamsMap| GuardResult::Redirect(RouteRedirect::new("/login")),
+        ),
+        NestedRoute::new(StaticSegment("projects"), || ())
+            .guard(|params: &ParamsMap| params.get_str("id") != Some("secret"))
+            .child(NestedRoute::new(ParamSegment("id"), || "project")),
+    )
+}
+
+fn app() -> impl IntoView {
+    view! {
+        <Router>
+            <Routes fallback=|| "not found">
+                <GuardedRoutes/>
+            </Routes>
+        </Router>
+    }
+}
+
+async fn get(path: &str) -> actix_web::dev::ServiceResponse {
+    let options = LeptosOptions::builder().output_name("guards").build();
+    let routes = generate_route_list(app);
+    let service = test::init_service(
+        App::new()
+            .app_data(web::Data::new(options))
+            .leptos_routes(routes, app),
+    )
+    .await;
+    test::call_service(
+        &service,
+        test::TestRequest::get().uri(path).to_request(),
+    )
+    .await
+}
+
+#[actix_web::test]
+async fn allowed_requests_are_rendered() {
+    assert_eq!(get("/home").await.status(), StatusCode::OK);
+    assert_eq!(get("/projects/public").await.status(), StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn forbidden_requests_are_rejected() {
+    // the guard of the parent route sees the params of the whole matched route
+    let res = get("/projects/secret").await;
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn redirected_requests_are_redirected() {
+    let res = get("/account").await;
+    assert_eq!(res.status(), StatusCode::FOUND);
+    assert_eq!(res.headers().get(header::LOCATION).unwrap(), "/login");
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "response_signing")]
+
+use actix_web::{http::StatusCode, test, web, App};
+use leptos::prelude::*;
+use leptos_actix::{generate_route_list, LeptosRoutes};
+use leptos_router::{
+    components::{Router, Routes},
+    MatchNestedRoutes, NestedRoute, ResponseSigningConfig, Secret,
+    SigningAlgorithm, StaticSegment, SIGNATURE_HEADER,
+};
+
+fn signing() -> ResponseSigningConfig {
+    ResponseSigningConfig {
+        algorithm: SigningAlgorithm::HmacSha256,
+        key_id: "test",
+        key: Secret::new(b"secret".to_vec()),
+    }
+}
+
+#[component(transparent)]
+fn SignedRoutes() -> impl MatchNestedRoutes + Clone {
+    (
+        NestedRoute::new(StaticSegment("signed"), || "signed")
+            .sign_response(signing()),
+        NestedRoute::new(StaticSegment("unsigned"), || "unsigned"),
+    )
+}
+
+fn app() -> impl IntoView {
+    view! {
+        <Router>
+            <Routes fallback=|| "not found">
+                <SignedRoutes/>
+            </Routes>
+        </Router>
+    }
+}
+
+async fn get(path: &str) -> actix_web::dev::ServiceResponse {
+    let options = LeptosOptions::builder().output_name("signing").build();
+    let routes = generate_route_list(app);
+    let service = test::init_service(
+        App::new()
+            .app_data(web::Data::new(options))
+            .leptos_routes(routes, app),
+    )
+    .await;
+    test::call_service(
+        &service,
+        test::TestRequest::get().uri(path).to_request(),
+    )
+    .await
+}
+
+#[actix_web::test]
+async fn responses_are_signed() {
+    let res = get("/signed").await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let signature = res.headers().get(SIGNATURE_HEADER).unwrap().clone();
+    let body = test::read_body(res).await;
+    assert_eq!(signature, signing().sign(&body).unwrap());
+
+    let res = get("/unsigned").await;
+    assert!(!res.headers().contains_key(SIGNATURE_HEADER));
+}
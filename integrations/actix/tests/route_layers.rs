@@ -0,0 +1,255 @@
+use actix_web::{
+    body,
+    http::{header, Method, StatusCode},
+    test, web, App, HttpResponse,
+};
+use leptos::prelude::*;
+use leptos_actix::{generate_route_list, LeptosRoutes, ResponseOptions};
+use leptos_router::{
+    components::{Router, Routes},
+    EnvelopeConfig, HmacAlgorithm, InMemoryDeduplicationStore,
+    MatchNestedRoutes, NestedRoute, Secret, ShutdownSignal, StaticSegment,
+    WebhookSignatureConfig,
+};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, LazyLock,
+    },
+    time::Duration,
+};
+
+/// Set once the test of the graceful shutdown has started.
+static SHUTDOWN: LazyLock<Arc<AtomicBool>> =
+    LazyLock::new(|| Arc::new(AtomicBool::new(false)));
+
+/// The number of times that `/shared` has been rendered.
+static SHARED_RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+// the example from GitHub's documentation on validating webhook deliveries
+const WEBHOOK_SECRET: &str = "It's a Secret to Everybody";
+const WEBHOOK_SIGNATURE: &str =
+    "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+fn webhook_signature() -> WebhookSignatureConfig {
+    WebhookSignatureConfig {
+        header: "X-Hub-Signature-256",
+        secret: Secret::new(WEBHOOK_SECRET.to_string()),
+        algorithm: HmacAlgorithm::Sha256,
+    }
+}
+
+/// A page that claims to be JSON, but is followed by the scripts of the
+/// rendered app.
+#[component]
+fn Data() -> impl IntoView {
+    expect_context::<ResponseOptions>().insert_header(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    r#"{"id":1}"#
+}
+
+/// A page that takes a while to render, which counts its renders.
+#[component]
+fn Shared() -> impl IntoView {
+    let renders = SHARED_RENDERS.fetch_add(1, Ordering::SeqCst) + 1;
+    let delay = Resource::new(
+        || (),
+        |_| tokio::time::sleep(Duration::from_millis(100)),
+    );
+    view! { <Suspense>{move || delay.get().map(|_| renders)}</Suspense> }
+}
+
+#[component(transparent)]
+fn LayeredRoutes() -> impl MatchNestedRoutes + Clone {
+    (
+        NestedRoute::new(StaticSegment("page"), || "page"),
+        NestedRoute::new(StaticSegment("form"), || "form")
+            .methods([leptos_router::Method::Get, leptos_router::Method::Post]),
+        NestedRoute::new(StaticSegment("gone"), || "gone").status(410),
+        NestedRoute::new(StaticSegment("hook"), || "hook")
+            .methods([leptos_router::Method::Post])
+            .webhook_signature(webhook_signature()),
+        NestedRoute::new(StaticSegment("draining"), || "draining")
+            .methods([leptos_router::Method::Post])
+            .webhook_signature(webhook_signature())
+            .graceful_shutdown(ShutdownSignal::new(Arc::clone(&SHUTDOWN))),
+        NestedRoute::new(StaticSegment("hello"), || "hello")
+            .language_negotiation(&["en", "fr"], "en"),
+        NestedRoute::new(StaticSegment("data"), Data).response_envelope(
+            EnvelopeConfig {
+                include_meta: false,
+                meta_fn: None,
+            },
+        ),
+        NestedRoute::new(StaticSegment("shared"), Shared)
+            .deduplicate(Arc::new(InMemoryDeduplicationStore::new())),
+    )
+}
+
+fn app() -> impl IntoView {
+    view! {
+        <Router>
+            <Routes fallback=|| "not found">
+                <LayeredRoutes/>
+            </Routes>
+        </Router>
+    }
+}
+
+/// Sends `req` to the app, with the response that actix-web makes of an error.
+async fn send(req: test::TestRequest) -> HttpResponse {
+    let options = LeptosOptions::builder().output_name("route_layers").build();
+    let routes = generate_route_list(app);
+    let service = test::init_service(
+        App::new()
+            .app_data(web::Data::new(options))
+            .leptos_routes(routes, app),
+    )
+    .await;
+    match test::try_call_service(&service, req.to_request()).await {
+        Ok(res) => res.into_parts().1,
+        Err(e) => e.error_response(),
+    }
+}
+
+async fn get(path: &str) -> HttpResponse {
+    send(test::TestRequest::get().uri(path)).await
+}
+
+fn signed(path: &str, body: &'static str) -> test::TestRequest {
+    test::TestRequest::post()
+        .uri(path)
+        .insert_header(("X-Hub-Signature-256", WEBHOOK_SIGNATURE))
+        .set_payload(body)
+}
+
+#[actix_web::test]
+async fn head_requests_are_answered_without_a_body() {
+    let res = send(
+        test::TestRequest::default()
+            .method(Method::HEAD)
+            .uri("/page"),
+    )
+    .await;
+    // actix-web removes the body as it writes the response
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn options_requests_list_the_allowed_methods() {
+    let options = |path| {
+        test::TestRequest::default()
+            .method(Method::OPTIONS)
+            .uri(path)
+    };
+    let res = send(options("/page")).await;
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+    assert_eq!(
+        res.headers().get(header::ALLOW).unwrap(),
+        "GET, HEAD, OPTIONS"
+    );
+
+    let res = send(options("/form")).await;
+    assert_eq!(
+        res.headers().get(header::ALLOW).unwrap(),
+        "GET, HEAD, POST, OPTIONS"
+    );
+}
+
+#[actix_web::test]
+async fn other_methods_are_not_allowed() {
+    // actix-web tries the other routes of the app, and none of them matches
+    let res = send(test::TestRequest::post().uri("/page")).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    let res = send(test::TestRequest::post().uri("/form")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn routes_respond_with_their_status() {
+    assert_eq!(get("/page").await.status(), StatusCode::OK);
+    assert_eq!(get("/gone").await.status(), StatusCode::GONE);
+}
+
+#[actix_web::test]
+async fn webhooks_without_a_valid_signature_are_rejected() {
+    let res = send(
+        test::TestRequest::post()
+            .uri("/hook")
+            .set_payload("Hello, World!"),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = send(signed("/hook", "Hello, World?")).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = send(signed("/hook", "Hello, World!")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn requests_are_rejected_once_the_app_shuts_down() {
+    let res = send(signed("/draining", "Hello, World!")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    SHUTDOWN.store(true, Ordering::Relaxed);
+    let res = send(signed("/draining", "Hello, World!")).await;
+    assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    // the shutdown is checked before the signature, which is never read
+    let res = send(test::TestRequest::post().uri("/draining")).await;
+    assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[actix_web::test]
+async fn the_language_is_negotiated() {
+    let res = send(
+        test::TestRequest::get()
+            .uri("/hello")
+            .insert_header((header::ACCEPT_LANGUAGE, "fr-CH, fr;q=0.9")),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers().get(header::CONTENT_LANGUAGE).unwrap(), "fr");
+
+    // an unsupported language is not an error: the fallback is used instead
+    let res = send(
+        test::TestRequest::get()
+            .uri("/hello")
+            .insert_header((header::ACCEPT_LANGUAGE, "de")),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers().get(header::CONTENT_LANGUAGE).unwrap(), "en");
+}
+
+#[actix_web::test]
+async fn responses_that_are_not_json_are_not_wrapped_in_the_envelope() {
+    let res = get("/data").await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = body::to_bytes(res.into_body()).await.unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.contains(r#"{"id":1}"#));
+    assert!(!body.starts_with(r#"{"data""#));
+}
+
+#[actix_web::test]
+async fn concurrent_requests_share_a_response() {
+    let options = LeptosOptions::builder().output_name("route_layers").build();
+    let routes = generate_route_list(app);
+    let service = test::init_service(
+        App::new()
+            .app_data(web::Data::new(options))
+            .leptos_routes(routes, app),
+    )
+    .await;
+    let request = || test::TestRequest::get().uri("/shared").to_request();
+    let (first, second) = futures::join!(
+        test::call_service(&service, request()),
+        test::call_service(&service, request())
+    );
+    assert_eq!(test::read_body(first).await, test::read_body(second).await);
+    assert_eq!(SHARED_RENDERS.load(Ordering::SeqCst), 1);
+}
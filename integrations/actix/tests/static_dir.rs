@@ -0,0 +1,107 @@
+#![cfg(feature = "static_dir")]
+
+use actix_web::{
+    http::{header, StatusCode},
+    test, web, App,
+};
+use include_dir::{Dir, DirEntry, File};
+use leptos::prelude::*;
+use leptos_actix::{generate_route_list, LeptosRoutes};
+use leptos_router::{
+    components::{Router, Routes},
+    EnvelopeConfig, MatchNestedRoutes, NestedRoute, StaticSegment,
+};
+
+static ASSETS: Dir<'static> = Dir::new(
+    "",
+    &[
+        DirEntry::File(File::new("app.css", b"body { margin: 0; }")),
+        DirEntry::Dir(Dir::new(
+            "js",
+            &[DirEntry::File(File::new("js/app.js", b"console.log(1);"))],
+        )),
+    ],
+);
+
+static API: Dir<'static> = Dir::new(
+    "",
+    &[DirEntry::File(File::new("data.json", br#"{"id":1}"#))],
+);
+
+#[component(transparent)]
+fn AssetRoutes() -> impl MatchNestedRoutes + Clone {
+    (
+        NestedRoute::static_dir(StaticSegment("assets"), &ASSETS),
+        NestedRoute::new(StaticSegment("api"), || ())
+            .response_envelope(EnvelopeConfig {
+                include_meta: false,
+                meta_fn: None,
+            })
+            .child(NestedRoute::static_dir(StaticSegment(""), &API)),
+    )
+}
+
+fn app() -> impl IntoView {
+    view! {
+        <Router>
+            <Routes fallback=|| "not found">
+                <AssetRoutes/>
+            </Routes>
+        </Router>
+    }
+}
+
+async fn send(req: test::TestRequest) -> actix_web::dev::ServiceResponse {
+    let options = LeptosOptions::builder().output_name("static_dir").build();
+    let routes = generate_route_list(app);
+    let service = test::init_service(
+        App::new()
+            .app_data(web::Data::new(options))
+            .leptos_routes(routes, app),
+    )
+    .await;
+    test::call_service(&service, req.to_request()).await
+}
+
+#[actix_web::test]
+async fn serves_the_files_of_a_static_dir() {
+    let res = send(test::TestRequest::get().uri("/assets/js/app.js")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+        res.headers().get(header::CONTENT_TYPE).unwrap(),
+        "text/javascript"
+    );
+    let etag = res.headers().get(header::ETAG).unwrap().clone();
+    assert_eq!(test::read_body(res).await, "console.log(1);");
+
+    let res = send(
+        test::TestRequest::get()
+            .uri("/assets/js/app.js")
+            .insert_header((header::IF_NONE_MATCH, etag)),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+}
+
+#[actix_web::test]
+async fn serves_ranges_of_the_files_of_a_static_dir() {
+    let res = send(
+        test::TestRequest::get()
+            .uri("/assets/app.css")
+            .insert_header((header::RANGE, "bytes=0-3")),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(
+        res.headers().get(header::CONTENT_RANGE).unwrap(),
+        "bytes 0-3/19"
+    );
+    assert_eq!(test::read_body(res).await, "body");
+}
+
+#[actix_web::test]
+async fn json_files_are_wrapped_in_the_envelope() {
+    let res = send(test::TestRequest::get().uri("/api/data.json")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(test::read_body(res).await, r#"{"data":{"id":1}}"#);
+}
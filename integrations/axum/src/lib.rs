@@ -38,44 +38,81 @@
 use axum::http::Uri;
 use axum::{
     body::{Body, Bytes},
-    extract::{FromRef, FromRequestParts, MatchedPath, State},
+    extract::{
+        ConnectInfo, FromRef, FromRequest, FromRequestParts, MatchedPath,
+        RawPathParams, State,
+    },
     http::{
         header::{self, HeaderName, HeaderValue, ACCEPT, LOCATION, REFERER},
         request::Parts,
         HeaderMap, Method, Request, Response, StatusCode,
     },
+    middleware::Next,
     response::IntoResponse,
-    routing::{delete, get, patch, post, put},
+    routing::{any, delete, get, patch, post, put, MethodRouter},
 };
 #[cfg(feature = "default")]
 use dashmap::DashMap;
+#[cfg(feature = "default")]
+use futures::future::BoxFuture;
 use futures::{stream::once, Future, Stream, StreamExt};
-use hydration_context::SsrSharedContext;
+use hydration_context::{SharedContext, SsrSharedContext};
 use leptos::{
     config::LeptosOptions,
     context::{provide_context, use_context},
     prelude::*,
     reactive::{computed::ScopedFuture, owner::Owner},
+    server::{RequestDeadline, RequestTimeout, REQUEST_TIMEOUT_HEADER},
     IntoView,
 };
+pub use leptos_integration_utils::{
+    on_response_metrics, IslandPayload, ResourcePayload, ResponseMetrics,
+};
 use leptos_integration_utils::{
     BoxedFnOnce, ExtendResponse, PinnedFuture, PinnedStream,
 };
 use leptos_meta::ServerMetaContext;
 #[cfg(feature = "default")]
 use leptos_router::static_routes::ResolvedStaticPath;
+#[cfg(feature = "minify_html")]
+use leptos_router::HtmlMinifier;
+#[cfg(feature = "static_dir")]
+use leptos_router::StaticAssetRequest;
 use leptos_router::{
-    components::provide_server_redirect, location::RequestUrl,
-    static_routes::RegenerationFn, ExpandOptionals, PathSegment, RouteList,
-    RouteListing, SsrMode,
+    accept_ch_header, buffer_body, components::provide_server_redirect,
+    location::RequestUrl, params::ParamsMap, preload_link_header,
+    segments_to_pattern, static_routes::RegenerationFn, x_robots_tag_header,
+    ApiKey, ApiKeyConfig, Batch, BatchRequest, BatchResponse, BodySchemas,
+    BufferedBody, ClientCert, ClientCertConfig, ClientHint, ClientHints,
+    ContentTypes, Deduplicated, DeduplicatedResponse, DeduplicationConfig,
+    EnvelopeConfig, ErrorFormat, ExcludeFromAccessLog, ExpandOptionals,
+    GuardResult, HeadInjection, HtmlMinifyConfig, LanguageNegotiation,
+    LanguageRedirect, MatchRequest, NegotiatedLanguage, NotFoundResponse,
+    Passthrough, PathSegment, PreloadLink, ProxyError, ProxyRequest,
+    QuerySchema, RequestDetails, ResponseSigningConfig, RouteConfig,
+    RouteError, RouteGuard, RouteList, RouteListing, RouteMiddleware,
+    ShutdownSignal, SlowRequestConfig, SsrMode, StickySessionPolicy,
+    SurrogateKeys, ValidatedBody, ValidatedQuery, WebSocketHandler,
+    WebSocketMessage, WebhookBody, WebhookSignatureConfig, XRobotsDirective,
+    BUFFERED_BODY_LIMIT, REQUEST_ID_HEADER,
 };
 use parking_lot::RwLock;
-use server_fn::{error::ServerFnErrorErr, redirect::REDIRECT_HEADER};
+use server_fn::{
+    error::ServerFnErrorErr, middleware::BoxedService,
+    redirect::REDIRECT_HEADER,
+};
 #[cfg(feature = "default")]
 use std::path::Path;
 #[cfg(feature = "default")]
-use std::sync::LazyLock;
-use std::{collections::HashSet, fmt::Debug, io, pin::Pin, sync::Arc};
+use std::time::Duration;
+use std::{
+    collections::HashSet,
+    fmt::Debug,
+    io,
+    pin::Pin,
+    sync::{Arc, LazyLock},
+    time::{Instant, SystemTime},
+};
 #[cfg(feature = "default")]
 use tower::util::ServiceExt;
 #[cfg(feature = "default")]
@@ -186,6 +223,14 @@ impl ExtendResponse for AxumResponse {
             );
         }
     }
+
+    fn insert_header(&mut self, name: &'static str, value: &str) {
+        if let Ok(value) = HeaderValue::from_str(value) {
+            self.0
+                .headers_mut()
+                .insert(HeaderName::from_static(name), value);
+        }
+    }
 }
 
 /// Provides an easy way to redirect the user from within a server function.
@@ -712,7 +757,7 @@ where
             SsrMode::PartiallyBlocked => pb(req),
             SsrMode::InOrder => io(req),
             SsrMode::Async => asyn(req),
-            SsrMode::Static(_) => {
+            SsrMode::Static(route) => {
                 #[cfg(feature = "default")]
                 {
                     let regenerate = listing.regenerate.clone();
@@ -720,11 +765,13 @@ where
                         additional_context.clone(),
                         app_fn.clone(),
                         regenerate,
+                        route.is_incremental(),
+                        listing.config().head.clone(),
                     )(state, req)
                 }
                 #[cfg(not(feature = "default"))]
                 {
-                    _ = state;
+                    _ = (state, route);
                     panic!(
                         "Static routes are not currently supported on WASM32 \
                          server targets."
@@ -943,6 +990,24 @@ where
     feature = "tracing",
     tracing::instrument(level = "trace", fields(error), skip_all)
 )]
+/// The method and headers of the request, which the router matches its routes
+/// against.
+fn request_details(parts: &Parts) -> RequestDetails {
+    // a `HEAD` request is answered by the handler of the route's `GET` requests
+    let method = if parts.method == axum::http::Method::HEAD {
+        leptos_router::Method::Get
+    } else {
+        leptos_router::Method::from_name(parts.method.as_str().to_owned())
+    };
+    parts
+        .headers
+        .iter()
+        .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)))
+        .fold(RequestDetails::new(method), |details, (name, value)| {
+            details.with_header(name, value)
+        })
+}
+
 fn provide_contexts(
     path: &str,
     meta_context: &ServerMetaContext,
@@ -950,7 +1015,41 @@ fn provide_contexts(
     default_res_options: ResponseOptions,
 ) {
     provide_context(RequestUrl::new(path));
+    provide_context(request_details(&parts));
     provide_context(meta_context.clone());
+    if let Some(body) = parts.extensions.get::<WebhookBody>() {
+        provide_context(body.clone());
+    }
+    if let Some(key) = parts.extensions.get::<ApiKey>() {
+        provide_context(key.clone());
+    }
+    if let Some(language) = parts.extensions.get::<NegotiatedLanguage>() {
+        provide_context(*language);
+    }
+    if let Some(cert) = parts.extensions.get::<ClientCert>() {
+        provide_context(cert.clone());
+    }
+    if let Some(hints) = parts.extensions.get::<ClientHints>() {
+        provide_context(hints.clone());
+    }
+    if let Some(query) = parts.extensions.get::<ValidatedQuery>() {
+        provide_context(query.clone());
+    }
+    if let Some(body) = parts.extensions.get::<ValidatedBody>() {
+        provide_context(body.clone());
+    }
+    if let Some(errors) = parts.extensions.get::<RouteErrors>() {
+        errors.watch();
+    }
+    if let Some(deadline) = RequestDeadline::for_request(
+        parts
+            .headers
+            .get(REQUEST_TIMEOUT_HEADER)
+            .and_then(|value| value.to_str().ok()),
+        parts.extensions.get::<RequestTimeout>().copied(),
+    ) {
+        provide_context(deadline);
+    }
     provide_context(parts);
     provide_context(default_res_options);
     provide_server_redirect(redirect);
@@ -1237,10 +1336,15 @@ where
 /// A route that this application can serve.
 pub struct AxumRouteListing {
     path: String,
+    /// The pattern of the path, as it is written in the route definitions, like
+    /// `/blog/:slug`.
+    pattern: String,
     mode: SsrMode,
     methods: Vec<leptos_router::Method>,
     #[allow(unused)]
     regenerate: Vec<RegenerationFn>,
+    config: RouteConfig,
+    language_redirect: Option<LanguageRedirect>,
     exclude: bool,
 }
 
@@ -1255,6 +1359,7 @@ impl IntoRouteListing for RouteListing {
             .expand_optionals()
             .into_iter()
             .map(|path| {
+                let pattern = segments_to_pattern(&path);
                 let path = path.to_axum_path();
                 let path = if path.is_empty() {
                     "/".to_string()
@@ -1266,9 +1371,12 @@ impl IntoRouteListing for RouteListing {
                 let regenerate = self.regenerate().into();
                 AxumRouteListing {
                     path,
+                    pattern,
                     mode: mode.clone(),
                     methods,
                     regenerate,
+                    config: self.config().clone(),
+                    language_redirect: self.language_redirect().cloned(),
                     exclude: false,
                 }
             })
@@ -1285,14 +1393,33 @@ impl AxumRouteListing {
         regenerate: impl Into<Vec<RegenerationFn>>,
     ) -> Self {
         Self {
+            pattern: path.clone(),
             path,
             mode,
             methods: methods.into_iter().collect(),
             regenerate: regenerate.into(),
+            config: RouteConfig::default(),
+            language_redirect: None,
             exclude: false,
         }
     }
 
+    /// Sets the options the server integrations apply to the requests to this
+    /// route.
+    pub fn with_config(mut self, config: RouteConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets how requests to this route are redirected to a path with a locale.
+    pub fn with_language_redirect(
+        mut self,
+        redirect: Option<LanguageRedirect>,
+    ) -> Self {
+        self.language_redirect = redirect;
+        self
+    }
+
     /// The path this route handles.
     pub fn path(&self) -> &str {
         &self.path
@@ -1305,7 +1432,19 @@ impl AxumRouteListing {
 
     /// The HTTP request methods this path can handle.
     pub fn methods(&self) -> impl Iterator<Item = leptos_router::Method> + '_ {
-        self.methods.iter().copied()
+        self.methods.iter().cloned()
+    }
+
+    /// The options the server integrations apply to the requests to this
+    /// route.
+    pub fn config(&self) -> &RouteConfig {
+        &self.config
+    }
+
+    /// How requests to this route are redirected to a path with a locale, if
+    /// they are.
+    pub fn language_redirect(&self) -> Option<&LanguageRedirect> {
+        self.language_redirect.as_ref()
     }
 }
 
@@ -1375,9 +1514,12 @@ where
             .flatten()
             .map(|path| AxumRouteListing {
                 path,
+                pattern: String::new(),
                 mode: Default::default(),
                 methods: Vec::new(),
                 regenerate: Vec::new(),
+                config: RouteConfig::default(),
+                language_redirect: None,
                 exclude: true,
             });
 
@@ -1576,6 +1718,8 @@ fn handle_static_route<S, IV>(
     additional_context: impl Fn() + 'static + Clone + Send,
     app_fn: impl Fn() -> IV + Clone + Send + 'static,
     regenerate: Vec<RegenerationFn>,
+    incremental: bool,
+    head: HeadInjection,
 ) -> impl Fn(
     State<S>,
     Request<Body>,
@@ -1594,6 +1738,7 @@ where
         let app_fn = app_fn.clone();
         let additional_context = additional_context.clone();
         let regenerate = regenerate.clone();
+        let head = head.clone();
         Box::pin(async move {
             let options = LeptosOptions::from_ref(&state);
             let orig_path = req.uri().path();
@@ -1601,17 +1746,35 @@ where
             let path = Path::new(&path);
             let exists = tokio::fs::try_exists(path).await.unwrap_or(false);
 
-            let (response_options, html) = if !exists {
+            let (response_options, html) = if !exists && !incremental {
+                // this path was not prerendered, and should not be written, so
+                // just render it
+                let (owner, html) = StaticRouteGenerator::render_route(
+                    orig_path.to_string(),
+                    app_fn,
+                    additional_context,
+                )
+                .await;
+                (
+                    owner.with(use_context::<ResponseOptions>),
+                    Some(head.inject(html)),
+                )
+            } else if !exists {
                 let path = ResolvedStaticPath::new(orig_path);
 
                 let (owner, html) = path
                     .build(
                         move |path: &ResolvedStaticPath| {
-                            StaticRouteGenerator::render_route(
+                            let render = StaticRouteGenerator::render_route(
                                 path.to_string(),
                                 app_fn.clone(),
                                 additional_context.clone(),
-                            )
+                            );
+                            let head = head.clone();
+                            async move {
+                                let (owner, html) = render.await;
+                                (owner, head.inject(html))
+                            }
                         },
                         move |path: &ResolvedStaticPath,
                               owner: &Owner,
@@ -1667,6 +1830,14 @@ where
 
 /// This trait allows one to pass a list of routes and a render function to Axum's router, letting us avoid
 /// having to use wildcards or manually define all routes in multiple places.
+///
+/// Every route that handles `GET` also handles `HEAD`, with the response body
+/// removed. Every route that does not handle `OPTIONS` itself responds to
+/// `OPTIONS` with `204 No Content` and an `Allow` header listing its methods.
+/// That response has no `Access-Control-*` headers, so it does not allow CORS
+/// preflight requests; to do that, add a CORS middleware like
+/// [`tower_http::cors::CorsLayer`](https://docs.rs/tower-http/latest/tower_http/cors/struct.CorsLayer.html),
+/// which answers preflight requests before they reach these routes.
 pub trait LeptosRoutes<S>
 where
     S: Clone + Send + Sync + 'static,
@@ -1838,97 +2009,130 @@ where
         // register router paths
         for listing in paths.iter().filter(|p| !p.exclude) {
             let path = listing.path();
-
-            for method in listing.methods() {
+            let cx_with_state_and_method = |method: &leptos_router::Method| {
+                let method = method.clone();
                 let cx_with_state = cx_with_state.clone();
-                let cx_with_state_and_method = move || {
-                    provide_context(method);
+                move || {
+                    provide_context(method.clone());
                     cx_with_state();
-                };
-                router = if matches!(listing.mode(), SsrMode::Static(_)) {
-                    #[cfg(feature = "default")]
-                    {
+                }
+            };
+
+            let proxied = listing
+                .config()
+                .websocket
+                .as_ref()
+                .map(|handler| websocket_router(handler.clone()))
+                .or_else(|| {
+                    listing
+                        .config()
+                        .passthrough
+                        .as_ref()
+                        .cloned()
+                        .map(passthrough_router)
+                })
+                .or_else(|| {
+                    listing.config().batch.as_ref().map(|batch| {
+                        batch_router(batch.clone(), cx_with_state.clone())
+                    })
+                })
+                .or_else(|| {
+                    listing
+                        .config()
+                        .not_found
+                        .as_ref()
+                        .cloned()
+                        .map(not_found_router)
+                });
+            router = if let Some(proxied) = proxied {
+                router.route(
+                    path,
+                    apply_route_layers(proxied, listing, RouteHandler::Proxied),
+                )
+            } else if let Some(file) = static_file_router(listing) {
+                router.route(
+                    path,
+                    apply_route_layers(file, listing, RouteHandler::File),
+                )
+            } else if let SsrMode::Static(route) = listing.mode() {
+                #[cfg(feature = "default")]
+                {
+                    let methods = listing.methods().collect::<Vec<_>>();
+                    let router = router.route(path, options_router(&methods));
+                    methods.into_iter().fold(router, |router, method| {
                         router.route(
                             path,
-                            get(handle_static_route(
-                                cx_with_state_and_method.clone(),
-                                app_fn.clone(),
-                                listing.regenerate.clone(),
-                            )),
+                            apply_route_layers(
+                                get(handle_static_route(
+                                    cx_with_state_and_method(&method),
+                                    app_fn.clone(),
+                                    listing.regenerate.clone(),
+                                    route.is_incremental(),
+                                    listing.config().head.clone(),
+                                )),
+                                listing,
+                                RouteHandler::Static,
+                            ),
                         )
+                    })
+                }
+                #[cfg(not(feature = "default"))]
+                {
+                    _ = route;
+                    panic!(
+                        "Static routes are not currently supported on WASM32 \
+                         server targets."
+                    );
+                }
+            } else {
+                let rendered = match listing.mode() {
+                    SsrMode::OutOfOrder => {
+                        method_router(listing.methods(), |method| {
+                            render_app_to_stream_with_context(
+                                cx_with_state_and_method(method),
+                                app_fn.clone(),
+                            )
+                        })
                     }
-                    #[cfg(not(feature = "default"))]
-                    {
-                        panic!(
-                            "Static routes are not currently supported on \
-                             WASM32 server targets."
-                        );
+                    SsrMode::PartiallyBlocked => {
+                        method_router(listing.methods(), |method| {
+                            render_app_to_stream_with_context_and_replace_blocks(
+                                cx_with_state_and_method(method),
+                                app_fn.clone(),
+                                true,
+                            )
+                        })
                     }
-                } else {
-                    router.route(
-                        path,
-                        match listing.mode() {
-                            SsrMode::OutOfOrder => {
-                                let s = render_app_to_stream_with_context(
-                                    cx_with_state_and_method.clone(),
-                                    app_fn.clone(),
-                                );
-                                match method {
-                                    leptos_router::Method::Get => get(s),
-                                    leptos_router::Method::Post => post(s),
-                                    leptos_router::Method::Put => put(s),
-                                    leptos_router::Method::Delete => delete(s),
-                                    leptos_router::Method::Patch => patch(s),
-                                }
-                            }
-                            SsrMode::PartiallyBlocked => {
-                                let s = render_app_to_stream_with_context_and_replace_blocks(
-                                    cx_with_state_and_method.clone(),
-                                    app_fn.clone(),
-                                    true
-                                );
-                                match method {
-                                    leptos_router::Method::Get => get(s),
-                                    leptos_router::Method::Post => post(s),
-                                    leptos_router::Method::Put => put(s),
-                                    leptos_router::Method::Delete => delete(s),
-                                    leptos_router::Method::Patch => patch(s),
-                                }
-                            }
-                            SsrMode::InOrder => {
-                                let s = render_app_to_stream_in_order_with_context(
-                                    cx_with_state_and_method.clone(),
-                                    app_fn.clone(),
-                                );
-                                match method {
-                                    leptos_router::Method::Get => get(s),
-                                    leptos_router::Method::Post => post(s),
-                                    leptos_router::Method::Put => put(s),
-                                    leptos_router::Method::Delete => delete(s),
-                                    leptos_router::Method::Patch => patch(s),
-                                }
-                            }
-                            SsrMode::Async => {
-                                let s = render_app_async_with_context(
-                                    cx_with_state_and_method.clone(),
-                                    app_fn.clone(),
-                                );
-                                match method {
-                                    leptos_router::Method::Get => get(s),
-                                    leptos_router::Method::Post => post(s),
-                                    leptos_router::Method::Put => put(s),
-                                    leptos_router::Method::Delete => delete(s),
-                                    leptos_router::Method::Patch => patch(s),
-                                }
-                            }
-                            _ => unreachable!()
-                        },
-                    )
+                    SsrMode::InOrder => {
+                        method_router(listing.methods(), |method| {
+                            render_app_to_stream_in_order_with_context(
+                                cx_with_state_and_method(method),
+                                app_fn.clone(),
+                            )
+                        })
+                    }
+                    SsrMode::Async => {
+                        method_router(listing.methods(), |method| {
+                            render_app_async_with_context(
+                                cx_with_state_and_method(method),
+                                app_fn.clone(),
+                            )
+                        })
+                    }
+                    _ => unreachable!(),
                 };
-            }
+                router.route(
+                    path,
+                    apply_route_layers(
+                        rendered,
+                        listing,
+                        RouteHandler::Rendered,
+                    ),
+                )
+            };
         }
 
-        router
+        serve_jwks(&paths, router)
     }
 
     #[cfg_attr(
@@ -1946,25 +2150,1845 @@ where
     {
         let mut router = self;
         for listing in paths.iter().filter(|p| !p.exclude) {
-            for method in listing.methods() {
-                router = router.route(
-                    listing.path(),
-                    match method {
-                        leptos_router::Method::Get => get(handler.clone()),
-                        leptos_router::Method::Post => post(handler.clone()),
-                        leptos_router::Method::Put => put(handler.clone()),
-                        leptos_router::Method::Delete => {
-                            delete(handler.clone())
+            router = router.route(
+                listing.path(),
+                apply_route_layers(
+                    method_router(listing.methods(), |_| handler.clone()),
+                    listing,
+                    RouteHandler::Rendered,
+                ),
+            );
+        }
+        serve_jwks(&paths, router)
+    }
+}
+
+/// Builds a [`MethodRouter`] that upgrades `GET` requests to WebSocket
+/// connections, which are handled by `handler`.
+fn websocket_router<S>(handler: WebSocketHandler) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    #[cfg(feature = "default")]
+    {
+        use axum::extract::ws::{Message, WebSocketUpgrade};
+        use futures::{channel::mpsc, FutureExt};
+
+        get(move |upgrade: WebSocketUpgrade| {
+            let handler = handler.clone();
+            async move {
+                upgrade.on_upgrade(move |mut socket| async move {
+                    let (incoming_tx, incoming_rx) = mpsc::unbounded();
+                    let mut outgoing = handler.handle(incoming_rx);
+                    loop {
+                        futures::select! {
+                            message = socket.recv().fuse() => {
+                                let message = match message {
+                                    Some(Ok(Message::Text(text))) => {
+                                        WebSocketMessage::Text(text.as_str().to_owned())
+                                    }
+                                    Some(Ok(Message::Binary(bytes))) => {
+                                        WebSocketMessage::Binary(bytes.to_vec())
+                                    }
+                                    Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                                    Some(Ok(_)) => continue,
+                                };
+                                if incoming_tx.unbounded_send(message).is_err() {
+                                    break;
+                                }
+                            },
+                            message = outgoing.next().fuse() => {
+                                let message = match message {
+                                    Some(WebSocketMessage::Text(text)) => {
+                                        Message::Text(text.into())
+                                    }
+                                    Some(WebSocketMessage::Binary(bytes)) => {
+                                        Message::Binary(bytes.into())
+                                    }
+                                    None => break,
+                                };
+                                if socket.send(message).await.is_err() {
+                                    break;
+                                }
+                            },
                         }
-                        leptos_router::Method::Patch => patch(handler.clone()),
-                    },
-                );
+                    }
+                    _ = socket.send(Message::Close(None)).await;
+                })
+            }
+        })
+    }
+    #[cfg(not(feature = "default"))]
+    {
+        _ = handler;
+        panic!(
+            "WebSocket routes are not currently supported on WASM32 server \
+             targets."
+        );
+    }
+}
+
+/// Builds a [`MethodRouter`] that forwards requests of every method to the
+/// upstream of `passthrough`, streaming its response back to the client.
+///
+/// The address of the client is only known if the app is served with
+/// `into_make_service_with_connect_info::<SocketAddr>()`.
+fn passthrough_router<S>(passthrough: Passthrough) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    any(move |req: Request<Body>| {
+        let passthrough = passthrough.clone();
+        async move {
+            let client_ip = req
+                .extensions()
+                .get::<ConnectInfo<std::net::SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip());
+            let headers = req
+                .headers()
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect();
+            let request = ProxyRequest {
+                method: req.method().to_string(),
+                path_and_query: req
+                    .uri()
+                    .path_and_query()
+                    .map_or("/", |path| path.as_str())
+                    .to_string(),
+                headers,
+                client_ip,
+                body: req
+                    .into_body()
+                    .into_data_stream()
+                    .map(|chunk| {
+                        chunk.map(|bytes| bytes.to_vec()).map_err(|error| {
+                            ProxyError::Request(error.to_string())
+                        })
+                    })
+                    .boxed(),
+            };
+            match passthrough.forward(request).await {
+                Ok(response) => {
+                    let mut res = Response::builder().status(response.status);
+                    for (name, value) in response.headers {
+                        res = res.header(name, value);
+                    }
+                    res.body(Body::from_stream(response.body)).unwrap_or_else(
+                        |_| StatusCode::BAD_GATEWAY.into_response(),
+                    )
+                }
+                Err(error) => (
+                    StatusCode::from_u16(error.status())
+                        .unwrap_or(StatusCode::BAD_GATEWAY),
+                    error.to_string(),
+                )
+                    .into_response(),
+            }
+        }
+    })
+}
+
+/// Builds a [`MethodRouter`] that responds to requests of every method with
+/// `404 Not Found` and the body of `response`.
+fn not_found_router<S>(response: NotFoundResponse) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    any(move || {
+        let response = response.clone();
+        async move {
+            (
+                StatusCode::NOT_FOUND,
+                [(header::CONTENT_TYPE, response.content_type())],
+                response.body().to_string(),
+            )
+                .into_response()
+        }
+    })
+}
+
+/// Builds a [`MethodRouter`] that serves the file of `listing`, if it is one of
+/// the routes generated for the files of a
+/// [static directory](leptos_router::NestedRoute::static_dir), with support for
+/// conditional and range requests.
+fn static_file_router<S>(listing: &AxumRouteListing) -> Option<MethodRouter<S>>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    #[cfg(feature = "static_dir")]
+    {
+        let file = listing.config().static_file.clone()?;
+        let handler = move |req: Request<Body>| {
+            let header_value = |name| {
+                req.headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string)
+            };
+            let res = file.respond(&StaticAssetRequest {
+                range: header_value(header::RANGE),
+                if_none_match: header_value(header::IF_NONE_MATCH),
+            });
+            async move {
+                let status = StatusCode::from_u16(res.status)
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                let mut builder = Response::builder().status(status);
+                for (name, value) in res.headers {
+                    builder = builder.header(name, value);
+                }
+                builder
+                    .body(Body::from(res.body))
+                    .unwrap_or_else(|_| status.into_response())
+            }
+        };
+        Some(method_router(listing.methods(), |_| handler.clone()))
+    }
+    #[cfg(not(feature = "static_dir"))]
+    {
+        _ = listing;
+        None
+    }
+}
+
+/// Builds a [`MethodRouter`] that runs the requests in the body of each `POST`
+/// as a batch, by calling the server function at the path of each of them.
+///
+/// Each request has the headers of the batch, like its cookies, and a JSON
+/// body, so the server functions it calls should take their arguments as JSON,
+/// or in the query of a `GET`.
+fn batch_router<S>(
+    batch: Batch,
+    additional_context: impl Fn() + Clone + Send + Sync + 'static,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    post(move |req: Request<Body>| {
+        let batch = batch.clone();
+        let additional_context = additional_context.clone();
+        async move {
+            let (parts, body) = req.into_parts();
+            let body = match axum::body::to_bytes(body, usize::MAX).await {
+                Ok(body) => body,
+                Err(error) => {
+                    return (StatusCode::BAD_REQUEST, error.to_string())
+                        .into_response()
+                }
+            };
+            let executor = move |request: BatchRequest| {
+                let mut req = Request::builder()
+                    .method(request.method.as_str())
+                    .uri(&request.path);
+                for (name, value) in &parts.headers {
+                    if name != header::CONTENT_TYPE
+                        && name != header::CONTENT_LENGTH
+                    {
+                        req = req.header(name, value);
+                    }
+                }
+                let req = req
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(
+                        request
+                            .body
+                            .map(|body| body.to_string())
+                            .unwrap_or_default(),
+                    ));
+                let additional_context = additional_context.clone();
+                async move {
+                    let req = match req {
+                        Ok(req) => req,
+                        Err(error) => {
+                            return BatchResponse::from_body(
+                                400,
+                                error.to_string().as_bytes(),
+                            )
+                        }
+                    };
+                    let res = handle_server_fns_inner(additional_context, req)
+                        .await
+                        .into_response();
+                    let status = res.status().as_u16();
+                    let body =
+                        axum::body::to_bytes(res.into_body(), usize::MAX)
+                            .await
+                            .unwrap_or_default();
+                    BatchResponse::from_body(status, &body)
+                }
+            };
+            match batch.execute(&body, &executor).await {
+                Ok(responses) => (
+                    [(header::CONTENT_TYPE, "application/json")],
+                    responses.to_string(),
+                )
+                    .into_response(),
+                Err(error) => (
+                    StatusCode::from_u16(error.status_code())
+                        .unwrap_or(StatusCode::BAD_REQUEST),
+                    error.to_string(),
+                )
+                    .into_response(),
+            }
+        }
+    })
+}
+
+/// Builds a [`MethodRouter`] that routes each of the given methods to the
+/// handler created for it.
+///
+/// Axum can only route the standard methods directly, so custom methods are
+/// handled by a fallback that compares the raw method of the request with each
+/// custom method's name.
+///
+/// Axum already answers `HEAD` requests with the `GET` handler; `OPTIONS`
+/// requests are answered by [`options_router`] unless one of the methods is
+/// `OPTIONS`.
+fn method_router<S, H, T>(
+    methods: impl IntoIterator<Item = leptos_router::Method>,
+    handler_for: impl Fn(&leptos_router::Method) -> H,
+) -> MethodRouter<S>
+where
+    H: axum::handler::Handler<T, S>,
+    T: 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    let methods = methods.into_iter().collect::<Vec<_>>();
+    let allow = leptos_router::allow_header(&methods);
+    let mut router = options_router(&methods);
+    let mut custom = Vec::new();
+    for method in methods {
+        let handler = handler_for(&method);
+        router = match method {
+            leptos_router::Method::Get => router.get(handler),
+            leptos_router::Method::Post => router.post(handler),
+            leptos_router::Method::Put => router.put(handler),
+            leptos_router::Method::Delete => router.delete(handler),
+            leptos_router::Method::Patch => router.patch(handler),
+            leptos_router::Method::Custom(name) => {
+                custom.push((name, handler));
+                router
             }
+        };
+    }
+
+    if !custom.is_empty() {
+        let custom = Arc::new(custom);
+        router = router.fallback(
+            move |State(state): State<S>, req: Request<Body>| {
+                let handler = custom
+                    .iter()
+                    .find(|(name, _)| name == req.method().as_str())
+                    .map(|(_, handler)| handler.clone());
+                let allow = allow.clone();
+                async move {
+                    match handler {
+                        Some(handler) => handler.call(req, state).await,
+                        None => (
+                            StatusCode::METHOD_NOT_ALLOWED,
+                            [(header::ALLOW, allow)],
+                        )
+                            .into_response(),
+                    }
+                }
+            },
+        );
+    }
+
+    router
+}
+
+/// Builds a [`MethodRouter`] that responds to `OPTIONS` requests with
+/// `204 No Content` and an `Allow` header listing `methods`, unless `OPTIONS`
+/// is one of them.
+///
+/// This is not a CORS preflight response: it does not include any
+/// `Access-Control-*` headers. To allow cross-origin requests, add a CORS layer
+/// (like `tower_http::cors::CorsLayer`), which answers preflight requests
+/// before they reach this handler.
+fn options_router<S>(methods: &[leptos_router::Method]) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    if methods.contains(&leptos_router::Method::from_name("OPTIONS")) {
+        return MethodRouter::new();
+    }
+    let allow = leptos_router::allow_header(methods);
+    MethodRouter::new().options(move || async move {
+        (StatusCode::NO_CONTENT, [(header::ALLOW, allow)])
+    })
+}
+
+/// What the [`MethodRouter`] of a route does with its requests, which decides
+/// which of the layers of [`apply_route_layers`] it gets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RouteHandler {
+    /// Renders the app.
+    Rendered,
+    /// Serves the prerendered page of a static route.
+    Static,
+    /// Serves a file of a static directory.
+    File,
+    /// Hands requests to a WebSocket, passthrough, batch or `not_found`
+    /// handler.
+    Proxied,
+}
+
+/// Wraps `router` in the layers for everything `listing` sets up for its route,
+/// which `handler` says how `router` handles.
+///
+/// Each layer wraps the ones before it, so the last one runs first.
+fn apply_route_layers<S>(
+    mut router: MethodRouter<S>,
+    listing: &AxumRouteListing,
+    handler: RouteHandler,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    // The responses of proxied routes do not come from the app and may be
+    // upgraded WebSocket connections or streamed from upstream, so they are
+    // passed on as they are, rather than buffered to be shared, rewritten or
+    // checked, or given the status of the route.
+    if handler != RouteHandler::Proxied {
+        router = with_route_status(listing.config().status, router);
+        router = wrap_response_envelope(
+            listing.config().response_envelope.as_ref(),
+            router,
+        );
+        router =
+            format_route_errors(listing.config().error_format.as_ref(), router);
+        router = validate_response_body(
+            listing.config().response_schemas.get(&200),
+            router,
+        );
+        router = minify_html_responses(listing.config().minify_html, router);
+        // static routes inject the head content into their pages before they
+        // are written to disk, so it would be injected twice
+        if handler == RouteHandler::Rendered {
+            router = inject_head_content(&listing.config().head, router);
+        }
+        router = deduplicate_requests(
+            listing.config().deduplication.as_ref(),
+            router,
+        );
+    }
+    router = verify_webhook_signature(
+        listing.config().webhook_signature.as_ref(),
+        router,
+    );
+    router = check_guards(&listing.config().guards, router);
+    router = sign_responses(listing.config().response_signing.as_ref(), router);
+    router = verify_body(listing.config().body_schemas.as_ref(), router);
+    router = verify_content_type(listing.config().consumes.as_ref(), router);
+    router = verify_query(listing.config().query_schema.as_ref(), router);
+    router = verify_api_key(listing.config().api_key.as_ref(), router);
+    router = negotiate_language(listing.config().language_negotiation, router);
+    router = verify_client_cert(listing.config().client_cert.as_ref(), router);
+    router = mark_access_log_exclusion(
+        listing.config().exclude_from_access_log.unwrap_or(false),
+        router,
+    );
+    router = set_sticky_session(listing.config().sticky_session, router);
+    router = set_x_robots_tag(&listing.config().x_robots_tag, router);
+    router =
+        set_surrogate_keys(listing.config().surrogate_keys.as_ref(), router);
+    router = set_preload_links(&listing.config().preload, router);
+    router = request_client_hints(&listing.config().client_hints, router);
+    router = redirect_to_language(listing.language_redirect(), router);
+    router =
+        apply_route_middleware(listing.config().middleware.as_ref(), router);
+    router = warn_on_slow_requests(
+        listing.config().slow_request,
+        &listing.pattern,
+        router,
+    );
+    reject_after_shutdown(listing.config().shutdown.as_ref(), router)
+}
+
+/// Measures a request to the route with the path `pattern`, and warns, as set
+/// by `config`, if it was slow when it is dropped, once its response body has
+/// been sent.
+struct SlowRequestTimer {
+    config: SlowRequestConfig,
+    pattern: Arc<str>,
+    path: String,
+    params: Vec<String>,
+    start: Instant,
+}
+
+impl Drop for SlowRequestTimer {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        if !self.config.should_warn(elapsed) {
+            return;
         }
-        router
+        let bucket = SlowRequestConfig::histogram_bucket(elapsed);
+        let msg = format!(
+            "Slow request to route {}: {} [{}] took {elapsed:?}, more than \
+             {:?} (histogram bucket le=\"{bucket}\")",
+            self.pattern,
+            self.path,
+            self.params.join(", "),
+            self.config.threshold
+        );
+
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            route = %self.pattern,
+            path = %self.path,
+            params = ?self.params,
+            elapsed_ms = elapsed.as_millis() as u64,
+            threshold_ms = self.config.threshold.as_millis() as u64,
+            histogram_bucket = bucket,
+            "{}",
+            &msg
+        );
+
+        #[cfg(not(feature = "tracing"))]
+        eprintln!("{}", &msg);
     }
 }
 
+/// Warns about the requests to `router`, the route with the path `pattern`,
+/// that are slower than the threshold of `config`, if there is one, until their
+/// response bodies have been sent.
+fn warn_on_slow_requests<S>(
+    config: Option<SlowRequestConfig>,
+    pattern: &str,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(config) = config else {
+        return router;
+    };
+    let pattern = Arc::<str>::from(pattern);
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| {
+            let pattern = Arc::clone(&pattern);
+            async move {
+                let start = Instant::now();
+                let (mut parts, body) = req.into_parts();
+                let params = RawPathParams::from_request_parts(&mut parts, &())
+                    .await
+                    .map(|params| {
+                        params
+                            .iter()
+                            .map(|(key, value)| format!("{key}={value}"))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let timer = SlowRequestTimer {
+                    config,
+                    pattern,
+                    path: parts.uri.path().to_string(),
+                    params,
+                    start,
+                };
+                next.run(Request::from_parts(parts, body))
+                    .await
+                    .map(|body| {
+                        Body::from_stream(body.into_data_stream().map(
+                            move |chunk| {
+                                let _ = &timer;
+                                chunk
+                            },
+                        ))
+                    })
+            }
+        },
+    ))
+}
+
+/// Answers requests to `router` with `503 Service Unavailable` once `shutdown`
+/// is set, if there is one, as set with `NestedRoute::graceful_shutdown`, and
+/// otherwise counts them as in flight until their response bodies have been
+/// sent, for [`drain_routes`].
+fn reject_after_shutdown<S>(
+    shutdown: Option<&ShutdownSignal>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(shutdown) = shutdown.cloned() else {
+        return router;
+    };
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| {
+            let shutdown = shutdown.clone();
+            async move {
+                if shutdown.is_shutting_down() {
+                    return (
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        [(header::CONNECTION, "close")],
+                    )
+                        .into_response();
+                }
+                let in_flight = shutdown.track_request();
+                next.run(req).await.map(|body| {
+                    Body::from_stream(body.into_data_stream().map(
+                        move |chunk| {
+                            let _ = &in_flight;
+                            chunk
+                        },
+                    ))
+                })
+            }
+        },
+    ))
+}
+
+/// Runs every request to `router` through the layers of `middleware` for axum,
+/// if there are any, as set with `NestedRoute::middleware`.
+fn apply_route_middleware<S>(
+    middleware: Option<&RouteMiddleware>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(middleware) = middleware
+        .filter(|middleware| {
+            middleware.layers::<Request<Body>, Response<Body>>().count() > 0
+        })
+        .cloned()
+    else {
+        return router;
+    };
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| {
+            let mut service = middleware.apply(BoxedService::new(
+                |error: ServerFnErrorErr| Bytes::from(error.to_string()),
+                next,
+            ));
+            async move { service.run(req).await }
+        },
+    ))
+}
+
+/// Waits for the requests to the routes that use `shutdown` to complete, or for
+/// `timeout` to pass, whichever is first, once it has been set, so that the
+/// server can exit without cutting off any response in the middle.
+///
+/// A request is in flight from when one of the routes accepts it until its
+/// response body has been sent in full, so streamed responses are waited for to
+/// the end.
+#[cfg(feature = "default")]
+pub fn drain_routes(
+    shutdown: &ShutdownSignal,
+    timeout: Duration,
+) -> BoxFuture<'static, ()> {
+    let shutdown = shutdown.clone();
+    Box::pin(async move {
+        _ = tokio::time::timeout(timeout, shutdown.drained()).await;
+    })
+}
+
+/// Redirects every request to `router` to the same path with a locale, as
+/// chosen by `redirect`, if there is one, which is the case for the path
+/// without a locale of a route set with `NestedRoute::language_redirect`.
+fn redirect_to_language<S>(
+    redirect: Option<&LanguageRedirect>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(redirect) = redirect.cloned() else {
+        return router;
+    };
+    let status = StatusCode::from_u16(redirect.config.status())
+        .expect("invalid language redirect status");
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, _next: Next| {
+            let header_value = |name| {
+                req.headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+            };
+            let (location, set_cookie) = redirect.redirect(
+                req.uri().path(),
+                req.uri().query(),
+                header_value(header::COOKIE),
+                header_value(header::ACCEPT_LANGUAGE),
+            );
+            async move {
+                let mut res = Response::builder()
+                    .status(status)
+                    .header(header::LOCATION, location)
+                    .header(header::VARY, "Accept-Language, Cookie");
+                if let Some(cookie) = set_cookie {
+                    res = res.header(header::SET_COOKIE, cookie);
+                }
+                res.body(Body::empty())
+                    .unwrap_or_else(|_| status.into_response())
+            }
+        },
+    ))
+}
+
+/// Asks browsers for `hints` with the `Accept-CH` and `Critical-CH` headers of
+/// every response from `router`, if there are any, and stores the hints a
+/// request was sent with as [`ClientHints`] in its extensions (and in the
+/// context of the rendered route).
+fn request_client_hints<S>(
+    hints: &[ClientHint],
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(value) = accept_ch_header(hints) else {
+        return router;
+    };
+    let value = HeaderValue::from_str(&value)
+        .unwrap_or_else(|_| panic!("invalid Accept-CH header: {value}"));
+    router.layer(axum::middleware::from_fn(
+        move |mut req: Request<Body>, next: Next| {
+            let value = value.clone();
+            async move {
+                let hints = ClientHints::from_headers(|name| {
+                    req.headers().get(name)?.to_str().ok()
+                });
+                req.extensions_mut().insert(hints);
+                let mut res = next.run(req).await;
+                let headers = res.headers_mut();
+                headers.insert(
+                    HeaderName::from_static("accept-ch"),
+                    value.clone(),
+                );
+                headers.insert(HeaderName::from_static("critical-ch"), value);
+                res
+            }
+        },
+    ))
+}
+
+/// Preloads `assets` with a `Link` header on every response from `router`, if
+/// there are any.
+fn set_preload_links<S>(
+    assets: &[PreloadLink],
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(value) = preload_link_header(assets) else {
+        return router;
+    };
+    let value = HeaderValue::from_str(&value)
+        .unwrap_or_else(|_| panic!("invalid Link header: {value}"));
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| {
+            let value = value.clone();
+            async move {
+                let mut res = next.run(req).await;
+                res.headers_mut().append(header::LINK, value);
+                res
+            }
+        },
+    ))
+}
+
+/// Sets the `X-Robots-Tag` header of every response from `router` to
+/// `directives`, if there are any.
+fn set_x_robots_tag<S>(
+    directives: &[XRobotsDirective],
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(value) = x_robots_tag_header(directives) else {
+        return router;
+    };
+    let value = HeaderValue::from_str(&value)
+        .unwrap_or_else(|_| panic!("invalid X-Robots-Tag header: {value}"));
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| {
+            let value = value.clone();
+            async move {
+                let mut res = next.run(req).await;
+                res.headers_mut()
+                    .insert(HeaderName::from_static("x-robots-tag"), value);
+                res
+            }
+        },
+    ))
+}
+
+/// Checks every request to `router` against `guards`, the
+/// [guards](leptos_router::NestedRoute::guard) of its route, with the path
+/// params of the request, before it is handled.
+///
+/// A request that a guard redirects is answered with the redirect, and one that
+/// a guard forbids with `403 Forbidden`.
+fn check_guards<S>(
+    guards: &[RouteGuard],
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    if guards.is_empty() {
+        return router;
+    }
+    let guards = Arc::<[RouteGuard]>::from(guards);
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| {
+            let guards = Arc::clone(&guards);
+            async move {
+                let (mut parts, body) = req.into_parts();
+                let params = RawPathParams::from_request_parts(&mut parts, &())
+                    .await
+                    .map(|params| {
+                        params
+                            .iter()
+                            .map(|(key, value)| (key.to_string(), value))
+                            .collect::<ParamsMap>()
+                    })
+                    .unwrap_or_default();
+                match RouteGuard::check_all(&guards, &params).await {
+                    GuardResult::Allow => {
+                        next.run(Request::from_parts(parts, body)).await
+                    }
+                    GuardResult::Redirect(redirect) => {
+                        let status = StatusCode::from_u16(redirect.status)
+                            .unwrap_or(StatusCode::FOUND);
+                        Response::builder()
+                            .status(status)
+                            .header(header::LOCATION, redirect.path)
+                            .body(Body::empty())
+                            .unwrap_or_else(|_| status.into_response())
+                    }
+                    GuardResult::Forbid => {
+                        StatusCode::FORBIDDEN.into_response()
+                    }
+                }
+            }
+        },
+    ))
+}
+
+/// Sets the `Surrogate-Key` and `Cache-Tag` headers of every response from
+/// `router` to the surrogate keys of `keys`, for the path params of its
+/// request, if there are any.
+fn set_surrogate_keys<S>(
+    keys: Option<&SurrogateKeys>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(keys) = keys.cloned() else {
+        return router;
+    };
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| {
+            let keys = keys.clone();
+            async move {
+                let (mut parts, body) = req.into_parts();
+                let params = RawPathParams::from_request_parts(&mut parts, &())
+                    .await
+                    .map(|params| {
+                        params
+                            .iter()
+                            .map(|(key, value)| (key.to_string(), value))
+                            .collect::<ParamsMap>()
+                    })
+                    .unwrap_or_default();
+                let headers = keys.headers(&params);
+                let mut res = next.run(Request::from_parts(parts, body)).await;
+                for (name, value) in headers {
+                    if let Ok(value) = HeaderValue::from_str(&value) {
+                        res.headers_mut()
+                            .insert(HeaderName::from_static(name), value);
+                    }
+                }
+                res
+            }
+        },
+    ))
+}
+
+/// Identifies this server instance with the cookie and header of `policy`, if
+/// there is one, in the responses from `router` to clients that do not send the
+/// cookie yet.
+fn set_sticky_session<S>(
+    policy: Option<StickySessionPolicy>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(policy) = policy else {
+        return router;
+    };
+    let header_name =
+        HeaderName::try_from(policy.header).unwrap_or_else(|_| {
+            panic!("invalid sticky session header name: {}", policy.header)
+        });
+    let instance_id = HeaderValue::from_str(StickySessionPolicy::instance_id())
+        .expect("invalid sticky session instance ID");
+    let cookie = HeaderValue::from_str(&policy.set_cookie())
+        .expect("invalid sticky session cookie");
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| {
+            let (header_name, instance_id, cookie) =
+                (header_name.clone(), instance_id.clone(), cookie.clone());
+            async move {
+                let first_contact = !req
+                    .headers()
+                    .get_all(header::COOKIE)
+                    .iter()
+                    .filter_map(|value| value.to_str().ok())
+                    .any(|cookies| policy.is_set_in(cookies));
+                let mut res = next.run(req).await;
+                if first_contact {
+                    res.headers_mut().append(header::SET_COOKIE, cookie);
+                    res.headers_mut().insert(header_name, instance_id);
+                }
+                res
+            }
+        },
+    ))
+}
+
+/// Marks every response from `router` with [`ExcludeFromAccessLog`], if it is
+/// `excluded`.
+fn mark_access_log_exclusion<S>(
+    excluded: bool,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    if !excluded {
+        return router;
+    }
+    router.layer(axum::middleware::from_fn(
+        |req: Request<Body>, next: Next| async move {
+            let mut res = next.run(req).await;
+            res.extensions_mut().insert(ExcludeFromAccessLog);
+            res
+        },
+    ))
+}
+
+/// Responds to every request to `router` with `status`, if there is one,
+/// whatever status its handler set.
+///
+/// Preflight `OPTIONS` requests are still answered as usual.
+fn with_route_status<S>(
+    status: Option<u16>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(status) = status else {
+        return router;
+    };
+    let status = StatusCode::from_u16(status)
+        .unwrap_or_else(|_| panic!("invalid route status code: {status}"));
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| async move {
+            let preflight = req.method() == Method::OPTIONS;
+            let mut res = next.run(req).await;
+            if !preflight {
+                *res.status_mut() = status;
+            }
+            res
+        },
+    ))
+}
+
+/// Wraps the JSON responses of `router` in the envelope of `config`, if there
+/// is one.
+///
+/// Responses that are not JSON, like the HTML of a page, whose bodies do not
+/// parse as JSON, or that are larger than the [`BUFFERED_BODY_LIMIT`], are left
+/// as they are.
+fn wrap_response_envelope<S>(
+    config: Option<&EnvelopeConfig>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(config) = config.cloned() else {
+        return router;
+    };
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| {
+            let config = config.clone();
+            async move {
+                let path = req.uri().path().to_string();
+                let query = req.uri().query().map(str::to_owned);
+                let headers = req
+                    .headers()
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        Some((
+                            name.to_string(),
+                            value.to_str().ok()?.to_owned(),
+                        ))
+                    })
+                    .collect::<Vec<_>>();
+                let res = next.run(req).await;
+                let is_json = res
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|mime| mime.split(';').next())
+                    .is_some_and(|mime| {
+                        let mime = mime.trim();
+                        mime == "application/json" || mime.ends_with("+json")
+                    });
+                if !is_json {
+                    return res;
+                }
+                let (mut parts, body) = res.into_parts();
+                let body = match buffer_body(
+                    body.into_data_stream(),
+                    BUFFERED_BODY_LIMIT,
+                )
+                .await
+                {
+                    Ok(BufferedBody::Whole(body)) => body,
+                    Ok(BufferedBody::TooLarge(read, rest)) => {
+                        let body =
+                            futures::stream::iter(read.into_iter().map(Ok))
+                                .chain(rest);
+                        return Response::from_parts(
+                            parts,
+                            Body::from_stream(body),
+                        );
+                    }
+                    Err(error) => {
+                        return (
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            error.to_string(),
+                        )
+                            .into_response()
+                    }
+                };
+                let Ok(data) =
+                    serde_json::from_slice::<serde_json::Value>(&body)
+                else {
+                    return Response::from_parts(parts, Body::from(body));
+                };
+                let headers = headers
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_str()))
+                    .collect::<Vec<_>>();
+                let mut match_req = MatchRequest::new(&path).headers(&headers);
+                if let Some(query) = &query {
+                    match_req = match_req.query(query);
+                }
+                if let Some(host) = match_req.header("host") {
+                    match_req = match_req.host(host);
+                }
+                let request_id = match_req.header(REQUEST_ID_HEADER);
+                let wrapped = config.wrap(
+                    data,
+                    &match_req,
+                    request_id,
+                    SystemTime::now(),
+                );
+                parts.headers.remove(header::CONTENT_LENGTH);
+                Response::from_parts(parts, Body::from(wrapped.to_string()))
+            }
+        },
+    ))
+}
+
+/// The errors caught by the `<ErrorBoundary/>`s of the app while a route with
+/// an [`ErrorFormat`] is rendered, which are registered with the shared context
+/// of its response.
+#[derive(Clone, Default)]
+struct RouteErrors(
+    Arc<parking_lot::Mutex<Option<Arc<dyn SharedContext + Send + Sync>>>>,
+);
+
+impl RouteErrors {
+    /// Collects the errors of the shared context of the current owner.
+    fn watch(&self) {
+        *self.0.lock() = Owner::current_shared_context();
+    }
+
+    fn take(&self) -> Vec<leptos::error::Error> {
+        self.0
+            .lock()
+            .as_ref()
+            .map(|sc| {
+                sc.take_errors()
+                    .into_iter()
+                    .map(|(_, _, error)| error)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Responds with the errors caught while `router` renders a route, encoded with
+/// `format`, instead of the page, if the format is not HTML and there are any.
+///
+/// The body of the response has to be buffered whole, as errors can be caught
+/// until its last chunk is rendered.
+fn format_route_errors<S>(
+    format: Option<&ErrorFormat>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(format) = format.filter(|format| !format.is_html()).cloned()
+    else {
+        return router;
+    };
+    router.layer(axum::middleware::from_fn(
+        move |mut req: Request<Body>, next: Next| {
+            let format = format.clone();
+            async move {
+                let errors = RouteErrors::default();
+                req.extensions_mut().insert(errors.clone());
+                let (parts, body) = next.run(req).await.into_parts();
+                let body = match axum::body::to_bytes(body, usize::MAX).await {
+                    Ok(body) => body,
+                    Err(error) => {
+                        return (
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            error.to_string(),
+                        )
+                            .into_response()
+                    }
+                };
+                let caught = errors.take();
+                if caught.is_empty() {
+                    return Response::from_parts(parts, Body::from(body));
+                }
+                let status = if parts.status.is_client_error()
+                    || parts.status.is_server_error()
+                {
+                    parts.status
+                } else {
+                    StatusCode::INTERNAL_SERVER_ERROR
+                };
+                let error = RouteError::new(status.as_u16(), caught);
+                match format.format(&error) {
+                    Some((error_body, mime)) => {
+                        (status, [(header::CONTENT_TYPE, mime)], error_body)
+                            .into_response()
+                    }
+                    None => Response::from_parts(parts, Body::from(body)),
+                }
+            }
+        },
+    ))
+}
+
+/// Rejects requests to `router` that are not signed according to `config`, if
+/// there is one.
+///
+/// The body has to be read in full to validate it, so it is stored as a
+/// [`WebhookBody`] in the request extensions (and provided via context by the
+/// Leptos handlers) for the handler to use.
+fn verify_webhook_signature<S>(
+    config: Option<&WebhookSignatureConfig>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(config) = config.cloned() else {
+        return router;
+    };
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| {
+            let config = config.clone();
+            async move {
+                let (parts, body) = req.into_parts();
+                let signature = parts
+                    .headers
+                    .get(config.header)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_owned);
+                let body = match Bytes::from_request(
+                    Request::from_parts(parts.clone(), body),
+                    &(),
+                )
+                .await
+                {
+                    Ok(body) => body,
+                    Err(rejection) => return rejection.into_response(),
+                };
+
+                let valid = signature
+                    .is_some_and(|signature| config.verify(&body, &signature));
+                if !valid {
+                    return StatusCode::UNAUTHORIZED.into_response();
+                }
+
+                let mut req =
+                    Request::from_parts(parts, Body::from(body.clone()));
+                req.extensions_mut().insert(WebhookBody::new(body.to_vec()));
+                next.run(req).await
+            }
+        },
+    ))
+}
+
+/// Inserts `head` into the `<head>` of the HTML responses of `router`, if it is
+/// not empty, as they are streamed.
+fn inject_head_content<S>(
+    head: &HeadInjection,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    if head.is_empty() {
+        return router;
+    }
+    let head = head.clone();
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| {
+            let head = head.clone();
+            async move {
+                let res = next.run(req).await;
+                let is_html = res
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|value| value.starts_with("text/html"));
+                if !is_html {
+                    return res;
+                }
+                let (mut parts, body) = res.into_parts();
+                parts.headers.remove(header::CONTENT_LENGTH);
+                let injector =
+                    Arc::new(parking_lot::Mutex::new(head.injector()));
+                let body = body
+                    .into_data_stream()
+                    .map({
+                        let injector = Arc::clone(&injector);
+                        move |chunk| {
+                            chunk.map(|chunk| {
+                                Bytes::from(injector.lock().push(&chunk))
+                            })
+                        }
+                    })
+                    .chain(once(async move {
+                        Ok(Bytes::from(injector.lock().finish()))
+                    }));
+                Response::from_parts(parts, Body::from_stream(body))
+            }
+        },
+    ))
+}
+
+/// Minifies the HTML responses of `router` with `config`, if there is one, a
+/// chunk at a time as they are streamed.
+fn minify_html_responses<S>(
+    config: Option<HtmlMinifyConfig>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    #[cfg(feature = "minify_html")]
+    if let Some(config) = config {
+        return router.layer(axum::middleware::from_fn(
+            move |req: Request<Body>, next: Next| async move {
+                let res = next.run(req).await;
+                let is_html = res
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|value| value.starts_with("text/html"));
+                if !is_html {
+                    return res;
+                }
+                let (mut parts, body) = res.into_parts();
+                parts.headers.remove(header::CONTENT_LENGTH);
+                let minifier = Arc::new(parking_lot::Mutex::new(
+                    HtmlMinifier::new(config),
+                ));
+                let body = body
+                    .into_data_stream()
+                    .map({
+                        let minifier = Arc::clone(&minifier);
+                        move |chunk| {
+                            chunk.map(|chunk| {
+                                Bytes::from(minifier.lock().push(&chunk))
+                            })
+                        }
+                    })
+                    .chain(once(async move {
+                        Ok(Bytes::from(minifier.lock().finish()))
+                    }));
+                Response::from_parts(parts, Body::from_stream(body))
+            },
+        ));
+    }
+    _ = config;
+    router
+}
+
+/// Checks the JSON bodies of the `200 OK` responses of `router` against
+/// `schema`, if there is one, with the `response_schema` feature, and logs each
+/// way in which they do not match it.
+///
+/// The body of a checked response has to be buffered whole, but it is sent on
+/// as it is, whether or not it matches. Bodies larger than the
+/// [`BUFFERED_BODY_LIMIT`] are not checked.
+fn validate_response_body<S>(
+    schema: Option<&serde_json::Value>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    #[cfg(feature = "response_schema")]
+    if let Some(schema) = schema.cloned() {
+        return router.layer(axum::middleware::from_fn(
+            move |req: Request<Body>, next: Next| {
+                let schema = schema.clone();
+                async move {
+                    let path = req.uri().path().to_owned();
+                    let res = next.run(req).await;
+                    let is_json = res
+                        .headers()
+                        .get(header::CONTENT_TYPE)
+                        .and_then(|value| value.to_str().ok())
+                        .is_some_and(|value| {
+                            value.starts_with("application/json")
+                        });
+                    if res.status() != StatusCode::OK || !is_json {
+                        return res;
+                    }
+                    let (parts, body) = res.into_parts();
+                    let body = match buffer_body(
+                        body.into_data_stream(),
+                        BUFFERED_BODY_LIMIT,
+                    )
+                    .await
+                    {
+                        Ok(BufferedBody::Whole(body)) => body,
+                        Ok(BufferedBody::TooLarge(read, rest)) => {
+                            let body =
+                                futures::stream::iter(read.into_iter().map(Ok))
+                                    .chain(rest);
+                            return Response::from_parts(
+                                parts,
+                                Body::from_stream(body),
+                            );
+                        }
+                        Err(e) => {
+                            return (
+                                StatusCode::INTERNAL_SERVER_ERROR,
+                                e.to_string(),
+                            )
+                                .into_response()
+                        }
+                    };
+                    let mismatches = match serde_json::from_slice(&body) {
+                        Ok(value) => leptos_router::validate_response_schema(
+                            &schema, &value,
+                        )
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                        Err(e) => vec![format!("the body is not JSON: {e}")],
+                    };
+                    for mismatch in mismatches {
+                        let msg = format!(
+                            "The response to {path} does not match its schema \
+                             {mismatch}"
+                        );
+
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!("{}", &msg);
+
+                        #[cfg(not(feature = "tracing"))]
+                        eprintln!("{}", &msg);
+                    }
+                    Response::from_parts(parts, Body::from(body))
+                }
+            },
+        ));
+    }
+    _ = schema;
+    router
+}
+
+/// Signs the body of each response of `router` according to `config`, if there
+/// is one, in the [`SIGNATURE_HEADER`](leptos_router::SIGNATURE_HEADER).
+///
+/// The body is buffered whole to sign it. A response that cannot be signed is
+/// replaced with `500 Internal Server Error`, rather than being sent without
+/// its signature.
+fn sign_responses<S>(
+    config: Option<&ResponseSigningConfig>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    #[cfg(feature = "response_signing")]
+    if let Some(config) = config.cloned() {
+        return router.layer(axum::middleware::from_fn(
+            move |req: Request<Body>, next: Next| {
+                let config = config.clone();
+                async move {
+                    let res = next.run(req).await;
+                    let (mut parts, body) = res.into_parts();
+                    let body =
+                        match axum::body::to_bytes(body, usize::MAX).await {
+                            Ok(body) => body,
+                            Err(e) => {
+                                return (
+                                    StatusCode::INTERNAL_SERVER_ERROR,
+                                    e.to_string(),
+                                )
+                                    .into_response()
+                            }
+                        };
+                    let signature = config
+                        .sign(&body)
+                        .map_err(|e| e.to_string())
+                        .and_then(|signature| {
+                            HeaderValue::try_from(signature)
+                                .map_err(|e| e.to_string())
+                        });
+                    match signature {
+                        Ok(signature) => {
+                            parts.headers.insert(
+                                leptos_router::SIGNATURE_HEADER,
+                                signature,
+                            );
+                            Response::from_parts(parts, Body::from(body))
+                        }
+                        Err(e) => {
+                            #[cfg(feature = "tracing")]
+                            tracing::error!("{}", &e);
+
+                            #[cfg(not(feature = "tracing"))]
+                            eprintln!("{}", &e);
+
+                            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                        }
+                    }
+                }
+            },
+        ));
+    }
+    _ = config;
+    router
+}
+
+/// Serves the public keys of the routes in `paths` whose responses are signed
+/// with RSA or ECDSA, if there are any, as a JSON Web Key Set at the
+/// [`JWKS_PATH`](leptos_router::JWKS_PATH).
+fn serve_jwks<S>(
+    paths: &[AxumRouteListing],
+    router: axum::Router<S>,
+) -> axum::Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    #[cfg(feature = "response_signing")]
+    {
+        let configs = paths
+            .iter()
+            .filter_map(|listing| listing.config().response_signing.as_ref())
+            .filter(|config| config.algorithm.is_asymmetric())
+            .collect::<Vec<_>>();
+        if !configs.is_empty() {
+            let jwks = leptos_router::jwks(configs).to_string();
+            return router.route(
+                leptos_router::JWKS_PATH,
+                get(move || {
+                    let jwks = jwks.clone();
+                    async move {
+                        ([(header::CONTENT_TYPE, "application/json")], jwks)
+                    }
+                }),
+            );
+        }
+    }
+    _ = paths;
+    router
+}
+
+/// Makes concurrent identical `GET` requests to `router` share the response of
+/// the first of them, if `config` is set.
+///
+/// The response of the first request is buffered whole, unless it is larger
+/// than the body limit of `config`. It is only shared if it succeeded and was
+/// buffered; otherwise, the requests that waited on it are handled on their
+/// own.
+fn deduplicate_requests<S>(
+    config: Option<&DeduplicationConfig>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(config) = config.cloned() else {
+        return router;
+    };
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| {
+            let config = config.clone();
+            async move {
+                if req.method() != Method::GET {
+                    return next.run(req).await;
+                }
+                let headers = req.headers().clone();
+                let header_value = |name| {
+                    headers.get(name).and_then(|value| value.to_str().ok())
+                };
+                let key = config.key(
+                    req.uri().path(),
+                    req.uri().query(),
+                    header_value(header::COOKIE),
+                    header_value(header::AUTHORIZATION),
+                );
+                let request_header = |name: &str| {
+                    headers.get(name).map(|value| value.as_bytes().to_vec())
+                };
+                let guard = match config.join(key, request_header).await {
+                    Deduplicated::First(guard) => guard,
+                    Deduplicated::Shared(shared) => {
+                        let mut res = Response::builder().status(shared.status);
+                        for (name, value) in shared.headers {
+                            res = res.header(name, value);
+                        }
+                        return res
+                            .body(Body::from(shared.body))
+                            .unwrap_or_else(|_| {
+                                StatusCode::INTERNAL_SERVER_ERROR
+                                    .into_response()
+                            });
+                    }
+                    Deduplicated::Alone => return next.run(req).await,
+                };
+                let res = next.run(req).await;
+                if !res.status().is_success() {
+                    guard.finish(None);
+                    return res;
+                }
+                let (parts, body) = res.into_parts();
+                match config.buffer_body(body.into_data_stream()).await {
+                    Ok(BufferedBody::Whole(body)) => {
+                        guard.finish(DeduplicatedResponse::new(
+                            parts.status.as_u16(),
+                            parts
+                                .headers
+                                .iter()
+                                .map(|(name, value)| {
+                                    (
+                                        name.to_string(),
+                                        value.as_bytes().to_vec(),
+                                    )
+                                })
+                                .collect(),
+                            body.clone(),
+                            request_header,
+                        ));
+                        Response::from_parts(parts, Body::from(body))
+                    }
+                    Ok(BufferedBody::TooLarge(read, rest)) => {
+                        guard.finish(None);
+                        let body =
+                            futures::stream::iter(read.into_iter().map(Ok))
+                                .chain(rest);
+                        Response::from_parts(parts, Body::from_stream(body))
+                    }
+                    Err(e) => {
+                        guard.finish(None);
+                        (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+                            .into_response()
+                    }
+                }
+            }
+        },
+    ))
+}
+
+/// Negotiates the language of every response from `router` with `negotiation`,
+/// if there is one, storing it as a [`NegotiatedLanguage`] in the extensions of
+/// the request (and in the context of the rendered route), and sending it in
+/// the `Content-Language` header, along with a `Set-Cookie` header that stores
+/// it if the request did not send it in its cookie.
+fn negotiate_language<S>(
+    negotiation: Option<LanguageNegotiation>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(negotiation) = negotiation else {
+        return router;
+    };
+    router.layer(axum::middleware::from_fn(
+        move |mut req: Request<Body>, next: Next| {
+            let header_value = |name| {
+                req.headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+            };
+            let (language, set_cookie) = negotiation.select(
+                header_value(header::COOKIE),
+                header_value(header::ACCEPT_LANGUAGE),
+            );
+            req.extensions_mut().insert(NegotiatedLanguage(language));
+            async move {
+                let mut res = next.run(req).await;
+                let headers = res.headers_mut();
+                headers.insert(
+                    header::CONTENT_LANGUAGE,
+                    HeaderValue::from_static(language),
+                );
+                headers.append(
+                    header::VARY,
+                    HeaderValue::from_static("Accept-Language, Cookie"),
+                );
+                if let Some(cookie) =
+                    set_cookie.and_then(|cookie| cookie.try_into().ok())
+                {
+                    headers.append(header::SET_COOKIE, cookie);
+                }
+                res
+            }
+        },
+    ))
+}
+
+/// Rejects requests to `router` that do not carry an API key accepted by
+/// `config`, if there is one.
+///
+/// The key of a valid request is stored as an [`ApiKey`] in the request
+/// extensions (and provided via context by the Leptos handlers) for the handler
+/// to use.
+fn verify_api_key<S>(
+    config: Option<&ApiKeyConfig>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(config) = config.cloned() else {
+        return router;
+    };
+    router.layer(axum::middleware::from_fn(
+        move |mut req: Request<Body>, next: Next| {
+            let config = config.clone();
+            async move {
+                let key = config
+                    .source
+                    .find(
+                        |name| {
+                            req.headers()
+                                .get(name)
+                                .and_then(|value| value.to_str().ok())
+                        },
+                        req.uri().query(),
+                    )
+                    .map(|key| key.into_owned());
+                let valid = match &key {
+                    Some(key) => config.validator.validate(key).await,
+                    None => false,
+                };
+                match key {
+                    Some(key) if valid => {
+                        req.extensions_mut().insert(ApiKey::new(key));
+                        next.run(req).await
+                    }
+                    _ => (
+                        StatusCode::UNAUTHORIZED,
+                        [(
+                            header::WWW_AUTHENTICATE,
+                            config.source.www_authenticate(),
+                        )],
+                    )
+                        .into_response(),
+                }
+            }
+        },
+    ))
+}
+
+/// Rejects requests to `router` that do not present a TLS client certificate
+/// accepted by `config`, if there is one.
+///
+/// The certificate must have been stored as a [`ClientCert`] in the request
+/// extensions by the server that terminates TLS. Requests without one are
+/// rejected with `401 Unauthorized`, and those with a certificate that is not
+/// valid with `403 Forbidden`.
+fn verify_client_cert<S>(
+    config: Option<&ClientCertConfig>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(config) = config.cloned() else {
+        return router;
+    };
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| {
+            let config = config.clone();
+            async move {
+                let Some(cert) = req.extensions().get::<ClientCert>().cloned()
+                else {
+                    return StatusCode::UNAUTHORIZED.into_response();
+                };
+                if !config.validator.validate(&cert).await {
+                    return StatusCode::FORBIDDEN.into_response();
+                }
+                next.run(req).await
+            }
+        },
+    ))
+}
+
+/// Rejects requests to `router` whose query does not match `schema`, if there
+/// is one, with `400 Bad Request` and a JSON body that lists the errors.
+///
+/// The params of a valid query are stored as a [`ValidatedQuery`] in the
+/// request extensions (and provided via context by the Leptos handlers) for the
+/// handler to use.
+fn verify_query<S>(
+    schema: Option<&QuerySchema>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(schema) = schema.cloned() else {
+        return router;
+    };
+    router.layer(axum::middleware::from_fn(
+        move |mut req: Request<Body>, next: Next| {
+            let query = schema.validate(req.uri().query().unwrap_or_default());
+            async move {
+                match query {
+                    Ok(query) => {
+                        req.extensions_mut().insert(query);
+                        next.run(req).await
+                    }
+                    Err(errors) => (
+                        StatusCode::BAD_REQUEST,
+                        [(header::CONTENT_TYPE, "application/json")],
+                        errors.to_json(),
+                    )
+                        .into_response(),
+                }
+            }
+        },
+    ))
+}
+
+/// Rejects requests to `router` with a body whose content type is not one of
+/// `consumes`, if they are restricted, with `415 Unsupported Media Type`.
+fn verify_content_type<S>(
+    consumes: Option<&ContentTypes>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(consumes) = consumes.cloned() else {
+        return router;
+    };
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| {
+            let unsupported = consumes.applies_to(req.method().as_str())
+                && !consumes.accepts(
+                    req.headers()
+                        .get(header::CONTENT_TYPE)
+                        .and_then(|value| value.to_str().ok()),
+                );
+            async move {
+                if unsupported {
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE.into_response()
+                } else {
+                    next.run(req).await
+                }
+            }
+        },
+    ))
+}
+
+/// Rejects requests to `router` whose body does not match the schema in
+/// `schemas` for its content type, if there are any, with
+/// `422 Unprocessable Entity` (or `400 Bad Request` or
+/// `415 Unsupported Media Type` for a body that cannot be checked) and a JSON
+/// body that lists the errors.
+///
+/// The body has to be read in full to validate it, so its parsed value is
+/// stored as a [`ValidatedBody`] in the request extensions (and provided via
+/// context by the Leptos handlers) for the handler to use.
+fn verify_body<S>(
+    schemas: Option<&BodySchemas>,
+    router: MethodRouter<S>,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Some(schemas) = schemas.cloned() else {
+        return router;
+    };
+    router.layer(axum::middleware::from_fn(
+        move |req: Request<Body>, next: Next| {
+            let schemas = schemas.clone();
+            async move {
+                if !schemas.applies_to(req.method().as_str()) {
+                    return next.run(req).await;
+                }
+                let (parts, body) = req.into_parts();
+                let body = match Bytes::from_request(
+                    Request::from_parts(parts.clone(), body),
+                    &(),
+                )
+                .await
+                {
+                    Ok(body) => body,
+                    Err(rejection) => return rejection.into_response(),
+                };
+
+                let content_type = parts
+                    .headers
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok());
+                match schemas.validate(content_type, &body) {
+                    Ok(validated) => {
+                        let mut req =
+                            Request::from_parts(parts, Body::from(body));
+                        req.extensions_mut().insert(validated);
+                        next.run(req).await
+                    }
+                    Err(error) => (
+                        StatusCode::from_u16(error.status_code())
+                            .unwrap_or(StatusCode::UNPROCESSABLE_ENTITY),
+                        [(header::CONTENT_TYPE, "application/json")],
+                        error.to_json(),
+                    )
+                        .into_response(),
+                }
+            }
+        },
+    ))
+}
+
 /// A helper to make it easier to use Axum extractors in server functions.
 ///
 /// It is generic over some type `T` that implements [`FromRequestParts`] and can
@@ -0,0 +1,74 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use leptos::prelude::*;
+use leptos_axum::{generate_route_list, LeptosRoutes};
+use leptos_router::{
+    components::{Router, Routes},
+    params::ParamsMap,
+    GuardResult, MatchNestedRoutes, NestedRoute, ParamSegment, RouteRedirect,
+    StaticSegment,
+};
+use tower::ServiceExt;
+
+/// The routes of the app, with a guard that redirects every request and one
+/// that forbids those for a secret project.
+#[component(transparent)]
+fn GuardedRoutes() -> impl MatchNestedRoutes + Clone {
+    (
+        NestedRoute::new(StaticSegment("home"), || "home"),
+        NestedRoute::new(StaticSegment("account"), || "account").guard(
+            |_: &ParamsMap| GuardResult::Redirect(RouteRedirect::new("/login")),
+        ),
+        NestedRoute::new(StaticSegment("projects"), || ())
+            .guard(|params: &ParamsMap| params.get_str("id") != Some("secret"))
+            .child(NestedRoute::new(ParamSegment("id"), || "project")),
+    )
+}
+
+fn app() -> impl IntoView {
+    view! {
+        <Router>
+            <Routes fallback=|| "not found">
+                <GuardedRoutes/>
+            </Routes>
+        </Router>
+    }
+}
+
+fn router() -> Router {
+    let options = LeptosOptions::builder().output_name("guards").build();
+    let routes = generate_route_list(app);
+    Router::new()
+        .leptos_routes(&options, routes, app)
+        .with_state(options)
+}
+
+async fn get(path: &str) -> axum::response::Response {
+    router()
+        .oneshot(Request::get(path).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn allowed_requests_are_rendered() {
+    assert_eq!(get("/home").await.status(), StatusCode::OK);
+    assert_eq!(get("/projects/public").await.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn forbidden_requests_are_rejected() {
+    // the guard of the parent route sees the params of the whole matched route
+    let res = get("/projects/secret").await;
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn redirected_requests_are_redirected() {
+    let res = get("/account").await;
+    assert_eq!(res.status(), StatusCode::FOUND);
+    assert_eq!(res.headers()[header::LOCATION], "/login");
+}
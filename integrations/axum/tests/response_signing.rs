@@ -0,0 +1,67 @@
+#![cfg(feature = "response_signing")]
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use leptos::prelude::*;
+use leptos_axum::{generate_route_list, LeptosRoutes};
+use leptos_router::{
+    components::{Router, Routes},
+    MatchNestedRoutes, NestedRoute, ResponseSigningConfig, Secret,
+    SigningAlgorithm, StaticSegment, SIGNATURE_HEADER,
+};
+use tower::ServiceExt;
+
+fn signing() -> ResponseSigningConfig {
+    ResponseSigningConfig {
+        algorithm: SigningAlgorithm::HmacSha256,
+        key_id: "test",
+        key: Secret::new(b"secret".to_vec()),
+    }
+}
+
+#[component(transparent)]
+fn SignedRoutes() -> impl MatchNestedRoutes + Clone {
+    (
+        NestedRoute::new(StaticSegment("signed"), || "signed")
+            .sign_response(signing()),
+        NestedRoute::new(StaticSegment("unsigned"), || "unsigned"),
+    )
+}
+
+fn app() -> impl IntoView {
+    view! {
+        <Router>
+            <Routes fallback=|| "not found">
+                <SignedRoutes/>
+            </Routes>
+        </Router>
+    }
+}
+
+async fn get(path: &str) -> axum::response::Response {
+    let options = LeptosOptions::builder().output_name("signing").build();
+    let routes = generate_route_list(app);
+    Router::new()
+        .leptos_routes(&options, routes, app)
+        .with_state(options)
+        .oneshot(Request::get(path).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn responses_are_signed() {
+    let res = get("/signed").await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let signature = res.headers()[SIGNATURE_HEADER].clone();
+    let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(signature, signing().sign(&body).unwrap());
+
+    let res = get("/unsigned").await;
+    assert!(!res.headers().contains_key(SIGNATURE_HEADER));
+}
@@ -0,0 +1,270 @@
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    response::IntoResponse,
+    Json, Router,
+};
+use leptos::prelude::*;
+use leptos_axum::{generate_route_list, AxumRouteListing, LeptosRoutes};
+use leptos_router::{
+    components::{Router, Routes},
+    EnvelopeConfig, HmacAlgorithm, InMemoryDeduplicationStore,
+    MatchNestedRoutes, NestedRoute, Secret, ShutdownSignal, StaticSegment,
+    WebhookSignatureConfig, BUFFERED_BODY_LIMIT,
+};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, LazyLock,
+    },
+    time::Duration,
+};
+use tower::ServiceExt;
+
+/// Set once the test of the graceful shutdown has started.
+static SHUTDOWN: LazyLock<Arc<AtomicBool>> =
+    LazyLock::new(|| Arc::new(AtomicBool::new(false)));
+
+/// The number of requests that the handler of `/shared` has handled.
+static SHARED_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+// the example from GitHub's documentation on validating webhook deliveries
+const WEBHOOK_SECRET: &str = "It's a Secret to Everybody";
+const WEBHOOK_SIGNATURE: &str =
+    "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+fn webhook_signature() -> WebhookSignatureConfig {
+    WebhookSignatureConfig {
+        header: "X-Hub-Signature-256",
+        secret: Secret::new(WEBHOOK_SECRET.to_string()),
+        algorithm: HmacAlgorithm::Sha256,
+    }
+}
+
+#[component(transparent)]
+fn LayeredRoutes() -> impl MatchNestedRoutes + Clone {
+    (
+        NestedRoute::new(StaticSegment("page"), || "page"),
+        NestedRoute::new(StaticSegment("form"), || "form")
+            .methods([leptos_router::Method::Get, leptos_router::Method::Post]),
+        NestedRoute::new(StaticSegment("gone"), || "gone").status(410),
+        NestedRoute::new(StaticSegment("hook"), || "hook")
+            .methods([leptos_router::Method::Post])
+            .webhook_signature(webhook_signature()),
+        NestedRoute::new(StaticSegment("draining"), || "draining")
+            .methods([leptos_router::Method::Post])
+            .webhook_signature(webhook_signature())
+            .graceful_shutdown(ShutdownSignal::new(Arc::clone(&SHUTDOWN))),
+        NestedRoute::new(StaticSegment("hello"), || "hello")
+            .language_negotiation(&["en", "fr"], "en"),
+        NestedRoute::new(StaticSegment("data"), || ()).response_envelope(
+            EnvelopeConfig {
+                include_meta: false,
+                meta_fn: None,
+            },
+        ),
+        NestedRoute::new(StaticSegment("shared"), || ())
+            .deduplicate(Arc::new(InMemoryDeduplicationStore::new())),
+    )
+}
+
+fn app() -> impl IntoView {
+    view! {
+        <Router>
+            <Routes fallback=|| "not found">
+                <LayeredRoutes/>
+            </Routes>
+        </Router>
+    }
+}
+
+/// Renders every route of the app, except `/data` and `/shared`, which are
+/// handled by JSON handlers. `/data?large` responds with more JSON than the
+/// [`BUFFERED_BODY_LIMIT`].
+fn router() -> Router {
+    let options = LeptosOptions::builder().output_name("route_layers").build();
+    let (data, routes): (Vec<AxumRouteListing>, _) = generate_route_list(app)
+        .into_iter()
+        .partition(|listing| listing.path() == "/data");
+    let (shared, routes): (Vec<AxumRouteListing>, _) = routes
+        .into_iter()
+        .partition(|listing| listing.path() == "/shared");
+    Router::new()
+        .leptos_routes(&options, routes, app)
+        .leptos_routes_with_handler(data, |req: Request<Body>| async move {
+            if req.uri().query() == Some("large") {
+                Json(serde_json::json!({ "text": "a".repeat(BUFFERED_BODY_LIMIT) }))
+            } else {
+                Json(serde_json::json!({ "id": 1 }))
+            }
+        })
+        .leptos_routes_with_handler(shared, || async {
+            let calls = SHARED_CALLS.fetch_add(1, Ordering::SeqCst) + 1;
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            calls.to_string().into_response()
+        })
+        .with_state(options)
+}
+
+async fn send(req: Request<Body>) -> axum::response::Response {
+    router().oneshot(req).await.unwrap()
+}
+
+async fn get(path: &str) -> axum::response::Response {
+    send(Request::get(path).body(Body::empty()).unwrap()).await
+}
+
+async fn body(res: axum::response::Response) -> String {
+    let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn head_requests_are_answered_without_a_body() {
+    let res = send(Request::head("/page").body(Body::empty()).unwrap()).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(body(res).await, "");
+}
+
+#[tokio::test]
+async fn options_requests_list_the_allowed_methods() {
+    let res = send(
+        Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/page")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+    assert_eq!(res.headers()[header::ALLOW], "GET, HEAD, OPTIONS");
+
+    let res = send(
+        Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/form")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(res.headers()[header::ALLOW], "GET, HEAD, POST, OPTIONS");
+}
+
+#[tokio::test]
+async fn other_methods_are_not_allowed() {
+    let res = send(Request::post("/page").body(Body::empty()).unwrap()).await;
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+    let res = send(Request::post("/form").body(Body::empty()).unwrap()).await;
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn routes_respond_with_their_status() {
+    assert_eq!(get("/page").await.status(), StatusCode::OK);
+    assert_eq!(get("/gone").await.status(), StatusCode::GONE);
+}
+
+#[tokio::test]
+async fn webhooks_without_a_valid_signature_are_rejected() {
+    let res = send(
+        Request::post("/hook")
+            .body(Body::from("Hello, World!"))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = send(
+        Request::post("/hook")
+            .header("X-Hub-Signature-256", WEBHOOK_SIGNATURE)
+            .body(Body::from("Hello, World?"))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = send(
+        Request::post("/hook")
+            .header("X-Hub-Signature-256", WEBHOOK_SIGNATURE)
+            .body(Body::from("Hello, World!"))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn requests_are_rejected_once_the_app_shuts_down() {
+    let signed = || {
+        Request::post("/draining")
+            .header("X-Hub-Signature-256", WEBHOOK_SIGNATURE)
+            .body(Body::from("Hello, World!"))
+            .unwrap()
+    };
+    assert_eq!(send(signed()).await.status(), StatusCode::OK);
+
+    SHUTDOWN.store(true, Ordering::Relaxed);
+    assert_eq!(
+        send(signed()).await.status(),
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+    // the shutdown is checked before the signature, which is never read
+    let unsigned = Request::post("/draining").body(Body::empty()).unwrap();
+    assert_eq!(
+        send(unsigned).await.status(),
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+}
+
+#[tokio::test]
+async fn the_language_is_negotiated() {
+    let res = send(
+        Request::get("/hello")
+            .header(header::ACCEPT_LANGUAGE, "fr-CH, fr;q=0.9")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()[header::CONTENT_LANGUAGE], "fr");
+
+    // an unsupported language is not an error: the fallback is used instead
+    let res = send(
+        Request::get("/hello")
+            .header(header::ACCEPT_LANGUAGE, "de")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()[header::CONTENT_LANGUAGE], "en");
+}
+
+#[tokio::test]
+async fn json_responses_are_wrapped_in_the_envelope() {
+    let res = get("/data").await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(body(res).await, r#"{"data":{"id":1}}"#);
+
+    // larger bodies are not buffered to be wrapped
+    let res = get("/data?large").await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = body(res).await;
+    assert!(body.starts_with(r#"{"text":"aaa"#));
+    assert_eq!(body.len(), BUFFERED_BODY_LIMIT + r#"{"text":""}"#.len());
+}
+
+#[tokio::test]
+async fn concurrent_requests_share_a_response() {
+    let router = router();
+    let request = || Request::get("/shared").body(Body::empty()).unwrap();
+    let (first, second) = tokio::join!(
+        router.clone().oneshot(request()),
+        router.oneshot(request())
+    );
+    assert_eq!(body(first.unwrap()).await, "1");
+    assert_eq!(body(second.unwrap()).await, "1");
+    assert_eq!(SHARED_CALLS.load(Ordering::SeqCst), 1);
+}
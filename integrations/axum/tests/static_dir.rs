@@ -0,0 +1,91 @@
+#![cfg(feature = "static_dir")]
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use include_dir::{Dir, DirEntry, File};
+use leptos::prelude::*;
+use leptos_axum::{generate_route_list, LeptosRoutes};
+use leptos_router::{
+    components::{Router, Routes},
+    MatchNestedRoutes, NestedRoute, StaticSegment,
+};
+use tower::ServiceExt;
+
+static ASSETS: Dir<'static> = Dir::new(
+    "",
+    &[
+        DirEntry::File(File::new("app.css", b"body { margin: 0; }")),
+        DirEntry::Dir(Dir::new(
+            "js",
+            &[DirEntry::File(File::new("js/app.js", b"console.log(1);"))],
+        )),
+    ],
+);
+
+#[component(transparent)]
+fn AssetRoutes() -> impl MatchNestedRoutes + Clone {
+    NestedRoute::static_dir(StaticSegment("assets"), &ASSETS)
+}
+
+fn app() -> impl IntoView {
+    view! {
+        <Router>
+            <Routes fallback=|| "not found">
+                <AssetRoutes/>
+            </Routes>
+        </Router>
+    }
+}
+
+async fn send(req: Request<Body>) -> axum::response::Response {
+    let options = LeptosOptions::builder().output_name("static_dir").build();
+    let routes = generate_route_list(app);
+    Router::new()
+        .leptos_routes(&options, routes, app)
+        .with_state(options)
+        .oneshot(req)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn serves_the_files_of_a_static_dir() {
+    let res = send(
+        Request::get("/assets/js/app.js")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()[header::CONTENT_TYPE], "text/javascript");
+    let etag = res.headers()[header::ETAG].clone();
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body, "console.log(1);");
+
+    let res = send(
+        Request::get("/assets/js/app.js")
+            .header(header::IF_NONE_MATCH, etag)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+}
+
+#[tokio::test]
+async fn serves_ranges_of_the_files_of_a_static_dir() {
+    let res = send(
+        Request::get("/assets/app.css")
+            .header(header::RANGE, "bytes=0-3")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(res.headers()[header::CONTENT_RANGE], "bytes 0-3/19");
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body, "body");
+}
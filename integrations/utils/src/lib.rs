@@ -1,8 +1,10 @@
 #![allow(clippy::type_complexity)]
 
 use futures::{stream::once, Stream, StreamExt};
+pub use hydration_context::{IslandPayload, ResourcePayload, ResponseMetrics};
 use hydration_context::{SharedContext, SsrSharedContext};
 use leptos::{
+    context::{provide_context, use_context},
     nonce::use_nonce,
     reactive::owner::{Owner, Sandboxed},
     IntoView,
@@ -25,6 +27,8 @@ pub trait ExtendResponse: Sized {
 
     fn set_default_content_type(&mut self, content_type: &str);
 
+    fn insert_header(&mut self, name: &'static str, value: &str);
+
     fn from_app<IV>(
         app_fn: impl FnOnce() -> IV + Send + 'static,
         meta_context: ServerMetaContextOutput,
@@ -47,8 +51,9 @@ pub trait ExtendResponse: Sized {
                 stream_builder,
                 supports_ooo,
             );
+            let owner = DisposeOnDrop(owner);
 
-            let sc = owner.shared_context().unwrap();
+            let sc = owner.0.shared_context().unwrap();
 
             let stream = stream.await.ready_chunks(32).map(|n| n.join(""));
 
@@ -73,20 +78,31 @@ pub trait ExtendResponse: Sized {
 
             // wait for the first chunk of the stream, then set the status and headers
             let first_chunk = stream.next().await.unwrap_or_default();
+            #[cfg(debug_assertions)]
+            let metrics = sc.response_metrics();
 
             let mut res = Self::from_stream(Sandboxed::new(
                 once(async move { first_chunk })
                     .chain(stream)
-                    // drop the owner, cleaning up the reactive runtime,
-                    // once the stream is over
+                    // report the metrics of the response, then drop the owner,
+                    // cleaning up the reactive runtime, once the stream is over
                     .chain(once(async move {
-                        owner.unset();
+                        report_response_metrics(&owner.0);
+                        drop(owner);
                         Default::default()
                     })),
             ));
 
             res.extend_response(&res_options);
 
+            // in debug builds, show the size of the hydration data in the
+            // browser's developer tools, as far as it was written before the
+            // headers were sent
+            #[cfg(debug_assertions)]
+            if let Some(metrics) = metrics {
+                res.insert_header("server-timing", &metrics.server_timing());
+            }
+
             // Set the Content Type headers on all responses. This makes Firefox show the page source
             // without complaining
             res.set_default_content_type("text/html; charset=utf-8");
@@ -96,6 +112,56 @@ pub trait ExtendResponse: Sized {
     }
 }
 
+/// Called with the [`ResponseMetrics`] of each response, set with
+/// [`on_response_metrics`].
+#[derive(Clone)]
+struct OnResponseMetrics(Arc<dyn Fn(ResponseMetrics) + Send + Sync>);
+
+/// Calls `callback` with the [`ResponseMetrics`] of the response that is being
+/// rendered, once all of it has been sent: the sizes of the resources and
+/// island props that were serialized for hydration, which can be used to keep
+/// the hydration data of each route within a budget.
+///
+/// This should be called in the additional context of the server integration,
+/// or at the root of the app.
+pub fn on_response_metrics(
+    callback: impl Fn(ResponseMetrics) + Send + Sync + 'static,
+) {
+    provide_context(OnResponseMetrics(Arc::new(callback)));
+}
+
+/// Calls the [`on_response_metrics`] callback of `owner`, if there is one, with
+/// the metrics of its shared context.
+fn report_response_metrics(owner: &Owner) {
+    let Some(callback) = owner.with(use_context::<OnResponseMetrics>) else {
+        return;
+    };
+    if let Some(metrics) =
+        owner.shared_context().and_then(|sc| sc.response_metrics())
+    {
+        (callback.0)(metrics);
+    }
+}
+
+/// Cleans up the reactive runtime of a response when it is dropped.
+///
+/// This is once the whole response has been sent, or earlier, if the client
+/// goes away and the server drops the response, or the future that is rendering
+/// it, before it is finished. The resources (and anything else) owned by the
+/// app are disposed of either way, which releases what they hold, like database
+/// connections, rather than rendering for nobody.
+struct DisposeOnDrop(Owner);
+
+impl Drop for DisposeOnDrop {
+    fn drop(&mut self) {
+        // tasks that are still running may hold on to the owner, so it is
+        // cleaned up explicitly, rather than when the last of them lets go of
+        // it
+        self.0.cleanup();
+        self.0.clone().unset();
+    }
+}
+
 pub fn build_response<IV>(
     app_fn: impl FnOnce() -> IV + Send + 'static,
     additional_context: impl FnOnce() + Send + 'static,
@@ -168,3 +234,29 @@ pub fn static_file_path(options: &LeptosOptions, path: &str) -> String {
     };
     format!("{}/{}.html", options.site_root, path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::DisposeOnDrop;
+    use leptos::reactive::owner::Owner;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn dropping_a_response_cleans_up_its_owner() {
+        let owner = Owner::new();
+        let cleaned_up = Arc::new(AtomicBool::new(false));
+        owner.with(|| {
+            let cleaned_up = Arc::clone(&cleaned_up);
+            Owner::on_cleanup(move || cleaned_up.store(true, Ordering::SeqCst));
+        });
+        // like a task that is still rendering, which keeps the owner alive
+        let task = owner.clone();
+
+        drop(DisposeOnDrop(owner));
+        assert!(cleaned_up.load(Ordering::SeqCst));
+        drop(task);
+    }
+}
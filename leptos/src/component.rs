@@ -50,6 +50,14 @@ where
 {
 }
 
+/// Adds the size of `props`, the serialized props of the island `island`, to
+/// the payload metrics of the response that is being rendered, if there is one.
+pub fn record_island_props(island: &'static str, props: &str) {
+    if let Some(sc) = reactive_graph::owner::Owner::current_shared_context() {
+        sc.record_island_props(island, props.len());
+    }
+}
+
 pub fn component_props_builder<P: PropsOrNoPropsBuilder>(
     _f: &impl Component<P>,
 ) -> <P as PropsOrNoPropsBuilder>::Builder {
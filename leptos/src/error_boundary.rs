@@ -120,6 +120,51 @@ where
     )
 }
 
+/// Creates a reactive view closure in which errors can be forwarded to the
+/// nearest [`ErrorBoundary`] with `?`, instead of being mapped by hand.
+///
+/// The body is wrapped in a `move` closure that returns
+/// `Result<_, `[`Error`](throw_error::Error)`>`, with the value of its last
+/// expression as the `Ok` view. Any error that implements [`std::error::Error`]
+/// can be returned with `?`: it is rendered as nothing, and registered with the
+/// `<ErrorBoundary/>` that contains it, exactly as if an `Err` had been
+/// rendered. Once the closure runs again and succeeds, the error is cleared.
+///
+/// To render nothing without an error, make the last expression an `Option`:
+/// `None` renders nothing, and `Some(view)` renders the view. Because the
+/// closure is an ordinary reactive view, it can be nested in `<Suspense/>` and
+/// `<ErrorBoundary/>` in either order, just like any other.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// #[component]
+/// pub fn Total() -> impl IntoView {
+///     let (a, set_a) = signal(String::from("1"));
+///     let (b, _) = signal(String::from("2"));
+///
+///     view! {
+///         <input on:input=move |ev| set_a.set(event_target_value(&ev))/>
+///         <ErrorBoundary fallback=|_| view! { <p>"Not a number."</p> }>
+///             {try_view! {
+///                 let a = a.get().parse::<i32>()?;
+///                 let b = b.get().parse::<i32>()?;
+///                 view! { <p>"Total: " {a + b}</p> }
+///             }}
+///         </ErrorBoundary>
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! try_view {
+    ($($body:tt)*) => {
+        move || -> ::core::result::Result<_, $crate::error::Error> {
+            ::core::result::Result::Ok({ $($body)* })
+        }
+    };
+}
+
+pub use try_view;
+
 pub(crate) type ErrorBoundarySuspendedChildren =
     ArcStoredValue<Vec<oneshot::Receiver<()>>>;
 
@@ -17,5 +17,8 @@ ws.onmessage = (ev) => {
 	if(msg.view) {
 		patch(msg.view);
 	}
+	if(msg.rebuild) {
+		rebuild(msg.rebuild);
+	}
 };
 ws.onclose = () => console.warn('Live-reload stopped. Manual reload necessary.');
@@ -175,6 +175,7 @@ pub mod prelude {
             effect::*,
             graph::untrack,
             owner::*,
+            pending::*,
             signal::*,
             wrappers::{read::*, write::*},
         };
@@ -209,9 +210,10 @@ mod error_boundary;
 
 /// Tools for handling errors.
 pub mod error {
-    pub use crate::error_boundary::*;
+    pub use crate::{error_boundary::*, sandbox::*};
     pub use throw_error::*;
 }
+mod sandbox;
 
 /// Control-flow components like `<Show>`, `<For>`, and `<Await>`.
 pub mod control_flow {
@@ -228,6 +230,10 @@ pub mod portal;
 /// Components to enable server-side rendering and client-side hydration.
 pub mod hydration;
 
+/// Rendering views to HTML strings, with or without waiting for async data.
+#[cfg(feature = "ssr")]
+pub mod ssr;
+
 /// Utilities for exporting nonces to be used for a Content Security Policy.
 #[cfg(feature = "nonce")]
 pub mod nonce;
@@ -0,0 +1,416 @@
+use crate::{
+    callback::{Callable, Callback},
+    children::TypedChildren,
+    IntoView,
+};
+use leptos_macro::component;
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    owner::Owner,
+    signal::ArcRwSignal,
+    traits::{Get, Set, WithUntracked},
+};
+use std::{
+    any::Any,
+    error::Error,
+    fmt, iter,
+    panic::{self, AssertUnwindSafe},
+    sync::{Mutex, Once},
+};
+use tachys::{
+    either::Either,
+    html::attribute::{any_attribute::AnyAttribute, Attribute},
+    hydration::Cursor,
+    reactive_graph::{OwnedView, OwnedViewState},
+    ssr::StreamBuilder,
+    view::{add_attr::AddAnyAttr, Position, PositionState, Render, RenderHtml},
+};
+
+/// A panic that was caught by a [`Sandbox`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandboxPanic {
+    message: String,
+    location: Option<String>,
+}
+
+impl SandboxPanic {
+    fn new(payload: &(dyn Any + Send), location: Option<String>) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".to_string());
+        Self { message, location }
+    }
+
+    /// The message the view panicked with.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The file, line and column the view panicked at, if they are known.
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+}
+
+impl fmt::Display for SandboxPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(
+                f,
+                "a sandboxed view panicked at {location}: {}",
+                self.message
+            ),
+            None => write!(f, "a sandboxed view panicked: {}", self.message),
+        }
+    }
+}
+
+impl Error for SandboxPanic {}
+
+/// Isolates views that can panic, like the views of third-party plugins, from
+/// the rest of the app.
+///
+/// A panic while the children are created or rendered, or in one of their
+/// effects, is caught instead of taking down the app: the owner of the children
+/// is torn down, which disposes of their signals and effects, the `fallback` is
+/// rendered in their place, and the panic is reported to `on_panic`.
+///
+/// When rendering on the server, the panic is rendered as an error instead, so
+/// that it reaches the nearest `<ErrorBoundary/>`, like the error view of a
+/// route, without aborting the rest of the response.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// #[component]
+/// pub fn Plugins(plugins: Vec<fn() -> AnyView>) -> impl IntoView {
+///     plugins
+///         .into_iter()
+///         .map(|plugin| {
+///             view! {
+///                 <Sandbox
+///                     fallback=|panic: SandboxPanic| view! {
+///                         <p class="error">
+///                             "This plugin failed: "
+///                             {panic.message().to_string()}
+///                         </p>
+///                     }
+///                     on_panic=|panic: SandboxPanic| {
+///                         leptos::logging::error!("{panic}")
+///                     }
+///                 >
+///                     {plugin()}
+///                 </Sandbox>
+///             }
+///         })
+///         .collect_view()
+/// }
+/// ```
+///
+/// Panics are found with a panic hook, which is installed the first time a
+/// `Sandbox` is rendered, and which passes the panics of views outside any
+/// sandbox to the hook that was installed before it. A panic can only be
+/// recovered from where it unwinds: in the browser, `wasm32-unknown-unknown`
+/// aborts on panic, so the panic is reported, but the app may not keep working
+/// after it.
+#[component]
+pub fn Sandbox<FalFn, Fal, Chil>(
+    /// The views that may panic.
+    children: TypedChildren<Chil>,
+    /// The view that is rendered in place of the children once they have
+    /// panicked.
+    fallback: FalFn,
+    /// Called with each panic that is caught.
+    #[prop(optional, into)]
+    on_panic: Option<Callback<(SandboxPanic,)>>,
+) -> impl IntoView
+where
+    FalFn: Fn(SandboxPanic) -> Fal + Send + 'static,
+    Fal: IntoView + 'static,
+    Chil: IntoView + Send + 'static,
+{
+    install_panic_hook();
+
+    let sandbox = SandboxScope {
+        owner: Owner::new(),
+        panicked: ArcRwSignal::new(None),
+        on_panic,
+    };
+    sandbox.register();
+
+    let children = panic::catch_unwind(AssertUnwindSafe(|| {
+        sandbox.owner.with(|| children.into_inner()())
+    }));
+    let mut children = match children {
+        Ok(view) => Some(SandboxView {
+            view: OwnedView::new_with_owner(view, sandbox.owner.clone()),
+            sandbox: sandbox.clone(),
+        }),
+        Err(payload) => {
+            sandbox.caught(payload);
+            None
+        }
+    };
+
+    move || match sandbox.panicked.get() {
+        None => Either::Left(children.take()),
+        Some(panic) => {
+            sandbox.report(&panic);
+            Either::Right(if cfg!(feature = "ssr") {
+                Err(panic)
+            } else {
+                Ok(fallback(panic))
+            })
+        }
+    }
+}
+
+/// The sandboxes that are rendered, by the [debug ID](Owner::debug_id) of the
+/// owner of their children, which the panic hook finds the sandbox of a panic
+/// in.
+static SANDBOXES: Mutex<Vec<(usize, ArcRwSignal<Option<SandboxPanic>>)>> =
+    Mutex::new(Vec::new());
+
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let caught = Owner::current().is_some_and(|owner| {
+                let location = info.location().map(ToString::to_string);
+                catch(&owner, SandboxPanic::new(info.payload(), location))
+            });
+            // where panics unwind, a caught panic is reported by its sandbox
+            // instead
+            if !caught || cfg!(not(panic = "unwind")) {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Hands `panic` to the innermost sandbox around `owner`, returning whether
+/// there was one.
+fn catch(owner: &Owner, panic: SandboxPanic) -> bool {
+    // the hook must not panic itself, so a sandbox that is being registered is
+    // skipped
+    let panicked = {
+        let Ok(sandboxes) = SANDBOXES.try_lock() else {
+            return false;
+        };
+        if sandboxes.is_empty() {
+            return false;
+        }
+        iter::once(owner.debug_id())
+            .chain(owner.ancestry())
+            .find_map(|id| {
+                sandboxes
+                    .iter()
+                    .find(|(sandbox, _)| *sandbox == id)
+                    .map(|(_, panicked)| panicked.clone())
+            })
+    };
+    match panicked {
+        Some(panicked) => {
+            panicked.set(Some(panic));
+            true
+        }
+        None => false,
+    }
+}
+
+#[derive(Clone)]
+struct SandboxScope {
+    owner: Owner,
+    panicked: ArcRwSignal<Option<SandboxPanic>>,
+    on_panic: Option<Callback<(SandboxPanic,)>>,
+}
+
+impl SandboxScope {
+    fn register(&self) {
+        let id = self.owner.debug_id();
+        SANDBOXES
+            .lock()
+            .or_poisoned()
+            .push((id, self.panicked.clone()));
+        self.owner.with(|| {
+            Owner::on_cleanup(move || {
+                SANDBOXES
+                    .lock()
+                    .or_poisoned()
+                    .retain(|(sandbox, _)| *sandbox != id);
+            })
+        });
+    }
+
+    /// The panic that was caught with `payload`, which the panic hook has
+    /// usually found already.
+    fn caught(&self, payload: Box<dyn Any + Send>) -> SandboxPanic {
+        match self.panicked.with_untracked(Clone::clone) {
+            Some(panic) => panic,
+            None => {
+                let panic = SandboxPanic::new(&*payload, None);
+                self.panicked.set(Some(panic.clone()));
+                panic
+            }
+        }
+    }
+
+    /// Renders the children to HTML with `render`, which sets their owner, or
+    /// the panic as an error for the nearest `<ErrorBoundary/>` if they panic,
+    /// returning whether they rendered.
+    fn render_html(&self, render: impl FnOnce()) -> bool {
+        match panic::catch_unwind(AssertUnwindSafe(render)) {
+            Ok(()) => true,
+            Err(payload) => {
+                let panic = self.caught(payload);
+                self.report(&panic);
+                throw_error::throw(panic);
+                false
+            }
+        }
+    }
+
+    /// Tears down the children, and reports `panic`.
+    fn report(&self, panic: &SandboxPanic) {
+        self.owner.cleanup();
+        if let Some(on_panic) = &self.on_panic {
+            on_panic.run((panic.clone(),));
+        }
+    }
+}
+
+/// The children of a [`Sandbox`], which catches their panics while they are
+/// rendered to HTML.
+struct SandboxView<V> {
+    view: OwnedView<V>,
+    sandbox: SandboxScope,
+}
+
+impl<V> Render for SandboxView<V>
+where
+    V: Render,
+{
+    type State = OwnedViewState<V::State>;
+
+    fn build(self) -> Self::State {
+        self.view.build()
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.view.rebuild(state);
+    }
+}
+
+impl<V> AddAnyAttr for SandboxView<V>
+where
+    V: AddAnyAttr,
+{
+    type Output<SomeNewAttr: Attribute> = SandboxView<V::Output<SomeNewAttr>>;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        SandboxView {
+            view: self.view.add_any_attr(attr),
+            sandbox: self.sandbox,
+        }
+    }
+}
+
+impl<V> RenderHtml for SandboxView<V>
+where
+    V: RenderHtml,
+{
+    type AsyncOutput = SandboxView<V::AsyncOutput>;
+    type Owned = SandboxView<V::Owned>;
+
+    const MIN_LENGTH: usize = 0;
+
+    fn dry_resolve(&mut self) {
+        self.view.dry_resolve();
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        SandboxView {
+            view: self.view.resolve().await,
+            sandbox: self.sandbox,
+        }
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) {
+        // the children are rendered apart, so that nothing is left of them if
+        // they panic
+        let mut new_buf = String::new();
+        let mut new_pos = *position;
+        let SandboxView { view, sandbox } = self;
+        let rendered = sandbox.render_html(|| {
+            view.to_html_with_buf(
+                &mut new_buf,
+                &mut new_pos,
+                escape,
+                mark_branches,
+                extra_attrs,
+            )
+        });
+        if rendered {
+            buf.push_str(&new_buf);
+            *position = new_pos;
+        }
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) where
+        Self: Sized,
+    {
+        let mut new_buf = StreamBuilder::new(buf.clone_id());
+        let mut new_pos = *position;
+        let SandboxView { view, sandbox } = self;
+        let rendered = sandbox.render_html(|| {
+            view.to_html_async_with_buf::<OUT_OF_ORDER>(
+                &mut new_buf,
+                &mut new_pos,
+                escape,
+                mark_branches,
+                extra_attrs,
+            )
+        });
+        if rendered {
+            buf.append(new_buf);
+            *position = new_pos;
+        }
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        self.view.hydrate::<FROM_SERVER>(cursor, position)
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        SandboxView {
+            view: self.view.into_owned(),
+            sandbox: self.sandbox,
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Rendering views to HTML strings.
+//!
+//! The server integrations render a whole application as a streamed HTTP
+//! response. The functions in this module render a single view to a `String`
+//! instead, which is useful outside of an HTTP handler: for example, to render
+//! emails, static pages, or snapshot tests.
+//!
+//! ```rust
+//! # use leptos::prelude::*;
+//! use leptos::ssr::{render_to_string, RenderOptions};
+//!
+//! let html = render_to_string(
+//!     || view! { <p>"Hello, " {"world"} "!"</p> },
+//!     &RenderOptions::new().hydration_markers(false),
+//! );
+//! assert_eq!(html, "<p>Hello, world!</p>");
+//! ```
+//!
+//! [`render_to_string`] renders `<Suspense/>` fallbacks for anything that has
+//! not loaded yet. [`render_to_string_async`] waits for every resource read
+//! under a `<Suspense/>` to resolve, and renders the loaded content instead. It
+//! spawns tasks, so an [`Executor`](any_spawner::Executor) must be initialized
+//! first.
+//!
+//! ## Metadata
+//!
+//! Components like `Title`, `Meta` and `Link` from `leptos_meta` do not render
+//! where they are used, but register themselves with a `ServerMetaContext`. To
+//! render them separately from the body, provide that context while rendering,
+//! and read the `<head>` afterward:
+//!
+//! ```rust,ignore
+//! let (meta, output) = ServerMetaContext::new();
+//! let body = render_to_string_async(
+//!     move || {
+//!         provide_context(meta);
+//!         view! { <Title text="Welcome"/> <Email/> }
+//!     },
+//!     &RenderOptions::new().hydration_markers(false),
+//! )
+//! .await;
+//! let head = output.head_html();
+//! ```
+
+use crate::IntoView;
+use futures::StreamExt;
+use hydration_context::{SharedContext, SsrSharedContext};
+use reactive_graph::owner::Owner;
+use std::{borrow::Cow, sync::Arc};
+#[doc(inline)]
+pub use tachys::{ssr::StreamBuilder, view::RenderHtml};
+
+/// Options for rendering a view to a string with [`render_to_string`] or
+/// [`render_to_string_async`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderOptions {
+    hydration_markers: bool,
+    pretty: bool,
+    wrapper: Option<Cow<'static, str>>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            hydration_markers: true,
+            pretty: false,
+            wrapper: None,
+        }
+    }
+}
+
+impl RenderOptions {
+    /// Creates the default options, which render the same HTML as
+    /// [`RenderHtml::to_html`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether to include the markers that are needed to hydrate the HTML in
+    /// the browser, like the `<!>` comments between adjacent text nodes.
+    /// Defaults to `true`.
+    ///
+    /// Turn this off for HTML that will never be hydrated, like an email. The
+    /// rendered HTML then never contains hydration markers or hydration IDs.
+    pub fn hydration_markers(mut self, include: bool) -> Self {
+        self.hydration_markers = include;
+        self
+    }
+
+    /// Whether to indent the HTML, with each element and text node on its own
+    /// line. Defaults to `false`.
+    ///
+    /// The contents of `<pre>`, `<textarea>`, `<script>` and `<style>` are kept
+    /// as they are, but whitespace between other inline elements may change.
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Wraps the rendered HTML in an element with the given tag name, like
+    /// `div`. By default, a fragment is rendered without a wrapper.
+    pub fn wrapper(mut self, tag: impl Into<Cow<'static, str>>) -> Self {
+        self.wrapper = Some(tag.into());
+        self
+    }
+
+    fn finish(&self, mut html: String) -> String {
+        if !self.hydration_markers {
+            html = html.replace("<!>", "");
+        }
+        if let Some(tag) = &self.wrapper {
+            html = format!("<{tag}>{html}</{tag}>");
+        }
+        if self.pretty {
+            html = pretty_print(&html);
+        }
+        html
+    }
+}
+
+/// Renders a view to HTML synchronously, with the fallback of any `<Suspense/>`
+/// that has not loaded yet.
+pub fn render_to_string<V>(
+    view: impl FnOnce() -> V,
+    options: &RenderOptions,
+) -> String
+where
+    V: IntoView,
+{
+    let owner = new_root();
+    let html = owner.with(|| view().into_view().to_html());
+    owner.unset();
+    options.finish(html)
+}
+
+/// Renders a view to HTML, after waiting for all of the resources read under a
+/// `<Suspense/>` to resolve.
+pub async fn render_to_string_async<V>(
+    view: impl FnOnce() -> V + Send + 'static,
+    options: &RenderOptions,
+) -> String
+where
+    V: IntoView + 'static,
+{
+    let owner = new_root();
+    let stream = owner.with(|| view().into_view().to_html_stream_in_order());
+    let html = stream.collect::<String>().await;
+    if let Some(shared_context) = owner.shared_context() {
+        while let Some(pending) = shared_context.await_deferred() {
+            pending.await;
+        }
+    }
+    owner.unset();
+    options.finish(html)
+}
+
+fn new_root() -> Owner {
+    let shared_context = Arc::new(SsrSharedContext::new())
+        as Arc<dyn SharedContext + Send + Sync>;
+    Owner::new_root(Some(shared_context))
+}
+
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta",
+    "param", "source", "track", "wbr",
+];
+
+const RAW_TEXT_ELEMENTS: [&str; 4] = ["pre", "script", "style", "textarea"];
+
+/// Puts each tag and text node of an HTML string on its own line, indented by
+/// its depth.
+fn pretty_print(html: &str) -> String {
+    let mut lines = Vec::new();
+    let mut depth = 0usize;
+    let mut rest = html;
+    let push = |lines: &mut Vec<String>, depth: usize, line: &str| {
+        lines.push(format!("{}{line}", "  ".repeat(depth)));
+    };
+
+    while !rest.is_empty() {
+        if !rest.starts_with('<') {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = rest[..end].trim();
+            if !text.is_empty() {
+                push(&mut lines, depth, text);
+            }
+            rest = &rest[end..];
+            continue;
+        }
+
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map_or(rest.len(), |end| end + 3);
+            push(&mut lines, depth, &rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+
+        let end = tag_end(rest);
+        let tag = &rest[..end];
+        rest = &rest[end..];
+        if tag.starts_with("</") {
+            depth = depth.saturating_sub(1);
+            push(&mut lines, depth, tag);
+            continue;
+        }
+
+        push(&mut lines, depth, tag);
+        let name = tag_name(tag);
+        if tag.starts_with("<!")
+            || tag.ends_with("/>")
+            || VOID_ELEMENTS.contains(&name.as_str())
+        {
+            continue;
+        }
+        if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+            // keep the contents and the closing tag on the same line as the
+            // opening tag
+            let close = format!("</{name}");
+            let content_end =
+                rest.to_ascii_lowercase().find(&close).unwrap_or(rest.len());
+            let close_end = content_end + tag_end(&rest[content_end..]);
+            if let Some(line) = lines.last_mut() {
+                line.push_str(&rest[..close_end]);
+            }
+            rest = &rest[close_end..];
+            continue;
+        }
+        depth += 1;
+    }
+
+    lines.join("\n")
+}
+
+/// The length of the tag at the start of `html`, skipping over any `>` in
+/// quoted attributes.
+fn tag_end(html: &str) -> usize {
+    let mut quote = None;
+    for (index, ch) in html.char_indices() {
+        match (quote, ch) {
+            (None, '"' | '\'') => quote = Some(ch),
+            (Some(open), _) if open == ch => quote = None,
+            (None, '>') => return index + 1,
+            _ => {}
+        }
+    }
+    html.len()
+}
+
+fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches('<')
+        .split(|ch: char| ch.is_whitespace() || ch == '>' || ch == '/')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
@@ -8,16 +8,17 @@ use hydration_context::SerializedDataId;
 use leptos_macro::component;
 use reactive_graph::{
     computed::{
-        suspense::{LocalResourceNotifier, SuspenseContext},
+        suspense::{LocalResourceNotifier, SuspenseContext, TaskHandle},
         ArcMemo, ScopedFuture,
     },
     effect::RenderEffect,
     owner::{provide_context, use_context, Owner},
     signal::ArcRwSignal,
-    traits::{Dispose, Get, Read, Track, With, WriteValue},
+    traits::{Dispose, Get, GetUntracked, Read, Set, Track, With, WriteValue},
+    wrappers::read::Signal,
 };
 use slotmap::{DefaultKey, SlotMap};
-use std::sync::Arc;
+use std::{future::Future, sync::Arc};
 use tachys::{
     either::Either,
     html::attribute::{any_attribute::AnyAttribute, Attribute},
@@ -30,6 +31,7 @@ use tachys::{
         Mountable, Position, PositionState, Render, RenderHtml,
     },
 };
+use thiserror::Error;
 use throw_error::ErrorHookFuture;
 
 /// If any [`Resource`](leptos_reactive::Resource) is read in the `children` of this
@@ -93,6 +95,10 @@ pub fn Suspense<Chil>(
     /// By default this is an empty view.
     #[prop(optional, into)]
     fallback: ViewFnOnce,
+    /// A handle that can be used to wait for this boundary to settle, or to
+    /// track whether it is waiting for resources, from outside it.
+    #[prop(optional)]
+    handle: Option<SuspenseHandle>,
     /// Children will be rendered once initially to catch any resource reads, then hidden until all
     /// data have loaded.
     children: TypedChildren<Chil>,
@@ -126,6 +132,10 @@ where
                 tasks.with(SlotMap::is_empty)
             }
         });
+        if let Some(handle) = &handle {
+            handle.attach();
+        }
+        register_pending_suspense(&none_pending, handle.as_ref());
 
         OwnedView::new(SuspenseBoundary::<false, _, _> {
             id,
@@ -137,6 +147,249 @@ where
     })
 }
 
+/// Tracks whether any [`Suspense`] or [`Transition`](crate::Transition) beneath
+/// it is waiting for its resources to load.
+///
+/// When this is provided as context, each `Suspense` created beneath it
+/// registers itself for as long as it has pending resources. The router uses
+/// this to wait for the content of a new page to load before moving focus into
+/// it.
+#[derive(Clone, Debug)]
+pub struct PendingSuspense(SuspenseContext);
+
+impl Default for PendingSuspense {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PendingSuspense {
+    /// Creates a new tracker, with nothing pending.
+    pub fn new() -> Self {
+        Self(SuspenseContext {
+            tasks: ArcRwSignal::new(SlotMap::new()),
+        })
+    }
+
+    /// Whether any `Suspense` beneath this tracker is currently waiting for
+    /// resources.
+    ///
+    /// This is reactive: reading it inside an effect will track it.
+    pub fn is_pending(&self) -> bool {
+        !self.0.tasks.with(SlotMap::is_empty)
+    }
+
+    /// Resolves once no `Suspense` beneath this tracker is waiting for
+    /// resources.
+    pub async fn ready(&self) {
+        let (tx, rx) = oneshot::channel();
+        let mut tx = Some(tx);
+        let this = self.clone();
+        let _effect = RenderEffect::new(move |_| {
+            if !this.is_pending() {
+                if let Some(tx) = tx.take() {
+                    _ = tx.send(());
+                }
+            }
+        });
+        // if effects are disabled, the sender is dropped, and this resolves
+        // immediately
+        _ = rx.await;
+    }
+
+    /// How many `Suspense` boundaries beneath this tracker are currently
+    /// waiting for resources.
+    ///
+    /// This is reactive: reading it inside an effect will track it.
+    pub fn pending_count(&self) -> usize {
+        self.0.tasks.with(SlotMap::len)
+    }
+
+    /// Keeps the [global](tachys::ssr::PENDING_FRAGMENTS_GLOBAL) that the
+    /// scripts of out-of-order streaming set on the server up to date with
+    /// [`pending_count`](Self::pending_count), dispatching the same
+    /// [event](tachys::ssr::PENDING_FRAGMENTS_EVENT) each time it changes, for
+    /// as long as the current owner is alive.
+    ///
+    /// This lets a loading indicator that is driven by the stream, like the
+    /// [snippet](tachys::ssr::STREAMING_PROGRESS_SNIPPET), go on working after
+    /// hydration.
+    pub fn report_to_window(&self) {
+        let this = self.clone();
+        let effect = RenderEffect::new(move |_| {
+            let pending = this.pending_count();
+            let window = tachys::dom::window();
+            _ = web_sys::js_sys::Reflect::set(
+                &window,
+                &tachys::ssr::PENDING_FRAGMENTS_GLOBAL.into(),
+                &(pending as u32).into(),
+            );
+            if let Ok(event) =
+                web_sys::Event::new(tachys::ssr::PENDING_FRAGMENTS_EVENT)
+            {
+                _ = tachys::dom::document().dispatch_event(&event);
+            }
+        });
+        Owner::on_cleanup(move || drop(effect));
+    }
+
+    fn task_id(&self) -> TaskHandle {
+        self.0.task_id()
+    }
+}
+
+/// Tracks every [`Suspense`] and [`Transition`](crate::Transition) of an app,
+/// for [`settle_all`], provided as context by the root owner of the app.
+#[derive(Clone, Debug)]
+struct AllSuspense(PendingSuspense);
+
+/// The tracker of every `Suspense` of the app that the current owner belongs
+/// to, if there is a current owner.
+fn all_suspense() -> Option<PendingSuspense> {
+    let root = Owner::current()?.root();
+    let AllSuspense(all) = root.with(|| {
+        use_context::<AllSuspense>().unwrap_or_else(|| {
+            let all = AllSuspense(PendingSuspense::new());
+            provide_context(all.clone());
+            all
+        })
+    });
+    Some(all)
+}
+
+/// Resolves once no [`Suspense`] or [`Transition`](crate::Transition) of the
+/// app that the current owner belongs to is waiting for its resources, like
+/// after dispatching an action that invalidates resources across the page.
+///
+/// The app is found when this is called, so it can be awaited in a task that is
+/// spawned without an owner. This resolves immediately if nothing is pending,
+/// or if there is no current owner.
+pub fn settle_all() -> impl Future<Output = ()> + 'static {
+    let all = all_suspense();
+    async move {
+        if let Some(all) = all {
+            all.ready().await;
+        }
+    }
+}
+
+/// The error returned by [`SuspenseHandle::settled`] when the [`Suspense`] it
+/// was passed to was disposed before it finished loading.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("the Suspense boundary was disposed before it settled")]
+pub struct SuspenseDisposed;
+
+/// A handle to a [`Suspense`], passed as its `handle`, to wait for it to finish
+/// loading, or to track whether it is loading, from outside it.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # if false { // don't run in doctests
+/// let suspense = SuspenseHandle::new();
+/// let is_pending = suspense.is_pending();
+///
+/// view! {
+///     <Suspense handle=suspense.clone()>
+///         <p>"Results"</p>
+///     </Suspense>
+/// };
+///
+/// leptos::task::spawn_local(async move {
+///     // after invalidating the resources read in the `Suspense`
+///     if suspense.settled().await.is_ok() {
+///         // scroll to the result
+///     }
+/// });
+/// # }
+/// ```
+///
+/// The handle only holds signals, so it does not keep the `Suspense` alive:
+/// once the boundary is disposed, [`settled`](Self::settled) returns
+/// [`SuspenseDisposed`].
+#[derive(Clone, Debug, Default)]
+pub struct SuspenseHandle {
+    pending: PendingSuspense,
+    disposed: ArcRwSignal<bool>,
+}
+
+impl SuspenseHandle {
+    /// Creates a new handle, which is not pending until it is passed to a
+    /// [`Suspense`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the `Suspense` is waiting for resources.
+    pub fn is_pending(&self) -> Signal<bool> {
+        let pending = self.pending.clone();
+        Signal::derive(move || pending.is_pending())
+    }
+
+    /// Resolves once the `Suspense` is not waiting for resources, immediately
+    /// if it is not, or with [`SuspenseDisposed`] if it is disposed first.
+    pub async fn settled(&self) -> Result<(), SuspenseDisposed> {
+        let (tx, rx) = oneshot::channel();
+        let mut tx = Some(tx);
+        let this = self.clone();
+        let _effect = RenderEffect::new(move |_| {
+            let settled = if this.disposed.get() {
+                Err(SuspenseDisposed)
+            } else if this.pending.is_pending() {
+                return;
+            } else {
+                Ok(())
+            };
+            if let Some(tx) = tx.take() {
+                _ = tx.send(settled);
+            }
+        });
+        // if effects are disabled, the sender is dropped, and this resolves
+        // immediately
+        rx.await.unwrap_or_else(|_| {
+            if self.disposed.get_untracked() {
+                Err(SuspenseDisposed)
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    /// Attaches this handle to the `Suspense` that is being created, until its
+    /// owner is cleaned up.
+    fn attach(&self) {
+        self.disposed.set(false);
+        let disposed = self.disposed.clone();
+        Owner::on_cleanup(move || disposed.set(true));
+    }
+}
+
+/// Registers a pending task with the [`PendingSuspense`] tracker in context, if
+/// any, the tracker of `handle`, if any, and the tracker of the app for
+/// [`settle_all`], whenever `none_pending` is `false`.
+pub(crate) fn register_pending_suspense(
+    none_pending: &ArcMemo<bool>,
+    handle: Option<&SuspenseHandle>,
+) {
+    let trackers = use_context::<PendingSuspense>()
+        .into_iter()
+        .chain(handle.map(|handle| handle.pending.clone()))
+        .chain(all_suspense());
+    for pending in trackers {
+        let none_pending = none_pending.clone();
+        let effect =
+            RenderEffect::new(move |handle: Option<Option<TaskHandle>>| {
+                if none_pending.get() {
+                    None
+                } else {
+                    handle.flatten().or_else(|| Some(pending.task_id()))
+                }
+            });
+        // dropping the effect drops the task handle, which unregisters this
+        // boundary
+        Owner::on_cleanup(move || drop(effect));
+    }
+}
+
 fn nonce_or_not() -> Option<Arc<str>> {
     #[cfg(feature = "nonce")]
     {
@@ -612,3 +865,138 @@ where
         self
     }
 }
+
+#[cfg(all(test, feature = "csr"))]
+mod tests {
+    use super::{
+        register_pending_suspense, settle_all, PendingSuspense,
+        SuspenseDisposed, SuspenseHandle,
+    };
+    use any_spawner::Executor;
+    use futures::{channel::oneshot, FutureExt};
+    use reactive_graph::{
+        computed::{suspense::SuspenseContext, ArcAsyncDerived, ArcMemo},
+        owner::{provide_context, Owner},
+        signal::ArcRwSignal,
+        traits::{GetUntracked, ReadUntracked, With},
+    };
+    use slotmap::{DefaultKey, SlotMap};
+    use tokio::task::JoinHandle;
+
+    /// Sets up a boundary with `handle` the way [`Suspense`](super::Suspense)
+    /// does, in which a resource that loads once the returned sender is sent to
+    /// is read.
+    fn loading_boundary(
+        handle: &SuspenseHandle,
+    ) -> (Owner, oneshot::Sender<()>) {
+        let (tx, rx) = oneshot::channel::<()>();
+        let rx = rx.shared();
+        let resource = ArcAsyncDerived::new(move || {
+            let rx = rx.clone();
+            async move {
+                _ = rx.await;
+            }
+        });
+        let boundary = Owner::new();
+        boundary.with(|| {
+            let tasks = ArcRwSignal::new(SlotMap::<DefaultKey, ()>::new());
+            provide_context(SuspenseContext {
+                tasks: tasks.clone(),
+            });
+            _ = resource.try_read_untracked();
+            // as in a view, the resource lives as long as the boundary
+            Owner::on_cleanup(move || drop(resource));
+            let none_pending =
+                ArcMemo::new(move |_| tasks.with(SlotMap::is_empty));
+            handle.attach();
+            register_pending_suspense(&none_pending, Some(handle));
+        });
+        (boundary, tx)
+    }
+
+    fn spawn_settled(
+        handle: &SuspenseHandle,
+    ) -> JoinHandle<Result<(), SuspenseDisposed>> {
+        let handle = handle.clone();
+        tokio::task::spawn_local(async move { handle.settled().await })
+    }
+
+    #[tokio::test]
+    async fn settled_resolves_once_loaded_or_disposed() {
+        _ = Executor::init_tokio();
+        let local = tokio::task::LocalSet::new();
+        local
+            .run_until(async {
+                let owner = Owner::new();
+                owner.set();
+                let handle = SuspenseHandle::new();
+                // before it is passed to a Suspense, nothing is pending
+                assert_eq!(handle.settled().now_or_never(), Some(Ok(())));
+
+                let (boundary, tx) = loading_boundary(&handle);
+                assert!(handle.is_pending().get_untracked());
+                let settled = spawn_settled(&handle);
+                let all_settled = tokio::task::spawn_local(settle_all());
+                Executor::tick().await;
+                assert!(!settled.is_finished() && !all_settled.is_finished());
+                tx.send(()).unwrap();
+                assert_eq!(settled.await.unwrap(), Ok(()));
+                all_settled.await.unwrap();
+                assert!(!handle.is_pending().get_untracked());
+                drop(boundary);
+
+                // a boundary that is disposed while it is loading does not
+                // settle
+                let (boundary, _tx) = loading_boundary(&handle);
+                let settled = spawn_settled(&handle);
+                Executor::tick().await;
+                assert!(settle_all().now_or_never().is_none());
+                // and it is only pending in its own app
+                let other_app = Owner::new_root(None);
+                assert_eq!(other_app.with(settle_all).now_or_never(), Some(()));
+                drop(boundary);
+                Executor::tick().await;
+                assert_eq!(settled.await.unwrap(), Err(SuspenseDisposed));
+                assert_eq!(settle_all().now_or_never(), Some(()));
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn pending_suspense_is_ready_once_the_boundaries_beneath_it_load() {
+        _ = Executor::init_tokio();
+        let local = tokio::task::LocalSet::new();
+        local
+            .run_until(async {
+                let owner = Owner::new();
+                owner.set();
+                let pending = PendingSuspense::new();
+                provide_context(pending.clone());
+                assert_eq!(pending.ready().now_or_never(), Some(()));
+
+                let (first_handle, second_handle) =
+                    (SuspenseHandle::new(), SuspenseHandle::new());
+                let (first, first_tx) = loading_boundary(&first_handle);
+                let (_second, second_tx) = loading_boundary(&second_handle);
+                assert_eq!(pending.pending_count(), 2);
+                let ready = tokio::task::spawn_local({
+                    let pending = pending.clone();
+                    async move { pending.ready().await }
+                });
+                Executor::tick().await;
+                assert!(!ready.is_finished());
+
+                first_tx.send(()).unwrap();
+                assert_eq!(spawn_settled(&first_handle).await.unwrap(), Ok(()));
+                Executor::tick().await;
+                assert_eq!(pending.pending_count(), 1);
+                assert!(!ready.is_finished());
+                drop(first);
+
+                second_tx.send(()).unwrap();
+                ready.await.unwrap();
+                assert!(!pending.is_pending());
+            })
+            .await;
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     children::{TypedChildren, ViewFnOnce},
     error::ErrorBoundarySuspendedChildren,
-    suspense_component::SuspenseBoundary,
+    suspense_component::{register_pending_suspense, SuspenseBoundary},
     IntoView,
 };
 use leptos_macro::component;
@@ -112,6 +112,7 @@ where
                 tasks.with(SlotMap::is_empty)
             }
         });
+        register_pending_suspense(&none_pending, None);
         if let Some(set_pending) = set_pending {
             Effect::new_isomorphic({
                 let none_pending = none_pending.clone();
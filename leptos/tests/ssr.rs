@@ -135,3 +135,146 @@ fn ssr_option() {
 
     assert_eq!(rendered.to_html(), "<option></option>");
 }
+
+#[cfg(feature = "ssr")]
+#[test]
+fn render_to_string_with_options() {
+    use leptos::{
+        prelude::*,
+        ssr::{render_to_string, RenderOptions},
+    };
+
+    let view = || {
+        let (value, _) = signal(1);
+        view! {
+            <p>"Value: " {move || value.get()} "!"</p>
+            <pre>"  keep\n  this"</pre>
+            <br/>
+        }
+    };
+
+    assert_eq!(
+        render_to_string(view, &RenderOptions::new()),
+        "<p>Value: <!>1<!>!</p><pre>  keep\n  this</pre><br>"
+    );
+    assert_eq!(
+        render_to_string(
+            view,
+            &RenderOptions::new()
+                .hydration_markers(false)
+                .wrapper("section")
+        ),
+        "<section><p>Value: 1!</p><pre>  keep\n  this</pre><br></section>"
+    );
+    assert_eq!(
+        render_to_string(
+            view,
+            &RenderOptions::new()
+                .hydration_markers(false)
+                .wrapper("div")
+                .pretty(true)
+        ),
+        "<div>\n  <p>\n    Value: 1!\n  </p>\n  <pre>  keep\n  \
+         this</pre>\n  <br>\n</div>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[tokio::test]
+async fn render_to_string_async_waits_for_resources() {
+    use any_spawner::Executor;
+    use leptos::{
+        prelude::*,
+        ssr::{render_to_string_async, RenderOptions},
+    };
+
+    _ = Executor::init_tokio();
+
+    let view = || {
+        let user = Resource::new(
+            || (),
+            |_| async {
+                tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+                "Ada".to_string()
+            },
+        );
+        view! {
+            <Suspense fallback=|| "Loading...">
+                <p>"Hello, " {move || Suspend::new(async move { user.await })} "!"</p>
+            </Suspense>
+        }
+    };
+
+    let options = RenderOptions::new().hydration_markers(false);
+    let html = render_to_string_async(view, &options).await;
+    assert_eq!(html, "<p>Hello, Ada!</p>");
+    assert!(!html.contains("<!"));
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn try_view_forwards_errors_to_the_error_boundary() {
+    use leptos::prelude::*;
+
+    let total = |a: &'static str| {
+        view! {
+            <ErrorBoundary fallback=|_| view! { <p>"Not a number."</p> }>
+                {try_view! {
+                    let a = a.parse::<i32>()?;
+                    (a > 0).then(|| view! { <p>"Total: " {a}</p> })
+                }}
+            </ErrorBoundary>
+        }
+        .to_html()
+    };
+
+    assert_eq!(total("2"), "<p>Total: <!>2</p>");
+    assert!(!total("-2").contains("<p>"));
+    assert_eq!(total("two"), "<p>Not a number.</p>");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn sandbox_panics_reach_the_error_boundary() {
+    use leptos::prelude::*;
+    use std::sync::{Arc, Mutex};
+
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let render = |plugin: fn() -> AnyView| {
+        let reported = Arc::clone(&reported);
+        view! {
+            <ErrorBoundary fallback=|_| view! { <p>"A plugin failed."</p> }>
+                <Sandbox
+                    fallback=|_| view! { <p>"Unreachable on the server."</p> }
+                    on_panic=move |panic: SandboxPanic| {
+                        reported.lock().unwrap().push(panic.message().to_string())
+                    }
+                >
+                    {plugin()}
+                </Sandbox>
+            </ErrorBoundary>
+            <p>"After the plugin."</p>
+        }
+        .to_html()
+    };
+
+    assert_eq!(
+        render(|| view! { <p>"Plugin"</p> }.into_any()),
+        "<p>Plugin</p><p>After the plugin.</p>"
+    );
+    assert_eq!(
+        render(|| panic!("while creating the view")),
+        "<p>A plugin failed.</p><p>After the plugin.</p>"
+    );
+    assert_eq!(
+        render(|| {
+            (move || -> &'static str { panic!("while rendering the view") })
+                .into_any()
+        }),
+        "<p>A plugin failed.</p><p>After the plugin.</p>"
+    );
+    assert_eq!(
+        *reported.lock().unwrap(),
+        ["while creating the view", "while rendering the view"]
+    );
+}
@@ -3,10 +3,15 @@
 use or_poisoned::OrPoisoned;
 #[cfg(debug_assertions)]
 use reactive_graph::diagnostics::SpecialNonReactiveZone;
-use reactive_graph::owner::Owner;
+use reactive_graph::{
+    effect::Effect, owner::Owner, traits::Get, wrappers::read::Signal,
+};
 use send_wrapper::SendWrapper;
-use std::time::Duration;
-use tachys::html::event::EventDescriptor;
+use std::{rc::Rc, time::Duration};
+use tachys::{
+    html::{element::ElementType, event::EventDescriptor},
+    reactive_graph::node_ref::NodeRef,
+};
 #[cfg(feature = "tracing")]
 use tracing::instrument;
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
@@ -593,6 +598,276 @@ impl WindowListenerHandle {
     }
 }
 
+/// Options for [`use_window_event_with_options`],
+/// [`use_document_event_with_options`], and
+/// [`use_event_listener_with_options`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UseEventListenerOptions {
+    once: bool,
+    passive: bool,
+    capture: bool,
+    enabled: Option<Signal<bool>>,
+}
+
+impl UseEventListenerOptions {
+    /// Removes the listener after it has been called once.
+    pub fn once(mut self, once: bool) -> Self {
+        self.once = once;
+        self
+    }
+
+    /// Marks the listener as passive, meaning it will never call
+    /// `preventDefault()`.
+    pub fn passive(mut self, passive: bool) -> Self {
+        self.passive = passive;
+        self
+    }
+
+    /// Handles the event during the capture phase, rather than the bubbling
+    /// phase.
+    pub fn capture(mut self, capture: bool) -> Self {
+        self.capture = capture;
+        self
+    }
+
+    /// Only listens for the event while this signal is `true`. The listener is
+    /// removed when it becomes `false`, and added again when it becomes `true`.
+    pub fn enabled(mut self, enabled: impl Into<Signal<bool>>) -> Self {
+        self.enabled = Some(enabled.into());
+        self
+    }
+}
+
+/// Adds an event listener to the `Window` for as long as the current reactive
+/// owner is alive.
+///
+/// The listener is only added in the browser, once the component has mounted,
+/// and is removed automatically when the owner is disposed, so there's no
+/// handle to keep track of.
+/// ```
+/// use leptos::{
+///     ev, leptos_dom::helpers::use_window_event, logging::log, prelude::*,
+/// };
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     use_window_event(ev::keydown, |ev| {
+///         log!("key = {:?}", ev.key());
+///     });
+/// }
+/// ```
+///
+/// ### Note about Context
+///
+/// The callback is called outside of the reactive ownership tree. This means
+/// that it does not have access to context via
+/// [`use_context`](reactive_graph::owner::use_context). If you want to use
+/// context inside the callback, you should either call `use_context` in the
+/// body of the component, and move the value into the callback, or access the
+/// current owner inside the component body using
+/// [`Owner::current`](reactive_graph::owner::Owner::current) and reestablish it
+/// in the callback with [`Owner::with`](reactive_graph::owner::Owner::with).
+pub fn use_window_event<E>(event: E, cb: impl Fn(E::EventType) + 'static)
+where
+    E: EventDescriptor + 'static,
+    E::EventType: JsCast,
+{
+    use_window_event_with_options(event, cb, Default::default())
+}
+
+/// Like [`use_window_event`], with the given [`UseEventListenerOptions`].
+pub fn use_window_event_with_options<E>(
+    event: E,
+    cb: impl Fn(E::EventType) + 'static,
+    options: UseEventListenerOptions,
+) where
+    E: EventDescriptor + 'static,
+    E::EventType: JsCast,
+{
+    use_event_listener_on_target(
+        || Some(window().into()),
+        &event.name(),
+        cb,
+        options,
+    )
+}
+
+/// Adds an event listener to the `Document` for as long as the current reactive
+/// owner is alive.
+///
+/// See [`use_window_event`] for details.
+pub fn use_document_event<E>(event: E, cb: impl Fn(E::EventType) + 'static)
+where
+    E: EventDescriptor + 'static,
+    E::EventType: JsCast,
+{
+    use_document_event_with_options(event, cb, Default::default())
+}
+
+/// Like [`use_document_event`], with the given [`UseEventListenerOptions`].
+pub fn use_document_event_with_options<E>(
+    event: E,
+    cb: impl Fn(E::EventType) + 'static,
+    options: UseEventListenerOptions,
+) where
+    E: EventDescriptor + 'static,
+    E::EventType: JsCast,
+{
+    use_event_listener_on_target(
+        || Some(document().into()),
+        &event.name(),
+        cb,
+        options,
+    )
+}
+
+/// Adds an event listener to the element referenced by a [`NodeRef`] for as
+/// long as the current reactive owner is alive.
+///
+/// The `NodeRef` does not need to be filled in yet: the listener is added once
+/// the element is mounted, and moved to the new element if the reference
+/// changes.
+/// ```
+/// use leptos::{
+///     ev, html, leptos_dom::helpers::use_event_listener, logging::log,
+///     prelude::*,
+/// };
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     let input = NodeRef::<html::Input>::new();
+///     use_event_listener(input, ev::focus, |_| log!("focused"));
+///
+///     view! { <input node_ref=input/> }
+/// }
+/// ```
+pub fn use_event_listener<El, E>(
+    target: NodeRef<El>,
+    event: E,
+    cb: impl Fn(E::EventType) + 'static,
+) where
+    El: ElementType + 'static,
+    El::Output: JsCast + Clone + 'static,
+    E: EventDescriptor + 'static,
+    E::EventType: JsCast,
+{
+    use_event_listener_with_options(target, event, cb, Default::default())
+}
+
+/// Like [`use_event_listener`], with the given [`UseEventListenerOptions`].
+pub fn use_event_listener_with_options<El, E>(
+    target: NodeRef<El>,
+    event: E,
+    cb: impl Fn(E::EventType) + 'static,
+    options: UseEventListenerOptions,
+) where
+    El: ElementType + 'static,
+    El::Output: JsCast + Clone + 'static,
+    E: EventDescriptor + 'static,
+    E::EventType: JsCast,
+{
+    use_event_listener_on_target(
+        move || target.get().map(|el| el.unchecked_into()),
+        &event.name(),
+        cb,
+        options,
+    )
+}
+
+fn use_event_listener_on_target<T>(
+    target: impl Fn() -> Option<web_sys::EventTarget> + 'static,
+    event_name: &str,
+    cb: impl Fn(T) + 'static,
+    options: UseEventListenerOptions,
+) where
+    T: JsCast,
+{
+    let event_name = event_name.to_string();
+    let cb: Rc<dyn Fn(web_sys::Event)> =
+        Rc::new(move |e: web_sys::Event| cb(e.unchecked_into::<T>()));
+
+    // effects only run in the browser, after the component has mounted, and run
+    // their cleanups before rerunning and when their owner is disposed
+    Effect::new(move |_| {
+        connect_listener(
+            options.enabled,
+            &target,
+            |target| {
+                let cb = add_listener(&target, &event_name, &cb, options);
+                (target, cb)
+            },
+            {
+                let event_name = event_name.clone();
+                let capture = options.capture;
+                move |(target, cb): (web_sys::EventTarget, JsValue)| {
+                    _ = target.remove_event_listener_with_callback_and_bool(
+                        &event_name,
+                        cb.unchecked_ref(),
+                        capture,
+                    );
+                }
+            },
+        )
+    });
+}
+
+/// Adds `cb` as a listener for `event_name` to `target` with `options`,
+/// returning the JS function that has to be passed to remove it.
+fn add_listener(
+    target: &web_sys::EventTarget,
+    event_name: &str,
+    cb: &Rc<dyn Fn(web_sys::Event)>,
+    options: UseEventListenerOptions,
+) -> JsValue {
+    let cb = Rc::clone(cb);
+    let cb = Closure::<dyn Fn(web_sys::Event)>::new(move |e| {
+        #[cfg(debug_assertions)]
+        let _z = SpecialNonReactiveZone::enter();
+        cb(e);
+    })
+    .into_js_value();
+
+    let listener_options = web_sys::AddEventListenerOptions::new();
+    listener_options.set_once(options.once);
+    listener_options.set_passive(options.passive);
+    listener_options.set_capture(options.capture);
+    _ = target.add_event_listener_with_callback_and_add_event_listener_options(
+        event_name,
+        cb.unchecked_ref(),
+        &listener_options,
+    );
+    cb
+}
+
+/// Adds a listener to the current target with `add`, if the listener is
+/// `enabled` and there is a target, and removes it with `remove` when the
+/// current owner is cleaned up.
+///
+/// This is what the effect of [`use_event_listener_on_target`] does each time
+/// it runs: it reads the target and `enabled`, so it runs again, after cleaning
+/// up, whenever either changes.
+fn connect_listener<T, L, R>(
+    enabled: Option<Signal<bool>>,
+    target: &dyn Fn() -> Option<T>,
+    add: impl FnOnce(T) -> L,
+    remove: R,
+) where
+    L: 'static,
+    R: FnOnce(L) + 'static,
+{
+    if !enabled.map(|enabled| enabled.get()).unwrap_or(true) {
+        return;
+    }
+    let Some(target) = target() else {
+        return;
+    };
+    let listener = SendWrapper::new((add(target), remove));
+    Owner::on_cleanup(move || {
+        let (listener, remove) = listener.take();
+        remove(listener);
+    });
+}
+
 fn is_server() -> bool {
     #[cfg(feature = "hydration")]
     {
@@ -605,3 +880,102 @@ fn is_server() -> bool {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::connect_listener;
+    use reactive_graph::{
+        owner::Owner,
+        signal::RwSignal,
+        traits::{Get, Set},
+        wrappers::read::Signal,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    /// Connects a listener to `target` in `effect`, as the effect of a listener
+    /// does each time it runs (after cleaning up its last run), logging when it
+    /// is added and removed.
+    fn run(
+        effect: &Owner,
+        log: &Log,
+        enabled: Option<Signal<bool>>,
+        target: impl Fn() -> Option<&'static str>,
+    ) {
+        effect.cleanup();
+        let (added, removed) = (Rc::clone(log), Rc::clone(log));
+        effect.with(|| {
+            connect_listener(
+                enabled,
+                &target,
+                move |target| {
+                    added.borrow_mut().push(format!("add {target}"));
+                    target
+                },
+                move |target| {
+                    removed.borrow_mut().push(format!("remove {target}"))
+                },
+            )
+        });
+    }
+
+    #[test]
+    fn listener_is_added_once_the_node_ref_is_filled_in() {
+        let owner = Owner::new();
+        owner.set();
+        // stands in for the signal of a `NodeRef`, which is set once its
+        // element is mounted
+        let node_ref = RwSignal::new(None);
+        let target = move || node_ref.get();
+        let log = Log::default();
+
+        let effect = Owner::new();
+        run(&effect, &log, None, target);
+        assert!(log.borrow().is_empty());
+
+        node_ref.set(Some("input"));
+        run(&effect, &log, None, target);
+        assert_eq!(*log.borrow(), ["add input"]);
+
+        // and moved to the new element when it is replaced
+        node_ref.set(Some("textarea"));
+        run(&effect, &log, None, target);
+        assert_eq!(
+            *log.borrow(),
+            ["add input", "remove input", "add textarea"]
+        );
+    }
+
+    #[test]
+    fn listener_is_removed_when_its_owner_is_disposed() {
+        let log = Log::default();
+        let owner = Owner::new();
+        let effect = owner.with(Owner::new);
+        run(&effect, &log, None, || Some("window"));
+        assert_eq!(*log.borrow(), ["add window"]);
+
+        drop(owner);
+        assert_eq!(*log.borrow(), ["add window", "remove window"]);
+    }
+
+    #[test]
+    fn listener_is_only_added_while_it_is_enabled() {
+        let owner = Owner::new();
+        owner.set();
+        let enabled = RwSignal::new(false);
+        let log = Log::default();
+
+        let effect = Owner::new();
+        run(&effect, &log, Some(enabled.into()), || Some("document"));
+        assert!(log.borrow().is_empty());
+
+        enabled.set(true);
+        run(&effect, &log, Some(enabled.into()), || Some("document"));
+        assert_eq!(*log.borrow(), ["add document"]);
+
+        enabled.set(false);
+        run(&effect, &log, Some(enabled.into()), || Some("document"));
+        assert_eq!(*log.borrow(), ["add document", "remove document"]);
+    }
+}
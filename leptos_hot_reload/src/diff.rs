@@ -1,4 +1,4 @@
-use crate::node::{LAttributeValue, LNode};
+use crate::node::{html_attrs, LAttributeValue, LNode};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
@@ -37,16 +37,11 @@ impl LNode {
                     children,
                 } => ReplacementNode::Element {
                     name: name.to_owned(),
-                    attrs: attrs
-                        .iter()
-                        .filter_map(|(name, value)| match value {
-                            LAttributeValue::Boolean => {
-                                Some((name.to_owned(), name.to_owned()))
-                            }
-                            LAttributeValue::Static(value) => {
-                                Some((name.to_owned(), value.to_owned()))
-                            }
-                            _ => None,
+                    attrs: html_attrs(attrs)
+                        .into_iter()
+                        .map(|(name, value)| {
+                            let value = value.unwrap_or_else(|| name.clone());
+                            (name, value)
                         })
                         .collect(),
                     children: children
@@ -185,43 +180,52 @@ impl LNode {
         old: &'a [(String, LAttributeValue)],
         new: &'a [(String, LAttributeValue)],
     ) -> impl Iterator<Item = Patch> + 'a {
-        let additions = new
-            .iter()
-            .filter_map(|(name, new_value)| {
-                let old_attr = old.iter().find(|(o_name, _)| o_name == name);
-                let replace = match old_attr {
-                    None => true,
-                    Some((_, old_value)) if old_value != new_value => true,
-                    _ => false,
-                };
-                if replace {
-                    match &new_value {
-                        LAttributeValue::Boolean => {
-                            Some((name.to_owned(), String::new()))
-                        }
-                        LAttributeValue::Static(s) => {
-                            Some((name.to_owned(), s.to_owned()))
-                        }
-                        _ => None,
+        let additions = new.iter().filter_map(|(name, new_value)| {
+            let old_attr = old.iter().find(|(o_name, _)| o_name == name);
+            let replace = match old_attr {
+                None => true,
+                Some((_, old_value)) if old_value != new_value => true,
+                _ => false,
+            };
+            if !replace {
+                return None;
+            }
+            let action = match &new_value {
+                LAttributeValue::Boolean => {
+                    PatchAction::SetAttribute(name.to_owned(), String::new())
+                }
+                LAttributeValue::Static(s) => {
+                    if let Some(class) = name.strip_prefix("class:") {
+                        PatchAction::ToggleClass(class.to_owned(), s == "true")
+                    } else if let Some(property) = name.strip_prefix("style:") {
+                        PatchAction::SetStyle(property.to_owned(), s.to_owned())
+                    } else {
+                        PatchAction::SetAttribute(name.to_owned(), s.to_owned())
                     }
-                } else {
-                    None
                 }
-            })
-            .map(|(name, value)| Patch {
+                _ => return None,
+            };
+            Some(Patch {
                 path: path.to_owned(),
-                action: PatchAction::SetAttribute(name, value),
-            });
+                action,
+            })
+        });
 
         let removals = old.iter().filter_map(|(name, _)| {
             if new.iter().any(|(new_name, _)| new_name == name) {
-                None
-            } else {
-                Some(Patch {
-                    path: path.to_owned(),
-                    action: PatchAction::RemoveAttribute(name.to_owned()),
-                })
+                return None;
             }
+            let action = if let Some(class) = name.strip_prefix("class:") {
+                PatchAction::ToggleClass(class.to_owned(), false)
+            } else if let Some(property) = name.strip_prefix("style:") {
+                PatchAction::RemoveStyle(property.to_owned())
+            } else {
+                PatchAction::RemoveAttribute(name.to_owned())
+            };
+            Some(Patch {
+                path: path.to_owned(),
+                action,
+            })
         });
 
         additions.chain(removals)
@@ -347,21 +351,156 @@ impl LNode {
     }
 }
 
+impl LNode {
+    /// The changes from `self` to `other` that cannot be patched in the
+    /// browser, because they change the Rust code in the view with the
+    /// given `id`.
+    ///
+    /// Rust code that is removed, or only moved around, is already in the
+    /// compiled app, so only new code is reported.
+    #[must_use]
+    pub fn unsupported_changes(
+        &self,
+        other: &LNode,
+        id: &str,
+    ) -> Vec<UnsupportedChange> {
+        let mut old = Vec::new();
+        self.add_rust_code(id, &mut old);
+        let mut new = Vec::new();
+        other.add_rust_code(id, &mut new);
+        new.into_iter()
+            .filter(|change| match old.iter().position(|old| old == change) {
+                Some(idx) => {
+                    old.swap_remove(idx);
+                    false
+                }
+                None => true,
+            })
+            .collect()
+    }
+
+    fn add_rust_code(&self, id: &str, code: &mut Vec<UnsupportedChange>) {
+        match self {
+            LNode::Fragment(children) => {
+                for child in children {
+                    child.add_rust_code(id, code);
+                }
+            }
+            LNode::Text(_) => {}
+            LNode::Element {
+                attrs, children, ..
+            } => {
+                for (name, value) in attrs {
+                    if let LAttributeValue::Dynamic(value) = value {
+                        code.push(UnsupportedChange::Attribute {
+                            view: id.to_owned(),
+                            name: name.to_owned(),
+                            code: value.to_owned(),
+                        });
+                    }
+                }
+                for child in children {
+                    child.add_rust_code(id, code);
+                }
+            }
+            LNode::Component {
+                name,
+                props,
+                children,
+            } => {
+                code.push(UnsupportedChange::Component {
+                    view: id.to_owned(),
+                    name: name.to_owned(),
+                    props: props.to_owned(),
+                });
+                for child in children {
+                    child.add_rust_code(id, code);
+                }
+            }
+            LNode::DynChild(block) => code.push(UnsupportedChange::Block {
+                view: id.to_owned(),
+                code: block.to_owned(),
+            }),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Patches(pub Vec<(String, Vec<Patch>)>);
 
+/// A change to a file that hot-reloading cannot patch, so that the app has
+/// to be rebuilt.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnsupportedChange {
+    /// `view!` macros were added or removed.
+    ViewCount { before: usize, after: usize },
+    /// A view has a new or changed `{block}`.
+    Block { view: String, code: String },
+    /// A view has a new or changed attribute with a Rust expression as its
+    /// value.
+    Attribute {
+        view: String,
+        name: String,
+        code: String,
+    },
+    /// A view has a new component, or a component with new or changed props.
+    Component {
+        view: String,
+        name: String,
+        props: Vec<(String, String)>,
+    },
+}
+
+impl std::fmt::Display for UnsupportedChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnsupportedChange::ViewCount { before, after } => write!(
+                f,
+                "the number of views changed from {before} to {after}"
+            ),
+            UnsupportedChange::Block { view, code } => {
+                write!(f, "{view}: the block {{{code}}} is new or changed")
+            }
+            UnsupportedChange::Attribute { view, name, code } => write!(
+                f,
+                "{view}: the attribute {name}={{{code}}} is new or changed"
+            ),
+            UnsupportedChange::Component { view, name, .. } => write!(
+                f,
+                "{view}: the component <{name}/> or its props are new or \
+                 changed"
+            ),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Patch {
     path: Vec<usize>,
     action: PatchAction,
 }
 
+impl Patch {
+    /// The path to the node to patch, as child indices from the view's root.
+    pub fn path(&self) -> &[usize] {
+        &self.path
+    }
+
+    /// What to do to the node.
+    pub fn action(&self) -> &PatchAction {
+        &self.action
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PatchAction {
     ReplaceWith(ReplacementNode),
     ChangeTagName(String),
     RemoveAttribute(String),
     SetAttribute(String, String),
+    ToggleClass(String, bool),
+    SetStyle(String, String),
+    RemoveStyle(String),
     SetText(String),
     ClearChildren,
     AppendChildren(Vec<ReplacementNode>),
@@ -2,7 +2,7 @@ extern crate proc_macro;
 
 use anyhow::Result;
 use camino::Utf8PathBuf;
-use diff::Patches;
+use diff::{Patches, UnsupportedChange};
 use node::LNode;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
@@ -70,27 +70,33 @@ impl ViewMacros {
         let mut file = File::open(path)?;
         let mut content = String::new();
         file.read_to_string(&mut content)?;
-        let ast = syn::parse_file(&content)?;
+        Self::parse_source(path, &content)
+    }
+
+    /// Parses the `view!` macros in `content`, the source of the file at
+    /// `path`, including the views nested inside other views.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the contents of the file cannot be parsed.
+    pub fn parse_source(
+        path: &Utf8PathBuf,
+        content: &str,
+    ) -> Result<Vec<MacroInvocation>> {
+        let ast = syn::parse_file(content)?;
 
         let mut visitor = ViewMacroVisitor::default();
         visitor.visit_file(&ast);
         let mut views = Vec::new();
         for view in visitor.views {
             let span = view.span();
-            let id = span_to_stable_id(path, span.start().line);
-            if view.tokens.is_empty() {
-                views.push(MacroInvocation {
-                    id,
-                    template: LNode::Fragment(Vec::new()),
-                });
-            } else {
-                let tokens = view.tokens.clone().into_iter();
-                // TODO handle class = ...
-                let rsx = rstml::parse2(
-                    tokens.collect::<proc_macro2::TokenStream>(),
-                )?;
-                let template = LNode::parse_view(rsx)?;
-                views.push(MacroInvocation { id, template });
+            views.push(MacroInvocation::parse(
+                path,
+                span.start().line,
+                view.tokens.clone(),
+            )?);
+            for (line, tokens) in parsing::nested_views(view.tokens.clone()) {
+                views.push(MacroInvocation::parse(path, line, tokens)?);
             }
         }
         Ok(views)
@@ -100,45 +106,103 @@ impl ViewMacros {
     ///
     /// Will return `Err` if the contents of the file cannot be parsed.
     pub fn patch(&self, path: &Utf8PathBuf) -> Result<Option<Patches>> {
+        Ok(match self.hot_reload(path)? {
+            Some(HotReload::Patch(patches)) => Some(patches),
+            Some(HotReload::Rebuild(_)) | None => None,
+        })
+    }
+
+    /// Decides how the changes to the file at `path` can be shown: either
+    /// by patching the views in the browser, or only by rebuilding the app,
+    /// for the reasons given. Returns `None` if the file was not known
+    /// before.
+    ///
+    /// Once the changes have been patched, the known views of the file are
+    /// updated; if they need a rebuild, the old views are kept, because they
+    /// are what is still running in the browser.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the contents of the file cannot be parsed.
+    pub fn hot_reload(&self, path: &Utf8PathBuf) -> Result<Option<HotReload>> {
         let new_views = Self::parse_file(path)?;
         let mut lock = self.views.write();
-        let diffs = match lock.get(path) {
-            None => return Ok(None),
-            Some(current_views) => {
-                if current_views.len() == new_views.len() {
-                    let mut diffs = Vec::new();
-                    for (current_view, new_view) in
-                        current_views.iter().zip(&new_views)
-                    {
-                        if current_view.id == new_view.id
-                            && current_view.template != new_view.template
-                        {
-                            diffs.push((
-                                current_view.id.clone(),
-                                current_view.template.diff(&new_view.template),
-                            ));
-                        }
-                    }
-                    diffs
-                } else {
-                    return Ok(None);
-                }
-            }
+        let Some(current_views) = lock.get(path) else {
+            return Ok(None);
         };
+        let hot_reload = Self::compare(current_views, &new_views);
+        if let HotReload::Patch(_) = hot_reload {
+            lock.insert(path.clone(), new_views);
+        }
+        Ok(Some(hot_reload))
+    }
 
-        // update the status to the new views
-        lock.insert(path.clone(), new_views);
-
-        Ok(Some(Patches(diffs)))
+    fn compare(
+        current_views: &[MacroInvocation],
+        new_views: &[MacroInvocation],
+    ) -> HotReload {
+        if current_views.len() != new_views.len() {
+            return HotReload::Rebuild(vec![UnsupportedChange::ViewCount {
+                before: current_views.len(),
+                after: new_views.len(),
+            }]);
+        }
+        let mut diffs = Vec::new();
+        let mut unsupported = Vec::new();
+        for (current_view, new_view) in current_views.iter().zip(new_views) {
+            if current_view.id == new_view.id
+                && current_view.template != new_view.template
+            {
+                unsupported.extend(
+                    current_view
+                        .template
+                        .unsupported_changes(&new_view.template, &new_view.id),
+                );
+                diffs.push((
+                    current_view.id.clone(),
+                    current_view.template.diff(&new_view.template),
+                ));
+            }
+        }
+        if unsupported.is_empty() {
+            HotReload::Patch(Patches(diffs))
+        } else {
+            HotReload::Rebuild(unsupported)
+        }
     }
 }
 
+/// How the changes to a file can be shown in the browser.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HotReload {
+    /// The views can be patched in place.
+    Patch(Patches),
+    /// The Rust code changed, so the app has to be rebuilt.
+    Rebuild(Vec<UnsupportedChange>),
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct MacroInvocation {
     id: String,
     template: LNode,
 }
 
+impl MacroInvocation {
+    fn parse(
+        path: &Utf8PathBuf,
+        line: usize,
+        tokens: proc_macro2::TokenStream,
+    ) -> Result<Self> {
+        let id = span_to_stable_id(path, line);
+        let template = if tokens.is_empty() {
+            LNode::Fragment(Vec::new())
+        } else {
+            LNode::parse_view(rstml::parse2(tokens)?)?
+        };
+        Ok(MacroInvocation { id, template })
+    }
+}
+
 impl core::fmt::Debug for MacroInvocation {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("MacroInvocation")
@@ -172,3 +236,128 @@ pub fn span_to_stable_id(path: impl AsRef<Path>, line: usize) -> String {
         .replace(['/', '\\'], "-");
     format!("{file}-{line}")
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        diff::{PatchAction, UnsupportedChange},
+        HotReload, ViewMacros,
+    };
+    use camino::Utf8PathBuf;
+
+    /// Decides how the change from the source `before` to `after` is
+    /// hot-reloaded.
+    fn hot_reload(before: &str, after: &str) -> HotReload {
+        let path = Utf8PathBuf::from("src/app.rs");
+        let before = ViewMacros::parse_source(&path, before).unwrap();
+        let after = ViewMacros::parse_source(&path, after).unwrap();
+        ViewMacros::compare(&before, &after)
+    }
+
+    /// Wraps the body of a `view!` in an `app` function.
+    fn app(view: &str) -> String {
+        format!("fn app() -> impl IntoView {{\n    view! {{ {view} }}\n}}")
+    }
+
+    fn actions(hot_reload: HotReload) -> Vec<PatchAction> {
+        match hot_reload {
+            HotReload::Patch(patches) => patches
+                .0
+                .into_iter()
+                .flat_map(|(_, patches)| patches)
+                .map(|patch| patch.action().clone())
+                .collect(),
+            HotReload::Rebuild(reasons) => {
+                panic!("expected a patch, but needs a rebuild: {reasons:?}")
+            }
+        }
+    }
+
+    fn reasons(hot_reload: HotReload) -> Vec<UnsupportedChange> {
+        match hot_reload {
+            HotReload::Rebuild(reasons) => reasons,
+            HotReload::Patch(patches) => {
+                panic!("expected a rebuild, but can be patched: {patches:?}")
+            }
+        }
+    }
+
+    #[test]
+    fn class_and_style_directives_are_patched() {
+        let before = app(
+            r#"<p class:active=true style:color="red" style:margin="0">{count}</p>"#,
+        );
+        let after = app(
+            r#"<p class:active=false class:big=true style:color="blue">{count}</p>"#,
+        );
+        assert_eq!(
+            actions(hot_reload(&before, &after)),
+            [
+                PatchAction::ToggleClass("active".into(), false),
+                PatchAction::ToggleClass("big".into(), true),
+                PatchAction::SetStyle("color".into(), "blue".into()),
+                PatchAction::RemoveStyle("margin".into()),
+            ]
+        );
+
+        // but a directive with a new Rust expression cannot be patched
+        let after = app(r#"<p class:active=is_active>{count}</p>"#);
+        assert_eq!(
+            reasons(hot_reload(&before, &after)),
+            [UnsupportedChange::Attribute {
+                view: "src-app.rs-2".into(),
+                name: "class:active".into(),
+                code: "is_active".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn nested_and_island_views_are_patched_on_their_own() {
+        let source = |text: &str| {
+            format!(
+                "#[island]\nfn Counter() -> impl IntoView {{\n    view! {{\n        \
+                 <button>{{move || if on() {{ view! {{ <b>\"{text}\"</b> }} }} \
+                 else {{ view! {{ <i>\"off\"</i> }} }}}}</button>\n    }}\n}}"
+            )
+        };
+        let HotReload::Patch(patches) =
+            hot_reload(&source("on"), &source("still on"))
+        else {
+            panic!("an edit inside a nested view should be patched");
+        };
+        // only the nested view changed; the block around it did not
+        assert_eq!(patches.0.len(), 1);
+        assert_eq!(patches.0[0].0, "src-app.rs-4");
+        assert_eq!(
+            actions(HotReload::Patch(patches)),
+            [PatchAction::SetText("still on".into())]
+        );
+
+        // editing the code around the nested views still needs a rebuild
+        let changed = source("on").replace("on()", "enabled()");
+        assert!(matches!(
+            reasons(hot_reload(&source("on"), &changed)).as_slice(),
+            [UnsupportedChange::Block { view, .. }] if view == "src-app.rs-3"
+        ));
+    }
+
+    #[test]
+    fn adding_a_view_needs_a_rebuild() {
+        let before = app("<p>one</p>");
+        let after =
+            format!("{before}\nfn other() {{ view! {{ <p>two</p> }} }}");
+        let reasons = reasons(hot_reload(&before, &after));
+        assert_eq!(
+            reasons,
+            [UnsupportedChange::ViewCount {
+                before: 1,
+                after: 2
+            }]
+        );
+        assert_eq!(
+            reasons[0].to_string(),
+            "the number of views changed from 1 to 2"
+        );
+    }
+}
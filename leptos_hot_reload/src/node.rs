@@ -1,4 +1,4 @@
-use crate::parsing::is_component_node;
+use crate::parsing::{is_component_node, strip_nested_views};
 use anyhow::Result;
 use quote::ToTokens;
 use rstml::node::{Node, NodeAttribute};
@@ -32,9 +32,13 @@ pub enum LNode {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum LAttributeValue {
     Boolean,
+    /// A literal value. The literal values of `class:` and `style:` directives,
+    /// like `class:active=true` and `style:color="red"`, are patched as classes
+    /// and style properties.
     Static(String),
-    // safely ignored
-    Dynamic,
+    /// A Rust expression, which cannot be patched: if it changes, the app must
+    /// be rebuilt.
+    Dynamic(String),
     Noop,
 }
 
@@ -71,7 +75,7 @@ impl LNode {
             }
             Node::Block(block) => {
                 views.push(LNode::DynChild(
-                    block.into_token_stream().to_string(),
+                    strip_nested_views(block.into_token_stream()).to_string(),
                 ));
             }
             Node::Element(el) => {
@@ -90,7 +94,14 @@ impl LNode {
                             .filter_map(|attr| match attr {
                                 NodeAttribute::Attribute(attr) => Some((
                                     attr.key.to_string(),
-                                    format!("{:#?}", attr.value()),
+                                    attr.value()
+                                        .map(|value| {
+                                            strip_nested_views(
+                                                value.to_token_stream(),
+                                            )
+                                            .to_string()
+                                        })
+                                        .unwrap_or_default(),
                                 )),
                                 NodeAttribute::Block(_) => None,
                             })
@@ -109,8 +120,18 @@ impl LNode {
                                     name,
                                     LAttributeValue::Static(value),
                                 ));
+                            } else if let Some(value) = attr.value() {
+                                attrs.push((
+                                    name,
+                                    LAttributeValue::Dynamic(
+                                        strip_nested_views(
+                                            value.to_token_stream(),
+                                        )
+                                        .to_string(),
+                                    ),
+                                ));
                             } else {
-                                attrs.push((name, LAttributeValue::Dynamic));
+                                attrs.push((name, LAttributeValue::Boolean));
                             }
                         }
                     }
@@ -153,16 +174,11 @@ impl LNode {
                 // I wouldn't do this for real code, but this is just for dev mode
                 let is_self_closing = children.is_empty();
 
-                let attrs = attrs
-                    .iter()
-                    .filter_map(|(name, value)| match value {
-                        LAttributeValue::Boolean => Some(format!("{name} ")),
-                        LAttributeValue::Static(value) => {
-                            Some(format!("{name}=\"{value}\" "))
-                        }
-                        LAttributeValue::Dynamic | LAttributeValue::Noop => {
-                            None
-                        }
+                let attrs = html_attrs(attrs)
+                    .into_iter()
+                    .map(|(name, value)| match value {
+                        None => format!("{name} "),
+                        Some(value) => format!("{name}=\"{value}\" "),
                     })
                     .collect::<String>();
 
@@ -178,3 +194,49 @@ impl LNode {
         }
     }
 }
+
+/// The attributes of an element that can be rendered as HTML, with `None` as
+/// the value of a boolean attribute.
+///
+/// The literal `class:` and `style:` directives are merged into the `class` and
+/// `style` attributes, and dynamic attributes are left out.
+pub(crate) fn html_attrs(
+    attrs: &[(String, LAttributeValue)],
+) -> Vec<(String, Option<String>)> {
+    let mut html = Vec::new();
+    let mut classes = Vec::new();
+    let mut styles = Vec::new();
+    for (name, value) in attrs {
+        match value {
+            LAttributeValue::Static(value) => {
+                if let Some(class) = name.strip_prefix("class:") {
+                    if value == "true" {
+                        classes.push(class.to_owned());
+                    }
+                } else if let Some(property) = name.strip_prefix("style:") {
+                    styles.push(format!("{property}: {value};"));
+                } else {
+                    html.push((name.to_owned(), Some(value.to_owned())));
+                }
+            }
+            LAttributeValue::Boolean => html.push((name.to_owned(), None)),
+            LAttributeValue::Dynamic(_) | LAttributeValue::Noop => {}
+        }
+    }
+    for (name, directives, separator) in
+        [("class", classes, " "), ("style", styles, " ")]
+    {
+        if directives.is_empty() {
+            continue;
+        }
+        let directives = directives.join(separator);
+        match html.iter_mut().find(|(attr, _)| attr == name) {
+            Some((_, Some(value))) => {
+                value.push_str(separator);
+                value.push_str(&directives);
+            }
+            _ => html.push((name.to_owned(), Some(directives))),
+        }
+    }
+    html
+}
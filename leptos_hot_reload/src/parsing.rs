@@ -1,3 +1,4 @@
+use proc_macro2::{Group, TokenStream, TokenTree};
 use rstml::node::{CustomNode, NodeElement, NodeName};
 
 /// Converts `syn::Block` to simple expression
@@ -68,3 +69,69 @@ pub fn is_component_tag_name(name: &NodeName) -> bool {
 pub fn is_component_node(node: &NodeElement<impl CustomNode>) -> bool {
     is_component_tag_name(node.name())
 }
+
+/// Replaces the body of each `view!` macro nested in `tokens` with an empty
+/// group.
+///
+/// Nested views are hot-reloaded on their own, so an edit inside one of them
+/// should not change the Rust code of the block or attribute around it.
+#[must_use]
+pub fn strip_nested_views(tokens: TokenStream) -> TokenStream {
+    let mut out = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Group(group) => {
+                let mut stripped = Group::new(
+                    group.delimiter(),
+                    strip_nested_views(group.stream()),
+                );
+                stripped.set_span(group.span());
+                out.push(TokenTree::Group(stripped));
+            }
+            TokenTree::Ident(ident) if ident == "view" => {
+                out.push(TokenTree::Ident(ident));
+                if matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '!')
+                {
+                    out.extend(tokens.next());
+                    if let Some(TokenTree::Group(body)) = tokens.next() {
+                        let mut empty =
+                            Group::new(body.delimiter(), TokenStream::new());
+                        empty.set_span(body.span());
+                        out.push(TokenTree::Group(empty));
+                    }
+                }
+            }
+            token => out.push(token),
+        }
+    }
+    out.into_iter().collect()
+}
+
+/// Finds each `view!` macro nested in `tokens`, like the views returned from
+/// closures inside another view, which `syn` does not visit. Each is returned
+/// with the line it starts on and its body.
+#[must_use]
+pub fn nested_views(tokens: TokenStream) -> Vec<(usize, TokenStream)> {
+    let mut views = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Group(group) => {
+                views.extend(nested_views(group.stream()));
+            }
+            TokenTree::Ident(ident) if ident == "view" => {
+                if matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '!')
+                {
+                    tokens.next();
+                    if let Some(TokenTree::Group(body)) = tokens.next() {
+                        views.push((ident.span().start().line, body.stream()));
+                        views.extend(nested_views(body.stream()));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    views
+}
@@ -1,4 +1,32 @@
 console.log("[HOT RELOADING] Connected to server.\n\nNote: `cargo-leptos watch --hot-reload` only works with the `nightly` feature enabled on Leptos.");
+function rebuild(json) {
+  try {
+    const reasons = JSON.parse(json);
+    console.warn(
+      "[HOT RELOADING] These changes can't be hot-reloaded, so the app is being rebuilt:\n" +
+        reasons.map((reason) => `  - ${describeChange(reason)}`).join("\n")
+    );
+  } catch (e) {
+    console.warn("[HOT RELOADING] Error: ", e);
+  }
+
+  function describeChange(reason) {
+    if (reason.ViewCount) {
+      const { before, after } = reason.ViewCount;
+      return `the number of views changed from ${before} to ${after}`;
+    } else if (reason.Block) {
+      return `${reason.Block.view}: the block {${reason.Block.code}} is new or changed`;
+    } else if (reason.Attribute) {
+      const { view, name, code } = reason.Attribute;
+      return `${view}: the attribute ${name}={${code}} is new or changed`;
+    } else if (reason.Component) {
+      return `${reason.Component.view}: the component <${reason.Component.name}/> or its props are new or changed`;
+    } else {
+      return JSON.stringify(reason);
+    }
+  }
+}
+
 function patch(json) {
   try {
     const views = JSON.parse(json);
@@ -76,6 +104,23 @@ function patch(json) {
               console.log("[HOT RELOAD] > SetAttribute", child.node, action.SetAttribute);
               child.node.setAttribute(name, value);
             });
+          } else if (action.ToggleClass) {
+            const [name, force] = action.ToggleClass;
+            actions.push(() => {
+              console.log("[HOT RELOAD] > ToggleClass", child.node, action.ToggleClass);
+              child.node.classList.toggle(name, force);
+            });
+          } else if (action.SetStyle) {
+            const [name, value] = action.SetStyle;
+            actions.push(() => {
+              console.log("[HOT RELOAD] > SetStyle", child.node, action.SetStyle);
+              child.node.style.setProperty(name, value);
+            });
+          } else if (action.RemoveStyle) {
+            actions.push(() => {
+              console.log("[HOT RELOAD] > RemoveStyle", child.node, action.RemoveStyle);
+              child.node.style.removeProperty(action.RemoveStyle);
+            });
           } else if (action.SetText) {
             const node = child.node;
             actions.push(() => {
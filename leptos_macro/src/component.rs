@@ -300,6 +300,7 @@ impl ToTokens for Model {
         let island_serialize_props = if is_island_with_other_props {
             quote! {
                 let _leptos_ser_props = ::leptos::serde_json::to_string(&props).expect("couldn't serialize island props");
+                ::leptos::component::record_island_props(#component_id, &_leptos_ser_props);
             }
         } else {
             quote! {}
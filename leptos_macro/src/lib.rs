@@ -243,6 +243,23 @@ mod slot;
 /// # }
 /// ```
 ///
+/// 12. A component’s props builder, with some of its props already set, can be
+///     spread onto the component with `..`, as in `<Component ..props/>`. The
+///     props written out in the view complete the builder, so a wrapper can set
+///     some props and leave the rest to its caller. Setting a prop that the
+///     builder has already set is a compile error. The spread must be a
+///     variable or a field, like `self.props`, and must come right after the
+///     component's name.
+/// ```rust
+/// # use leptos::prelude::*;
+/// # #[component]
+/// # fn Counter(initial_value: i32, step: i32) -> impl IntoView { view! { <p></p>} }
+/// # fn test() -> impl IntoView {
+/// let by_tens = CounterProps::builder().step(10);
+/// view! { <Counter ..by_tens initial_value=3/> }
+/// # }
+/// ```
+///
 /// Here’s a simple example that shows off several of these features, put together
 /// ```rust
 /// # use leptos::prelude::*;
@@ -323,7 +340,13 @@ fn view_macro_impl(tokens: TokenStream, template: bool) -> TokenStream {
             .chain(tokens)
             .collect()
     };
-    let config = rstml::ParserConfig::default().recover_block(true);
+    let config = rstml::ParserConfig::default()
+        .recover_block(true)
+        // `<MyComponent ..props>` is closed by `</MyComponent>`
+        .element_close_wildcard(|open_tag, close_tag| {
+            view::split_props_spread(&open_tag.name)
+                .is_some_and(|(name, _)| name == close_tag.name)
+        });
     let parser = rstml::Parser::new(config);
     let (mut nodes, errors) = parser.parse_recoverable(tokens).split_vec();
     let errors = errors.into_iter().map(|e| e.emit_as_expr_tokens());
@@ -966,6 +989,24 @@ pub fn params_derive(
     }
 }
 
+/// Derives a trait that serializes a typed data structure into a map of string
+/// keys and values, e.g., for the query of a link, as the inverse of the
+/// `Params` derive.
+///
+/// `Option` fields are left out when they are `None`, `Vec` fields are repeated
+/// once for each of their values, and values are written with their
+/// [`Display`](std::fmt::Display) implementations, so that the map parses back
+/// into the same value.
+#[proc_macro_derive(ToParams)]
+pub fn to_params_derive(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    match syn::parse(input) {
+        Ok(ast) => params::to_params_impl(&ast),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 /// Generates a `slice` into a struct with a default getter and setter.
 ///
 /// Can be used to access deeply nested fields within a global state object.
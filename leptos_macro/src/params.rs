@@ -25,8 +25,8 @@ pub fn params_impl(ast: &syn::DeriveInput) -> proc_macro::TokenStream {
 				let span = field.span();
 
 				quote_spanned! {
-					span=> #ident: <#ty as ::leptos_router::params::IntoParam>::into_param(
-                        map.get_str(#field_name_string),
+					span=> #ident: <#ty as ::leptos_router::params::IntoParam>::into_param_from_map(
+                        map,
                         #field_name_string
                     )?
 				}
@@ -47,3 +47,51 @@ pub fn params_impl(ast: &syn::DeriveInput) -> proc_macro::TokenStream {
     };
     gen.into()
 }
+
+pub fn to_params_impl(ast: &syn::DeriveInput) -> proc_macro::TokenStream {
+    let name = &ast.ident;
+
+    let fields = if let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(ref fields),
+        ..
+    }) = ast.data
+    {
+        fields
+            .named
+            .iter()
+            .map(|field| {
+                let field_name_string = &field
+                    .ident
+                    .as_ref()
+                    .expect("expected named struct fields")
+                    .to_string()
+                    .trim_start_matches("r#")
+                    .to_owned();
+                let ident = &field.ident;
+                let ty = &field.ty;
+                let span = field.span();
+
+                quote_spanned! {
+                    span=> <#ty as ::leptos_router::params::ToParam>::to_param(
+                        &self.#ident,
+                        #field_name_string,
+                        &mut map
+                    )
+                }
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+
+    let gen = quote! {
+        impl ::leptos_router::params::ToParams for #name {
+            fn to_map(&self) -> ::leptos_router::params::ParamsMap {
+                let mut map = ::leptos_router::params::ParamsMap::new();
+                #(#fields;)*
+                map
+            }
+        }
+    };
+    gen.into()
+}
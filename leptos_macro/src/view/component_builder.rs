@@ -17,6 +17,7 @@ use syn::{
 
 pub(crate) fn component_to_tokens(
     node: &mut NodeElement<impl CustomNode>,
+    props_spread: Option<Expr>,
     global_class: Option<&TokenTree>,
     disable_inert_html: bool,
 ) -> TokenStream {
@@ -286,6 +287,15 @@ pub(crate) fn component_to_tokens(
     };
 
     let name = node.name();
+    // a spread builder is completed with the props written out here;
+    // typed-builder makes setting a prop that the builder has already set a
+    // compile error
+    let props_builder = match props_spread {
+        Some(spread) => quote! { (#spread) },
+        None => quote! {
+            ::leptos::component::component_props_builder(&#name #generics)
+        },
+    };
     #[allow(unused_mut)] // used in debug
     let mut component = quote! {
         {
@@ -296,7 +306,7 @@ pub(crate) fn component_to_tokens(
                 #[allow(clippy::needless_borrows_for_generic_args)]
                 &#name,
                 {
-                    let mut props = ::leptos::component::component_props_builder(&#name #generics)
+                    let mut props = #props_builder
                         #(#required_props)*
                         #(#slots)*
                         #children
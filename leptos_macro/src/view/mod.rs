@@ -2,6 +2,7 @@ mod component_builder;
 mod slot_helper;
 mod utils;
 
+pub(crate) use self::utils::split_props_spread;
 use self::{
     component_builder::component_to_tokens,
     slot_helper::{get_slot, slot_to_tokens},
@@ -844,6 +845,18 @@ pub(crate) fn element_to_tokens(
         }
     }
 
+    // `<MyComponent ..props>` spreads a props builder onto the component
+    let props_spread = split_props_spread(node.name()).map(|(name, spread)| {
+        node.open_tag.name = name;
+        if !is_component_node(node) {
+            proc_macro_error2::emit_error!(
+                spread.span(),
+                "Props can only be spread onto components."
+            );
+        }
+        spread
+    });
+
     let name = node.name();
     if is_component_node(node) {
         if let Some(slot) = get_slot(node) {
@@ -857,7 +870,12 @@ pub(crate) fn element_to_tokens(
             );
             None
         } else {
-            Some(component_to_tokens(node, global_class, disable_inert_html))
+            Some(component_to_tokens(
+                node,
+                props_spread,
+                global_class,
+                disable_inert_html,
+            ))
         }
     } else if is_spread_marker(node) {
         let mut attributes = Vec::new();
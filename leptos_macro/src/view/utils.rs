@@ -1,7 +1,7 @@
 use proc_macro2::Ident;
-use quote::format_ident;
-use rstml::node::{KeyedAttribute, NodeName};
-use syn::{spanned::Spanned, ExprPath};
+use quote::{format_ident, quote};
+use rstml::node::{KeyedAttribute, NodeName, NodeNameFragment};
+use syn::{spanned::Spanned, Expr, ExprPath};
 
 pub fn filter_prefixed_attrs<'a, A>(attrs: A, prefix: &str) -> Vec<Ident>
 where
@@ -51,3 +51,44 @@ pub fn is_nostrip_optional_and_update_key(key: &mut NodeName) -> bool {
         false
     }
 }
+
+/// Splits the name of a tag that spreads a props builder onto a component, like
+/// `<MyComponent ..props>`, into the name of the component and the spread
+/// expression.
+///
+/// The parser reads `MyComponent ..props` as a single name punctuated by dots,
+/// with an empty fragment between the two dots, so the spread can only be a
+/// variable or a field access like `self.props`.
+pub fn split_props_spread(name: &NodeName) -> Option<(NodeName, Expr)> {
+    let NodeName::Punctuated(punct) = name else {
+        return None;
+    };
+    if punct.len() < 3
+        || punct.pairs().any(|pair| {
+            pair.punct().is_some_and(|punct| punct.as_char() != '.')
+        })
+    {
+        return None;
+    }
+    let mut fragments = punct.iter();
+    let (
+        Some(NodeNameFragment::Ident(component)),
+        Some(NodeNameFragment::Empty),
+    ) = (fragments.next(), fragments.next())
+    else {
+        return None;
+    };
+    let fields = fragments
+        .map(|fragment| match fragment {
+            NodeNameFragment::Ident(ident) => Some(ident),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let spread = syn::parse2(quote! { #(#fields).* }).ok()?;
+    let component = NodeName::Path(ExprPath {
+        attrs: vec![],
+        qself: None,
+        path: component.clone().into(),
+    });
+    Some((component, spread))
+}
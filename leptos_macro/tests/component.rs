@@ -120,6 +120,39 @@ fn returns_static_lifetime() {
     }
 }
 
+thread_local! {
+    static GREETED: std::cell::RefCell<Vec<(&'static str, bool)>> =
+        Default::default();
+}
+
+#[component]
+fn Greeting(
+    name: &'static str,
+    #[prop(optional)] excited: bool,
+    children: Children,
+) -> impl IntoView {
+    GREETED.with_borrow_mut(|greeted| greeted.push((name, excited)));
+    children()
+}
+
+struct Wrapper {
+    greeting: GreetingPropsBuilder<((&'static str,), (), ())>,
+}
+
+#[test]
+fn component_props_spread() {
+    let props = GreetingProps::builder().name("Ada");
+    _ = view! { <Greeting ..props excited=true>"Hi"</Greeting> };
+
+    // a partial builder can be stored and completed elsewhere
+    let wrapper = Wrapper {
+        greeting: GreetingProps::builder().name("Grace"),
+    };
+    _ = view! { <Greeting ..wrapper.greeting>"Hi"</Greeting> };
+
+    assert_eq!(GREETED.take(), [("Ada", true), ("Grace", false)]);
+}
+
 // an attempt to catch unhygienic macros regression
 mod macro_hygiene {
     // To ensure no relative module path to leptos inside macros.
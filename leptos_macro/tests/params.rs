@@ -1,5 +1,5 @@
 use leptos::prelude::*;
-use leptos_router::params::Params;
+use leptos_router::params::{Params, ToParams};
 
 #[derive(PartialEq, Debug, Params)]
 struct UserInfo {
@@ -26,3 +26,25 @@ fn params_test() {
         user_info
     );
 }
+
+#[derive(PartialEq, Debug, Default, Params, ToParams)]
+struct ProductQuery {
+    page: Option<u32>,
+    sort: Option<String>,
+    tags: Vec<String>,
+}
+
+#[test]
+fn to_params_test() {
+    let query = ProductQuery {
+        page: Some(2),
+        sort: None,
+        tags: vec!["new".to_owned(), "on sale".to_owned()],
+    };
+    let map = query.to_map();
+    assert_eq!(map.get_all("tags").unwrap(), ["new", "on sale"]);
+    assert_eq!(map.get("sort"), None);
+    assert_eq!(map.to_query_string(), "?page=2&tags=new&tags=on%20sale");
+    assert_eq!(ProductQuery::from_map(&map).unwrap(), query);
+    assert_eq!(ProductQuery::default().to_query_string(), "");
+}
@@ -15,6 +15,8 @@ pub use once_resource::*;
 mod resource;
 pub use resource::*;
 mod shared;
+mod timeout;
+pub use timeout::*;
 
 use base64::{engine::general_purpose::STANDARD_NO_PAD, DecodeError, Engine};
 /// Re-export of the `codee` crate.
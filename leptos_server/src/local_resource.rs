@@ -104,6 +104,18 @@ impl<T> ArcLocalResource<T> {
         *self.refetch.write() += 1;
     }
 
+    /// Stops this resource from counting towards the
+    /// [global pending count](reactive_graph::pending) while it is loading.
+    pub fn untracked_loading(self) -> Self
+    where
+        T: 'static,
+    {
+        Self {
+            data: self.data.untracked_loading(),
+            ..self
+        }
+    }
+
     /// Synchronously, reactively reads the current value of the resource and applies the function
     /// `f` to its value if it is `Some(_)`.
     #[track_caller]
@@ -345,6 +357,17 @@ impl<T> LocalResource<T> {
         self.refetch.try_update(|n| *n += 1);
     }
 
+    /// Stops this resource from counting towards the
+    /// [global pending count](reactive_graph::pending) while it is loading.
+    #[track_caller]
+    pub fn untracked_loading(self) -> Self
+    where
+        T: 'static,
+    {
+        self.data.untracked_loading();
+        self
+    }
+
     /// Synchronously, reactively reads the current value of the resource and applies the function
     /// `f` to its value if it is `Some(_)`.
     #[track_caller]
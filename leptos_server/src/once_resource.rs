@@ -154,6 +154,10 @@ where
             }
 
             if shared_context.get_is_hydrating() {
+                // names the resource in the payload metrics of the response
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                shared_context
+                    .name_data(&id, Location::caller().to_string().into());
                 shared_context.write_async(
                     id,
                     Box::pin(async move {
@@ -1,4 +1,4 @@
-use crate::{FromEncodedStr, IntoEncodedString};
+use crate::{FromEncodedStr, IntoEncodedString, ResourceTimeout};
 #[cfg(feature = "rkyv")]
 use codee::binary::RkyvCodec;
 #[cfg(feature = "serde-wasm-bindgen")]
@@ -20,7 +20,7 @@ use reactive_graph::{
         AsyncDerivedRefFuture,
     },
     graph::{Source, ToAnySubscriber},
-    owner::Owner,
+    owner::{use_context, Owner},
     prelude::*,
     signal::{ArcRwSignal, RwSignal},
 };
@@ -32,6 +32,7 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
+    time::Duration,
 };
 
 pub(crate) static IS_SUPPRESSING_RESOURCE_LOAD: AtomicBool =
@@ -306,6 +307,10 @@ where
             }
 
             if shared_context.get_is_hydrating() {
+                // names the resource in the payload metrics of the response
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                shared_context
+                    .name_data(&id, Location::caller().to_string().into());
                 shared_context.write_async(
                     id,
                     Box::pin(async move {
@@ -345,6 +350,18 @@ where
     pub fn refetch(&self) {
         *self.refetch.write() += 1;
     }
+
+    /// Stops this resource from counting towards the
+    /// [global pending count](reactive_graph::pending) while it is loading.
+    pub fn untracked_loading(self) -> Self
+    where
+        T: 'static,
+    {
+        Self {
+            data: self.data.untracked_loading(),
+            ..self
+        }
+    }
 }
 
 #[inline(always)]
@@ -478,6 +495,83 @@ where
     }
 }
 
+impl<T> ArcResource<Result<T, ResourceTimeout>, JsonSerdeCodec>
+where
+    JsonSerdeCodec:
+        Encoder<Result<T, ResourceTimeout>> + Decoder<Result<T, ResourceTimeout>>,
+    <JsonSerdeCodec as Encoder<Result<T, ResourceTimeout>>>::Error: Debug,
+    <JsonSerdeCodec as Decoder<Result<T, ResourceTimeout>>>::Error: Debug,
+    <<JsonSerdeCodec as Decoder<Result<T, ResourceTimeout>>>::Encoded as FromEncodedStr>::DecodingError:
+        Debug,
+    <JsonSerdeCodec as Encoder<Result<T, ResourceTimeout>>>::Encoded:
+        IntoEncodedString,
+    <JsonSerdeCodec as Decoder<Result<T, ResourceTimeout>>>::Encoded:
+        FromEncodedStr,
+    T: Send + Sync + 'static,
+{
+    /// Creates a new resource with the encoding [`JsonSerdeCodec`], which gives
+    /// up on loading if the `fetcher` takes longer than `timeout`.
+    ///
+    /// This works like [`ArcResource::new`], except that if the [`Future`]
+    /// generated by the `fetcher` has not loaded after `timeout`, it is dropped
+    /// and the resource resolves to `Err(ResourceTimeout)`, which can be shown
+    /// by an `ErrorBoundary` like any other error. The timeout is measured on
+    /// the wall clock, on the server as well as on the client, and starts again
+    /// each time the resource reloads, like when it is refetched.
+    #[track_caller]
+    pub fn new_with_timeout<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> Fut + Send + Sync + 'static,
+        timeout: Duration,
+    ) -> Self
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        ArcResource::new_with_options(
+            source,
+            move |source| crate::timeout(timeout, fetcher(source)),
+            false,
+        )
+    }
+
+    /// Creates a new blocking resource with the encoding [`JsonSerdeCodec`],
+    /// which gives up on loading when the
+    /// [`RequestDeadline`](crate::RequestDeadline) of the request that is being
+    /// rendered passes.
+    ///
+    /// This works like [`ArcResource::new_blocking`], except that if the
+    /// [`Future`] generated by the `fetcher` has not loaded by the deadline, it
+    /// is dropped and the resource resolves to `Err(ResourceTimeout)`, so that
+    /// the response is not held back past the point where the client has
+    /// stopped waiting for it. Without a deadline, like in the browser, it
+    /// never gives up.
+    #[track_caller]
+    pub fn new_blocking_with_deadline<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let deadline = use_context::<crate::RequestDeadline>();
+        ArcResource::new_with_options(
+            source,
+            move |source| {
+                let fut = fetcher(source);
+                async move {
+                    match deadline {
+                        Some(deadline) => deadline.run(fut).await,
+                        None => Ok(fut.await),
+                    }
+                }
+            },
+            true,
+        )
+    }
+}
+
 impl<T> ArcResource<T, FromToStringCodec>
 where
     FromToStringCodec: Encoder<T> + Decoder<T>,
@@ -1047,6 +1141,83 @@ where
     }
 }
 
+impl<T> Resource<Result<T, ResourceTimeout>, JsonSerdeCodec>
+where
+    JsonSerdeCodec:
+        Encoder<Result<T, ResourceTimeout>> + Decoder<Result<T, ResourceTimeout>>,
+    <JsonSerdeCodec as Encoder<Result<T, ResourceTimeout>>>::Error: Debug,
+    <JsonSerdeCodec as Decoder<Result<T, ResourceTimeout>>>::Error: Debug,
+    <<JsonSerdeCodec as Decoder<Result<T, ResourceTimeout>>>::Encoded as FromEncodedStr>::DecodingError:
+        Debug,
+    <JsonSerdeCodec as Encoder<Result<T, ResourceTimeout>>>::Encoded:
+        IntoEncodedString,
+    <JsonSerdeCodec as Decoder<Result<T, ResourceTimeout>>>::Encoded:
+        FromEncodedStr,
+    T: Send + Sync + 'static,
+{
+    /// Creates a new resource with the encoding [`JsonSerdeCodec`], which gives
+    /// up on loading if the `fetcher` takes longer than `timeout`.
+    ///
+    /// This works like [`Resource::new`], except that if the [`Future`]
+    /// generated by the `fetcher` has not loaded after `timeout`, it is dropped
+    /// and the resource resolves to `Err(ResourceTimeout)`, which can be shown
+    /// by an `ErrorBoundary` like any other error. The timeout is measured on
+    /// the wall clock, on the server as well as on the client, and starts again
+    /// each time the resource reloads, like when it is refetched.
+    #[track_caller]
+    pub fn new_with_timeout<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> Fut + Send + Sync + 'static,
+        timeout: Duration,
+    ) -> Self
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        Resource::new_with_options(
+            source,
+            move |source| crate::timeout(timeout, fetcher(source)),
+            false,
+        )
+    }
+
+    /// Creates a new blocking resource with the encoding [`JsonSerdeCodec`],
+    /// which gives up on loading when the
+    /// [`RequestDeadline`](crate::RequestDeadline) of the request that is being
+    /// rendered passes.
+    ///
+    /// This works like [`Resource::new_blocking`], except that if the
+    /// [`Future`] generated by the `fetcher` has not loaded by the deadline, it
+    /// is dropped and the resource resolves to `Err(ResourceTimeout)`, so that
+    /// the response is not held back past the point where the client has
+    /// stopped waiting for it. Without a deadline, like in the browser, it
+    /// never gives up.
+    #[track_caller]
+    pub fn new_blocking_with_deadline<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let deadline = use_context::<crate::RequestDeadline>();
+        Resource::new_with_options(
+            source,
+            move |source| {
+                let fut = fetcher(source);
+                async move {
+                    match deadline {
+                        Some(deadline) => deadline.run(fut).await,
+                        None => Ok(fut.await),
+                    }
+                }
+            },
+            true,
+        )
+    }
+}
+
 #[cfg(feature = "serde-wasm-bindgen")]
 impl<T> Resource<T, JsonSerdeWasmCodec>
 where
@@ -1339,6 +1510,14 @@ where
     pub fn refetch(&self) {
         self.refetch.try_update(|n| *n += 1);
     }
+
+    /// Stops this resource from counting towards the
+    /// [global pending count](reactive_graph::pending) while it is loading.
+    #[track_caller]
+    pub fn untracked_loading(self) -> Self {
+        self.data.untracked_loading();
+        self
+    }
 }
 
 impl<T, E, Ser> Resource<Result<T, E>, Ser>
@@ -1389,3 +1568,80 @@ where
         self.data.by_ref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ArcResource;
+    use crate::{RequestDeadline, ResourceTimeout};
+    use any_spawner::Executor;
+    use reactive_graph::owner::{provide_context, Owner};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    #[tokio::test]
+    async fn a_resource_that_times_out_loads_again_when_refetched() {
+        _ = Executor::init_tokio();
+        let owner = Owner::new();
+        owner.set();
+
+        let timeout = Duration::from_millis(20);
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let resource = ArcResource::new_with_timeout(
+            || (),
+            {
+                let fetches = Arc::clone(&fetches);
+                move |_| {
+                    let fetch = fetches.fetch_add(1, Ordering::SeqCst);
+                    async move {
+                        // only the first fetch hangs
+                        if fetch == 0 {
+                            futures::future::pending::<()>().await;
+                        }
+                        fetch
+                    }
+                }
+            },
+            timeout,
+        );
+        assert_eq!(resource.clone().await, Err(ResourceTimeout { timeout }));
+
+        resource.refetch();
+        Executor::tick().await;
+        assert_eq!(resource.clone().await, Ok(1));
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn a_blocking_resource_gives_up_when_the_deadline_of_the_request_passes(
+    ) {
+        _ = Executor::init_tokio();
+        let owner = Owner::new();
+        owner.set();
+
+        // without a deadline, the resource waits as long as the fetcher takes
+        let resource = ArcResource::new_blocking_with_deadline(
+            || (),
+            |_| async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                String::from("loaded")
+            },
+        );
+        assert_eq!(resource.await.as_deref(), Ok("loaded"));
+
+        let budget = Duration::from_millis(20);
+        provide_context(RequestDeadline::after(budget));
+        let resource = ArcResource::new_blocking_with_deadline(
+            || (),
+            |_| futures::future::pending::<()>(),
+        );
+        let Err(ResourceTimeout { timeout }) = resource.await else {
+            panic!("the resource loaded after the deadline");
+        };
+        assert!(timeout <= budget);
+    }
+}
@@ -0,0 +1,333 @@
+use futures::future::{select, Either};
+use or_poisoned::OrPoisoned;
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fmt,
+    future::Future,
+    pin::{pin, Pin},
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    time::{Duration, Instant},
+};
+
+/// The error a resource with a timeout, like one created with
+/// [`Resource::new_with_timeout`](crate::Resource::new_with_timeout), resolves
+/// to when its fetcher takes longer than the timeout.
+///
+/// This is an ordinary [`Error`], so it can be handled by an `ErrorBoundary`
+/// like any other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ResourceTimeout {
+    /// How long the fetcher was allowed to take.
+    pub timeout: Duration,
+}
+
+impl fmt::Display for ResourceTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the resource did not load within {:?}", self.timeout)
+    }
+}
+
+impl Error for ResourceTimeout {}
+
+/// Runs `fut`, unless it has not resolved after `duration`, in which case it is
+/// dropped and this resolves to a [`ResourceTimeout`] instead.
+///
+/// The time is measured on the wall clock from when this is first polled, on
+/// the server as well as in the browser, without depending on any particular
+/// async runtime.
+pub async fn timeout<Fut>(
+    duration: Duration,
+    fut: Fut,
+) -> Result<Fut::Output, ResourceTimeout>
+where
+    Fut: Future,
+{
+    match select(pin!(fut), Sleep::new(duration)).await {
+        Either::Left((value, _)) => Ok(value),
+        Either::Right(_) => Err(ResourceTimeout { timeout: duration }),
+    }
+}
+
+/// The name of the request header that the server integrations read a
+/// [`RequestDeadline`] from, with the number of seconds that the client (or a
+/// load balancer in front of the server) waits for the response, like `10` or
+/// `2.5`.
+pub const REQUEST_TIMEOUT_HEADER: &str = "x-request-timeout";
+
+/// How long the server integrations allow every request to take, if it does not
+/// send a shorter timeout in the [`REQUEST_TIMEOUT_HEADER`].
+///
+/// This is set as a request extension with Axum, like with
+/// `.layer(Extension(RequestTimeout(Duration::from_secs(10))))`, and as app
+/// data with Actix, like with
+/// `.app_data(RequestTimeout(Duration::from_secs(10)))`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RequestTimeout(pub Duration);
+
+/// The point in time after which nobody is waiting for the response to the
+/// current request anymore, like when a load balancer times requests out.
+///
+/// The server integrations provide this as context while rendering a request
+/// with a [`RequestTimeout`] or a [`REQUEST_TIMEOUT_HEADER`], so that server
+/// functions that are called while rendering can find it with
+/// `use_context::<RequestDeadline>()`, and so that resources created with
+/// [`Resource::new_blocking_with_deadline`](crate::Resource::new_blocking_with_deadline)
+/// give up when it passes.
+///
+/// This measures time with [`Instant`], which is not available in the
+/// browser, so it is only ever created on the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RequestDeadline(Instant);
+
+impl RequestDeadline {
+    /// The deadline `budget` from now.
+    pub fn after(budget: Duration) -> Self {
+        let now = Instant::now();
+        // a budget too long to represent never runs out, so the furthest
+        // deadline is as good
+        Self(
+            now.checked_add(budget).unwrap_or_else(|| {
+                now + Duration::from_secs(60 * 60 * 24 * 365)
+            }),
+        )
+    }
+
+    /// The deadline of a request that started now, from the value of its
+    /// [`REQUEST_TIMEOUT_HEADER`], if it has one that can be parsed, and the
+    /// [`RequestTimeout`] of the server, if it has one, whichever is sooner.
+    pub fn for_request(
+        header: Option<&str>,
+        timeout: Option<RequestTimeout>,
+    ) -> Option<Self> {
+        let from_header = header
+            .and_then(|seconds| seconds.trim().parse::<f64>().ok())
+            .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok());
+        from_header
+            .into_iter()
+            .chain(timeout.map(|RequestTimeout(timeout)| timeout))
+            .min()
+            .map(Self::after)
+    }
+
+    /// How much time is left until the deadline, which is zero once it has
+    /// passed.
+    pub fn remaining(&self) -> Duration {
+        self.0.saturating_duration_since(Instant::now())
+    }
+
+    /// Whether the deadline has passed.
+    pub fn is_expired(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    /// Runs `fut`, unless the deadline passes first, in which case it is
+    /// dropped and this resolves to a [`ResourceTimeout`] with the time that
+    /// was left when it started.
+    pub async fn run<Fut>(
+        &self,
+        fut: Fut,
+    ) -> Result<Fut::Output, ResourceTimeout>
+    where
+        Fut: Future,
+    {
+        timeout(self.remaining(), fut).await
+    }
+}
+
+#[derive(Default)]
+struct TimerState {
+    fired: bool,
+    waker: Option<Waker>,
+}
+
+/// Resolves once `duration` has passed, starting the timer when it is first
+/// polled.
+struct Sleep {
+    duration: Duration,
+    state: Option<Arc<Mutex<TimerState>>>,
+}
+
+impl Sleep {
+    fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            state: None,
+        }
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let duration = self.duration;
+        let state = self.state.get_or_insert_with(|| {
+            let state = Arc::<Mutex<TimerState>>::default();
+            start_timer(duration, Arc::clone(&state));
+            state
+        });
+        let mut state = state.lock().or_poisoned();
+        if state.fired {
+            Poll::Ready(())
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+fn fire(state: &Mutex<TimerState>) {
+    let waker = {
+        let mut state = state.lock().or_poisoned();
+        state.fired = true;
+        state.waker.take()
+    };
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+/// Off the web, every timer is run by a single thread, which sleeps until the
+/// next deadline.
+#[cfg(not(target_arch = "wasm32"))]
+fn start_timer(duration: Duration, state: Arc<Mutex<TimerState>>) {
+    use std::{
+        sync::{mpsc, OnceLock},
+        thread,
+    };
+
+    type Timer = (Instant, Arc<Mutex<TimerState>>);
+    static TIMERS: OnceLock<mpsc::Sender<Timer>> = OnceLock::new();
+
+    // a timeout too long to represent can never pass
+    let Some(deadline) = Instant::now().checked_add(duration) else {
+        return;
+    };
+    let timers = TIMERS.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<Timer>();
+        thread::Builder::new()
+            .name("leptos-resource-timeouts".to_string())
+            .spawn(move || {
+                let mut pending = Vec::<Timer>::new();
+                loop {
+                    let now = Instant::now();
+                    pending.retain(|(deadline, state)| {
+                        let passed = *deadline <= now;
+                        if passed {
+                            fire(state);
+                        }
+                        !passed
+                    });
+                    let next =
+                        pending.iter().map(|(deadline, _)| *deadline).min();
+                    let received = match next {
+                        Some(next) => rx
+                            .recv_timeout(next.saturating_duration_since(now))
+                            .ok(),
+                        None => match rx.recv() {
+                            Ok(timer) => Some(timer),
+                            Err(_) => return,
+                        },
+                    };
+                    pending.extend(received);
+                }
+            })
+            .expect("could not spawn the thread for resource timeouts");
+        tx
+    });
+    _ = timers.send((deadline, state));
+}
+
+#[cfg(target_arch = "wasm32")]
+fn start_timer(duration: Duration, state: Arc<Mutex<TimerState>>) {
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+    let set_timeout = js_sys::Reflect::get(
+        &js_sys::global(),
+        &JsValue::from_str("setTimeout"),
+    )
+    .ok()
+    .and_then(|set_timeout| set_timeout.dyn_into::<js_sys::Function>().ok());
+    if let Some(set_timeout) = set_timeout {
+        // the longest delay `setTimeout` supports is `i32::MAX` milliseconds
+        let millis = duration.as_millis().min(i32::MAX as u128) as f64;
+        let callback = Closure::once_into_js(move || fire(&state));
+        _ = set_timeout.call2(
+            &JsValue::NULL,
+            &callback,
+            &JsValue::from_f64(millis),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{timeout, RequestDeadline, RequestTimeout, ResourceTimeout};
+    use futures::executor::block_on;
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    /// Sets its flag when it is dropped.
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn a_future_that_takes_too_long_is_dropped() {
+        let duration = Duration::from_millis(10);
+        assert_eq!(block_on(timeout(duration, async { 42 })), Ok(42));
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        let hanging = {
+            let flag = DropFlag(Arc::clone(&dropped));
+            async move {
+                let _flag = flag;
+                futures::future::pending::<()>().await
+            }
+        };
+        assert_eq!(
+            block_on(timeout(duration, hanging)),
+            Err(ResourceTimeout { timeout: duration })
+        );
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn the_deadline_of_a_request_is_the_sooner_of_its_header_and_the_server() {
+        let remaining = |header, timeout: Option<u64>| {
+            RequestDeadline::for_request(
+                header,
+                timeout.map(|secs| RequestTimeout(Duration::from_secs(secs))),
+            )
+            .map(|deadline| deadline.remaining())
+        };
+        assert_eq!(remaining(None, None), None);
+        assert_eq!(remaining(Some("soon"), None), None);
+        assert!(remaining(Some(" 2.5 "), Some(10))
+            .is_some_and(|left| left <= Duration::from_millis(2500)
+                && left > Duration::from_secs(2)));
+        assert!(remaining(Some("30"), Some(10))
+            .is_some_and(|left| left <= Duration::from_secs(10)
+                && left > Duration::from_secs(9)));
+
+        let expired = RequestDeadline::after(Duration::ZERO);
+        assert!(expired.is_expired());
+        assert_eq!(
+            block_on(expired.run(futures::future::pending::<()>())),
+            Err(ResourceTimeout {
+                timeout: Duration::ZERO
+            })
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use html_escape::{decode_html_entities, encode_double_quoted_attribute};
+
+/// Merges the attributes set by [`Html`](crate::Html) or [`Body`](crate::Body)
+/// components into the opening `<{tag}` tag in `chunk`, so the tag ends up with
+/// a single value for each attribute.
+///
+/// The attributes already written in the tag come first, followed by those of
+/// each component in the order they were rendered. For each attribute, the last
+/// value wins, except that the classes of `class` and the declarations of
+/// `style` are combined.
+pub(crate) fn merge_into_tag(
+    chunk: &mut String,
+    tag: &str,
+    contributions: impl IntoIterator<Item = String>,
+) {
+    let contributions = contributions.into_iter().collect::<Vec<_>>();
+    if contributions.is_empty() {
+        return;
+    }
+    let open = format!("<{tag}");
+    let Some(start) = chunk.find(&open).map(|idx| idx + open.len()) else {
+        return;
+    };
+    let Some(end) = tag_end(&chunk[start..]).map(|idx| start + idx) else {
+        return;
+    };
+    let (existing, self_closing) = match chunk[start..end].strip_suffix('/') {
+        Some(existing) => (existing, true),
+        None => (&chunk[start..end], false),
+    };
+
+    let mut attributes = Attributes::default();
+    attributes.extend(existing);
+    for contribution in &contributions {
+        attributes.extend(contribution);
+    }
+
+    let mut merged = attributes.to_html();
+    if self_closing {
+        merged.push('/');
+    }
+    chunk.replace_range(start..end, &merged);
+}
+
+#[derive(Default)]
+struct Attributes(Vec<(String, Option<String>)>);
+
+impl Attributes {
+    fn extend(&mut self, html: &str) {
+        for (name, value) in parse(html) {
+            let name = name.to_ascii_lowercase();
+            match self.0.iter_mut().find(|(existing, _)| *existing == name) {
+                None => self.0.push((name, value)),
+                Some((_, existing)) => {
+                    *existing = match (name.as_str(), existing.take(), value) {
+                        ("class", Some(prev), Some(value)) => {
+                            let mut classes =
+                                prev.split_whitespace().collect::<Vec<_>>();
+                            for class in value.split_whitespace() {
+                                if !classes.contains(&class) {
+                                    classes.push(class);
+                                }
+                            }
+                            Some(classes.join(" "))
+                        }
+                        ("style", Some(prev), Some(value)) => {
+                            let prev = prev.trim().trim_end_matches(';');
+                            Some(if prev.trim().is_empty() {
+                                value
+                            } else if value.trim().is_empty() {
+                                prev.to_string()
+                            } else {
+                                format!("{prev};{value}")
+                            })
+                        }
+                        (_, prev, value) => value.or(prev),
+                    }
+                }
+            }
+        }
+    }
+
+    fn to_html(&self) -> String {
+        let mut html = String::new();
+        for (name, value) in &self.0 {
+            html.push(' ');
+            html.push_str(name);
+            if let Some(value) = value {
+                html.push_str("=\"");
+                html.push_str(&encode_double_quoted_attribute(value));
+                html.push('"');
+            }
+        }
+        html
+    }
+}
+
+/// The index of the `>` that closes the tag whose attributes start `html`,
+/// skipping any in quoted values.
+fn tag_end(html: &str) -> Option<usize> {
+    let mut quote = None;
+    html.char_indices().find_map(|(idx, c)| {
+        match (quote, c) {
+            (None, '>') => return Some(idx),
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if open == c => quote = None,
+            _ => {}
+        }
+        None
+    })
+}
+
+/// Parses the attributes of an HTML tag, like ` lang="en" dir=rtl hidden`,
+/// unescaping their values.
+fn parse(html: &str) -> Vec<(&str, Option<String>)> {
+    let mut attributes = Vec::new();
+    let mut rest = html.trim_start();
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=')
+            .unwrap_or(rest.len());
+        if name_end == 0 {
+            // a stray `=` that does not follow a name
+            rest = rest[1..].trim_start();
+            continue;
+        }
+        let (name, after_name) = rest.split_at(name_end);
+        let after_name = after_name.trim_start();
+        let Some(after_eq) = after_name.strip_prefix('=') else {
+            attributes.push((name, None));
+            rest = after_name;
+            continue;
+        };
+        let after_eq = after_eq.trim_start();
+        let (value, after_value) = match after_eq.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let value = &after_eq[1..];
+                let end = value.find(quote).unwrap_or(value.len());
+                (&value[..end], value.get(end + 1..).unwrap_or(""))
+            }
+            _ => {
+                let end = after_eq
+                    .find(char::is_whitespace)
+                    .unwrap_or(after_eq.len());
+                after_eq.split_at(end)
+            }
+        };
+        attributes.push((name, Some(decode_html_entities(value).into_owned())));
+        rest = after_value.trim_start();
+    }
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge_into_tag, parse};
+
+    fn merged(chunk: &str, tag: &str, contributions: &[&str]) -> String {
+        let mut chunk = chunk.to_string();
+        merge_into_tag(
+            &mut chunk,
+            tag,
+            contributions.iter().map(|html| html.to_string()),
+        );
+        chunk
+    }
+
+    #[test]
+    fn attributes_are_parsed() {
+        assert_eq!(
+            parse(
+                r#" lang="en" dir=rtl hidden data-x ='a > b=c' title="&quot;hi&quot;""#
+            ),
+            vec![
+                ("lang", Some("en".to_string())),
+                ("dir", Some("rtl".to_string())),
+                ("hidden", None),
+                ("data-x", Some("a > b=c".to_string())),
+                ("title", Some("\"hi\"".to_string())),
+            ]
+        );
+        assert_eq!(
+            parse(" = async defer"),
+            vec![("async", None), ("defer", None)]
+        );
+        assert_eq!(
+            parse(r#"lang="en" lang="fr""#),
+            vec![
+                ("lang", Some("en".to_string())),
+                ("lang", Some("fr".to_string()))
+            ]
+        );
+    }
+
+    #[test]
+    fn the_last_value_wins() {
+        assert_eq!(
+            merged(
+                r#"<!DOCTYPE html><html lang="en"><head>"#,
+                "html",
+                &[r#" lang="fr" dir="rtl""#, " lang=de"]
+            ),
+            r#"<!DOCTYPE html><html lang="de" dir="rtl"><head>"#
+        );
+        // a duplicate attribute in a single contribution also keeps its last
+        // value
+        assert_eq!(
+            merged("<body>", "body", &[" id=a id=b"]),
+            r#"<body id="b">"#
+        );
+        // valueless attributes are kept, unless a value is given later
+        assert_eq!(
+            merged("<body hidden>", "body", &[" inert", " hidden=until-found"]),
+            r#"<body hidden="until-found" inert>"#
+        );
+        assert_eq!(merged("<body/>", "body", &[" inert"]), "<body inert/>");
+    }
+
+    #[test]
+    fn classes_and_styles_are_combined() {
+        assert_eq!(
+            merged(
+                r#"<body class="a b" style="color: red;">"#,
+                "body",
+                &[r#" class="b c" style="margin: 0""#, " class=d"]
+            ),
+            r#"<body class="a b c d" style="color: red;margin: 0">"#
+        );
+        // an empty style adds no separator
+        assert_eq!(
+            merged(r#"<body style="">"#, "body", &[r#" style="margin: 0""#]),
+            r#"<body style="margin: 0">"#
+        );
+        assert_eq!(
+            merged(r#"<body style=";">"#, "body", &[" style=margin:0"]),
+            r#"<body style="margin:0">"#
+        );
+        assert_eq!(
+            merged("<body style=margin:0>", "body", &[r#" style="""#]),
+            r#"<body style="margin:0">"#
+        );
+    }
+
+    #[test]
+    fn quoted_values_can_contain_tag_characters() {
+        assert_eq!(
+            merged(
+                r#"<html data-a="x > y" data-b='p="q"'><head>"#,
+                "html",
+                &[" lang='en'"]
+            ),
+            r#"<html data-a="x &gt; y" data-b="p=&quot;q&quot;" lang="en"><head>"#
+        );
+    }
+}
@@ -44,6 +44,10 @@ use leptos::{
 ///     }
 /// }
 /// ```
+///
+/// As with [`Html`](crate::Html), several components can set attributes on the
+/// `<body>` element, and their initial values are merged into the `<body>` tag
+/// of the server-rendered page, with the same precedence.
 #[component]
 pub fn Body() -> impl IntoView {
     BodyView { attributes: () }
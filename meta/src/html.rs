@@ -41,6 +41,38 @@ use leptos::{
 ///     }
 /// }
 /// ```
+///
+/// Attributes can be reactive, so that they update the `<html>` element
+/// whenever a signal changes. During server rendering, their initial values are
+/// written into the `<html>` tag of the page itself, so a theme read from a
+/// cookie applies before the app has hydrated:
+///
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn Theme(theme: RwSignal<String>) -> impl IntoView {
+///     view! {
+///       <Html {..} data-theme=move || theme.get() />
+///       <Meta
+///         name="theme-color"
+///         content=move || {
+///             if theme.get() == "dark" { "#000000" } else { "#ffffff" }
+///         }
+///       />
+///     }
+/// }
+/// ```
+///
+/// Any number of components can set attributes on the `<html>` element. In the
+/// server-rendered page, each attribute has a single value: the one set by the
+/// component that rendered last, which overrides the attributes written in the
+/// `<html>` tag of the page itself. The classes in `class` and the declarations
+/// in `style` are combined rather than overridden. On the client, each
+/// component sets its attributes on the element as it renders, so a whole
+/// `class` or `style` value replaces the one before it; use `class:name` and
+/// `style:name` to combine classes and styles set by different components.
 #[component]
 pub fn Html() -> impl IntoView {
     HtmlView { attributes: () }
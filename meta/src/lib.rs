@@ -74,6 +74,7 @@ use std::{
 use wasm_bindgen::JsCast;
 use web_sys::HtmlHeadElement;
 
+mod attributes;
 mod body;
 mod html;
 mod link;
@@ -206,6 +207,21 @@ impl ServerMetaContext {
 }
 
 impl ServerMetaContextOutput {
+    /// Consumes the metadata, returning the HTML for the `<head>`: every
+    /// registered meta tag, followed by the `<title>`.
+    ///
+    /// This is useful when rendering a view to a string outside of a server
+    /// integration, to place the metadata separately from the body.
+    pub fn head_html(self) -> String {
+        let mut head = self.elements.try_iter().collect::<String>();
+        if let Some(title) = self.title.as_string() {
+            head.push_str("<title>");
+            head.push_str(&title);
+            head.push_str("</title>");
+        }
+        head
+    }
+
     /// Consumes the metadata, injecting it into the the first chunk of an HTML stream in the
     /// appropriate place.
     ///
@@ -235,10 +251,6 @@ impl ServerMetaContextOutput {
         // collect all registered meta tags
         let meta_buf = self.elements.try_iter().collect::<String>();
 
-        // get HTML strings for `<html>` and `<body>`
-        let html_attrs = self.html.try_iter().collect::<String>();
-        let body_attrs = self.body.try_iter().collect::<String>();
-
         let mut modified_chunk = if title_len == 0 && meta_buf.is_empty() {
             first_chunk
         } else {
@@ -267,21 +279,17 @@ impl ServerMetaContextOutput {
             buf
         };
 
-        if !html_attrs.is_empty() {
-            if let Some(index) = modified_chunk.find("<html") {
-                // Calculate the position where the new string should be inserted
-                let insert_pos = index + "<html".len();
-                modified_chunk.insert_str(insert_pos, &html_attrs);
-            }
-        }
-
-        if !body_attrs.is_empty() {
-            if let Some(index) = modified_chunk.find("<body") {
-                // Calculate the position where the new string should be inserted
-                let insert_pos = index + "<body".len();
-                modified_chunk.insert_str(insert_pos, &body_attrs);
-            }
-        }
+        // merge the attributes set with <Html> and <Body> into the actual tags
+        attributes::merge_into_tag(
+            &mut modified_chunk,
+            "html",
+            self.html.try_iter(),
+        );
+        attributes::merge_into_tag(
+            &mut modified_chunk,
+            "body",
+            self.body.try_iter(),
+        );
 
         futures::stream::once(async move { modified_chunk }).chain(stream)
     }
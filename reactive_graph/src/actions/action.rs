@@ -2,6 +2,7 @@ use crate::{
     computed::{ArcMemo, Memo},
     diagnostics::is_suppressing_resource_load,
     owner::{ArcStoredValue, ArenaItem},
+    pending::PendingGuard,
     send_wrapper_ext::SendOption,
     signal::{ArcMappedSignal, ArcRwSignal, MappedSignal, RwSignal},
     traits::{DefinedAt, Dispose, Get, GetUntracked, GetValue, Update, Write},
@@ -265,6 +266,7 @@ where
                 let dispatched = self.dispatched.clone();
                 let value = self.value.clone();
                 let in_flight = self.in_flight.clone();
+                let pending = PendingGuard::new();
                 async move {
                     select! {
                         // if the abort message has been sent, bail and do nothing
@@ -284,6 +286,7 @@ where
                     if in_flight.get_untracked() == 0 {
                         input.update(|inp| **inp = None);
                     }
+                    drop(pending);
                 }
             });
         }
@@ -317,6 +320,7 @@ where
                 let value = self.value.clone();
                 let dispatched = self.dispatched.clone();
                 let in_flight = self.in_flight.clone();
+                let pending = PendingGuard::new();
                 async move {
                     select! {
                         // if the abort message has been sent, bail and do nothing
@@ -336,6 +340,7 @@ where
                     if in_flight.get_untracked() == 0 {
                         input.update(|inp| **inp = None);
                     }
+                    drop(pending);
                 }
             });
         }
@@ -1,6 +1,7 @@
 use crate::{
     diagnostics::is_suppressing_resource_load,
     owner::{ArenaItem, FromLocal, LocalStorage, Storage, SyncStorage},
+    pending::PendingGuard,
     signal::{ArcReadSignal, ArcRwSignal, ReadSignal, RwSignal},
     traits::{DefinedAt, Dispose, GetUntracked, Set, Update},
     unwrap_signal,
@@ -505,6 +506,7 @@ where
 
             let version = self.version.clone();
 
+            let pending = PendingGuard::new();
             crate::spawn(async move {
                 let new_value = fut.await;
                 let canceled = submission.canceled.get_untracked();
@@ -514,6 +516,7 @@ where
                 submission.input.try_set(None);
                 submission.pending.try_set(false);
                 version.try_update(|n| *n += 1);
+                drop(pending);
             })
         }
     }
@@ -154,17 +154,18 @@ where
     pub fn new_owning(
         fun: impl Fn(Option<T>) -> (T, bool) + Send + Sync + 'static,
     ) -> Self {
+        let defined_at = Location::caller();
         let inner = Arc::new_cyclic(|weak| {
             let subscriber = AnySubscriber(
                 weak.as_ptr() as usize,
                 Weak::clone(weak) as Weak<dyn Subscriber + Send + Sync>,
             );
 
-            MemoInner::new(Arc::new(fun), subscriber)
+            MemoInner::new(Arc::new(fun), subscriber, defined_at)
         });
         Self {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
-            defined_at: Location::caller(),
+            defined_at,
             inner,
         }
     }
@@ -200,6 +201,41 @@ where
     }
 }
 
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+impl<T, S> ArcMemo<T, S>
+where
+    S: Storage<T>,
+{
+    /// The sources that this memo tracked the last time it ran.
+    ///
+    /// Each source's [`defined_at`](DefinedAt::defined_at) is the location at
+    /// which it was created.
+    pub fn debug_dependencies(&self) -> Vec<AnySource> {
+        use or_poisoned::OrPoisoned;
+
+        self.inner
+            .reactivity
+            .read()
+            .or_poisoned()
+            .sources
+            .clone()
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+impl<T, S> crate::introspection::DebugSubscriberCount for ArcMemo<T, S>
+where
+    S: Storage<T>,
+{
+    fn debug_subscriber_count(&self) -> usize {
+        use or_poisoned::OrPoisoned;
+
+        self.inner.reactivity.read().or_poisoned().subscribers.len()
+    }
+}
+
 impl<T, S> Debug for ArcMemo<T, S>
 where
     S: Storage<T>,
@@ -13,6 +13,7 @@ use crate::{
         SubscriberSet, ToAnySource, ToAnySubscriber, WithObserver,
     },
     owner::{use_context, Owner},
+    pending::PendingGuard,
     send_wrapper_ext::SendOption,
     signal::{
         guards::{AsyncPlain, Mapped, MappedMut, ReadGuard, WriteGuard},
@@ -235,7 +236,9 @@ macro_rules! spawn_derived {
             state: AsyncDerivedState::Clean,
             version: 0,
             suspenses: Vec::new(),
-            pending_suspenses: Vec::new()
+            pending_suspenses: Vec::new(),
+            pending: None,
+            untracked_loading: false,
         }));
         let value = Arc::new(AsyncRwLock::new($initial));
         let wakers = Arc::new(RwLock::new(Vec::new()));
@@ -273,6 +276,7 @@ macro_rules! spawn_derived {
                 let initial = initial_fut.as_mut().now_or_never();
                 match initial {
                     None => {
+                        Self::start_pending(&inner);
                         inner.write().or_poisoned().notifier.notify();
                         (false, Some(initial_fut))
                     }
@@ -364,6 +368,7 @@ macro_rules! spawn_derived {
 
                                     // generate and assign new value
                                     loading.store(true, Ordering::Relaxed);
+                                    Self::start_pending(&inner);
 
                                     let this_version = {
                                         let mut guard = inner.write().or_poisoned();
@@ -407,6 +412,21 @@ macro_rules! spawn_derived {
 }
 
 impl<T: 'static> ArcAsyncDerived<T> {
+    // counts this as pending, unless it already is, so that rerunning several
+    // times before finishing only counts once
+    fn start_pending(inner: &Arc<RwLock<ArcAsyncDerivedInner>>) {
+        let should_count = {
+            let inner = inner.read().or_poisoned();
+            inner.pending.is_none() && !inner.untracked_loading
+        };
+        if should_count {
+            // created outside the lock, because it notifies subscribers of the
+            // global count
+            let pending = PendingGuard::new();
+            inner.write().or_poisoned().pending.get_or_insert(pending);
+        }
+    }
+
     async fn set_inner_value(
         new_value: SendOption<T>,
         value: Arc<AsyncRwLock<SendOption<T>>>,
@@ -426,6 +446,8 @@ impl<T: 'static> ArcAsyncDerived<T> {
         ready_tx: Option<oneshot::Sender<()>>,
     ) {
         loading.store(false, Ordering::Relaxed);
+        let pending = inner.write().or_poisoned().pending.take();
+        drop(pending);
 
         let prev_state = mem::replace(
             &mut inner.write().or_poisoned().state,
@@ -586,6 +608,18 @@ impl<T: 'static> ArcAsyncDerived<T> {
         this
     }
 
+    /// Stops this from counting towards the
+    /// [global pending count](crate::pending) while it is loading.
+    pub fn untracked_loading(self) -> Self {
+        let pending = {
+            let mut inner = self.inner.write().or_poisoned();
+            inner.untracked_loading = true;
+            inner.pending.take()
+        };
+        drop(pending);
+        self
+    }
+
     /// Returns a `Future` that is ready when this resource has next finished loading.
     pub fn ready(&self) -> AsyncDerivedReadyFuture {
         AsyncDerivedReadyFuture::new(
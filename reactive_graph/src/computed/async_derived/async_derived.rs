@@ -279,6 +279,18 @@ where
             .unwrap_or_else(unwrap_signal!(self));
         this.ready()
     }
+
+    /// Stops this from counting towards the
+    /// [global pending count](crate::pending) while it is loading.
+    #[track_caller]
+    pub fn untracked_loading(self) -> Self {
+        let this = self
+            .inner
+            .try_get_value()
+            .unwrap_or_else(unwrap_signal!(self));
+        this.untracked_loading();
+        self
+    }
 }
 
 impl<T, S> Copy for AsyncDerived<T, S> {}
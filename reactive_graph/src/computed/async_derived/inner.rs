@@ -7,6 +7,7 @@ use crate::{
         SubscriberSet,
     },
     owner::Owner,
+    pending::PendingGuard,
 };
 use or_poisoned::OrPoisoned;
 use std::sync::RwLock;
@@ -24,6 +25,9 @@ pub(crate) struct ArcAsyncDerivedInner {
     pub version: usize,
     pub suspenses: Vec<SuspenseContext>,
     pub pending_suspenses: Vec<TaskHandle>,
+    // counts this towards the global pending count while it is loading
+    pub pending: Option<PendingGuard>,
+    pub untracked_loading: bool,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -1,3 +1,5 @@
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+use crate::traits::DefinedAt;
 use crate::{
     graph::{
         AnySource, AnySubscriber, Observer, ReactiveNode, ReactiveNodeState,
@@ -8,6 +10,7 @@ use crate::{
 use or_poisoned::OrPoisoned;
 use std::{
     fmt::Debug,
+    panic::Location,
     sync::{Arc, RwLock, RwLockWriteGuard},
 };
 
@@ -21,6 +24,8 @@ where
     pub(crate) fun: Arc<dyn Fn(Option<T>) -> (T, bool) + Send + Sync>,
     pub(crate) owner: Owner,
     pub(crate) reactivity: RwLock<MemoInnerReactivity>,
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    pub(crate) defined_at: &'static Location<'static>,
 }
 
 pub(crate) struct MemoInnerReactivity {
@@ -28,6 +33,9 @@ pub(crate) struct MemoInnerReactivity {
     pub(crate) sources: SourceSet,
     pub(crate) subscribers: SubscriberSet,
     pub(crate) any_subscriber: AnySubscriber,
+    /// The source that caused this memo to be marked dirty, if any.
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    pub(crate) triggered_by: Option<&'static Location<'static>>,
 }
 
 impl<T, S> Debug for MemoInner<T, S>
@@ -47,7 +55,10 @@ where
     pub fn new(
         fun: Arc<dyn Fn(Option<T>) -> (T, bool) + Send + Sync>,
         any_subscriber: AnySubscriber,
+        defined_at: &'static Location<'static>,
     ) -> Self {
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        let _ = defined_at;
         Self {
             value: Arc::new(RwLock::new(None)),
             fun,
@@ -57,7 +68,11 @@ where
                 sources: Default::default(),
                 subscribers: SubscriberSet::new(),
                 any_subscriber,
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                triggered_by: None,
             }),
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at,
         }
     }
 }
@@ -67,7 +82,14 @@ where
     S: Storage<T>,
 {
     fn mark_dirty(&self) {
-        self.reactivity.write().or_poisoned().state = ReactiveNodeState::Dirty;
+        {
+            let mut lock = self.reactivity.write().or_poisoned();
+            lock.state = ReactiveNodeState::Dirty;
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            {
+                lock.triggered_by = crate::introspection::notifier();
+            }
+        }
         self.mark_subscribers_check();
     }
 
@@ -108,7 +130,13 @@ where
                 ReactiveNodeState::Dirty => true,
                 ReactiveNodeState::Check => {
                     (&sources).into_iter().any(|source| {
-                        source.update_if_necessary()
+                        let changed = source.update_if_necessary();
+                        #[cfg(any(debug_assertions, leptos_debuginfo))]
+                        if changed {
+                            reactivity.write().or_poisoned().triggered_by =
+                                source.defined_at();
+                        }
+                        changed
                             || reactivity.read().or_poisoned().state
                                 == ReactiveNodeState::Dirty
                     })
@@ -120,6 +148,19 @@ where
             // No deadlock risk, because we only hold the value lock.
             let value = self.value.write().or_poisoned().take();
 
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            {
+                let triggered_by =
+                    self.reactivity.write().or_poisoned().triggered_by.take();
+                if value.is_some() {
+                    crate::introspection::recomputed(
+                        crate::introspection::NodeKind::Memo,
+                        self.defined_at,
+                        triggered_by,
+                    );
+                }
+            }
+
             /// codegen optimisation:
             fn inner_1(
                 reactivity: &RwLock<MemoInnerReactivity>,
@@ -167,6 +208,11 @@ where
                     drop(reactivity_lock);
                 }
             }
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            crate::introspection::notifying(Some(self.defined_at), || {
+                inner_2(changed, reactivity_lock)
+            });
+            #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
             inner_2(changed, reactivity_lock);
 
             changed
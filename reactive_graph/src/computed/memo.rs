@@ -287,6 +287,38 @@ where
     }
 }
 
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+impl<T, S> Memo<T, S>
+where
+    T: 'static,
+    S: Storage<ArcMemo<T, S>> + Storage<T>,
+{
+    /// The sources that this memo tracked the last time it ran.
+    ///
+    /// Each source's [`defined_at`](DefinedAt::defined_at) is the location at
+    /// which it was created.
+    pub fn debug_dependencies(&self) -> Vec<crate::graph::AnySource> {
+        self.inner
+            .try_get_value()
+            .map(|inner| inner.debug_dependencies())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+impl<T, S> crate::introspection::DebugSubscriberCount for Memo<T, S>
+where
+    T: 'static,
+    S: Storage<ArcMemo<T, S>> + Storage<T>,
+{
+    fn debug_subscriber_count(&self) -> usize {
+        self.inner
+            .try_get_value()
+            .map(|inner| inner.debug_subscriber_count())
+            .unwrap_or(0)
+    }
+}
+
 impl<T, S> Track for Memo<T, S>
 where
     T: 'static,
@@ -2,17 +2,17 @@ use crate::{
     channel::{channel, Receiver},
     effect::{inner::EffectInner, EffectFunction},
     graph::{
-        AnySubscriber, ReactiveNode, SourceSet, Subscriber, ToAnySubscriber,
-        WithObserver,
+        AnySubscriber, ReactiveNode, Subscriber, ToAnySubscriber, WithObserver,
     },
     owner::{ArenaItem, LocalStorage, Owner, Storage, SyncStorage},
-    traits::Dispose,
+    traits::{DefinedAt, Dispose},
 };
 use any_spawner::Executor;
 use futures::StreamExt;
 use or_poisoned::OrPoisoned;
 use std::{
     mem,
+    panic::Location,
     sync::{atomic::AtomicBool, Arc, RwLock},
 };
 
@@ -92,7 +92,31 @@ impl<S> Dispose for Effect<S> {
     }
 }
 
-fn effect_base() -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
+impl<S> DefinedAt for Effect<S>
+where
+    S: Storage<StoredEffect>,
+{
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            self.inner?
+                .try_with_value(|inner| {
+                    inner
+                        .as_ref()
+                        .map(|inner| inner.read().or_poisoned().defined_at)
+                })
+                .flatten()
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+fn effect_base(
+    defined_at: &'static Location<'static>,
+) -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
     let (mut observer, rx) = channel();
 
     // spawn the effect asynchronously
@@ -101,11 +125,8 @@ fn effect_base() -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
     observer.notify();
 
     let owner = Owner::new();
-    let inner = Arc::new(RwLock::new(EffectInner {
-        dirty: true,
-        observer,
-        sources: SourceSet::new(),
-    }));
+    let inner =
+        Arc::new(RwLock::new(EffectInner::new(true, observer, defined_at)));
 
     (rx, owner, inner)
 }
@@ -155,12 +176,14 @@ impl Effect<LocalStorage> {
     /// This spawns a task on the local thread using
     /// [`spawn_local`](any_spawner::Executor::spawn_local). For an effect that can be spawned on
     /// any thread, use [`new_sync`](Effect::new_sync).
+    #[track_caller]
     pub fn new<T, M>(mut fun: impl EffectFunction<T, M> + 'static) -> Self
     where
         T: 'static,
     {
+        let defined_at = Location::caller();
         let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
+            let (mut rx, owner, inner) = effect_base(defined_at);
             let value = Arc::new(RwLock::new(None::<T>));
             let mut first_run = true;
 
@@ -300,6 +323,7 @@ impl Effect<LocalStorage> {
     /// # }).await;
     /// # });
     /// ```
+    #[track_caller]
     pub fn watch<D, T>(
         mut dependency_fn: impl FnMut() -> D + 'static,
         mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T + 'static,
@@ -309,8 +333,9 @@ impl Effect<LocalStorage> {
         D: 'static,
         T: 'static,
     {
+        let defined_at = Location::caller();
         let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
+            let (mut rx, owner, inner) = effect_base(defined_at);
             let mut first_run = true;
             let dep_value = Arc::new(RwLock::new(None::<D>));
             let watch_value = Arc::new(RwLock::new(None::<T>));
@@ -373,6 +398,7 @@ impl Effect<SyncStorage> {
     ///
     /// This spawns a task that can be run on any thread. For an effect that will be spawned on
     /// the current thread, use [`new`](Effect::new).
+    #[track_caller]
     pub fn new_sync<T, M>(
         fun: impl EffectFunction<T, M> + Send + Sync + 'static,
     ) -> Self
@@ -390,13 +416,15 @@ impl Effect<SyncStorage> {
     /// that are read inside it change.
     ///
     /// This will run whether the `effects` feature is enabled or not.
+    #[track_caller]
     pub fn new_isomorphic<T, M>(
         mut fun: impl EffectFunction<T, M> + Send + Sync + 'static,
     ) -> Self
     where
         T: Send + Sync + 'static,
     {
-        let (mut rx, owner, inner) = effect_base();
+        let defined_at = Location::caller();
+        let (mut rx, owner, inner) = effect_base(defined_at);
         let mut first_run = true;
         let value = Arc::new(RwLock::new(None::<T>));
 
@@ -435,6 +463,7 @@ impl Effect<SyncStorage> {
     }
 
     /// This is to [`Effect::watch`] what [`Effect::new_sync`] is to [`Effect::new`].
+    #[track_caller]
     pub fn watch_sync<D, T>(
         mut dependency_fn: impl FnMut() -> D + Send + Sync + 'static,
         mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T
@@ -447,7 +476,8 @@ impl Effect<SyncStorage> {
         D: Send + Sync + 'static,
         T: Send + Sync + 'static,
     {
-        let (mut rx, owner, inner) = effect_base();
+        let defined_at = Location::caller();
+        let (mut rx, owner, inner) = effect_base(defined_at);
         let mut first_run = true;
         let dep_value = Arc::new(RwLock::new(None::<D>));
         let watch_value = Arc::new(RwLock::new(None::<T>));
@@ -194,6 +194,13 @@ mod inner {
         fun: Arc<dyn Fn() + Send + Sync>,
         sources: SourceSet,
         any_subscriber: AnySubscriber,
+        /// The source that caused this effect to be marked dirty, if any.
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        triggered_by: Option<&'static Location<'static>>,
+        /// Whether the effect has run once, so that later runs are reported as
+        /// re-runs.
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        has_run: bool,
     }
 
     impl EffectInner {
@@ -221,6 +228,10 @@ mod inner {
                     fun: Arc::new(fun),
                     sources: SourceSet::new(),
                     any_subscriber,
+                    #[cfg(any(debug_assertions, leptos_debuginfo))]
+                    triggered_by: None,
+                    #[cfg(any(debug_assertions, leptos_debuginfo))]
+                    has_run: false,
                 })
             })
         }
@@ -253,9 +264,15 @@ mod inner {
                 ReactiveNodeState::Clean => false,
                 ReactiveNodeState::Check => {
                     let sources = self.read().or_poisoned().sources.clone();
-                    sources
-                        .into_iter()
-                        .any(|source| source.update_if_necessary())
+                    sources.into_iter().any(|source| {
+                        let changed = source.update_if_necessary();
+                        #[cfg(any(debug_assertions, leptos_debuginfo))]
+                        if changed {
+                            self.write().or_poisoned().triggered_by =
+                                source.defined_at();
+                        }
+                        changed
+                    })
                 }
                 ReactiveNodeState::Dirty => true,
             };
@@ -263,6 +280,18 @@ mod inner {
             if needs_update {
                 let mut guard = self.write().or_poisoned();
 
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                {
+                    let triggered_by = guard.triggered_by.take();
+                    if std::mem::replace(&mut guard.has_run, true) {
+                        crate::introspection::recomputed(
+                            crate::introspection::NodeKind::Effect,
+                            guard.defined_at,
+                            triggered_by,
+                        );
+                    }
+                }
+
                 let owner = guard.owner.clone();
                 let any_subscriber = guard.any_subscriber.clone();
                 let fun = guard.fun.clone();
@@ -321,7 +350,14 @@ mod inner {
         }
 
         fn mark_dirty(&self) {
-            self.write().or_poisoned().state = ReactiveNodeState::Dirty;
+            {
+                let mut guard = self.write().or_poisoned();
+                guard.state = ReactiveNodeState::Dirty;
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                {
+                    guard.triggered_by = crate::introspection::notifier();
+                }
+            }
             self.update_if_necessary();
         }
     }
@@ -6,7 +6,10 @@ use crate::{
     },
 };
 use or_poisoned::OrPoisoned;
-use std::sync::{Arc, RwLock, Weak};
+use std::{
+    panic::Location,
+    sync::{Arc, RwLock, Weak},
+};
 
 /// Handles internal subscription logic for effects.
 #[derive(Debug)]
@@ -14,6 +17,39 @@ pub struct EffectInner {
     pub(crate) dirty: bool,
     pub(crate) observer: Sender,
     pub(crate) sources: SourceSet,
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    pub(crate) defined_at: &'static Location<'static>,
+    /// The source that caused this effect to be marked dirty, if any.
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    pub(crate) triggered_by: Option<&'static Location<'static>>,
+    /// Whether the effect has already checked whether to run once, so that
+    /// later runs are reported as re-runs.
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    pub(crate) has_run: bool,
+}
+
+impl EffectInner {
+    /// Creates the inner state of an effect. If `dirty` is `true`, the effect's
+    /// first run happens the first time it checks whether to run.
+    pub(crate) fn new(
+        dirty: bool,
+        observer: Sender,
+        defined_at: &'static Location<'static>,
+    ) -> Self {
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        let _ = defined_at;
+        Self {
+            dirty,
+            observer,
+            sources: SourceSet::new(),
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at,
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            triggered_by: None,
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            has_run: !dirty,
+        }
+    }
 }
 
 impl ToAnySubscriber for Arc<RwLock<EffectInner>> {
@@ -33,6 +69,20 @@ impl ReactiveNode for RwLock<EffectInner> {
 
         if guard.dirty {
             guard.dirty = false;
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            {
+                let triggered_by = guard.triggered_by.take();
+                let has_run = std::mem::replace(&mut guard.has_run, true);
+                let defined_at = guard.defined_at;
+                drop(guard);
+                if has_run {
+                    crate::introspection::recomputed(
+                        crate::introspection::NodeKind::Effect,
+                        defined_at,
+                        triggered_by,
+                    );
+                }
+            }
             return true;
         }
 
@@ -40,6 +90,24 @@ impl ReactiveNode for RwLock<EffectInner> {
 
         drop(guard);
 
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            use crate::traits::DefinedAt;
+
+            let trigger = sources
+                .into_iter()
+                .find(|source| source.update_if_necessary());
+            if let Some(source) = &trigger {
+                let defined_at = self.read().or_poisoned().defined_at;
+                crate::introspection::recomputed(
+                    crate::introspection::NodeKind::Effect,
+                    defined_at,
+                    source.defined_at(),
+                );
+            }
+            trigger.is_some()
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
         sources
             .into_iter()
             .any(|source| source.update_if_necessary())
@@ -52,6 +120,10 @@ impl ReactiveNode for RwLock<EffectInner> {
     fn mark_dirty(&self) {
         let mut lock = self.write().or_poisoned();
         lock.dirty = true;
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            lock.triggered_by = crate::introspection::notifier();
+        }
         lock.observer.notify()
     }
 }
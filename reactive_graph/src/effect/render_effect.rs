@@ -2,16 +2,17 @@ use crate::{
     channel::channel,
     effect::inner::EffectInner,
     graph::{
-        AnySubscriber, ReactiveNode, SourceSet, Subscriber, ToAnySubscriber,
-        WithObserver,
+        AnySubscriber, ReactiveNode, Subscriber, ToAnySubscriber, WithObserver,
     },
     owner::Owner,
+    traits::DefinedAt,
 };
 use futures::StreamExt;
 use or_poisoned::OrPoisoned;
 use std::{
     fmt::Debug,
     mem,
+    panic::Location,
     sync::{Arc, RwLock, Weak},
 };
 
@@ -39,6 +40,19 @@ where
     inner: Arc<RwLock<EffectInner>>,
 }
 
+impl<T> DefinedAt for RenderEffect<T> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.inner.read().or_poisoned().defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
 impl<T> Debug for RenderEffect<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RenderEffect")
@@ -52,11 +66,13 @@ where
     T: 'static,
 {
     /// Creates a new render effect, which immediately runs `fun`.
+    #[track_caller]
     pub fn new(fun: impl FnMut(Option<T>) -> T + 'static) -> Self {
         Self::new_with_value_erased(Box::new(fun), None)
     }
 
     /// Creates a new render effect with an initial value.
+    #[track_caller]
     pub fn new_with_value(
         fun: impl FnMut(Option<T>) -> T + 'static,
         initial_value: Option<T>,
@@ -64,24 +80,25 @@ where
         Self::new_with_value_erased(Box::new(fun), initial_value)
     }
 
+    #[track_caller]
     fn new_with_value_erased(
         mut fun: Box<dyn FnMut(Option<T>) -> T + 'static>,
         initial_value: Option<T>,
     ) -> Self {
         // codegen optimisation:
-        fn prep() -> (Owner, Arc<RwLock<EffectInner>>, crate::channel::Receiver)
+        fn prep(
+            defined_at: &'static Location<'static>,
+        ) -> (Owner, Arc<RwLock<EffectInner>>, crate::channel::Receiver)
         {
             let (observer, rx) = channel();
             let owner = Owner::new();
-            let inner = Arc::new(RwLock::new(EffectInner {
-                dirty: false,
-                observer,
-                sources: SourceSet::new(),
-            }));
+            let inner = Arc::new(RwLock::new(EffectInner::new(
+                false, observer, defined_at,
+            )));
             (owner, inner, rx)
         }
 
-        let (owner, inner, mut rx) = prep();
+        let (owner, inner, mut rx) = prep(Location::caller());
 
         let value = Arc::new(RwLock::new(None::<T>));
 
@@ -146,20 +163,20 @@ where
     T: Send + Sync + 'static,
 {
     /// Creates a render effect that will run whether the `effects` feature is enabled or not.
+    #[track_caller]
     pub fn new_isomorphic(
         fun: impl FnMut(Option<T>) -> T + Send + Sync + 'static,
     ) -> Self {
         fn erased<T: Send + Sync + 'static>(
             mut fun: Box<dyn FnMut(Option<T>) -> T + Send + Sync + 'static>,
+            defined_at: &'static Location<'static>,
         ) -> RenderEffect<T> {
             let (observer, mut rx) = channel();
             let value = Arc::new(RwLock::new(None::<T>));
             let owner = Owner::new();
-            let inner = Arc::new(RwLock::new(EffectInner {
-                dirty: false,
-                observer,
-                sources: SourceSet::new(),
-            }));
+            let inner = Arc::new(RwLock::new(EffectInner::new(
+                false, observer, defined_at,
+            )));
 
             let initial_value = owner
                 .with(|| inner.to_any_subscriber().with_observer(|| fun(None)));
@@ -192,7 +209,7 @@ where
             RenderEffect { value, inner }
         }
 
-        erased(Box::new(fun))
+        erased(Box::new(fun), Location::caller())
     }
 }
 
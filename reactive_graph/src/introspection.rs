@@ -0,0 +1,130 @@
+//! Tools for debugging why memos and effects re-run.
+//!
+//! These are only available in debug builds, or when building with
+//! `--cfg leptos_debuginfo`, because they rely on the locations at which
+//! reactive nodes are created.
+//!
+//! ```rust
+//! # use reactive_graph::prelude::*;
+//! # use reactive_graph::{computed::Memo, signal::RwSignal};
+//! # let owner = reactive_graph::owner::Owner::new(); owner.set();
+//! let count = RwSignal::new(0);
+//! let double = Memo::new(move |_| count.get() * 2);
+//! assert_eq!(double.get(), 0);
+//!
+//! // the memo is subscribed to the signal, and has tracked it as its
+//! // dependency
+//! assert_eq!(count.debug_subscriber_count(), 1);
+//! assert_eq!(double.debug_dependencies()[0].defined_at(), count.defined_at());
+//!
+//! reactive_graph::on_recompute(|info| {
+//!     println!(
+//!         "{:?} at {} re-ran because of {:?}",
+//!         info.kind, info.defined_at, info.triggered_by
+//!     );
+//! });
+//! ```
+
+use crate::{graph::SubscriberSet, signal::subscriber_traits::AsSubscriberSet};
+use or_poisoned::OrPoisoned;
+use std::{
+    borrow::Borrow,
+    cell::Cell,
+    panic::Location,
+    sync::{Arc, RwLock},
+};
+
+/// The kind of reactive node that is re-running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    /// A memo, recalculating its value.
+    Memo,
+    /// An effect (including a render effect), running its function again.
+    Effect,
+}
+
+/// Describes a memo or effect that is about to re-run, for the hook set with
+/// [`on_recompute`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecomputeInfo {
+    /// Whether this is a memo or an effect.
+    pub kind: NodeKind,
+    /// The location at which the memo or effect was created.
+    pub defined_at: &'static Location<'static>,
+    /// The location at which the dependency that caused it to re-run was
+    /// created, if known.
+    pub triggered_by: Option<&'static Location<'static>>,
+}
+
+type RecomputeHook = Arc<dyn Fn(&RecomputeInfo) + Send + Sync>;
+
+static RECOMPUTE_HOOK: RwLock<Option<RecomputeHook>> = RwLock::new(None);
+
+/// Sets a function that is called every time a memo or effect re-runs, on any
+/// thread, replacing any hook that was set before.
+///
+/// The first run of each memo or effect is not reported.
+pub fn on_recompute(hook: impl Fn(&RecomputeInfo) + Send + Sync + 'static) {
+    *RECOMPUTE_HOOK.write().or_poisoned() = Some(Arc::new(hook));
+}
+
+/// Removes the hook set with [`on_recompute`].
+pub fn clear_recompute_hook() {
+    *RECOMPUTE_HOOK.write().or_poisoned() = None;
+}
+
+pub(crate) fn recomputed(
+    kind: NodeKind,
+    defined_at: &'static Location<'static>,
+    triggered_by: Option<&'static Location<'static>>,
+) {
+    // clone the hook so that it can itself set a new hook without deadlocking
+    let hook = RECOMPUTE_HOOK.read().or_poisoned().clone();
+    if let Some(hook) = hook {
+        hook(&RecomputeInfo {
+            kind,
+            defined_at,
+            triggered_by,
+        });
+    }
+}
+
+thread_local! {
+    static NOTIFYING: Cell<Option<&'static Location<'static>>> = const { Cell::new(None) };
+}
+
+/// Runs `fun`, which notifies the subscribers of the source created at
+/// `source`, so that any subscriber that is marked dirty can tell what
+/// triggered it.
+pub(crate) fn notifying<T>(
+    source: Option<&'static Location<'static>>,
+    fun: impl FnOnce() -> T,
+) -> T {
+    let prev = NOTIFYING.replace(source);
+    let value = fun();
+    NOTIFYING.set(prev);
+    value
+}
+
+/// The source whose subscribers are currently being notified, if any.
+pub(crate) fn notifier() -> Option<&'static Location<'static>> {
+    NOTIFYING.get()
+}
+
+/// Counts the subscribers of a reactive source.
+pub trait DebugSubscriberCount {
+    /// The number of memos and effects that are currently subscribed to this
+    /// source.
+    fn debug_subscriber_count(&self) -> usize;
+}
+
+impl<T: AsSubscriberSet> DebugSubscriberCount for T {
+    fn debug_subscriber_count(&self) -> usize {
+        self.as_subscriber_set()
+            .map(|subs| {
+                let subs: &RwLock<SubscriberSet> = subs.borrow();
+                subs.read().or_poisoned().len()
+            })
+            .unwrap_or(0)
+    }
+}
@@ -80,7 +80,10 @@ pub mod computed;
 pub mod diagnostics;
 pub mod effect;
 pub mod graph;
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+pub mod introspection;
 pub mod owner;
+pub mod pending;
 pub mod send_wrapper_ext;
 #[cfg(feature = "serde")]
 mod serde;
@@ -97,9 +100,14 @@ mod nightly;
 
 /// Reexports frequently-used traits.
 pub mod prelude {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    pub use crate::introspection::DebugSubscriberCount;
     pub use crate::{owner::FromLocal, traits::*};
 }
 
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+pub use introspection::on_recompute;
+
 // TODO remove this, it's just useful while developing
 #[allow(unused)]
 #[doc(hidden)]
@@ -315,6 +315,32 @@ impl Owner {
         OWNER.with(|o| o.borrow().as_ref().and_then(|n| n.upgrade()))
     }
 
+    /// Returns the furthest ancestor of this `Owner` that is still alive, like
+    /// the root owner of the app, or of the request being rendered on the
+    /// server, or this `Owner` if it has no parent.
+    pub fn root(&self) -> Owner {
+        let mut root = self.clone();
+        loop {
+            let parent = root
+                .inner
+                .read()
+                .or_poisoned()
+                .parent
+                .as_ref()
+                .and_then(|parent| parent.upgrade());
+            match parent {
+                Some(inner) => {
+                    root = Owner {
+                        inner,
+                        #[cfg(feature = "hydration")]
+                        shared_context: root.shared_context.clone(),
+                    }
+                }
+                None => return root,
+            }
+        }
+    }
+
     /// Returns the [`SharedContext`] associated with this owner, if any.
     #[cfg(feature = "hydration")]
     pub fn shared_context(
@@ -0,0 +1,86 @@
+//! Tracks how much asynchronous work is in progress across the whole app, which
+//! can be used to show a global loading indicator, like a progress bar at the
+//! top of the page.
+//!
+//! An [`AsyncDerived`](crate::computed::AsyncDerived) (and so any resource
+//! built on it) counts as pending while it is loading, unless it has opted out
+//! with
+//! [`untracked_loading`](crate::computed::ArcAsyncDerived::untracked_loading).
+//! An [`Action`](crate::actions::Action) or
+//! [`MultiAction`](crate::actions::MultiAction) counts as pending for each
+//! dispatch that has not resolved yet. Anything else can be counted by holding
+//! a [`PendingGuard`].
+//!
+//! An async derived signal that already has its value when it is created, like
+//! a resource that was resolved on the server and is being hydrated, is not
+//! counted until it loads again.
+//!
+//! ```rust
+//! # use reactive_graph::prelude::*;
+//! # use reactive_graph::pending::*;
+//! # let owner = reactive_graph::owner::Owner::new(); owner.set();
+//! let pending = use_global_pending();
+//! let count = use_global_pending_count();
+//! assert!(!pending.get());
+//!
+//! let guard = PendingGuard::new();
+//! assert!(pending.get());
+//! assert_eq!(count.get(), 1);
+//!
+//! drop(guard);
+//! assert!(!pending.get());
+//! ```
+
+use crate::{
+    computed::Memo,
+    signal::ArcRwSignal,
+    traits::{Get, Update},
+    wrappers::read::Signal,
+};
+use std::sync::OnceLock;
+
+static PENDING: OnceLock<ArcRwSignal<usize>> = OnceLock::new();
+
+fn global_pending() -> &'static ArcRwSignal<usize> {
+    PENDING.get_or_init(|| ArcRwSignal::new(0))
+}
+
+/// Counts as one piece of pending work, for as long as it is alive.
+#[must_use = "the work is only pending until the guard is dropped"]
+#[derive(Debug)]
+pub struct PendingGuard(());
+
+impl PendingGuard {
+    /// Adds one to the number of pending tasks, until the guard is dropped.
+    pub fn new() -> Self {
+        global_pending().update(|n| *n += 1);
+        Self(())
+    }
+}
+
+impl Default for PendingGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        global_pending().update(|n| *n = n.saturating_sub(1));
+    }
+}
+
+/// Whether any async derived signal, resource or action in the app is currently
+/// loading.
+#[track_caller]
+pub fn use_global_pending() -> Signal<bool> {
+    let count = global_pending().clone();
+    Memo::new(move |_| count.get() > 0).into()
+}
+
+/// The number of async derived signals, resources and action dispatches in the
+/// app that are currently loading.
+#[track_caller]
+pub fn use_global_pending_count() -> Signal<usize> {
+    global_pending().read_only().into()
+}
@@ -9,7 +9,7 @@ pub mod guards;
 mod mapped;
 mod read;
 mod rw;
-mod subscriber_traits;
+pub(crate) mod subscriber_traits;
 mod trigger;
 mod write;
 
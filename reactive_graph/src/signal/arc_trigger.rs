@@ -101,6 +101,11 @@ impl DefinedAt for ArcTrigger {
 
 impl Notify for ArcTrigger {
     fn notify(&self) {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        crate::introspection::notifying(self.defined_at(), || {
+            self.inner.mark_dirty()
+        });
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
         self.inner.mark_dirty();
     }
 }
@@ -56,9 +56,15 @@ impl<T: AsSubscriberSet + DefinedAt> ReactiveNode for T {
     fn mark_subscribers_check(&self) {
         if let Some(inner) = self.as_subscriber_set() {
             let subs = inner.borrow().read().unwrap().clone();
-            for sub in subs {
-                sub.mark_dirty();
-            }
+            let notify = || {
+                for sub in subs {
+                    sub.mark_dirty();
+                }
+            };
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            crate::introspection::notifying(self.defined_at(), notify);
+            #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+            notify();
         }
     }
 
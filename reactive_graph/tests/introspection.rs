@@ -0,0 +1,169 @@
+#![cfg(any(debug_assertions, leptos_debuginfo))]
+
+use reactive_graph::{
+    computed::Memo,
+    introspection::{NodeKind, RecomputeInfo},
+    owner::Owner,
+    prelude::*,
+    signal::RwSignal,
+};
+use std::{
+    panic::Location,
+    sync::{Mutex, Once},
+};
+
+#[cfg(feature = "effects")]
+pub mod imports {
+    pub use any_spawner::Executor;
+    pub use reactive_graph::effect::{Effect, ImmediateEffect, RenderEffect};
+    pub use tokio::task;
+}
+
+// the hook is global, so every test records into the same log and only looks at
+// its own nodes
+static RECOMPUTED: Mutex<Vec<RecomputeInfo>> = Mutex::new(Vec::new());
+
+fn record_recomputes() {
+    static SET_HOOK: Once = Once::new();
+    SET_HOOK.call_once(|| {
+        reactive_graph::on_recompute(|info| {
+            RECOMPUTED.lock().unwrap().push(*info)
+        })
+    });
+}
+
+fn recomputed(
+    defined_at: Option<&'static Location<'static>>,
+) -> Vec<RecomputeInfo> {
+    RECOMPUTED
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|info| Some(info.defined_at) == defined_at)
+        .copied()
+        .collect()
+}
+
+#[test]
+fn memo_lists_its_dependencies() {
+    let owner = Owner::new();
+    owner.set();
+
+    let use_b = RwSignal::new(false);
+    let a = RwSignal::new(1);
+    let b = RwSignal::new(2);
+    let memo = Memo::new(move |_| if use_b.get() { b.get() } else { a.get() });
+
+    assert_eq!(memo.get(), 1);
+    let deps = memo
+        .debug_dependencies()
+        .iter()
+        .map(|source| source.defined_at())
+        .collect::<Vec<_>>();
+    assert_eq!(deps, [use_b.defined_at(), a.defined_at()]);
+    assert_eq!(a.debug_subscriber_count(), 1);
+    assert_eq!(b.debug_subscriber_count(), 0);
+
+    use_b.set(true);
+    assert_eq!(memo.get(), 2);
+    let deps = memo
+        .debug_dependencies()
+        .iter()
+        .map(|source| source.defined_at())
+        .collect::<Vec<_>>();
+    assert_eq!(deps, [use_b.defined_at(), b.defined_at()]);
+    assert_eq!(a.debug_subscriber_count(), 0);
+    assert_eq!(b.debug_subscriber_count(), 1);
+    assert_eq!(memo.debug_subscriber_count(), 0);
+}
+
+#[test]
+fn on_recompute_reports_memo_trigger() {
+    record_recomputes();
+    let owner = Owner::new();
+    owner.set();
+
+    let a = RwSignal::new(1);
+    let b = RwSignal::new(10);
+    let double = Memo::new(move |_| a.get() * 2);
+    let sum = Memo::new(move |_| double.get() + b.get());
+
+    assert_eq!(sum.get(), 12);
+    // the first run is not a re-run
+    assert!(recomputed(sum.defined_at()).is_empty());
+
+    b.set(20);
+    assert_eq!(sum.get(), 22);
+    a.set(2);
+    assert_eq!(sum.get(), 24);
+
+    let reruns = recomputed(sum.defined_at());
+    assert_eq!(reruns.len(), 2);
+    assert!(reruns.iter().all(|info| info.kind == NodeKind::Memo));
+    assert_eq!(reruns[0].triggered_by, b.defined_at());
+    assert_eq!(reruns[1].triggered_by, double.defined_at());
+    assert_eq!(
+        recomputed(double.defined_at())
+            .iter()
+            .map(|info| info.triggered_by)
+            .collect::<Vec<_>>(),
+        [a.defined_at()]
+    );
+}
+
+#[cfg(feature = "effects")]
+#[test]
+fn on_recompute_reports_immediate_effect_trigger() {
+    use imports::*;
+
+    record_recomputes();
+
+    let owner = Owner::new();
+    owner.set();
+
+    let a = RwSignal::new(1);
+    let effect = ImmediateEffect::new(move || {
+        a.get();
+    });
+    a.set(2);
+
+    let reruns = recomputed(effect.defined_at());
+    assert_eq!(reruns.len(), 1);
+    assert_eq!(reruns[0].kind, NodeKind::Effect);
+    assert_eq!(reruns[0].triggered_by, a.defined_at());
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn on_recompute_reports_effect_trigger() {
+    use imports::*;
+
+    record_recomputes();
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(1);
+            let double = Memo::new(move |_| a.get() * 2);
+            let effect = Effect::new(move |_| {
+                a.get();
+            });
+            let render_effect = RenderEffect::new(move |_| {
+                double.get();
+            });
+
+            Executor::tick().await;
+            a.set(2);
+            Executor::tick().await;
+
+            let reruns = recomputed(effect.defined_at());
+            assert_eq!(reruns.len(), 1);
+            assert_eq!(reruns[0].triggered_by, a.defined_at());
+            let reruns = recomputed(render_effect.defined_at());
+            assert_eq!(reruns.len(), 1);
+            assert_eq!(reruns[0].triggered_by, double.defined_at());
+        })
+        .await;
+}
@@ -0,0 +1,83 @@
+use any_spawner::Executor;
+use reactive_graph::{
+    actions::ArcAction,
+    computed::ArcAsyncDerived,
+    owner::Owner,
+    pending::{use_global_pending, use_global_pending_count},
+    signal::{ArcTrigger, RwSignal},
+    traits::{Get, Set},
+};
+use std::future::pending;
+
+// the pending count is global, so everything is checked in a single test to
+// keep the counts from other tests out of it
+#[tokio::test]
+async fn global_pending_count() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let is_pending = use_global_pending();
+    let count = use_global_pending_count();
+    assert_eq!(count.get(), 0);
+    assert!(!is_pending.get());
+
+    // counts once while loading, even if it reruns several times before
+    // finishing
+    let signal = RwSignal::new(0);
+    let value = ArcAsyncDerived::new(move || async move {
+        let value = signal.get();
+        Executor::tick().await;
+        value
+    });
+    assert_eq!(count.get(), 1);
+    assert!(is_pending.get());
+    signal.set(1);
+    signal.set(2);
+    Executor::tick().await;
+    assert!(count.get() <= 1);
+    assert_eq!(value.clone().await, 2);
+    assert_eq!(count.get(), 0);
+    assert!(!is_pending.get());
+
+    // stops counting when it is dropped before it finishes
+    let forever = ArcAsyncDerived::new(pending::<()>);
+    assert_eq!(count.get(), 1);
+    drop(forever);
+    assert_eq!(count.get(), 0);
+
+    // can opt out
+    let untracked = ArcAsyncDerived::new(pending::<()>).untracked_loading();
+    Executor::tick().await;
+    assert_eq!(count.get(), 0);
+    drop(untracked);
+
+    // is not counted if it already has its value, as when it is hydrated
+    let source = ArcTrigger::new();
+    let hydrated = ArcAsyncDerived::new_with_manual_dependencies(
+        Some(1),
+        pending::<i32>,
+        &source,
+    );
+    Executor::tick().await;
+    assert_eq!(count.get(), 0);
+    drop(hydrated);
+
+    // counts each dispatch of an action until it resolves or is aborted
+    let action = ArcAction::new(|_: &()| async {
+        Executor::tick().await;
+    });
+    action.dispatch(());
+    action.dispatch(());
+    assert_eq!(count.get(), 2);
+    Executor::tick().await;
+    Executor::tick().await;
+    assert_eq!(count.get(), 0);
+
+    let forever = ArcAction::new(|_: &()| pending::<()>());
+    let handle = forever.dispatch(());
+    assert_eq!(count.get(), 1);
+    handle.abort();
+    Executor::tick().await;
+    assert_eq!(count.get(), 0);
+}
@@ -0,0 +1,32 @@
+//! Prints the route manifest of a small app as JSON.
+//!
+//! ```sh
+//! cargo run -p leptos_router --example route_manifest --features manifest
+//! ```
+
+use leptos_router::{
+    manifest, path,
+    static_routes::{StaticParamsMap, StaticRoute},
+    Method, NestedRoute, RouteDefs, SsrMode,
+};
+
+fn main() {
+    let routes = RouteDefs::<_>::new((
+        NestedRoute::new(path!("/"), || ()),
+        NestedRoute::new(path!("/users/:id"), || ())
+            .custom_method(Method::Post.as_str().to_string()),
+        NestedRoute::new(path!("/blog/:slug"), || ()).ssr_mode(
+            SsrMode::Static(StaticRoute::new().prerender_params(|| async {
+                let mut params = StaticParamsMap::new();
+                params.insert("slug", vec!["hello-world".into()]);
+                params
+            })),
+        ),
+        NestedRoute::not_found_route(|| ()),
+    ));
+
+    let manifest = futures::executor::block_on(
+        manifest::export_with_static_params(&routes),
+    );
+    println!("{}", manifest.to_json());
+}
@@ -0,0 +1,21 @@
+/// Marks a response from a route that should not be written to the access log,
+/// because it is set with
+/// [`NestedRoute::exclude_from_access_log`](crate::NestedRoute::exclude_from_access_log).
+///
+/// The server integrations insert this into the extensions of every response
+/// from such a route, including the ones that reject a request before it is
+/// rendered. An access-logging middleware can check for it, like this for
+/// `tower-http`'s `TraceLayer`:
+///
+/// ```rust,ignore
+/// TraceLayer::new_for_http().on_response(
+///     |res: &Response<_>, latency, span: &Span| {
+///         if res.extensions().get::<ExcludeFromAccessLog>().is_none() {
+///             let status = res.status();
+///             tracing::info!(parent: span, %status, ?latency, "response");
+///         }
+///     },
+/// )
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ExcludeFromAccessLog;
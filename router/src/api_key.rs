@@ -0,0 +1,149 @@
+use crate::location::Url;
+use futures::future::BoxFuture;
+use std::{borrow::Cow, fmt, sync::Arc};
+
+/// Where the API key for a request is found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiKeySource {
+    /// A request header, like `X-API-Key`.
+    Header(&'static str),
+    /// A parameter in the query string, like `api_key`.
+    QueryParam(&'static str),
+    /// A cookie.
+    Cookie(&'static str),
+}
+
+impl ApiKeySource {
+    /// Finds the API key in a request, given a way to look up its headers and
+    /// its query string (without the leading `?`).
+    pub fn find<'a>(
+        &self,
+        header: impl Fn(&str) -> Option<&'a str>,
+        query: Option<&'a str>,
+    ) -> Option<Cow<'a, str>> {
+        let key = match self {
+            ApiKeySource::Header(name) => header(name).map(Cow::Borrowed),
+            ApiKeySource::QueryParam(name) => query?
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| Cow::Owned(Url::unescape(value))),
+            ApiKeySource::Cookie(name) => header("cookie")?
+                .split(';')
+                .filter_map(|pair| pair.trim().split_once('='))
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| Cow::Borrowed(value)),
+        };
+        key.filter(|key| !key.is_empty())
+    }
+
+    /// The value of the `WWW-Authenticate` header sent when a request is
+    /// rejected.
+    pub fn www_authenticate(&self) -> String {
+        let (location, name) = match self {
+            ApiKeySource::Header(name) => ("header", name),
+            ApiKeySource::QueryParam(name) => ("query", name),
+            ApiKeySource::Cookie(name) => ("cookie", name),
+        };
+        format!("ApiKey realm=\"api\", in=\"{location}\", name=\"{name}\"")
+    }
+}
+
+/// Checks whether an API key is allowed to access a route.
+pub trait ApiKeyValidator: Send + Sync {
+    /// Resolves to `true` if the key is valid.
+    fn validate(&self, key: &str) -> BoxFuture<'static, bool>;
+}
+
+/// Defines how requests to a route are authenticated with an API key.
+///
+/// The server integrations read the key from `source` and pass it to
+/// `validator`. Requests with a missing or invalid key are rejected with
+/// `401 Unauthorized` and a `WWW-Authenticate` header.
+#[derive(Clone)]
+pub struct ApiKeyConfig {
+    /// Where the key is found in the request.
+    pub source: ApiKeySource,
+    /// Checks whether the key is valid.
+    pub validator: Arc<dyn ApiKeyValidator>,
+}
+
+impl fmt::Debug for ApiKeyConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKeyConfig")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for ApiKeyConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+            && Arc::ptr_eq(&self.validator, &other.validator)
+    }
+}
+
+impl Eq for ApiKeyConfig {}
+
+/// The API key of a request, after it has been validated.
+///
+/// The server integrations provide this via context when handling a route with
+/// an [`ApiKeyConfig`], so that it can be used to scope what the request can
+/// access.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ApiKey(Arc<str>);
+
+impl ApiKey {
+    /// Wraps a validated key.
+    pub fn new(key: impl Into<Arc<str>>) -> Self {
+        Self(key.into())
+    }
+
+    /// The key itself.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ApiKeySource;
+
+    #[test]
+    fn finds_key_in_header_or_cookie() {
+        let headers = |name: &str| match name {
+            "X-API-Key" => Some("from-header"),
+            "cookie" => Some("theme=dark; api_key=from-cookie"),
+            _ => None,
+        };
+        assert_eq!(
+            ApiKeySource::Header("X-API-Key")
+                .find(headers, None)
+                .as_deref(),
+            Some("from-header")
+        );
+        assert_eq!(
+            ApiKeySource::Cookie("api_key")
+                .find(headers, None)
+                .as_deref(),
+            Some("from-cookie")
+        );
+        assert_eq!(
+            ApiKeySource::Header("Authorization").find(headers, None),
+            None
+        );
+    }
+
+    // unescaping query values requires `ssr` outside the browser
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn finds_key_in_query() {
+        let source = ApiKeySource::QueryParam("api_key");
+        assert_eq!(
+            source.find(|_| None, Some("q=1&api_key=a%2Fb")).as_deref(),
+            Some("a/b")
+        );
+        assert_eq!(source.find(|_| None, Some("api_key=")), None);
+        assert_eq!(source.find(|_| None, None), None);
+    }
+}
@@ -0,0 +1,284 @@
+use crate::{MatchError, MatchNestedRoutes, MatchRequest, Method, RouteDefs};
+use futures::future::{self, join_all, Either};
+use or_poisoned::OrPoisoned;
+use serde_json::{json, Value};
+use std::{
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+
+/// One of the requests in the body of a request to a
+/// [batch route](crate::NestedRoute::batch).
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchRequest {
+    /// The method of the request, like `GET`.
+    pub method: Method,
+    /// The path of the request, which may include a query.
+    pub path: String,
+    /// The JSON body of the request, if it has one.
+    pub body: Option<Value>,
+}
+
+impl BatchRequest {
+    fn from_json(value: Value) -> Result<Self, String> {
+        let Value::Object(mut request) = value else {
+            return Err("each request must be an object".into());
+        };
+        let method = match request.remove("method") {
+            None => Method::Get,
+            Some(Value::String(method)) => Method::from_name(method),
+            Some(_) => return Err("`method` must be a string".into()),
+        };
+        let path = match request.remove("path") {
+            Some(Value::String(path)) if path.starts_with('/') => path,
+            _ => {
+                return Err(
+                    "`path` must be a string that starts with `/`".into()
+                )
+            }
+        };
+        let body = request.remove("body").filter(|body| !body.is_null());
+        Ok(Self { method, path, body })
+    }
+}
+
+/// The response to one of the requests to a
+/// [batch route](crate::NestedRoute::batch), which is sent back in the same
+/// position as its request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResponse {
+    /// The HTTP status code of the response.
+    pub status: u16,
+    /// The body of the response, which is its JSON if it is JSON, or its text
+    /// otherwise.
+    pub body: Value,
+}
+
+impl BatchResponse {
+    /// A response with the given status, and a body that is parsed as JSON if
+    /// it can be, or kept as text otherwise.
+    pub fn from_body(status: u16, body: &[u8]) -> Self {
+        let body = serde_json::from_slice(body).unwrap_or_else(|_| {
+            Value::String(String::from_utf8_lossy(body).into_owned())
+        });
+        Self { status, body }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({ "status": self.status, "body": self.body })
+    }
+}
+
+/// Runs the requests of a [batch route](crate::NestedRoute::batch), which the
+/// server integrations implement by calling the server function at the path of
+/// each request.
+///
+/// Any async function that takes a [`BatchRequest`] and returns a
+/// [`BatchResponse`] is an executor. Its future does not have to be `Send`,
+/// although the batch is only `Send` if it is.
+pub trait BatchExecutor {
+    /// The future of a response.
+    type Future: Future<Output = BatchResponse>;
+
+    /// Runs `request`, returning its response.
+    fn execute(&self, request: BatchRequest) -> Self::Future;
+}
+
+impl<F, Fut> BatchExecutor for F
+where
+    F: Fn(BatchRequest) -> Fut,
+    Fut: Future<Output = BatchResponse>,
+{
+    type Future = Fut;
+
+    fn execute(&self, request: BatchRequest) -> Self::Future {
+        self(request)
+    }
+}
+
+/// Why the body of a request to a [batch route](crate::NestedRoute::batch) was
+/// rejected as a whole, rather than answered with a response per request.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum BatchError {
+    /// The body is not a JSON array of requests.
+    #[error("invalid batch: {0}")]
+    Invalid(String),
+    /// The body has more requests than the route allows.
+    #[error("a batch can have at most {0} requests")]
+    TooManyRequests(usize),
+}
+
+impl BatchError {
+    /// The HTTP status code that corresponds to this error.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            BatchError::Invalid(_) => 400,
+            BatchError::TooManyRequests(_) => 413,
+        }
+    }
+}
+
+type MatchFn = dyn Fn(&BatchRequest) -> Result<(), MatchError> + Send + Sync;
+
+/// The requests a [batch route](crate::NestedRoute::batch) accepts, which are
+/// those that match its inner routes.
+///
+/// A request that does not match them, or whose method they do not accept, is
+/// answered with `404 Not Found` or `405 Method Not Allowed` without being run,
+/// even if the routes have an [error route](RouteDefs::on_error) that would
+/// handle it. The others are run concurrently, and their responses are returned
+/// in the same order as the requests.
+#[derive(Clone)]
+pub struct Batch {
+    matches: Arc<MatchFn>,
+    max_requests: usize,
+}
+
+impl Batch {
+    /// Accepts the requests that match `routes`, up to 50 at a time.
+    pub fn new<Children>(routes: RouteDefs<Children>) -> Self
+    where
+        Children: MatchNestedRoutes + Send + 'static,
+    {
+        // the views of routes are not `Sync`, so they are matched one batch
+        // request at a time
+        let routes = Mutex::new(routes);
+        Self {
+            matches: Arc::new(move |request| {
+                let (path, query) =
+                    request.path.split_once('?').map_or(
+                        (request.path.as_str(), None),
+                        |(path, query)| (path, Some(query)),
+                    );
+                let mut req = MatchRequest::new(path).method(&request.method);
+                if let Some(query) = query {
+                    req = req.query(query);
+                }
+                let routes = routes.lock().or_poisoned();
+                match routes.resolve_request(req)?.error() {
+                    None => Ok(()),
+                    Some(error) => Err(error.clone()),
+                }
+            }),
+            max_requests: 50,
+        }
+    }
+
+    /// Sets the most requests a batch can have.
+    pub fn max_requests(mut self, max_requests: usize) -> Self {
+        self.max_requests = max_requests;
+        self
+    }
+
+    /// Parses the requests in the JSON `body` of a request to the batch route.
+    pub fn parse(&self, body: &[u8]) -> Result<Vec<BatchRequest>, BatchError> {
+        let Value::Array(requests) = serde_json::from_slice(body)
+            .map_err(|error| BatchError::Invalid(error.to_string()))?
+        else {
+            return Err(BatchError::Invalid(
+                "the body must be an array of requests".into(),
+            ));
+        };
+        if requests.len() > self.max_requests {
+            return Err(BatchError::TooManyRequests(self.max_requests));
+        }
+        requests
+            .into_iter()
+            .map(BatchRequest::from_json)
+            .collect::<Result<_, _>>()
+            .map_err(BatchError::Invalid)
+    }
+
+    /// Runs the requests in the JSON `body` of a request to the batch route
+    /// with `executor`, returning the JSON array of their responses.
+    pub async fn execute(
+        &self,
+        body: &[u8],
+        executor: &impl BatchExecutor,
+    ) -> Result<Value, BatchError> {
+        let responses =
+            join_all(self.parse(body)?.into_iter().map(|request| {
+                match (self.matches)(&request) {
+                    Ok(()) => Either::Left(executor.execute(request)),
+                    Err(error) => Either::Right(future::ready(BatchResponse {
+                        status: error.status_code(),
+                        body: json!({ "error": error.to_string() }),
+                    })),
+                }
+            }))
+            .await;
+        Ok(Value::Array(
+            responses.iter().map(BatchResponse::to_json).collect(),
+        ))
+    }
+}
+
+impl fmt::Debug for Batch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Batch")
+            .field("max_requests", &self.max_requests)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Batch {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.matches, &other.matches)
+    }
+}
+
+impl Eq for Batch {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Batch, BatchError, BatchRequest, BatchResponse};
+    use crate::{NestedRoute, RouteDefs, StaticSegment, WildcardSegment};
+    use futures::executor::block_on;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn responses_are_in_the_order_of_the_requests() {
+        let batch = Batch::new(RouteDefs::new(
+            NestedRoute::new((StaticSegment("api"), WildcardSegment("fn")), ())
+                .custom_method("POST"),
+        ))
+        .max_requests(4);
+        let body = json!([
+            { "method": "GET", "path": "/api/slow" },
+            { "path": "/api/fast?id=1" },
+            { "method": "POST", "path": "/api/fast", "body": { "id": 2 } },
+            { "path": "/admin" },
+        ])
+        .to_string();
+        let executor = |request: BatchRequest| async move {
+            BatchResponse {
+                status: 200,
+                body: json!({ "path": request.path, "body": request.body }),
+            }
+        };
+        let responses = block_on(batch.execute(body.as_bytes(), &executor));
+        assert_eq!(
+            responses,
+            Ok(json!([
+                { "status": 200, "body": { "path": "/api/slow", "body": null } },
+                { "status": 200, "body": { "path": "/api/fast?id=1", "body": null } },
+                { "status": 200, "body": { "path": "/api/fast", "body": { "id": 2 } } },
+                { "status": 404, "body": { "error": "no route matches this path" } },
+            ]))
+        );
+
+        let too_many =
+            Value::Array(vec![json!({ "path": "/api/a" }); 5]).to_string();
+        assert_eq!(
+            block_on(batch.execute(too_many.as_bytes(), &executor)),
+            Err(BatchError::TooManyRequests(4))
+        );
+        assert!(matches!(
+            batch.parse(br#"[{ "path": "api/a" }]"#),
+            Err(BatchError::Invalid(_))
+        ));
+        assert!(matches!(batch.parse(br#"{}"#), Err(BatchError::Invalid(_))));
+    }
+}
@@ -0,0 +1,253 @@
+use crate::{validate_response_schema, SchemaMismatch};
+use serde_json::{json, Value};
+use std::{borrow::Cow, sync::Arc};
+use thiserror::Error;
+
+/// The JSON schemas that the bodies of requests to a route must match, by their
+/// content type, set with
+/// [`NestedRoute::validate_body_json`](crate::NestedRoute::validate_body_json)
+/// and
+/// [`NestedRoute::validate_body_for`](crate::NestedRoute::validate_body_for).
+///
+/// The schemas are checked with [`validate_response_schema`], so they can use
+/// the keywords it understands, and others are ignored.
+///
+/// ```rust
+/// use leptos_router::{BodySchemas, BodyValidationError};
+/// use serde_json::json;
+///
+/// let schemas = BodySchemas::default().json(json!({
+///     "type": "object",
+///     "properties": { "name": { "type": "string" } },
+///     "required": ["name"],
+/// }));
+///
+/// let body = schemas
+///     .validate(Some("application/json"), br#"{ "name": "Ferris" }"#)
+///     .unwrap();
+/// assert_eq!(body.value()["name"], "Ferris");
+///
+/// let error = schemas.validate(None, br#"{}"#).unwrap_err();
+/// assert!(matches!(error, BodyValidationError::Invalid(_)));
+/// assert_eq!(error.status_code(), 422);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BodySchemas {
+    json: Option<Arc<Value>>,
+    by_mime: Vec<(Cow<'static, str>, Arc<Value>)>,
+}
+
+impl BodySchemas {
+    /// Checks bodies with the content type `application/json`, or another JSON
+    /// type like `application/merge-patch+json`, or without a content type,
+    /// against `schema`.
+    pub fn json(mut self, schema: Value) -> Self {
+        self.json = Some(Arc::new(schema));
+        self
+    }
+
+    /// Checks bodies with the content type `mime`, like
+    /// `application/vnd.api+json`, against `schema`, which comes before the
+    /// [JSON schema](Self::json) for that type. The bodies are parsed as JSON,
+    /// whatever the type.
+    pub fn mime(
+        mut self,
+        mime: impl Into<Cow<'static, str>>,
+        schema: Value,
+    ) -> Self {
+        let mime = mime.into();
+        let schema = Arc::new(schema);
+        match self
+            .by_mime
+            .iter_mut()
+            .find(|(existing, _)| existing.eq_ignore_ascii_case(&mime))
+        {
+            Some((_, existing)) => *existing = schema,
+            None => self.by_mime.push((mime, schema)),
+        }
+        self
+    }
+
+    /// Adds the schemas of `other` to these, replacing those for the same
+    /// content types.
+    pub(crate) fn extend(&mut self, other: BodySchemas) {
+        if other.json.is_some() {
+            self.json = other.json;
+        }
+        for (mime, schema) in other.by_mime {
+            match self
+                .by_mime
+                .iter_mut()
+                .find(|(existing, _)| existing.eq_ignore_ascii_case(&mime))
+            {
+                Some((_, existing)) => *existing = schema,
+                None => self.by_mime.push((mime, schema)),
+            }
+        }
+    }
+
+    /// Whether the bodies of requests with `method` are checked: requests with
+    /// methods that do not have a body, like `GET`, `HEAD` and `OPTIONS`, are
+    /// not.
+    pub fn applies_to(&self, method: &str) -> bool {
+        !["GET", "HEAD", "OPTIONS"]
+            .iter()
+            .any(|exempt| method.eq_ignore_ascii_case(exempt))
+    }
+
+    /// The schema for bodies with the `content_type` header, if there is one.
+    pub fn schema_for(&self, content_type: Option<&str>) -> Option<&Value> {
+        let mime = content_type
+            .map(|content_type| {
+                content_type.split(';').next().unwrap_or_default().trim()
+            })
+            .filter(|mime| !mime.is_empty());
+        let Some(mime) = mime else {
+            return self.json.as_deref();
+        };
+        self.by_mime
+            .iter()
+            .find(|(existing, _)| existing.eq_ignore_ascii_case(mime))
+            .map(|(_, schema)| &**schema)
+            .or_else(|| {
+                let mime = mime.to_ascii_lowercase();
+                (mime == "application/json" || mime.ends_with("+json"))
+                    .then_some(self.json.as_deref())
+                    .flatten()
+            })
+    }
+
+    /// Parses `body` as JSON, and checks it against the schema for its
+    /// `content_type`, returning the parsed body if it matches.
+    pub fn validate(
+        &self,
+        content_type: Option<&str>,
+        body: &[u8],
+    ) -> Result<ValidatedBody, BodyValidationError> {
+        let schema = self.schema_for(content_type).ok_or_else(|| {
+            BodyValidationError::UnsupportedMediaType(
+                content_type.unwrap_or_default().to_string(),
+            )
+        })?;
+        let value = serde_json::from_slice::<Value>(body)
+            .map_err(|error| BodyValidationError::NotJson(error.to_string()))?;
+        let mismatches = validate_response_schema(schema, &value);
+        if mismatches.is_empty() {
+            Ok(ValidatedBody(Arc::new(value)))
+        } else {
+            Err(BodyValidationError::Invalid(mismatches))
+        }
+    }
+}
+
+/// Why the body of a request does not match the [`BodySchemas`] of its route.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum BodyValidationError {
+    /// The route has no schema for the content type of the body.
+    #[error("bodies with the content type `{0}` are not accepted")]
+    UnsupportedMediaType(String),
+    /// The body is not JSON.
+    #[error("the body is not JSON: {0}")]
+    NotJson(String),
+    /// The body does not match the schema, in each of these ways.
+    #[error("the body does not match its schema")]
+    Invalid(Vec<SchemaMismatch>),
+}
+
+impl BodyValidationError {
+    /// The HTTP status code of the response to a request with this error:
+    /// `415 Unsupported Media Type`, `400 Bad Request`, or
+    /// `422 Unprocessable Entity` for a body that does not match its schema.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            BodyValidationError::UnsupportedMediaType(_) => 415,
+            BodyValidationError::NotJson(_) => 400,
+            BodyValidationError::Invalid(_) => 422,
+        }
+    }
+
+    /// The JSON body of the response to a request with this error, which lists
+    /// each way in which the body does not match, like
+    /// `{"errors":[{"path":"/name","message":"at `/name`: expected a value of type string"}]}`.
+    pub fn to_json(&self) -> String {
+        let errors = match self {
+            BodyValidationError::Invalid(mismatches) => mismatches
+                .iter()
+                .map(|mismatch| {
+                    json!({
+                        "path": mismatch.path(),
+                        "message": mismatch.to_string(),
+                    })
+                })
+                .collect(),
+            error => vec![json!({ "path": "", "message": error.to_string() })],
+        };
+        json!({ "errors": errors }).to_string()
+    }
+}
+
+/// The body of a request that matched the [`BodySchemas`] of its route, parsed
+/// as JSON.
+///
+/// The server integrations provide this via context when handling a route with
+/// [`NestedRoute::validate_body_json`](crate::NestedRoute::validate_body_json),
+/// because the body has already been read and parsed to validate it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedBody(Arc<Value>);
+
+impl ValidatedBody {
+    /// The parsed body.
+    pub fn value(&self) -> &Value {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BodySchemas, BodyValidationError};
+    use serde_json::json;
+
+    #[test]
+    fn bodies_are_checked_against_the_schema_for_their_content_type() {
+        let schemas = BodySchemas::default()
+            .json(json!({ "type": "object", "required": ["name"] }))
+            .mime(
+                "application/vnd.api+json",
+                json!({ "type": "object", "required": ["data"] }),
+            );
+
+        assert!(schemas
+            .validate(Some("application/json; charset=utf-8"), br#"{"name":1}"#)
+            .is_ok());
+        assert!(schemas
+            .validate(Some("application/merge-patch+json"), br#"{"name":1}"#)
+            .is_ok());
+        assert!(schemas
+            .validate(Some("Application/VND.api+json"), br#"{"data":[]}"#)
+            .is_ok());
+
+        let error = schemas
+            .validate(Some("application/vnd.api+json"), br#"{"name":1}"#)
+            .unwrap_err();
+        assert_eq!(error.status_code(), 422);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&error.to_json())
+                .unwrap(),
+            json!({ "errors": [{
+                "path": "",
+                "message": "at `/`: expected a property `data`",
+            }] })
+        );
+
+        assert_eq!(
+            schemas.validate(Some("text/plain"), b"name").unwrap_err(),
+            BodyValidationError::UnsupportedMediaType("text/plain".into())
+        );
+        assert_eq!(
+            schemas.validate(None, b"{").unwrap_err().status_code(),
+            400
+        );
+        assert!(schemas.applies_to("POST"));
+        assert!(!schemas.applies_to("get"));
+    }
+}
@@ -0,0 +1,140 @@
+/// Whether the canonical form of a path ends with a slash.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TrailingSlash {
+    /// Keep the trailing slash of each path as it is.
+    #[default]
+    Keep,
+    /// Remove any trailing slash, so that `/about/` is canonicalized to
+    /// `/about`.
+    Remove,
+    /// Add a trailing slash, so that `/about` is canonicalized to `/about/`.
+    Add,
+}
+
+/// Defines the canonical form of the paths an application serves, so that
+/// requests for any other form of a path can be redirected to it.
+///
+/// Used with [`RouteDefs::canonical_paths`](crate::RouteDefs::canonical_paths).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CanonicalPathConfig {
+    /// Whether canonical paths end with a slash. The root path is always `/`.
+    pub trailing_slash: TrailingSlash,
+    /// Whether canonical paths are lowercase. Percent-encoded characters are
+    /// left as they are.
+    pub lowercase: bool,
+    /// Whether repeated slashes are merged into one, so that `/blog//post` is
+    /// canonicalized to `/blog/post`.
+    pub merge_slashes: bool,
+}
+
+impl Default for CanonicalPathConfig {
+    fn default() -> Self {
+        Self {
+            trailing_slash: TrailingSlash::Keep,
+            lowercase: false,
+            merge_slashes: true,
+        }
+    }
+}
+
+impl CanonicalPathConfig {
+    /// Returns the canonical form of `path`, or `None` if it is already
+    /// canonical.
+    ///
+    /// Any query string or fragment is kept as it is. This is meant for issuing
+    /// permanent (`301`) redirects from every other form of a path to its
+    /// canonical form.
+    pub fn canonicalize(&self, path: &str) -> Option<String> {
+        let (path, rest) = path
+            .find(['?', '#'])
+            .map_or((path, ""), |idx| path.split_at(idx));
+
+        let mut canonical = String::with_capacity(path.len() + 1);
+        let mut chars = path.chars();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '/' if self.merge_slashes && canonical.ends_with('/') => {}
+                '%' => {
+                    // keep the case of percent-encoded characters, which is not
+                    // significant
+                    canonical.push(ch);
+                    canonical.extend(chars.by_ref().take(2));
+                }
+                _ if self.lowercase => canonical.extend(ch.to_lowercase()),
+                _ => canonical.push(ch),
+            }
+        }
+
+        match self.trailing_slash {
+            TrailingSlash::Keep => {}
+            TrailingSlash::Remove => {
+                let len = canonical.trim_end_matches('/').len();
+                canonical.truncate(len.max(1));
+            }
+            TrailingSlash::Add => {
+                if !canonical.ends_with('/') {
+                    canonical.push('/');
+                }
+            }
+        }
+        if canonical.is_empty() || !canonical.starts_with('/') {
+            canonical.insert(0, '/');
+        }
+
+        (canonical != path).then(|| canonical + rest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CanonicalPathConfig, TrailingSlash};
+
+    #[test]
+    fn canonicalizes_paths() {
+        let config = CanonicalPathConfig {
+            trailing_slash: TrailingSlash::Remove,
+            lowercase: true,
+            merge_slashes: true,
+        };
+        assert_eq!(
+            config.canonicalize("/Blog/Post"),
+            Some("/blog/post".into())
+        );
+        assert_eq!(
+            config.canonicalize("/blog/post/"),
+            Some("/blog/post".into())
+        );
+        assert_eq!(
+            config.canonicalize("//blog///post"),
+            Some("/blog/post".into())
+        );
+        assert_eq!(
+            config.canonicalize("/Blog//Post/?Page=2"),
+            Some("/blog/post?Page=2".into())
+        );
+        assert_eq!(
+            config.canonicalize("/Caf%C3%A9"),
+            Some("/caf%C3%A9".into())
+        );
+        assert_eq!(config.canonicalize("/blog/post"), None);
+        assert_eq!(config.canonicalize("/"), None);
+        assert_eq!(config.canonicalize("/?page=2"), None);
+    }
+
+    #[test]
+    fn adds_or_keeps_trailing_slash() {
+        let add = CanonicalPathConfig {
+            trailing_slash: TrailingSlash::Add,
+            ..Default::default()
+        };
+        assert_eq!(add.canonicalize("/about"), Some("/about/".into()));
+        assert_eq!(add.canonicalize("/about//"), Some("/about/".into()));
+        assert_eq!(add.canonicalize("/about/"), None);
+        assert_eq!(add.canonicalize("/"), None);
+
+        let keep = CanonicalPathConfig::default();
+        assert_eq!(keep.canonicalize("/About/"), None);
+        assert_eq!(keep.canonicalize("/about"), None);
+        assert_eq!(keep.canonicalize("/about//"), Some("/about/".into()));
+    }
+}
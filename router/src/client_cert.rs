@@ -0,0 +1,401 @@
+use futures::future::BoxFuture;
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    sync::Arc,
+};
+
+/// The certificate a client presented during the TLS handshake, for routes that
+/// [require one](crate::NestedRoute::require_client_cert).
+///
+/// The server that terminates TLS (or a middleware in front of the Leptos
+/// routes) inserts this into the request extensions, from the DER encoding of
+/// the client's leaf certificate. The server integrations then provide it via
+/// context to the routes that require it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClientCert(Arc<[u8]>);
+
+impl ClientCert {
+    /// Wraps the DER encoding of an X.509 certificate.
+    pub fn from_der(der: impl Into<Arc<[u8]>>) -> Self {
+        Self(der.into())
+    }
+
+    /// The DER encoding of the certificate.
+    pub fn der(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// The common name (`CN`) of the certificate's subject, if it has one.
+    ///
+    /// Returns `None` if the certificate cannot be parsed.
+    pub fn common_name(&self) -> Option<String> {
+        let subject = tbs_fields(&self.0)?.subject;
+        for (_, rdn) in Der(subject) {
+            for (_, attribute) in Der(rdn) {
+                let mut attribute = Der(attribute);
+                let (_, oid) = attribute.next()?;
+                if oid == OID_COMMON_NAME {
+                    let (_, value) = attribute.next()?;
+                    return String::from_utf8(value.to_vec()).ok();
+                }
+            }
+        }
+        None
+    }
+
+    /// The subject alternative names of the certificate: DNS names, email
+    /// addresses and URIs as they are, and IP addresses in their usual text
+    /// form.
+    ///
+    /// Returns no names if the certificate cannot be parsed.
+    pub fn subject_alt_names(&self) -> Vec<String> {
+        let Some(extensions) =
+            tbs_fields(&self.0).and_then(|tbs| tbs.extensions)
+        else {
+            return Vec::new();
+        };
+        let mut names = Vec::new();
+        for (_, extension) in Der(extensions) {
+            let mut extension = Der(extension);
+            if extension.next().map(|(_, oid)| oid)
+                != Some(OID_SUBJECT_ALT_NAME)
+            {
+                continue;
+            }
+            // skip the `critical` flag, if it is present
+            let Some((_, value)) =
+                extension.find(|(tag, _)| *tag == OCTET_STRING)
+            else {
+                continue;
+            };
+            let Some((_, general_names)) = Der(value).next() else {
+                continue;
+            };
+            for (tag, name) in Der(general_names) {
+                let name = match tag {
+                    // rfc822Name, dNSName and uniformResourceIdentifier
+                    0x81 | 0x82 | 0x86 => String::from_utf8(name.to_vec()).ok(),
+                    // iPAddress
+                    0x87 => match name.len() {
+                        4 => <[u8; 4]>::try_from(name)
+                            .ok()
+                            .map(|ip| IpAddr::from(Ipv4Addr::from(ip))),
+                        16 => <[u8; 16]>::try_from(name)
+                            .ok()
+                            .map(|ip| IpAddr::from(Ipv6Addr::from(ip))),
+                        _ => None,
+                    }
+                    .map(|ip| ip.to_string()),
+                    _ => None,
+                };
+                names.extend(name);
+            }
+        }
+        names
+    }
+
+    /// The SHA-256 fingerprint of the certificate, which is the hash of its DER
+    /// encoding.
+    #[cfg(feature = "ssr")]
+    pub fn fingerprint_sha256(&self) -> [u8; 32] {
+        use sha2::{Digest, Sha256};
+
+        Sha256::digest(&self.0).into()
+    }
+}
+
+const SEQUENCE: u8 = 0x30;
+const OCTET_STRING: u8 = 0x04;
+// 2.5.4.3
+const OID_COMMON_NAME: &[u8] = &[0x55, 0x04, 0x03];
+// 2.5.29.17
+const OID_SUBJECT_ALT_NAME: &[u8] = &[0x55, 0x1d, 0x11];
+
+/// Iterates over the `(tag, contents)` of a sequence of DER values, stopping at
+/// the first one that is malformed.
+struct Der<'a>(&'a [u8]);
+
+impl<'a> Iterator for Der<'a> {
+    type Item = (u8, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&tag, rest) = self.0.split_first()?;
+        let (&first, rest) = rest.split_first()?;
+        let (len, rest) = if first < 0x80 {
+            (first as usize, rest)
+        } else {
+            let count = (first & 0x7f) as usize;
+            if count == 0 || count > 4 || rest.len() < count {
+                return None;
+            }
+            let (len, rest) = rest.split_at(count);
+            let len = len
+                .iter()
+                .fold(0usize, |len, &byte| (len << 8) | byte as usize);
+            (len, rest)
+        };
+        if rest.len() < len {
+            self.0 = &[];
+            return None;
+        }
+        let (contents, rest) = rest.split_at(len);
+        self.0 = rest;
+        Some((tag, contents))
+    }
+}
+
+struct TbsFields<'a> {
+    subject: &'a [u8],
+    extensions: Option<&'a [u8]>,
+}
+
+fn tbs_fields(der: &[u8]) -> Option<TbsFields<'_>> {
+    let (SEQUENCE, certificate) = Der(der).next()? else {
+        return None;
+    };
+    let (SEQUENCE, tbs) = Der(certificate).next()? else {
+        return None;
+    };
+    let mut fields = Der(tbs).peekable();
+    // the version is optional, and tagged [0]
+    fields.next_if(|(tag, _)| *tag == 0xa0);
+    // serial number, signature algorithm, issuer and validity
+    for _ in 0..4 {
+        fields.next()?;
+    }
+    let (SEQUENCE, subject) = fields.next()? else {
+        return None;
+    };
+    // the extensions are tagged [3], after the public key and the optional
+    // unique IDs
+    let extensions = fields
+        .find(|(tag, _)| *tag == 0xa3)
+        .and_then(|(_, extensions)| Der(extensions).next())
+        .map(|(_, extensions)| extensions);
+    Some(TbsFields {
+        subject,
+        extensions,
+    })
+}
+
+/// Checks whether a client certificate is allowed to access a route.
+pub trait ClientCertValidator: Send + Sync {
+    /// Resolves to `true` if the certificate is valid.
+    fn validate(&self, cert: &ClientCert) -> BoxFuture<'static, bool>;
+}
+
+/// Defines how the client certificates of requests to a route are validated.
+///
+/// The server integrations find the [`ClientCert`] in the request extensions
+/// and pass it to `validator`. Requests without a certificate are rejected with
+/// `401 Unauthorized`, and those with a certificate the validator rejects with
+/// `403 Forbidden`.
+#[derive(Clone)]
+pub struct ClientCertConfig {
+    /// Checks whether the certificate is valid.
+    pub validator: Arc<dyn ClientCertValidator>,
+}
+
+impl fmt::Debug for ClientCertConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ClientCertConfig(..)")
+    }
+}
+
+impl PartialEq for ClientCertConfig {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.validator, &other.validator)
+    }
+}
+
+impl Eq for ClientCertConfig {}
+
+/// Accepts the client certificates whose SHA-256 fingerprints are in a fixed
+/// set.
+///
+/// ```rust
+/// use leptos_router::PinnedCertValidator;
+///
+/// let validator = PinnedCertValidator::from_hex([
+///     "3f:8a:1c:5e:90:d2:47:b6:0e:c4:29:7a:f1:63:88:de:52:0b:9c:e7:14:a6:3d:f0:c8:71:2e:95:4b:d9:06:aa",
+/// ])
+/// .unwrap();
+/// ```
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedCertValidator {
+    fingerprints: std::collections::HashSet<[u8; 32]>,
+}
+
+#[cfg(feature = "ssr")]
+impl PinnedCertValidator {
+    /// Accepts the certificates with the given SHA-256 fingerprints.
+    pub fn new(fingerprints: impl IntoIterator<Item = [u8; 32]>) -> Self {
+        Self {
+            fingerprints: fingerprints.into_iter().collect(),
+        }
+    }
+
+    /// Accepts the certificates with the given hex-encoded SHA-256
+    /// fingerprints, in either case, with or without colons between the bytes
+    /// (as printed by `openssl x509 -fingerprint -sha256`).
+    pub fn from_hex<S: AsRef<str>>(
+        fingerprints: impl IntoIterator<Item = S>,
+    ) -> Result<Self, InvalidFingerprint> {
+        fingerprints
+            .into_iter()
+            .map(|fingerprint| {
+                let fingerprint = fingerprint.as_ref();
+                let hex = fingerprint.replace(':', "");
+                crate::webhook::decode_hex(&hex)
+                    .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                    .ok_or_else(|| InvalidFingerprint(fingerprint.to_string()))
+            })
+            .collect::<Result<_, _>>()
+            .map(|fingerprints| Self { fingerprints })
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl ClientCertValidator for PinnedCertValidator {
+    fn validate(&self, cert: &ClientCert) -> BoxFuture<'static, bool> {
+        let valid = self.fingerprints.contains(&cert.fingerprint_sha256());
+        Box::pin(async move { valid })
+    }
+}
+
+/// A fingerprint passed to [`PinnedCertValidator::from_hex`] that is not a
+/// hex-encoded SHA-256 hash.
+#[cfg(feature = "ssr")]
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("`{0}` is not a hex-encoded SHA-256 fingerprint")]
+pub struct InvalidFingerprint(pub String);
+
+#[cfg(test)]
+mod tests {
+    use super::ClientCert;
+
+    fn tlv(tag: u8, contents: &[u8]) -> Vec<u8> {
+        let mut der = vec![tag];
+        if contents.len() < 0x80 {
+            der.push(contents.len() as u8);
+        } else {
+            der.push(0x82);
+            der.extend((contents.len() as u16).to_be_bytes());
+        }
+        der.extend(contents);
+        der
+    }
+
+    fn seq(values: &[Vec<u8>]) -> Vec<u8> {
+        tlv(0x30, &values.concat())
+    }
+
+    /// A certificate with the fields the parser looks at, and placeholders for
+    /// the rest.
+    fn cert(common_name: &str, alt_names: &[Vec<u8>]) -> ClientCert {
+        let name = |cn: &str| {
+            seq(&[
+                tlv(
+                    0x31,
+                    &seq(&[
+                        // organization name
+                        tlv(0x06, &[0x55, 0x04, 0x0a]),
+                        tlv(0x0c, b"Example"),
+                    ]),
+                ),
+                tlv(
+                    0x31,
+                    &seq(&[
+                        tlv(0x06, &[0x55, 0x04, 0x03]),
+                        tlv(0x0c, cn.as_bytes()),
+                    ]),
+                ),
+            ])
+        };
+        let alt_names =
+            seq(&[tlv(0x06, &[0x55, 0x1d, 0x11]), tlv(0x04, &seq(alt_names))]);
+        let key_usage = seq(&[
+            tlv(0x06, &[0x55, 0x1d, 0x0f]),
+            tlv(0x01, &[0xff]),
+            tlv(0x04, &tlv(0x03, &[0x05, 0xa0])),
+        ]);
+        let tbs = seq(&[
+            tlv(0xa0, &tlv(0x02, &[0x02])),
+            tlv(0x02, &[0x01, 0x23]),
+            seq(&[tlv(0x06, &[0x2a, 0x86, 0x48])]),
+            name("Example CA"),
+            seq(&[tlv(0x17, b"250101000000Z"), tlv(0x17, b"350101000000Z")]),
+            name(common_name),
+            seq(&[
+                seq(&[tlv(0x06, &[0x2a, 0x86, 0x48])]),
+                tlv(0x03, &[0; 200]),
+            ]),
+            tlv(0xa3, &seq(&[key_usage, alt_names])),
+        ]);
+        ClientCert::from_der(seq(&[
+            tbs,
+            seq(&[tlv(0x06, &[0x2a, 0x86, 0x48])]),
+            tlv(0x03, &[0; 64]),
+        ]))
+    }
+
+    #[test]
+    fn reads_names_from_certificate() {
+        let cert = cert(
+            "client.example.com",
+            &[
+                tlv(0x82, b"client.example.com"),
+                tlv(0x81, b"ops@example.com"),
+                tlv(0x87, &[10, 0, 0, 1]),
+                // otherName, which is skipped
+                tlv(0xa0, &[0x06, 0x01, 0x00]),
+                tlv(0x86, b"spiffe://example.com/client"),
+            ],
+        );
+        assert_eq!(cert.common_name().as_deref(), Some("client.example.com"));
+        assert_eq!(
+            cert.subject_alt_names(),
+            vec![
+                "client.example.com",
+                "ops@example.com",
+                "10.0.0.1",
+                "spiffe://example.com/client"
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_certificates_have_no_names() {
+        let cert = ClientCert::from_der(vec![0x30, 0x05, 0x30]);
+        assert_eq!(cert.common_name(), None);
+        assert!(cert.subject_alt_names().is_empty());
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn pinned_validator_checks_fingerprint() {
+        use super::{
+            ClientCertValidator, InvalidFingerprint, PinnedCertValidator,
+        };
+        use futures::executor::block_on;
+
+        let trusted = cert("trusted", &[]);
+        let other = cert("other", &[]);
+        let fingerprint = trusted
+            .fingerprint_sha256()
+            .iter()
+            .map(|byte| format!("{byte:02X}"))
+            .collect::<Vec<_>>()
+            .join(":");
+        let validator = PinnedCertValidator::from_hex([&fingerprint]).unwrap();
+        assert!(block_on(validator.validate(&trusted)));
+        assert!(!block_on(validator.validate(&other)));
+
+        assert_eq!(
+            PinnedCertValidator::from_hex(["abcd"]),
+            Err(InvalidFingerprint("abcd".into()))
+        );
+    }
+}
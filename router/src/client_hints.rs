@@ -0,0 +1,143 @@
+use std::str::FromStr;
+
+/// A
+/// [client hint](https://developer.mozilla.org/en-US/docs/Web/HTTP/Client_hints)
+/// that a route asks browsers to send, with
+/// [`request_client_hints`](crate::NestedRoute::request_client_hints).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClientHint {
+    /// The ratio of physical to CSS pixels of the device, in `Sec-CH-DPR`.
+    Dpr,
+    /// The width of the viewport in CSS pixels, in `Sec-CH-Viewport-Width`.
+    ViewportWidth,
+    /// The width an image will be shown at in physical pixels, in
+    /// `Sec-CH-Width`.
+    Width,
+    /// About how much memory the device has in gigabytes, in
+    /// `Sec-CH-Device-Memory`.
+    DeviceMemory,
+    /// How fast the network connection is, in `ECT`, `RTT` and `Downlink`.
+    NetworkInfo,
+    /// Whether the user asked to use less data, in `Save-Data`.
+    SaveData,
+}
+
+impl ClientHint {
+    /// The names of the request headers in which browsers send this hint.
+    pub fn headers(&self) -> &'static [&'static str] {
+        match self {
+            ClientHint::Dpr => &["Sec-CH-DPR"],
+            ClientHint::ViewportWidth => &["Sec-CH-Viewport-Width"],
+            ClientHint::Width => &["Sec-CH-Width"],
+            ClientHint::DeviceMemory => &["Sec-CH-Device-Memory"],
+            ClientHint::NetworkInfo => &["ECT", "RTT", "Downlink"],
+            ClientHint::SaveData => &["Save-Data"],
+        }
+    }
+}
+
+/// The value of the `Accept-CH` and `Critical-CH` headers that request the
+/// given hints, or `None` if there are none.
+pub fn accept_ch_header(hints: &[ClientHint]) -> Option<String> {
+    if hints.is_empty() {
+        return None;
+    }
+    Some(
+        hints
+            .iter()
+            .flat_map(ClientHint::headers)
+            .copied()
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/// The client hints a browser sent with a request to a route that requested
+/// them.
+///
+/// The server integrations provide this as context while rendering the route,
+/// so that a view can pick the right size of an image, or leave out heavy
+/// content on slow connections. A hint is `None` if the browser did not send
+/// it, like on its first request, before it has seen the `Accept-CH` header.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClientHints {
+    /// The ratio of physical to CSS pixels of the device.
+    pub dpr: Option<f64>,
+    /// The width of the viewport in CSS pixels.
+    pub viewport_width: Option<u32>,
+    /// The width an image will be shown at in physical pixels.
+    pub width: Option<u32>,
+    /// About how much memory the device has in gigabytes.
+    pub device_memory: Option<f64>,
+    /// The effective type of the network connection, like `4g` or `slow-2g`.
+    pub effective_connection_type: Option<String>,
+    /// The round-trip time of the network connection in milliseconds.
+    pub rtt: Option<u32>,
+    /// The bandwidth of the network connection in megabits per second.
+    pub downlink: Option<f64>,
+    /// Whether the user asked to use less data.
+    pub save_data: bool,
+}
+
+impl ClientHints {
+    /// Reads the hints from the headers of a request, given a way to look up a
+    /// header by its (case-insensitive) name. Values that cannot be parsed are
+    /// ignored.
+    pub fn from_headers<'a>(header: impl Fn(&str) -> Option<&'a str>) -> Self {
+        Self {
+            dpr: parse(header("sec-ch-dpr")),
+            viewport_width: parse(header("sec-ch-viewport-width")),
+            width: parse(header("sec-ch-width")),
+            device_memory: parse(header("sec-ch-device-memory")),
+            effective_connection_type: header("ect")
+                .map(|ect| ect.trim().to_string()),
+            rtt: parse(header("rtt")),
+            downlink: parse(header("downlink")),
+            save_data: header("save-data")
+                .is_some_and(|save| save.trim().eq_ignore_ascii_case("on")),
+        }
+    }
+}
+
+fn parse<T: FromStr>(value: Option<&str>) -> Option<T> {
+    value?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{accept_ch_header, ClientHint, ClientHints};
+
+    #[test]
+    fn hints_are_read_from_the_headers_they_were_requested_in() {
+        assert_eq!(accept_ch_header(&[]), None);
+        assert_eq!(
+            accept_ch_header(&[ClientHint::Dpr, ClientHint::NetworkInfo])
+                .as_deref(),
+            Some("Sec-CH-DPR, ECT, RTT, Downlink")
+        );
+
+        let headers = [
+            ("sec-ch-dpr", "2"),
+            ("sec-ch-viewport-width", " 390"),
+            ("sec-ch-width", "wide"),
+            ("ect", "3g"),
+            ("save-data", "on"),
+        ];
+        let hints = ClientHints::from_headers(|name| {
+            headers
+                .iter()
+                .find(|(header, _)| *header == name)
+                .map(|(_, value)| *value)
+        });
+        assert_eq!(
+            hints,
+            ClientHints {
+                dpr: Some(2.0),
+                viewport_width: Some(390),
+                effective_connection_type: Some("3g".to_string()),
+                save_data: true,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -8,11 +8,11 @@ use crate::{
     location::{
         BrowserUrl, Location, LocationChange, LocationProvider, State, Url,
     },
-    navigate::NavigateOptions,
+    navigate::{FocusBehavior, NavigateOptions, NavigationEnd},
     nested_router::NestedRoutesView,
     resolve_path::resolve_path,
-    ChooseView, MatchNestedRoutes, NestedRoute, PossibleRouteMatch, RouteDefs,
-    SsrMode,
+    ChooseView, DocumentBase, MatchNestedRoutes, NestedRoute,
+    PossibleRouteMatch, RouteDefs, SsrMode, SsrModePrecedence,
 };
 use either_of::EitherOf3;
 use leptos::{children, prelude::*};
@@ -63,6 +63,23 @@ pub fn Router<Chil>(
     ///// How trailing slashes should be handled in [`Route`] paths.
     //#[prop(optional)]
     //trailing_slash: TrailingSlash,
+    /// Where to move focus after each client-side navigation. Defaults to
+    /// [`FocusBehavior::Auto`]; this can be overridden for a single navigation
+    /// with [`NavigateOptions::focus`].
+    #[prop(optional)]
+    focus_behavior: FocusBehavior,
+    /// Called at the end of each client-side navigation, once the new route has
+    /// been rendered and the title and meta tags of the document have been
+    /// updated for it.
+    #[prop(optional, into)]
+    on_navigation_end: Option<Callback<(NavigationEnd,)>>,
+    /// Whether relative URLs in links, forms, redirects and server function
+    /// calls are left for the browser to resolve against the `<base href>` of
+    /// the document, or are made absolute from the router's `base`, which is
+    /// the default. This also sets it for server functions, with
+    /// [`set_document_base`](server_fn::client::set_document_base).
+    #[prop(optional)]
+    document_base: DocumentBase,
     /// The `<Router/>` should usually wrap your whole page. It can contain
     /// any elements, and should include a [`Routes`] component somewhere
     /// to define and display [`Route`]s.
@@ -76,6 +93,7 @@ where
         let req = use_context::<RequestUrl>().expect("no RequestUrl provided");
         let parsed = req.parse().expect("could not parse RequestUrl");
         let current_url = ArcRwSignal::new(parsed);
+        _ = focus_behavior;
 
         (None, current_url, Box::new(move |_: &str| {}))
     };
@@ -86,6 +104,9 @@ where
         let location =
             BrowserUrl::new().expect("could not access browser navigation"); // TODO options here
         location.init(base.clone());
+        *location.focus_behavior.write_value() = focus_behavior;
+        provide_context(location.pending_suspense.clone());
+        location.pending_suspense.report_to_window();
         provide_context(location.clone());
         let current_url = location.as_url().clone();
 
@@ -103,6 +124,7 @@ where
 
     // set server function redirect hook
     _ = server_fn::redirect::set_redirect_hook(redirect_hook);
+    server_fn::client::set_document_base(document_base);
 
     provide_context(RouterContext {
         base,
@@ -110,6 +132,8 @@ where
         location,
         state,
         set_is_routing,
+        on_navigation_end,
+        document_base,
         query_mutations: Default::default(),
         location_provider,
     });
@@ -125,6 +149,8 @@ pub(crate) struct RouterContext {
     pub location: Location,
     pub state: ArcRwSignal<State>,
     pub set_is_routing: Option<SignalSetter<bool>>,
+    pub on_navigation_end: Option<Callback<(NavigationEnd,)>>,
+    pub document_base: DocumentBase,
     pub query_mutations:
         ArcStoredValue<Vec<(Oco<'static, str>, Option<String>)>>,
     pub location_provider: Option<BrowserUrl>,
@@ -191,18 +217,10 @@ impl RouterContext {
                 replace: options.replace,
                 scroll: options.scroll,
                 state: options.state,
+                focus: options.focus,
             });
         }
     }
-
-    pub fn resolve_path<'a>(
-        &'a self,
-        path: &'a str,
-        from: Option<&'a str>,
-    ) -> Cow<'a, str> {
-        let base = self.base.as_deref().unwrap_or_default();
-        resolve_path(base, path, from)
-    }
 }
 
 impl Debug for RouterContext {
@@ -222,6 +240,20 @@ pub fn Routes<Defs, FallbackFn, Fallback>(
     /// Whether to use the View Transition API during navigation.
     #[prop(optional)]
     transition: bool,
+    /// How the [`SsrMode`] of each route is combined with that of its parent,
+    /// as with [`RouteDefs::ssr_mode_precedence`].
+    #[prop(optional)]
+    ssr_mode_precedence: Option<SsrModePrecedence>,
+    /// Whether to build a table of the routes whose segments are all static
+    /// when the routes are created, as with [`RouteDefs::compile`], so that
+    /// matching one of their paths is a single lookup.
+    ///
+    /// This is `false` by default, because building the table walks every
+    /// route. It is worth it for large sets of routes that are matched on many
+    /// navigations in the browser, but the routes are created again for each
+    /// request on the server.
+    #[prop(optional)]
+    compile: bool,
     /// The route definitions. This should consist of one or more [`ParentRoute`] or [`Route`]
     /// components.
     children: RouteChildren<Defs>,
@@ -236,6 +268,7 @@ where
         current_url,
         base,
         set_is_routing,
+        on_navigation_end,
         ..
     } = use_context()
         .expect("<Routes> should be used inside a <Router> component");
@@ -244,10 +277,16 @@ where
         base.upgrade_inplace();
         base
     });
-    let routes = RouteDefs::new_with_base(
+    let mut routes = RouteDefs::new_with_base(
         children.into_inner(),
         base.clone().unwrap_or_default(),
     );
+    if let Some(precedence) = ssr_mode_precedence {
+        routes = routes.ssr_mode_precedence(precedence);
+    }
+    if compile {
+        routes = routes.compile();
+    }
     let outer_owner =
         Owner::current().expect("creating Routes, but no Owner was found");
     move || {
@@ -263,6 +302,7 @@ where
             base: base.clone(),
             fallback: fallback.clone(),
             set_is_routing,
+            on_navigation_end,
             transition,
         }
     }
@@ -275,6 +315,20 @@ pub fn FlatRoutes<Defs, FallbackFn, Fallback>(
     /// Whether to use the View Transition API during navigation.
     #[prop(optional)]
     transition: bool,
+    /// How the [`SsrMode`] of each route is combined with that of its parent,
+    /// as with [`RouteDefs::ssr_mode_precedence`].
+    #[prop(optional)]
+    ssr_mode_precedence: Option<SsrModePrecedence>,
+    /// Whether to build a table of the routes whose segments are all static
+    /// when the routes are created, as with [`RouteDefs::compile`], so that
+    /// matching one of their paths is a single lookup.
+    ///
+    /// This is `false` by default, because building the table walks every
+    /// route. It is worth it for large sets of routes that are matched on many
+    /// navigations in the browser, but the routes are created again for each
+    /// request on the server.
+    #[prop(optional)]
+    compile: bool,
     /// The route definitions. This should consist of one or more [`ParentRoute`] or [`Route`]
     /// components.
     children: RouteChildren<Defs>,
@@ -289,6 +343,7 @@ where
         current_url,
         base,
         set_is_routing,
+        on_navigation_end,
         ..
     } = use_context()
         .expect("<FlatRoutes> should be used inside a <Router> component");
@@ -300,10 +355,16 @@ where
         base.upgrade_inplace();
         base
     });
-    let routes = RouteDefs::new_with_base(
+    let mut routes = RouteDefs::new_with_base(
         children.into_inner(),
         base.clone().unwrap_or_default(),
     );
+    if let Some(precedence) = ssr_mode_precedence {
+        routes = routes.ssr_mode_precedence(precedence);
+    }
+    if compile {
+        routes = routes.compile();
+    }
 
     let outer_owner =
         Owner::current().expect("creating Router, but no Owner was found");
@@ -320,6 +381,7 @@ where
             fallback: fallback.clone(),
             outer_owner: outer_owner.clone(),
             set_is_routing,
+            on_navigation_end,
             transition,
         }
     }
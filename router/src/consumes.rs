@@ -0,0 +1,70 @@
+use std::borrow::Cow;
+
+/// The content types that the bodies of requests to a route must have, set with
+/// [`NestedRoute::consumes`](crate::NestedRoute::consumes).
+///
+/// ```rust
+/// use leptos_router::ContentTypes;
+///
+/// let json = ContentTypes::new(["application/json"]);
+/// assert!(json.accepts(Some("application/json; charset=utf-8")));
+/// assert!(!json.accepts(Some("text/plain")));
+/// assert!(!json.accepts(None));
+///
+/// // the restriction only applies to methods that have a body
+/// assert!(json.applies_to("POST"));
+/// assert!(!json.applies_to("GET"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentTypes(Vec<Cow<'static, str>>);
+
+impl ContentTypes {
+    /// Accepts bodies with any of `types`, like `application/json`, or
+    /// `image/*` for every image type.
+    pub fn new(
+        types: impl IntoIterator<Item = impl Into<Cow<'static, str>>>,
+    ) -> Self {
+        Self(types.into_iter().map(Into::into).collect())
+    }
+
+    /// Also accepts bodies with the content type `mime`.
+    pub(crate) fn push(&mut self, mime: impl Into<Cow<'static, str>>) {
+        self.0.push(mime.into());
+    }
+
+    /// Whether the bodies of requests with `method` are checked: requests with
+    /// methods that do not have a body, like `GET`, `HEAD` and `OPTIONS`, are
+    /// not.
+    pub fn applies_to(&self, method: &str) -> bool {
+        !["GET", "HEAD", "OPTIONS"]
+            .iter()
+            .any(|exempt| method.eq_ignore_ascii_case(exempt))
+    }
+
+    /// Whether a body with the `content_type` header, if there is one, is
+    /// accepted. The parameters of the content type, like its `charset`, are
+    /// ignored, and a body without a content type is not accepted.
+    pub fn accepts(&self, content_type: Option<&str>) -> bool {
+        let Some(mime) = content_type
+            .map(|content_type| {
+                content_type.split(';').next().unwrap_or_default().trim()
+            })
+            .filter(|mime| !mime.is_empty())
+        else {
+            return false;
+        };
+        self.0
+            .iter()
+            .any(|accepted| match accepted.strip_suffix("/*") {
+                Some(kind) => mime
+                    .split_once('/')
+                    .is_some_and(|(other, _)| other.eq_ignore_ascii_case(kind)),
+                None => accepted.eq_ignore_ascii_case(mime),
+            })
+    }
+
+    /// The accepted content types, in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(AsRef::as_ref)
+    }
+}
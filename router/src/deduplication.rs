@@ -0,0 +1,648 @@
+use crate::RouteMatchId;
+use futures::{Stream, StreamExt};
+use or_poisoned::OrPoisoned;
+use std::{
+    collections::HashMap,
+    fmt,
+    pin::pin,
+    sync::{Arc, Mutex, OnceLock},
+};
+use tokio::sync::Notify;
+
+/// The largest response body, 4 MiB, that the server integrations buffer whole
+/// to rewrite or check it, like for a
+/// [response envelope](crate::NestedRoute::response_envelope). A larger body is
+/// sent as it is read, unchanged.
+pub const BUFFERED_BODY_LIMIT: usize = 4 * 1024 * 1024;
+
+/// The largest response body that is buffered to be shared by default, the
+/// [`BUFFERED_BODY_LIMIT`].
+pub const DEFAULT_DEDUPLICATION_BODY_LIMIT: usize = BUFFERED_BODY_LIMIT;
+
+/// Identifies identical requests: the route they matched, their normalized URL,
+/// and the credentials they sent.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeduplicationKey {
+    route: Option<RouteMatchId>,
+    url: String,
+    credentials: Option<(Option<String>, Option<String>)>,
+}
+
+impl DeduplicationKey {
+    /// The key of a request to `path`, with the query string `query` (without
+    /// the leading `?`), that matched `route`.
+    ///
+    /// The URL is normalized so that requests that only differ in a trailing
+    /// slash, in the order of their query params, or in empty query params,
+    /// have the same key.
+    pub fn new(
+        route: Option<RouteMatchId>,
+        path: &str,
+        query: Option<&str>,
+    ) -> Self {
+        let mut url = match path.trim_end_matches('/') {
+            "" => "/".to_string(),
+            path => path.to_string(),
+        };
+        let mut params = query
+            .unwrap_or_default()
+            .split('&')
+            .filter(|pair| !pair.is_empty() && !pair.ends_with('='))
+            .collect::<Vec<_>>();
+        params.sort_unstable();
+        if !params.is_empty() {
+            url.push('?');
+            url.push_str(&params.join("&"));
+        }
+        Self {
+            route,
+            url,
+            credentials: None,
+        }
+    }
+
+    /// The same key, for a request that sent these `Cookie` and `Authorization`
+    /// headers.
+    ///
+    /// A request with credentials may be answered with data that only its user
+    /// can see, so it is only identical to requests that sent the same
+    /// credentials.
+    pub fn with_credentials(
+        mut self,
+        cookie: Option<&str>,
+        authorization: Option<&str>,
+    ) -> Self {
+        self.credentials =
+            (cookie.is_some() || authorization.is_some()).then(|| {
+                (cookie.map(str::to_owned), authorization.map(str::to_owned))
+            });
+        self
+    }
+
+    /// The route that the requests matched.
+    pub fn route(&self) -> Option<RouteMatchId> {
+        self.route
+    }
+
+    /// The normalized URL of the requests.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+/// A response that is shared with every identical request that arrived while it
+/// was being produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeduplicatedResponse {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The headers, in order.
+    pub headers: Vec<(String, Vec<u8>)>,
+    /// The whole body.
+    pub body: Vec<u8>,
+    /// The request headers named by the `Vary` header of the response, in
+    /// lowercase, with the values the request that produced it sent for them.
+    pub vary: Vec<(String, Option<Vec<u8>>)>,
+}
+
+impl DeduplicatedResponse {
+    /// The response to share, with the values that `request_header` returns for
+    /// the request headers named by its `Vary` header, or `None` if it cannot
+    /// be shared because it has `Vary: *`.
+    pub fn new(
+        status: u16,
+        headers: Vec<(String, Vec<u8>)>,
+        body: Vec<u8>,
+        request_header: impl Fn(&str) -> Option<Vec<u8>>,
+    ) -> Option<Self> {
+        let mut vary = Vec::new();
+        let varied = headers
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case("vary"))
+            .flat_map(|(_, value)| {
+                String::from_utf8_lossy(value)
+                    .split(',')
+                    .map(|name| name.trim().to_ascii_lowercase())
+                    .collect::<Vec<_>>()
+            });
+        for name in varied {
+            if name == "*" {
+                return None;
+            }
+            if !name.is_empty()
+                && !vary.iter().any(|(varied, _)| *varied == name)
+            {
+                let value = request_header(&name);
+                vary.push((name, value));
+            }
+        }
+        Some(Self {
+            status,
+            headers,
+            body,
+            vary,
+        })
+    }
+
+    /// Whether this response can be shared with a request whose headers
+    /// `request_header` returns: it must have sent the same values as the
+    /// request that produced it for every header named by its `Vary` header.
+    pub fn is_shareable_with(
+        &self,
+        request_header: impl Fn(&str) -> Option<Vec<u8>>,
+    ) -> bool {
+        self.vary
+            .iter()
+            .all(|(name, value)| request_header(name) == *value)
+    }
+}
+
+/// Reads the chunks of a response `body` until it ends, or until it is larger
+/// than `limit` bytes.
+pub async fn buffer_body<B, C, E>(
+    mut body: B,
+    limit: usize,
+) -> Result<BufferedBody<B, C>, E>
+where
+    B: Stream<Item = Result<C, E>> + Unpin,
+    C: AsRef<[u8]>,
+{
+    let mut chunks = Vec::new();
+    let mut len = 0;
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk?;
+        len += chunk.as_ref().len();
+        chunks.push(chunk);
+        if len > limit {
+            return Ok(BufferedBody::TooLarge(chunks, body));
+        }
+    }
+    Ok(BufferedBody::Whole(
+        chunks
+            .iter()
+            .flat_map(|chunk| chunk.as_ref())
+            .copied()
+            .collect(),
+    ))
+}
+
+/// A response body read by [`buffer_body`].
+#[derive(Debug)]
+pub enum BufferedBody<B, C> {
+    /// The whole body, which was no larger than the limit.
+    Whole(Vec<u8>),
+    /// The body was larger than the limit, so it was not read in full: these
+    /// are the chunks that were read, and the rest of it.
+    TooLarge(Vec<C>, B),
+}
+
+/// A request that is being handled, which identical requests wait on.
+#[derive(Debug, Default)]
+pub struct InFlightRequest {
+    notify: Arc<Notify>,
+    response: OnceLock<Option<DeduplicatedResponse>>,
+}
+
+impl InFlightRequest {
+    /// Creates a request that has not finished yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Notified when the request finishes.
+    pub fn notify(&self) -> Arc<Notify> {
+        Arc::clone(&self.notify)
+    }
+
+    /// Waits for the request to finish, returning its response, or `None` if it
+    /// did not have a response that could be shared.
+    pub async fn wait(&self) -> Option<DeduplicatedResponse> {
+        let mut notified = pin!(self.notify.notified());
+        // registers for the notification before checking, so it cannot be
+        // missed
+        notified.as_mut().enable();
+        if let Some(response) = self.response.get() {
+            return response.clone();
+        }
+        notified.await;
+        self.response.get().cloned().flatten()
+    }
+
+    /// Stores the response of the request, and wakes every request that is
+    /// waiting on it.
+    ///
+    /// Its `Set-Cookie` headers are removed, because they are meant for the
+    /// request that produced it, not for the requests it is shared with.
+    pub fn finish(&self, response: Option<DeduplicatedResponse>) {
+        let response = response.map(|mut response| {
+            response
+                .headers
+                .retain(|(name, _)| !name.eq_ignore_ascii_case("set-cookie"));
+            response
+        });
+        _ = self.response.set(response);
+        self.notify.notify_waiters();
+    }
+}
+
+/// Whether an identical request was already in flight, in a
+/// [`DeduplicationStore`].
+#[derive(Debug, Clone)]
+pub enum InFlight {
+    /// There was none, so this request is now in flight, and must be handled.
+    First(Arc<InFlightRequest>),
+    /// There was one, which this request should wait on.
+    Waiting(Arc<InFlightRequest>),
+}
+
+/// Tracks the requests that are in flight, so that identical ones can share a
+/// response.
+pub trait DeduplicationStore: Send + Sync {
+    /// Marks a request with `key` as in flight, unless there already is one.
+    fn start(&self, key: &DeduplicationKey) -> InFlight;
+
+    /// Forgets the request with `key` when it finishes, so that the next
+    /// request with the same key is handled again.
+    fn remove(&self, key: &DeduplicationKey);
+}
+
+/// A [`DeduplicationStore`] that tracks the requests in flight on this server.
+#[derive(Debug, Default)]
+pub struct InMemoryDeduplicationStore(
+    Mutex<HashMap<DeduplicationKey, Arc<InFlightRequest>>>,
+);
+
+impl InMemoryDeduplicationStore {
+    /// Creates a store with no requests in flight.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl DeduplicationStore for InMemoryDeduplicationStore {
+    fn start(&self, key: &DeduplicationKey) -> InFlight {
+        let mut requests = self.0.lock().or_poisoned();
+        match requests.get(key) {
+            Some(request) => InFlight::Waiting(Arc::clone(request)),
+            None => {
+                let request = Arc::new(InFlightRequest::new());
+                requests.insert(key.clone(), Arc::clone(&request));
+                InFlight::First(request)
+            }
+        }
+    }
+
+    fn remove(&self, key: &DeduplicationKey) {
+        self.0.lock().or_poisoned().remove(key);
+    }
+}
+
+/// Defines how concurrent identical `GET` requests to a route share a single
+/// response.
+///
+/// Used with [`NestedRoute::deduplicate`](crate::NestedRoute::deduplicate).
+#[derive(Clone)]
+pub struct DeduplicationConfig {
+    /// Tracks the requests that are in flight.
+    pub store: Arc<dyn DeduplicationStore>,
+    route: Option<RouteMatchId>,
+    body_limit: usize,
+}
+
+impl DeduplicationConfig {
+    /// Deduplicates requests with `store`.
+    pub fn new(store: Arc<dyn DeduplicationStore>) -> Self {
+        Self {
+            store,
+            route: None,
+            body_limit: DEFAULT_DEDUPLICATION_BODY_LIMIT,
+        }
+    }
+
+    /// Sets the size of the largest response body, in bytes, that is buffered
+    /// to be shared, which is [`DEFAULT_DEDUPLICATION_BODY_LIMIT`] by default.
+    /// A larger response is sent to its own request as it is read, and the
+    /// requests waiting on it are handled on their own.
+    pub fn body_limit(mut self, bytes: usize) -> Self {
+        self.body_limit = bytes;
+        self
+    }
+
+    /// The same config, for the requests that match `route`.
+    pub(crate) fn for_route(self, route: Option<RouteMatchId>) -> Self {
+        Self { route, ..self }
+    }
+
+    /// The key of a request to `path` with the query string `query`, and the
+    /// `Cookie` and `Authorization` headers `cookie` and `authorization`.
+    pub fn key(
+        &self,
+        path: &str,
+        query: Option<&str>,
+        cookie: Option<&str>,
+        authorization: Option<&str>,
+    ) -> DeduplicationKey {
+        DeduplicationKey::new(self.route, path, query)
+            .with_credentials(cookie, authorization)
+    }
+
+    /// Reads the chunks of a response `body` until it ends, or until it is
+    /// larger than the [body limit](Self::body_limit).
+    pub async fn buffer_body<B, C, E>(
+        &self,
+        body: B,
+    ) -> Result<BufferedBody<B, C>, E>
+    where
+        B: Stream<Item = Result<C, E>> + Unpin,
+        C: AsRef<[u8]>,
+    {
+        buffer_body(body, self.body_limit).await
+    }
+
+    /// Waits for an identical request that is in flight to finish, if there is
+    /// one.
+    ///
+    /// Its response is only shared if this request, whose headers
+    /// `request_header` returns, sent the same values as it did for the headers
+    /// named by its `Vary` header.
+    pub async fn join(
+        &self,
+        key: DeduplicationKey,
+        request_header: impl Fn(&str) -> Option<Vec<u8>>,
+    ) -> Deduplicated {
+        match self.store.start(&key) {
+            InFlight::First(request) => {
+                Deduplicated::First(DeduplicationGuard {
+                    store: Arc::clone(&self.store),
+                    key,
+                    request,
+                    finished: false,
+                })
+            }
+            InFlight::Waiting(request) => match request.wait().await {
+                Some(response)
+                    if response.is_shareable_with(request_header) =>
+                {
+                    Deduplicated::Shared(response)
+                }
+                _ => Deduplicated::Alone,
+            },
+        }
+    }
+}
+
+impl fmt::Debug for DeduplicationConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeduplicationConfig")
+            .field("route", &self.route)
+            .field("body_limit", &self.body_limit)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for DeduplicationConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.route == other.route
+            && self.body_limit == other.body_limit
+            && Arc::ptr_eq(&self.store, &other.store)
+    }
+}
+
+impl Eq for DeduplicationConfig {}
+
+/// How to handle a request to a route that
+/// [deduplicates](crate::NestedRoute::deduplicate) its requests, from
+/// [`DeduplicationConfig::join`].
+#[derive(Debug)]
+pub enum Deduplicated {
+    /// No identical request was in flight, so this one must be handled, and its
+    /// response passed to [`DeduplicationGuard::finish`].
+    First(DeduplicationGuard),
+    /// An identical request finished while this one waited, with this response.
+    Shared(DeduplicatedResponse),
+    /// An identical request finished while this one waited, without a response
+    /// that could be shared, so this one must be handled on its own.
+    Alone,
+}
+
+/// Shares the response of the first of a set of identical requests with the
+/// others.
+///
+/// If it is dropped before it is finished, as when the request is cancelled,
+/// the waiting requests are handled on their own.
+pub struct DeduplicationGuard {
+    store: Arc<dyn DeduplicationStore>,
+    key: DeduplicationKey,
+    request: Arc<InFlightRequest>,
+    finished: bool,
+}
+
+impl fmt::Debug for DeduplicationGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeduplicationGuard")
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DeduplicationGuard {
+    /// Shares `response` with the requests that are waiting on this one, or
+    /// makes them handle themselves if it is `None`.
+    pub fn finish(mut self, response: Option<DeduplicatedResponse>) {
+        self.finish_with(response);
+    }
+
+    fn finish_with(&mut self, response: Option<DeduplicatedResponse>) {
+        self.finished = true;
+        self.store.remove(&self.key);
+        self.request.finish(response);
+    }
+}
+
+impl Drop for DeduplicationGuard {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.finish_with(None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BufferedBody, Deduplicated, DeduplicatedResponse, DeduplicationConfig,
+        DeduplicationKey, InMemoryDeduplicationStore,
+    };
+    use futures::{executor::block_on, future::join, stream};
+    use std::{convert::Infallible, sync::Arc};
+
+    fn response(body: &str) -> DeduplicatedResponse {
+        DeduplicatedResponse {
+            status: 200,
+            headers: vec![("content-type".into(), b"text/plain".to_vec())],
+            body: body.as_bytes().to_vec(),
+            vary: Vec::new(),
+        }
+    }
+
+    fn no_headers(_: &str) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn config() -> DeduplicationConfig {
+        DeduplicationConfig::new(Arc::new(InMemoryDeduplicationStore::new()))
+    }
+
+    #[test]
+    fn keys_are_normalized() {
+        let key = |path, query| DeduplicationKey::new(None, path, query);
+        assert_eq!(
+            key("/users/", Some("b=2&a=1")),
+            key("/users", Some("a=1&b=2&c="))
+        );
+        assert_eq!(key("/", None).url(), "/");
+        assert_ne!(key("/users", Some("a=1")), key("/users", Some("a=2")));
+    }
+
+    #[test]
+    fn waiting_requests_share_the_first_response() {
+        let config = config();
+        let key = config.key("/report", None, None, None);
+
+        let Deduplicated::First(guard) =
+            block_on(config.join(key.clone(), no_headers))
+        else {
+            panic!("the first request is handled");
+        };
+        let (waiting, ()) =
+            block_on(join(config.join(key.clone(), no_headers), async {
+                guard.finish(Some(response("report")));
+            }));
+        assert!(
+            matches!(waiting, Deduplicated::Shared(res) if res == response("report"))
+        );
+
+        // once it has finished, the next request is handled again
+        let Deduplicated::First(guard) =
+            block_on(config.join(key.clone(), no_headers))
+        else {
+            panic!("the request is handled again");
+        };
+        // and if it is cancelled, the requests waiting on it are handled on
+        // their own
+        let (waiting, ()) =
+            block_on(join(config.join(key, no_headers), async { drop(guard) }));
+        assert!(matches!(waiting, Deduplicated::Alone));
+    }
+
+    #[test]
+    fn requests_from_different_users_are_not_identical() {
+        let config = config();
+        let alice = config.key("/account", None, Some("session=alice"), None);
+        let bob = config.key("/account", None, Some("session=bob"), None);
+        let token = config.key("/account", None, None, Some("Bearer bob"));
+        let anonymous = config.key("/account", None, None, None);
+        assert_ne!(alice, bob);
+        assert_ne!(bob, token);
+        assert_ne!(token, anonymous);
+        assert_eq!(
+            alice,
+            config.key("/account/", None, Some("session=alice"), None)
+        );
+
+        // while the request of one user is in flight, another user's request is
+        // handled
+        let Deduplicated::First(_alice) =
+            block_on(config.join(alice, no_headers))
+        else {
+            panic!("the first request is handled");
+        };
+        assert!(matches!(
+            block_on(config.join(bob, no_headers)),
+            Deduplicated::First(_)
+        ));
+    }
+
+    #[test]
+    fn shared_responses_have_no_cookies_and_respect_vary() {
+        let config = config();
+        let key = config.key("/", None, None, None);
+        let request = |language: &'static str| {
+            move |name: &str| {
+                (name == "accept-language")
+                    .then(|| language.as_bytes().to_vec())
+            }
+        };
+        let shared = DeduplicatedResponse::new(
+            200,
+            vec![
+                ("Vary".into(), b"Accept-Language, accept-language".to_vec()),
+                ("Set-Cookie".into(), b"session=first".to_vec()),
+            ],
+            b"bonjour".to_vec(),
+            request("fr"),
+        )
+        .unwrap();
+        assert_eq!(
+            shared.vary,
+            [("accept-language".to_string(), Some(b"fr".to_vec()))]
+        );
+
+        let share = |language| {
+            let Deduplicated::First(guard) =
+                block_on(config.join(key.clone(), no_headers))
+            else {
+                panic!("the first request is handled");
+            };
+            let (waiting, ()) = block_on(join(
+                config.join(key.clone(), request(language)),
+                async { guard.finish(Some(shared.clone())) },
+            ));
+            waiting
+        };
+        let Deduplicated::Shared(res) = share("fr") else {
+            panic!(
+                "the response is shared with a request in the same language"
+            );
+        };
+        assert_eq!(
+            res.headers,
+            [("Vary".into(), b"Accept-Language, accept-language".to_vec())]
+        );
+        assert!(matches!(share("en"), Deduplicated::Alone));
+
+        // a response that varies on everything is never shared
+        assert_eq!(
+            DeduplicatedResponse::new(
+                200,
+                vec![("vary".into(), b"*".to_vec())],
+                Vec::new(),
+                no_headers
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn bodies_larger_than_the_limit_are_not_buffered() {
+        let config = config().body_limit(6);
+        let body =
+            || stream::iter(["<p>", "hello", "</p>"].map(Ok::<_, Infallible>));
+        assert!(matches!(
+            block_on(config.clone().body_limit(16).buffer_body(body())),
+            Ok(BufferedBody::Whole(whole)) if whole == b"<p>hello</p>"
+        ));
+
+        let Ok(BufferedBody::TooLarge(read, rest)) =
+            block_on(config.buffer_body(body()))
+        else {
+            panic!("the body is larger than the limit");
+        };
+        // nothing that was read is lost
+        assert_eq!(read, ["<p>", "hello"]);
+        assert_eq!(
+            block_on(futures::StreamExt::collect::<Vec<_>>(rest)),
+            [Ok("</p>")]
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use crate::matching::resolve_path::{has_scheme, resolve_path};
+pub use leptos::server_fn::client::DocumentBase;
+use std::borrow::Cow;
+
+/// Whether `path` is resolved against something else, rather than being a full
+/// URL or a path from the root of the origin.
+fn is_relative(path: &str) -> bool {
+    !path.starts_with('/') && !has_scheme(path)
+}
+
+/// The `href` of a link, or the `action` of a form, to `path` from the route
+/// that matched `from`.
+///
+/// A relative path is resolved from the route under `router_base`, unless the
+/// document base is respected, in which case it is left for the browser to
+/// resolve against the `<base href>`. A path from the root of the origin is
+/// left as it is.
+pub(crate) fn resolve_href<'a>(
+    document_base: DocumentBase,
+    router_base: &'a str,
+    path: &'a str,
+    from: Option<&'a str>,
+) -> Cow<'a, str> {
+    if path.starts_with('/')
+        || (document_base == DocumentBase::Respect && is_relative(path))
+    {
+        path.into()
+    } else {
+        resolve_path(router_base, path, from)
+    }
+}
+
+/// The location a server function redirect to `location` is resolved from.
+///
+/// A relative location is resolved under `router_base`, as the `href` of a link
+/// without a route would be, unless the document base is respected, in which
+/// case it is left for the browser to resolve against the `<base href>`.
+pub(crate) fn redirect_location<'a>(
+    document_base: DocumentBase,
+    router_base: &'a str,
+    location: &'a str,
+) -> Cow<'a, str> {
+    if document_base == DocumentBase::Ignore && is_relative(location) {
+        resolve_path(router_base, location, None)
+    } else {
+        location.into()
+    }
+}
+
+/// The URL a `GET` form navigates to when it is submitted, from its resolved
+/// `action` and the query string of its fields.
+///
+/// The action is already resolved, so the result is navigated to without
+/// resolving it again.
+pub(crate) fn form_get_url(action: &str, query: &str) -> String {
+    if query.is_empty() {
+        action.to_string()
+    } else {
+        format!("{action}?{query}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{form_get_url, redirect_location, resolve_href, DocumentBase};
+    use crate::matching::resolve_path::resolve_path;
+
+    const BASE: &str = "/nested/app";
+
+    #[test]
+    fn form_get_submissions_stay_under_the_router_base() {
+        let from = Some("/nested/app/docs");
+        let action = resolve_href(DocumentBase::Ignore, BASE, "search", from);
+        assert_eq!(action, "/nested/app/docs/search");
+        let url = form_get_url(&action, "q=router");
+        assert_eq!(url, "/nested/app/docs/search?q=router");
+        // navigating without resolving again leaves the URL as it is, rather
+        // than adding the router base a second time
+        assert_eq!(resolve_path("", &url, None), url);
+
+        // the browser resolves the action against the `<base href>` instead
+        assert_eq!(
+            resolve_href(DocumentBase::Respect, BASE, "search", from),
+            "search"
+        );
+        for document_base in [DocumentBase::Ignore, DocumentBase::Respect] {
+            assert_eq!(
+                resolve_href(document_base, BASE, "/search", from),
+                "/search"
+            );
+            assert_eq!(
+                resolve_href(document_base, BASE, "https://a.b/c", from),
+                "https://a.b/c"
+            );
+        }
+        assert_eq!(
+            form_get_url("/nested/app/search", ""),
+            "/nested/app/search"
+        );
+    }
+
+    #[test]
+    fn relative_redirects_are_resolved_under_the_router_base() {
+        assert_eq!(
+            redirect_location(DocumentBase::Ignore, BASE, "login?next=1"),
+            "/nested/app/login?next=1"
+        );
+        assert_eq!(
+            redirect_location(DocumentBase::Respect, BASE, "login"),
+            "login"
+        );
+        for document_base in [DocumentBase::Ignore, DocumentBase::Respect] {
+            assert_eq!(
+                redirect_location(document_base, BASE, "/login"),
+                "/login"
+            );
+            assert_eq!(
+                redirect_location(document_base, BASE, "https://a.b/login"),
+                "https://a.b/login"
+            );
+        }
+    }
+}
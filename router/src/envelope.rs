@@ -0,0 +1,190 @@
+use crate::MatchRequest;
+use serde_json::{json, Map, Value};
+use std::{
+    fmt,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The header a request's ID is read from, for the `meta` of its
+/// [envelope](EnvelopeConfig).
+///
+/// This is the header set by load balancers and by request ID middleware, like
+/// the `SetRequestId` layer of `tower-http`.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Returns the fields to add to the `meta` object of a response's envelope, for
+/// [`EnvelopeConfig::meta_fn`].
+pub type EnvelopeMetaFn = Arc<dyn Fn(&MatchRequest<'_>) -> Value + Send + Sync>;
+
+/// How the JSON responses of a route are wrapped in a standard envelope, like
+/// `{"data":...,"meta":{"request_id":"...","timestamp":"..."}}`, set with
+/// [`NestedRoute::response_envelope`](crate::NestedRoute::response_envelope).
+///
+/// ```rust
+/// use leptos_router::{EnvelopeConfig, MatchRequest};
+/// use serde_json::json;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let config = EnvelopeConfig {
+///     include_meta: true,
+///     meta_fn: None,
+/// };
+/// let now = UNIX_EPOCH + Duration::from_secs(1_750_000_000);
+/// assert_eq!(
+///     config.wrap(
+///         json!([1, 2]),
+///         &MatchRequest::new("/api/items"),
+///         Some("abc"),
+///         now
+///     ),
+///     json!({
+///         "data": [1, 2],
+///         "meta": {
+///             "request_id": "abc",
+///             "timestamp": "2025-06-15T15:06:40Z"
+///         },
+///     })
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct EnvelopeConfig {
+    /// Whether the envelope has a `meta` object, with the ID of the request
+    /// (from its [`X-Request-Id`](REQUEST_ID_HEADER) header, or `null`) and the
+    /// time of the response. Otherwise, the envelope is only `{"data":...}`.
+    pub include_meta: bool,
+    /// Returns more fields for the `meta` object of each response, if it is
+    /// included. A value that is not an object is ignored.
+    pub meta_fn: Option<EnvelopeMetaFn>,
+}
+
+impl EnvelopeConfig {
+    /// Wraps `data`, the body of the response to `req`, in the envelope, at the
+    /// time `now`.
+    pub fn wrap(
+        &self,
+        data: Value,
+        req: &MatchRequest<'_>,
+        request_id: Option<&str>,
+        now: SystemTime,
+    ) -> Value {
+        if !self.include_meta {
+            return json!({ "data": data });
+        }
+        let mut meta = Map::new();
+        meta.insert("request_id".to_string(), json!(request_id));
+        meta.insert("timestamp".to_string(), json!(rfc3339(now)));
+        if let Some(Value::Object(fields)) =
+            self.meta_fn.as_ref().map(|meta_fn| meta_fn(req))
+        {
+            meta.extend(fields);
+        }
+        json!({ "data": data, "meta": meta })
+    }
+}
+
+impl fmt::Debug for EnvelopeConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EnvelopeConfig")
+            .field("include_meta", &self.include_meta)
+            .field("meta_fn", &self.meta_fn.is_some())
+            .finish()
+    }
+}
+
+impl PartialEq for EnvelopeConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.include_meta == other.include_meta
+            && match (&self.meta_fn, &other.meta_fn) {
+                (None, None) => true,
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                _ => false,
+            }
+    }
+}
+
+impl Eq for EnvelopeConfig {}
+
+/// Formats `time` as an RFC 3339 timestamp in UTC, to the second, like
+/// `2025-06-15T15:06:40Z`.
+fn rfc3339(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or_default();
+    let (days, secs) = ((secs / 86_400) as i64, secs % 86_400);
+    // the civil date of a number of days since the Unix epoch, from
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs / 3_600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rfc3339, EnvelopeConfig};
+    use crate::MatchRequest;
+    use serde_json::json;
+    use std::{
+        sync::Arc,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    #[test]
+    fn timestamps_are_civil_dates_in_utc() {
+        let at = |secs| rfc3339(UNIX_EPOCH + Duration::from_secs(secs));
+        assert_eq!(at(0), "1970-01-01T00:00:00Z");
+        assert_eq!(at(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(at(1_735_689_599), "2024-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn meta_fn_adds_fields_to_the_meta() {
+        let config = EnvelopeConfig {
+            include_meta: true,
+            meta_fn: Some(Arc::new(
+                |req: &MatchRequest<'_>| json!({ "path": req.as_path() }),
+            )),
+        };
+        let wrapped = config.wrap(
+            json!({ "id": 1 }),
+            &MatchRequest::new("/api/items/1"),
+            None,
+            UNIX_EPOCH,
+        );
+        assert_eq!(
+            wrapped,
+            json!({
+                "data": { "id": 1 },
+                "meta": {
+                    "request_id": null,
+                    "timestamp": "1970-01-01T00:00:00Z",
+                    "path": "/api/items/1",
+                },
+            })
+        );
+
+        let config = EnvelopeConfig::default();
+        assert_eq!(
+            config.wrap(
+                json!(1),
+                &MatchRequest::new("/"),
+                Some("a"),
+                UNIX_EPOCH
+            ),
+            json!({ "data": 1 })
+        );
+    }
+}
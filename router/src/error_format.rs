@@ -0,0 +1,215 @@
+use leptos::error::Error;
+use serde_json::json;
+use std::{fmt, sync::Arc};
+
+/// The errors a route failed with, which its [`ErrorFormat`] encodes as the
+/// body of its error response.
+///
+/// The server integrations collect the errors that were caught by the
+/// `<ErrorBoundary/>`s of the app while the route was rendered.
+#[derive(Debug, Clone)]
+pub struct RouteError {
+    status: u16,
+    errors: Vec<Error>,
+}
+
+impl RouteError {
+    /// The errors of a response with the HTTP `status` code, like `500`.
+    pub fn new(status: u16, errors: impl IntoIterator<Item = Error>) -> Self {
+        Self {
+            status,
+            errors: errors.into_iter().collect(),
+        }
+    }
+
+    /// The HTTP status code of the response.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// The reason phrase of the [status code](Self::status), like
+    /// `Internal Server Error`.
+    pub fn title(&self) -> &'static str {
+        match self.status {
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            409 => "Conflict",
+            410 => "Gone",
+            413 => "Content Too Large",
+            415 => "Unsupported Media Type",
+            422 => "Unprocessable Content",
+            429 => "Too Many Requests",
+            500 => "Internal Server Error",
+            501 => "Not Implemented",
+            502 => "Bad Gateway",
+            503 => "Service Unavailable",
+            504 => "Gateway Timeout",
+            _ => "Error",
+        }
+    }
+
+    /// The errors, in the order they were caught.
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// The message of each of the errors.
+    pub fn messages(&self) -> impl Iterator<Item = String> + '_ {
+        self.errors.iter().map(ToString::to_string)
+    }
+}
+
+/// Encodes the errors of a route as the body of its error response, for
+/// [`ErrorFormat::Custom`].
+pub trait ErrorFormatter: Send + Sync {
+    /// The body of the response, and its MIME type.
+    fn format(&self, error: &RouteError) -> (Vec<u8>, &'static str);
+}
+
+/// An [`ErrorFormatter`] for GraphQL APIs, which responds with
+/// [GraphQL errors](https://spec.graphql.org/October2021/#sec-Errors), like
+/// `{"errors":[{"message":"..."}]}`.
+///
+/// ```rust
+/// use leptos_router::{ErrorFormatter, GraphQLErrorFormat, RouteError};
+///
+/// let error = RouteError::new(500, [std::fmt::Error.into()]);
+/// let (body, mime) = GraphQLErrorFormat.format(&error);
+/// assert_eq!(mime, "application/json");
+/// assert_eq!(
+///     String::from_utf8(body).unwrap(),
+///     r#"{"errors":[{"message":"an error occurred when formatting an argument"}]}"#
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GraphQLErrorFormat;
+
+impl ErrorFormatter for GraphQLErrorFormat {
+    fn format(&self, error: &RouteError) -> (Vec<u8>, &'static str) {
+        let errors = error
+            .messages()
+            .map(|message| json!({ "message": message }))
+            .collect::<Vec<_>>();
+        (
+            json!({ "errors": errors }).to_string().into_bytes(),
+            "application/json",
+        )
+    }
+}
+
+/// How the errors of a route are encoded in its error responses, set with
+/// [`NestedRoute::error_format`](crate::NestedRoute::error_format).
+#[derive(Clone, Default)]
+pub enum ErrorFormat {
+    /// The HTML of the page, as the `<ErrorBoundary/>`s of the app render it.
+    #[default]
+    Html,
+    /// A JSON object, like `{"status":500,"errors":["..."]}`, as
+    /// `application/json`.
+    Json,
+    /// The [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807) problem details
+    /// of the error, as `application/problem+json`, with the messages of the
+    /// errors as its `detail`.
+    ProblemDetails,
+    /// The body returned by the formatter, like [`GraphQLErrorFormat`].
+    Custom(Arc<dyn ErrorFormatter>),
+}
+
+impl ErrorFormat {
+    /// The body of the error response for `error`, and its MIME type, or `None`
+    /// if the errors are rendered as HTML.
+    pub fn format(
+        &self,
+        error: &RouteError,
+    ) -> Option<(Vec<u8>, &'static str)> {
+        match self {
+            ErrorFormat::Html => None,
+            ErrorFormat::Json => Some((
+                json!({
+                    "status": error.status(),
+                    "errors": error.messages().collect::<Vec<_>>(),
+                })
+                .to_string()
+                .into_bytes(),
+                "application/json",
+            )),
+            ErrorFormat::ProblemDetails => Some((
+                json!({
+                    "type": "about:blank",
+                    "title": error.title(),
+                    "status": error.status(),
+                    "detail": error.messages().collect::<Vec<_>>().join("; "),
+                })
+                .to_string()
+                .into_bytes(),
+                "application/problem+json",
+            )),
+            ErrorFormat::Custom(formatter) => Some(formatter.format(error)),
+        }
+    }
+
+    /// Whether the errors are rendered as HTML, so that the response does not
+    /// have to be checked for them.
+    pub fn is_html(&self) -> bool {
+        matches!(self, ErrorFormat::Html)
+    }
+}
+
+impl fmt::Debug for ErrorFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorFormat::Html => f.write_str("Html"),
+            ErrorFormat::Json => f.write_str("Json"),
+            ErrorFormat::ProblemDetails => f.write_str("ProblemDetails"),
+            ErrorFormat::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+impl PartialEq for ErrorFormat {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ErrorFormat::Html, ErrorFormat::Html)
+            | (ErrorFormat::Json, ErrorFormat::Json)
+            | (ErrorFormat::ProblemDetails, ErrorFormat::ProblemDetails) => {
+                true
+            }
+            (ErrorFormat::Custom(a), ErrorFormat::Custom(b)) => {
+                Arc::ptr_eq(a, b)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ErrorFormat {}
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorFormat, RouteError};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn problem_details_name_the_status_and_the_errors() {
+        let error = RouteError::new(
+            503,
+            [std::fmt::Error.into(), std::fmt::Error.into()],
+        );
+        let (body, mime) = ErrorFormat::ProblemDetails.format(&error).unwrap();
+        assert_eq!(mime, "application/problem+json");
+        assert_eq!(
+            serde_json::from_slice::<Value>(&body).unwrap(),
+            json!({
+                "type": "about:blank",
+                "title": "Service Unavailable",
+                "status": 503,
+                "detail": "an error occurred when formatting an argument; \
+                           an error occurred when formatting an argument",
+            })
+        );
+        assert_eq!(ErrorFormat::Html.format(&error), None);
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{
+    guard::NavigationGuard,
     hooks::Matched,
     location::{LocationProvider, Url},
     matching::{MatchParams, RouteDefs},
+    navigate::{end_navigation, NavigationEnd},
     params::ParamsMap,
     view_transition::start_view_transition,
     ChooseView, MatchInterface, MatchNestedRoutes, PathSegment, RouteList,
@@ -10,7 +12,10 @@ use crate::{
 use any_spawner::Executor;
 use either_of::Either;
 use futures::FutureExt;
-use leptos::attr::{any_attribute::AnyAttribute, Attribute};
+use leptos::{
+    attr::{any_attribute::AnyAttribute, Attribute},
+    callback::Callback,
+};
 use reactive_graph::{
     computed::{ArcMemo, ScopedFuture},
     owner::{provide_context, Owner},
@@ -38,6 +43,7 @@ pub(crate) struct FlatRoutesView<Loc, Defs, FalFn> {
     pub fallback: FalFn,
     pub outer_owner: Owner,
     pub set_is_routing: Option<SignalSetter<bool>>,
+    pub on_navigation_end: Option<Callback<(NavigationEnd,)>>,
     pub transition: bool,
 }
 
@@ -79,7 +85,7 @@ impl<Loc, Defs, FalFn, Fal> Render for FlatRoutesView<Loc, Defs, FalFn>
 where
     Loc: LocationProvider,
     Defs: MatchNestedRoutes + 'static,
-    FalFn: FnOnce() -> Fal + Send,
+    FalFn: FnOnce() -> Fal + Send + 'static,
     Fal: IntoAny,
 {
     type State = Rc<RefCell<FlatRoutesViewState>>;
@@ -95,7 +101,7 @@ where
         let current_url = current_url.read_untracked();
 
         // we always need to match the new route
-        let new_match = routes.match_route(current_url.path());
+        let new_match = routes.match_url(&current_url);
         let id = new_match.as_ref().map(|n| n.as_id());
         let matched = ArcRwSignal::new(
             new_match
@@ -131,6 +137,8 @@ where
                 matched,
             })),
             Some(new_match) => {
+                let guard = NavigationGuard::new(&new_match, &outer_owner);
+                owner.with(|| new_match.provide_route_contexts());
                 let (view, child) = new_match.into_view_and_child();
 
                 #[cfg(debug_assertions)]
@@ -148,7 +156,12 @@ where
                             provide_context(params_memo);
                             provide_context(url);
                             provide_context(Matched(ArcMemo::from(matched)));
-                            OwnedView::new(view.choose().await)
+                            // the view of a route that a guard does not allow
+                            // is never shown
+                            if !guard.allows().await {
+                                return fallback().into_any();
+                            }
+                            OwnedView::new(view.choose().await).into_any()
                         }
                     })
                 }));
@@ -199,18 +212,33 @@ where
             fallback,
             outer_owner,
             set_is_routing,
+            on_navigation_end,
             transition,
         } = self;
         let url_snapshot = current_url.read_untracked();
 
-        // if the path is the same, we do not need to re-route
-        // we can just update the search query and go about our day
         let mut initial_state = state.borrow_mut();
-        if url_snapshot.path() == initial_state.path {
+
+        // if the path is the same, and the query does not change which route
+        // matches it, we do not need to re-route: we can just update the search
+        // query and go about our day
+        if url_snapshot.path() == initial_state.path
+            && routes.match_url(&url_snapshot).map(|n| n.as_id())
+                == initial_state.id
+        {
+            // if only the hash has changed, this is not a new navigation at
+            // all: the route only needs to see the new URL
+            let hash_only = initial_state
+                .url
+                .read_untracked()
+                .differs_only_in_hash(&url_snapshot);
             initial_state.url.set(url_snapshot.to_owned());
             if let Some(location) = location {
                 location.ready_to_complete();
             }
+            if !hash_only {
+                end_navigation(on_navigation_end, url_snapshot.to_owned());
+            }
             return;
         }
 
@@ -219,7 +247,7 @@ where
         initial_state.path.push_str(url_snapshot.path());
 
         // otherwise, match the new route
-        let new_match = routes.match_route(url_snapshot.path());
+        let new_match = routes.match_url(&url_snapshot);
         let new_id = new_match.as_ref().map(|n| n.as_id());
         let matched_string = new_match
             .as_ref()
@@ -237,6 +265,7 @@ where
             if let Some(location) = location {
                 location.ready_to_complete();
             }
+            end_navigation(on_navigation_end, url_snapshot.to_owned());
             return;
         }
 
@@ -274,8 +303,11 @@ where
                 if let Some(location) = location {
                     location.ready_to_complete();
                 }
+                end_navigation(on_navigation_end, url_snapshot.to_owned());
             }
             Some(new_match) => {
+                let guard = NavigationGuard::new(&new_match, &outer_owner);
+                owner.with(|| new_match.provide_route_contexts());
                 let (view, child) = new_match.into_view_and_child();
 
                 #[cfg(debug_assertions)]
@@ -300,18 +332,28 @@ where
                             provide_context(Matched(ArcMemo::from(
                                 new_matched,
                             )));
-                            let view = OwnedView::new(
-                                if let Some(set_is_routing) = set_is_routing {
-                                    set_is_routing.set(true);
-                                    let value =
-                                        AsyncTransition::run(|| view.choose())
+                            // the view of a route that a guard does not allow
+                            // is never shown
+                            let view = if !guard.allows().await {
+                                fallback().into_any()
+                            } else {
+                                OwnedView::new(
+                                    if let Some(set_is_routing) = set_is_routing
+                                    {
+                                        set_is_routing.set(true);
+                                        let value =
+                                            AsyncTransition::run(|| {
+                                                view.choose()
+                                            })
                                             .await;
-                                    set_is_routing.set(false);
-                                    value
-                                } else {
-                                    view.choose().await
-                                },
-                            );
+                                        set_is_routing.set(false);
+                                        value
+                                    } else {
+                                        view.choose().await
+                                    },
+                                )
+                                .into_any()
+                            };
 
                             // only update the route if it's still the current path
                             // i.e., if we've navigated away before this has loaded, do nothing
@@ -332,6 +374,10 @@ where
                             if let Some(location) = location {
                                 location.ready_to_complete();
                             }
+                            end_navigation(
+                                on_navigation_end,
+                                current_url.get_untracked(),
+                            );
                             drop(old_owner);
                             drop(old_params);
                             drop(old_url);
@@ -486,7 +532,7 @@ where
 {
     fn choose_ssr(self) -> OwnedView<AnyView> {
         let current_url = self.current_url.read_untracked();
-        let new_match = self.routes.match_route(current_url.path());
+        let new_match = self.routes.match_url(&current_url);
         let owner = self.outer_owner.child();
         let url = ArcRwSignal::new(current_url.to_owned());
         let params = ArcRwSignal::new(
@@ -510,6 +556,7 @@ where
             None => (self.fallback)().into_any(),
             Some(new_match) => {
                 let id = new_match.as_matched().to_string();
+                owner.with(|| new_match.provide_route_contexts());
                 let (view, _) = new_match.into_view_and_child();
                 let view = owner
                     .with(|| {
@@ -573,12 +620,18 @@ where
                         })
                         .chain(data.segments)
                         .collect::<Vec<_>>();
+                    let mut config = data.config;
+                    config.deduplication = config
+                        .deduplication
+                        .map(|config| config.for_route(data.id));
                     RouteListing::new(
                         path,
                         data.ssr_mode,
                         data.methods,
                         data.regenerate,
                     )
+                    .with_config(config)
+                    .with_language_redirect(data.language_redirect)
                 })
                 .collect::<Vec<_>>();
 
@@ -653,7 +706,7 @@ where
         let current_url = current_url.read_untracked();
 
         // we always need to match the new route
-        let new_match = routes.match_route(current_url.path());
+        let new_match = routes.match_url(&current_url);
         let id = new_match.as_ref().map(|n| n.as_id());
         let matched = ArcRwSignal::new(
             new_match
@@ -691,6 +744,7 @@ where
                 matched,
             })),
             Some(new_match) => {
+                owner.with(|| new_match.provide_route_contexts());
                 let (view, child) = new_match.into_view_and_child();
 
                 #[cfg(debug_assertions)]
@@ -1,9 +1,11 @@
 use crate::{
     components::ToHref,
+    document_base::form_get_url,
     hooks::{has_router, use_navigate, use_resolved_path},
     location::{BrowserUrl, LocationProvider},
     NavigateOptions,
 };
+use leptos::server_fn::client::{get_document_base, DocumentBase};
 use leptos::{ev, html::form, logging::*, prelude::*, task::spawn_local};
 use std::{error::Error, sync::Arc};
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
@@ -15,6 +17,11 @@ type OnError = Arc<dyn Fn(&gloo_net::Error)>;
 
 /// An HTML [`form`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form) progressively
 /// enhanced to use client-side routing.
+///
+/// A submission that the router cannot handle is left to the browser, as if the
+/// form were not enhanced: one to an action on another origin, one that opens
+/// in another browsing context with `target` (or the `formtarget` of its submit
+/// button), one with `download`, and one with the `dialog` method.
 #[component]
 pub fn Form<A>(
     /// [`method`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form#attr-method)
@@ -52,6 +59,10 @@ pub fn Form<A>(
     /// Sets whether the page should replace the current location in the history when the form is submitted.
     #[prop(optional)]
     replace: bool,
+    /// Leaves every submission of the form to the browser, even to an action on
+    /// the same origin, which reloads the page with the response.
+    #[prop(optional)]
+    native: bool,
     /// Component children; should include the HTML of the form elements.
     children: Children,
 ) -> impl IntoView
@@ -97,6 +108,7 @@ where
         children: Children,
         noscroll: bool,
         replace: bool,
+        native: bool,
     ) -> impl IntoView {
         let action_version = version;
         let navigate = has_router.then(use_navigate);
@@ -114,6 +126,13 @@ where
 
                 let (form, method, action, enctype) =
                     extract_form_attributes(&ev);
+                // the form data is not read, so that the browser submits it as
+                // it is
+                if submission(&ev, &form, &method, native)
+                    .is_native(&current_window_origin())
+                {
+                    return;
+                }
 
                 let form_data =
                     web_sys::FormData::new_with_form(&form).unwrap_throw();
@@ -281,15 +300,25 @@ where
                 else {
                     let params =
                         params.to_string().as_string().unwrap_or_default();
+                    // a relative action is only left in the attribute for the
+                    // browser to resolve against the `<base href>`, which it
+                    // has done for the property
+                    let action = match get_document_base() {
+                        DocumentBase::Ignore => action,
+                        DocumentBase::Respect => form.action(),
+                    };
+                    let url = form_get_url(&action, &params);
                     if let Some(navigate) = navigate {
+                        // the action has already been resolved
                         navigate(
-                            &format!("{action}?{params}"),
-                            navigate_options,
+                            &url,
+                            NavigateOptions {
+                                resolve: false,
+                                ..navigate_options
+                            },
                         );
                     } else {
-                        _ = window()
-                            .location()
-                            .set_href(&format!("{action}?{params}"));
+                        _ = window().location().set_href(&url);
                     }
                     ev.prevent_default();
                     ev.stop_propagation();
@@ -326,6 +355,7 @@ where
         children,
         noscroll,
         replace,
+        native,
     )
 }
 
@@ -343,6 +373,81 @@ fn current_window_origin() -> String {
     )
 }
 
+/// What decides whether a submission of a [`Form`] is left to the browser.
+#[derive(Debug, Default)]
+struct Submission<'a> {
+    /// Whether the form has the `native` prop.
+    native: bool,
+    method: &'a str,
+    /// The `formtarget` of the submit button, or else the `target` of the form.
+    target: Option<String>,
+    /// Whether the form or its submit button has the `download` attribute.
+    download: bool,
+    /// The origin of the resolved URL of the action, if it could be parsed.
+    action_origin: Option<String>,
+}
+
+impl Submission<'_> {
+    /// Whether the submission has to be left to the browser, because it does
+    /// not navigate within this app, on `origin`: it opens in another browsing
+    /// context, downloads the response, closes a dialog, or is sent to another
+    /// origin. Or because the form is `native`.
+    fn is_native(&self, origin: &str) -> bool {
+        self.native
+            || self.method.eq_ignore_ascii_case("dialog")
+            || self.target.as_deref().is_some_and(|target| {
+                !target.is_empty() && !target.eq_ignore_ascii_case("_self")
+            })
+            || self.download
+            || self
+                .action_origin
+                .as_deref()
+                .is_some_and(|action_origin| action_origin != origin)
+    }
+}
+
+/// The [`Submission`] of `form` by `ev`.
+fn submission<'a>(
+    ev: &web_sys::SubmitEvent,
+    form: &web_sys::HtmlFormElement,
+    method: &'a str,
+    native: bool,
+) -> Submission<'a> {
+    let submitter = ev.submitter();
+    let target = submitter
+        .as_ref()
+        .and_then(|el| el.get_attribute("formtarget"))
+        .or_else(|| form.get_attribute("target"));
+    let download = form.has_attribute("download")
+        || submitter
+            .as_ref()
+            .is_some_and(|el| el.has_attribute("download"));
+
+    // the resolved URL of the action, which the submit button can override
+    let action = submitter
+        .as_ref()
+        .filter(|el| el.has_attribute("formaction"))
+        .and_then(|el| {
+            el.dyn_ref::<web_sys::HtmlButtonElement>()
+                .map(web_sys::HtmlButtonElement::form_action)
+                .or_else(|| {
+                    el.dyn_ref::<web_sys::HtmlInputElement>()
+                        .map(web_sys::HtmlInputElement::form_action)
+                })
+        })
+        .unwrap_or_else(|| form.action());
+    let action_origin = BrowserUrl::parse(&action)
+        .ok()
+        .map(|url| url.origin().to_string());
+    Submission {
+        native,
+        method,
+        target,
+        download,
+        action_origin,
+    }
+}
+
 fn extract_form_attributes(
     ev: &web_sys::Event,
 ) -> (web_sys::HtmlFormElement, String, String, String) {
@@ -448,3 +553,63 @@ fn extract_form_attributes(
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Submission;
+
+    const ORIGIN: &str = "https://leptos.dev";
+
+    fn to(action_origin: &str) -> Submission<'static> {
+        Submission {
+            method: "post",
+            action_origin: Some(action_origin.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn submissions_within_the_app_are_handled_by_the_router() {
+        assert!(!to(ORIGIN).is_native(ORIGIN));
+        assert!(!Submission {
+            target: Some("_self".into()),
+            ..to(ORIGIN)
+        }
+        .is_native(ORIGIN));
+        assert!(!Submission {
+            target: Some(String::new()),
+            ..to(ORIGIN)
+        }
+        .is_native(ORIGIN));
+    }
+
+    #[test]
+    fn submissions_that_leave_the_app_are_left_to_the_browser() {
+        assert!(to("https://external.example").is_native(ORIGIN));
+        assert!(to("http://leptos.dev").is_native(ORIGIN));
+        assert!(Submission {
+            target: Some("_blank".into()),
+            ..to(ORIGIN)
+        }
+        .is_native(ORIGIN));
+        assert!(Submission {
+            download: true,
+            ..to(ORIGIN)
+        }
+        .is_native(ORIGIN));
+        assert!(Submission {
+            method: "DIALOG",
+            ..to(ORIGIN)
+        }
+        .is_native(ORIGIN));
+    }
+
+    #[test]
+    fn the_native_prop_leaves_every_submission_to_the_browser() {
+        assert!(Submission {
+            native: true,
+            ..to(ORIGIN)
+        }
+        .is_native(ORIGIN));
+    }
+}
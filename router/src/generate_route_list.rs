@@ -3,9 +3,10 @@ use crate::{
     static_routes::{
         RegenerationFn, ResolvedStaticPath, StaticPath, StaticRoute,
     },
-    Method, SsrMode,
+    LanguageRedirect, Method, RouteConfig, SsrMode,
 };
-use futures::future::join_all;
+use futures::{future::join_all, StreamExt};
+use leptos::task::spawn;
 use reactive_graph::owner::Owner;
 use std::{
     cell::{Cell, RefCell},
@@ -22,6 +23,8 @@ pub struct RouteListing {
     mode: SsrMode,
     methods: HashSet<Method>,
     regenerate: Vec<RegenerationFn>,
+    config: RouteConfig,
+    language_redirect: Option<LanguageRedirect>,
 }
 
 impl RouteListing {
@@ -37,9 +40,27 @@ impl RouteListing {
             mode,
             methods: methods.into_iter().collect(),
             regenerate: regenerate.into_iter().collect(),
+            config: RouteConfig::default(),
+            language_redirect: None,
         }
     }
 
+    /// Sets the options the server integrations apply to the requests to this
+    /// route.
+    pub fn with_config(mut self, config: RouteConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets how requests to this route are redirected to a path with a locale.
+    pub fn with_language_redirect(
+        mut self,
+        redirect: Option<LanguageRedirect>,
+    ) -> Self {
+        self.language_redirect = redirect;
+        self
+    }
+
     /// Create a route listing from a path, with the other fields set to default values.
     pub fn from_path(path: impl IntoIterator<Item = PathSegment>) -> Self {
         Self::new(path, SsrMode::Async, [], [])
@@ -57,7 +78,7 @@ impl RouteListing {
 
     /// The HTTP request methods this path can handle.
     pub fn methods(&self) -> impl Iterator<Item = Method> + '_ {
-        self.methods.iter().copied()
+        self.methods.iter().cloned()
     }
 
     /// The set of regeneration functions that should be applied to this route, if it is statically
@@ -66,6 +87,18 @@ impl RouteListing {
         &self.regenerate
     }
 
+    /// The options the server integrations apply to the requests to this
+    /// route.
+    pub fn config(&self) -> &RouteConfig {
+        &self.config
+    }
+
+    /// How requests to this route are redirected to a path with a locale, if
+    /// they are.
+    pub fn language_redirect(&self) -> Option<&LanguageRedirect> {
+        self.language_redirect.as_ref()
+    }
+
     /// Whether this route is statically rendered.
     #[inline(always)]
     pub fn static_route(&self) -> Option<&StaticRoute> {
@@ -94,12 +127,28 @@ impl RouteListing {
         Fut: Future<Output = (Owner, String)> + Send + 'static,
         WriterFut: Future<Output = Result<(), std::io::Error>> + Send + 'static,
     {
-        if let SsrMode::Static(_) = self.mode() {
+        if let SsrMode::Static(route) = self.mode() {
             let (all_initial_tx, all_initial_rx) = std::sync::mpsc::channel();
 
-            let render_fn = render_fn.clone();
+            let route = route.clone();
+            let segments = self.path.clone();
+            // the HTML injected into the <head> is baked into each file as it
+            // is rendered
+            let head = mem::take(&mut self.config.head);
+            let render_fn = move |path: &ResolvedStaticPath| {
+                let render = render_fn(path);
+                let head = head.clone();
+                async move {
+                    let (owner, html) = render.await;
+                    (owner, head.inject(html))
+                }
+            };
             let regenerate = mem::take(&mut self.regenerate);
             let paths = self.into_static_paths().await.unwrap_or_default();
+            let mut built = paths
+                .iter()
+                .map(|path| path.path.clone())
+                .collect::<HashSet<_>>();
 
             for path in paths {
                 // Err(_) here would just mean they've dropped the rx and are no longer awaiting
@@ -114,6 +163,31 @@ impl RouteListing {
             }
 
             join_all(all_initial_rx.try_iter()).await;
+
+            // when the params are refreshed, build any paths that did not exist
+            // before
+            if let Some(refresh) = route.refresh_params.clone() {
+                spawn(async move {
+                    let mut refresh = refresh();
+                    while refresh.next().await.is_some() {
+                        let params = route.to_prerendered_params().await;
+                        let new_paths = StaticPath::new(segments.clone())
+                            .into_paths(params)
+                            .into_iter()
+                            .filter(|path| built.insert(path.path.clone()))
+                            .map(|path| {
+                                path.build(
+                                    render_fn.clone(),
+                                    writer.clone(),
+                                    was_404.clone(),
+                                    regenerate.clone(),
+                                )
+                            })
+                            .collect::<Vec<_>>();
+                        join_all(new_paths).await;
+                    }
+                });
+            }
         }
     }
 
@@ -0,0 +1,214 @@
+use crate::{
+    hooks::use_navigate, params::ParamsMap, MatchInterface, MatchParams,
+    NavigateOptions, RouteRedirect,
+};
+use reactive_graph::owner::Owner;
+use std::{fmt, future::Future, pin::Pin, sync::Arc};
+
+/// Whether a [route guard](crate::NestedRoute::guard) lets a request through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardResult {
+    /// The request can access the route.
+    Allow,
+    /// The request should be redirected instead, like to a login page.
+    Redirect(RouteRedirect),
+    /// The request is not allowed to access the route, which is a
+    /// [`MatchError::Forbidden`](crate::MatchError::Forbidden).
+    Forbid,
+}
+
+impl From<bool> for GuardResult {
+    /// `true` allows the request, and `false` forbids it.
+    fn from(allow: bool) -> Self {
+        if allow {
+            GuardResult::Allow
+        } else {
+            GuardResult::Forbid
+        }
+    }
+}
+
+type GuardFn = dyn Fn(&ParamsMap) -> Pin<Box<dyn Future<Output = GuardResult> + Send>>
+    + Send
+    + Sync;
+
+/// Checks whether a request can access a route, with [`NestedRoute::guard`] or
+/// [`NestedRoute::guard_async`].
+///
+/// [`NestedRoute::guard`]: crate::NestedRoute::guard
+/// [`NestedRoute::guard_async`]: crate::NestedRoute::guard_async
+#[derive(Clone)]
+pub struct RouteGuard(Arc<GuardFn>);
+
+impl RouteGuard {
+    /// Creates a guard that decides synchronously, from the params of the
+    /// matched route.
+    pub fn new<F, R>(guard: F) -> Self
+    where
+        F: Fn(&ParamsMap) -> R + Send + Sync + 'static,
+        R: Into<GuardResult>,
+    {
+        Self(Arc::new(move |params| {
+            let result = guard(params).into();
+            Box::pin(async move { result })
+        }))
+    }
+
+    /// Creates a guard that decides asynchronously, from the params of the
+    /// matched route, like by looking up a session in a database.
+    pub fn new_async<F, Fut>(guard: F) -> Self
+    where
+        F: Fn(&ParamsMap) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = GuardResult> + Send + 'static,
+    {
+        Self(Arc::new(move |params| Box::pin(guard(params))))
+    }
+
+    /// Checks a request to a route with the given params.
+    pub fn check(
+        &self,
+        params: &ParamsMap,
+    ) -> impl Future<Output = GuardResult> + Send {
+        (self.0)(params)
+    }
+
+    /// Checks a request to a route with the given params against each of
+    /// `guards` in turn, returning the result of the first one that does not
+    /// allow it. The rest are never run.
+    pub async fn check_all(
+        guards: &[RouteGuard],
+        params: &ParamsMap,
+    ) -> GuardResult {
+        for guard in guards {
+            match guard.check(params).await {
+                GuardResult::Allow => {}
+                denied => return denied,
+            }
+        }
+        GuardResult::Allow
+    }
+}
+
+/// The guards of the routes matched by a client-side navigation, which decide
+/// whether their views are shown.
+pub(crate) struct NavigationGuard {
+    guards: Vec<RouteGuard>,
+    params: ParamsMap,
+    owner: Owner,
+}
+
+impl NavigationGuard {
+    /// The guards of `matched`, the routes a navigation under the router of
+    /// `owner` matched.
+    pub fn new(
+        matched: &(impl MatchInterface + MatchParams),
+        owner: &Owner,
+    ) -> Self {
+        let guards = matched.guards();
+        let params = if guards.is_empty() {
+            ParamsMap::new()
+        } else {
+            matched.to_params().into_iter().collect()
+        };
+        Self {
+            guards,
+            params,
+            owner: owner.clone(),
+        }
+    }
+
+    /// Checks the navigation with [`allow_navigation`].
+    pub async fn allows(self) -> bool {
+        if self.guards.is_empty() {
+            return true;
+        }
+        let navigate = self.owner.with(use_navigate);
+        allow_navigation(&self.guards, &self.params, navigate).await
+    }
+}
+
+/// Checks a client-side navigation against `guards`, the guards of the matched
+/// routes, before their views are shown, and returns whether they can be.
+///
+/// A navigation that a guard redirects is replaced with one to the path of the
+/// redirect, with `navigate`. A forbidden one is not: the router shows its
+/// fallback instead of the views, as for a path that no route matches.
+pub(crate) async fn allow_navigation(
+    guards: &[RouteGuard],
+    params: &ParamsMap,
+    navigate: impl Fn(&str, NavigateOptions),
+) -> bool {
+    match RouteGuard::check_all(guards, params).await {
+        GuardResult::Allow => true,
+        GuardResult::Redirect(redirect) => {
+            navigate(
+                &redirect.path,
+                NavigateOptions {
+                    replace: true,
+                    ..Default::default()
+                },
+            );
+            false
+        }
+        GuardResult::Forbid => false,
+    }
+}
+
+impl fmt::Debug for RouteGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RouteGuard(..)")
+    }
+}
+
+impl PartialEq for RouteGuard {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for RouteGuard {}
+
+#[cfg(test)]
+mod tests {
+    use super::{allow_navigation, GuardResult, RouteGuard};
+    use crate::{params::ParamsMap, NavigateOptions, RouteRedirect};
+    use futures::executor::block_on;
+    use std::sync::Mutex;
+
+    #[test]
+    fn navigations_are_only_allowed_if_every_guard_allows_them() {
+        let params = ParamsMap::new();
+        let navigated = Mutex::new(Vec::new());
+        let navigate = |path: &str, options: NavigateOptions| {
+            navigated
+                .lock()
+                .unwrap()
+                .push((path.to_string(), options.replace));
+        };
+
+        let allow = RouteGuard::new(|_: &ParamsMap| true);
+        assert!(block_on(allow_navigation(
+            std::slice::from_ref(&allow),
+            &params,
+            navigate
+        )));
+
+        let login = RouteGuard::new(|_: &ParamsMap| {
+            GuardResult::Redirect(RouteRedirect::new("/login"))
+        });
+        let never = RouteGuard::new(|_: &ParamsMap| -> bool {
+            unreachable!("the guards after a redirect are not run")
+        });
+        assert!(!block_on(allow_navigation(
+            &[allow, login, never],
+            &params,
+            navigate
+        )));
+        // the redirect replaces the navigation that was not allowed
+        assert_eq!(*navigated.lock().unwrap(), [("/login".to_string(), true)]);
+
+        let forbid = RouteGuard::new(|_: &ParamsMap| false);
+        assert!(!block_on(allow_navigation(&[forbid], &params, navigate)));
+        assert_eq!(navigated.lock().unwrap().len(), 1);
+    }
+}
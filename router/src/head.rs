@@ -0,0 +1,134 @@
+use std::borrow::Cow;
+
+const HEAD_END: &[u8] = b"</head>";
+
+/// Arbitrary HTML that is inserted into the `<head>` of a route, with
+/// [`NestedRoute::inject_head`](crate::NestedRoute::inject_head).
+///
+/// The HTML is inserted as it is, right before `</head>`, so that it comes
+/// after any meta tags the route renders. It is never escaped, so inline
+/// `<script>`s and `<style>`s can be written exactly as they should be sent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HeadInjection(Vec<Cow<'static, str>>);
+
+impl HeadInjection {
+    /// Adds `html` after the HTML that was already added.
+    pub fn push(&mut self, html: impl Into<Cow<'static, str>>) {
+        self.0.push(html.into());
+    }
+
+    /// Adds all of `other`'s HTML after this HTML.
+    pub fn extend(&mut self, other: HeadInjection) {
+        self.0.extend(other.0);
+    }
+
+    /// Whether there is no HTML to insert.
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|html| html.is_empty())
+    }
+
+    /// All of the HTML to insert, in order.
+    pub fn html(&self) -> String {
+        self.0.concat()
+    }
+
+    /// Inserts the HTML into the `<head>` of the whole document `html`.
+    ///
+    /// This is how the HTML is baked into the routes that are statically
+    /// generated.
+    pub fn inject(&self, html: String) -> String {
+        if self.is_empty() {
+            return html;
+        }
+        let mut injector = self.injector();
+        let mut injected = injector.push(html.as_bytes());
+        injected.extend(injector.finish());
+        String::from_utf8(injected).unwrap_or(html)
+    }
+
+    /// Creates a [`HeadInjector`] that inserts the HTML into a document as it
+    /// is streamed.
+    pub fn injector(&self) -> HeadInjector {
+        HeadInjector {
+            head: (!self.is_empty()).then(|| self.html().into_bytes()),
+            pending: Vec::new(),
+        }
+    }
+}
+
+/// Inserts a [`HeadInjection`] into an HTML document as it is streamed, a chunk
+/// at a time.
+///
+/// A chunk can end in the middle of `</head>`: the bytes that could be the
+/// start of it are kept until the next chunk, or until
+/// [`finish`](HeadInjector::finish). Once the HTML has been inserted, the
+/// chunks are passed on as they are.
+#[derive(Debug)]
+pub struct HeadInjector {
+    head: Option<Vec<u8>>,
+    pending: Vec<u8>,
+}
+
+impl HeadInjector {
+    /// Adds the next chunk of the document, returning what can be sent of it.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<u8> {
+        let Some(head) = self.head.as_ref() else {
+            return chunk.to_vec();
+        };
+        self.pending.extend_from_slice(chunk);
+        if let Some(pos) = self
+            .pending
+            .windows(HEAD_END.len())
+            .position(|window| window == HEAD_END)
+        {
+            let mut out = Vec::with_capacity(self.pending.len() + head.len());
+            out.extend_from_slice(&self.pending[..pos]);
+            out.extend_from_slice(head);
+            out.extend_from_slice(&self.pending[pos..]);
+            self.pending.clear();
+            self.head = None;
+            return out;
+        }
+        let keep = self.pending.len().min(HEAD_END.len() - 1);
+        self.pending.drain(..self.pending.len() - keep).collect()
+    }
+
+    /// Returns the rest of the document, once every chunk has been pushed.
+    ///
+    /// If the document had no `</head>`, nothing is inserted.
+    pub fn finish(&mut self) -> Vec<u8> {
+        self.head = None;
+        std::mem::take(&mut self.pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HeadInjection;
+
+    #[test]
+    fn html_is_inserted_verbatim_before_the_end_of_the_head() {
+        let mut head = HeadInjection::default();
+        head.push(r#"<link rel="preconnect" href="https://cdn.example.com">"#);
+        head.push("<script>if (a < b && c) { go(\"</p>\"); }</script>");
+
+        let html = "<html><head><title>Home</title></head><body></body></html>";
+        assert_eq!(
+            head.inject(html.to_string()),
+            "<html><head><title>Home</title><link rel=\"preconnect\" \
+             href=\"https://cdn.example.com\"><script>if (a < b && c) { \
+             go(\"</p>\"); }</script></head><body></body></html>"
+        );
+
+        // the end of the head can be split across chunks
+        let mut injector = head.injector();
+        let mut streamed = injector.push(b"<html><head></he");
+        streamed.extend(injector.push(b"ad><body>"));
+        streamed.extend(injector.push(b"</head>"));
+        streamed.extend(injector.finish());
+        assert_eq!(
+            String::from_utf8(streamed).unwrap(),
+            format!("<html><head>{}</head><body></head>", head.html())
+        );
+    }
+}
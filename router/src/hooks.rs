@@ -1,8 +1,10 @@
 use crate::{
     components::RouterContext,
+    document_base::resolve_href,
     location::{Location, Url},
     navigate::NavigateOptions,
-    params::{Params, ParamsError, ParamsMap},
+    nested_router::OutletContexts,
+    params::{Params, ParamsError, ParamsMap, ToParams},
 };
 use leptos::{leptos_dom::helpers::request_animation_frame, oco::Oco};
 use reactive_graph::{
@@ -10,7 +12,7 @@ use reactive_graph::{
     owner::{expect_context, use_context},
     signal::{ArcRwSignal, ReadSignal},
     traits::{Get, GetUntracked, ReadUntracked, With, WriteValue},
-    wrappers::write::SignalSetter,
+    wrappers::{read::Signal, write::SignalSetter},
 };
 use std::{
     str::FromStr,
@@ -189,7 +191,23 @@ pub fn use_params_map() -> Memo<ParamsMap> {
     use_params_raw().into()
 }
 
+/// Returns the current value of a single route param.
+///
+/// Reading a param from [`use_params_map`] subscribes to the whole map, which
+/// changes when any of its params does. This only notifies when the value of
+/// `key` changes, so that a layout that reads only `org_id` does not re-run
+/// when navigating from `/org/1/project/2` to `/org/1/project/3`.
+#[track_caller]
+pub fn use_param(key: impl Into<Oco<'static, str>>) -> Signal<Option<String>> {
+    let key = key.into();
+    let params = use_params_raw();
+    Memo::new(move |_| params.with(|params| params.get(&key))).into()
+}
+
 /// Returns the current route params, parsed into the given type, or an error.
+///
+/// This only notifies when the parsed value changes, so navigating to a path
+/// whose other params differ does not re-run anything that reads it.
 #[track_caller]
 pub fn use_params<T>() -> Memo<Result<T, ParamsError>>
 where
@@ -233,6 +251,51 @@ where
     Memo::new(move |_| url.with(|url| T::from_map(url.search_params())))
 }
 
+/// Returns the href of the current path with the current URL search query,
+/// parsed into the given type, changed by `update`, like the link to the next
+/// page of a list.
+///
+/// The query of the href is built with [`ToParams`], so it only has the params
+/// of the type, and it parses back into the value that `update` left. If the
+/// current query cannot be parsed, `update` changes the default value instead.
+/// The href is updated whenever the query changes, so `update` is called again
+/// each time.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::{components::A, hooks::use_query_link, params::Params};
+/// #[derive(Params, ToParams, Default, Clone, PartialEq)]
+/// struct ProductQuery {
+///     page: Option<u32>,
+///     tags: Vec<String>,
+/// }
+///
+/// #[component]
+/// fn NextPage() -> impl IntoView {
+///     let href = use_query_link(|query: &mut ProductQuery| {
+///         query.page = Some(query.page.unwrap_or(1) + 1);
+///     });
+///     view! { <A href=move || href.get()>"Next"</A> }
+/// }
+/// ```
+#[track_caller]
+pub fn use_query_link<T>(
+    update: impl Fn(&mut T) + Send + Sync + 'static,
+) -> Memo<String>
+where
+    T: Params + ToParams + Default,
+{
+    let url = use_url_raw();
+    Memo::new(move |_| {
+        url.with(|url| {
+            let mut query =
+                T::from_map(url.search_params()).unwrap_or_default();
+            update(&mut query);
+            format!("{}{}", url.path(), query.to_query_string())
+        })
+    })
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct Matched(pub ArcMemo<String>);
 
@@ -247,16 +310,13 @@ pub(crate) fn use_resolved_path(
     let matched = use_context::<Matched>().map(|n| n.0);
     ArcMemo::new(move |_| {
         let path = path();
-        if path.starts_with('/') {
-            path
-        } else {
-            router
-                .resolve_path(
-                    &path,
-                    matched.as_ref().map(|n| n.get()).as_deref(),
-                )
-                .to_string()
-        }
+        resolve_href(
+            router.document_base,
+            router.base.as_deref().unwrap_or_default(),
+            &path,
+            matched.as_ref().map(|n| n.get()).as_deref(),
+        )
+        .into_owned()
     })
 }
 
@@ -287,3 +347,109 @@ pub fn use_matched() -> Memo<String> {
         .0
         .into()
 }
+
+/// Passes `value` from the view of the current route to the view of its child
+/// route, which can read it with [`use_outlet_context`].
+///
+/// Unlike [`provide_context`](reactive_graph::owner::provide_context), the
+/// value is only seen by the route rendered in this route's `<Outlet/>`, and
+/// not by the routes nested further down, so the same child route can be reused
+/// under several parents that each pass it a value of the same type. The value
+/// is kept while the child route changes, or is rendered again for new params,
+/// without this route's view being rendered again.
+///
+/// Call it in the view before the `<Outlet/>` is rendered. A later call with a
+/// value of the same type replaces it.
+#[track_caller]
+pub fn provide_outlet_context<T: Send + Sync + 'static>(value: T) {
+    use_context::<OutletContexts>()
+        .expect("provide_outlet_context called outside a matched Route")
+        .own
+        .insert(value);
+}
+
+/// Returns the value of type `T` that the parent route passed to the current
+/// route with [`provide_outlet_context`], if it did.
+#[track_caller]
+pub fn use_outlet_context<T: Clone + 'static>() -> Option<T> {
+    use_context::<OutletContexts>()?.parent?.get()
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{use_param, use_params};
+    use crate::params::{Params, ParamsError, ParamsMap};
+    use reactive_graph::{
+        computed::ArcMemo,
+        owner::{provide_context, Owner},
+        signal::ArcRwSignal,
+        traits::{Get, GetUntracked, Set, With},
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Org {
+        org_id: Option<String>,
+    }
+
+    impl Params for Org {
+        fn from_map(map: &ParamsMap) -> Result<Self, ParamsError> {
+            Ok(Self {
+                org_id: map.get("org_id"),
+            })
+        }
+    }
+
+    fn params(org_id: &str, project_id: &str) -> ParamsMap {
+        [("org_id", org_id), ("project_id", project_id)]
+            .into_iter()
+            .map(|(key, value)| (key, value.to_string()))
+            .collect()
+    }
+
+    /// Counts how many times a memo that reads `read` runs, returning a
+    /// function which brings it up to date and returns the count.
+    fn runs_of(read: impl Fn() + Send + Sync + 'static) -> impl Fn() -> usize {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let memo = ArcMemo::new({
+            let runs = Arc::clone(&runs);
+            move |_| {
+                read();
+                runs.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        move || {
+            memo.get_untracked();
+            runs.load(Ordering::SeqCst)
+        }
+    }
+
+    #[test]
+    fn a_param_only_notifies_when_its_own_value_changes() {
+        let owner = Owner::new();
+        owner.set();
+        let route_params = ArcRwSignal::new(params("1", "2"));
+        provide_context(ArcMemo::new({
+            let route_params = route_params.clone();
+            move |_| route_params.get()
+        }));
+
+        let org_id = use_param("org_id");
+        let org = use_params::<Org>();
+        let org_id_runs = runs_of(move || _ = org_id.get());
+        let org_runs = runs_of(move || org.with(|_| ()));
+        assert_eq!((org_id_runs(), org_runs()), (1, 1));
+
+        // from /org/1/project/2 to /org/1/project/3
+        route_params.set(params("1", "3"));
+        assert_eq!((org_id_runs(), org_runs()), (1, 1));
+
+        route_params.set(params("4", "3"));
+        assert_eq!((org_id_runs(), org_runs()), (2, 2));
+        assert_eq!(org_id.get_untracked().as_deref(), Some("4"));
+        assert_eq!(use_param("team_id").get_untracked(), None);
+    }
+}
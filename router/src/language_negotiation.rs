@@ -0,0 +1,323 @@
+use crate::MatchRequest;
+use reactive_graph::owner::use_context;
+use std::{fmt, time::Duration};
+
+/// How a route negotiates the language of its responses, set with
+/// [`language_negotiation`](crate::NestedRoute::language_negotiation).
+///
+/// The path of the route does not include the language. The server integrations
+/// choose one of the `supported` languages for each request, from the language
+/// cookie, which remembers the language of a returning visitor, or else from
+/// the quality values of its `Accept-Language` header. They send it in the
+/// `Content-Language` header of the response, and store it in the cookie if the
+/// request did not send it yet, for a year unless
+/// [`cookie_max_age`](Self::cookie_max_age) sets another lifetime.
+///
+/// ```rust
+/// use leptos_router::LanguageNegotiation;
+///
+/// let negotiation = LanguageNegotiation::new(&["en", "fr", "pt-BR"], "en");
+/// assert_eq!(negotiation.negotiate(Some("de, fr-CA;q=0.8, pt;q=0.5")), "fr");
+///
+/// // a visitor who has no cookie yet is sent one
+/// assert_eq!(
+///     negotiation.select(None, Some("pt-BR")),
+///     (
+///         "pt-BR",
+///         Some(
+///             "lang=pt-BR; Path=/; Max-Age=31536000; SameSite=Lax"
+///                 .to_string()
+///         )
+///     )
+/// );
+/// // and then keeps that language, whatever their browser prefers
+/// assert_eq!(
+///     negotiation.select(Some("theme=dark; lang=pt-BR"), Some("fr")),
+///     ("pt-BR", None)
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LanguageNegotiation {
+    supported: &'static [&'static str],
+    fallback: &'static str,
+    cookie: &'static str,
+    cookie_max_age: Duration,
+}
+
+impl LanguageNegotiation {
+    /// Supports the given language tags, like `en` or `pt-BR`, choosing
+    /// `fallback` if none of them fits the languages a visitor prefers.
+    pub const fn new(
+        supported: &'static [&'static str],
+        fallback: &'static str,
+    ) -> Self {
+        Self {
+            supported,
+            fallback,
+            cookie: "lang",
+            cookie_max_age: Duration::from_secs(365 * 24 * 60 * 60),
+        }
+    }
+
+    /// Sets the name of the cookie that stores the language of a visitor, which
+    /// is `lang` by default.
+    pub const fn cookie(mut self, name: &'static str) -> Self {
+        self.cookie = name;
+        self
+    }
+
+    /// Sets how long the cookie that stores the language of a visitor is kept,
+    /// which is a year by default. It is sent in whole seconds, in its
+    /// `Max-Age` attribute.
+    pub const fn cookie_max_age(mut self, max_age: Duration) -> Self {
+        self.cookie_max_age = max_age;
+        self
+    }
+
+    /// The supported languages.
+    pub fn supported(&self) -> &'static [&'static str] {
+        self.supported
+    }
+
+    /// The language for visitors who prefer none of the supported ones.
+    pub fn fallback(&self) -> &'static str {
+        self.fallback
+    }
+
+    /// The name of the cookie that stores the language of a visitor.
+    pub fn cookie_name(&self) -> &'static str {
+        self.cookie
+    }
+
+    /// The supported language that best fits an `Accept-Language` header, by
+    /// the quality values of its languages, or the fallback.
+    ///
+    /// A language matches a supported one exactly or by its primary language
+    /// subtag, so that `fr-CA` matches `fr`, and `en` matches `en-US`, if no
+    /// supported language matches it exactly. Tags are compared without case,
+    /// as they are in RFC 5646.
+    pub fn negotiate(&self, accept_language: Option<&str>) -> &'static str {
+        let supported = self.supported.iter().copied();
+        negotiate_language(supported, self.fallback, accept_language)
+    }
+
+    /// The language stored in the language cookie of a `Cookie` header, if it
+    /// is supported.
+    pub fn from_cookie(
+        &self,
+        cookie_header: Option<&str>,
+    ) -> Option<&'static str> {
+        cookie_header?.split(';').find_map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+            if name.trim() != self.cookie {
+                return None;
+            }
+            self.supported
+                .iter()
+                .find(|language| language.eq_ignore_ascii_case(value.trim()))
+                .copied()
+        })
+    }
+
+    /// The language of the response to a request with these headers, and the
+    /// value of a `Set-Cookie` header that stores it, if the request did not
+    /// send it in its cookie.
+    pub fn select(
+        &self,
+        cookie_header: Option<&str>,
+        accept_language: Option<&str>,
+    ) -> (&'static str, Option<String>) {
+        match self.from_cookie(cookie_header) {
+            Some(language) => (language, None),
+            None => {
+                let language = self.negotiate(accept_language);
+                let set_cookie = format!(
+                    "{}={language}; Path=/; Max-Age={}; SameSite=Lax",
+                    self.cookie,
+                    self.cookie_max_age.as_secs()
+                );
+                (language, Some(set_cookie))
+            }
+        }
+    }
+
+    /// The language of a route matched for `req`.
+    ///
+    /// A request that is only known by its path has the language that the
+    /// server integrations negotiated for the response being rendered, or,
+    /// during client-side navigation, the one in the cookie of the document,
+    /// which the server set.
+    pub(crate) fn language(&self, req: &MatchRequest<'_>) -> &'static str {
+        if let Some(language) = self.from_cookie(req.as_cookies()) {
+            return language;
+        }
+        let accept_language = req.as_accept_language();
+        if req.as_cookies().is_none() && accept_language.is_none() {
+            let negotiated = use_context::<NegotiatedLanguage>().and_then(
+                |NegotiatedLanguage(negotiated)| {
+                    self.supported
+                        .iter()
+                        .find(|language| **language == negotiated)
+                        .copied()
+                },
+            );
+            if let Some(language) = negotiated
+                .or_else(|| self.from_cookie(document_cookie().as_deref()))
+            {
+                return language;
+            }
+        }
+        self.negotiate(accept_language)
+    }
+}
+
+/// The language of `languages` that best fits an `Accept-Language` header, by
+/// the weights of its languages, or `default`.
+pub(crate) fn negotiate_language<'a, I>(
+    languages: I,
+    default: &'a str,
+    accept_language: Option<&str>,
+) -> &'a str
+where
+    I: Iterator<Item = &'a str> + Clone,
+{
+    let mut preferred = accept_language
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|language| {
+            let mut parts = language.split(';');
+            let tag = parts.next()?.trim();
+            let weight = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+            (!tag.is_empty() && weight > 0.0).then_some((tag, weight))
+        })
+        .collect::<Vec<_>>();
+    // the sort is stable, so languages with the same weight keep their order
+    preferred.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    let primary = |tag: &str| tag.split('-').next().unwrap_or(tag).to_owned();
+    preferred
+        .into_iter()
+        .find_map(|(tag, _)| {
+            if tag == "*" {
+                return Some(default);
+            }
+            languages
+                .clone()
+                .find(|language| language.eq_ignore_ascii_case(tag))
+                .or_else(|| {
+                    let tag = primary(tag);
+                    languages.clone().find(|language| {
+                        primary(language).eq_ignore_ascii_case(&tag)
+                    })
+                })
+        })
+        .unwrap_or(default)
+}
+
+/// The cookies of the document, if this is running in the browser.
+fn document_cookie() -> Option<String> {
+    use leptos::leptos_dom::helpers::document;
+    use reactive_graph::owner::Owner;
+
+    let in_browser = cfg!(target_arch = "wasm32")
+        && Owner::current_shared_context()
+            .map(|sc| sc.is_browser())
+            .unwrap_or(true);
+    if !in_browser {
+        return None;
+    }
+    js_sys::Reflect::get(&document(), &"cookie".into())
+        .ok()?
+        .as_string()
+}
+
+/// The language negotiated for a route with
+/// [`NestedRoute::language_negotiation`](crate::NestedRoute::language_negotiation),
+/// which is provided via context to its view and to all of its nested routes.
+///
+/// The server integrations also provide the language they negotiated for a
+/// request while its response is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NegotiatedLanguage(pub &'static str);
+
+impl NegotiatedLanguage {
+    /// The language tag, like `pt-BR`.
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl fmt::Display for NegotiatedLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LanguageNegotiation;
+    use crate::MatchRequest;
+    use std::time::Duration;
+
+    #[test]
+    fn the_best_supported_language_is_negotiated() {
+        let negotiation =
+            LanguageNegotiation::new(&["fr", "en-US", "pt-BR"], "en-US");
+        assert_eq!(negotiation.negotiate(None), "en-US");
+        assert_eq!(
+            negotiation.negotiate(Some("de, pt-BR;q=0.5, fr;q=0.8")),
+            "fr"
+        );
+        assert_eq!(negotiation.negotiate(Some("fr-CA")), "fr");
+        assert_eq!(
+            negotiation.negotiate(Some("pt;q=0.9, en-GB;q=0.9")),
+            "pt-BR"
+        );
+        assert_eq!(negotiation.negotiate(Some("ja, *;q=0.1")), "en-US");
+        assert_eq!(negotiation.negotiate(Some("fr;q=0")), "en-US");
+    }
+
+    #[test]
+    fn the_cookie_is_preferred_over_the_accept_language_header() {
+        let negotiation =
+            LanguageNegotiation::new(&["en-US", "fr"], "en-US").cookie("l");
+        let language = |cookies: Option<&str>, accept_language: &str| {
+            let mut req =
+                MatchRequest::new("/").accept_language(accept_language);
+            if let Some(cookies) = cookies {
+                req = req.cookies(cookies);
+            }
+            negotiation.language(&req)
+        };
+        assert_eq!(language(None, "fr;q=0.4, en;q=0.9"), "en-US");
+        assert_eq!(language(Some("l=fr"), "en"), "fr");
+        // an unsupported language in the cookie is negotiated again
+        assert_eq!(language(Some("l=de"), "fr"), "fr");
+        assert_eq!(language(Some("lang=fr"), "ja, *;q=0.5"), "en-US");
+        assert_eq!(
+            negotiation.select(Some("l=FR"), None),
+            ("fr", None),
+            "tags are compared without case"
+        );
+    }
+
+    #[test]
+    fn the_language_cookie_expires() {
+        let negotiation = LanguageNegotiation::new(&["en", "fr"], "en");
+        assert_eq!(
+            negotiation.select(None, Some("fr")).1.as_deref(),
+            Some("lang=fr; Path=/; Max-Age=31536000; SameSite=Lax")
+        );
+        assert_eq!(
+            negotiation
+                .cookie("locale")
+                .cookie_max_age(Duration::from_secs(30 * 24 * 60 * 60))
+                .select(Some("locale=de"), None)
+                .1
+                .as_deref(),
+            Some("locale=en; Path=/; Max-Age=2592000; SameSite=Lax")
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{language_negotiation::negotiate_language, SeparatorConfig};
+use std::{borrow::Cow, sync::Arc};
+
+/// How a route redirects visitors to the version of it in their preferred
+/// language, set with
+/// [`NestedRoute::language_redirect`](crate::NestedRoute::language_redirect).
+///
+/// The route, and each of its children, can be visited with one of the
+/// `locales` as the first segment of its path, like `/fr/about` for `/about`. A
+/// request to the path without a locale is redirected to the locale from the
+/// locale cookie, which remembers the choice of a returning visitor, or else to
+/// the supported locale that best fits their `Accept-Language` header, which is
+/// then stored in the cookie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageRedirectConfig {
+    locales: Vec<Cow<'static, str>>,
+    default: Cow<'static, str>,
+    preserve_method: bool,
+    cookie: Cow<'static, str>,
+}
+
+impl LanguageRedirectConfig {
+    /// Supports the given locales, like `en` or `pt-BR`, redirecting to
+    /// `default` if none of them fits the languages a visitor prefers.
+    pub fn new<L>(
+        locales: impl IntoIterator<Item = L>,
+        default: impl Into<Cow<'static, str>>,
+    ) -> Self
+    where
+        L: Into<Cow<'static, str>>,
+    {
+        let default = default.into();
+        let mut locales =
+            locales.into_iter().map(Into::into).collect::<Vec<_>>();
+        if !locales.contains(&default) {
+            locales.push(default.clone());
+        }
+        Self {
+            locales,
+            default,
+            preserve_method: false,
+            cookie: Cow::Borrowed("locale"),
+        }
+    }
+
+    /// Redirects with `307 Temporary Redirect`, which keeps the method and body
+    /// of the request, rather than with `302 Found`.
+    pub fn preserve_method(mut self, preserve_method: bool) -> Self {
+        self.preserve_method = preserve_method;
+        self
+    }
+
+    /// Sets the name of the cookie that stores the locale of a visitor, which
+    /// is `locale` by default.
+    pub fn cookie(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.cookie = name.into();
+        self
+    }
+
+    /// The supported locales.
+    pub fn locales(&self) -> &[Cow<'static, str>] {
+        &self.locales
+    }
+
+    /// The locale for visitors who prefer none of the supported ones.
+    pub fn default_locale(&self) -> &str {
+        &self.default
+    }
+
+    /// The status code of the redirects.
+    pub fn status(&self) -> u16 {
+        if self.preserve_method {
+            307
+        } else {
+            302
+        }
+    }
+
+    /// The name of the cookie that stores the locale of a visitor.
+    pub fn cookie_name(&self) -> &str {
+        &self.cookie
+    }
+
+    /// The supported locale that best fits an `Accept-Language` header, by the
+    /// weights of its languages, or the default locale.
+    ///
+    /// A language matches a locale exactly or by its primary language, so that
+    /// `fr-CA` matches `fr`, and `en` matches `en-US`, if no locale matches it
+    /// exactly.
+    pub fn negotiate(&self, accept_language: Option<&str>) -> &str {
+        negotiate_language(
+            self.locales.iter().map(AsRef::as_ref),
+            &self.default,
+            accept_language,
+        )
+    }
+
+    /// Splits a supported locale off the start of `path`, returning the part
+    /// with the locale, like `/fr`, and the rest of the path.
+    pub(crate) fn strip_locale<'a>(
+        &self,
+        path: &'a str,
+        separator: SeparatorConfig,
+    ) -> Option<(&'a str, &'a str)> {
+        let separator = separator.separator();
+        let rest = path.strip_prefix(separator)?;
+        let end = rest.find(separator).unwrap_or(rest.len());
+        let locale = &rest[..end];
+        self.locales
+            .iter()
+            .any(|supported| supported == locale)
+            .then(|| path.split_at(separator.len_utf8() + end))
+    }
+}
+
+/// The [`LanguageRedirectConfig`] of a route that is generated without a locale
+/// in its path, which the server integrations answer with a redirect to the
+/// same path with a locale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageRedirect {
+    /// How to choose the locale, which is shared with the route that set it.
+    pub config: Arc<LanguageRedirectConfig>,
+    /// How many segments of the path come before the locale, which are those of
+    /// the ancestors of the route that set the config.
+    pub depth: usize,
+}
+
+impl LanguageRedirect {
+    /// The response to a request to `path` (and its `query`): the URL to
+    /// redirect to, and the value of a `Set-Cookie` header that stores the
+    /// chosen locale, if the request did not send a supported one in its
+    /// `Cookie` header yet.
+    pub fn redirect(
+        &self,
+        path: &str,
+        query: Option<&str>,
+        cookie_header: Option<&str>,
+        accept_language: Option<&str>,
+    ) -> (String, Option<String>) {
+        let from_cookie = cookie_header.and_then(|cookies| {
+            cookies.split(';').find_map(|pair| {
+                let (name, value) = pair.split_once('=')?;
+                (name.trim() == self.config.cookie_name())
+                    .then(|| value.trim())
+                    .filter(|value| {
+                        self.config.locales.iter().any(|locale| locale == value)
+                    })
+            })
+        });
+        let locale = from_cookie
+            .unwrap_or_else(|| self.config.negotiate(accept_language));
+
+        let mut segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+        let trailing_slash = !segments.is_empty() && path.ends_with('/');
+        segments.insert(self.depth.min(segments.len()), locale);
+        let mut location = format!("/{}", segments.join("/"));
+        if trailing_slash {
+            location.push('/');
+        }
+        if let Some(query) = query.filter(|query| !query.is_empty()) {
+            location.push('?');
+            location.push_str(query);
+        }
+
+        let set_cookie = from_cookie.is_none().then(|| {
+            format!(
+                "{}={locale}; Path=/; SameSite=Lax",
+                self.config.cookie_name()
+            )
+        });
+        (location, set_cookie)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LanguageRedirect, LanguageRedirectConfig};
+    use std::sync::Arc;
+
+    #[test]
+    fn the_best_supported_locale_is_chosen() {
+        let config =
+            LanguageRedirectConfig::new(["fr", "en-US", "pt-BR"], "en-US");
+        assert_eq!(config.negotiate(None), "en-US");
+        assert_eq!(config.negotiate(Some("de, pt-BR;q=0.5, fr;q=0.8")), "fr");
+        assert_eq!(config.negotiate(Some("fr-CA")), "fr");
+        assert_eq!(config.negotiate(Some("pt;q=0.9, en-GB;q=0.9")), "pt-BR");
+        assert_eq!(config.negotiate(Some("ja, *;q=0.1")), "en-US");
+        assert_eq!(config.negotiate(Some("fr;q=0")), "en-US");
+        assert_eq!(config.status(), 302);
+        assert_eq!(config.preserve_method(true).status(), 307);
+    }
+
+    #[test]
+    fn redirects_insert_the_locale_after_the_ancestors() {
+        let redirect = LanguageRedirect {
+            config: Arc::new(LanguageRedirectConfig::new(["en", "fr"], "en")),
+            depth: 1,
+        };
+        assert_eq!(
+            redirect.redirect("/docs/intro/", Some("a=1"), None, Some("fr")),
+            (
+                "/docs/fr/intro/?a=1".to_string(),
+                Some("locale=fr; Path=/; SameSite=Lax".to_string())
+            )
+        );
+        assert_eq!(
+            redirect.redirect(
+                "/docs",
+                None,
+                Some("theme=dark; locale=en"),
+                Some("fr")
+            ),
+            ("/docs/en".to_string(), None)
+        );
+        let root = LanguageRedirect {
+            depth: 0,
+            ..redirect
+        };
+        assert_eq!(root.redirect("/", None, Some("locale=de"), None).0, "/en");
+    }
+}
@@ -123,36 +123,111 @@
 #![deny(missing_docs)]
 #![cfg_attr(all(feature = "nightly", rustc_nightly), feature(auto_traits))]
 #![cfg_attr(all(feature = "nightly", rustc_nightly), feature(negative_impls))]
+#![cfg_attr(all(feature = "nightly", rustc_nightly), feature(allocator_api))]
 
+mod access_log;
+mod api_key;
+mod batch;
+mod body_schema;
+mod canonical;
+mod client_cert;
+mod client_hints;
 /// Components for route definition and for enhanced links and forms.
 pub mod components;
+mod consumes;
+mod deduplication;
+mod document_base;
+mod envelope;
+mod error_format;
 /// An optimized "flat" router without nested routes.
 pub mod flat_router;
 mod form;
 mod generate_route_list;
+mod guard;
+mod head;
 /// Hooks that can be used to access router state inside your components.
 pub mod hooks;
+mod language_negotiation;
+mod language_redirect;
 mod link;
 /// Utilities for accessing the current location.
 pub mod location;
+#[cfg(feature = "manifest")]
+pub mod manifest;
 mod matching;
 mod method;
+mod middleware;
+mod minify;
 mod navigate;
 /// A nested router that supports multiple levels of route definitions.
 pub mod nested_router;
+mod not_found;
+#[cfg(feature = "openapi")]
+mod openapi;
+mod pagination;
 /// Support for maps of parameters in the path or in the query.
 pub mod params;
+mod passthrough;
+mod preload;
+mod query_schema;
+mod region;
+mod response_schema;
+mod response_signing;
+mod services;
+mod shutdown;
+mod slow_request;
 mod ssr_mode;
 /// Support for static routing.
 pub mod static_routes;
+mod sticky_session;
+mod surrogate_key;
+mod tenant;
+mod webhook;
+mod websocket;
+mod x_robots_tag;
 
+pub use access_log::*;
+pub use api_key::*;
+pub use batch::*;
+pub use body_schema::*;
+pub use canonical::*;
+pub use client_cert::*;
+pub use client_hints::*;
+pub use consumes::*;
+pub use deduplication::*;
+pub use document_base::*;
+pub use envelope::*;
+pub use error_format::*;
 pub use generate_route_list::*;
+pub use guard::*;
+pub use head::*;
+pub use language_negotiation::*;
+pub use language_redirect::*;
 #[doc(inline)]
 pub use leptos_router_macro::path;
 pub use matching::*;
 pub use method::*;
+pub use middleware::*;
+pub use minify::*;
 pub use navigate::*;
+pub use not_found::*;
+pub use pagination::*;
+pub use passthrough::*;
+pub use preload::*;
+pub use query_schema::*;
+pub use region::*;
+pub use response_schema::*;
+pub use response_signing::*;
+pub use services::*;
+pub use shutdown::*;
+pub use slow_request::*;
 pub use ssr_mode::*;
+pub use sticky_session::*;
+pub use surrogate_key::*;
+pub use tenant::*;
+pub use webhook::*;
+pub use websocket::*;
+pub use x_robots_tag::*;
 
 pub(crate) mod view_transition {
     use js_sys::{Function, Promise, Reflect};
@@ -1,5 +1,12 @@
 use super::{handle_anchor_click, LocationChange, LocationProvider, Url};
-use crate::{hooks::use_navigate, params::ParamsMap};
+use crate::{
+    components::RouterContext,
+    document_base::{redirect_location, DocumentBase},
+    hooks::use_navigate,
+    params::ParamsMap,
+    FocusBehavior,
+};
+use any_spawner::Executor;
 use core::fmt;
 use futures::channel::oneshot;
 use js_sys::{try_iter, Array, JsString};
@@ -17,7 +24,7 @@ use std::{
 };
 use tachys::dom::{document, window};
 use wasm_bindgen::{closure::Closure, JsCast, JsValue};
-use web_sys::{Event, UrlSearchParams};
+use web_sys::{Event, FocusOptions, HtmlElement, UrlSearchParams};
 
 #[derive(Clone)]
 pub struct BrowserUrl {
@@ -25,6 +32,8 @@ pub struct BrowserUrl {
     pub(crate) pending_navigation: Arc<Mutex<Option<oneshot::Sender<()>>>>,
     pub(crate) path_stack: ArcStoredValue<Vec<Url>>,
     pub(crate) is_back: ArcRwSignal<bool>,
+    pub(crate) focus_behavior: ArcStoredValue<FocusBehavior>,
+    pub(crate) pending_suspense: PendingSuspense,
 }
 
 impl fmt::Debug for BrowserUrl {
@@ -54,6 +63,61 @@ impl BrowserUrl {
             window().scroll_to_with_x_and_y(0.0, 0.0);
         }
     }
+
+    /// Moves focus into the content of the new route, once any `<Suspense/>` in
+    /// it has loaded.
+    fn focus_new_route(&self, behavior: FocusBehavior) {
+        let pending_suspense = self.pending_suspense.clone();
+        Executor::spawn_local(async move {
+            // let the new route render before checking whether it is suspended
+            Executor::tick().await;
+            pending_suspense.ready().await;
+            request_animation_frame(move || focus_element(&behavior));
+        });
+    }
+}
+
+/// Where to move focus after navigating from `prev` to `url`, if anywhere, with
+/// the `focus` of the navigation, or else the `default` of the router.
+///
+/// Focus is not moved on changes to the query or hash, where the content of the
+/// route stays.
+fn focus_after_navigation(
+    prev: Option<&Url>,
+    url: &Url,
+    focus: Option<&FocusBehavior>,
+    default: &FocusBehavior,
+) -> Option<FocusBehavior> {
+    let path_changed = prev.map_or(true, |prev| prev.path() != url.path());
+    let behavior = focus.unwrap_or(default);
+    (path_changed && *behavior != FocusBehavior::Preserve)
+        .then(|| behavior.clone())
+}
+
+fn focus_element(behavior: &FocusBehavior) {
+    let document = document();
+    let query =
+        |selector: &str| document.query_selector(selector).ok().flatten();
+    let target = match behavior {
+        FocusBehavior::Auto => query("[autofocus], [data-router-focus]"),
+        FocusBehavior::Selector(selector) => query(selector),
+        FocusBehavior::Preserve => return,
+    }
+    .or_else(|| query("main, [role=main]"))
+    .or_else(|| document.body().map(Into::into));
+    let Some(target) = target.and_then(|el| el.dyn_into::<HtmlElement>().ok())
+    else {
+        return;
+    };
+
+    // elements that can't otherwise be focused, like <main>, need a tabindex
+    if target.tab_index() < 0 && !target.has_attribute("tabindex") {
+        target.set_tab_index(-1);
+    }
+    let options = FocusOptions::new();
+    // the router has already scrolled as needed
+    options.set_prevent_scroll(true);
+    _ = target.focus_with_options(&options);
 }
 
 impl LocationProvider for BrowserUrl {
@@ -69,6 +133,8 @@ impl LocationProvider for BrowserUrl {
             pending_navigation: Default::default(),
             path_stack,
             is_back: Default::default(),
+            focus_behavior: Default::default(),
+            pending_suspense: Default::default(),
         })
     }
 
@@ -240,8 +306,20 @@ impl LocationProvider for BrowserUrl {
         // add this URL to the "path stack" for detecting back navigations, and
         // unset "navigating back" state
         if let Ok(url) = Self::current() {
-            self.path_stack.write_value().push(url);
+            let mut path_stack = self.path_stack.write_value();
+            let focus = focus_after_navigation(
+                path_stack.last(),
+                &url,
+                loc.focus.as_ref(),
+                &self.focus_behavior.read_value(),
+            );
+            path_stack.push(url);
+            drop(path_stack);
             self.is_back.set(false);
+
+            if let Some(focus) = focus {
+                self.focus_new_route(focus);
+            }
         }
 
         // scroll to el
@@ -250,7 +328,13 @@ impl LocationProvider for BrowserUrl {
 
     fn redirect(loc: &str) {
         let navigate = use_navigate();
-        let Some(url) = resolve_redirect_url(loc) else {
+        let (router_base, document_base) = use_context::<RouterContext>()
+            .map(|router| {
+                (router.base.unwrap_or_default(), router.document_base)
+            })
+            .unwrap_or_default();
+        let loc = redirect_location(document_base, &router_base, loc);
+        let Some(url) = resolve_redirect_url(&loc, document_base) else {
             return; // resolve_redirect_url() already logs an error
         };
         let current_origin = location().origin().unwrap();
@@ -290,18 +374,24 @@ fn search_params_from_web_url(
 }
 
 /// Resolves a redirect location to an (absolute) URL.
-pub(crate) fn resolve_redirect_url(loc: &str) -> Option<web_sys::Url> {
-    let origin = match window().location().origin() {
-        Ok(origin) => origin,
+pub(crate) fn resolve_redirect_url(
+    loc: &str,
+    document_base: DocumentBase,
+) -> Option<web_sys::Url> {
+    let base = match document_base {
+        DocumentBase::Ignore => window().location().origin(),
+        DocumentBase::Respect => {
+            document().base_uri().map(Option::unwrap_or_default)
+        }
+    };
+    let base = match base {
+        Ok(base) => base,
         Err(e) => {
-            leptos::logging::error!("Failed to get origin: {:#?}", e);
+            leptos::logging::error!("Failed to get base URL: {:#?}", e);
             return None;
         }
     };
 
-    // TODO: Use server function's URL as base instead.
-    let base = origin;
-
     match web_sys::Url::new_with_base(loc, &base) {
         Ok(url) => Some(url),
         Err(e) => {
@@ -313,3 +403,95 @@ pub(crate) fn resolve_redirect_url(loc: &str) -> Option<web_sys::Url> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::focus_after_navigation;
+    use crate::{location::Url, FocusBehavior};
+
+    fn url(path: &str, search: &str, hash: &str) -> Url {
+        Url {
+            origin: "https://leptos.dev".into(),
+            path: path.into(),
+            search: search.into(),
+            hash: hash.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn focus_moves_only_when_the_path_changes() {
+        let docs = url("/docs", "", "");
+        let auto = FocusBehavior::Auto;
+        assert_eq!(
+            focus_after_navigation(
+                Some(&docs),
+                &url("/api", "", ""),
+                None,
+                &auto
+            ),
+            Some(FocusBehavior::Auto)
+        );
+        assert_eq!(
+            focus_after_navigation(None, &docs, None, &auto),
+            Some(FocusBehavior::Auto)
+        );
+        assert_eq!(
+            focus_after_navigation(
+                Some(&docs),
+                &url("/docs", "v=2", ""),
+                None,
+                &auto
+            ),
+            None,
+            "focus stays on query-only changes"
+        );
+        assert_eq!(
+            focus_after_navigation(
+                Some(&docs),
+                &url("/docs", "", "#usage"),
+                None,
+                &auto
+            ),
+            None,
+            "focus stays on hash-only changes"
+        );
+    }
+
+    #[test]
+    fn the_focus_of_a_navigation_overrides_the_router() {
+        let (docs, api) = (url("/docs", "", ""), url("/api", "", ""));
+        let heading = FocusBehavior::Selector("h1".into());
+        assert_eq!(
+            focus_after_navigation(
+                Some(&docs),
+                &api,
+                Some(&heading),
+                &FocusBehavior::Auto
+            ),
+            Some(heading.clone())
+        );
+        assert_eq!(
+            focus_after_navigation(Some(&docs), &api, None, &heading),
+            Some(heading.clone())
+        );
+        assert_eq!(
+            focus_after_navigation(
+                Some(&docs),
+                &api,
+                Some(&FocusBehavior::Preserve),
+                &heading
+            ),
+            None
+        );
+        assert_eq!(
+            focus_after_navigation(
+                Some(&docs),
+                &api,
+                None,
+                &FocusBehavior::Preserve
+            ),
+            None
+        );
+    }
+}
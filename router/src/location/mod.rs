@@ -18,7 +18,7 @@ use web_sys::{Event, HtmlAnchorElement, MouseEvent};
 
 mod history;
 mod server;
-use crate::params::ParamsMap;
+use crate::{params::ParamsMap, FocusBehavior};
 pub use history::*;
 pub use server::*;
 
@@ -84,6 +84,20 @@ impl Url {
         }
     }
 
+    /// Whether going from this URL to `other` only changes the hash, like
+    /// following an `<a href="#section">` or an `<a href="#">` that clears the
+    /// hash.
+    ///
+    /// This is a same-document navigation: the routes stay as they are, without
+    /// being matched again, and only the URL they see, the history and the
+    /// scroll position are updated.
+    pub(crate) fn differs_only_in_hash(&self, other: &Url) -> bool {
+        self.hash != other.hash
+            && self.origin == other.origin
+            && self.path == other.path
+            && self.search == other.search
+    }
+
     pub(crate) fn to_full_path(&self) -> String {
         let mut path = self.path.to_string();
         if !self.search.is_empty() {
@@ -198,6 +212,9 @@ pub struct LocationChange {
     pub scroll: bool,
     /// The [`state`](https://developer.mozilla.org/en-US/docs/Web/API/History/state) that will be added during navigation.
     pub state: State,
+    /// Where to move focus at the end of the navigation, overriding the
+    /// router's default.
+    pub focus: Option<FocusBehavior>,
 }
 
 impl Default for LocationChange {
@@ -207,6 +224,7 @@ impl Default for LocationChange {
             replace: true,
             scroll: true,
             state: Default::default(),
+            focus: None,
         }
     }
 }
@@ -370,6 +388,7 @@ where
                 scroll: !a.has_attribute("noscroll")
                     && !a.has_attribute("data-noscroll"),
                 state: State::new(state),
+                focus: None,
             };
 
             Executor::spawn_local(navigate(url, change));
@@ -378,3 +397,41 @@ where
         Ok(())
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Url;
+
+    fn url(path: &str, search: &str, hash: &str) -> Url {
+        Url {
+            origin: "https://leptos.dev".into(),
+            path: path.into(),
+            search: search.into(),
+            hash: hash.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn anchor_links_are_same_document_navigations() {
+        let intro = url("/docs", "", "#intro");
+        assert!(intro.differs_only_in_hash(&url("/docs", "", "#usage")));
+        // the same hash is not a change at all
+        assert!(!intro.differs_only_in_hash(&intro.clone()));
+        // a hash change on a different path, or with a different query, is a
+        // normal navigation
+        assert!(!intro.differs_only_in_hash(&url("/api", "", "#usage")));
+        assert!(!intro.differs_only_in_hash(&url("/docs", "v=2", "#usage")));
+    }
+
+    #[test]
+    fn empty_hash_clears_the_hash() {
+        // the browser parses `href="#"` with an empty hash
+        let from_empty_anchor = url("/docs", "", "");
+        assert!(
+            url("/docs", "", "#intro").differs_only_in_hash(&from_empty_anchor)
+        );
+        assert!(!url("/docs", "", "").differs_only_in_hash(&from_empty_anchor));
+        assert_eq!(from_empty_anchor.to_full_path(), "/docs");
+    }
+}
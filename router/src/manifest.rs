@@ -0,0 +1,311 @@
+//! Exports the routes of an application as a [`RouteManifest`], which can be
+//! serialized to JSON for tooling outside the application, like generating CDN
+//! rules or edge function configs.
+//!
+//! ```rust
+//! use leptos_router::{
+//!     manifest, NestedRoute, ParamSegment, RouteDefs, StaticSegment,
+//! };
+//!
+//! let routes = RouteDefs::<_>::new((
+//!     NestedRoute::new(StaticSegment("about"), || ()),
+//!     NestedRoute::new((StaticSegment("users"), ParamSegment("id")), || ()),
+//! ));
+//! let manifest = manifest::export(&routes);
+//! assert_eq!(manifest.routes[1].path, "/users/:id");
+//! println!("{}", manifest.to_json());
+//! ```
+//!
+//! The JSON format is versioned by [`RouteManifest::schema_version`], which
+//! changes whenever a field is removed or changes meaning.
+
+use crate::{
+    matching::{
+        normalize_segments, segments_to_pattern,
+        structure_hash::{ssr_mode_name, Fnv1a},
+    },
+    GeneratedRouteData, MatchNestedRoutes, PathSegment, RouteDefs, SsrMode,
+};
+use serde::Serialize;
+use std::hash::{Hash, Hasher};
+
+/// The version of the JSON format of [`RouteManifest`].
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A description of every route in an application.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RouteManifest {
+    /// The version of this format, currently [`SCHEMA_VERSION`].
+    pub schema_version: u32,
+    /// The base path of the router, which comes before the path of every route.
+    pub base: Option<String>,
+    /// The routes, in the order they are defined in.
+    pub routes: Vec<ManifestRoute>,
+}
+
+/// A single route in a [`RouteManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ManifestRoute {
+    /// An ID for this route, which stays the same across builds as long as its
+    /// path does.
+    pub id: String,
+    /// The path pattern of this route, like `/users/:id`, not including the
+    /// base.
+    pub path: String,
+    /// The segments of the path pattern.
+    pub segments: Vec<ManifestSegment>,
+    /// The HTTP methods this route accepts, in alphabetical order.
+    pub methods: Vec<String>,
+    /// The name of the route's [`SsrMode`], like `"OutOfOrder"` or `"Static"`.
+    pub ssr_mode: String,
+    /// The HTTP status code this route always responds with, if it is set with
+    /// [`NestedRoute::status`](crate::NestedRoute::status).
+    pub status: Option<u16>,
+    /// The params this route is prerendered with, if it is a static route and
+    /// the manifest was created with [`export_with_static_params`].
+    pub static_params: Option<Vec<(String, Vec<String>)>>,
+}
+
+/// A segment of a [`ManifestRoute`] path, serialized as
+/// `{ "kind": ..., "value": ... }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum ManifestSegment {
+    /// A segment that must match exactly.
+    Static(String),
+    /// A named param, like `:id`.
+    Param(String),
+    /// A named param that can be absent, like `:id?`.
+    OptionalParam(String),
+    /// A named wildcard that matches the rest of the path, like `*rest`.
+    Splat(String),
+}
+
+impl RouteManifest {
+    /// Serializes the manifest as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self)
+            .expect("route manifest can always be serialized")
+    }
+}
+
+/// Creates a manifest of the given routes.
+///
+/// This does not include the params of static routes, which are loaded
+/// asynchronously; use [`export_with_static_params`] for those.
+pub fn export<Children>(routes: &RouteDefs<Children>) -> RouteManifest
+where
+    Children: MatchNestedRoutes,
+{
+    let (base, generated) = routes.generate_routes();
+    RouteManifest {
+        schema_version: SCHEMA_VERSION,
+        base: base.map(String::from),
+        routes: generated.into_iter().map(manifest_route).collect(),
+    }
+}
+
+/// Creates a manifest of the given routes, including the params that each
+/// static route is prerendered with.
+pub async fn export_with_static_params<Children>(
+    routes: &RouteDefs<Children>,
+) -> RouteManifest
+where
+    Children: MatchNestedRoutes,
+{
+    let (base, generated) = routes.generate_routes();
+    let mut manifest_routes = Vec::new();
+    for route in generated {
+        let static_params = match &route.ssr_mode {
+            SsrMode::Static(static_route) => static_route
+                .to_prerendered_params()
+                .await
+                .map(|params| params.0),
+            _ => None,
+        };
+        manifest_routes.push(ManifestRoute {
+            static_params,
+            ..manifest_route(route)
+        });
+    }
+    RouteManifest {
+        schema_version: SCHEMA_VERSION,
+        base: base.map(String::from),
+        routes: manifest_routes,
+    }
+}
+
+fn manifest_route(route: GeneratedRouteData) -> ManifestRoute {
+    let path = segments_to_pattern(&route.segments);
+    let mut hasher = Fnv1a::default();
+    path.hash(&mut hasher);
+
+    let segments = normalize_segments(&route.segments)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            PathSegment::Unit => None,
+            PathSegment::Static(s) => Some(ManifestSegment::Static(s.into())),
+            PathSegment::Param(s) => Some(ManifestSegment::Param(s.into())),
+            PathSegment::OptionalParam(s) => {
+                Some(ManifestSegment::OptionalParam(s.into()))
+            }
+            PathSegment::Splat(s) => Some(ManifestSegment::Splat(s.into())),
+        })
+        .collect();
+    let mut methods = route
+        .methods
+        .iter()
+        .map(|method| method.as_str().to_string())
+        .collect::<Vec<_>>();
+    methods.sort();
+
+    ManifestRoute {
+        id: format!("{:016x}", hasher.finish()),
+        path,
+        segments,
+        methods,
+        ssr_mode: ssr_mode_name(&route.ssr_mode).to_string(),
+        status: route.status,
+        static_params: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::export;
+    use crate::{
+        static_routes::{StaticParamsMap, StaticRoute},
+        Method, NestedRoute, OptionalParamSegment, ParamSegment, RouteDefs,
+        SsrMode, StaticSegment,
+    };
+
+    fn routes() -> RouteDefs<impl crate::MatchNestedRoutes> {
+        RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment(""), || ()),
+            NestedRoute::new(
+                (StaticSegment("users"), ParamSegment("id")),
+                || (),
+            )
+            .custom_method(Method::Post.as_str().to_string())
+            .child(NestedRoute::new(OptionalParamSegment("tab"), || ())),
+            NestedRoute::new(StaticSegment("blog"), || ())
+                .ssr_mode(SsrMode::Static(StaticRoute::new().prerender_params(
+                    || async {
+                        let mut params = StaticParamsMap::new();
+                        params.insert("slug", vec!["hello".into()]);
+                        params
+                    },
+                )))
+                .child(NestedRoute::new(ParamSegment("slug"), || ())),
+            NestedRoute::not_found_route(|| ()),
+        ))
+    }
+
+    #[test]
+    fn manifest_json_is_stable() {
+        // external tooling depends on this format: if this test needs to
+        // change, so does `SCHEMA_VERSION`
+        assert_eq!(
+            export(&routes()).to_json(),
+            r#"{
+  "schema_version": 1,
+  "base": null,
+  "routes": [
+    {
+      "id": "07d5c907b49bccb3",
+      "path": "/",
+      "segments": [],
+      "methods": [
+        "GET"
+      ],
+      "ssr_mode": "OutOfOrder",
+      "status": null,
+      "static_params": null
+    },
+    {
+      "id": "adbe412b5262e658",
+      "path": "/users/:id/:tab?",
+      "segments": [
+        {
+          "kind": "static",
+          "value": "users"
+        },
+        {
+          "kind": "param",
+          "value": "id"
+        },
+        {
+          "kind": "optional_param",
+          "value": "tab"
+        }
+      ],
+      "methods": [
+        "GET",
+        "POST"
+      ],
+      "ssr_mode": "OutOfOrder",
+      "status": null,
+      "static_params": null
+    },
+    {
+      "id": "f9a4e69ff68ecb19",
+      "path": "/blog/:slug",
+      "segments": [
+        {
+          "kind": "static",
+          "value": "blog"
+        },
+        {
+          "kind": "param",
+          "value": "slug"
+        }
+      ],
+      "methods": [
+        "GET"
+      ],
+      "ssr_mode": "Static",
+      "status": null,
+      "static_params": null
+    },
+    {
+      "id": "6e59c6b77ed19b6b",
+      "path": "/*any",
+      "segments": [
+        {
+          "kind": "splat",
+          "value": "any"
+        }
+      ],
+      "methods": [
+        "GET"
+      ],
+      "ssr_mode": "OutOfOrder",
+      "status": 404,
+      "static_params": null
+    }
+  ]
+}"#
+        );
+    }
+
+    #[test]
+    fn static_params_are_exported() {
+        let manifest = futures::executor::block_on(
+            super::export_with_static_params(&routes()),
+        );
+        let blog = manifest
+            .routes
+            .iter()
+            .find(|route| route.path == "/blog/:slug")
+            .unwrap();
+        assert_eq!(
+            blog.static_params,
+            Some(vec![("slug".into(), vec!["hello".into()])])
+        );
+        assert!(manifest
+            .routes
+            .iter()
+            .filter(|route| route.path != "/blog/:slug")
+            .all(|route| route.static_params.is_none()));
+    }
+}
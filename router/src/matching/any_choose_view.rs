@@ -3,7 +3,9 @@ use futures::FutureExt;
 use std::{future::Future, pin::Pin};
 use tachys::{erased::Erased, view::any_view::AnyView};
 
-/// A type-erased [`ChooseView`].
+/// A type-erased [`ChooseView`], which allows views of different types to be
+/// stored together, as with the tenant views of a
+/// [multi-tenant route](crate::NestedRoute::multi_tenant).
 pub struct AnyChooseView {
     value: Erased,
     clone: fn(&Erased) -> AnyChooseView,
@@ -18,7 +20,8 @@ impl Clone for AnyChooseView {
 }
 
 impl AnyChooseView {
-    pub(crate) fn new<T: ChooseView>(value: T) -> Self {
+    /// Erases the type of a view.
+    pub fn new<T: ChooseView>(value: T) -> Self {
         fn clone<T: ChooseView>(value: &Erased) -> AnyChooseView {
             AnyChooseView::new(value.get_ref::<T>().clone())
         }
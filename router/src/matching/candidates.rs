@@ -0,0 +1,32 @@
+use super::PathSegment;
+use std::cmp::Reverse;
+
+/// How specific the path of a route is, so that of the routes a path matches,
+/// those that match fewer other paths sort first: a route without wildcards
+/// before one with them, then those with more static segments, then those with
+/// more params that must be present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Specificity {
+    splats: Reverse<usize>,
+    statics: usize,
+    params: usize,
+    optional_params: Reverse<usize>,
+}
+
+impl Specificity {
+    pub(crate) fn of(segments: &[PathSegment]) -> Self {
+        let count = |f: fn(&PathSegment) -> bool| {
+            segments.iter().filter(|segment| f(segment)).count()
+        };
+        Self {
+            splats: Reverse(count(|s| matches!(s, PathSegment::Splat(_)))),
+            statics: count(
+                |s| matches!(s, PathSegment::Static(s) if !s.is_empty()),
+            ),
+            params: count(|s| matches!(s, PathSegment::Param(_))),
+            optional_params: Reverse(count(|s| {
+                matches!(s, PathSegment::OptionalParam(_))
+            })),
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use either_of::*;
-use std::{future::Future, marker::PhantomData};
-use tachys::view::any_view::{AnyView, IntoAny};
+use leptos::{children::ViewFn, error::Error, suspense::Suspense, view};
+use std::{fmt, future::Future, marker::PhantomData, sync::Arc};
+use tachys::{
+    reactive_graph::Suspend,
+    view::any_view::{AnyView, IntoAny},
+};
 
 pub trait ChooseView
 where
@@ -67,6 +71,134 @@ impl<T> Default for Lazy<T> {
     }
 }
 
+/// A route view that has to be awaited, and may fail, like one that loads a
+/// module with a dynamic import before rendering it.
+///
+/// While the future is pending, the route renders its
+/// [`pending`](TryAsyncView::pending) view. If it resolves to an error, the
+/// error is thrown to the nearest `<ErrorBoundary/>`, which is the error view
+/// of the route, so there's no need to wrap each route in its own `<Suspense/>`
+/// and `<ErrorBoundary/>`.
+///
+/// The future can also await the view of a [`LazyRoute`], so that loading the
+/// code of the route and loading it with this share the one pending view.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::{NestedRoute, StaticSegment, TryAsyncView};
+/// async fn load_dashboard() -> Result<AnyView, ServerFnError> {
+///     // for example, import the module of the dashboard here
+/// #   Ok(().into_any())
+/// }
+///
+/// let route = NestedRoute::new(
+///     StaticSegment("dashboard"),
+///     TryAsyncView::new(load_dashboard)
+///         .pending(|| view! { <p>"Loading..."</p> }),
+/// );
+/// ```
+pub struct TryAsyncView<F> {
+    fun: F,
+    pending: ViewFn,
+}
+
+impl<F> TryAsyncView<F> {
+    /// Renders the view `fun` resolves to, once it has.
+    pub fn new(fun: F) -> Self {
+        Self {
+            fun,
+            pending: ViewFn::default(),
+        }
+    }
+
+    /// Sets the view that is rendered while the future is pending, which is
+    /// empty by default.
+    pub fn pending(mut self, pending: impl Into<ViewFn>) -> Self {
+        self.pending = pending.into();
+        self
+    }
+}
+
+impl<F: Clone> Clone for TryAsyncView<F> {
+    fn clone(&self) -> Self {
+        Self {
+            fun: self.fun.clone(),
+            pending: self.pending.clone(),
+        }
+    }
+}
+
+impl<F> fmt::Debug for TryAsyncView<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TryAsyncView").finish_non_exhaustive()
+    }
+}
+
+impl<F, Fut, View, E> ChooseView for TryAsyncView<F>
+where
+    F: Fn() -> Fut + Send + Clone + 'static,
+    Fut: Future<Output = Result<View, E>> + Send + 'static,
+    View: IntoAny,
+    E: Into<Error> + Send + 'static,
+{
+    async fn choose(self) -> AnyView {
+        let TryAsyncView { fun, pending } = self;
+        view! {
+            <Suspense fallback=move || pending.run()>
+                {move || {
+                    let view = fun();
+                    Suspend::new(async move {
+                        view.await.map(IntoAny::into_any).map_err(Into::into)
+                    })
+                }}
+            </Suspense>
+        }
+        .into_any()
+    }
+
+    async fn preload(&self) {}
+}
+
+/// A route view that is shared by every match of its route, created with
+/// [`NestedRoute::new_shared`](crate::NestedRoute::new_shared), so that
+/// matching the route bumps a reference count instead of cloning a view that is
+/// expensive to clone.
+///
+/// The view is only cloned when the route is rendered.
+pub struct SharedView<T>(Arc<T>);
+
+impl<T> SharedView<T> {
+    /// Shares `view`.
+    pub fn new(view: T) -> Self {
+        Self(Arc::new(view))
+    }
+}
+
+impl<T> Clone for SharedView<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> fmt::Debug for SharedView<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedView").finish_non_exhaustive()
+    }
+}
+
+impl<T> ChooseView for SharedView<T>
+where
+    T: ChooseView + Sync,
+{
+    async fn choose(self) -> AnyView {
+        Arc::unwrap_or_clone(self.0).choose().await
+    }
+
+    async fn preload(&self) {
+        self.0.preload().await
+    }
+}
+
 impl ChooseView for () {
     async fn choose(self) -> AnyView {
         ().into_any()
@@ -130,6 +262,22 @@ tuples!(EitherOf13 => A, B, C, D, E, F, G, H, I, J, K, L, M);
 tuples!(EitherOf14 => A, B, C, D, E, F, G, H, I, J, K, L, M, N);
 tuples!(EitherOf15 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
 tuples!(EitherOf16 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+tuples!(EitherOf17 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q);
+tuples!(EitherOf18 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R);
+tuples!(EitherOf19 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S);
+tuples!(EitherOf20 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T);
+tuples!(EitherOf21 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U);
+tuples!(EitherOf22 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V);
+tuples!(EitherOf23 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W);
+tuples!(EitherOf24 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X);
+tuples!(EitherOf25 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y);
+tuples!(EitherOf26 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
+tuples!(EitherOf27 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA);
+tuples!(EitherOf28 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB);
+tuples!(EitherOf29 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC);
+tuples!(EitherOf30 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD);
+tuples!(EitherOf31 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE);
+tuples!(EitherOf32 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF);
 
 /// A version of [`IntoMaybeErased`] for the [`ChooseView`] trait.
 pub trait IntoChooseViewMaybeErased {
@@ -161,3 +309,94 @@ where
         }
     }
 }
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{ChooseView, TryAsyncView};
+    use any_spawner::Executor;
+    use futures::{executor::block_on, StreamExt};
+    use leptos::{
+        error::{self, Error, ErrorHook, ErrorId},
+        prelude::RenderHtml,
+    };
+    use reactive_graph::owner::Owner;
+    use std::{
+        fmt,
+        sync::{Arc, Mutex},
+    };
+
+    #[derive(Debug)]
+    struct ImportFailed;
+
+    impl fmt::Display for ImportFailed {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("the module could not be imported")
+        }
+    }
+
+    impl std::error::Error for ImportFailed {}
+
+    /// Stands in for an `<ErrorBoundary/>`, keeping the errors that are thrown
+    /// to it.
+    #[derive(Default)]
+    struct Thrown(Mutex<Vec<String>>);
+
+    impl ErrorHook for Thrown {
+        fn throw(&self, error: Error) -> ErrorId {
+            self.0.lock().unwrap().push(error.to_string());
+            ErrorId::default()
+        }
+
+        fn clear(&self, _id: &ErrorId) {}
+    }
+
+    fn render(view: impl ChooseView) -> (String, Vec<String>) {
+        _ = Executor::init_futures_executor();
+        let owner = Owner::new();
+        owner.set();
+        let thrown = Arc::new(Thrown::default());
+        let _hook = error::set_error_hook(Arc::clone(&thrown) as _);
+        let html = block_on(async {
+            view.choose()
+                .await
+                .to_html_stream_in_order()
+                .collect::<String>()
+                .await
+        });
+        let thrown = thrown.0.lock().unwrap().clone();
+        (html, thrown)
+    }
+
+    #[test]
+    fn the_view_is_rendered_once_it_has_loaded() {
+        let view =
+            TryAsyncView::new(|| async { Ok::<_, ImportFailed>("Dashboard") })
+                .pending(|| "Loading...");
+        let (html, thrown) = render(view);
+        assert!(html.contains("Dashboard"), "{html}");
+        assert!(!html.contains("Loading..."), "{html}");
+        assert!(thrown.is_empty());
+    }
+
+    #[test]
+    fn the_error_is_thrown_to_the_error_boundary() {
+        let view = TryAsyncView::new(|| async { Err::<&str, _>(ImportFailed) })
+            .pending(|| "Loading...");
+        let (html, thrown) = render(view);
+        assert!(!html.contains("Loading..."), "{html}");
+        assert_eq!(thrown, ["the module could not be imported"]);
+    }
+
+    #[test]
+    fn the_pending_view_is_rendered_while_loading() {
+        _ = Executor::init_futures_executor();
+        let owner = Owner::new();
+        owner.set();
+        let view = TryAsyncView::new(|| async {
+            futures::future::pending::<Result<&str, ImportFailed>>().await
+        })
+        .pending(|| "Loading...");
+        let html = block_on(view.choose()).to_html();
+        assert!(html.contains("Loading..."), "{html}");
+    }
+}
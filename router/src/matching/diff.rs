@@ -0,0 +1,165 @@
+use super::{
+    segments_to_pattern, structure_hash::ssr_mode_name, GeneratedRouteData,
+    RouteMatchId,
+};
+use std::collections::HashMap;
+
+/// How the routes of one set of route definitions differ from those of another,
+/// found by [`RouteDefs::diff`](super::RouteDefs::diff).
+///
+/// Routes are identified by their path pattern, since [`RouteMatchId`]s are
+/// assigned in the order in which routes are created and are not the same
+/// across builds. This lets a dev server that reloads the routes keep the state
+/// of the routes that are still there.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RouteDiff {
+    /// The old and the new IDs of the routes that have the same pattern,
+    /// methods and [`SsrMode`](crate::SsrMode) in both, even if their views
+    /// changed.
+    pub persisted: Vec<(RouteMatchId, RouteMatchId)>,
+    /// The old and the new IDs of the routes that have the same pattern in
+    /// both, but whose methods or [`SsrMode`](crate::SsrMode) changed.
+    pub changed: Vec<(RouteMatchId, RouteMatchId)>,
+    /// The IDs of the new routes, whose patterns are not in the old routes.
+    pub added: Vec<RouteMatchId>,
+    /// The IDs of the old routes, whose patterns are not in the new routes.
+    pub removed: Vec<RouteMatchId>,
+}
+
+impl RouteDiff {
+    /// The new ID of the old route `old`, if it [persisted](Self::persisted).
+    pub fn persisted_id(&self, old: RouteMatchId) -> Option<RouteMatchId> {
+        self.persisted
+            .iter()
+            .find(|(id, _)| *id == old)
+            .map(|(_, new)| *new)
+    }
+
+    /// Whether the routes are the same, apart from their IDs and views.
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+    }
+}
+
+/// What identifies a generated route across builds, and what it is compared by.
+struct Listing {
+    id: RouteMatchId,
+    methods: Vec<String>,
+    ssr_mode: &'static str,
+}
+
+/// The generated routes with IDs, by pattern, in the order they were generated.
+fn listings(
+    routes: impl IntoIterator<Item = GeneratedRouteData>,
+) -> Vec<(String, Listing)> {
+    let mut listings = Vec::<(String, Listing)>::new();
+    for route in routes {
+        let Some(id) = route.id else {
+            continue;
+        };
+        let pattern = segments_to_pattern(&route.segments);
+        if listings.iter().any(|(seen, _)| *seen == pattern) {
+            continue;
+        }
+        let mut methods = route
+            .methods
+            .iter()
+            .map(|method| method.as_str().to_string())
+            .collect::<Vec<_>>();
+        methods.sort();
+        listings.push((
+            pattern,
+            Listing {
+                id,
+                methods,
+                ssr_mode: ssr_mode_name(&route.ssr_mode),
+            },
+        ));
+    }
+    listings
+}
+
+pub(crate) fn diff_routes(
+    old: impl IntoIterator<Item = GeneratedRouteData>,
+    new: impl IntoIterator<Item = GeneratedRouteData>,
+) -> RouteDiff {
+    let old = listings(old);
+    let mut new = listings(new).into_iter().collect::<HashMap<_, _>>();
+    let mut diff = RouteDiff::default();
+    for (pattern, old) in &old {
+        match new.remove(pattern) {
+            Some(new)
+                if new.methods == old.methods
+                    && new.ssr_mode == old.ssr_mode =>
+            {
+                diff.persisted.push((old.id, new.id));
+            }
+            Some(new) => diff.changed.push((old.id, new.id)),
+            None => diff.removed.push(old.id),
+        }
+    }
+    // the routes that are left are new, and keep the order they were generated
+    // in
+    diff.added = new.into_values().map(|listing| listing.id).collect();
+    diff.added.sort_by_key(|id| id.0);
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        GeneratedRouteData, Method, NestedRoute, ParamSegment, RouteDefs,
+        SsrMode, StaticSegment,
+    };
+
+    #[test]
+    fn routes_with_the_same_pattern_persist_when_their_views_change() {
+        let old = RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment(""), || ()),
+            NestedRoute::new(StaticSegment("users"), || ()).child((
+                NestedRoute::new(ParamSegment("id"), || ()),
+                NestedRoute::new(StaticSegment("new"), || ()),
+            )),
+            NestedRoute::new(StaticSegment("about"), || ()),
+            NestedRoute::new(StaticSegment("feed"), || ()),
+        ));
+        let new = RouteDefs::<_>::new((
+            // only the view of the home page changed
+            NestedRoute::new(StaticSegment(""), || "home"),
+            NestedRoute::new(StaticSegment("users"), || ()).child((
+                NestedRoute::new(ParamSegment("id"), || ()),
+                NestedRoute::new(StaticSegment("new"), || ())
+                    .custom_method(Method::Post.as_str().to_string()),
+            )),
+            NestedRoute::new(StaticSegment("feed"), || ())
+                .ssr_mode(SsrMode::Async),
+            NestedRoute::new(StaticSegment("contact"), || ()),
+        ));
+        let ids = |routes: Vec<GeneratedRouteData>| {
+            routes
+                .into_iter()
+                .map(|route| route.id.unwrap())
+                .collect::<Vec<_>>()
+        };
+        let old_ids = ids(old.generate_routes().1.into_iter().collect());
+        let new_ids = ids(new.generate_routes().1.into_iter().collect());
+
+        let diff = old.diff(&new);
+        assert_eq!(
+            diff.persisted,
+            [(old_ids[0], new_ids[0]), (old_ids[1], new_ids[1])]
+        );
+        assert_eq!(
+            diff.changed,
+            [(old_ids[2], new_ids[2]), (old_ids[4], new_ids[3])]
+        );
+        assert_eq!(diff.added, [new_ids[4]]);
+        assert_eq!(diff.removed, [old_ids[3]]);
+        assert_eq!(diff.persisted_id(old_ids[0]), Some(new_ids[0]));
+        assert_eq!(diff.persisted_id(old_ids[2]), None);
+        assert!(!diff.is_empty());
+        assert!(old.diff(&old).is_empty());
+    }
+}
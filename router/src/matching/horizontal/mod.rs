@@ -1,9 +1,11 @@
 use super::{PartialPathMatch, PathSegment};
 use std::sync::Arc;
 mod param_segments;
+mod path_segments;
 mod static_segment;
 mod tuples;
 pub use param_segments::*;
+pub use path_segments::*;
 pub use static_segment::*;
 
 /// Defines a route which may or may not be matched by any given URL,
@@ -32,6 +34,23 @@ pub trait PossibleRouteMatch {
     ///    tested can detect wherever they are matching from the beginning of the given path segment.
     fn test<'a>(&self, path: &'a str) -> Option<PartialPathMatch<'a>>;
 
+    /// Checks if this segment matches the beginning of the path, like
+    /// [`test`](Self::test), for a router that splits paths on `separator`
+    /// rather than `/`, as set with
+    /// [`RouteDefs::separator`](crate::RouteDefs::separator).
+    ///
+    /// The built-in segments split the path with
+    /// [`PathSegments::with_separator`], and their `test` is this with `/`. By
+    /// default, this ignores `separator` and calls `test`.
+    fn test_with_separator<'a>(
+        &self,
+        path: &'a str,
+        separator: char,
+    ) -> Option<PartialPathMatch<'a>> {
+        _ = separator;
+        self.test(path)
+    }
+
     fn generate_path(&self, path: &mut Vec<PathSegment>);
 }
 
@@ -44,6 +63,14 @@ impl PossibleRouteMatch for Box<dyn PossibleRouteMatch + Send + Sync> {
         (**self).test(path)
     }
 
+    fn test_with_separator<'a>(
+        &self,
+        path: &'a str,
+        separator: char,
+    ) -> Option<PartialPathMatch<'a>> {
+        (**self).test_with_separator(path, separator)
+    }
+
     fn generate_path(&self, path: &mut Vec<PathSegment>) {
         (**self).generate_path(path);
     }
@@ -58,6 +85,14 @@ impl PossibleRouteMatch for Arc<dyn PossibleRouteMatch + Send + Sync> {
         (**self).test(path)
     }
 
+    fn test_with_separator<'a>(
+        &self,
+        path: &'a str,
+        separator: char,
+    ) -> Option<PartialPathMatch<'a>> {
+        (**self).test_with_separator(path, separator)
+    }
+
     fn generate_path(&self, path: &mut Vec<PathSegment>) {
         (**self).generate_path(path);
     }
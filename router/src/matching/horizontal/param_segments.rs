@@ -1,6 +1,6 @@
-use super::{PartialPathMatch, PathSegment, PossibleRouteMatch};
+use super::{PartialPathMatch, PathSegment, PathSegments, PossibleRouteMatch};
 use core::iter;
-use std::borrow::Cow;
+use std::{borrow::Cow, ops::RangeInclusive};
 
 /// A segment that captures a value from the url and maps it to a key.
 ///
@@ -40,38 +40,169 @@ impl PossibleRouteMatch for ParamSegment {
     }
 
     fn test<'a>(&self, path: &'a str) -> Option<PartialPathMatch<'a>> {
-        let mut matched_len = 0;
-        let mut param_offset = 0;
-        let mut param_len = 0;
-        let mut test = path.chars();
-
-        // match an initial /
-        if let Some('/') = test.next() {
-            matched_len += 1;
-            param_offset = 1;
-        }
-        for char in test {
-            // when we get a closing /, stop matching
-            if char == '/' {
-                break;
-            }
-            // otherwise, push into the matched param
-            else {
-                matched_len += char.len_utf8();
-                param_len += char.len_utf8();
+        self.test_with_separator(path, '/')
+    }
+
+    fn test_with_separator<'a>(
+        &self,
+        path: &'a str,
+        separator: char,
+    ) -> Option<PartialPathMatch<'a>> {
+        let component = PathSegments::with_separator(path, separator)
+            .next()
+            .filter(|component| !component.value().is_empty())?;
+        let param_value =
+            vec![(Cow::Borrowed(self.0), component.value().to_string())];
+        Some(PartialPathMatch::separated_by(
+            separator,
+            component.remaining(),
+            param_value,
+            component.matched(),
+        ))
+    }
+
+    fn generate_path(&self, path: &mut Vec<PathSegment>) {
+        path.push(PathSegment::Param(self.0.into()));
+    }
+}
+
+impl ParamSegment {
+    /// Only matches a segment that is an integer in `range`, like a page
+    /// number, so that another route (or a 404) handles any other value.
+    ///
+    /// ```rust
+    /// use leptos_router::{ParamSegment, PossibleRouteMatch, StaticSegment};
+    ///
+    /// let def = (StaticSegment("p"), ParamSegment("page").range(1..=1000));
+    /// assert_eq!(def.test("/p/5").unwrap().params()[0].1, "5");
+    /// assert!(def.test("/p/0").is_none());
+    /// assert!(def.test("/p/abc").is_none());
+    /// ```
+    pub fn range(self, range: RangeInclusive<i64>) -> RangeParamSegment {
+        RangeParamSegment(self.0, range)
+    }
+
+    /// Also matches an empty value at the end of the path, so that `/search/`
+    /// is matched with `q` set to `""`, while `/search` is not matched at all.
+    ///
+    /// ```rust
+    /// use leptos_router::{ParamSegment, PossibleRouteMatch, StaticSegment};
+    ///
+    /// let def = (StaticSegment("search"), ParamSegment("q").allow_empty());
+    /// assert_eq!(def.test("/search/foo").unwrap().params()[0].1, "foo");
+    /// assert_eq!(def.test("/search/").unwrap().params()[0].1, "");
+    /// assert!(def.test("/search").is_none());
+    /// ```
+    pub fn allow_empty(self) -> EmptyParamSegment {
+        EmptyParamSegment(self.0)
+    }
+}
+
+/// A segment that captures a value from the url and maps it to a key, like a
+/// [`ParamSegment`], but which is also matched by a trailing separator with
+/// nothing after it, as an empty value. Created with
+/// [`ParamSegment::allow_empty`].
+///
+/// The value can only be empty at the end of the path: an empty segment between
+/// two separators, like the one in `/search//foo`, is not matched, and neither
+/// is the end of a path without a trailing separator, so `/search` can be
+/// handled by another route.
+///
+/// A route is fully matched when all that is left of the path is empty or a
+/// single separator, so a route for `/search` also matches `/search/`. This
+/// segment consumes that trailing separator, so to tell `/search/` and
+/// `/search` apart, the route with this segment must come before the route for
+/// `/search`, and the trailing slash must be kept by
+/// [`RouteDefs::canonical_paths`](crate::RouteDefs::canonical_paths). When the
+/// routes are listed for a server integration, this is listed as a
+/// [`ParamSegment`], which does not match an empty value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EmptyParamSegment(pub &'static str);
+
+impl PossibleRouteMatch for EmptyParamSegment {
+    fn optional(&self) -> bool {
+        false
+    }
+
+    fn test<'a>(&self, path: &'a str) -> Option<PartialPathMatch<'a>> {
+        self.test_with_separator(path, '/')
+    }
+
+    fn test_with_separator<'a>(
+        &self,
+        path: &'a str,
+        separator: char,
+    ) -> Option<PartialPathMatch<'a>> {
+        match PathSegments::with_separator(path, separator).next() {
+            Some(component) if !component.value().is_empty() => {
+                Some(PartialPathMatch::separated_by(
+                    separator,
+                    component.remaining(),
+                    vec![(
+                        Cow::Borrowed(self.0),
+                        component.value().to_string(),
+                    )],
+                    component.matched(),
+                ))
             }
+            // an empty segment before the end of the path
+            Some(_) => None,
+            // nothing but a trailing separator is left, which is the empty
+            // value
+            None => (path.strip_prefix(separator) == Some("")).then(|| {
+                PartialPathMatch::separated_by(
+                    separator,
+                    "",
+                    vec![(Cow::Borrowed(self.0), String::new())],
+                    path,
+                )
+            }),
         }
+    }
 
-        if matched_len == 0 || (matched_len == 1 && path.starts_with('/')) {
-            return None;
-        }
+    fn generate_path(&self, path: &mut Vec<PathSegment>) {
+        path.push(PathSegment::Param(self.0.into()));
+    }
+}
 
-        let (matched, remaining) = path.split_at(matched_len);
-        let param_value = vec![(
-            Cow::Borrowed(self.0),
-            path[param_offset..param_len + param_offset].to_string(),
-        )];
-        Some(PartialPathMatch::new(remaining, param_value, matched))
+/// A segment that captures a value from the url that is an integer in a range,
+/// and maps it to a key, created with [`ParamSegment::range`].
+///
+/// The value is captured as it is written in the url, so it can still be parsed
+/// as any type when the params are extracted. When the routes are listed for a
+/// server integration, this is listed as a [`ParamSegment`], which matches any
+/// value.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RangeParamSegment(pub &'static str, pub RangeInclusive<i64>);
+
+impl PossibleRouteMatch for RangeParamSegment {
+    fn optional(&self) -> bool {
+        false
+    }
+
+    fn test<'a>(&self, path: &'a str) -> Option<PartialPathMatch<'a>> {
+        self.test_with_separator(path, '/')
+    }
+
+    fn test_with_separator<'a>(
+        &self,
+        path: &'a str,
+        separator: char,
+    ) -> Option<PartialPathMatch<'a>> {
+        let component = PathSegments::with_separator(path, separator)
+            .next()
+            .filter(|component| {
+                component
+                    .value()
+                    .parse::<i64>()
+                    .is_ok_and(|value| self.1.contains(&value))
+            })?;
+        Some(PartialPathMatch::separated_by(
+            separator,
+            component.remaining(),
+            vec![(Cow::Borrowed(self.0), component.value().to_string())],
+            component.matched(),
+        ))
     }
 
     fn generate_path(&self, path: &mut Vec<PathSegment>) {
@@ -130,30 +261,22 @@ impl PossibleRouteMatch for WildcardSegment {
     }
 
     fn test<'a>(&self, path: &'a str) -> Option<PartialPathMatch<'a>> {
-        let mut matched_len = 0;
-        let mut param_offset = 0;
-        let mut param_len = 0;
-        let mut test = path.chars();
-
-        // match an initial /
-        if let Some('/') = test.next() {
-            matched_len += 1;
-            param_offset += 1;
-        }
-        for char in test {
-            matched_len += char.len_utf8();
-            param_len += char.len_utf8();
-        }
+        self.test_with_separator(path, '/')
+    }
 
-        let (matched, remaining) = path.split_at(matched_len);
-        let param_value = iter::once((
-            Cow::Borrowed(self.0),
-            path[param_offset..param_len + param_offset].to_string(),
-        ));
-        Some(PartialPathMatch::new(
-            remaining,
+    fn test_with_separator<'a>(
+        &self,
+        path: &'a str,
+        separator: char,
+    ) -> Option<PartialPathMatch<'a>> {
+        let rest = PathSegments::with_separator(path, separator).rest();
+        let param_value =
+            iter::once((Cow::Borrowed(self.0), rest.value().to_string()));
+        Some(PartialPathMatch::separated_by(
+            separator,
+            rest.remaining(),
             param_value.into_iter().collect(),
-            matched,
+            rest.matched(),
         ))
     }
 
@@ -171,44 +294,26 @@ impl PossibleRouteMatch for OptionalParamSegment {
     }
 
     fn test<'a>(&self, path: &'a str) -> Option<PartialPathMatch<'a>> {
-        let mut matched_len = 0;
-        let mut param_offset = 0;
-        let mut param_len = 0;
-        let mut test = path.chars();
-
-        // match an initial /
-        if let Some('/') = test.next() {
-            matched_len += 1;
-            param_offset = 1;
-        }
-        for char in test {
-            // when we get a closing /, stop matching
-            if char == '/' {
-                break;
-            }
-            // otherwise, push into the matched param
-            else {
-                matched_len += char.len_utf8();
-                param_len += char.len_utf8();
-            }
-        }
+        self.test_with_separator(path, '/')
+    }
 
-        let matched_len = if matched_len == 1 && path.starts_with('/') {
-            0
-        } else {
-            matched_len
-        };
-        let (matched, remaining) = path.split_at(matched_len);
-        let param_value = (matched_len > 0)
-            .then(|| {
-                (
-                    Cow::Borrowed(self.0),
-                    path[param_offset..param_len + param_offset].to_string(),
-                )
-            })
-            .into_iter()
-            .collect();
-        Some(PartialPathMatch::new(remaining, param_value, matched))
+    fn test_with_separator<'a>(
+        &self,
+        path: &'a str,
+        separator: char,
+    ) -> Option<PartialPathMatch<'a>> {
+        let component = PathSegments::with_separator(path, separator)
+            .next()
+            .filter(|component| !component.value().is_empty());
+        Some(match component {
+            Some(component) => PartialPathMatch::separated_by(
+                separator,
+                component.remaining(),
+                vec![(Cow::Borrowed(self.0), component.value().to_string())],
+                component.matched(),
+            ),
+            None => PartialPathMatch::separated_by(separator, path, vec![], ""),
+        })
     }
 
     fn generate_path(&self, path: &mut Vec<PathSegment>) {
@@ -216,11 +321,76 @@ impl PossibleRouteMatch for OptionalParamSegment {
     }
 }
 
+/// A segment that captures the name of a file and its extension as two separate
+/// values, like `report` and `pdf` for `/report.pdf`, mapping them to the first
+/// and second key.
+///
+/// The extension is the part of the segment after its last `.`, so
+/// `/archive.tar.gz` captures `archive.tar` and `gz`. A segment without an
+/// extension does not match, nor does one whose only `.` is its first or last
+/// character, like `/.env` or `/report.`, so that another route can handle
+/// those.
+///
+/// When the routes are listed for a server integration, this is listed as a
+/// [`ParamSegment`] with the first key, which matches the whole segment.
+///
+/// ```rust
+/// # (|| -> Option<()> { // Option does not impl Terminate, so no main
+/// use leptos_router::{ExtensionSegment, PossibleRouteMatch, StaticSegment};
+///
+/// let def = (StaticSegment("files"), ExtensionSegment("name", "ext"));
+/// let params = def.test("/files/report.pdf")?.params();
+///
+/// assert_eq!(params[0], ("name".into(), "report".into()));
+/// assert_eq!(params[1], ("ext".into(), "pdf".into()));
+/// assert!(def.test("/files/report").is_none());
+/// # Some(())
+/// # })().unwrap();
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ExtensionSegment(pub &'static str, pub &'static str);
+
+impl PossibleRouteMatch for ExtensionSegment {
+    fn optional(&self) -> bool {
+        false
+    }
+
+    fn test<'a>(&self, path: &'a str) -> Option<PartialPathMatch<'a>> {
+        self.test_with_separator(path, '/')
+    }
+
+    fn test_with_separator<'a>(
+        &self,
+        path: &'a str,
+        separator: char,
+    ) -> Option<PartialPathMatch<'a>> {
+        let component = PathSegments::with_separator(path, separator).next()?;
+        let (stem, ext) = component
+            .value()
+            .rsplit_once('.')
+            .filter(|(stem, ext)| !stem.is_empty() && !ext.is_empty())?;
+        Some(PartialPathMatch::separated_by(
+            separator,
+            component.remaining(),
+            vec![
+                (Cow::Borrowed(self.0), stem.to_string()),
+                (Cow::Borrowed(self.1), ext.to_string()),
+            ],
+            component.matched(),
+        ))
+    }
+
+    fn generate_path(&self, path: &mut Vec<PathSegment>) {
+        path.push(PathSegment::Param(self.0.into()));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::PossibleRouteMatch;
     use crate::{
-        OptionalParamSegment, ParamSegment, StaticSegment, WildcardSegment,
+        ExtensionSegment, OptionalParamSegment, ParamSegment, StaticSegment,
+        WildcardSegment,
     };
 
     #[test]
@@ -245,6 +415,24 @@ mod tests {
         assert_eq!(params[0], ("a".into(), "foo".into()));
     }
 
+    #[test]
+    fn empty_param_only_matches_empty_at_the_end() {
+        let def = (StaticSegment("search"), ParamSegment("q").allow_empty());
+
+        let matched = def.test("/search/").expect("couldn't match route");
+        assert_eq!(matched.matched(), "/search/");
+        assert_eq!(matched.remaining(), "");
+        assert_eq!(matched.params()[0], ("q".into(), "".into()));
+
+        let matched = def.test("/search/foo/").expect("couldn't match route");
+        assert_eq!(matched.matched(), "/search/foo");
+        assert_eq!(matched.remaining(), "/");
+        assert_eq!(matched.params()[0], ("q".into(), "foo".into()));
+
+        assert!(def.test("/search").is_none());
+        assert!(def.test("/search//foo").is_none());
+    }
+
     #[test]
     fn tuple_of_param_matches() {
         let path = "/foo/bar";
@@ -380,4 +568,38 @@ mod tests {
         assert_eq!(params[0], ("a".into(), "foo".into()));
         assert_eq!(params[1], ("b".into(), "qux".into()));
     }
+
+    #[test]
+    fn range_params_only_match_integers_in_the_range() {
+        let def = (StaticSegment("p"), ParamSegment("page").range(1..=1000));
+        let matched = def.test("/p/5").expect("couldn't match route");
+        assert_eq!(matched.matched(), "/p/5");
+        assert_eq!(matched.params()[0], ("page".into(), "5".into()));
+        assert!(def.test("/p/1000").is_some());
+
+        for path in ["/p/0", "/p/1001", "/p/abc", "/p/-3", "/p/", "/p"] {
+            assert!(def.test(path).is_none(), "{path} should not match");
+        }
+    }
+
+    #[test]
+    fn extension_is_captured_apart_from_the_stem() {
+        let def = (StaticSegment("reports"), ExtensionSegment("name", "ext"));
+        let matched = def
+            .test("/reports/report.pdf/")
+            .expect("couldn't match route");
+        assert_eq!(matched.matched(), "/reports/report.pdf");
+        assert_eq!(matched.remaining(), "/");
+        let params = matched.params();
+        assert_eq!(params[0], ("name".into(), "report".into()));
+        assert_eq!(params[1], ("ext".into(), "pdf".into()));
+
+        let params = def.test("/reports/archive.tar.gz").unwrap().params();
+        assert_eq!(params[0], ("name".into(), "archive.tar".into()));
+        assert_eq!(params[1], ("ext".into(), "gz".into()));
+
+        for path in ["/reports/report", "/reports/.env", "/reports/report."] {
+            assert!(def.test(path).is_none(), "{path} should not match");
+        }
+    }
 }
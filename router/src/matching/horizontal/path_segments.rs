@@ -0,0 +1,144 @@
+/// An iterator over the components of a path, which splits it in the same way
+/// as the built-in [`PossibleRouteMatch`](super::PossibleRouteMatch)
+/// implementations, without allocating.
+///
+/// Each component is the text up to the next separator, and may start with a
+/// separator: a path does not need to start with one, so `a/b` is split in the
+/// same way as `/a/b`. A single trailing separator does not start a new
+/// component, but a repeated separator does, so `//a/` is split into an empty
+/// component and then `a`. Components are not percent-decoded, so an encoded
+/// separator like `%2F` is part of the component it appears in.
+///
+/// ```rust
+/// use leptos_router::PathSegments;
+///
+/// let components = PathSegments::new("/a%2Fb/c/")
+///     .map(|component| component.value())
+///     .collect::<Vec<_>>();
+/// assert_eq!(components, ["a%2Fb", "c"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathSegments<'a> {
+    remaining: &'a str,
+    separator: char,
+}
+
+impl<'a> PathSegments<'a> {
+    /// Splits `path` on `/`.
+    pub fn new(path: &'a str) -> Self {
+        Self::with_separator(path, '/')
+    }
+
+    /// Splits `path` on `separator`.
+    pub fn with_separator(path: &'a str, separator: char) -> Self {
+        Self {
+            remaining: path,
+            separator,
+        }
+    }
+
+    /// The part of the path that has not been split yet.
+    pub fn remaining(&self) -> &'a str {
+        self.remaining
+    }
+
+    /// Takes the whole rest of the path as a single component, separators
+    /// included, as a [`WildcardSegment`](crate::WildcardSegment) does.
+    pub fn rest(self) -> PathComponent<'a> {
+        let value = self
+            .remaining
+            .strip_prefix(self.separator)
+            .unwrap_or(self.remaining);
+        PathComponent {
+            matched: self.remaining,
+            value,
+            remaining: "",
+        }
+    }
+}
+
+impl<'a> Iterator for PathSegments<'a> {
+    type Item = PathComponent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (offset, rest) = match self.remaining.strip_prefix(self.separator) {
+            // a trailing separator does not start a component
+            Some("") => return None,
+            Some(rest) => (self.separator.len_utf8(), rest),
+            None if self.remaining.is_empty() => return None,
+            None => (0, self.remaining),
+        };
+        let len = rest.find(self.separator).unwrap_or(rest.len());
+        let (matched, remaining) = self.remaining.split_at(offset + len);
+        self.remaining = remaining;
+        Some(PathComponent {
+            matched,
+            value: &rest[..len],
+            remaining,
+        })
+    }
+}
+
+/// A component of a path, from [`PathSegments`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathComponent<'a> {
+    matched: &'a str,
+    value: &'a str,
+    remaining: &'a str,
+}
+
+impl<'a> PathComponent<'a> {
+    /// The component, including the separator it starts with, if it does.
+    pub fn matched(&self) -> &'a str {
+        self.matched
+    }
+
+    /// The component, without the separator it starts with. This is still
+    /// percent-encoded.
+    pub fn value(&self) -> &'a str {
+        self.value
+    }
+
+    /// The rest of the path, after this component.
+    pub fn remaining(&self) -> &'a str {
+        self.remaining
+    }
+
+    /// Whether the component starts with a separator, so that it is known to be
+    /// at the start of a segment of the path.
+    pub fn starts_with_separator(&self) -> bool {
+        self.matched.len() > self.value.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PathSegments;
+
+    fn split(path: &str) -> Vec<(&str, &str)> {
+        PathSegments::with_separator(path, '/')
+            .map(|component| (component.matched(), component.value()))
+            .collect()
+    }
+
+    #[test]
+    fn tricky_paths_are_split_consistently() {
+        assert_eq!(split(""), []);
+        assert_eq!(split("/"), []);
+        assert_eq!(split("/a/b"), [("/a", "a"), ("/b", "b")]);
+        assert_eq!(split("a/b/"), [("a", "a"), ("/b", "b")]);
+        assert_eq!(split("//a/"), [("/", ""), ("/a", "a")]);
+        assert_eq!(split("/a%2Fb/c"), [("/a%2Fb", "a%2Fb"), ("/c", "c")]);
+
+        let mut components = PathSegments::with_separator("/a//b/", '/');
+        assert_eq!(components.next().map(|c| c.remaining()), Some("//b/"));
+        let rest = components.rest();
+        assert_eq!((rest.matched(), rest.value()), ("//b/", "/b/"));
+        assert_eq!(
+            PathSegments::with_separator("x.y", '.')
+                .map(|c| c.value())
+                .collect::<Vec<_>>(),
+            ["x", "y"]
+        );
+    }
+}
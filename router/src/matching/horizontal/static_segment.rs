@@ -1,4 +1,4 @@
-use super::{PartialPathMatch, PathSegment, PossibleRouteMatch};
+use super::{PartialPathMatch, PathSegment, PathSegments, PossibleRouteMatch};
 use std::fmt::Debug;
 
 impl PossibleRouteMatch for () {
@@ -63,69 +63,57 @@ impl<T: AsPath> PossibleRouteMatch for StaticSegment<T> {
     }
 
     fn test<'a>(&self, path: &'a str) -> Option<PartialPathMatch<'a>> {
-        let mut matched_len = 0;
-        let mut test = path.chars().peekable();
-        let mut this = self.0.as_path().chars();
-        let mut has_matched =
-            self.0.as_path().is_empty() || self.0.as_path() == "/";
-
-        // match an initial /
-        if let Some('/') = test.peek() {
-            test.next();
-
-            if !self.0.as_path().is_empty() {
-                matched_len += 1;
-            }
-            if self.0.as_path().starts_with('/') || self.0.as_path().is_empty()
-            {
-                this.next();
-            }
-        } else if !path.is_empty() {
-            // Path must start with `/` otherwise we are not certain about being at the beginning of the segment in the path
-            return None;
-        }
-
-        for char in test {
-            let n = this.next();
-            // when we get a closing /, stop matching
-            if char == '/' {
-                if n.is_some() {
-                    return None;
-                }
-                break;
-            } else if n.is_none() {
-                break;
-            }
-            // if the next character in the path matches the
-            // next character in the segment, add it to the match
-            else if Some(char) == n {
-                has_matched = true;
-                matched_len += char.len_utf8();
-            }
-            // otherwise, this route doesn't match and we should
-            // return None
-            else {
-                return None;
-            }
-        }
+        self.test_with_separator(path, '/')
+    }
 
-        // if we still have remaining, unmatched characters in this segment, it was not a match
-        if this.next().is_some() {
-            return None;
+    fn test_with_separator<'a>(
+        &self,
+        path: &'a str,
+        separator: char,
+    ) -> Option<PartialPathMatch<'a>> {
+        let segment = self.0.as_path();
+        let value = segment.strip_prefix('/').unwrap_or(segment);
+
+        if value.is_empty() {
+            // an empty segment matches at the start of a path segment, without
+            // consuming it, so that the next segment can tell it is matching
+            // from the start of a path segment
+            return if path.is_empty() {
+                segment.is_empty().then(|| {
+                    PartialPathMatch::separated_by(separator, "", vec![], "")
+                })
+            } else if path.starts_with(separator) {
+                let matched = if segment.is_empty() {
+                    ""
+                } else {
+                    &path[..separator.len_utf8()]
+                };
+                Some(PartialPathMatch::separated_by(
+                    separator,
+                    path,
+                    vec![],
+                    matched,
+                ))
+            } else {
+                None
+            };
         }
 
-        // build the match object
-        let (matched, remaining) = if matched_len == 1 && path.starts_with('/')
-        {
-            // If only thing that matched is `/` we can't eat it, otherwise next invocation of the
-            // test function will not be able to tell that we are matching from the beginning of the path segment
-            ("/", path)
-        } else {
-            // the remaining is built from the path in, with the slice moved
-            // by the length of this match
-            path.split_at(matched_len)
-        };
-        has_matched.then(|| PartialPathMatch::new(remaining, vec![], matched))
+        // the path must start with a separator, otherwise we are not certain
+        // about being at the beginning of the segment in the path
+        PathSegments::with_separator(path, separator)
+            .next()
+            .filter(|component| {
+                component.starts_with_separator() && component.value() == value
+            })
+            .map(|component| {
+                PartialPathMatch::separated_by(
+                    separator,
+                    component.remaining(),
+                    vec![],
+                    component.matched(),
+                )
+            })
     }
 
     fn generate_path(&self, path: &mut Vec<PathSegment>) {
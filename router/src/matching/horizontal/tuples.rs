@@ -15,6 +15,14 @@ macro_rules! tuples {
             }
 
             fn test<'a>(&self, path: &'a str) -> Option<PartialPathMatch<'a>> {
+                self.test_with_separator(path, '/')
+            }
+
+            fn test_with_separator<'a>(
+                &self,
+                path: &'a str,
+                separator: char,
+            ) -> Option<PartialPathMatch<'a>> {
                 #[allow(non_snake_case)]
                 let ($first, $($ty,)*) = &self;
 
@@ -29,18 +37,20 @@ macro_rules! tuples {
                     let mut r = path;
 
                     let mut p = Vec::new();
+                    let mut s = Vec::new();
                     let mut m = String::new();
 
                     if $first.optional() {
                         nth_field += 1;
                     }
                     if !$first.optional() || nth_field <= include_optionals {
-                        match $first.test(r) {
+                        match $first.test_with_separator(r, separator) {
                             None => {
                                 return None;
                             },
-                            Some(PartialPathMatch { remaining, matched, params }) => {
+                            Some(PartialPathMatch { remaining, matched, params, segments }) => {
                                 p.extend(params.into_iter());
+                                s.extend(segments);
                                 m.push_str(matched);
                                 r = remaining;
                             },
@@ -56,8 +66,9 @@ macro_rules! tuples {
                             let PartialPathMatch {
                                 remaining,
                                 matched,
-                                params
-                            } = match $ty.test(r) {
+                                params,
+                                segments,
+                            } = match $ty.test_with_separator(r, separator) {
                                 None => if $ty.optional() {
                                     return None;
                                 } else {
@@ -72,12 +83,14 @@ macro_rules! tuples {
                             r = remaining;
                             matched_len += matched.len();
                             p.extend(params);
+                            s.extend(segments);
                         }
                     )*
                     return Some(PartialPathMatch {
                         remaining: r,
                         matched: &path[0..matched_len],
-                        params: p
+                        params: p,
+                        segments: s,
                     });
                 }
             }
@@ -104,16 +117,26 @@ where
     }
 
     fn test<'a>(&self, path: &'a str) -> Option<PartialPathMatch<'a>> {
+        self.test_with_separator(path, '/')
+    }
+
+    fn test_with_separator<'a>(
+        &self,
+        path: &'a str,
+        separator: char,
+    ) -> Option<PartialPathMatch<'a>> {
         let remaining = path;
         let PartialPathMatch {
             remaining,
             matched,
             params,
-        } = self.0.test(remaining)?;
+            segments,
+        } = self.0.test_with_separator(remaining, separator)?;
         Some(PartialPathMatch {
             remaining,
             matched: &path[0..matched.len()],
             params,
+            segments,
         })
     }
 
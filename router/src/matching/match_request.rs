@@ -0,0 +1,352 @@
+use crate::{Method, SeparatorConfig};
+use std::collections::HashSet;
+
+/// The request being matched against a set of route definitions.
+///
+/// Only the path is required. Every other field is optional, and is `None` when
+/// matching only on a path (for example, during client-side navigation, where
+/// there is no request method or host to match against). Anything that matches
+/// on one of these fields should treat `None` as "unknown" rather than as a
+/// mismatch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchRequest<'a> {
+    path: &'a str,
+    query: Option<&'a str>,
+    method: Option<&'a Method>,
+    host: Option<&'a str>,
+    accept: Option<&'a str>,
+    content_type: Option<&'a str>,
+    cookies: Option<&'a str>,
+    accept_language: Option<&'a str>,
+    headers: &'a [(&'a str, &'a str)],
+    separator: SeparatorConfig,
+    default_methods: Option<&'a HashSet<Method>>,
+    skip_matchers: bool,
+}
+
+impl<'a> MatchRequest<'a> {
+    /// Creates a request that contains only a path.
+    pub fn new(path: &'a str) -> Self {
+        Self {
+            path,
+            query: None,
+            method: None,
+            host: None,
+            accept: None,
+            content_type: None,
+            cookies: None,
+            accept_language: None,
+            headers: &[],
+            separator: SeparatorConfig::default(),
+            default_methods: None,
+            skip_matchers: false,
+        }
+    }
+
+    /// Sets the query string of the request, without the leading `?`.
+    pub fn query(mut self, query: &'a str) -> Self {
+        self.query = Some(query);
+        self
+    }
+
+    /// Sets the HTTP method of the request.
+    pub fn method(mut self, method: &'a Method) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    /// Sets the host the request was made to, e.g. the value of the `Host`
+    /// header.
+    pub fn host(mut self, host: &'a str) -> Self {
+        self.host = Some(host);
+        self
+    }
+
+    /// Sets the value of the request's `Accept` header.
+    pub fn accept(mut self, accept: &'a str) -> Self {
+        self.accept = Some(accept);
+        self
+    }
+
+    /// Sets the value of the request's `Content-Type` header, which is the
+    /// content type of its body.
+    pub fn content_type(mut self, content_type: &'a str) -> Self {
+        self.content_type = Some(content_type);
+        self
+    }
+
+    /// Sets the value of the request's `Cookie` header.
+    pub fn cookies(mut self, cookies: &'a str) -> Self {
+        self.cookies = Some(cookies);
+        self
+    }
+
+    /// Sets the value of the request's `Accept-Language` header.
+    pub fn accept_language(mut self, accept_language: &'a str) -> Self {
+        self.accept_language = Some(accept_language);
+        self
+    }
+
+    /// Sets the other headers of the request, as pairs of names and values, for
+    /// the matchers that read headers without a field of their own, like the
+    /// region of an edge deployment.
+    pub fn headers(mut self, headers: &'a [(&'a str, &'a str)]) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Returns the same request, for a different (usually, the remaining) path.
+    pub fn with_path(self, path: &'a str) -> Self {
+        Self { path, ..self }
+    }
+
+    /// Returns the same request, with an unknown method.
+    pub(crate) fn without_method(self) -> Self {
+        Self {
+            method: None,
+            ..self
+        }
+    }
+
+    /// Returns the same request, for a router that splits paths on the given
+    /// separator.
+    pub(crate) fn separated_by(self, separator: SeparatorConfig) -> Self {
+        Self { separator, ..self }
+    }
+
+    /// Returns the same request, for a router whose routes handle `methods`
+    /// unless they set their own.
+    pub(crate) fn with_default_methods(
+        self,
+        methods: &'a HashSet<Method>,
+    ) -> Self {
+        Self {
+            default_methods: Some(methods),
+            ..self
+        }
+    }
+
+    /// Returns the same request, which matches routes by their segments alone,
+    /// whatever their [custom matchers](crate::NestedRoute::custom_matcher).
+    #[cfg(feature = "diagnostics")]
+    pub(crate) fn skipping_matchers(self) -> Self {
+        Self {
+            skip_matchers: true,
+            ..self
+        }
+    }
+
+    /// Whether routes are matched by their segments alone.
+    pub(crate) fn skips_matchers(&self) -> bool {
+        self.skip_matchers
+    }
+
+    /// Returns the same request, with an unknown query.
+    pub(crate) fn without_query(self) -> Self {
+        Self {
+            query: None,
+            ..self
+        }
+    }
+
+    /// The path that is being matched.
+    pub fn as_path(&self) -> &'a str {
+        self.path
+    }
+
+    /// The separator that the router matching this request splits paths on,
+    /// which is `/` unless it is set with
+    /// [`RouteDefs::separator`](crate::RouteDefs::separator).
+    pub fn as_separator(&self) -> SeparatorConfig {
+        self.separator
+    }
+
+    /// The methods handled by the routes that do not
+    /// [set their own](crate::NestedRoute::methods), as set with
+    /// [`RouteDefs::default_methods`](crate::RouteDefs::default_methods) on the
+    /// router matching this request, if known.
+    pub fn as_default_methods(&self) -> Option<&'a HashSet<Method>> {
+        self.default_methods
+    }
+
+    /// The query string of the request, if known.
+    pub fn as_query(&self) -> Option<&'a str> {
+        self.query
+    }
+
+    /// The HTTP method of the request, if known.
+    pub fn as_method(&self) -> Option<&'a Method> {
+        self.method
+    }
+
+    /// The host of the request, if known.
+    pub fn as_host(&self) -> Option<&'a str> {
+        self.host
+    }
+
+    /// The `Accept` header of the request, if known.
+    pub fn as_accept(&self) -> Option<&'a str> {
+        self.accept
+    }
+
+    /// The `Content-Type` header of the request, if it has one.
+    pub fn as_content_type(&self) -> Option<&'a str> {
+        self.content_type
+    }
+
+    /// The `Cookie` header of the request, if known.
+    pub fn as_cookies(&self) -> Option<&'a str> {
+        self.cookies
+    }
+
+    /// The `Accept-Language` header of the request, if known.
+    pub fn as_accept_language(&self) -> Option<&'a str> {
+        self.accept_language
+    }
+
+    /// The value of the header `name`, compared without case, if it is one of
+    /// the request's [other headers](Self::headers).
+    pub fn header(&self, name: &str) -> Option<&'a str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    }
+
+    /// The value of a single cookie in the request's `Cookie` header, if that
+    /// header is known and contains the cookie.
+    pub fn cookie(&self, name: &str) -> Option<&'a str> {
+        self.cookies?.split(';').find_map(|cookie| {
+            let (key, value) = cookie.split_once('=')?;
+            (key.trim() == name).then(|| value.trim())
+        })
+    }
+}
+
+/// The request being rendered on the server, which the server integrations
+/// provide as context, so that the router matches its routes against the whole
+/// request, and not only against its path.
+///
+/// Without it, as in the browser, the routes are matched against the path and
+/// query of the URL, and the cookies of the document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestDetails {
+    method: Option<Method>,
+    headers: Vec<(String, String)>,
+}
+
+impl RequestDetails {
+    /// Creates the details of a request with the given method and no headers.
+    pub fn new(method: Method) -> Self {
+        Self {
+            method: Some(method),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Adds a header of the request.
+    pub fn with_header(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// The method of the request, if it is known.
+    pub fn method(&self) -> Option<&Method> {
+        self.method.as_ref()
+    }
+
+    /// Calls `f` with a [`MatchRequest`] for `path` and `query` with the method
+    /// and headers of this request.
+    ///
+    /// The host, `Accept`, `Content-Type`, `Cookie` and `Accept-Language` of
+    /// the match request are read from the headers.
+    pub fn with_match_request<T>(
+        &self,
+        path: &str,
+        query: Option<&str>,
+        f: impl FnOnce(MatchRequest<'_>) -> T,
+    ) -> T {
+        let headers = self
+            .headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+        let mut req = MatchRequest::new(path).headers(&headers);
+        if let Some(query) = query {
+            req = req.query(query);
+        }
+        if let Some(method) = &self.method {
+            req = req.method(method);
+        }
+        if let Some(host) = req.header("host") {
+            req = req.host(host);
+        }
+        if let Some(accept) = req.header("accept") {
+            req = req.accept(accept);
+        }
+        if let Some(content_type) = req.header("content-type") {
+            req = req.content_type(content_type);
+        }
+        if let Some(cookies) = req.header("cookie") {
+            req = req.cookies(cookies);
+        }
+        if let Some(accept_language) = req.header("accept-language") {
+            req = req.accept_language(accept_language);
+        }
+        f(req)
+    }
+}
+
+impl<'a> From<&'a str> for MatchRequest<'a> {
+    fn from(path: &'a str) -> Self {
+        Self::new(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MatchRequest, RequestDetails};
+    use crate::Method;
+
+    #[test]
+    fn with_path_keeps_other_fields() {
+        let req = MatchRequest::new("/users/1")
+            .method(&Method::Post)
+            .host("example.com");
+        let child = req.with_path("/1");
+        assert_eq!(child.as_path(), "/1");
+        assert_eq!(child.as_method(), Some(&Method::Post));
+        assert_eq!(child.as_host(), Some("example.com"));
+        assert_eq!(child.as_accept(), None);
+    }
+
+    #[test]
+    fn reads_single_cookie() {
+        let req = MatchRequest::new("/").cookies("theme=dark; session=abc");
+        assert_eq!(req.cookie("session"), Some("abc"));
+        assert_eq!(req.cookie("theme"), Some("dark"));
+        assert_eq!(req.cookie("missing"), None);
+        assert_eq!(MatchRequest::new("/").cookie("session"), None);
+    }
+
+    #[test]
+    fn request_details_fill_in_the_match_request() {
+        let details = RequestDetails::new(Method::Post)
+            .with_header("Host", "acme.example.com")
+            .with_header("cookie", "tenant=acme")
+            .with_header("x-region", "eu");
+        details.with_match_request("/orders", Some("page=2"), |req| {
+            assert_eq!(req.as_path(), "/orders");
+            assert_eq!(req.as_query(), Some("page=2"));
+            assert_eq!(req.as_method(), Some(&Method::Post));
+            assert_eq!(req.as_host(), Some("acme.example.com"));
+            assert_eq!(req.cookie("tenant"), Some("acme"));
+            assert_eq!(req.header("X-Region"), Some("eu"));
+            assert_eq!(req.as_accept(), None);
+        });
+    }
+}
@@ -1,24 +1,66 @@
 #![allow(missing_docs)]
 
 mod any_choose_view;
+#[cfg(feature = "diagnostics")]
+mod candidates;
 mod choose_view;
+mod diff;
+pub use any_choose_view::AnyChooseView;
+pub use diff::RouteDiff;
 mod path_segment;
+mod resolve;
 pub(crate) mod resolve_path;
+mod rewrite;
+mod route_config;
+mod route_defaults;
+mod separator;
 pub use choose_view::*;
 pub use path_segment::*;
+pub use resolve::*;
+pub use route_config::*;
+pub use route_defaults::*;
+pub use separator::*;
 mod horizontal;
+mod match_request;
 mod nested;
+mod static_paths;
+pub(crate) mod structure_hash;
+mod trace;
+mod validate;
 mod vertical;
-use crate::{static_routes::RegenerationFn, Method, SsrMode};
+use crate::{
+    location::Url, params::ParamsMap, static_routes::RegenerationFn,
+    CanonicalPathConfig, ContentTypes, GuardResult, LanguageRedirect, Method,
+    QueryErrors, RouteGuard, SsrMode, SsrModePrecedence, SurrogateKeys,
+    TooManyPerPage, WebSocketHandler,
+};
 pub use horizontal::*;
+pub use match_request::*;
 pub use nested::*;
-use std::{borrow::Cow, collections::HashSet, sync::atomic::Ordering};
+use reactive_graph::owner::use_context;
+use rewrite::PathRewriters;
+use static_paths::StaticPaths;
+use std::{
+    any::{Any, TypeId},
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    sync::{atomic::Ordering, Arc},
+};
+pub use validate::*;
 pub use vertical::*;
 
 #[derive(Debug)]
 pub struct RouteDefs<Children> {
     base: Option<Cow<'static, str>>,
     children: Children,
+    static_paths: Option<Arc<StaticPaths>>,
+    method_not_allowed: Option<Cow<'static, str>>,
+    error: Option<Cow<'static, str>>,
+    canonical_paths: CanonicalPathConfig,
+    separator: SeparatorConfig,
+    defaults: RouteDefaults,
+    rewriters: PathRewriters,
+    trace_query: bool,
 }
 
 impl<Children> Clone for RouteDefs<Children>
@@ -29,6 +71,14 @@ where
         Self {
             base: self.base.clone(),
             children: self.children.clone(),
+            static_paths: self.static_paths.clone(),
+            method_not_allowed: self.method_not_allowed.clone(),
+            error: self.error.clone(),
+            canonical_paths: self.canonical_paths,
+            separator: self.separator,
+            defaults: self.defaults.clone(),
+            rewriters: self.rewriters.clone(),
+            trace_query: self.trace_query,
         }
     }
 }
@@ -38,6 +88,14 @@ impl<Children> RouteDefs<Children> {
         Self {
             base: None,
             children,
+            static_paths: None,
+            method_not_allowed: None,
+            error: None,
+            canonical_paths: Default::default(),
+            separator: Default::default(),
+            defaults: Default::default(),
+            rewriters: Default::default(),
+            trace_query: false,
         }
     }
 
@@ -48,34 +106,425 @@ impl<Children> RouteDefs<Children> {
         Self {
             base: Some(base.into()),
             children,
+            static_paths: None,
+            method_not_allowed: None,
+            error: None,
+            canonical_paths: Default::default(),
+            separator: Default::default(),
+            defaults: Default::default(),
+            rewriters: Default::default(),
+            trace_query: false,
         }
     }
+
+    /// Sets the base path that every path these routes match has to start with,
+    /// like [`new_with_base`](Self::new_with_base) does.
+    pub fn base(mut self, base: impl Into<Cow<'static, str>>) -> Self {
+        self.base = Some(base.into());
+        self
+    }
+
+    /// Sets the path of the route that handles requests whose method is not
+    /// accepted by the route that matches their path.
+    ///
+    /// This is the full path of one of these routes, including the base, so it
+    /// renders inside the same layouts as any other route at that path. See
+    /// [`resolve_error`](Self::resolve_error) for how it is matched.
+    pub fn on_method_not_allowed(
+        mut self,
+        path: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.method_not_allowed = Some(path.into());
+        self
+    }
+
+    /// Sets the path of the route that handles every other error, like
+    /// [`MatchError::Internal`], and method-not-allowed errors if no route is
+    /// set with [`on_method_not_allowed`](Self::on_method_not_allowed).
+    ///
+    /// Not-found errors and redirects are never handled by this route.
+    pub fn on_error(mut self, path: impl Into<Cow<'static, str>>) -> Self {
+        self.error = Some(path.into());
+        self
+    }
+
+    /// Sets the canonical form of the paths these routes serve, for
+    /// [`canonicalize`](Self::canonicalize).
+    pub fn canonical_paths(mut self, config: CanonicalPathConfig) -> Self {
+        self.canonical_paths = config;
+        self
+    }
+
+    /// Sets the character that separates the segments of the paths these routes
+    /// match, which is `/` by default.
+    pub fn separator(mut self, config: SeparatorConfig) -> Self {
+        self.separator = config;
+        self
+    }
+
+    /// Sets the HTTP methods handled by the routes that do not set their own
+    /// with [`NestedRoute::methods`], which are only `GET` by default.
+    ///
+    /// In an API, where most routes handle methods other than `GET`, this can
+    /// be empty, so that each route only handles the methods it declares, and
+    /// answers any other with `405 Method Not Allowed`. The methods added with
+    /// [`NestedRoute::custom_method`] are handled as well as the defaults.
+    pub fn default_methods(
+        mut self,
+        methods: impl IntoIterator<Item = Method>,
+    ) -> Self {
+        self.defaults = self.defaults.with_methods(methods);
+        self
+    }
+
+    /// Sets how the [`SsrMode`] of a route is combined with that of its parent,
+    /// instead of [`SsrMode::resolve`], which uses the more restrictive of the
+    /// two.
+    ///
+    /// The function is called with the mode of the parent and that of the
+    /// child, from the outermost route inwards. It is not called for the routes
+    /// that opt out of static rendering with [`NestedRoute::no_static`], which
+    /// never become static.
+    pub fn ssr_mode_precedence(
+        mut self,
+        precedence: SsrModePrecedence,
+    ) -> Self {
+        self.defaults = self.defaults.with_ssr_mode_precedence(precedence);
+        self
+    }
+
+    /// Adds a function that rewrites the path of each request before it is
+    /// matched, like one that maps legacy URLs to their current routes, or
+    /// strips a tracking segment.
+    ///
+    /// The path is rewritten once, before any route is tried, by each rewriter
+    /// in the order they were added, with the output of one as the input of the
+    /// next. A rewriter returns the path it was given, borrowed, to leave it as
+    /// it is. The routes are matched against the rewritten path, so their
+    /// params are read from it, and the path as it was requested is still
+    /// available with [`MatchSuccess::original_path`].
+    ///
+    /// ```rust
+    /// use leptos_router::{
+    ///     NestedRoute, ParamSegment, RouteDefs, StaticSegment,
+    /// };
+    /// use std::borrow::Cow;
+    ///
+    /// let routes = RouteDefs::new(NestedRoute::new(
+    ///     (StaticSegment("blog"), ParamSegment("slug")),
+    ///     || (),
+    /// ))
+    /// .rewrite(|path| match path.strip_prefix("/old-blog/") {
+    ///     Some(slug) => Cow::Owned(format!("/blog/{slug}")),
+    ///     None => Cow::Borrowed(path),
+    /// });
+    ///
+    /// let matched = routes.resolve("/old-blog/hello").unwrap();
+    /// assert_eq!(matched.original_path(), Some("/old-blog/hello"));
+    /// ```
+    pub fn rewrite(
+        mut self,
+        rewriter: impl Fn(&str) -> Cow<'_, str> + Send + Sync + 'static,
+    ) -> Self {
+        self.rewriters.push(rewriter);
+        self
+    }
+
+    /// Sets whether the spans for resolving requests, with the `tracing`
+    /// feature, record the query of each request as well as its path.
+    ///
+    /// This is `false` by default, because queries often carry data like tokens
+    /// or email addresses that should not end up in logs.
+    pub fn trace_query(mut self, trace_query: bool) -> Self {
+        self.trace_query = trace_query;
+        self
+    }
+
+    /// Returns the canonical form of `path`, according to the
+    /// [`canonical_paths`](Self::canonical_paths) of these routes, or `None` if
+    /// it is already canonical.
+    ///
+    /// The server integrations can use this to redirect requests permanently
+    /// (with `301 Moved Permanently`) to the canonical URL of a page.
+    pub fn canonicalize(&self, path: &str) -> Option<String> {
+        self.canonical_paths.canonicalize(path)
+    }
 }
 
 impl<Children> RouteDefs<Children>
 where
     Children: MatchNestedRoutes,
 {
+    /// Builds a table of the routes whose segments are all static, so that
+    /// matching one of their paths is a single lookup, rather than testing
+    /// every route that comes before it.
+    ///
+    /// Other paths are still matched by walking the route definitions, and
+    /// every path matches the same route as it would without the table. Static
+    /// routes that come after a route with params have to be checked against
+    /// the whole tree while building it, so this is worth it for large sets of
+    /// routes that are matched many times.
+    pub fn compile(mut self) -> Self {
+        self.static_paths = Some(Arc::new(StaticPaths::compile(
+            &self.children,
+            &self.defaults,
+        )));
+        self
+    }
+
     pub fn match_route(&self, path: &str) -> Option<Children::Match> {
-        let path = match &self.base {
-            None => path,
+        self.resolve(path).ok().map(MatchSuccess::into_route)
+    }
+
+    /// Matches the routes against the current request or navigation to `url`.
+    ///
+    /// On the server, this is the [request](RequestDetails) the server
+    /// integration provides, with its method and headers. Otherwise, it is the
+    /// path and query of `url`, with the cookies of the document in the
+    /// browser.
+    pub(crate) fn match_url(&self, url: &Url) -> Option<Children::Match> {
+        let matched = match use_context::<RequestDetails>() {
+            Some(request) => request.with_match_request(
+                url.path(),
+                Some(url.search()),
+                |req| self.resolve_request(req).ok(),
+            ),
+            None => {
+                let req = MatchRequest::new(url.path()).query(url.search());
+                #[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
+                let cookies = document_cookies();
+                #[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
+                let req = match &cookies {
+                    Some(cookies) => req.cookies(cookies),
+                    None => req,
+                };
+                self.resolve_request(req).ok()
+            }
+        };
+        matched.map(MatchSuccess::into_route)
+    }
+
+    /// Resolves the path against these route definitions, returning either the
+    /// matched route or the reason no route could be matched.
+    pub fn resolve(
+        &self,
+        path: &str,
+    ) -> Result<MatchSuccess<Children::Match>, MatchError> {
+        self.resolve_request(MatchRequest::new(path))
+    }
+
+    /// Resolves a request against these route definitions, returning either the
+    /// matched route or the reason no route could be matched.
+    ///
+    /// If the request has a method that the matched route does not accept, this
+    /// is a [`MatchError::MethodNotAllowed`], which is
+    /// [handled](Self::resolve_error) by the route set with
+    /// [`on_method_not_allowed`](Self::on_method_not_allowed) or
+    /// [`on_error`](Self::on_error), if there is one. Likewise, if the request
+    /// has a query that the matched route
+    /// [does not accept](NestedRoute::validate_query), this is a
+    /// [`MatchError::InvalidQuery`], which is handled by the `on_error` route.
+    ///
+    /// Resolving is synchronous and does not render anything, so when streaming
+    /// a response, these checks can decide its status, and headers like
+    /// [`Allow`](MatchError::allow_header), before its first byte is sent.
+    pub fn resolve_request(
+        &self,
+        req: MatchRequest<'_>,
+    ) -> Result<MatchSuccess<Children::Match>, MatchError> {
+        let path = self.rewriters.rewrite(req.as_path());
+        let original_path = match &path {
+            Cow::Owned(rewritten) if rewritten != req.as_path() => {
+                Some(req.as_path().to_string())
+            }
+            _ => None,
+        };
+        let req = req
+            .with_path(&path)
+            .separated_by(self.separator)
+            .with_default_methods(self.defaults.methods());
+        let matched = trace::resolve_span(&req, self.trace_query, || {
+            self.resolve_with_separator(req)
+        })?;
+        Ok(match original_path {
+            Some(original_path) => matched.rewritten_from(original_path),
+            None => matched,
+        })
+    }
+
+    /// Resolves a request against these route definitions, like
+    /// [`resolve_request`](Self::resolve_request), and then checks it against
+    /// the [guards](NestedRoute::guard_async) of the matched routes.
+    ///
+    /// The guards are awaited one at a time, those of each route before those
+    /// of its children, and the first one that does not allow the request
+    /// decides the result: a redirect is a [`MatchError::Redirect`], and a
+    /// forbidden request is a [`MatchError::Forbidden`], which is
+    /// [handled](Self::resolve_error) by the [`on_error`](Self::on_error)
+    /// route, if there is one. The guards of a route that handles an error are
+    /// not checked.
+    pub async fn resolve_request_async(
+        &self,
+        req: MatchRequest<'_>,
+    ) -> Result<MatchSuccess<Children::Match>, MatchError> {
+        let matched = self.resolve_request(req)?;
+        if matched.error().is_some() {
+            return Ok(matched);
+        }
+        let guards = matched.route().guards();
+        if guards.is_empty() {
+            return Ok(matched);
+        }
+        let params = matched
+            .route()
+            .to_params()
+            .into_iter()
+            .collect::<ParamsMap>();
+        match RouteGuard::check_all(&guards, &params).await {
+            GuardResult::Allow => Ok(matched),
+            GuardResult::Redirect(redirect) => {
+                Err(MatchError::Redirect(redirect))
+            }
+            GuardResult::Forbid => {
+                self.resolve_error(MatchError::Forbidden, req)
+            }
+        }
+    }
+
+    fn resolve_with_separator(
+        &self,
+        req: MatchRequest<'_>,
+    ) -> Result<MatchSuccess<Children::Match>, MatchError> {
+        let separator = self.separator.separator();
+        let mut with_separator = String::new();
+        let path = self
+            .path_under_base(req.as_path(), &mut with_separator)
+            .ok_or(MatchError::NotFound)?;
+
+        let req = req.with_path(path);
+        let (id, matched) = self
+            .static_paths
+            .as_ref()
+            // the table is built from the paths the routes generate, which use
+            // `/`
+            .filter(|_| separator == '/')
+            .and_then(|static_paths| static_paths.get(path))
+            .and_then(|branch| {
+                // the route at this branch can still refuse the request (for
+                // example, because of its query), in which case the rest of the
+                // routes are tried as usual
+                match self.children.match_branch(branch, req) {
+                    (Some(matched), remaining)
+                        if self.separator.is_empty_path(remaining) =>
+                    {
+                        Some(matched)
+                    }
+                    _ => None,
+                }
+            })
+            .or_else(|| match self.children.match_request(req) {
+                (Some(matched), remaining)
+                    if self.separator.is_empty_path(remaining) =>
+                {
+                    Some(matched)
+                }
+                _ => None,
+            })
+            .ok_or(MatchError::NotFound)?;
+
+        if let Some(method) = req.as_method() {
+            if let Some(allowed) = matched
+                .allowed_methods()
+                .filter(|allowed| !allowed.contains(method))
+            {
+                return self
+                    .resolve_error(MatchError::MethodNotAllowed(allowed), req);
+            }
+            if let Some(consumes) = matched.consumes().filter(|consumes| {
+                consumes.applies_to(method.as_str())
+                    && !consumes.accepts(req.as_content_type())
+            }) {
+                return self.resolve_error(
+                    MatchError::UnsupportedMediaType(consumes),
+                    req,
+                );
+            }
+        }
+        if let Some(error) = matched.pagination_error() {
+            return self
+                .resolve_error(MatchError::InvalidPagination(error), req);
+        }
+        match matched.query_errors() {
+            Some(errors) => {
+                self.resolve_error(MatchError::InvalidQuery(errors), req)
+            }
+            None => Ok(MatchSuccess::new(id, matched)),
+        }
+    }
+
+    /// The part of `path` the routes are matched against, which is the part
+    /// after the base, or `None` if it is not under the base.
+    fn path_under_base<'a>(
+        &self,
+        path: &'a str,
+        with_separator: &'a mut String,
+    ) -> Option<&'a str> {
+        let separator = self.separator.separator();
+        let path = match path {
+            // the segments only match from the start of the path if it starts
+            // with a separator, which URLs always do, but other paths may not
+            path if separator != '/' && !path.starts_with(separator) => {
+                *with_separator = format!("{separator}{path}");
+                with_separator.as_str()
+            }
+            path => path,
+        };
+        match &self.base {
+            None => Some(path),
             Some(base) => {
                 let (base, path) = if base.starts_with('/') {
                     (base.trim_start_matches('/'), path.trim_start_matches('/'))
                 } else {
                     (base.as_ref(), path)
                 };
-                path.strip_prefix(base)?
+                path.strip_prefix(base)
             }
-        };
-
-        let (matched, remaining) = self.children.match_nested(path);
-        let matched = matched?;
+        }
+    }
 
-        if !(remaining.is_empty() || remaining == "/") {
-            None
-        } else {
-            Some(matched.1)
+    /// Resolves the route registered to handle `error`, for a request that
+    /// failed with it.
+    ///
+    /// The handler is matched as though `req` had been made to its path, with
+    /// no method or query, so its params are those in its own path, and it
+    /// provides the same context as it would for a request to that path: the
+    /// handler route and every one of its ancestors are matched and provide
+    /// their contexts, so it renders inside its layouts. The original error is
+    /// available with [`MatchSuccess::error`], and gives the response its
+    /// [status code](MatchSuccess::status_code).
+    ///
+    /// Returns the error if no handler is registered for it, or if the
+    /// handler's path does not match any route.
+    pub fn resolve_error(
+        &self,
+        error: MatchError,
+        req: MatchRequest<'_>,
+    ) -> Result<MatchSuccess<Children::Match>, MatchError> {
+        let handler = match &error {
+            MatchError::NotFound | MatchError::Redirect(_) => None,
+            MatchError::MethodNotAllowed(_) => {
+                self.method_not_allowed.as_ref().or(self.error.as_ref())
+            }
+            _ => self.error.as_ref(),
+        };
+        let Some(handler) = handler else {
+            return Err(error);
+        };
+        match self.resolve_request(
+            req.with_path(handler).without_method().without_query(),
+        ) {
+            Ok(matched) => Ok(matched.handling(error)),
+            Err(_) => Err(error),
         }
     }
 
@@ -85,7 +534,110 @@ where
         Option<&str>,
         impl IntoIterator<Item = GeneratedRouteData> + '_,
     ) {
-        (self.base.as_deref(), self.children.generate_routes())
+        (self.base.as_deref(), self.generated_routes())
+    }
+
+    /// The routes, with the [default methods](Self::default_methods) of these
+    /// route definitions for those that do not set their own.
+    fn generated_routes(&self) -> Vec<GeneratedRouteData> {
+        self.children
+            .generate_routes(&self.defaults)
+            .into_iter()
+            .collect()
+    }
+
+    /// Checks the route definitions for routes that can never be matched, or
+    /// that are likely to be mistakes: routes shadowed by an earlier sibling,
+    /// params that are declared more than once in the same chain of nested
+    /// routes, and segments that follow a wildcard.
+    pub fn validate(&self) -> Vec<RouteWarning> {
+        validate_routes(self.generated_routes())
+    }
+
+    /// Finds the routes that can never be matched because every path they match
+    /// is matched first by an earlier route with a wildcard segment, which
+    /// consumes the rest of the path.
+    ///
+    /// Unlike [`validate`](Self::validate), this identifies the routes involved
+    /// by their [`RouteMatchId`], so they can be traced back to their
+    /// definitions.
+    pub fn check_conflicts(&self) -> Vec<RouteConflict> {
+        check_conflicts(self.generated_routes())
+    }
+
+    /// A hash of the structure of these route definitions: the base, and the
+    /// path pattern, methods and [`SsrMode`] of every route.
+    ///
+    /// The hash does not depend on the order in which routes are defined, or on
+    /// anything that varies between runs (like route IDs), so it can be
+    /// compared across builds to tell whether the routes have changed.
+    pub fn structure_hash(&self) -> u64 {
+        structure_hash::structure_hash(
+            self.base.as_deref(),
+            self.generated_routes(),
+        )
+    }
+
+    /// Compares these route definitions with `new`, the route definitions that
+    /// replace them, to find which routes [persisted](RouteDiff::persisted) by
+    /// their path pattern, and which were changed, added or removed, like when
+    /// the routes are reloaded during development.
+    ///
+    /// Views are not compared, so a route whose view changed but whose pattern,
+    /// methods and [`SsrMode`] did not still persists, and can keep its state.
+    pub fn diff<Other>(&self, new: &RouteDefs<Other>) -> RouteDiff
+    where
+        Other: MatchNestedRoutes,
+    {
+        diff::diff_routes(self.generated_routes(), new.generated_routes())
+    }
+
+    /// Every route whose segments match `path`, most specific first, rather
+    /// than only the one that [`resolve`](Self::resolve) would choose, to find
+    /// out why a route won or whether the routes are ambiguous.
+    ///
+    /// This is a dry run: each route is tried on its own, whatever would match
+    /// first, and the methods and
+    /// [custom matchers](NestedRoute::custom_matcher) of the routes are
+    /// ignored. Routes that are as specific as each other are in the order they
+    /// are declared, which is the order `resolve` tries them in.
+    #[cfg(feature = "diagnostics")]
+    pub fn candidates(&self, path: &str) -> Vec<RouteMatchId> {
+        let path = self.rewriters.rewrite(path);
+        let mut with_separator = String::new();
+        let Some(path) = self.path_under_base(&path, &mut with_separator)
+        else {
+            return Vec::new();
+        };
+        let req = MatchRequest::new(path)
+            .separated_by(self.separator)
+            .with_default_methods(self.defaults.methods())
+            .skipping_matchers();
+        let mut candidates =
+            Vec::<(Vec<usize>, RouteMatchId, candidates::Specificity)>::new();
+        for route in self.children.generate_routes(&self.defaults) {
+            // a route with optional params generates a route for each
+            // combination of them, which are all the same candidate
+            if candidates
+                .iter()
+                .any(|(branch, ..)| *branch == route.branch)
+            {
+                continue;
+            }
+            let matched = match self.children.match_branch(&route.branch, req) {
+                (Some((id, _)), remaining)
+                    if self.separator.is_empty_path(remaining) =>
+                {
+                    route.id.unwrap_or(id)
+                }
+                _ => continue,
+            };
+            let specificity = candidates::Specificity::of(&route.segments);
+            candidates.push((route.branch, matched, specificity));
+        }
+        // the sort is stable, so routes that are as specific keep their order
+        candidates.sort_by(|(_, _, a), (_, _, b)| b.cmp(a));
+        candidates.into_iter().map(|(_, id, _)| id).collect()
     }
 }
 
@@ -109,22 +661,127 @@ pub trait MatchInterface {
 
     fn as_matched(&self) -> &str;
 
+    /// The components of the path that this route [matched](Self::as_matched),
+    /// without the separators between them, so that `/users/42` is
+    /// `["users", "42"]`.
+    ///
+    /// These are the raw components of the path, so an encoded separator (like
+    /// `%2F`) inside a component does not split it.
+    fn matched_segments(&self) -> Vec<&str>;
+
     fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>);
+
+    /// Provides the context values that this route makes available to its
+    /// subtree, with [`NestedRoute::provide`].
+    ///
+    /// This is called in the reactive owner of the matched route, so the values
+    /// are available to its view and to any nested routes, and are removed when
+    /// the route leaves the matched chain.
+    fn provide_route_contexts(&self) {}
+
+    /// The HTTP methods accepted by the matched chain of routes, or `None` if
+    /// it accepts any method.
+    ///
+    /// As with the [`methods`](GeneratedRouteData::methods) of the generated
+    /// routes, a nested route accepts its own methods and those of its
+    /// ancestors.
+    fn allowed_methods(&self) -> Option<HashSet<Method>> {
+        None
+    }
+
+    /// The errors in the query of the request, if the matched chain of routes
+    /// [validates it](NestedRoute::validate_query) and it is invalid.
+    fn query_errors(&self) -> Option<QueryErrors> {
+        None
+    }
+
+    /// Why the page params of the request are rejected, if the matched chain of
+    /// routes is [paginated](NestedRoute::with_pagination) and rejects them.
+    fn pagination_error(&self) -> Option<TooManyPerPage> {
+        None
+    }
+
+    /// The content types the bodies of requests to the matched route must have,
+    /// from the innermost route in its chain that
+    /// [restricts them](NestedRoute::consumes).
+    fn consumes(&self) -> Option<ContentTypes> {
+        None
+    }
+
+    /// The functions that return the surrogate keys of the responses from the
+    /// matched route, of the routes in its chain that
+    /// [set them](NestedRoute::surrogate_key), outermost first.
+    fn surrogate_keys(&self) -> Option<SurrogateKeys> {
+        None
+    }
+
+    /// The handler of the matched route's connections, if it is a
+    /// [WebSocket route](NestedRoute::websocket) rather than a route that
+    /// renders a view.
+    fn websocket(&self) -> Option<WebSocketHandler> {
+        None
+    }
+
+    /// The [guards](NestedRoute::guard_async) of the matched chain of routes,
+    /// with those of each route before those of its children.
+    fn guards(&self) -> Vec<RouteGuard> {
+        Vec::new()
+    }
+
+    /// The metadata of type `type_id` attached to the matched chain of routes,
+    /// with [`NestedRoute::meta`]. Use [`meta`](Self::meta) to read it as its
+    /// type.
+    fn meta_of(&self, type_id: TypeId) -> Option<&(dyn Any + Send + Sync)> {
+        _ = type_id;
+        None
+    }
+
+    /// The metadata of type `T` attached to the matched chain of routes, with
+    /// [`NestedRoute::meta`].
+    ///
+    /// As with the [`websocket`](Self::websocket) handler, the metadata of a
+    /// nested route takes precedence over that of its ancestors, so a parent
+    /// route's metadata acts as the default for its children.
+    fn meta<T>(&self) -> Option<&T>
+    where
+        T: Any + Send + Sync,
+    {
+        self.meta_of(TypeId::of::<T>())?.downcast_ref()
+    }
 }
 
 pub trait MatchParams {
+    /// The params of the matched route, in the order they are defined in, with
+    /// the params of each route before those of its children.
+    ///
+    /// An optional param that is absent from the path is left out, so params
+    /// should be looked up by key rather than by position.
     fn to_params(&self) -> Vec<(Cow<'static, str>, String)>;
+
+    /// The [params](Self::to_params) of the matched route, keyed by name.
+    ///
+    /// If several params have the same name, the last one wins, as it does when
+    /// a map is extended, so the value of a nested route replaces that of its
+    /// ancestors, as in [`use_params_map`](crate::hooks::use_params_map). The
+    /// values are not percent-decoded.
+    fn params_map(&self) -> HashMap<String, String> {
+        self.to_params()
+            .into_iter()
+            .map(|(key, value)| (key.into_owned(), value))
+            .collect()
+    }
 }
 
 pub trait MatchNestedRoutes {
     type Data;
     type Match: MatchInterface + MatchParams;
 
-    /// Matches nested routes
+    /// Matches nested routes against a request.
     ///
     /// # Arguments
     ///
-    /// * path - A path which is being navigated to
+    /// * req - The request which is being matched, including the path which is
+    ///   being navigated to
     ///
     /// # Returns
     ///
@@ -134,34 +791,96 @@ pub trait MatchNestedRoutes {
     ///     * 0 - [RouteMatchId] identifying the matching route
     ///     * 1 - [Self::Match] matching route
     /// * 1 - Remaining path
+    fn match_request<'a>(
+        &'a self,
+        req: MatchRequest<'a>,
+    ) -> (Option<(RouteMatchId, Self::Match)>, &'a str);
+
+    /// Matches nested routes against a path only.
+    ///
+    /// This is equivalent to calling [`match_request`](Self::match_request)
+    /// with a [`MatchRequest`] that contains only the path.
     fn match_nested<'a>(
         &'a self,
         path: &'a str,
-    ) -> (Option<(RouteMatchId, Self::Match)>, &'a str);
+    ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
+        self.match_request(MatchRequest::new(path))
+    }
+
+    /// Matches the request against only the route at `branch`, skipping all of
+    /// its siblings.
+    ///
+    /// `branch` is the [`branch`](GeneratedRouteData::branch) of one of the
+    /// routes generated by [`generate_routes`](Self::generate_routes). The
+    /// result is the same as that of [`match_request`](Self::match_request) as
+    /// long as none of the routes that come before it would match the request.
+    fn match_branch<'a>(
+        &'a self,
+        branch: &[usize],
+        req: MatchRequest<'a>,
+    ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
+        _ = branch;
+        self.match_request(req)
+    }
 
+    /// Generates the routes these route definitions can match, with the
+    /// [`RouteDefaults`] of the router for the options that they do not set
+    /// themselves.
     fn generate_routes(
         &self,
-    ) -> impl IntoIterator<Item = GeneratedRouteData> + '_;
+        defaults: &RouteDefaults,
+    ) -> impl IntoIterator<Item = GeneratedRouteData>;
 
     fn optional(&self) -> bool;
 }
 
-#[derive(Default, Debug, PartialEq)]
+/// The cookies of the document, which the browser sends with every request to
+/// the server.
+#[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
+fn document_cookies() -> Option<String> {
+    use wasm_bindgen::JsCast;
+
+    tachys::dom::document()
+        .dyn_into::<web_sys::HtmlDocument>()
+        .ok()?
+        .cookie()
+        .ok()
+}
+
+#[derive(Clone, Default, Debug, PartialEq)]
 pub struct GeneratedRouteData {
     pub segments: Vec<PathSegment>,
     pub ssr_mode: SsrMode,
+    /// Whether this route, or one of its ancestors, is kept dynamic with
+    /// [`NestedRoute::no_static`], in which case its mode is never
+    /// [`SsrMode::Static`].
+    pub no_static: bool,
     pub methods: HashSet<Method>,
     pub regenerate: Vec<RegenerationFn>,
+    /// The options the server integrations apply to the requests to this
+    /// route, merged with those of its ancestors.
+    pub config: RouteConfig,
+    /// How requests to this route are redirected to a path with a locale, if it
+    /// is the path without a locale of a route set with
+    /// [`NestedRoute::language_redirect`].
+    pub language_redirect: Option<LanguageRedirect>,
+    /// The index of this route among its siblings, at each level of nesting.
+    pub branch: Vec<usize>,
+    /// The ID of the innermost route in this chain, which is the ID it is
+    /// [matched](MatchInterface::as_id) with.
+    pub id: Option<RouteMatchId>,
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{NestedRoute, ParamSegment, RouteDefs};
+    use super::{MatchError, NestedRoute, ParamSegment, RouteDefs};
     use crate::{
-        matching::MatchParams, MatchInterface, PathSegment, StaticSegment,
-        WildcardSegment,
+        matching::MatchParams, HmacAlgorithm, MatchInterface, Method,
+        OptionalParamSegment, PathSegment, Secret, StaticSegment,
+        WebhookSignatureConfig, WildcardSegment,
     };
-    use either_of::{Either, EitherOf4};
+    use either_of::{Either, EitherOf32, EitherOf4};
+    use std::borrow::Cow;
 
     #[test]
     pub fn matches_single_root_route() {
@@ -213,6 +932,80 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn nested_params_replace_those_of_their_ancestors_in_the_map() {
+        let routes: RouteDefs<_> = RouteDefs::new(
+            NestedRoute::new(
+                (StaticSegment("users"), ParamSegment("id")),
+                || (),
+            )
+            .child(NestedRoute::new(
+                (StaticSegment("posts"), WildcardSegment("id")),
+                || (),
+            )),
+        );
+        let matched = routes.match_route("/users/7/posts/42").unwrap();
+        assert_eq!(
+            matched.to_params(),
+            vec![("id".into(), "7".into()), ("id".into(), "42".into())]
+        );
+        let params = matched.params_map();
+        assert_eq!(params.len(), 1);
+        assert_eq!(params["id"], "42");
+    }
+
+    #[test]
+    pub fn matched_segments_are_split_per_route() {
+        let routes: RouteDefs<_> = RouteDefs::new(
+            NestedRoute::new(
+                (StaticSegment("api"), StaticSegment("v1")),
+                || (),
+            )
+            .child(NestedRoute::new(
+                (StaticSegment("files"), ParamSegment("name")),
+                || (),
+            )),
+        );
+
+        let matched = routes.match_route("/api/v1/files/a%2Fb").unwrap();
+        assert_eq!(matched.matched_segments(), ["api", "v1"]);
+        let (_, child) = MatchInterface::into_view_and_child(matched);
+        let child = child.unwrap();
+        assert_eq!(MatchInterface::as_matched(&child), "/files/a%2Fb");
+        // an encoded separator stays in its component
+        assert_eq!(child.matched_segments(), ["files", "a%2Fb"]);
+    }
+
+    #[test]
+    pub fn route_meta_is_read_back_by_type() {
+        #[derive(Debug, PartialEq)]
+        struct Tags(&'static [&'static str]);
+        #[derive(Debug, PartialEq)]
+        struct Permission(&'static str);
+
+        let routes: RouteDefs<_> = RouteDefs::new(
+            NestedRoute::new(StaticSegment("admin"), || ())
+                .meta(Permission("admin"))
+                .child((
+                    NestedRoute::new(StaticSegment("users"), || ())
+                        .meta(Tags(&["users", "admin"])),
+                    NestedRoute::new(StaticSegment("billing"), || ())
+                        .meta(Permission("billing"))
+                        .meta(Tags(&["billing"])),
+                )),
+        );
+
+        let matched = routes.match_route("/admin/users").unwrap();
+        assert_eq!(matched.meta::<Tags>(), Some(&Tags(&["users", "admin"])));
+        // inherited from the parent route
+        assert_eq!(matched.meta::<Permission>(), Some(&Permission("admin")));
+        assert_eq!(matched.meta::<String>(), None);
+
+        let matched = routes.match_route("/admin/billing").unwrap();
+        assert_eq!(matched.meta::<Tags>(), Some(&Tags(&["billing"])));
+        assert_eq!(matched.meta::<Permission>(), Some(&Permission("billing")));
+    }
+
     #[test]
     pub fn does_not_match_route_unless_full_param_matches() {
         let routes = RouteDefs::<_>::new((
@@ -340,65 +1133,1781 @@ mod tests {
     }
 
     #[test]
-    pub fn dont_match_smooshed_static_segments() {
+    pub fn resolve_reports_not_found() {
+        let routes = RouteDefs::<_>::new_with_base(
+            (
+                NestedRoute::new(StaticSegment("users"), || ()),
+                NestedRoute::new(
+                    (StaticSegment("users"), ParamSegment("id")),
+                    || (),
+                ),
+            ),
+            "/app",
+        );
+
+        let resolved = routes.resolve("/app/users/42").unwrap();
+        assert_ne!(resolved.id(), routes.resolve("/app/users").unwrap().id());
+        assert_eq!(
+            resolved.route().to_params(),
+            vec![("id".into(), "42".into())]
+        );
+
+        assert_eq!(
+            routes.resolve("/app/posts").unwrap_err(),
+            MatchError::NotFound
+        );
+        assert_eq!(routes.resolve("/users").unwrap_err(), MatchError::NotFound);
+        assert_eq!(MatchError::NotFound.status_code(), 404);
+    }
+
+    #[test]
+    pub fn async_guards_allow_or_redirect() {
+        use crate::{GuardResult, MatchRequest, RouteRedirect};
+        use futures::executor::block_on;
+
         let routes = RouteDefs::<_>::new((
-            NestedRoute::new(StaticSegment(""), || ()),
-            NestedRoute::new(StaticSegment("users"), || ()),
-            NestedRoute::new(
-                (StaticSegment("users"), StaticSegment("id")),
-                || (),
+            NestedRoute::new(StaticSegment("home"), || ())
+                .guard_async(|_| async { GuardResult::Allow }),
+            NestedRoute::new(StaticSegment("account"), || ()).guard_async(
+                |_| async {
+                    GuardResult::Redirect(RouteRedirect::new("/login"))
+                },
             ),
-            NestedRoute::new(WildcardSegment("any"), || ()),
         ));
 
-        let matched = routes.match_route("/users");
-        assert!(matches!(matched, Some(EitherOf4::B(..))));
+        let allowed =
+            block_on(routes.resolve_request_async(MatchRequest::new("/home")));
+        assert_eq!(allowed.unwrap().status_code(), 200);
+        assert_eq!(
+            block_on(
+                routes.resolve_request_async(MatchRequest::new("/account"))
+            )
+            .unwrap_err(),
+            MatchError::Redirect(RouteRedirect::new("/login"))
+        );
+        // guards are not checked by the synchronous resolver
+        assert!(routes.resolve("/account").is_ok());
+    }
 
-        let matched = routes.match_route("/users/id");
-        assert!(matches!(matched, Some(EitherOf4::C(..))));
+    #[test]
+    pub fn ancestor_guards_run_before_child_guards() {
+        use crate::{GuardResult, MatchRequest};
+        use futures::executor::block_on;
+        use std::sync::{Arc, Mutex};
 
-        let matched = routes.match_route("/usersid");
-        assert!(matches!(matched, Some(EitherOf4::D(..))));
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("admin"), || ())
+                .guard_async({
+                    let order = Arc::clone(&order);
+                    move |_| {
+                        let order = Arc::clone(&order);
+                        async move {
+                            order.lock().unwrap().push("admin");
+                            GuardResult::Allow
+                        }
+                    }
+                })
+                .child((
+                    NestedRoute::new(StaticSegment("users"), || ()).guard({
+                        let order = Arc::clone(&order);
+                        move |_| {
+                            order.lock().unwrap().push("users");
+                            false
+                        }
+                    }),
+                    NestedRoute::new(StaticSegment("logs"), || ()),
+                )),
+        )
+        .on_error("/admin/logs");
+
+        let forbidden = block_on(
+            routes.resolve_request_async(MatchRequest::new("/admin/users")),
+        )
+        .unwrap();
+        assert_eq!(*order.lock().unwrap(), ["admin", "users"]);
+        // a forbidden request is handled by the error route, without checking
+        // its guards again
+        assert_eq!(forbidden.error(), Some(&MatchError::Forbidden));
+        assert_eq!(forbidden.status_code(), 403);
     }
-}
 
-/// Successful result of [testing](PossibleRouteMatch::test) a single segment in the route path
-#[derive(Debug)]
-pub struct PartialPathMatch<'a> {
-    /// unmatched yet part of the path
-    pub(crate) remaining: &'a str,
-    /// value of parameters encoded inside of the path
-    pub(crate) params: Vec<(Cow<'static, str>, String)>,
-    /// part of the original path that was matched by segment
-    pub(crate) matched: &'a str,
-}
+    #[test]
+    pub fn resolve_reports_method_not_allowed() {
+        use crate::MatchRequest;
 
-impl<'a> PartialPathMatch<'a> {
-    pub fn new(
-        remaining: &'a str,
-        params: Vec<(Cow<'static, str>, String)>,
-        matched: &'a str,
-    ) -> Self {
-        Self {
-            remaining,
-            params,
-            matched,
-        }
-    }
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("users"), || ()).child((
+                NestedRoute::new(StaticSegment(""), || ()),
+                NestedRoute::new(StaticSegment("new"), || ())
+                    .custom_method("POST"),
+            )),
+        );
 
-    pub fn is_complete(&self) -> bool {
-        self.remaining.is_empty() || self.remaining == "/"
+        let post = Method::Post;
+        let get = Method::Get;
+        assert!(routes
+            .resolve_request(MatchRequest::new("/users/new").method(&post))
+            .is_ok());
+        // children accept the methods of their ancestors
+        assert!(routes
+            .resolve_request(MatchRequest::new("/users/new").method(&get))
+            .is_ok());
+        assert_eq!(
+            routes
+                .resolve_request(MatchRequest::new("/users").method(&post))
+                .unwrap_err(),
+            MatchError::MethodNotAllowed([Method::Get].into())
+        );
+        // the method is ignored when it is not known
+        assert!(routes.resolve("/users").is_ok());
+        assert_eq!(
+            routes
+                .clone()
+                .compile()
+                .resolve_request(MatchRequest::new("/users").method(&post))
+                .unwrap_err(),
+            MatchError::MethodNotAllowed([Method::Get].into())
+        );
     }
 
-    pub fn remaining(&self) -> &'a str {
-        self.remaining
-    }
+    #[test]
+    pub fn routes_without_methods_use_the_default_methods_of_the_router() {
+        use crate::MatchRequest;
 
-    pub fn params(self) -> Vec<(Cow<'static, str>, String)> {
-        self.params
-    }
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("api"), || ()).child((
+                NestedRoute::new(StaticSegment("status"), || ()),
+                NestedRoute::new(StaticSegment("users"), || ())
+                    .methods([Method::Get, Method::Post]),
+                NestedRoute::new(StaticSegment("cache"), || ())
+                    .custom_method("PURGE")
+                    // not a method name, so it is ignored
+                    .custom_method("PURGE ALL"),
+            )),
+        )
+        .default_methods([]);
+
+        let get = Method::Get;
+        let post = Method::Post;
+        let resolve = |path, method| {
+            routes.resolve_request(MatchRequest::new(path).method(method))
+        };
+        let error = resolve("/api/status", &get).unwrap_err();
+        assert_eq!(error, MatchError::MethodNotAllowed([].into()));
+        assert_eq!(error.status_code(), 405);
+        assert!(resolve("/api/users", &get).is_ok());
+        assert!(resolve("/api/users", &post).is_ok());
+        assert_eq!(
+            resolve("/api/cache", &get).unwrap_err(),
+            MatchError::MethodNotAllowed([Method::from_name("PURGE")].into())
+        );
+
+        let (_, generated) = routes.generate_routes();
+        let methods = generated
+            .into_iter()
+            .map(|route| route.methods)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            methods,
+            [
+                [].into(),
+                [Method::Get, Method::Post].into(),
+                [Method::from_name("PURGE")].into(),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn method_not_allowed_is_decided_before_streaming() {
+        use crate::MatchRequest;
+        use futures::{executor::block_on, stream, StreamExt};
+
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(
+                (StaticSegment("users"), ParamSegment("id")),
+                || (),
+            )
+            .custom_method("GET")
+            .custom_method("DELETE"),
+        );
+
+        // like the streaming modes, the status and headers are sent before the
+        // body, which is only rendered if the request was resolved to a route
+        let respond = |req: MatchRequest<'_>| {
+            let (status, headers, body) = match routes.resolve_request(req) {
+                Ok(matched) => (
+                    matched.status_code(),
+                    vec![],
+                    Some(stream::iter(["<p>", "user", "</p>"])),
+                ),
+                Err(error) => (
+                    error.status_code(),
+                    error
+                        .allow_header()
+                        .map(|allow| ("allow", allow))
+                        .into_iter()
+                        .collect::<Vec<_>>(),
+                    None,
+                ),
+            };
+            let body = block_on(async {
+                match body {
+                    Some(body) => body.collect::<Vec<_>>().await.concat(),
+                    None => String::new(),
+                }
+            });
+            (status, headers, body)
+        };
+
+        let get = Method::Get;
+        let post = Method::Post;
+        assert_eq!(
+            respond(MatchRequest::new("/users/42").method(&get)),
+            (200, vec![], "<p>user</p>".to_string())
+        );
+        assert_eq!(
+            respond(MatchRequest::new("/users/42").method(&post)),
+            (
+                405,
+                vec![("allow", "DELETE, GET".to_string())],
+                String::new()
+            )
+        );
+    }
+
+    #[test]
+    pub fn error_routes_are_resolved_with_their_layouts() {
+        use crate::{MatchNestedRoutes, MatchRequest};
+        use std::borrow::Cow;
+
+        let routes = RouteDefs::<_>::new_with_base(
+            NestedRoute::new(StaticSegment(""), || "Layout").child((
+                NestedRoute::new(StaticSegment("users"), || ()),
+                NestedRoute::new(StaticSegment("405"), || ()),
+                NestedRoute::new(StaticSegment("500"), || ()),
+                NestedRoute::new(ParamSegment("id"), || ()),
+            )),
+            "/app",
+        );
+        let post = Method::Post;
+        let req = MatchRequest::new("/app/42").method(&post);
+        assert!(matches!(
+            routes.resolve_request(req).unwrap_err(),
+            MatchError::MethodNotAllowed(_)
+        ));
+
+        fn handled_at<Children: MatchNestedRoutes>(
+            routes: &RouteDefs<Children>,
+            error: MatchError,
+            req: MatchRequest<'_>,
+        ) -> (String, Vec<(Cow<'static, str>, String)>, u16) {
+            let resolved = routes.resolve_error(error, req).unwrap();
+            let status = resolved.status_code();
+            let matched = resolved.into_route();
+            let params = matched.to_params();
+            let (_, child) = matched.into_view_and_child();
+            (child.unwrap().as_matched().to_string(), params, status)
+        }
+
+        // without a handler, the error is returned as it is
+        assert_eq!(
+            routes
+                .resolve_error(MatchError::Internal("oops".into()), req)
+                .unwrap_err(),
+            MatchError::Internal("oops".into())
+        );
+
+        // errors fall back to the error route when there is no more specific
+        // one
+        let routes = routes.on_error("/app/500");
+        let resolved = routes.resolve_request(req).unwrap();
+        assert!(matches!(
+            resolved.error(),
+            Some(MatchError::MethodNotAllowed(_))
+        ));
+        assert_eq!(
+            handled_at(&routes, MatchError::Internal("oops".into()), req),
+            ("/500".to_string(), vec![], 500)
+        );
+
+        // params come from the handler's own path, not the original request's
+        let routes = routes.on_method_not_allowed("/app/405");
+        let resolved = routes.resolve_request(req).unwrap();
+        assert_eq!(resolved.status_code(), 405);
+        assert!(resolved.route().to_params().is_empty());
+        assert_eq!(
+            handled_at(&routes, MatchError::MethodNotAllowed([].into()), req),
+            ("/405".to_string(), vec![], 405)
+        );
+
+        // not-found errors are left to the router's fallback
+        assert_eq!(
+            routes.resolve_error(MatchError::NotFound, req).unwrap_err(),
+            MatchError::NotFound
+        );
+        assert_eq!(routes.resolve("/app/42").unwrap().status_code(), 200);
+    }
+
+    #[test]
+    pub fn dont_match_smooshed_static_segments() {
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment(""), || ()),
+            NestedRoute::new(StaticSegment("users"), || ()),
+            NestedRoute::new(
+                (StaticSegment("users"), StaticSegment("id")),
+                || (),
+            ),
+            NestedRoute::new(WildcardSegment("any"), || ()),
+        ));
+
+        let matched = routes.match_route("/users");
+        assert!(matches!(matched, Some(EitherOf4::B(..))));
+
+        let matched = routes.match_route("/users/id");
+        assert!(matches!(matched, Some(EitherOf4::C(..))));
+
+        let matched = routes.match_route("/usersid");
+        assert!(matches!(matched, Some(EitherOf4::D(..))));
+    }
+
+    #[test]
+    pub fn matches_among_32_siblings() {
+        macro_rules! siblings {
+            ($($path:literal),*) => {
+                ($(NestedRoute::new(StaticSegment($path), || ()),)*)
+            };
+        }
+
+        let routes = RouteDefs::<_>::new(siblings!(
+            "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "r10",
+            "r11", "r12", "r13", "r14", "r15", "r16", "r17", "r18", "r19",
+            "r20", "r21", "r22", "r23", "r24", "r25", "r26", "r27", "r28",
+            "r29", "r30", "r31"
+        ));
+
+        let matched = routes.match_route("/r31");
+        assert!(matches!(matched, Some(EitherOf32::AF(..))));
+        assert!(routes.match_route("/r32").is_none());
+        assert_eq!(routes.generate_routes().1.into_iter().count(), 32);
+    }
+
+    #[test]
+    pub fn generated_routes_include_custom_methods() {
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("cache"), || ())
+                .custom_method("PURGE")
+                .child(
+                    NestedRoute::new(StaticSegment("search"), || ())
+                        .custom_method("SEARCH"),
+                ),
+        );
+        let methods = routes
+            .generate_routes()
+            .1
+            .into_iter()
+            .next()
+            .unwrap()
+            .methods;
+        assert_eq!(
+            methods,
+            [Method::Get, "PURGE".into(), "SEARCH".into()].into()
+        );
+    }
+
+    #[test]
+    pub fn generated_routes_inherit_api_key() {
+        use crate::{ApiKeyConfig, ApiKeySource, ApiKeyValidator};
+        use futures::future::{self, BoxFuture, FutureExt};
+        use std::sync::Arc;
+
+        struct AllowAll;
+
+        impl ApiKeyValidator for AllowAll {
+            fn validate(&self, _key: &str) -> BoxFuture<'static, bool> {
+                future::ready(true).boxed()
+            }
+        }
+
+        let config = ApiKeyConfig {
+            source: ApiKeySource::Header("X-API-Key"),
+            validator: Arc::new(AllowAll),
+        };
+        let other = ApiKeyConfig {
+            source: ApiKeySource::QueryParam("key"),
+            ..config.clone()
+        };
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("api"), || ())
+                .api_key(config.clone())
+                .child((
+                    NestedRoute::new(StaticSegment("users"), || ()),
+                    NestedRoute::new(StaticSegment("export"), || ())
+                        .api_key(other.clone()),
+                )),
+        );
+        let routes = routes.generate_routes().1.into_iter().collect::<Vec<_>>();
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].config.api_key.as_ref(), Some(&config));
+        assert_eq!(routes[1].config.api_key.as_ref(), Some(&other));
+    }
+
+    #[test]
+    pub fn language_negotiation_provides_the_language_of_the_request() {
+        use crate::{MatchInterface, MatchRequest, NegotiatedLanguage};
+        use reactive_graph::owner::{provide_context, use_context, Owner};
+
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("docs"), || ())
+                .language_negotiation(&["en", "de", "pt-BR"], "en")
+                .child(NestedRoute::new(StaticSegment("intro"), || ())),
+        );
+        let language = |req: MatchRequest<'_>| {
+            let matched = routes.resolve_request(req).unwrap().into_route();
+            let provided = Owner::new().with(|| {
+                matched.provide_route_contexts();
+                use_context::<NegotiatedLanguage>()
+            });
+            assert_eq!(provided.map(|language| language.0), matched.language());
+            provided
+        };
+
+        let req = MatchRequest::new("/docs/intro");
+        assert_eq!(
+            language(req.accept_language("pt-PT, de;q=0.5")),
+            Some(NegotiatedLanguage("pt-BR"))
+        );
+        assert_eq!(
+            language(req.accept_language("pt-PT").cookies("lang=de")),
+            Some(NegotiatedLanguage("de"))
+        );
+        assert_eq!(language(req), Some(NegotiatedLanguage("en")));
+        // while rendering, the route has the language the server integration
+        // negotiated
+        let rendered = Owner::new().with(|| {
+            provide_context(NegotiatedLanguage("de"));
+            routes.match_route("/docs/intro").unwrap().language()
+        });
+        assert_eq!(rendered, Some("de"));
+
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("docs"), || ())
+                .language_negotiation(&["en"], "en")
+                .child(
+                    NestedRoute::new(StaticSegment("fr"), || ())
+                        .language_negotiation(&["fr"], "fr"),
+                ),
+        );
+        let generated =
+            routes.generate_routes().1.into_iter().collect::<Vec<_>>();
+        assert_eq!(
+            generated[0]
+                .config
+                .language_negotiation
+                .map(|config| config.fallback()),
+            Some("fr")
+        );
+    }
+
+    #[test]
+    pub fn child_routes_inherit_the_error_format() {
+        use crate::{ErrorFormat, GraphQLErrorFormat};
+        use std::sync::Arc;
+
+        let graphql = ErrorFormat::Custom(Arc::new(GraphQLErrorFormat));
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("api"), || ())
+                .error_format(ErrorFormat::Json)
+                .child((
+                    NestedRoute::new(StaticSegment("users"), || ()),
+                    NestedRoute::new(StaticSegment("graphql"), || ())
+                        .error_format(graphql.clone()),
+                )),
+        );
+        let routes = routes.generate_routes().1.into_iter().collect::<Vec<_>>();
+        assert_eq!(routes[0].config.error_format, Some(ErrorFormat::Json));
+        assert_eq!(routes[1].config.error_format, Some(graphql));
+    }
+
+    #[test]
+    pub fn routes_only_consume_the_content_types_they_declare() {
+        use crate::{ContentTypes, MatchRequest, Method};
+
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("orders"), || ())
+                .methods([Method::Get, Method::Post])
+                .consumes("application/json"),
+        );
+        let post = Method::Post;
+        let req = MatchRequest::new("/orders").method(&post);
+        assert!(routes
+            .resolve_request(
+                req.content_type("application/json; charset=utf-8")
+            )
+            .is_ok());
+
+        let error = routes
+            .resolve_request(req.content_type("text/plain"))
+            .unwrap_err();
+        assert_eq!(
+            error,
+            MatchError::UnsupportedMediaType(ContentTypes::new([
+                "application/json"
+            ]))
+        );
+        assert_eq!(error.status_code(), 415);
+        assert!(matches!(
+            routes.resolve_request(req),
+            Err(MatchError::UnsupportedMediaType(_))
+        ));
+
+        // requests without a body, or with an unknown method, are not checked
+        assert!(routes
+            .resolve_request(MatchRequest::new("/orders").method(&Method::Get))
+            .is_ok());
+        assert!(routes.resolve("/orders").is_ok());
+    }
+
+    // unescaping param values requires `ssr` outside the browser
+    #[cfg(feature = "ssr")]
+    #[test]
+    pub fn surrogate_keys_interpolate_the_params_of_the_route() {
+        use crate::params::ParamsMap;
+
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("posts"), || ())
+                .surrogate_key(|_| vec!["posts".into()])
+                .child(
+                    NestedRoute::new(ParamSegment("slug"), || ())
+                        .surrogate_key(|params: &ParamsMap| {
+                            vec![
+                                format!(
+                                    "post:{}",
+                                    params.get_str("slug").unwrap()
+                                ),
+                                "posts".into(),
+                            ]
+                        }),
+                ),
+        );
+        let matched = routes.resolve("/posts/hello-world").unwrap();
+        assert_eq!(
+            matched.route().resolve_surrogate_keys(),
+            ["posts", "post:hello-world"]
+        );
+
+        let routes = routes.generate_routes().1.into_iter().collect::<Vec<_>>();
+        let params = [("slug", "other")].into_iter().collect::<ParamsMap>();
+        assert_eq!(
+            routes[0]
+                .config
+                .surrogate_keys
+                .as_ref()
+                .unwrap()
+                .resolve(&params),
+            ["posts", "post:other"]
+        );
+    }
+
+    #[test]
+    pub fn child_routes_run_inside_the_middleware_of_their_parents() {
+        use leptos::server_fn::middleware::{BoxedService, Layer};
+
+        struct Noop;
+
+        impl Layer<(), ()> for Noop {
+            fn layer(
+                &self,
+                inner: BoxedService<(), ()>,
+            ) -> BoxedService<(), ()> {
+                inner
+            }
+        }
+
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment("admin"), || ())
+                .middleware(Noop)
+                .child((
+                    NestedRoute::new(StaticSegment("users"), || ()),
+                    NestedRoute::new(StaticSegment("audit"), || ())
+                        .middleware(Noop),
+                )),
+            NestedRoute::new(StaticSegment(""), || ()),
+        ));
+        let routes = routes.generate_routes().1.into_iter().collect::<Vec<_>>();
+        let layers = |index: usize| {
+            routes[index]
+                .config
+                .middleware
+                .as_ref()
+                .map_or(0, |middleware| middleware.layers::<(), ()>().count())
+        };
+        assert_eq!((layers(0), layers(1), layers(2)), (1, 2, 0));
+    }
+
+    #[test]
+    pub fn json_not_found_routes_respond_with_their_payload() {
+        use crate::{MatchRequest, Method, NotFoundResponse};
+        use serde_json::json;
+
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("api"), || ()).child((
+                NestedRoute::new(StaticSegment("users"), || ()),
+                NestedRoute::not_found_json(
+                    WildcardSegment("any"),
+                    json!({ "error": "not_found" }),
+                ),
+            )),
+        );
+        let post = Method::Post;
+        assert!(routes
+            .resolve_request(MatchRequest::new("/api/orders").method(&post))
+            .is_ok());
+
+        let routes = routes.generate_routes().1.into_iter().collect::<Vec<_>>();
+        assert_eq!(routes[0].config.not_found, None);
+        assert_eq!(routes[1].config.status, Some(404));
+        assert!(routes[1].no_static);
+        let not_found = routes[1].config.not_found.as_ref().unwrap();
+        assert_eq!(
+            not_found,
+            &NotFoundResponse::json(&json!({ "error": "not_found" }))
+        );
+        assert_eq!(not_found.content_type(), "application/json");
+        assert_eq!(not_found.body(), r#"{"error":"not_found"}"#);
+    }
+
+    #[test]
+    pub fn required_query_params_reject_after_path_match() {
+        use crate::{MatchRequest, QueryError, QueryErrors};
+
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment("download"), || ())
+                .require_query(&["token", "file"]),
+            NestedRoute::new(StaticSegment("download"), || ()),
+        ));
+
+        let req = MatchRequest::new("/download");
+        let matched = routes
+            .resolve_request(req.query("token=abc&file=a.txt"))
+            .unwrap();
+        assert!(matches!(matched.into_route(), Either::Left(_)));
+
+        // a missing param does not fall through to the next route
+        let error =
+            routes.resolve_request(req.query("file=a.txt")).unwrap_err();
+        assert_eq!(
+            error,
+            MatchError::InvalidQuery(QueryErrors(vec![QueryError::Missing {
+                param: "token"
+            }]))
+        );
+        assert_eq!(error.status_code(), 400);
+        assert_eq!(
+            routes.resolve_request(req.query("")).unwrap_err(),
+            MatchError::InvalidQuery(QueryErrors(vec![
+                QueryError::Missing { param: "token" },
+                QueryError::Missing { param: "file" }
+            ]))
+        );
+
+        // matching on the path alone is unaffected
+        assert!(matches!(
+            routes.match_route("/download").unwrap(),
+            Either::Left(_)
+        ));
+    }
+
+    #[test]
+    pub fn required_query_params_merge_with_the_schema() {
+        use crate::{
+            MatchRequest, QueryError, QueryErrors, QuerySchema, QueryType,
+        };
+
+        let schema = || {
+            QuerySchema::new()
+                .optional("page", QueryType::Integer { min: 1, max: 10 })
+        };
+        let view = || ();
+        // in either order, `token` is required and `page` is an integer
+        for route in [
+            NestedRoute::new(StaticSegment("list"), view)
+                .require_query(&["token", "page"])
+                .validate_query(schema()),
+            NestedRoute::new(StaticSegment("list"), view)
+                .validate_query(schema())
+                .require_query(&["token", "page"]),
+        ] {
+            let routes = RouteDefs::<_>::new(route);
+            let req = MatchRequest::new("/list");
+            assert!(routes
+                .resolve_request(req.query("token=a&page=2"))
+                .is_ok());
+            assert_eq!(
+                routes.resolve_request(req.query("page=20")).unwrap_err(),
+                MatchError::InvalidQuery(QueryErrors(vec![
+                    QueryError::OutOfRange {
+                        param: "page",
+                        min: 1,
+                        max: 10
+                    },
+                    QueryError::Missing { param: "token" },
+                ]))
+            );
+        }
+    }
+
+    #[test]
+    pub fn query_is_validated_against_schema() {
+        use crate::{
+            MatchRequest, QueryError, QueryErrors, QuerySchema, QueryType,
+        };
+
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment("search"), || ())
+                .validate_query(
+                    QuerySchema::new().required("q", QueryType::String),
+                )
+                .child(
+                    NestedRoute::new(StaticSegment("products"), || ())
+                        .validate_query(QuerySchema::new().optional(
+                            "limit",
+                            QueryType::Integer { min: 1, max: 100 },
+                        )),
+                ),
+            NestedRoute::new(StaticSegment("error"), || ()),
+        ));
+
+        let req = MatchRequest::new("/search/products");
+        assert!(routes.resolve_request(req.query("q=shoes&limit=5")).is_ok());
+        // the query is not validated when it is not known
+        assert!(routes.resolve_request(req).is_ok());
+
+        let error = routes
+            .resolve_request(req.query("q=shoes&limit=500"))
+            .unwrap_err();
+        assert_eq!(
+            error,
+            MatchError::InvalidQuery(QueryErrors(vec![
+                QueryError::OutOfRange {
+                    param: "limit",
+                    min: 1,
+                    max: 100
+                }
+            ]))
+        );
+        assert_eq!(error.status_code(), 400);
+
+        let routes = routes.on_error("/error");
+        let resolved = routes.resolve_request(req.query("limit=5")).unwrap();
+        assert_eq!(resolved.status_code(), 400);
+        assert_eq!(resolved.into_route().as_matched(), "/error");
+
+        // generated routes validate the params of their ancestors' schemas too
+        let generated =
+            routes.generate_routes().1.into_iter().collect::<Vec<_>>();
+        let schema = generated[0].config.query_schema.as_ref().unwrap();
+        assert!(schema.validate("q=shoes&limit=5").is_ok());
+        assert_eq!(schema.validate("limit=500").unwrap_err().0.len(), 2);
+        assert_eq!(generated[1].config.query_schema, None);
+    }
+
+    #[test]
+    pub fn custom_matchers_select_routes() {
+        use crate::MatchRequest;
+        use either_of::EitherOf3;
+
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment("dashboard"), || "admin")
+                .custom_matcher(|req| {
+                    req.as_host() == Some("admin.example.com")
+                }),
+            NestedRoute::new(StaticSegment("dashboard"), || "beta")
+                .custom_matcher(|req| req.cookie("beta") == Some("1"))
+                .custom_matcher(|req| req.as_path().starts_with("/dashboard")),
+            NestedRoute::new(StaticSegment("dashboard"), || "default"),
+        ));
+        let view = |req: MatchRequest<'_>| match routes
+            .resolve_request(req)
+            .unwrap()
+            .into_route()
+        {
+            EitherOf3::A(_) => "admin",
+            EitherOf3::B(_) => "beta",
+            EitherOf3::C(_) => "default",
+        };
+
+        let req = MatchRequest::new("/dashboard");
+        assert_eq!(view(req.host("admin.example.com")), "admin");
+        assert_eq!(view(req.host("example.com").cookies("beta=1")), "beta");
+        assert_eq!(view(req.cookies("beta=0")), "default");
+        assert_eq!(view(req), "default");
+        // the matcher is only called for paths that match the route's segments
+        assert!(routes
+            .resolve_request(
+                MatchRequest::new("/other").host("admin.example.com")
+            )
+            .is_err());
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    pub fn candidates_are_every_route_that_matches_most_specific_first() {
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(WildcardSegment("any"), || "catch-all"),
+            NestedRoute::new(
+                (StaticSegment("users"), ParamSegment("id")),
+                || "user",
+            )
+            .custom_matcher(|_| false),
+            NestedRoute::new(
+                (StaticSegment("users"), StaticSegment("me")),
+                || "me",
+            )
+            .custom_method("POST"),
+            NestedRoute::new(StaticSegment("about"), || "about"),
+        ))
+        .base("/app");
+        let ids = routes
+            .generate_routes()
+            .1
+            .into_iter()
+            .map(|route| route.id.unwrap())
+            .collect::<Vec<_>>();
+
+        // the catch-all is declared first, so it is the route that is resolved
+        assert_eq!(
+            routes.resolve("/app/users/me").unwrap().route().as_id(),
+            ids[0]
+        );
+        assert_eq!(
+            routes.candidates("/app/users/me"),
+            vec![ids[2], ids[1], ids[0]]
+        );
+        assert_eq!(routes.candidates("/app/about"), vec![ids[3], ids[0]]);
+        assert!(routes.candidates("/users/me").is_empty());
+    }
+
+    #[test]
+    pub fn multi_tenant_routes_choose_tenant_views() {
+        use crate::{
+            AnyChooseView, ChooseView, MatchInterface, MatchRequest, TenantId,
+        };
+        use futures::executor::block_on;
+        use reactive_graph::owner::{use_context, Owner};
+        use std::{
+            collections::HashMap,
+            sync::{Arc, Mutex},
+        };
+
+        let rendered = Arc::new(Mutex::new(Vec::new()));
+        let view = |name: &'static str| {
+            let rendered = Arc::clone(&rendered);
+            move || rendered.lock().unwrap().push(name)
+        };
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("dashboard"), view("default"))
+                .multi_tenant(
+                    Arc::new(|req: &MatchRequest<'_>| {
+                        let host = req.as_host()?;
+                        let (tenant, _) = host.split_once('.')?;
+                        Some(tenant.to_string())
+                    }),
+                    HashMap::from([(
+                        "acme".to_string(),
+                        AnyChooseView::new(view("acme")),
+                    )]),
+                ),
+        );
+        let render = |req: MatchRequest<'_>| {
+            let matched = routes.resolve_request(req).unwrap().into_route();
+            let tenant_id = matched.tenant_id().map(str::to_string);
+
+            let owner = Owner::new();
+            let provided = owner.with(|| {
+                matched.provide_route_contexts();
+                use_context::<TenantId>()
+            });
+            assert_eq!(
+                provided.as_deref(),
+                tenant_id.as_deref(),
+                "the tenant is provided via context"
+            );
+
+            block_on(matched.into_view_and_child().0.choose());
+            tenant_id
+        };
+
+        let req = MatchRequest::new("/dashboard");
+        assert_eq!(
+            render(req.host("acme.example.com")).as_deref(),
+            Some("acme")
+        );
+        assert_eq!(
+            render(req.host("globex.example.com")).as_deref(),
+            Some("globex")
+        );
+        assert_eq!(render(req), None);
+        assert_eq!(*rendered.lock().unwrap(), ["acme", "default", "default"]);
+    }
+
+    #[test]
+    pub fn region_hints_provide_the_region_of_the_request() {
+        use crate::{CfWorkerRegionHint, MatchInterface, MatchRequest, Region};
+        use reactive_graph::owner::{use_context, Owner};
+
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("shop"), || ()).with_region_hint(
+                CfWorkerRegionHint::new(Region("us-east-1"))
+                    .country("de", Region("eu-central-1")),
+            ),
+        );
+        let region = |req: MatchRequest<'_>| {
+            let matched = routes.resolve_request(req).unwrap().into_route();
+            let provided = Owner::new().with(|| {
+                matched.provide_route_contexts();
+                use_context::<Region>()
+            });
+            assert_eq!(provided, matched.region());
+            provided
+        };
+
+        let headers = [("CF-IPCountry", "DE")];
+        assert_eq!(
+            region(MatchRequest::new("/shop").headers(&headers)),
+            Some(Region("eu-central-1"))
+        );
+        let headers = [("CF-IPCountry", "XX")];
+        assert_eq!(
+            region(MatchRequest::new("/shop").headers(&headers)),
+            Some(Region("us-east-1"))
+        );
+        assert_eq!(region(MatchRequest::new("/shop")), None);
+    }
+
+    #[test]
+    pub fn matches_with_custom_separator() {
+        use crate::{PossibleRouteMatch, SeparatorConfig, WildcardSegment};
+
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(
+                (StaticSegment("a"), ParamSegment("x"), StaticSegment("c")),
+                || (),
+            ),
+            NestedRoute::new(StaticSegment("files"), || ())
+                .child(NestedRoute::new(WildcardSegment("rest"), || ())),
+        ))
+        .separator(SeparatorConfig::new('.'));
+
+        let matched = routes.match_route("a.b.c").unwrap();
+        assert_eq!(matched.to_params(), vec![("x".into(), "b".into())]);
+        assert!(routes.match_route(".a.b.c").is_some());
+        assert!(routes.match_route("a.b.c.").is_some());
+        assert!(routes.match_route("a.b").is_none());
+        assert!(routes.match_route("a.b.c.d").is_none());
+        // `/` is not a separator, so it is part of the param
+        let matched = routes.match_route("a.b/d.c").unwrap();
+        assert_eq!(matched.to_params(), vec![("x".into(), "b/d".into())]);
+        assert!(routes.match_route("/a/b/c").is_none());
+
+        let matched = routes.match_route("files.docs.readme").unwrap();
+        assert_eq!(
+            matched.to_params(),
+            vec![("rest".into(), "docs.readme".into())]
+        );
+
+        // segments that are tested on their own still split on `/`
+        let matched = (StaticSegment("a"), ParamSegment("x"))
+            .test("/a/b.c")
+            .unwrap();
+        assert_eq!(matched.params(), vec![("x".into(), "b.c".into())]);
+    }
+
+    #[test]
+    pub fn websocket_routes_are_flagged() {
+        use crate::{MatchRequest, WebSocketHandler, WebSocketMessage};
+        use futures::{executor::block_on, stream, StreamExt};
+
+        let echo = WebSocketHandler::new(|incoming| incoming);
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("chat"), || ()).child((
+                NestedRoute::new(StaticSegment(""), || ()),
+                NestedRoute::websocket(StaticSegment("ws"), echo.clone()),
+            )),
+        );
+
+        assert_eq!(routes.match_route("/chat").unwrap().websocket(), None);
+        let handler = routes.match_route("/chat/ws").unwrap().websocket();
+        assert_eq!(handler.as_ref(), Some(&echo));
+        let message = WebSocketMessage::Text("hello".into());
+        let replies = block_on(
+            handler
+                .unwrap()
+                .handle(stream::iter([message.clone()]))
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(replies, vec![message]);
+
+        let post = Method::Post;
+        assert!(matches!(
+            routes.resolve_request(MatchRequest::new("/chat/ws").method(&post)),
+            Err(MatchError::MethodNotAllowed(_))
+        ));
+
+        let generated =
+            routes.generate_routes().1.into_iter().collect::<Vec<_>>();
+        assert_eq!(generated[0].config.websocket, None);
+        assert_eq!(generated[1].config.websocket.as_ref(), Some(&echo));
+        assert_eq!(generated[1].methods, [Method::Get].into());
+    }
+
+    #[test]
+    pub fn passthrough_routes_match_the_paths_no_other_route_does() {
+        use crate::{
+            Passthrough, ProxyError, ProxyRequest, ProxyResponse, UpstreamProxy,
+        };
+        use futures::future::BoxFuture;
+        use std::sync::Arc;
+
+        struct Legacy;
+
+        impl UpstreamProxy for Legacy {
+            fn forward(
+                &self,
+                _request: ProxyRequest,
+            ) -> BoxFuture<'static, Result<ProxyResponse, ProxyError>>
+            {
+                Box::pin(async { Err(ProxyError::Timeout) })
+            }
+        }
+
+        let legacy: Arc<dyn UpstreamProxy> = Arc::new(Legacy);
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment("app"), || ()),
+            NestedRoute::passthrough(Arc::clone(&legacy)),
+        ));
+        assert!(matches!(routes.match_route("/app"), Some(Either::Left(_))));
+        assert!(matches!(
+            routes.match_route("/reports/2024"),
+            Some(Either::Right(_))
+        ));
+
+        let generated =
+            routes.generate_routes().1.into_iter().collect::<Vec<_>>();
+        assert_eq!(generated[0].config.passthrough, None);
+        assert_eq!(
+            generated[1].config.passthrough,
+            Some(Passthrough::new(legacy))
+        );
+        assert_eq!(generated[1].segments, [PathSegment::Splat("path".into())]);
+        assert!(generated[1].methods.contains(&Method::Post));
+    }
+
+    #[test]
+    pub fn generated_routes_inherit_status() {
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment("archive"), || ())
+                .status(410)
+                .child((
+                    NestedRoute::new(StaticSegment("2019"), || ()),
+                    NestedRoute::new(StaticSegment("moved"), || ()).status(301),
+                )),
+            NestedRoute::new(StaticSegment("about"), || ()),
+            NestedRoute::not_found_route(|| ()),
+        ));
+        let statuses = routes
+            .generate_routes()
+            .1
+            .into_iter()
+            .map(|route| route.config.status)
+            .collect::<Vec<_>>();
+        assert_eq!(statuses, [Some(410), Some(301), None, Some(404)]);
+        assert!(routes.match_route("/missing/page").is_some());
+    }
+
+    #[test]
+    pub fn children_can_opt_back_into_the_access_log() {
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment("assets"), || ())
+                .exclude_from_access_log(true)
+                .child((
+                    NestedRoute::new(StaticSegment("logo.svg"), || ()),
+                    NestedRoute::new(StaticSegment("upload"), || ())
+                        .exclude_from_access_log(false),
+                )),
+            NestedRoute::new(StaticSegment("about"), || ()),
+        ));
+        let excluded = routes
+            .generate_routes()
+            .1
+            .into_iter()
+            .map(|route| route.config.exclude_from_access_log)
+            .collect::<Vec<_>>();
+        assert_eq!(excluded, [Some(true), Some(false), None]);
+    }
+
+    #[test]
+    pub fn children_inherit_sticky_sessions() {
+        use crate::StickySessionPolicy;
+
+        const POD: StickySessionPolicy = StickySessionPolicy {
+            cookie: "pod",
+            header: "x-pod",
+        };
+        const SOCKET: StickySessionPolicy = StickySessionPolicy {
+            cookie: "socket",
+            header: "x-socket",
+        };
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment("live"), || ())
+                .sticky_session(POD)
+                .child((
+                    NestedRoute::new(StaticSegment("events"), || ()),
+                    NestedRoute::new(StaticSegment("socket"), || ())
+                        .sticky_session(SOCKET),
+                )),
+            NestedRoute::new(StaticSegment("about"), || ()),
+        ));
+        let policies = routes
+            .generate_routes()
+            .1
+            .into_iter()
+            .map(|route| route.config.sticky_session)
+            .collect::<Vec<_>>();
+        assert_eq!(policies, [Some(POD), Some(SOCKET), None]);
+    }
+
+    #[test]
+    pub fn children_add_to_the_x_robots_tag_of_their_ancestors() {
+        use crate::XRobotsDirective::{NoFollow, NoIndex};
+
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("drafts"), || ())
+                .x_robots_tag(NoIndex)
+                .child((
+                    NestedRoute::new(StaticSegment("preview"), || ())
+                        .x_robots_tag(NoFollow)
+                        .x_robots_tag(NoIndex),
+                    NestedRoute::new(StaticSegment("list"), || ()),
+                )),
+        );
+        let directives = routes
+            .generate_routes()
+            .1
+            .into_iter()
+            .map(|route| route.config.x_robots_tag)
+            .collect::<Vec<_>>();
+        assert_eq!(directives, [vec![NoIndex, NoFollow], vec![NoIndex]]);
+    }
+
+    #[test]
+    pub fn nested_routes_accumulate_the_preloads_of_their_ancestors() {
+        use crate::PreloadLink;
+
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("docs"), || ())
+                .preload(PreloadLink::style("/docs.css"))
+                .child((
+                    NestedRoute::new(StaticSegment("search"), || ())
+                        .preload(PreloadLink::script("/search.js"))
+                        .preload(PreloadLink::style("/docs.css")),
+                    NestedRoute::new(StaticSegment("intro"), || ()),
+                )),
+        );
+        let preloads = routes
+            .generate_routes()
+            .1
+            .into_iter()
+            .map(|route| {
+                route
+                    .config
+                    .preload
+                    .iter()
+                    .map(|asset| asset.href().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            preloads,
+            [vec!["/docs.css", "/search.js"], vec!["/docs.css"]]
+        );
+    }
+
+    #[test]
+    pub fn nested_routes_request_the_client_hints_of_their_ancestors() {
+        use crate::ClientHint::{Dpr, SaveData, ViewportWidth};
+
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("gallery"), || ())
+                .request_client_hints(vec![Dpr, ViewportWidth])
+                .child((
+                    NestedRoute::new(StaticSegment("photo"), || ())
+                        .request_client_hints(vec![SaveData, Dpr]),
+                    NestedRoute::new(StaticSegment("grid"), || ()),
+                )),
+        );
+        let hints = routes
+            .generate_routes()
+            .1
+            .into_iter()
+            .map(|route| route.config.client_hints)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            hints,
+            [vec![Dpr, ViewportWidth, SaveData], vec![Dpr, ViewportWidth]]
+        );
+    }
+
+    #[test]
+    pub fn a_single_root_router_matches_like_its_root() {
+        use super::MatchNestedRoutes;
+
+        let root = NestedRoute::new(StaticSegment("app"), || ()).child((
+            NestedRoute::new(StaticSegment("settings"), || ()),
+            NestedRoute::new(ParamSegment("id"), || ()),
+        ));
+        let router = root.clone().into_router();
+        for path in ["/app/settings", "/app/42", "/app", "/app/42/more", "/"] {
+            let (raw, remaining) = root.match_nested(path);
+            let raw = raw
+                .filter(|_| remaining.is_empty())
+                .map(|(_, matched)| (matched.as_id(), matched.to_params()));
+            let resolved = router
+                .match_route(path)
+                .map(|matched| (matched.as_id(), matched.to_params()));
+            assert_eq!(raw, resolved, "{path}");
+        }
+        assert!(router.base("/admin").match_route("/admin/app/42").is_some());
+    }
+
+    #[test]
+    pub fn language_redirects_generate_a_route_for_each_locale() {
+        use crate::{
+            static_routes::StaticRoute, LanguageRedirectConfig, SsrMode,
+        };
+
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("docs"), || ()).child(
+                NestedRoute::new(StaticSegment(""), || ())
+                    .language_redirect(LanguageRedirectConfig::new(
+                        ["en", "fr"],
+                        "en",
+                    ))
+                    .child(
+                        NestedRoute::new(StaticSegment("intro"), || ())
+                            .ssr_mode(SsrMode::Static(StaticRoute::new())),
+                    ),
+            ),
+        );
+        let generated = routes
+            .generate_routes()
+            .1
+            .into_iter()
+            .map(|route| {
+                let path = route
+                    .segments
+                    .iter()
+                    .map(PathSegment::as_raw_str)
+                    .filter(|segment| !segment.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                (
+                    path,
+                    route.language_redirect.map(|redirect| redirect.depth),
+                    matches!(route.ssr_mode, SsrMode::Static(_)),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            generated,
+            [
+                ("docs/en/intro".to_string(), None, true),
+                ("docs/fr/intro".to_string(), None, true),
+                ("docs/intro".to_string(), Some(1), false),
+            ]
+        );
+
+        let (_, localized) = routes
+            .match_route("/docs/fr/intro")
+            .unwrap()
+            .into_view_and_child();
+        let localized = localized.unwrap();
+        assert_eq!(localized.as_matched(), "/fr");
+        assert_eq!(localized.matched_segments(), ["fr"]);
+        assert!(routes.match_route("/docs/intro").is_some());
+        assert!(routes.match_route("/docs/de/intro").is_none());
+    }
+
+    #[test]
+    pub fn shared_views_are_not_cloned_when_routes_match() {
+        use crate::ChooseView;
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+        use tachys::view::any_view::{AnyView, IntoAny};
+
+        struct CountedView(Arc<AtomicUsize>);
+
+        impl Clone for CountedView {
+            fn clone(&self) -> Self {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                Self(Arc::clone(&self.0))
+            }
+        }
+
+        impl ChooseView for CountedView {
+            async fn choose(self) -> AnyView {
+                ().into_any()
+            }
+
+            async fn preload(&self) {}
+        }
+
+        let owned = Arc::new(AtomicUsize::new(0));
+        let shared = Arc::new(AtomicUsize::new(0));
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(
+                StaticSegment("owned"),
+                CountedView(Arc::clone(&owned)),
+            ),
+            NestedRoute::new_shared(
+                StaticSegment("shared"),
+                CountedView(Arc::clone(&shared)),
+            ),
+        ));
+        for _ in 0..3 {
+            for path in ["/owned", "/shared"] {
+                let matched = routes.match_route(path).unwrap();
+                drop(MatchInterface::into_view_and_child(matched));
+            }
+        }
+        assert!(owned.load(Ordering::Relaxed) >= 3);
+        assert_eq!(shared.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    pub fn ssr_mode_precedence_replaces_how_child_modes_are_resolved() {
+        use crate::{static_routes::StaticRoute, SsrMode};
+
+        fn streaming_wins(parent: &SsrMode, child: &SsrMode) -> SsrMode {
+            match (parent, child) {
+                (SsrMode::Async, SsrMode::Static(_)) => SsrMode::Async,
+                _ => SsrMode::resolve(parent, child),
+            }
+        }
+
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("reports"), || ())
+                .ssr_mode(SsrMode::Async)
+                .child((
+                    NestedRoute::new(StaticSegment("yearly"), || ())
+                        .ssr_mode(SsrMode::Static(StaticRoute::new())),
+                    NestedRoute::new(StaticSegment("live"), || ())
+                        .ssr_mode(SsrMode::InOrder),
+                )),
+        );
+        let modes = |routes: &RouteDefs<_>| {
+            routes
+                .generate_routes()
+                .1
+                .into_iter()
+                .map(|route| matches!(route.ssr_mode, SsrMode::Static(_)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(modes(&routes), [true, false]);
+
+        let routes = routes.ssr_mode_precedence(streaming_wins);
+        assert_eq!(modes(&routes), [false, false]);
+        assert!(routes
+            .generate_routes()
+            .1
+            .into_iter()
+            .all(|route| route.ssr_mode == SsrMode::Async));
+    }
+
+    #[test]
+    pub fn dynamic_routes_are_not_made_static_by_their_parent() {
+        use crate::{static_routes::StaticRoute, SsrMode};
+
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("site"), || ())
+                .ssr_mode(SsrMode::Static(StaticRoute::new()))
+                .child((
+                    NestedRoute::new(StaticSegment("about"), || ()),
+                    NestedRoute::new(StaticSegment("search"), || ())
+                        .ssr_mode(SsrMode::InOrder)
+                        .no_static()
+                        .child(
+                            NestedRoute::new(StaticSegment("results"), || ())
+                                .ssr_mode(SsrMode::Static(StaticRoute::new())),
+                        ),
+                    NestedRoute::new(StaticSegment("feed"), || ()).no_static(),
+                )),
+        );
+        let generated = routes
+            .generate_routes()
+            .1
+            .into_iter()
+            .map(|route| {
+                let path = route
+                    .segments
+                    .iter()
+                    .map(PathSegment::as_raw_str)
+                    .filter(|segment| !segment.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                let mode = match route.ssr_mode {
+                    SsrMode::Static(_) => "static",
+                    SsrMode::InOrder => "in-order",
+                    SsrMode::OutOfOrder => "out-of-order",
+                    _ => "other",
+                };
+                (path, mode, route.no_static)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            generated,
+            [
+                ("site/about".to_string(), "static", false),
+                ("site/search/results".to_string(), "in-order", true),
+                ("site/feed".to_string(), "out-of-order", true),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn absent_optional_params_do_not_shift_params() {
+        use crate::params::IntoParam;
+
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(
+                (ParamSegment("org"), OptionalParamSegment("lang")),
+                || (),
+            )
+            .child(NestedRoute::new(
+                (
+                    StaticSegment("posts"),
+                    OptionalParamSegment("year"),
+                    ParamSegment("id"),
+                ),
+                || (),
+            )),
+        );
+        let params = |path: &str| routes.match_route(path).unwrap().to_params();
+
+        // params are always in the order they are defined in, parent before
+        // child, and an absent optional param is left out, rather than taking
+        // the next segment's value
+        let all = params("/acme/en/posts/2024/1");
+        assert_eq!(
+            all,
+            [
+                ("org".into(), "acme".into()),
+                ("lang".into(), "en".into()),
+                ("year".into(), "2024".into()),
+                ("id".into(), "1".into()),
+            ]
+        );
+        let no_lang = params("/acme/posts/2024/1");
+        assert_eq!(
+            no_lang,
+            [
+                ("org".into(), "acme".into()),
+                ("year".into(), "2024".into()),
+                ("id".into(), "1".into()),
+            ]
+        );
+        let neither = params("/acme/posts/1");
+        assert_eq!(
+            neither,
+            [("org".into(), "acme".into()), ("id".into(), "1".into())]
+        );
+
+        // extracting params by key gives the same values, whichever optionals
+        // are present
+        for (params, lang) in
+            [(all, Some("en")), (no_lang, None), (neither, None)]
+        {
+            let get = |key: &str| {
+                params
+                    .iter()
+                    .find(|(k, _)| k == key)
+                    .map(|(_, v)| v.as_str())
+            };
+            assert_eq!(
+                Option::<String>::into_param(get("org"), "org"),
+                Ok(Some("acme".into()))
+            );
+            assert_eq!(
+                Option::<String>::into_param(get("lang"), "lang"),
+                Ok(lang.map(String::from))
+            );
+            assert_eq!(Option::<u32>::into_param(get("id"), "id"), Ok(Some(1)));
+        }
+    }
+
+    #[test]
+    pub fn allow_list_params_constrain_matches_and_generated_routes() {
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(
+                (StaticSegment("team"), ParamSegment("tab")),
+                || (),
+            )
+            .allow_list_params(
+                [("tab", &["overview", "settings"][..])]
+                    .into_iter()
+                    .collect(),
+            )
+            .child(
+                NestedRoute::new(OptionalParamSegment("view"), || ())
+                    .allow_list_params(
+                        [("view", &["grid"][..])].into_iter().collect(),
+                    ),
+            ),
+            NestedRoute::new(
+                (StaticSegment("team"), ParamSegment("id")),
+                || (),
+            ),
+        ));
+
+        let params = |path: &str| routes.match_route(path).unwrap().to_params();
+        assert_eq!(
+            params("/team/settings"),
+            [("tab".into(), "settings".into())]
+        );
+        // values that are not allowed fall through to the next route
+        assert_eq!(params("/team/42"), [("id".into(), "42".into())]);
+
+        let paths = routes
+            .generate_routes()
+            .1
+            .into_iter()
+            .map(|route| {
+                route
+                    .segments
+                    .iter()
+                    .filter(|segment| **segment != PathSegment::Unit)
+                    .map(PathSegment::as_raw_str)
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            [
+                "team/overview/grid",
+                "team/settings/grid",
+                "team/overview",
+                "team/settings",
+                "team/id"
+            ]
+        );
+    }
+
+    #[test]
+    pub fn paginated_route_reads_query() {
+        use crate::{
+            MatchRequest, PaginationConfig, PaginationParams, PerPageOverflow,
+            TooManyPerPage,
+        };
+
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("posts"), || ()).with_pagination(
+                PaginationConfig {
+                    overflow: PerPageOverflow::Reject,
+                    ..Default::default()
+                },
+            ),
+        );
+        let matched = routes
+            .resolve_request(
+                MatchRequest::new("/posts").query("page=3&per_page=10"),
+            )
+            .unwrap()
+            .into_route();
+        assert_eq!(
+            matched.pagination(),
+            PaginationParams {
+                page: 3,
+                per_page: 10,
+                offset: 20
+            }
+        );
+        assert!(matched.to_params().is_empty());
+
+        // without a known query string, the defaults are used
+        let matched = routes.match_route("/posts").unwrap();
+        assert_eq!(matched.pagination(), PaginationParams::default());
+
+        assert_eq!(
+            routes
+                .resolve_request(
+                    MatchRequest::new("/posts").query("per_page=1000")
+                )
+                .unwrap_err(),
+            MatchError::InvalidPagination(TooManyPerPage {
+                requested: 1000,
+                max: 100
+            })
+        );
+    }
+
+    #[test]
+    pub fn generated_routes_inherit_webhook_signature() {
+        let config = WebhookSignatureConfig {
+            header: "X-Signature",
+            secret: Secret::new("secret".to_string()),
+            algorithm: HmacAlgorithm::Sha256,
+        };
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("hooks"), || ())
+                .webhook_signature(config.clone())
+                .child((
+                    NestedRoute::new(StaticSegment("github"), || ()),
+                    NestedRoute::new(StaticSegment("stripe"), || ()),
+                )),
+        );
+        let routes = routes.generate_routes().1.into_iter().collect::<Vec<_>>();
+        assert_eq!(routes.len(), 2);
+        for route in routes {
+            assert_eq!(route.config.webhook_signature.as_ref(), Some(&config));
+        }
+    }
+
+    #[test]
+    pub fn rewriters_run_in_order_before_matching() {
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment("old-blog"), || ()),
+            NestedRoute::new(
+                (StaticSegment("blog"), ParamSegment("slug")),
+                || (),
+            ),
+        ))
+        // strips a leading `/t/<campaign>` tracking segment
+        .rewrite(|path| {
+            match path.strip_prefix("/t/").and_then(|rest| rest.find('/')) {
+                Some(idx) => Cow::Owned(path[3 + idx..].to_string()),
+                None => Cow::Borrowed(path),
+            }
+        })
+        .rewrite(|path| match path.strip_prefix("/old-blog/") {
+            Some(slug) => Cow::Owned(format!("/blog/{slug}")),
+            None => Cow::Borrowed(path),
+        });
+
+        let matched = routes.resolve("/old-blog/hello").unwrap();
+        assert_eq!(
+            matched.route().to_params(),
+            [("slug".into(), "hello".into())]
+        );
+        assert_eq!(matched.route().as_matched(), "/blog/hello");
+        assert_eq!(matched.original_path(), Some("/old-blog/hello"));
+
+        let matched = routes.resolve("/t/spring/old-blog/hello").unwrap();
+        assert_eq!(
+            matched.route().to_params(),
+            [("slug".into(), "hello".into())]
+        );
+        assert_eq!(matched.original_path(), Some("/t/spring/old-blog/hello"));
+
+        // a path that is not rewritten is matched as it is
+        let matched = routes.resolve("/blog/hello").unwrap();
+        assert_eq!(matched.original_path(), None);
+        assert!(routes
+            .resolve("/old-blog")
+            .unwrap()
+            .route()
+            .to_params()
+            .is_empty());
+    }
+
+    #[test]
+    pub fn empty_trailing_param_is_told_apart_from_no_trailing_slash() {
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(
+                (StaticSegment("search"), ParamSegment("q").allow_empty()),
+                || (),
+            ),
+            NestedRoute::new(StaticSegment("search"), || ()),
+        ));
+        let params = |path: &str| routes.match_route(path).unwrap().to_params();
+        assert_eq!(params("/search/"), [("q".into(), "".into())]);
+        assert_eq!(params("/search/foo"), [("q".into(), "foo".into())]);
+        assert_eq!(params("/search/foo/"), [("q".into(), "foo".into())]);
+        // matched by the second route
+        assert_eq!(params("/search"), []);
+
+        let routes = RouteDefs::<_>::new(NestedRoute::new(
+            (StaticSegment("search"), ParamSegment("q").allow_empty()),
+            || (),
+        ));
+        assert!(routes.match_route("/search").is_none());
+        assert!(routes.match_route("/search//foo").is_none());
+    }
+}
+
+/// Successful result of [testing](PossibleRouteMatch::test) a single segment in
+/// the route path
+#[derive(Debug)]
+pub struct PartialPathMatch<'a> {
+    /// unmatched yet part of the path
+    pub(crate) remaining: &'a str,
+    /// value of parameters encoded inside of the path
+    pub(crate) params: Vec<(Cow<'static, str>, String)>,
+    /// part of the original path that was matched by segment
+    pub(crate) matched: &'a str,
+    /// components of the matched part, without separators
+    pub(crate) segments: Vec<&'a str>,
+}
+
+impl<'a> PartialPathMatch<'a> {
+    pub fn new(
+        remaining: &'a str,
+        params: Vec<(Cow<'static, str>, String)>,
+        matched: &'a str,
+    ) -> Self {
+        Self::separated_by('/', remaining, params, matched)
+    }
+
+    /// Creates a match whose path is split on `separator`, for
+    /// [`test_with_separator`](PossibleRouteMatch::test_with_separator).
+    pub(crate) fn separated_by(
+        separator: char,
+        remaining: &'a str,
+        params: Vec<(Cow<'static, str>, String)>,
+        matched: &'a str,
+    ) -> Self {
+        Self {
+            remaining,
+            params,
+            matched,
+            segments: matched
+                .split(separator)
+                .filter(|segment| !segment.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.remaining.is_empty() || self.remaining == "/"
+    }
+
+    pub fn remaining(&self) -> &'a str {
+        self.remaining
+    }
+
+    pub fn params(self) -> Vec<(Cow<'static, str>, String)> {
+        self.params
+    }
 
     pub fn matched(&self) -> &'a str {
         self.matched
     }
+
+    /// The components of the [matched](Self::matched) part of the path, without
+    /// separators.
+    pub fn segments(&self) -> &[&'a str] {
+        &self.segments
+    }
 }
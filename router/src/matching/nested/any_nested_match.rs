@@ -1,9 +1,15 @@
 #![allow(clippy::type_complexity)]
 use crate::{
-    matching::any_choose_view::AnyChooseView, ChooseView, MatchInterface,
-    MatchParams, RouteMatchId,
+    matching::any_choose_view::AnyChooseView, ChooseView, ContentTypes,
+    MatchInterface, MatchParams, Method, QueryErrors, RouteGuard, RouteMatchId,
+    SurrogateKeys, TooManyPerPage, WebSocketHandler,
+};
+use std::{
+    any::{Any, TypeId},
+    borrow::Cow,
+    collections::HashSet,
+    fmt::Debug,
 };
-use std::{borrow::Cow, fmt::Debug};
 use tachys::erased::ErasedLocal;
 
 /// A type-erased container for any [`MatchParams'] + [`MatchInterface`].
@@ -12,8 +18,21 @@ pub struct AnyNestedMatch {
     to_params: fn(&ErasedLocal) -> Vec<(Cow<'static, str>, String)>,
     as_id: fn(&ErasedLocal) -> RouteMatchId,
     as_matched: for<'a> fn(&'a ErasedLocal) -> &'a str,
+    matched_segments: for<'a> fn(&'a ErasedLocal) -> Vec<&'a str>,
     into_view_and_child:
         fn(ErasedLocal) -> (AnyChooseView, Option<AnyNestedMatch>),
+    provide_route_contexts: fn(&ErasedLocal),
+    allowed_methods: fn(&ErasedLocal) -> Option<HashSet<Method>>,
+    query_errors: fn(&ErasedLocal) -> Option<QueryErrors>,
+    pagination_error: fn(&ErasedLocal) -> Option<TooManyPerPage>,
+    websocket: fn(&ErasedLocal) -> Option<WebSocketHandler>,
+    consumes: fn(&ErasedLocal) -> Option<ContentTypes>,
+    surrogate_keys: fn(&ErasedLocal) -> Option<SurrogateKeys>,
+    guards: fn(&ErasedLocal) -> Vec<RouteGuard>,
+    meta_of: for<'a> fn(
+        &'a ErasedLocal,
+        TypeId,
+    ) -> Option<&'a (dyn Any + Send + Sync)>,
 }
 
 impl Debug for AnyNestedMatch {
@@ -56,6 +75,13 @@ where
             value.as_matched()
         }
 
+        fn matched_segments<T: MatchInterface + 'static>(
+            value: &ErasedLocal,
+        ) -> Vec<&str> {
+            let value = value.get_ref::<T>();
+            value.matched_segments()
+        }
+
         fn into_view_and_child<T: MatchInterface + 'static>(
             value: ErasedLocal,
         ) -> (AnyChooseView, Option<AnyNestedMatch>) {
@@ -67,12 +93,86 @@ where
             )
         }
 
+        fn provide_route_contexts<T: MatchInterface + 'static>(
+            value: &ErasedLocal,
+        ) {
+            let value = value.get_ref::<T>();
+            value.provide_route_contexts()
+        }
+
+        fn allowed_methods<T: MatchInterface + 'static>(
+            value: &ErasedLocal,
+        ) -> Option<HashSet<Method>> {
+            let value = value.get_ref::<T>();
+            value.allowed_methods()
+        }
+
+        fn query_errors<T: MatchInterface + 'static>(
+            value: &ErasedLocal,
+        ) -> Option<QueryErrors> {
+            let value = value.get_ref::<T>();
+            value.query_errors()
+        }
+
+        fn pagination_error<T: MatchInterface + 'static>(
+            value: &ErasedLocal,
+        ) -> Option<TooManyPerPage> {
+            let value = value.get_ref::<T>();
+            value.pagination_error()
+        }
+
+        fn websocket<T: MatchInterface + 'static>(
+            value: &ErasedLocal,
+        ) -> Option<WebSocketHandler> {
+            let value = value.get_ref::<T>();
+            value.websocket()
+        }
+
+        fn consumes<T: MatchInterface + 'static>(
+            value: &ErasedLocal,
+        ) -> Option<ContentTypes> {
+            let value = value.get_ref::<T>();
+            value.consumes()
+        }
+
+        fn surrogate_keys<T: MatchInterface + 'static>(
+            value: &ErasedLocal,
+        ) -> Option<SurrogateKeys> {
+            let value = value.get_ref::<T>();
+            value.surrogate_keys()
+        }
+
+        fn guards<T: MatchInterface + 'static>(
+            value: &ErasedLocal,
+        ) -> Vec<RouteGuard> {
+            let value = value.get_ref::<T>();
+            value.guards()
+        }
+
+        fn meta_of<T: MatchInterface + 'static>(
+            value: &ErasedLocal,
+            type_id: TypeId,
+        ) -> Option<&(dyn Any + Send + Sync)> {
+            let value = value.get_ref::<T>();
+            value.meta_of(type_id)
+        }
+
         AnyNestedMatch {
             value,
             to_params: to_params::<T>,
             as_id: as_id::<T>,
             as_matched: as_matched::<T>,
+            matched_segments: matched_segments::<T>,
             into_view_and_child: into_view_and_child::<T>,
+            provide_route_contexts: provide_route_contexts::<T>,
+            allowed_methods: allowed_methods::<T>,
+            query_errors: query_errors::<T>,
+            pagination_error: pagination_error::<T>,
+            websocket: websocket::<T>,
+            consumes: consumes::<T>,
+            surrogate_keys: surrogate_keys::<T>,
+            guards: guards::<T>,
+            meta_of: meta_of::<T>,
         }
     }
 }
@@ -94,7 +194,47 @@ impl MatchInterface for AnyNestedMatch {
         (self.as_matched)(&self.value)
     }
 
+    fn matched_segments(&self) -> Vec<&str> {
+        (self.matched_segments)(&self.value)
+    }
+
     fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>) {
         (self.into_view_and_child)(self.value)
     }
+
+    fn provide_route_contexts(&self) {
+        (self.provide_route_contexts)(&self.value)
+    }
+
+    fn allowed_methods(&self) -> Option<HashSet<Method>> {
+        (self.allowed_methods)(&self.value)
+    }
+
+    fn query_errors(&self) -> Option<QueryErrors> {
+        (self.query_errors)(&self.value)
+    }
+
+    fn pagination_error(&self) -> Option<TooManyPerPage> {
+        (self.pagination_error)(&self.value)
+    }
+
+    fn websocket(&self) -> Option<WebSocketHandler> {
+        (self.websocket)(&self.value)
+    }
+
+    fn consumes(&self) -> Option<ContentTypes> {
+        (self.consumes)(&self.value)
+    }
+
+    fn surrogate_keys(&self) -> Option<SurrogateKeys> {
+        (self.surrogate_keys)(&self.value)
+    }
+
+    fn guards(&self) -> Vec<RouteGuard> {
+        (self.guards)(&self.value)
+    }
+
+    fn meta_of(&self, type_id: TypeId) -> Option<&(dyn Any + Send + Sync)> {
+        (self.meta_of)(&self.value, type_id)
+    }
 }
@@ -1,7 +1,8 @@
 #![allow(clippy::type_complexity)]
 use crate::{
     matching::nested::any_nested_match::{AnyNestedMatch, IntoAnyNestedMatch},
-    GeneratedRouteData, MatchNestedRoutes, RouteMatchId,
+    GeneratedRouteData, MatchNestedRoutes, MatchRequest, RouteDefaults,
+    RouteMatchId,
 };
 use std::fmt::Debug;
 use tachys::{erased::Erased, prelude::IntoMaybeErased};
@@ -10,13 +11,20 @@ use tachys::{erased::Erased, prelude::IntoMaybeErased};
 pub struct AnyNestedRoute {
     value: Erased,
     clone: fn(&Erased) -> AnyNestedRoute,
-    match_nested:
+    match_request:
         for<'a> fn(
             &'a Erased,
-            &'a str,
+            MatchRequest<'a>,
         )
             -> (Option<(RouteMatchId, AnyNestedMatch)>, &'a str),
-    generate_routes: fn(&Erased) -> Vec<GeneratedRouteData>,
+    match_branch:
+        for<'a> fn(
+            &'a Erased,
+            &[usize],
+            MatchRequest<'a>,
+        )
+            -> (Option<(RouteMatchId, AnyNestedMatch)>, &'a str),
+    generate_routes: fn(&Erased, &RouteDefaults) -> Vec<GeneratedRouteData>,
     optional: fn(&Erased) -> bool,
 }
 
@@ -57,11 +65,25 @@ where
             value.get_ref::<T>().clone().into_any_nested_route()
         }
 
-        fn match_nested<'a, T: MatchNestedRoutes + Send + Clone + 'static>(
+        fn match_request<'a, T: MatchNestedRoutes + Send + Clone + 'static>(
             value: &'a Erased,
-            path: &'a str,
+            req: MatchRequest<'a>,
         ) -> (Option<(RouteMatchId, AnyNestedMatch)>, &'a str) {
-            let (maybe_match, path) = value.get_ref::<T>().match_nested(path);
+            let (maybe_match, path) = value.get_ref::<T>().match_request(req);
+            (
+                maybe_match
+                    .map(|(id, matched)| (id, matched.into_any_nested_match())),
+                path,
+            )
+        }
+
+        fn match_branch<'a, T: MatchNestedRoutes + Send + Clone + 'static>(
+            value: &'a Erased,
+            branch: &[usize],
+            req: MatchRequest<'a>,
+        ) -> (Option<(RouteMatchId, AnyNestedMatch)>, &'a str) {
+            let (maybe_match, path) =
+                value.get_ref::<T>().match_branch(branch, req);
             (
                 maybe_match
                     .map(|(id, matched)| (id, matched.into_any_nested_match())),
@@ -71,8 +93,13 @@ where
 
         fn generate_routes<T: MatchNestedRoutes + Send + Clone + 'static>(
             value: &Erased,
+            defaults: &RouteDefaults,
         ) -> Vec<GeneratedRouteData> {
-            value.get_ref::<T>().generate_routes().into_iter().collect()
+            value
+                .get_ref::<T>()
+                .generate_routes(defaults)
+                .into_iter()
+                .collect()
         }
 
         fn optional<T: MatchNestedRoutes + Send + Clone + 'static>(
@@ -84,7 +111,8 @@ where
         AnyNestedRoute {
             value: Erased::new(self),
             clone: clone::<T>,
-            match_nested: match_nested::<T>,
+            match_request: match_request::<T>,
+            match_branch: match_branch::<T>,
             generate_routes: generate_routes::<T>,
             optional: optional::<T>,
         }
@@ -95,15 +123,26 @@ impl MatchNestedRoutes for AnyNestedRoute {
     type Data = AnyNestedMatch;
     type Match = AnyNestedMatch;
 
-    fn match_nested<'a>(
+    fn match_request<'a>(
+        &'a self,
+        req: MatchRequest<'a>,
+    ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
+        (self.match_request)(&self.value, req)
+    }
+
+    fn match_branch<'a>(
         &'a self,
-        path: &'a str,
+        branch: &[usize],
+        req: MatchRequest<'a>,
     ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
-        (self.match_nested)(&self.value, path)
+        (self.match_branch)(&self.value, branch, req)
     }
 
-    fn generate_routes(&self) -> impl IntoIterator<Item = GeneratedRouteData> {
-        (self.generate_routes)(&self.value)
+    fn generate_routes(
+        &self,
+        defaults: &RouteDefaults,
+    ) -> impl IntoIterator<Item = GeneratedRouteData> {
+        (self.generate_routes)(&self.value, defaults)
     }
 
     fn optional(&self) -> bool {
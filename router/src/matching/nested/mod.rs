@@ -1,23 +1,53 @@
 use super::{
-    IntoChooseViewMaybeErased, MatchInterface, MatchNestedRoutes,
-    PartialPathMatch, PathSegment, PossibleRouteMatch, RouteMatchId,
+    trace, AnyChooseView, IntoChooseViewMaybeErased, MatchInterface,
+    MatchNestedRoutes, MatchRequest, PartialPathMatch, PathSegment,
+    PossibleRouteMatch, RouteConfig, RouteDefaults, RouteDefs, RouteMatchId,
+    SharedView, WildcardSegment,
 };
-use crate::{ChooseView, GeneratedRouteData, MatchParams, Method, SsrMode};
-use core::{fmt, iter};
+use crate::{
+    params::ParamsMap, ApiKeyConfig, Batch, ChooseView, ClientCertConfig,
+    ClientCertValidator, ClientHint, ContentTypes, DeduplicationConfig,
+    DeduplicationStore, EnvelopeConfig, ErrorFormat, GeneratedRouteData,
+    GuardResult, HtmlMinifyConfig, LanguageNegotiation, LanguageRedirect,
+    LanguageRedirectConfig, MatchParams, Method, NegotiatedLanguage,
+    NotFoundResponse, PaginationConfig, PaginationParams, Passthrough,
+    PreloadLink, QueryErrors, QuerySchema, Region, RegionHint,
+    RegionHintConfig, ResponseSigningConfig, RouteGuard, RouteServices,
+    ServiceLocator, ShutdownSignal, SlowRequestConfig, SsrMode,
+    StickySessionPolicy, SurrogateKeys, TenantConfig, TenantId, TenantMatch,
+    TenantResolver, TooManyPerPage, UpstreamProxy, ValidatedQuery,
+    WebSocketHandler, WebhookSignatureConfig, XRobotsDirective,
+};
+use core::{fmt, future::Future, iter};
 use either_of::Either;
+use leptos::server_fn::middleware::Layer;
+use reactive_graph::owner::provide_context;
 use std::{
+    any::{Any, TypeId},
     borrow::Cow,
-    collections::HashSet,
-    sync::atomic::{AtomicU16, Ordering},
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicU16, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 use tachys::prelude::IntoMaybeErased;
 
 pub mod any_nested_match;
 pub mod any_nested_route;
+#[cfg(feature = "static_dir")]
+mod static_dir;
 mod tuples;
+#[cfg(feature = "static_dir")]
+pub use static_dir::*;
 
 pub(crate) static ROUTE_ID: AtomicU16 = AtomicU16::new(1);
 
+/// A route matched against the start of a path, with its ID, if it matched, and
+/// the rest of the path.
+type PartialRouteMatch<'a, Match> = (Option<(RouteMatchId, Match)>, &'a str);
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct NestedRoute<Segments, Children, Data, View> {
     id: u16,
@@ -26,9 +56,103 @@ pub struct NestedRoute<Segments, Children, Data, View> {
     data: Data,
     view: View,
     methods: HashSet<Method>,
+    default_methods: bool,
     ssr_mode: SsrMode,
+    no_static: bool,
+    config: RouteConfig,
+    contexts: RouteContexts,
+    meta: RouteMeta,
+    pagination: Option<PaginationConfig>,
+    language_redirect: Option<Arc<LanguageRedirectConfig>>,
+    allowed_params: HashMap<&'static str, &'static [&'static str]>,
+    matchers: RouteMatchers,
+    tenant: Option<TenantConfig>,
+    region_hint: Option<RegionHintConfig>,
+}
+
+/// The context values a route provides to its subtree, with
+/// [`NestedRoute::provide`].
+#[derive(Clone, Default)]
+pub(crate) struct RouteContexts(Vec<Arc<dyn Fn() + Send + Sync>>);
+
+impl RouteContexts {
+    fn provide(&self) {
+        for provide in &self.0 {
+            provide();
+        }
+    }
 }
 
+impl fmt::Debug for RouteContexts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RouteContexts").field(&self.0.len()).finish()
+    }
+}
+
+impl PartialEq for RouteContexts {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self.0.iter().zip(&other.0).all(|(a, b)| Arc::ptr_eq(a, b))
+    }
+}
+
+impl Eq for RouteContexts {}
+
+/// The typed metadata attached to a route, with [`NestedRoute::meta`].
+#[derive(Clone, Default)]
+pub(crate) struct RouteMeta(HashMap<TypeId, Arc<dyn Any + Send + Sync>>);
+
+impl RouteMeta {
+    fn get(&self, type_id: TypeId) -> Option<&(dyn Any + Send + Sync)> {
+        self.0.get(&type_id).map(|value| &**value)
+    }
+}
+
+impl fmt::Debug for RouteMeta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RouteMeta").field(&self.0.len()).finish()
+    }
+}
+
+impl PartialEq for RouteMeta {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self.0.iter().all(|(type_id, a)| {
+                other.0.get(type_id).is_some_and(|b| Arc::ptr_eq(a, b))
+            })
+    }
+}
+
+impl Eq for RouteMeta {}
+
+type RouteMatcher = dyn Fn(&MatchRequest<'_>) -> bool + Send + Sync;
+
+/// The custom predicates a request must pass to match a route, with
+/// [`NestedRoute::custom_matcher`].
+#[derive(Clone, Default)]
+pub(crate) struct RouteMatchers(Vec<Arc<RouteMatcher>>);
+
+impl RouteMatchers {
+    fn test(&self, req: &MatchRequest<'_>) -> bool {
+        self.0.iter().all(|matcher| matcher(req))
+    }
+}
+
+impl fmt::Debug for RouteMatchers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RouteMatchers").field(&self.0.len()).finish()
+    }
+}
+
+impl PartialEq for RouteMatchers {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self.0.iter().zip(&other.0).all(|(a, b)| Arc::ptr_eq(a, b))
+    }
+}
+
+impl Eq for RouteMatchers {}
+
 impl<Segments, Children, Data, View> IntoMaybeErased
     for NestedRoute<Segments, Children, Data, View>
 where
@@ -70,7 +194,18 @@ where
             data: self.data.clone(),
             view: self.view.clone(),
             methods: self.methods.clone(),
+            default_methods: self.default_methods,
             ssr_mode: self.ssr_mode.clone(),
+            no_static: self.no_static,
+            config: self.config.clone(),
+            contexts: self.contexts.clone(),
+            meta: self.meta.clone(),
+            pagination: self.pagination,
+            language_redirect: self.language_redirect.clone(),
+            allowed_params: self.allowed_params.clone(),
+            matchers: self.matchers.clone(),
+            tenant: self.tenant.clone(),
+            region_hint: self.region_hint.clone(),
         }
     }
 }
@@ -94,10 +229,198 @@ impl<Segments, View> NestedRoute<Segments, (), (), View> {
             children: None,
             data: (),
             view: view.into_maybe_erased(),
+            methods: HashSet::new(),
+            default_methods: true,
+            ssr_mode: Default::default(),
+            no_static: false,
+            config: RouteConfig::default(),
+            contexts: Default::default(),
+            meta: Default::default(),
+            pagination: None,
+            language_redirect: None,
+            allowed_params: HashMap::new(),
+            matchers: Default::default(),
+            tenant: None,
+            region_hint: None,
+        }
+    }
+
+    /// Creates a route whose view is shared by all of its matches, like
+    /// [`new`](Self::new).
+    ///
+    /// The view of a route is cloned each time the route matches, which is
+    /// cheap for most views, like functions. A view that captures data that is
+    /// expensive to clone can be shared instead, so that a match only bumps a
+    /// reference count, and it is only cloned when the route is rendered.
+    pub fn new_shared(
+        path: Segments,
+        view: View,
+    ) -> NestedRoute<
+        Segments,
+        (),
+        (),
+        <SharedView<View> as IntoChooseViewMaybeErased>::Output,
+    >
+    where
+        View: ChooseView + Sync,
+    {
+        NestedRoute::new(path, SharedView::new(view))
+    }
+}
+
+impl<View> NestedRoute<WildcardSegment, (), (), View> {
+    /// Creates a route that matches any path, and responds with
+    /// `404 Not Found`.
+    ///
+    /// Routes are matched in order, so this should come after every other route
+    /// at the same level, where it only matches paths that none of them do.
+    pub fn not_found_route(
+        view: View,
+    ) -> NestedRoute<
+        WildcardSegment,
+        (),
+        (),
+        <View as IntoChooseViewMaybeErased>::Output,
+    >
+    where
+        View: ChooseView,
+    {
+        NestedRoute::new(WildcardSegment("any"), view).status(404)
+    }
+}
+
+impl<Segments> NestedRoute<Segments, (), (), ()> {
+    /// Creates a route whose requests are upgraded to WebSocket connections,
+    /// which are handled by `handler`, instead of rendering a view.
+    ///
+    /// It is matched like any other route, and only accepts `GET` requests, as
+    /// the upgrade request is always a `GET`. When it is matched, the handler
+    /// is available from [`MatchInterface::websocket`].
+    pub fn websocket(path: Segments, handler: WebSocketHandler) -> Self {
+        NestedRoute {
+            id: ROUTE_ID.fetch_add(1, Ordering::Relaxed),
+            segments: path,
+            children: None,
+            data: (),
+            view: (),
             methods: [Method::Get].into(),
+            default_methods: false,
             ssr_mode: Default::default(),
+            no_static: false,
+            config: RouteConfig {
+                websocket: Some(handler),
+                ..Default::default()
+            },
+            contexts: Default::default(),
+            meta: Default::default(),
+            pagination: None,
+            language_redirect: None,
+            allowed_params: HashMap::new(),
+            matchers: Default::default(),
+            tenant: None,
+            region_hint: None,
         }
     }
+
+    /// Defines a route that runs several requests that are sent together as one
+    /// `POST`, with a JSON array of `{ "method", "path", "body" }` objects, and
+    /// responds with a JSON array of their `{ "status", "body" }` in the same
+    /// order.
+    ///
+    /// Each request that the routes of `batch` accept is sent by the server
+    /// integrations to the server function at its path, and they are run
+    /// concurrently. The checks of this route, like its
+    /// [API key](Self::api_key), are made once for the whole batch, so they are
+    /// not made again for each request in it.
+    pub fn batch(
+        path: Segments,
+        batch: Batch,
+    ) -> NestedRoute<Segments, (), (), <() as IntoChooseViewMaybeErased>::Output>
+    {
+        let mut route = NestedRoute::new(path, ());
+        route.methods = [Method::Post].into();
+        route.default_methods = false;
+        route.config.batch = Some(batch);
+        route
+    }
+
+    /// Creates a route that responds to every request with `404 Not Found` and
+    /// the JSON `payload`, as `application/json`, instead of rendering a view,
+    /// like the fallback of a JSON API, as
+    /// [`not_found_route`](NestedRoute::not_found_route) is for pages.
+    ///
+    /// It accepts requests with any of the standard methods, and is left out of
+    /// static generation.
+    pub fn not_found_json(
+        path: Segments,
+        payload: serde_json::Value,
+    ) -> NestedRoute<Segments, (), (), <() as IntoChooseViewMaybeErased>::Output>
+    {
+        NestedRoute::not_found_with(path, NotFoundResponse::json(&payload))
+    }
+
+    /// Creates a route that responds to every request with `404 Not Found` and
+    /// the [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807) problem details
+    /// of the error, as `application/problem+json`, with `detail` as its
+    /// explanation, like [`not_found_json`](Self::not_found_json).
+    pub fn not_found_problem(
+        path: Segments,
+        detail: &'static str,
+    ) -> NestedRoute<Segments, (), (), <() as IntoChooseViewMaybeErased>::Output>
+    {
+        NestedRoute::not_found_with(path, NotFoundResponse::problem(detail))
+    }
+
+    fn not_found_with(
+        path: Segments,
+        response: NotFoundResponse,
+    ) -> NestedRoute<Segments, (), (), <() as IntoChooseViewMaybeErased>::Output>
+    {
+        let mut route = NestedRoute::new(path, ())
+            .methods([
+                Method::Get,
+                Method::Post,
+                Method::Put,
+                Method::Delete,
+                Method::Patch,
+            ])
+            .status(404)
+            .no_static();
+        route.config.not_found = Some(response);
+        route
+    }
+}
+
+impl NestedRoute<WildcardSegment, (), (), ()> {
+    /// Creates a route that matches any path, and forwards its requests to
+    /// `upstream` instead of rendering a view, like the routes of a legacy
+    /// backend that Leptos is deployed alongside.
+    ///
+    /// Routes are matched in order, so this should come after every other route
+    /// at the same level, where it only receives the requests that none of them
+    /// match. It accepts every method, and the server integrations stream the
+    /// response of the upstream back to the client.
+    pub fn passthrough(
+        upstream: Arc<dyn UpstreamProxy>,
+    ) -> NestedRoute<
+        WildcardSegment,
+        (),
+        (),
+        <() as IntoChooseViewMaybeErased>::Output,
+    > {
+        let mut route = NestedRoute::new(WildcardSegment("path"), ());
+        route.methods = [
+            Method::Get,
+            Method::Post,
+            Method::Put,
+            Method::Delete,
+            Method::Patch,
+        ]
+        .into();
+        route.default_methods = false;
+        route.config.passthrough = Some(Passthrough::new(upstream));
+        route
+    }
 }
 
 impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
@@ -111,7 +434,18 @@ impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
             data,
             view,
             ssr_mode,
+            no_static,
             methods,
+            default_methods,
+            config,
+            contexts,
+            meta,
+            pagination,
+            language_redirect,
+            allowed_params,
+            matchers,
+            tenant,
+            region_hint,
             ..
         } = self;
         NestedRoute {
@@ -121,7 +455,18 @@ impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
             data,
             view,
             ssr_mode,
+            no_static,
             methods,
+            default_methods,
+            config,
+            contexts,
+            meta,
+            pagination,
+            language_redirect,
+            allowed_params,
+            matchers,
+            tenant,
+            region_hint,
         }
     }
 
@@ -129,6 +474,774 @@ impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
         self.ssr_mode = ssr_mode;
         self
     }
+
+    /// Keeps this route (and its children) dynamic, even under a parent with
+    /// [`SsrMode::Static`], like a search page in a statically generated site.
+    ///
+    /// Otherwise, the most restrictive mode of the nested routes is used, which
+    /// makes every child of a static route static too. Static generation skips
+    /// this route, which is rendered for each request with its own mode
+    /// instead, or with out-of-order streaming if that mode is `Static`.
+    pub fn no_static(mut self) -> Self {
+        self.no_static = true;
+        self
+    }
+
+    /// Allows this route to handle a non-standard HTTP method, like `PURGE` or
+    /// `SEARCH`, in addition to the methods it already handles.
+    ///
+    /// A name that is not a [valid method name](Method::is_valid), like one
+    /// with a space, is ignored with a warning, as no request could have it.
+    pub fn custom_method(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        let method = Method::from_name(name);
+        if method.is_valid() {
+            self.methods.insert(method);
+        } else {
+            leptos::logging::warn!(
+                "{method:?} is not a valid HTTP method name, so the route \
+                 does not handle it."
+            );
+        }
+        self
+    }
+
+    /// Sets the HTTP methods this route handles, instead of the
+    /// [default methods](RouteDefs::default_methods) of its router, which are
+    /// only `GET` unless the router sets others.
+    pub fn methods(
+        mut self,
+        methods: impl IntoIterator<Item = Method>,
+    ) -> Self {
+        self.methods = methods.into_iter().collect();
+        self.default_methods = false;
+        self
+    }
+
+    /// Responds to every request to this route (and its children) with the
+    /// given HTTP status code, like `410` for a page that has been permanently
+    /// removed, whatever its view does.
+    ///
+    /// By default a route responds with `200 OK`, unless its view sets another
+    /// status.
+    pub fn status(mut self, code: u16) -> Self {
+        self.config.status = Some(code);
+        self
+    }
+
+    /// Sets how the errors of this route (and its children, unless they set it
+    /// themselves) are encoded in its error responses, like
+    /// [`ErrorFormat::Json`] for the routes of a JSON API.
+    ///
+    /// By default, errors are rendered as HTML by the `<ErrorBoundary/>`s of
+    /// the app. With another format, the server integrations collect the errors
+    /// caught by those boundaries while the route is rendered, and if there are
+    /// any, respond with the encoded errors instead of the page, with the
+    /// status code of the response if it is an error, or `500` otherwise. The
+    /// page is buffered whole to check it for errors.
+    pub fn error_format(mut self, format: ErrorFormat) -> Self {
+        self.config.error_format = Some(format);
+        self
+    }
+
+    /// Wraps the JSON responses of this route (and its children, unless they
+    /// set it themselves) in a standard envelope, like
+    /// `{"data":...,"meta":{...}}`, as set by `config`.
+    ///
+    /// The server integrations wrap the body of every response whose
+    /// `Content-Type` is JSON, which is buffered whole to do so, unless it is
+    /// larger than the [`BUFFERED_BODY_LIMIT`](crate::BUFFERED_BODY_LIMIT).
+    /// HTML responses, larger responses, and error responses encoded with the
+    /// [`error_format`](Self::error_format) of the route, are left as they are.
+    pub fn response_envelope(mut self, config: EnvelopeConfig) -> Self {
+        self.config.response_envelope = Some(config);
+        self
+    }
+
+    /// Adds a middleware `layer` that the server integrations apply to the
+    /// requests to this route (and its children), like a check that the user is
+    /// signed in for the pages under `/admin`, without affecting the other
+    /// routes of the app.
+    ///
+    /// As with the middleware of a server function, `layer` is specific to a
+    /// server integration: `leptos_axum` applies the layers for
+    /// `http::Request<axum::body::Body>` and
+    /// `http::Response<axum::body::Body>`, which include any `tower` layer, and
+    /// `leptos_actix` the layers for `ActixRequest` and `ActixResponse`. The
+    /// other layers are ignored, so a route can have layers for several
+    /// integrations.
+    ///
+    /// The layers run in the order they are added, and the layers of a parent
+    /// route run around those of its children. They run after any middleware
+    /// the app applies to all of its routes, like a `tower` layer added to the
+    /// whole `axum::Router`, and before the route is rendered, so the reactive
+    /// owner and the context provided by the server integration (and by the
+    /// `additional_context` of its `leptos_routes_with_context`) do not exist
+    /// yet when they run. Anything they add to the extensions of the request
+    /// can be read from the request parts the integration provides as context,
+    /// and requests that are rejected once the route is
+    /// [shutting down](Self::graceful_shutdown) do not reach them.
+    pub fn middleware<Req: 'static, Res: 'static>(
+        mut self,
+        layer: impl Layer<Req, Res>,
+    ) -> Self {
+        self.config
+            .middleware
+            .get_or_insert_with(Default::default)
+            .push(Arc::new(layer));
+        self
+    }
+
+    /// Sets whether the responses from this route (and its children, unless
+    /// they set it themselves) should be left out of the server's access log,
+    /// like those of a health check or of static assets, which are only noise
+    /// there.
+    ///
+    /// The server integrations mark each of its responses with an
+    /// [`ExcludeFromAccessLog`](crate::ExcludeFromAccessLog) extension, which
+    /// an access-logging middleware can skip.
+    pub fn exclude_from_access_log(mut self, exclude: bool) -> Self {
+        self.config.exclude_from_access_log = Some(exclude);
+        self
+    }
+
+    /// Warns when a request to this route (or its children, unless they set
+    /// their own threshold) takes longer than `threshold`, from when it is
+    /// matched until its response has been sent, which is a sign that its
+    /// handler has become slower.
+    ///
+    /// This warns about every slow request. To only warn about a sample of
+    /// them, use [`slow_request_warning`](Self::slow_request_warning).
+    pub fn slow_threshold(self, threshold: Duration) -> Self {
+        self.slow_request_warning(SlowRequestConfig::new(threshold))
+    }
+
+    /// Warns when a request to this route (or its children, unless they set it
+    /// themselves) is slow, as set by `config`.
+    ///
+    /// The server integrations emit the warning with `tracing`, if its feature
+    /// is enabled, and otherwise print it to `stderr`.
+    pub fn slow_request_warning(mut self, config: SlowRequestConfig) -> Self {
+        self.config.slow_request = Some(config);
+        self
+    }
+
+    /// Stops the server integrations from accepting new requests to this route
+    /// (and its children, unless they set their own signal) once `shutdown` is
+    /// set, and counts the requests it has accepted as in flight until their
+    /// responses have been sent, so that the server can wait for them before it
+    /// exits.
+    ///
+    /// The same [`ShutdownSignal`] is usually set on the outermost route, so
+    /// that it applies to the whole app, and is then passed to the
+    /// `drain_routes` of the server integration.
+    pub fn graceful_shutdown(mut self, shutdown: ShutdownSignal) -> Self {
+        self.config.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Asks load balancers to keep routing a client's requests to this route
+    /// (and its children, unless they set their own policy) to the same server
+    /// instance, like for WebSocket upgrades or long-running server-sent event
+    /// connections.
+    ///
+    /// The server integrations set the policy's cookie and header to the
+    /// [ID of the instance](StickySessionPolicy::instance_id) on the first
+    /// response to a client, and
+    /// [`generate_routes`](crate::RouteDefs::generate_routes) lists the policy
+    /// of each route, so that the load balancer can be configured with it.
+    pub fn sticky_session(mut self, policy: StickySessionPolicy) -> Self {
+        self.config.sticky_session = Some(policy);
+        self
+    }
+
+    /// Adds a directive to the `X-Robots-Tag` header of every response from
+    /// this route (and its children, after their ancestors' directives), which
+    /// tells crawlers how to index it.
+    ///
+    /// Unlike a `<meta name="robots">` tag, this also works for responses that
+    /// are not HTML, like those of API or file routes.
+    pub fn x_robots_tag(mut self, directive: XRobotsDirective) -> Self {
+        if !self.config.x_robots_tag.contains(&directive) {
+            self.config.x_robots_tag.push(directive);
+        }
+        self
+    }
+
+    /// Adds an asset that this route (and its children, after their ancestors'
+    /// assets) needs, which the browser can start loading as soon as it
+    /// receives the response headers.
+    pub fn preload(mut self, asset: PreloadLink) -> Self {
+        if !self.config.preload.contains(&asset) {
+            self.config.preload.push(asset);
+        }
+        self
+    }
+
+    /// Asks browsers to send the given client hints with their later requests
+    /// to this route (and its children, along with the hints of their
+    /// ancestors), with the `Accept-CH` and `Critical-CH` headers.
+    ///
+    /// The hints a browser sends are provided as
+    /// [`ClientHints`](crate::ClientHints) context while rendering the route.
+    pub fn request_client_hints(mut self, hints: Vec<ClientHint>) -> Self {
+        for hint in hints {
+            if !self.config.client_hints.contains(&hint) {
+                self.config.client_hints.push(hint);
+            }
+        }
+        self
+    }
+
+    /// Serves this route (and its children) with one of the locales of `config`
+    /// at the start of its path, like `/fr/about`, and redirects visitors from
+    /// the path without a locale to the one in their preferred language.
+    ///
+    /// [`generate_routes`](crate::RouteDefs::generate_routes) lists the path of
+    /// each locale, which are the ones that are statically generated, and the
+    /// path without a locale, which the server integrations answer with the
+    /// redirect.
+    pub fn language_redirect(mut self, config: LanguageRedirectConfig) -> Self {
+        self.language_redirect = Some(Arc::new(config));
+        self
+    }
+
+    /// Describes what this route (and its children, unless they have their own
+    /// description) is for, for documentation generated from the route table,
+    /// like
+    /// [`RouteDefs::to_openapi_paths`](crate::RouteDefs::to_openapi_paths).
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.config.description = Some(description.into());
+        self
+    }
+
+    /// Adds a tag that groups this route (and its children) with others in
+    /// documentation generated from the route table. Each call adds another
+    /// tag.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.config.tags.push(tag.into());
+        self
+    }
+
+    /// Documents the JSON schema of the body this route (and its children,
+    /// unless they have their own schema for the same status) responds with,
+    /// when it responds with `status`.
+    ///
+    /// Each call documents another status code, like `200`, `201` or `404`. The
+    /// schemas are used for the responses in
+    /// [`RouteDefs::to_openapi_paths`](crate::RouteDefs::to_openapi_paths).
+    /// With the `response_schema` feature of a server integration, the JSON
+    /// bodies of successful responses are also checked against the `200`
+    /// schema, and mismatches are logged. Bodies larger than the
+    /// [`BUFFERED_BODY_LIMIT`](crate::BUFFERED_BODY_LIMIT) are not checked.
+    pub fn response_schema(
+        mut self,
+        status: u16,
+        schema: serde_json::Value,
+    ) -> Self {
+        self.config.response_schemas.insert(status, schema);
+        self
+    }
+
+    /// Requires requests to this route (and its children) to be signed with an
+    /// HMAC of the request body, as is common for webhooks.
+    ///
+    /// Requests with a missing or invalid signature are rejected by the server
+    /// integration with `401 Unauthorized`. The raw body of a valid request is
+    /// provided via context as a [`WebhookBody`](crate::WebhookBody).
+    pub fn webhook_signature(mut self, config: WebhookSignatureConfig) -> Self {
+        self.config.webhook_signature = Some(config);
+        self
+    }
+
+    /// Requires requests to this route (and its children) to carry a valid API
+    /// key.
+    ///
+    /// Requests with a missing or invalid key are rejected by the server
+    /// integration with `401 Unauthorized`. The key of a valid request is
+    /// provided via context as an [`ApiKey`](crate::ApiKey).
+    pub fn api_key(mut self, config: ApiKeyConfig) -> Self {
+        self.config.api_key = Some(config);
+        self
+    }
+
+    /// Negotiates the language of the responses from this route (and its
+    /// children, unless they negotiate their own) from the `supported` language
+    /// tags, choosing `fallback` if none of them fits the languages a visitor
+    /// prefers, without a language in its path.
+    ///
+    /// The server integrations choose the language of each request from the
+    /// language cookie, or from its `Accept-Language` header, which is then
+    /// stored in the cookie, and send it in the `Content-Language` header of
+    /// the response. The language is available from [`NestedMatch::language`],
+    /// and is provided via context as a [`NegotiatedLanguage`] to this route's
+    /// view and to all of its nested routes. During client-side navigation, the
+    /// headers of the request are not known, so the language is the one in the
+    /// cookie.
+    ///
+    /// To name the cookie, use
+    /// [`language_negotiation_config`](Self::language_negotiation_config).
+    pub fn language_negotiation(
+        self,
+        supported: &'static [&'static str],
+        fallback: &'static str,
+    ) -> Self {
+        self.language_negotiation_config(LanguageNegotiation::new(
+            supported, fallback,
+        ))
+    }
+
+    /// Negotiates the language of the responses from this route (and its
+    /// children, unless they negotiate their own) as set by `config`.
+    pub fn language_negotiation_config(
+        mut self,
+        config: LanguageNegotiation,
+    ) -> Self {
+        self.config.language_negotiation = Some(config);
+        self
+    }
+
+    /// Requires requests to this route (and its children) to present a TLS
+    /// client certificate that `validator` accepts, for mutual TLS.
+    ///
+    /// The server that terminates TLS must insert the certificate into the
+    /// request extensions as a [`ClientCert`](crate::ClientCert). Requests
+    /// without one are rejected by the server integration with
+    /// `401 Unauthorized`, and those with a certificate that `validator`
+    /// rejects with `403 Forbidden`. The certificate of a valid request is
+    /// provided via context.
+    pub fn require_client_cert(
+        mut self,
+        validator: Arc<dyn ClientCertValidator>,
+    ) -> Self {
+        self.config.client_cert = Some(ClientCertConfig { validator });
+        self
+    }
+
+    /// Makes concurrent identical `GET` requests to this route (and its
+    /// children) share a single response, so that only the first of them is
+    /// rendered, and the others wait for it instead of loading the same data
+    /// again.
+    ///
+    /// Requests are identical if they have the same path and query, and send
+    /// the same `Cookie` and `Authorization` headers, so that users never
+    /// receive each other's responses. The server integrations track the
+    /// requests in flight with `store`, and buffer the whole response of the
+    /// first request, up to the [body limit](DeduplicationConfig::body_limit).
+    /// Only successful responses are shared, without their `Set-Cookie`
+    /// headers, and only with requests that have the same values for the
+    /// headers named by their `Vary` header: if the first request fails or is
+    /// cancelled, the requests that were waiting on it are rendered on their
+    /// own.
+    pub fn deduplicate(self, store: Arc<dyn DeduplicationStore>) -> Self {
+        self.deduplicate_config(DeduplicationConfig::new(store))
+    }
+
+    /// Makes concurrent identical `GET` requests to this route (and its
+    /// children) share a single response, as set by `config`, like
+    /// [`deduplicate`](Self::deduplicate).
+    pub fn deduplicate_config(mut self, config: DeduplicationConfig) -> Self {
+        self.config.deduplication = Some(config);
+        self
+    }
+
+    /// Minifies the HTML that the server renders for this route (and its
+    /// children) with `config`, before it is sent.
+    ///
+    /// Streamed responses are minified a chunk at a time, so a tag that is
+    /// split across two chunks is only sent once it is complete. The minifier
+    /// is only included with the `minify_html` feature; without it, this has no
+    /// effect.
+    pub fn minify_html(mut self, config: HtmlMinifyConfig) -> Self {
+        self.config.minify_html = Some(config);
+        self
+    }
+
+    /// Inserts `html` into the `<head>` that the server renders for this route
+    /// (and its children), after the route's own meta tags.
+    ///
+    /// The HTML is inserted verbatim: it is not escaped, so inline scripts are
+    /// sent exactly as they are written. Each call adds to the HTML of the
+    /// previous ones, and the HTML of a parent route comes before that of its
+    /// children. Statically-generated routes have it baked into their files
+    /// when they are built.
+    pub fn inject_head(mut self, html: impl Into<Cow<'static, str>>) -> Self {
+        self.config.head.push(html);
+        self
+    }
+
+    /// Shows a loading bar at the top of the page while the `Suspense`
+    /// fragments of this route (and its children) are still being streamed with
+    /// [`SsrMode::OutOfOrder`], by [inserting](Self::inject_head) the
+    /// [`STREAMING_PROGRESS_SNIPPET`](tachys::ssr::STREAMING_PROGRESS_SNIPPET)
+    /// into its `<head>`.
+    ///
+    /// The bar is driven by the scripts the stream already sends, so it works
+    /// before the WASM has loaded, and after hydration, the router keeps it up
+    /// to date while new pages load.
+    pub fn streaming_progress(self) -> Self {
+        self.inject_head(tachys::ssr::STREAMING_PROGRESS_SNIPPET)
+    }
+
+    /// Provides `value` via context to this route's view and to all of its
+    /// nested routes, while this route is matched.
+    ///
+    /// Unlike loading data in the route's view, this is synchronous: the value
+    /// is provided before any view in this route's subtree is created.
+    pub fn provide<T>(mut self, value: T) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.contexts
+            .0
+            .push(Arc::new(move || provide_context(value.clone())));
+        self
+    }
+
+    /// Provides `locator` to this route's view and to all of its nested routes,
+    /// which can then get services from it with
+    /// [`use_service`](crate::use_service).
+    ///
+    /// A nested route that sets its own locator replaces this one for its
+    /// subtree.
+    pub fn with_service_locator(self, locator: impl ServiceLocator) -> Self {
+        self.provide(RouteServices(Arc::new(locator)))
+    }
+
+    /// Attaches `value` to this route as metadata, which can be read back from
+    /// a match with [`MatchInterface::meta`].
+    ///
+    /// This is a map keyed by type, so metadata of several types (like tags,
+    /// descriptions or permission names) can be attached to the same route;
+    /// attaching another value of the same type replaces the previous one.
+    pub fn meta<T>(mut self, value: T) -> Self
+    where
+        T: Any + Send + Sync,
+    {
+        self.meta.0.insert(TypeId::of::<T>(), Arc::new(value));
+        self
+    }
+
+    /// Reads page parameters (like `?page=2&per_page=20`) from the query string
+    /// of requests to this route, which are then available from
+    /// [`NestedMatch::pagination`].
+    ///
+    /// Missing parameters use their defaults. If the config uses
+    /// [`PerPageOverflow::Reject`](crate::PerPageOverflow::Reject), a request
+    /// for too many items per page matches this route, but is rejected with
+    /// [`MatchError::InvalidPagination`].
+    pub fn with_pagination(mut self, config: PaginationConfig) -> Self {
+        self.pagination = Some(config);
+        self
+    }
+
+    /// Restricts the values that the given params of this route can take, like
+    /// a `:tab` param that can only be `"overview"`, `"settings"` or
+    /// `"members"`.
+    ///
+    /// A path with any other value for one of these params does not match this
+    /// route, so the routes after it are tried instead. An optional param that
+    /// is absent is always allowed.
+    ///
+    /// When generating routes, one route is generated for each combination of
+    /// allowed values, so that each of them can be rendered statically.
+    pub fn allow_list_params(
+        mut self,
+        constraints: HashMap<&'static str, &'static [&'static str]>,
+    ) -> Self {
+        self.allowed_params.extend(constraints);
+        self
+    }
+
+    /// Validates the query string of requests to this route (and its children)
+    /// against `schema`.
+    ///
+    /// The server integrations reject requests with an invalid query with
+    /// `400 Bad Request`, and a JSON body that lists every
+    /// [error](crate::QueryError) in it. The params of a valid query are
+    /// provided via context as a [`ValidatedQuery`].
+    ///
+    /// The params already required with [`require_query`](Self::require_query)
+    /// stay required.
+    pub fn validate_query(mut self, schema: QuerySchema) -> Self {
+        let schema = match self.config.query_schema.take() {
+            Some(required) => schema.require_all(&required),
+            None => schema,
+        };
+        self.config.query_schema = Some(schema);
+        self
+    }
+
+    /// Adds the surrogate keys (or cache tags) returned by `keys`, from the
+    /// params of the request, to the responses from this route (and its
+    /// children), so that a CDN can invalidate them by key, like `post:{slug}`
+    /// for every page of the post `/posts/:slug`.
+    ///
+    /// The server integrations send the keys of the route and of its ancestors,
+    /// in order, in the `Surrogate-Key` header of Fastly and the `Cache-Tag`
+    /// header of Cloudflare. See [`SurrogateKeys`].
+    pub fn surrogate_key(
+        mut self,
+        keys: impl Fn(&ParamsMap) -> Vec<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.config
+            .surrogate_keys
+            .get_or_insert_with(Default::default)
+            .push(keys);
+        self
+    }
+
+    /// Only accepts requests to this route (and its children, unless they set
+    /// it themselves) with a body of the content type `mime`, like
+    /// `application/json`, or of any of the types set by calling this again,
+    /// like `application/x-www-form-urlencoded` for the same route.
+    ///
+    /// Once the path is matched, a request with a method that has a body (one
+    /// other than `GET`, `HEAD` or `OPTIONS`) with another `Content-Type`, or
+    /// none, is a
+    /// [`MatchError::UnsupportedMediaType`](crate::MatchError::UnsupportedMediaType),
+    /// and the server integrations reject it with `415 Unsupported Media Type`.
+    /// A type like `image/*` accepts every type of the kind, and the parameters
+    /// of the content type, like its `charset`, are ignored.
+    pub fn consumes(mut self, mime: impl Into<Cow<'static, str>>) -> Self {
+        self.config
+            .consumes
+            .get_or_insert_with(Default::default)
+            .push(mime);
+        self
+    }
+
+    /// Validates the JSON bodies of requests to this route (and its children)
+    /// against `schema`, a JSON Schema document, before they are handled.
+    ///
+    /// The server integrations parse the body of each request that is not a
+    /// `GET`, `HEAD` or `OPTIONS` request, and reject it with
+    /// `422 Unprocessable Entity`, and a JSON body that lists every
+    /// [mismatch](crate::SchemaMismatch) in it, if it does not match the
+    /// schema. A body that is not JSON is rejected with `400 Bad Request`, and
+    /// one with a content type that is not JSON, and has no
+    /// [schema of its own](Self::validate_body_for), with
+    /// `415 Unsupported Media Type`. The parsed body of a valid request is
+    /// provided via context as a [`ValidatedBody`](crate::ValidatedBody), so it
+    /// does not have to be parsed again.
+    ///
+    /// The schema is checked with
+    /// [`validate_response_schema`](crate::validate_response_schema), which
+    /// ignores the keywords it does not understand.
+    pub fn validate_body_json(mut self, schema: serde_json::Value) -> Self {
+        self.config.body_schemas = Some(
+            self.config
+                .body_schemas
+                .take()
+                .unwrap_or_default()
+                .json(schema),
+        );
+        self
+    }
+
+    /// Validates the bodies of requests to this route (and its children) with
+    /// the content type `mime`, like `application/vnd.api+json`, against
+    /// `schema`, like [`validate_body_json`](Self::validate_body_json) does for
+    /// JSON bodies.
+    ///
+    /// Each call adds a schema for another content type. The bodies are parsed
+    /// as JSON, whatever their type, and a child's schema for a content type
+    /// replaces that of its ancestors.
+    pub fn validate_body_for(
+        mut self,
+        mime: impl Into<Cow<'static, str>>,
+        schema: serde_json::Value,
+    ) -> Self {
+        self.config.body_schemas = Some(
+            self.config
+                .body_schemas
+                .take()
+                .unwrap_or_default()
+                .mime(mime, schema),
+        );
+        self
+    }
+
+    /// Signs the responses of this route (and its children) according to
+    /// `config`, so that clients can check that they come from this server.
+    ///
+    /// With the `response_signing` feature of the server integrations, the
+    /// whole body of each response is buffered and signed, and the signature is
+    /// sent in the `X-Signature` header. The public key of an RSA or ECDSA key
+    /// is served at `/.well-known/jwks.json`, with those of the other signed
+    /// routes.
+    pub fn sign_response(mut self, config: ResponseSigningConfig) -> Self {
+        self.config.response_signing = Some(config);
+        self
+    }
+
+    /// Requires requests to this route (and its children) to have each of the
+    /// query params in `params`, with a non-empty value.
+    ///
+    /// This does not affect whether the path matches: a request whose path
+    /// matches without one of these params is an
+    /// [invalid query](crate::MatchError::InvalidQuery), with a
+    /// [`QueryError::Missing`](crate::QueryError::Missing) for each missing
+    /// param, which the server integrations reject with `400 Bad Request`. This
+    /// adds the params to the route's [query schema](Self::validate_query),
+    /// whether it is set before or after them, keeping the type it gives them.
+    pub fn require_query(mut self, params: &[&'static str]) -> Self {
+        let schema = params.iter().fold(
+            self.config.query_schema.take().unwrap_or_default(),
+            |schema, name| schema.require(name),
+        );
+        self.config.query_schema = Some(schema);
+        self
+    }
+
+    /// Only matches this route if `matcher` returns `true` for the request,
+    /// after its path segments have matched, so that routes can be selected by
+    /// the host, cookies or query of the request, like for subdomain or A/B
+    /// routing.
+    ///
+    /// The [path](MatchRequest::as_path) of the request is the part of the path
+    /// that this route is matched against. If the route does not match, the
+    /// routes after it are tried instead. Calling this more than once adds
+    /// another matcher, and every one of them must match.
+    ///
+    /// During client-side navigation, the only part of the request that is
+    /// known is its path, so the matcher should treat any other part that is
+    /// unknown (`None`) as matching.
+    pub fn custom_matcher(
+        mut self,
+        matcher: impl Fn(&MatchRequest<'_>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.matchers.0.push(Arc::new(matcher));
+        self
+    }
+
+    /// Checks whether a request can access this route (and its children) with
+    /// `guard`, given the params of the whole matched route.
+    ///
+    /// This is a shorthand for [`guard_async`](Self::guard_async) with a guard
+    /// that decides right away; `guard` can return a [`GuardResult`] or a
+    /// `bool`, which allows the request if it is `true` and forbids it
+    /// otherwise.
+    pub fn guard<R>(
+        self,
+        guard: impl Fn(&ParamsMap) -> R + Send + Sync + 'static,
+    ) -> Self
+    where
+        R: Into<GuardResult>,
+    {
+        self.with_guard(RouteGuard::new(guard))
+    }
+
+    /// Checks whether a request can access this route (and its children) with
+    /// the future returned by `guard`, given the params of the whole matched
+    /// route, like by looking up a session in a database.
+    ///
+    /// The guards of the matched routes are awaited one at a time, those of
+    /// each route before those of its children, in the order they were added.
+    /// The first one that does not allow the request decides the result, and
+    /// the rest are never run. They are checked by the server integrations
+    /// before the route is rendered, which answer a redirect with its status
+    /// and a forbidden request with `403 Forbidden`, by the router during
+    /// client-side navigation, which follows a redirect and shows its fallback
+    /// instead of a forbidden route, and by
+    /// [`resolve_request_async`](crate::RouteDefs::resolve_request_async).
+    pub fn guard_async<Fut>(
+        self,
+        guard: impl Fn(&ParamsMap) -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        Fut: Future<Output = GuardResult> + Send + 'static,
+    {
+        self.with_guard(RouteGuard::new_async(guard))
+    }
+
+    fn with_guard(mut self, guard: RouteGuard) -> Self {
+        self.config.guards.push(guard);
+        self
+    }
+
+    /// Serves several tenants from this route, finding the tenant of each
+    /// request with `resolver`.
+    ///
+    /// A tenant with an entry in `views` is rendered with that view, and any
+    /// other tenant (or a request without one) with this route's own view. The
+    /// ID of the tenant is available from [`NestedMatch::tenant_id`], and is
+    /// provided via context as a [`TenantId`] to this route's view and to all
+    /// of its nested routes, for scoping their data to the tenant.
+    ///
+    /// The [path](MatchRequest::as_path) of the request passed to `resolver` is
+    /// the part of the path that this route is matched against. During
+    /// client-side navigation, the only parts of the request that are known are
+    /// its path, its query and the cookies of the document (those that are not
+    /// `HttpOnly`), so a tenant that is found from the host or a header on the
+    /// server should also be found from one of these.
+    pub fn multi_tenant(
+        mut self,
+        resolver: Arc<dyn TenantResolver>,
+        views: HashMap<String, AnyChooseView>,
+    ) -> Self {
+        self.tenant = Some(TenantConfig::new(resolver, views));
+        self
+    }
+
+    /// Finds the region each request to this route should be served from with
+    /// `hint`, like [`CfWorkerRegionHint`](crate::CfWorkerRegionHint) or
+    /// [`FlyRegionHint`](crate::FlyRegionHint) in an app deployed to the edge.
+    ///
+    /// The region is available from [`NestedMatch::region`], and is provided
+    /// via context as a [`Region`] to this route's view and to all of its
+    /// nested routes, so that their data loaders can query the replica in that
+    /// region. During client-side navigation, the headers of the request are
+    /// not known, so a route has no region unless `hint` finds it from another
+    /// part of the request.
+    pub fn with_region_hint(mut self, hint: impl RegionHint + 'static) -> Self {
+        self.region_hint = Some(RegionHintConfig::new(Arc::new(hint)));
+        self
+    }
+}
+
+/// Replaces each param in `segments` that has a list of allowed values with
+/// each of those values in turn, returning every combination.
+fn expand_allowed_params(
+    segments: Vec<PathSegment>,
+    allowed_params: &HashMap<&'static str, &'static [&'static str]>,
+) -> Vec<Vec<PathSegment>> {
+    let mut variants = vec![Vec::with_capacity(segments.len())];
+    for segment in segments {
+        let (allowed, optional) = match &segment {
+            PathSegment::Param(name) => {
+                (allowed_params.get(name.as_ref()), false)
+            }
+            PathSegment::OptionalParam(name) => {
+                (allowed_params.get(name.as_ref()), true)
+            }
+            _ => (None, false),
+        };
+        match allowed {
+            None => {
+                for variant in &mut variants {
+                    variant.push(segment.clone());
+                }
+            }
+            Some(allowed) => {
+                variants = variants
+                    .into_iter()
+                    .flat_map(|variant| {
+                        allowed
+                            .iter()
+                            .map(|value| PathSegment::Static((*value).into()))
+                            // an absent optional param is also allowed
+                            .chain(optional.then_some(PathSegment::Unit))
+                            .map(move |segment| {
+                                let mut variant = variant.clone();
+                                variant.push(segment);
+                                variant
+                            })
+                    })
+                    .collect();
+            }
+        }
+    }
+    variants
 }
 
 #[derive(PartialEq, Eq)]
@@ -136,11 +1249,89 @@ pub struct NestedMatch<Child, View> {
     id: RouteMatchId,
     /// The portion of the full path matched only by this nested route.
     matched: String,
+    /// The components of `matched`, without separators.
+    segments: Vec<String>,
     /// The map of params matched only by this nested route.
     params: Vec<(Cow<'static, str>, String)>,
     /// The nested route.
     child: Option<Child>,
     view_fn: View,
+    /// The context values provided by this nested route.
+    contexts: RouteContexts,
+    /// The metadata attached to this nested route.
+    meta: RouteMeta,
+    /// The page parameters, or why they are rejected, if this nested route is
+    /// paginated.
+    pagination: Option<Result<PaginationParams, TooManyPerPage>>,
+    /// The language negotiated for this nested route, if it negotiates one.
+    language: Option<&'static str>,
+    /// The HTTP methods this nested route accepts.
+    methods: HashSet<Method>,
+    /// The result of validating the query, if this nested route validates it
+    /// and it is known.
+    query: Option<Result<ValidatedQuery, QueryErrors>>,
+    /// The handler of this nested route's WebSocket connections, if it is a
+    /// WebSocket route.
+    websocket: Option<WebSocketHandler>,
+    /// The content types this nested route consumes, if it restricts them.
+    consumes: Option<ContentTypes>,
+    /// The functions that return the surrogate keys of this nested route, if it
+    /// has any.
+    surrogate_keys: Option<SurrogateKeys>,
+    /// The tenant this nested route was matched for, if it is a multi-tenant
+    /// route.
+    tenant: Option<TenantMatch>,
+    /// The region this nested route was matched for, if it has a region hint
+    /// that found one.
+    region: Option<Region>,
+    /// The guards of this nested route.
+    guards: Vec<RouteGuard>,
+}
+
+impl<Child, View> NestedMatch<Child, View> {
+    /// The page parameters read from the query string, if the route was defined
+    /// [with pagination](NestedRoute::with_pagination), or the defaults
+    /// otherwise.
+    ///
+    /// These are not included in the route's [params](MatchParams::to_params).
+    pub fn pagination(&self) -> PaginationParams {
+        self.pagination.and_then(Result::ok).unwrap_or_default()
+    }
+
+    /// The language negotiated for the route, if it was defined
+    /// [with language negotiation](NestedRoute::language_negotiation).
+    pub fn language(&self) -> Option<&'static str> {
+        self.language
+    }
+
+    /// The ID of the tenant the route was matched for, if it was defined as a
+    /// [multi-tenant route](NestedRoute::multi_tenant) and the request is for a
+    /// tenant.
+    pub fn tenant_id(&self) -> Option<&str> {
+        self.tenant.as_ref().map(|tenant| tenant.id.as_str())
+    }
+
+    /// The region the route was matched for, if it was defined
+    /// [with a region hint](NestedRoute::with_region_hint) that found the
+    /// region of the request.
+    pub fn region(&self) -> Option<Region> {
+        self.region
+    }
+}
+
+impl<Child, View> NestedMatch<Child, View>
+where
+    Child: MatchInterface + MatchParams + 'static,
+    View: ChooseView,
+{
+    /// The surrogate keys of the response, from the
+    /// [functions](NestedRoute::surrogate_key) of the matched route and of its
+    /// ancestors, called with its params.
+    pub fn resolve_surrogate_keys(&self) -> Vec<String> {
+        self.surrogate_keys()
+            .map(|keys| keys.resolve(&self.to_params().into_iter().collect()))
+            .unwrap_or_default()
+    }
 }
 
 impl<Child, View> fmt::Debug for NestedMatch<Child, View>
@@ -178,86 +1369,248 @@ where
         &self.matched
     }
 
+    fn matched_segments(&self) -> Vec<&str> {
+        self.segments.iter().map(String::as_str).collect()
+    }
+
     fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>) {
-        (self.view_fn, self.child)
+        let view = match self.tenant.and_then(|tenant| tenant.view) {
+            Some(view) => Either::Right(view),
+            None => Either::Left(self.view_fn),
+        };
+        (view, self.child)
+    }
+
+    fn provide_route_contexts(&self) {
+        self.contexts.provide();
+        if let Some(language) = self.language {
+            provide_context(NegotiatedLanguage(language));
+        }
+        if let Some(tenant) = &self.tenant {
+            provide_context(TenantId::new(tenant.id.clone()));
+        }
+        if let Some(region) = self.region {
+            provide_context(region);
+        }
+        if let Some(Ok(query)) = &self.query {
+            provide_context(query.clone());
+        }
+    }
+
+    fn allowed_methods(&self) -> Option<HashSet<Method>> {
+        let mut methods = self.methods.clone();
+        if let Some(child_methods) =
+            self.child.as_ref().and_then(Child::allowed_methods)
+        {
+            methods.extend(child_methods);
+        }
+        Some(methods)
+    }
+
+    fn query_errors(&self) -> Option<QueryErrors> {
+        match &self.query {
+            Some(Err(errors)) => Some(errors.clone()),
+            _ => self.child.as_ref().and_then(Child::query_errors),
+        }
+    }
+
+    fn pagination_error(&self) -> Option<TooManyPerPage> {
+        match self.pagination {
+            Some(Err(error)) => Some(error),
+            _ => self.child.as_ref().and_then(Child::pagination_error),
+        }
+    }
+
+    fn websocket(&self) -> Option<WebSocketHandler> {
+        self.child
+            .as_ref()
+            .and_then(Child::websocket)
+            .or_else(|| self.websocket.clone())
+    }
+
+    fn consumes(&self) -> Option<ContentTypes> {
+        self.child
+            .as_ref()
+            .and_then(Child::consumes)
+            .or_else(|| self.consumes.clone())
+    }
+
+    fn surrogate_keys(&self) -> Option<SurrogateKeys> {
+        SurrogateKeys::inherit(
+            self.surrogate_keys.as_ref(),
+            self.child.as_ref().and_then(Child::surrogate_keys),
+        )
+    }
+
+    fn meta_of(&self, type_id: TypeId) -> Option<&(dyn Any + Send + Sync)> {
+        self.child
+            .as_ref()
+            .and_then(|child| child.meta_of(type_id))
+            .or_else(|| self.meta.get(type_id))
+    }
+
+    fn guards(&self) -> Vec<RouteGuard> {
+        let mut guards = self.guards.clone();
+        if let Some(child) = &self.child {
+            guards.extend(child.guards());
+        }
+        guards
     }
 }
 
-impl<Segments, Children, Data, View> MatchNestedRoutes
-    for NestedRoute<Segments, Children, Data, View>
+impl<Segments, Children, Data, View>
+    NestedRoute<Segments, Children, Data, View>
+{
+    /// Uses this route, with its children, as the root of a whole set of route
+    /// definitions, without wrapping it in a tuple.
+    ///
+    /// The route definitions match a path exactly like
+    /// [`match_nested`](MatchNestedRoutes::match_nested) on this route does,
+    /// and are where the options of the whole router are set, like its
+    /// [`base`](RouteDefs::base),
+    /// [`canonical_paths`](RouteDefs::canonical_paths) and
+    /// [`separator`](RouteDefs::separator).
+    pub fn into_router(self) -> RouteDefs<Self> {
+        RouteDefs::new(self)
+    }
+
+    /// The methods this route handles, with the default methods of its router,
+    /// or only `GET` if they are not known, unless it sets its own.
+    fn route_methods(
+        &self,
+        defaults: Option<&HashSet<Method>>,
+    ) -> HashSet<Method> {
+        let mut methods = self.methods.clone();
+        if self.default_methods {
+            match defaults {
+                Some(defaults) => methods.extend(defaults.iter().cloned()),
+                None => {
+                    methods.insert(Method::Get);
+                }
+            }
+        }
+        methods
+    }
+}
+
+impl<Segments, Children, Data, View> NestedRoute<Segments, Children, Data, View>
 where
-    Self: 'static,
     Segments: PossibleRouteMatch,
     Children: MatchNestedRoutes,
     Children::Match: MatchParams,
-    Children: 'static,
     View: ChooseView + Clone,
 {
-    type Data = Data;
-    type Match = NestedMatch<Children::Match, View>;
-
-    fn optional(&self) -> bool {
-        self.segments.optional()
-            && self.children.as_ref().map(|n| n.optional()).unwrap_or(true)
-    }
-
-    fn match_nested<'a>(
+    /// Matches this route against a request, using `match_children` to match
+    /// its children against the rest of the path.
+    fn match_with_children<'a>(
         &'a self,
-        path: &'a str,
-    ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
+        req: MatchRequest<'a>,
+        match_children: impl Fn(
+            &'a Children,
+            MatchRequest<'a>,
+        ) -> PartialRouteMatch<'a, Children::Match>,
+    ) -> PartialRouteMatch<'a, NestedMatch<Children::Match, View>> {
+        // a route with a language redirect can also be matched with a locale
+        // before its path
+        let (locale, path) =
+            match self.language_redirect.as_ref().and_then(|config| {
+                config.strip_locale(req.as_path(), req.as_separator())
+            }) {
+                Some((locale, path)) => (Some(locale), path),
+                None => (None, req.as_path()),
+            };
+
         // if this was optional (for example, this whole nested route definition consisted of an optional param),
         // then we'll need to retest the inner value against the starting path, if this one succeeds and the inner one fails
         let this_was_optional = self.segments.optional();
 
-        self.segments
-            .test(path)
+        let separator = req.as_separator();
+        let segments = self
+            .segments
+            .test_with_separator(path, separator.separator());
+        trace::route_tried(self.id, segments.is_some());
+        segments
             .and_then(
                 |PartialPathMatch {
                      remaining,
                      mut params,
                      matched,
+                     mut segments,
                  }| {
-                    let (_, inner, remaining, was_optional_fallback) =
-                        match &self.children {
-                            None => (None, None, remaining, false),
-                            Some(children) => {
-                                let (inner, remaining) =
-                                    children.match_nested(remaining);
-
-                                match inner {
-                                    Some((id, inner)) => (
-                                        Some(id),
-                                        Some(inner),
-                                        remaining,
-                                        false,
-                                    ),
-                                    None if this_was_optional => {
-                                        // if the parent route was optional, re-match children against full path
-                                        let (inner, remaining) =
-                                            children.match_nested(path);
-                                        let (id, inner) = inner?;
-                                        (Some(id), Some(inner), remaining, true)
-                                    }
-                                    None => {
-                                        return None;
-                                    }
+                    if !req.skips_matchers() && !self.matchers.test(&req) {
+                        return None;
+                    }
+                    let tenant = self
+                        .tenant
+                        .as_ref()
+                        .and_then(|tenant| tenant.resolve(&req));
+                    let region = self
+                        .region_hint
+                        .as_ref()
+                        .and_then(|hint| hint.region(&req));
+
+                    let (inner, matched, remaining) = match &self.children {
+                        None => (None, matched, remaining),
+                        Some(children) => {
+                            match match_children(
+                                children,
+                                req.with_path(remaining),
+                            ) {
+                                (Some((_, inner)), remaining) => {
+                                    (Some(inner), matched, remaining)
+                                }
+                                (None, _) if this_was_optional => {
+                                    // this route may have matched too much of
+                                    // the path by including its optional
+                                    // segments, so try shorter matches, ending
+                                    // at each segment it matched, until the
+                                    // children match the rest
+                                    let (inner, shorter, remaining) = matched
+                                        .rmatch_indices(separator.separator())
+                                        .map(|(idx, _)| idx)
+                                        .filter(|&idx| idx > 0)
+                                        .chain(iter::once(0))
+                                        .find_map(|idx| {
+                                            let shorter = self
+                                                .segments
+                                                .test_with_separator(
+                                                    &path[..idx],
+                                                    separator.separator(),
+                                                )?;
+                                            if !shorter.remaining.is_empty() {
+                                                return None;
+                                            }
+                                            let (inner, remaining) =
+                                                match_children(
+                                                    children,
+                                                    req.with_path(&path[idx..]),
+                                                );
+                                            Some((inner?.1, shorter, remaining))
+                                        })?;
+                                    // an optional param that is left out has no
+                                    // value, so it is absent from the params
+                                    // rather than taking the value of the
+                                    // segment after it
+                                    params = shorter.params;
+                                    segments = shorter.segments;
+                                    (Some(inner), shorter.matched, remaining)
                                 }
+                                (None, _) => return None,
                             }
-                        };
-
-                    // if this was an optional route, re-parse its params
-                    if was_optional_fallback {
-                        // new params are based on the path it matched (up to the point where the matched child begins)
-                        // e.g., if we have /:foo?/bar, for /bar we should *not* have { "foo": "bar" }
-                        // so, we re-parse based on "" to yield { "foo": "" }
-                        let matched = inner
-                            .as_ref()
-                            .map(|inner| inner.as_matched())
-                            .unwrap_or("");
-                        let rematch = path
-                            .trim_end_matches(&format!("{matched}{remaining}"));
-                        let new_partial = self.segments.test(rematch).unwrap();
-                        params = new_partial.params;
+                        }
+                    };
+
+                    let params_allowed = params.iter().all(|(key, value)| {
+                        value.is_empty()
+                            || self
+                                .allowed_params
+                                .get(key.as_ref())
+                                .map_or(true, |allowed| {
+                                    allowed.contains(&value.as_str())
+                                })
+                    });
+                    if !params_allowed {
+                        return None;
                     }
 
                     let inner_params = inner
@@ -267,17 +1620,59 @@ where
 
                     let id = RouteMatchId(self.id);
 
-                    if remaining.is_empty() || remaining == "/" {
+                    let language = self
+                        .config
+                        .language_negotiation
+                        .as_ref()
+                        .map(|config| config.language(&req));
+                    let pagination = self
+                        .pagination
+                        .as_ref()
+                        .map(|config| config.params(req.as_query()));
+                    let query = self
+                        .config
+                        .query_schema
+                        .as_ref()
+                        .zip(req.as_query())
+                        .map(|(schema, query)| schema.validate(query));
+
+                    // a lone trailing separator is left unmatched, so that
+                    // `/about/` matches a route for `/about`, unless a segment
+                    // like `EmptyParamSegment` consumes it
+                    if separator.is_empty_path(remaining) {
                         params.extend(inner_params);
                         Some((
                             Some((
                                 id,
                                 NestedMatch {
                                     id,
-                                    matched: matched.to_string(),
+                                    matched: with_locale(locale, matched),
+                                    segments: locale
+                                        .map(|locale| &locale[1..])
+                                        .into_iter()
+                                        .chain(segments)
+                                        .map(str::to_string)
+                                        .collect(),
                                     params,
                                     child: inner,
                                     view_fn: self.view.clone(),
+                                    contexts: self.contexts.clone(),
+                                    language,
+                                    meta: self.meta.clone(),
+                                    pagination,
+                                    methods: self.route_methods(
+                                        req.as_default_methods(),
+                                    ),
+                                    query,
+                                    websocket: self.config.websocket.clone(),
+                                    consumes: self.config.consumes.clone(),
+                                    surrogate_keys: self
+                                        .config
+                                        .surrogate_keys
+                                        .clone(),
+                                    tenant,
+                                    region,
+                                    guards: self.config.guards.clone(),
                                 },
                             )),
                             remaining,
@@ -287,17 +1682,106 @@ where
                     }
                 },
             )
-            .unwrap_or((None, path))
+            .unwrap_or((None, req.as_path()))
+    }
+}
+
+/// The path a route matched, after the `locale` that came before it, if there
+/// was one.
+fn with_locale(locale: Option<&str>, matched: &str) -> String {
+    match locale {
+        Some(locale) => format!("{locale}{matched}"),
+        None => matched.to_string(),
+    }
+}
+
+/// Generates a route with each of the locales of `config` at the start of its
+/// path, and the route without a locale, which only redirects to one of them,
+/// so it is never statically generated.
+fn with_locales(
+    config: &Arc<LanguageRedirectConfig>,
+    route: GeneratedRouteData,
+) -> Vec<GeneratedRouteData> {
+    let mut routes = config
+        .locales()
+        .iter()
+        .map(|locale| GeneratedRouteData {
+            segments: iter::once(PathSegment::Static(locale.clone()))
+                .chain(route.segments.iter().cloned())
+                .collect(),
+            ..route.clone()
+        })
+        .collect::<Vec<_>>();
+    let ssr_mode = match route.ssr_mode {
+        SsrMode::Static(_) => SsrMode::default(),
+        ssr_mode => ssr_mode,
+    };
+    routes.push(GeneratedRouteData {
+        ssr_mode,
+        regenerate: Vec::new(),
+        language_redirect: Some(LanguageRedirect {
+            config: config.clone(),
+            depth: 0,
+        }),
+        ..route
+    });
+    routes
+}
+
+impl<Segments, Children, Data, View> MatchNestedRoutes
+    for NestedRoute<Segments, Children, Data, View>
+where
+    Self: 'static,
+    Segments: PossibleRouteMatch,
+    Children: MatchNestedRoutes,
+    Children::Match: MatchParams,
+    Children: 'static,
+    View: ChooseView + Clone,
+{
+    type Data = Data;
+    type Match = NestedMatch<Children::Match, View>;
+
+    fn optional(&self) -> bool {
+        self.segments.optional()
+            && self.children.as_ref().map(|n| n.optional()).unwrap_or(true)
+    }
+
+    fn match_request<'a>(
+        &'a self,
+        req: MatchRequest<'a>,
+    ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
+        self.match_with_children(req, |children, req| {
+            children.match_request(req)
+        })
+    }
+
+    fn match_branch<'a>(
+        &'a self,
+        branch: &[usize],
+        req: MatchRequest<'a>,
+    ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
+        self.match_with_children(req, |children, req| {
+            children.match_branch(branch, req)
+        })
     }
 
     fn generate_routes(
         &self,
-    ) -> impl IntoIterator<Item = GeneratedRouteData> + '_ {
+        defaults: &RouteDefaults,
+    ) -> impl IntoIterator<Item = GeneratedRouteData> {
         let mut segment_routes = Vec::new();
         self.segments.generate_path(&mut segment_routes);
+        let segment_variants =
+            expand_allowed_params(segment_routes, &self.allowed_params);
         let children = self.children.as_ref();
-        let ssr_mode = self.ssr_mode.clone();
-        let methods = self.methods.clone();
+        let no_static = self.no_static;
+        let ssr_mode = match &self.ssr_mode {
+            SsrMode::Static(_) if no_static => SsrMode::default(),
+            ssr_mode => ssr_mode.clone(),
+        };
+        let methods = self.route_methods(Some(defaults.methods()));
+        let config = self.config.clone();
+        let language_redirect = self.language_redirect.clone();
         let regenerate = match &ssr_mode {
             SsrMode::Static(data) => match data.regenerate.as_ref() {
                 None => vec![],
@@ -306,47 +1790,113 @@ where
             _ => vec![],
         };
 
-        match children {
-            None => Either::Left(iter::once(GeneratedRouteData {
-                segments: segment_routes,
-                ssr_mode,
-                methods,
-                regenerate,
-            })),
+        let routes = match children {
+            None => {
+                let route = GeneratedRouteData {
+                    segments: Vec::new(),
+                    ssr_mode,
+                    no_static,
+                    methods,
+                    regenerate,
+                    config,
+                    language_redirect: None,
+                    branch: Vec::new(),
+                    id: Some(RouteMatchId(self.id)),
+                };
+                Either::Left(segment_variants.into_iter().map(
+                    move |segments| GeneratedRouteData {
+                        segments,
+                        ..route.clone()
+                    },
+                ))
+            }
             Some(children) => {
-                Either::Right(children.generate_routes().into_iter().map(
-                    move |child| {
-                        // extend this route's segments with child segments
-                        let segments = segment_routes
-                            .clone()
-                            .into_iter()
-                            .chain(child.segments)
-                            .collect();
-
-                        let mut methods = methods.clone();
-                        methods.extend(child.methods);
-
-                        let mut regenerate = regenerate.clone();
-                        regenerate.extend(child.regenerate);
-
-                        if child.ssr_mode > ssr_mode {
-                            GeneratedRouteData {
-                                segments,
-                                ssr_mode: child.ssr_mode,
-                                methods,
-                                regenerate,
+                Either::Right(
+                    children.generate_routes(defaults).into_iter().flat_map(
+                        move |child| {
+                            let mut methods = methods.clone();
+                            methods.extend(child.methods);
+
+                            let no_static = no_static || child.no_static;
+                            let ssr_mode = match (&ssr_mode, child.ssr_mode) {
+                                // a dynamic route is not made static by its
+                                // parent, and does not become static because of
+                                // its children
+                                (ssr_mode, SsrMode::Static(_)) if no_static => {
+                                    ssr_mode.clone()
+                                }
+                                (SsrMode::Static(_), child_mode)
+                                    if no_static =>
+                                {
+                                    child_mode
+                                }
+                                (ssr_mode, child_mode) => defaults
+                                    .resolve_ssr_mode(ssr_mode, &child_mode),
+                            };
+                            let mut regenerate = regenerate.clone();
+                            regenerate.extend(child.regenerate);
+                            if no_static {
+                                regenerate.clear();
                             }
-                        } else {
-                            GeneratedRouteData {
-                                segments,
-                                ssr_mode: ssr_mode.clone(),
+
+                            let config = config.inherited_by(child.config);
+
+                            let route = GeneratedRouteData {
+                                segments: Vec::new(),
+                                ssr_mode,
+                                no_static,
                                 methods,
                                 regenerate,
-                            }
-                        }
-                    },
-                ))
+                                config,
+                                language_redirect: None,
+                                branch: child.branch,
+                                id: child.id,
+                            };
+                            let child_segments = child.segments;
+                            let child_language_redirect =
+                                child.language_redirect;
+
+                            // extend each of this route's segments with child
+                            // segments
+                            segment_variants.clone().into_iter().map(
+                                move |segments| GeneratedRouteData {
+                                    // the locale of a descendant comes after
+                                    // these segments
+                                    language_redirect: child_language_redirect
+                                        .clone()
+                                        .map(|redirect| LanguageRedirect {
+                                            depth: redirect.depth
+                                                + segments
+                                                    .iter()
+                                                    .filter(|segment| {
+                                                        !segment
+                                                            .as_raw_str()
+                                                            .is_empty()
+                                                    })
+                                                    .count(),
+                                            ..redirect
+                                        }),
+                                    segments: segments
+                                        .into_iter()
+                                        .chain(child_segments.iter().cloned())
+                                        .collect(),
+                                    ..route.clone()
+                                },
+                            )
+                        },
+                    ),
+                )
             }
+        };
+
+        // the routes are collected, rather than returned as an iterator,
+        // because the iterators of many siblings would otherwise chain into one
+        // value too large for the stack
+        match &language_redirect {
+            None => routes.collect::<Vec<_>>(),
+            Some(config) => routes
+                .flat_map(|route| with_locales(config, route))
+                .collect(),
         }
     }
 }
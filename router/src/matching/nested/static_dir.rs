@@ -0,0 +1,491 @@
+use super::{NestedRoute, ROUTE_ID};
+use crate::{
+    matching::{
+        MatchInterface, MatchNestedRoutes, MatchRequest, PathSegment,
+        RouteDefaults, RouteMatchId,
+    },
+    ChooseView, GeneratedRouteData, MatchParams, Method, RouteConfig,
+};
+use include_dir::{Dir, File};
+use std::{borrow::Cow, ptr, sync::atomic::Ordering};
+
+/// A directory of embedded files, each of which is served as its own route.
+///
+/// This is created by [`NestedRoute::static_dir`], and can be used to build
+/// responses for the files it contains with [`StaticDir::respond`]. The route
+/// generated for each file carries it as a [`StaticFile`] in its
+/// [`RouteConfig`](crate::RouteConfig), which the server integrations serve it
+/// with.
+#[derive(Debug, Clone, Copy)]
+pub struct StaticDir {
+    id: u16,
+    dir: &'static Dir<'static>,
+}
+
+impl PartialEq for StaticDir {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && ptr::eq(self.dir, other.dir)
+    }
+}
+
+impl Eq for StaticDir {}
+
+impl<Segments> NestedRoute<Segments, StaticDir, (), ()> {
+    /// Creates a route that serves the files in an embedded directory, with
+    /// each file matched at `{path}/{filename}`.
+    ///
+    /// The path of the file within the directory is available as the `filename`
+    /// param.
+    pub fn static_dir(path: Segments, dir: &'static Dir<'static>) -> Self {
+        NestedRoute {
+            id: ROUTE_ID.fetch_add(1, Ordering::Relaxed),
+            segments: path,
+            children: Some(StaticDir::new(dir)),
+            data: (),
+            view: (),
+            methods: [Method::Get].into(),
+            default_methods: false,
+            ssr_mode: Default::default(),
+            no_static: false,
+            config: Default::default(),
+            contexts: Default::default(),
+            meta: Default::default(),
+            pagination: None,
+            language_redirect: None,
+            allowed_params: Default::default(),
+            matchers: Default::default(),
+            tenant: None,
+            region_hint: None,
+        }
+    }
+}
+
+impl StaticDir {
+    /// Wraps an embedded directory.
+    pub fn new(dir: &'static Dir<'static>) -> Self {
+        Self {
+            id: ROUTE_ID.fetch_add(1, Ordering::Relaxed),
+            dir,
+        }
+    }
+
+    /// The embedded directory.
+    pub fn dir(&self) -> &'static Dir<'static> {
+        self.dir
+    }
+
+    /// Builds the response for the file at `path` within this directory, or
+    /// `None` if there is no such file.
+    ///
+    /// This responds with `304 Not Modified` if the request's `If-None-Match`
+    /// header matches the file's `ETag`, and with `206 Partial Content` (or
+    /// `416 Range Not Satisfiable`) if the request has a single-range `Range`
+    /// header.
+    pub fn respond(
+        &self,
+        path: &str,
+        request: &StaticAssetRequest,
+    ) -> Option<StaticAssetResponse> {
+        let file = self.dir.get_file(path.trim_start_matches('/'))?;
+        let contents: &'static [u8] = file.contents();
+        let len = contents.len();
+        let etag = etag(contents);
+
+        if request
+            .if_none_match
+            .as_deref()
+            .is_some_and(|header| etag_matches(header, &etag))
+        {
+            return Some(StaticAssetResponse {
+                status: 304,
+                headers: vec![("etag", etag)],
+                body: &[],
+            });
+        }
+
+        let mut headers = vec![
+            ("content-type", content_type(file)),
+            ("etag", etag),
+            ("accept-ranges", "bytes".to_string()),
+        ];
+
+        match request
+            .range
+            .as_deref()
+            .and_then(|range| parse_range(range, len))
+        {
+            None => {
+                headers.push(("content-length", len.to_string()));
+                Some(StaticAssetResponse {
+                    status: 200,
+                    headers,
+                    body: contents,
+                })
+            }
+            Some(Ok((start, end))) => {
+                headers.push(("content-length", (end - start + 1).to_string()));
+                headers.push((
+                    "content-range",
+                    format!("bytes {start}-{end}/{len}"),
+                ));
+                Some(StaticAssetResponse {
+                    status: 206,
+                    headers,
+                    body: &contents[start..=end],
+                })
+            }
+            Some(Err(())) => Some(StaticAssetResponse {
+                status: 416,
+                headers: vec![("content-range", format!("bytes */{len}"))],
+                body: &[],
+            }),
+        }
+    }
+
+    fn files(&self) -> Vec<&'static File<'static>> {
+        fn collect(
+            dir: &'static Dir<'static>,
+            files: &mut Vec<&'static File<'static>>,
+        ) {
+            files.extend(dir.files());
+            for dir in dir.dirs() {
+                collect(dir, files);
+            }
+        }
+
+        let mut files = Vec::new();
+        collect(self.dir, &mut files);
+        files
+    }
+}
+
+/// A file of a [`StaticDir`], which the route generated for it serves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticFile {
+    dir: StaticDir,
+    path: String,
+}
+
+impl StaticFile {
+    /// The path of the file within its directory.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Builds the response for the file, as with [`StaticDir::respond`].
+    pub fn respond(&self, request: &StaticAssetRequest) -> StaticAssetResponse {
+        self.dir
+            .respond(&self.path, request)
+            .expect("a static file is one of the files of its directory")
+    }
+}
+
+/// The request headers used by [`StaticDir::respond`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StaticAssetRequest {
+    /// The value of the `Range` header.
+    pub range: Option<String>,
+    /// The value of the `If-None-Match` header.
+    pub if_none_match: Option<String>,
+}
+
+/// A response for a file in a [`StaticDir`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticAssetResponse {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The response headers, with lowercase names.
+    pub headers: Vec<(&'static str, String)>,
+    /// The response body.
+    pub body: &'static [u8],
+}
+
+fn content_type(file: &File<'_>) -> String {
+    mime_guess::from_path(file.path())
+        .first_or_octet_stream()
+        .to_string()
+}
+
+/// A strong `ETag` derived from the file contents (64-bit FNV-1a), so that it
+/// is stable across builds of the same assets.
+fn etag(contents: &[u8]) -> String {
+    let hash = contents.iter().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("\"{hash:016x}\"")
+}
+
+fn etag_matches(header: &str, etag: &str) -> bool {
+    header.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.trim_start_matches("W/") == etag
+    })
+}
+
+/// Parses a `Range` header for a resource of length `len`.
+///
+/// Returns `None` if the header should be ignored (because it is malformed or
+/// requests multiple ranges), or `Some(Err(()))` if the range cannot be
+/// satisfied.
+fn parse_range(range: &str, len: usize) -> Option<Result<(usize, usize), ()>> {
+    let range = range.trim().strip_prefix("bytes=")?;
+    if range.contains(',') {
+        return None;
+    }
+    let (start, end) = range.split_once('-')?;
+    let (start, end) = (start.trim(), end.trim());
+
+    let range = if start.is_empty() {
+        let suffix = end.parse::<usize>().ok()?;
+        if suffix == 0 || len == 0 {
+            return Some(Err(()));
+        }
+        (len.saturating_sub(suffix), len - 1)
+    } else {
+        let start = start.parse::<usize>().ok()?;
+        let end = if end.is_empty() {
+            usize::MAX
+        } else {
+            end.parse::<usize>().ok()?
+        };
+        if end < start {
+            return None;
+        }
+        if start >= len {
+            return Some(Err(()));
+        }
+        (start, end.min(len - 1))
+    };
+    Some(Ok(range))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct StaticFileMatch {
+    id: RouteMatchId,
+    matched: String,
+}
+
+impl MatchParams for StaticFileMatch {
+    fn to_params(&self) -> Vec<(Cow<'static, str>, String)> {
+        vec![("filename".into(), self.matched.clone())]
+    }
+}
+
+impl MatchInterface for StaticFileMatch {
+    type Child = ();
+
+    fn as_id(&self) -> RouteMatchId {
+        self.id
+    }
+
+    fn as_matched(&self) -> &str {
+        &self.matched
+    }
+
+    fn matched_segments(&self) -> Vec<&str> {
+        self.matched
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect()
+    }
+
+    fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>) {
+        ((), None)
+    }
+}
+
+impl MatchNestedRoutes for StaticDir {
+    type Data = ();
+    type Match = StaticFileMatch;
+
+    fn optional(&self) -> bool {
+        false
+    }
+
+    fn match_request<'a>(
+        &'a self,
+        req: MatchRequest<'a>,
+    ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
+        let path = req.as_path();
+        let filename = path.trim_start_matches('/');
+        match self.dir.get_file(filename) {
+            Some(_) => {
+                let id = RouteMatchId(self.id);
+                (
+                    Some((
+                        id,
+                        StaticFileMatch {
+                            id,
+                            matched: filename.to_string(),
+                        },
+                    )),
+                    "",
+                )
+            }
+            None => (None, path),
+        }
+    }
+
+    fn generate_routes(
+        &self,
+        defaults: &RouteDefaults,
+    ) -> impl IntoIterator<Item = GeneratedRouteData> {
+        // the files are only ever served to `GET` requests
+        _ = defaults;
+        self.files().into_iter().map(|file| {
+            let path = file.path().to_string_lossy().into_owned();
+            GeneratedRouteData {
+                segments: vec![PathSegment::Static(path.clone().into())],
+                methods: [Method::Get].into(),
+                config: RouteConfig {
+                    static_file: Some(StaticFile { dir: *self, path }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StaticAssetRequest, StaticDir};
+    use crate::{
+        matching::MatchParams, NestedRoute, PathSegment, RouteDefs,
+        StaticSegment,
+    };
+    use include_dir::{Dir, DirEntry, File};
+
+    static ASSETS: Dir<'static> = Dir::new(
+        "",
+        &[
+            DirEntry::File(File::new("app.css", b"body { margin: 0; }")),
+            DirEntry::Dir(Dir::new(
+                "js",
+                &[DirEntry::File(File::new("js/app.js", b"console.log(1);"))],
+            )),
+        ],
+    );
+
+    fn header<'a>(
+        headers: &'a [(&'static str, String)],
+        name: &str,
+    ) -> &'a str {
+        headers
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.as_str())
+            .unwrap()
+    }
+
+    #[test]
+    fn static_dir_generates_route_per_file() {
+        let routes = RouteDefs::<_>::new(NestedRoute::static_dir(
+            StaticSegment("assets"),
+            &ASSETS,
+        ));
+        let (paths, files): (Vec<_>, Vec<_>) = routes
+            .generate_routes()
+            .1
+            .into_iter()
+            .map(|route| (route.segments, route.config.static_file.unwrap()))
+            .unzip();
+        assert_eq!(
+            paths,
+            vec![
+                vec![
+                    PathSegment::Static("assets".into()),
+                    PathSegment::Static("app.css".into())
+                ],
+                vec![
+                    PathSegment::Static("assets".into()),
+                    PathSegment::Static("js/app.js".into())
+                ]
+            ]
+        );
+        // each route serves its own file
+        assert_eq!(
+            files.iter().map(|file| file.path()).collect::<Vec<_>>(),
+            ["app.css", "js/app.js"]
+        );
+        assert_eq!(
+            files[1].respond(&StaticAssetRequest::default()).body,
+            b"console.log(1);"
+        );
+    }
+
+    #[test]
+    fn static_dir_matches_files() {
+        let routes = RouteDefs::<_>::new(NestedRoute::static_dir(
+            StaticSegment("assets"),
+            &ASSETS,
+        ));
+        let matched = routes.match_route("/assets/js/app.js").unwrap();
+        assert_eq!(
+            matched.to_params(),
+            vec![("filename".into(), "js/app.js".into())]
+        );
+        assert!(routes.match_route("/assets/missing.css").is_none());
+    }
+
+    #[test]
+    fn responds_with_full_file() {
+        let dir = StaticDir::new(&ASSETS);
+        let res = dir
+            .respond("app.css", &StaticAssetRequest::default())
+            .unwrap();
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body, b"body { margin: 0; }");
+        assert_eq!(header(&res.headers, "content-type"), "text/css");
+        assert_eq!(header(&res.headers, "content-length"), "19");
+        assert!(dir.respond("missing.css", &Default::default()).is_none());
+    }
+
+    #[test]
+    fn responds_to_conditional_request() {
+        let dir = StaticDir::new(&ASSETS);
+        let res = dir.respond("app.css", &Default::default()).unwrap();
+        let etag = header(&res.headers, "etag").to_string();
+
+        let res = dir
+            .respond(
+                "app.css",
+                &StaticAssetRequest {
+                    if_none_match: Some(format!("\"other\", W/{etag}")),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(res.status, 304);
+        assert!(res.body.is_empty());
+    }
+
+    #[test]
+    fn responds_to_range_request() {
+        let dir = StaticDir::new(&ASSETS);
+        let range = |range: &str| {
+            dir.respond(
+                "app.css",
+                &StaticAssetRequest {
+                    range: Some(range.to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+
+        let res = range("bytes=0-3");
+        assert_eq!(res.status, 206);
+        assert_eq!(res.body, b"body");
+        assert_eq!(header(&res.headers, "content-range"), "bytes 0-3/19");
+
+        let res = range("bytes=-3");
+        assert_eq!(res.body, b"; }");
+
+        let res = range("bytes=100-");
+        assert_eq!(res.status, 416);
+        assert_eq!(header(&res.headers, "content-range"), "bytes */19");
+
+        let res = range("bytes=0-1, 4-5");
+        assert_eq!(res.status, 200);
+    }
+}
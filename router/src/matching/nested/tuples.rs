@@ -1,8 +1,18 @@
-use super::{MatchInterface, MatchNestedRoutes, PathSegment, RouteMatchId};
-use crate::{ChooseView, GeneratedRouteData, MatchParams};
+use super::{
+    MatchInterface, MatchNestedRoutes, MatchRequest, PathSegment,
+    RouteDefaults, RouteMatchId,
+};
+use crate::{
+    ChooseView, ContentTypes, GeneratedRouteData, MatchParams, Method,
+    QueryErrors, RouteGuard, SurrogateKeys, TooManyPerPage, WebSocketHandler,
+};
 use core::iter;
 use either_of::*;
-use std::borrow::Cow;
+use std::{
+    any::{Any, TypeId},
+    borrow::Cow,
+    collections::HashSet,
+};
 use tachys::view::iterators::StaticVec;
 
 impl MatchParams for () {
@@ -22,6 +32,10 @@ impl MatchInterface for () {
         ""
     }
 
+    fn matched_segments(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
     fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>) {
         ((), None)
     }
@@ -35,16 +49,18 @@ impl MatchNestedRoutes for () {
         false
     }
 
-    fn match_nested<'a>(
+    fn match_request<'a>(
         &self,
-        path: &'a str,
+        req: MatchRequest<'a>,
     ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
-        (Some((RouteMatchId(0), ())), path)
+        (Some((RouteMatchId(0), ())), req.as_path())
     }
 
     fn generate_routes(
         &self,
-    ) -> impl IntoIterator<Item = GeneratedRouteData> + '_ {
+        defaults: &RouteDefaults,
+    ) -> impl IntoIterator<Item = GeneratedRouteData> {
+        _ = defaults;
         iter::once(GeneratedRouteData {
             segments: vec![PathSegment::Unit],
             ..Default::default()
@@ -75,9 +91,49 @@ where
         self.0.as_matched()
     }
 
+    fn matched_segments(&self) -> Vec<&str> {
+        self.0.matched_segments()
+    }
+
     fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>) {
         self.0.into_view_and_child()
     }
+
+    fn provide_route_contexts(&self) {
+        self.0.provide_route_contexts()
+    }
+
+    fn allowed_methods(&self) -> Option<HashSet<Method>> {
+        self.0.allowed_methods()
+    }
+
+    fn query_errors(&self) -> Option<QueryErrors> {
+        self.0.query_errors()
+    }
+
+    fn pagination_error(&self) -> Option<TooManyPerPage> {
+        self.0.pagination_error()
+    }
+
+    fn websocket(&self) -> Option<WebSocketHandler> {
+        self.0.websocket()
+    }
+
+    fn consumes(&self) -> Option<ContentTypes> {
+        self.0.consumes()
+    }
+
+    fn surrogate_keys(&self) -> Option<SurrogateKeys> {
+        self.0.surrogate_keys()
+    }
+
+    fn guards(&self) -> Vec<RouteGuard> {
+        self.0.guards()
+    }
+
+    fn meta_of(&self, type_id: TypeId) -> Option<&(dyn Any + Send + Sync)> {
+        self.0.meta_of(type_id)
+    }
 }
 
 impl<A> MatchNestedRoutes for (A,)
@@ -87,17 +143,26 @@ where
     type Data = A::Data;
     type Match = A::Match;
 
-    fn match_nested<'a>(
+    fn match_request<'a>(
+        &'a self,
+        req: MatchRequest<'a>,
+    ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
+        self.0.match_request(req)
+    }
+
+    fn match_branch<'a>(
         &'a self,
-        path: &'a str,
+        branch: &[usize],
+        req: MatchRequest<'a>,
     ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
-        self.0.match_nested(path)
+        self.0.match_branch(branch, req)
     }
 
     fn generate_routes(
         &self,
-    ) -> impl IntoIterator<Item = GeneratedRouteData> + '_ {
-        self.0.generate_routes()
+        defaults: &RouteDefaults,
+    ) -> impl IntoIterator<Item = GeneratedRouteData> {
+        self.0.generate_routes(defaults)
     }
 
     fn optional(&self) -> bool {
@@ -139,6 +204,13 @@ where
         }
     }
 
+    fn matched_segments(&self) -> Vec<&str> {
+        match self {
+            Either::Left(i) => i.matched_segments(),
+            Either::Right(i) => i.matched_segments(),
+        }
+    }
+
     fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>) {
         match self {
             Either::Left(i) => {
@@ -151,6 +223,69 @@ where
             }
         }
     }
+
+    fn provide_route_contexts(&self) {
+        match self {
+            Either::Left(i) => i.provide_route_contexts(),
+            Either::Right(i) => i.provide_route_contexts(),
+        }
+    }
+
+    fn allowed_methods(&self) -> Option<HashSet<Method>> {
+        match self {
+            Either::Left(i) => i.allowed_methods(),
+            Either::Right(i) => i.allowed_methods(),
+        }
+    }
+
+    fn query_errors(&self) -> Option<QueryErrors> {
+        match self {
+            Either::Left(i) => i.query_errors(),
+            Either::Right(i) => i.query_errors(),
+        }
+    }
+
+    fn pagination_error(&self) -> Option<TooManyPerPage> {
+        match self {
+            Either::Left(i) => i.pagination_error(),
+            Either::Right(i) => i.pagination_error(),
+        }
+    }
+
+    fn websocket(&self) -> Option<WebSocketHandler> {
+        match self {
+            Either::Left(i) => i.websocket(),
+            Either::Right(i) => i.websocket(),
+        }
+    }
+
+    fn consumes(&self) -> Option<ContentTypes> {
+        match self {
+            Either::Left(i) => i.consumes(),
+            Either::Right(i) => i.consumes(),
+        }
+    }
+
+    fn surrogate_keys(&self) -> Option<SurrogateKeys> {
+        match self {
+            Either::Left(i) => i.surrogate_keys(),
+            Either::Right(i) => i.surrogate_keys(),
+        }
+    }
+
+    fn guards(&self) -> Vec<RouteGuard> {
+        match self {
+            Either::Left(i) => i.guards(),
+            Either::Right(i) => i.guards(),
+        }
+    }
+
+    fn meta_of(&self, type_id: TypeId) -> Option<&(dyn Any + Send + Sync)> {
+        match self {
+            Either::Left(i) => i.meta_of(type_id),
+            Either::Right(i) => i.meta_of(type_id),
+        }
+    }
 }
 
 impl<A, B> MatchNestedRoutes for (A, B)
@@ -161,30 +296,55 @@ where
     type Data = (A::Data, B::Data);
     type Match = Either<A::Match, B::Match>;
 
-    fn match_nested<'a>(
+    fn match_request<'a>(
         &'a self,
-        path: &'a str,
+        req: MatchRequest<'a>,
     ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
         #[allow(non_snake_case)]
         let (A, B) = &self;
-        if let (Some((id, matched)), remaining) = A.match_nested(path) {
+        if let (Some((id, matched)), remaining) = A.match_request(req) {
             return (Some((id, Either::Left(matched))), remaining);
         }
-        if let (Some((id, matched)), remaining) = B.match_nested(path) {
+        if let (Some((id, matched)), remaining) = B.match_request(req) {
             return (Some((id, Either::Right(matched))), remaining);
         }
-        (None, path)
+        (None, req.as_path())
+    }
+
+    fn match_branch<'a>(
+        &'a self,
+        branch: &[usize],
+        req: MatchRequest<'a>,
+    ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
+        #[allow(non_snake_case)]
+        let (A, B) = &self;
+        match branch.split_first() {
+            Some((0, rest)) => match A.match_branch(rest, req) {
+                (Some((id, matched)), remaining) => {
+                    (Some((id, Either::Left(matched))), remaining)
+                }
+                (None, _) => (None, req.as_path()),
+            },
+            Some((1, rest)) => match B.match_branch(rest, req) {
+                (Some((id, matched)), remaining) => {
+                    (Some((id, Either::Right(matched))), remaining)
+                }
+                (None, _) => (None, req.as_path()),
+            },
+            _ => self.match_request(req),
+        }
     }
 
     fn generate_routes(
         &self,
-    ) -> impl IntoIterator<Item = GeneratedRouteData> + '_ {
+        defaults: &RouteDefaults,
+    ) -> impl IntoIterator<Item = GeneratedRouteData> {
         #![allow(non_snake_case)]
 
         let (A, B) = &self;
 
-        let A = A.generate_routes().into_iter();
-        let B = B.generate_routes().into_iter();
+        let A = at_index(0, A.generate_routes(defaults));
+        let B = at_index(1, B.generate_routes(defaults));
 
         A.chain(B)
     }
@@ -201,22 +361,39 @@ where
     type Data = Vec<T::Data>;
     type Match = T::Match;
 
-    fn match_nested<'a>(
+    fn match_request<'a>(
         &'a self,
-        path: &'a str,
+        req: MatchRequest<'a>,
     ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
         for item in self.iter() {
-            if let (Some((id, matched)), remaining) = item.match_nested(path) {
+            if let (Some((id, matched)), remaining) = item.match_request(req) {
                 return (Some((id, matched)), remaining);
             }
         }
-        (None, path)
+        (None, req.as_path())
+    }
+
+    fn match_branch<'a>(
+        &'a self,
+        branch: &[usize],
+        req: MatchRequest<'a>,
+    ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
+        match branch.split_first() {
+            Some((index, rest)) => match self.iter().as_slice().get(*index) {
+                Some(item) => item.match_branch(rest, req),
+                None => (None, req.as_path()),
+            },
+            None => self.match_request(req),
+        }
     }
 
     fn generate_routes(
         &self,
-    ) -> impl IntoIterator<Item = GeneratedRouteData> + '_ {
-        self.iter().flat_map(T::generate_routes)
+        defaults: &RouteDefaults,
+    ) -> impl IntoIterator<Item = GeneratedRouteData> {
+        self.iter().enumerate().flat_map(|(index, item)| {
+            at_index(index, item.generate_routes(defaults))
+        })
     }
 
     fn optional(&self) -> bool {
@@ -224,6 +401,18 @@ where
     }
 }
 
+/// Adds the index of a set of routes among their siblings to the start of their
+/// branches.
+fn at_index(
+    index: usize,
+    routes: impl IntoIterator<Item = GeneratedRouteData>,
+) -> impl Iterator<Item = GeneratedRouteData> {
+    routes.into_iter().map(move |mut route| {
+        route.branch.insert(0, index);
+        route
+    })
+}
+
 macro_rules! chain_generated {
     ($first:expr, $second:expr, ) => {
         $first.chain($second)
@@ -267,6 +456,12 @@ macro_rules! tuples {
                 }
             }
 
+            fn matched_segments(&self) -> Vec<&str> {
+                match self {
+                    $($either::$ty(i) => i.matched_segments(),)*
+                }
+            }
+
             fn into_view_and_child(
                 self,
             ) -> (
@@ -280,6 +475,63 @@ macro_rules! tuples {
                     })*
                 }
             }
+
+            fn provide_route_contexts(&self) {
+                match self {
+                    $($either::$ty(i) => i.provide_route_contexts(),)*
+                }
+            }
+
+            fn allowed_methods(&self) -> Option<HashSet<Method>> {
+                match self {
+                    $($either::$ty(i) => i.allowed_methods(),)*
+                }
+            }
+
+            fn query_errors(&self) -> Option<QueryErrors> {
+                match self {
+                    $($either::$ty(i) => i.query_errors(),)*
+                }
+            }
+
+            fn pagination_error(&self) -> Option<TooManyPerPage> {
+                match self {
+                    $($either::$ty(i) => i.pagination_error(),)*
+                }
+            }
+
+            fn websocket(&self) -> Option<WebSocketHandler> {
+                match self {
+                    $($either::$ty(i) => i.websocket(),)*
+                }
+            }
+
+            fn consumes(&self) -> Option<ContentTypes> {
+                match self {
+                    $($either::$ty(i) => i.consumes(),)*
+                }
+            }
+
+            fn surrogate_keys(&self) -> Option<SurrogateKeys> {
+                match self {
+                    $($either::$ty(i) => i.surrogate_keys(),)*
+                }
+            }
+
+            fn guards(&self) -> Vec<RouteGuard> {
+                match self {
+                    $($either::$ty(i) => i.guards(),)*
+                }
+            }
+
+            fn meta_of(
+                &self,
+                type_id: TypeId,
+            ) -> Option<&(dyn Any + Send + Sync)> {
+                match self {
+                    $($either::$ty(i) => i.meta_of(type_id),)*
+                }
+            }
         }
 
         impl<$($ty),*> MatchNestedRoutes for ($($ty,)*)
@@ -296,23 +548,36 @@ macro_rules! tuples {
                 true
             }
 
-            fn match_nested<'a>(&'a self, path: &'a str) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
+            fn match_request<'a>(&'a self, req: MatchRequest<'a>) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
                 #[allow(non_snake_case)]
 
                 let ($($ty,)*) = &self;
-                $(if let (Some((_, matched)), remaining) = $ty.match_nested(path) {
+                $(if let (Some((_, matched)), remaining) = $ty.match_request(req) {
                     return (Some((RouteMatchId($count), $either::$ty(matched))), remaining);
                 })*
-                (None, path)
+                (None, req.as_path())
+            }
+
+            fn match_branch<'a>(&'a self, branch: &[usize], req: MatchRequest<'a>) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
+                #[allow(non_snake_case)]
+                let ($($ty,)*) = &self;
+                match branch.split_first() {
+                    $(Some((&index, rest)) if index == $count => match $ty.match_branch(rest, req) {
+                        (Some((_, matched)), remaining) => (Some((RouteMatchId($count), $either::$ty(matched))), remaining),
+                        (None, _) => (None, req.as_path()),
+                    },)*
+                    _ => self.match_request(req),
+                }
             }
 
             fn generate_routes(
                 &self,
-            ) -> impl IntoIterator<Item = GeneratedRouteData> + '_ {
+                defaults: &RouteDefaults,
+            ) -> impl IntoIterator<Item = GeneratedRouteData> {
                 #![allow(non_snake_case)]
 
                 let ($($ty,)*) = &self;
-                $(let $ty = $ty.generate_routes().into_iter();)*
+                $(let $ty = at_index($count, $ty.generate_routes(defaults));)*
                 chain_generated!($($ty,)*)
             }
         }
@@ -333,3 +598,19 @@ tuples!(EitherOf13 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I
 tuples!(EitherOf14 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13);
 tuples!(EitherOf15 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14);
 tuples!(EitherOf16 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15);
+tuples!(EitherOf17 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16);
+tuples!(EitherOf18 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16, R = 17);
+tuples!(EitherOf19 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16, R = 17, S = 18);
+tuples!(EitherOf20 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16, R = 17, S = 18, T = 19);
+tuples!(EitherOf21 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16, R = 17, S = 18, T = 19, U = 20);
+tuples!(EitherOf22 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16, R = 17, S = 18, T = 19, U = 20, V = 21);
+tuples!(EitherOf23 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16, R = 17, S = 18, T = 19, U = 20, V = 21, W = 22);
+tuples!(EitherOf24 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16, R = 17, S = 18, T = 19, U = 20, V = 21, W = 22, X = 23);
+tuples!(EitherOf25 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16, R = 17, S = 18, T = 19, U = 20, V = 21, W = 22, X = 23, Y = 24);
+tuples!(EitherOf26 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16, R = 17, S = 18, T = 19, U = 20, V = 21, W = 22, X = 23, Y = 24, Z = 25);
+tuples!(EitherOf27 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16, R = 17, S = 18, T = 19, U = 20, V = 21, W = 22, X = 23, Y = 24, Z = 25, AA = 26);
+tuples!(EitherOf28 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16, R = 17, S = 18, T = 19, U = 20, V = 21, W = 22, X = 23, Y = 24, Z = 25, AA = 26, AB = 27);
+tuples!(EitherOf29 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16, R = 17, S = 18, T = 19, U = 20, V = 21, W = 22, X = 23, Y = 24, Z = 25, AA = 26, AB = 27, AC = 28);
+tuples!(EitherOf30 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16, R = 17, S = 18, T = 19, U = 20, V = 21, W = 22, X = 23, Y = 24, Z = 25, AA = 26, AB = 27, AC = 28, AD = 29);
+tuples!(EitherOf31 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16, R = 17, S = 18, T = 19, U = 20, V = 21, W = 22, X = 23, Y = 24, Z = 25, AA = 26, AB = 27, AC = 28, AD = 29, AE = 30);
+tuples!(EitherOf32 => A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7, I = 8, J = 9, K = 10, L = 11, M = 12, N = 13, O = 14, P = 15, Q = 16, R = 17, S = 18, T = 19, U = 20, V = 21, W = 22, X = 23, Y = 24, Z = 25, AA = 26, AB = 27, AC = 28, AD = 29, AE = 30, AF = 31);
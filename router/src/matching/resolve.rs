@@ -0,0 +1,185 @@
+use super::RouteMatchId;
+use crate::{
+    params::ParamsError, ContentTypes, Method, QueryErrors, TooManyPerPage,
+};
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// The successful result of [resolving](super::RouteDefs::resolve) a request
+/// against a set of route definitions.
+#[derive(Debug)]
+pub struct MatchSuccess<Match> {
+    id: RouteMatchId,
+    route: Match,
+    error: Option<MatchError>,
+    original_path: Option<String>,
+}
+
+impl<Match> MatchSuccess<Match> {
+    pub(crate) fn new(id: RouteMatchId, route: Match) -> Self {
+        Self {
+            id,
+            route,
+            error: None,
+            original_path: None,
+        }
+    }
+
+    pub(crate) fn rewritten_from(mut self, original_path: String) -> Self {
+        self.original_path = Some(original_path);
+        self
+    }
+
+    pub(crate) fn handling(mut self, error: MatchError) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    /// The ID of the outermost route that was matched.
+    pub fn id(&self) -> RouteMatchId {
+        self.id
+    }
+
+    /// The matched route.
+    pub fn route(&self) -> &Match {
+        &self.route
+    }
+
+    /// The error this route was resolved to handle, if it is the route
+    /// registered with
+    /// [`on_method_not_allowed`](super::RouteDefs::on_method_not_allowed) or
+    /// [`on_error`](super::RouteDefs::on_error), rather than the route that
+    /// matches the request.
+    pub fn error(&self) -> Option<&MatchError> {
+        self.error.as_ref()
+    }
+
+    /// The path as it was requested, if a [rewriter](super::RouteDefs::rewrite)
+    /// changed it before the routes were matched against it.
+    pub fn original_path(&self) -> Option<&str> {
+        self.original_path.as_deref()
+    }
+
+    /// The HTTP status code of the response: `200`, or that of the
+    /// [`error`](Self::error) this route is handling.
+    pub fn status_code(&self) -> u16 {
+        self.error.as_ref().map_or(200, MatchError::status_code)
+    }
+
+    /// Consumes the result, returning the matched route.
+    pub fn into_route(self) -> Match {
+        self.route
+    }
+}
+
+/// A redirect produced while resolving a route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteRedirect {
+    /// The path to redirect to.
+    pub path: String,
+    /// The HTTP status code that should be used for the redirect.
+    pub status: u16,
+}
+
+impl RouteRedirect {
+    /// Creates a temporary (`302 Found`) redirect to the given path.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            status: 302,
+        }
+    }
+
+    /// Sets the status code used for the redirect.
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+}
+
+/// Every way in which resolving a request against a set of route definitions
+/// can fail.
+///
+/// This gives the server integrations a single, exhaustive set of outcomes to
+/// handle.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum MatchError {
+    /// No route matches the path.
+    #[error("no route matches this path")]
+    NotFound,
+    /// A route matches the path, but not the request method. Contains the set
+    /// of methods that are allowed for this path.
+    #[error("method not allowed")]
+    MethodNotAllowed(HashSet<Method>),
+    /// The request should be redirected.
+    #[error("redirect to {}", .0.path)]
+    Redirect(RouteRedirect),
+    /// A route matches, but the request is not allowed to access it.
+    #[error("forbidden")]
+    Forbidden,
+    /// The params of the matched route could not be parsed.
+    #[error("failed to parse params: {0}")]
+    ParamParse(ParamsError),
+    /// The query of the request does not match the route's
+    /// [schema](crate::NestedRoute::validate_query).
+    #[error("invalid query: {0}")]
+    InvalidQuery(QueryErrors),
+    /// The request asks for more items per page than the
+    /// [paginated](crate::NestedRoute::with_pagination) route allows.
+    #[error("invalid page params: {0}")]
+    InvalidPagination(TooManyPerPage),
+    /// A route matches, but the request has a body with a content type that it
+    /// does not [consume](crate::NestedRoute::consumes). Contains the content
+    /// types that it does.
+    #[error("unsupported media type")]
+    UnsupportedMediaType(ContentTypes),
+    /// The request body is larger than the route allows.
+    #[error("request body too large")]
+    BodyTooLarge,
+    /// The server failed while handling the request.
+    #[error("internal error: {0}")]
+    Internal(String),
+}
+
+impl MatchError {
+    /// The HTTP status code that corresponds to this error.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            MatchError::NotFound => 404,
+            MatchError::MethodNotAllowed(_) => 405,
+            MatchError::Redirect(redirect) => redirect.status,
+            MatchError::Forbidden => 403,
+            MatchError::ParamParse(_) => 400,
+            MatchError::InvalidQuery(_) => 400,
+            MatchError::InvalidPagination(_) => 400,
+            MatchError::UnsupportedMediaType(_) => 415,
+            MatchError::BodyTooLarge => 413,
+            MatchError::Internal(_) => 500,
+        }
+    }
+
+    /// The value of the `Allow` header for a
+    /// [`MethodNotAllowed`](Self::MethodNotAllowed) error, which lists the
+    /// allowed methods in alphabetical order, like `GET, POST`.
+    pub fn allow_header(&self) -> Option<String> {
+        let MatchError::MethodNotAllowed(allowed) = self else {
+            return None;
+        };
+        let mut methods =
+            allowed.iter().map(Method::as_str).collect::<Vec<_>>();
+        methods.sort_unstable();
+        Some(methods.join(", "))
+    }
+}
+
+impl From<ParamsError> for MatchError {
+    fn from(value: ParamsError) -> Self {
+        MatchError::ParamParse(value)
+    }
+}
+
+impl From<RouteRedirect> for MatchError {
+    fn from(value: RouteRedirect) -> Self {
+        MatchError::Redirect(value)
+    }
+}
@@ -29,7 +29,7 @@ pub fn resolve_path<'a>(
     }
 }
 
-fn has_scheme(path: &str) -> bool {
+pub(crate) fn has_scheme(path: &str) -> bool {
     path.starts_with("//")
         || path.starts_with("tel:")
         || path.starts_with("mailto:")
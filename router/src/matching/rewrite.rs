@@ -0,0 +1,35 @@
+use std::{borrow::Cow, fmt, sync::Arc};
+
+type Rewriter = Arc<dyn Fn(&str) -> Cow<'_, str> + Send + Sync>;
+
+/// The functions that rewrite a path before it is matched, set with
+/// [`RouteDefs::rewrite`](super::RouteDefs::rewrite).
+#[derive(Clone, Default)]
+pub(crate) struct PathRewriters(Vec<Rewriter>);
+
+impl PathRewriters {
+    pub(crate) fn push(
+        &mut self,
+        rewriter: impl Fn(&str) -> Cow<'_, str> + Send + Sync + 'static,
+    ) {
+        self.0.push(Arc::new(rewriter));
+    }
+
+    /// Passes `path` through each of the rewriters in turn, in the order they
+    /// were added.
+    pub(crate) fn rewrite<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        let mut path = Cow::Borrowed(path);
+        for rewriter in &self.0 {
+            if let Cow::Owned(rewritten) = rewriter(&path) {
+                path = Cow::Owned(rewritten);
+            }
+        }
+        path
+    }
+}
+
+impl fmt::Debug for PathRewriters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PathRewriters").field(&self.0.len()).finish()
+    }
+}
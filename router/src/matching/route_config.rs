@@ -0,0 +1,312 @@
+use crate::{
+    ApiKeyConfig, Batch, BodySchemas, ClientCertConfig, ClientHint,
+    ContentTypes, DeduplicationConfig, EnvelopeConfig, ErrorFormat,
+    HeadInjection, HtmlMinifyConfig, LanguageNegotiation, NotFoundResponse,
+    Passthrough, PreloadLink, QuerySchema, ResponseSigningConfig, RouteGuard,
+    RouteMiddleware, ShutdownSignal, SlowRequestConfig, StickySessionPolicy,
+    SurrogateKeys, WebSocketHandler, WebhookSignatureConfig, XRobotsDirective,
+};
+use std::collections::HashMap;
+
+/// The options of a route that the server integrations apply to its
+/// requests and responses, which are set with the methods of
+/// [`NestedRoute`](crate::NestedRoute).
+///
+/// A route has its own options, and those it inherits from its ancestors,
+/// which are merged while the routes are
+/// [generated](crate::MatchNestedRoutes::generate_routes): most options are
+/// those of the innermost route in the chain that sets them, and those that
+/// are lists are those of every route in the chain, outermost first. The
+/// [`RouteListing`](crate::RouteListing) of a route carries the merged
+/// options to the server integrations.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct RouteConfig {
+    /// How requests to the route must be signed, set with
+    /// [`webhook_signature`](crate::NestedRoute::webhook_signature).
+    pub webhook_signature: Option<WebhookSignatureConfig>,
+    /// The API key that requests to the route must carry, set with
+    /// [`api_key`](crate::NestedRoute::api_key).
+    pub api_key: Option<ApiKeyConfig>,
+    /// How the language of the responses from the route is negotiated, set with
+    /// [`language_negotiation`](crate::NestedRoute::language_negotiation).
+    pub language_negotiation: Option<LanguageNegotiation>,
+    /// How the TLS client certificates of requests to the route are validated,
+    /// set with
+    /// [`require_client_cert`](crate::NestedRoute::require_client_cert).
+    pub client_cert: Option<ClientCertConfig>,
+    /// How concurrent identical requests to the route share a response, set
+    /// with [`deduplicate`](crate::NestedRoute::deduplicate).
+    pub deduplication: Option<DeduplicationConfig>,
+    /// How the HTML rendered for the route is minified, set with
+    /// [`minify_html`](crate::NestedRoute::minify_html).
+    pub minify_html: Option<HtmlMinifyConfig>,
+    /// The HTML inserted into the `<head>` of the route, with that of each of
+    /// its ancestors first, set with
+    /// [`inject_head`](crate::NestedRoute::inject_head).
+    pub head: HeadInjection,
+    /// What the route is for, set with
+    /// [`description`](crate::NestedRoute::description).
+    pub description: Option<String>,
+    /// The tags of the route and of each of its ancestors, set with
+    /// [`tag`](crate::NestedRoute::tag).
+    pub tags: Vec<String>,
+    /// The JSON schemas of the bodies the route responds with, by status
+    /// code, set with
+    /// [`response_schema`](crate::NestedRoute::response_schema).
+    /// A route's own schema for a status replaces that of its ancestors.
+    pub response_schemas: HashMap<u16, serde_json::Value>,
+    /// The schema that the query of every request to the route must match,
+    /// with the params of each of its ancestors' schemas, set with
+    /// [`validate_query`](crate::NestedRoute::validate_query).
+    pub query_schema: Option<QuerySchema>,
+    /// The schemas that the bodies of requests to the route must match, with
+    /// those of each of its ancestors for other content types, set with
+    /// [`validate_body_json`](crate::NestedRoute::validate_body_json) or
+    /// [`validate_body_for`](crate::NestedRoute::validate_body_for).
+    pub body_schemas: Option<BodySchemas>,
+    /// How the responses of the route are signed, set with
+    /// [`sign_response`](crate::NestedRoute::sign_response).
+    pub response_signing: Option<ResponseSigningConfig>,
+    /// The handler of the route's connections, if it is a
+    /// [WebSocket route](crate::NestedRoute::websocket), whose requests are
+    /// upgraded instead of rendering it.
+    pub websocket: Option<WebSocketHandler>,
+    /// The upstream the route's requests are forwarded to, if it is a
+    /// [passthrough route](crate::NestedRoute::passthrough), whose requests
+    /// are proxied instead of rendering it.
+    pub passthrough: Option<Passthrough>,
+    /// The requests the route runs together, if it is a
+    /// [batch route](crate::NestedRoute::batch), whose requests are run
+    /// instead of rendering it.
+    pub batch: Option<Batch>,
+    /// The body the route responds to every request with, with
+    /// `404 Not Found`, if it is a
+    /// [JSON not-found route](crate::NestedRoute::not_found_json).
+    pub not_found: Option<NotFoundResponse>,
+    /// How the errors of the route are encoded in its error responses, set with
+    /// [`error_format`](crate::NestedRoute::error_format).
+    pub error_format: Option<ErrorFormat>,
+    /// When a request to the route is slow enough to warn about, set with
+    /// [`slow_request_warning`](crate::NestedRoute::slow_request_warning).
+    pub slow_request: Option<SlowRequestConfig>,
+    /// The signal that stops the route from accepting requests, set with
+    /// [`graceful_shutdown`](crate::NestedRoute::graceful_shutdown).
+    pub shutdown: Option<ShutdownSignal>,
+    /// The functions that return the surrogate keys of the responses from
+    /// the route, of every route in its chain that sets them with
+    /// [`surrogate_key`](crate::NestedRoute::surrogate_key).
+    pub surrogate_keys: Option<SurrogateKeys>,
+    /// The content types the bodies of requests to the route must have, set
+    /// with [`consumes`](crate::NestedRoute::consumes).
+    pub consumes: Option<ContentTypes>,
+    /// How the JSON responses of the route are wrapped in an envelope, set with
+    /// [`response_envelope`](crate::NestedRoute::response_envelope).
+    pub response_envelope: Option<EnvelopeConfig>,
+    /// The middleware applied to the requests to the route, of every route in
+    /// its chain that sets it with
+    /// [`middleware`](crate::NestedRoute::middleware).
+    pub middleware: Option<RouteMiddleware>,
+    /// The HTTP status code of every response from the route, set with
+    /// [`status`](crate::NestedRoute::status). Otherwise, the view sets the
+    /// status, which is `200` by default.
+    pub status: Option<u16>,
+    /// Whether the responses from the route are left out of the access log,
+    /// which
+    /// [`exclude_from_access_log`](crate::NestedRoute::exclude_from_access_log)
+    /// sets.
+    pub exclude_from_access_log: Option<bool>,
+    /// How load balancers should keep routing a client's requests to the
+    /// route to the same server instance, set with
+    /// [`sticky_session`](crate::NestedRoute::sticky_session).
+    pub sticky_session: Option<StickySessionPolicy>,
+    /// The directives of the `X-Robots-Tag` header of every response from
+    /// the route, with those of its ancestors first, set with
+    /// [`x_robots_tag`](crate::NestedRoute::x_robots_tag).
+    pub x_robots_tag: Vec<XRobotsDirective>,
+    /// The assets the route needs, with those of its ancestors first, set with
+    /// [`preload`](crate::NestedRoute::preload).
+    pub preload: Vec<PreloadLink>,
+    /// The client hints the route asks browsers to send, with those of its
+    /// ancestors first, set with
+    /// [`request_client_hints`](crate::NestedRoute::request_client_hints).
+    pub client_hints: Vec<ClientHint>,
+    /// The guards that check whether a request can access the route, with those
+    /// of its ancestors first, set with [`guard`](crate::NestedRoute::guard) or
+    /// [`guard_async`](crate::NestedRoute::guard_async).
+    pub guards: Vec<RouteGuard>,
+    /// The file the route serves, if it is one of the routes generated for
+    /// the files of a [static directory](crate::NestedRoute::static_dir).
+    #[cfg(feature = "static_dir")]
+    pub static_file: Option<crate::StaticFile>,
+}
+
+impl RouteConfig {
+    /// The options of a route nested in one with these options, whose own
+    /// options are `child`.
+    pub(crate) fn inherited_by(&self, child: RouteConfig) -> RouteConfig {
+        let RouteConfig {
+            webhook_signature,
+            api_key,
+            language_negotiation,
+            client_cert,
+            deduplication,
+            minify_html,
+            head,
+            description,
+            tags,
+            response_schemas,
+            query_schema,
+            body_schemas,
+            response_signing,
+            websocket,
+            passthrough,
+            batch,
+            not_found,
+            error_format,
+            slow_request,
+            shutdown,
+            surrogate_keys,
+            consumes,
+            response_envelope,
+            middleware,
+            status,
+            exclude_from_access_log,
+            sticky_session,
+            x_robots_tag,
+            preload,
+            client_hints,
+            guards,
+            #[cfg(feature = "static_dir")]
+            static_file,
+        } = child;
+
+        let mut inherited_head = self.head.clone();
+        inherited_head.extend(head);
+        let mut inherited_response_schemas = self.response_schemas.clone();
+        inherited_response_schemas.extend(response_schemas);
+        let query_schema = match (&self.query_schema, query_schema) {
+            (Some(schema), Some(child_schema)) => {
+                let mut schema = schema.clone();
+                schema.extend(child_schema);
+                Some(schema)
+            }
+            (schema, child_schema) => child_schema.or_else(|| schema.clone()),
+        };
+        let body_schemas = match (&self.body_schemas, body_schemas) {
+            (Some(schemas), Some(child_schemas)) => {
+                let mut schemas = schemas.clone();
+                schemas.extend(child_schemas);
+                Some(schemas)
+            }
+            (schemas, child_schemas) => {
+                child_schemas.or_else(|| schemas.clone())
+            }
+        };
+
+        RouteConfig {
+            webhook_signature: webhook_signature
+                .or_else(|| self.webhook_signature.clone()),
+            api_key: api_key.or_else(|| self.api_key.clone()),
+            language_negotiation: language_negotiation
+                .or(self.language_negotiation),
+            client_cert: client_cert.or_else(|| self.client_cert.clone()),
+            deduplication: deduplication.or_else(|| self.deduplication.clone()),
+            minify_html: minify_html.or(self.minify_html),
+            head: inherited_head,
+            description: description.or_else(|| self.description.clone()),
+            tags: extend_unique(&self.tags, tags),
+            response_schemas: inherited_response_schemas,
+            query_schema,
+            body_schemas,
+            response_signing: response_signing
+                .or_else(|| self.response_signing.clone()),
+            websocket: websocket.or_else(|| self.websocket.clone()),
+            passthrough: passthrough.or_else(|| self.passthrough.clone()),
+            batch: batch.or_else(|| self.batch.clone()),
+            not_found: not_found.or_else(|| self.not_found.clone()),
+            error_format: error_format.or_else(|| self.error_format.clone()),
+            slow_request: slow_request.or(self.slow_request),
+            shutdown: shutdown.or_else(|| self.shutdown.clone()),
+            surrogate_keys: SurrogateKeys::inherit(
+                self.surrogate_keys.as_ref(),
+                surrogate_keys,
+            ),
+            consumes: consumes.or_else(|| self.consumes.clone()),
+            response_envelope: response_envelope
+                .or_else(|| self.response_envelope.clone()),
+            middleware: RouteMiddleware::inherit(
+                self.middleware.as_ref(),
+                middleware,
+            ),
+            status: status.or(self.status),
+            exclude_from_access_log: exclude_from_access_log
+                .or(self.exclude_from_access_log),
+            sticky_session: sticky_session.or(self.sticky_session),
+            x_robots_tag: extend_unique(&self.x_robots_tag, x_robots_tag),
+            preload: extend_unique(&self.preload, preload),
+            client_hints: extend_unique(&self.client_hints, client_hints),
+            guards: self.guards.iter().cloned().chain(guards).collect(),
+            #[cfg(feature = "static_dir")]
+            static_file: static_file.or_else(|| self.static_file.clone()),
+        }
+    }
+}
+
+/// The items of `parent` followed by those of `child` that are not in it.
+fn extend_unique<T>(parent: &[T], child: Vec<T>) -> Vec<T>
+where
+    T: Clone + PartialEq,
+{
+    let mut items = parent.to_vec();
+    for item in child {
+        if !items.contains(&item) {
+            items.push(item);
+        }
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RouteConfig;
+    use crate::{HeadInjection, XRobotsDirective};
+
+    #[test]
+    fn a_child_inherits_the_options_it_does_not_set() {
+        let mut head = HeadInjection::default();
+        head.push("<meta name=\"parent\">");
+        let parent = RouteConfig {
+            status: Some(410),
+            description: Some("parent".into()),
+            tags: vec!["docs".into()],
+            x_robots_tag: vec![XRobotsDirective::NoIndex],
+            head,
+            ..Default::default()
+        };
+        let mut head = HeadInjection::default();
+        head.push("<meta name=\"child\">");
+        let child = RouteConfig {
+            description: Some("child".into()),
+            tags: vec!["docs".into(), "api".into()],
+            x_robots_tag: vec![
+                XRobotsDirective::NoFollow,
+                XRobotsDirective::NoIndex,
+            ],
+            head,
+            ..Default::default()
+        };
+
+        let config = parent.inherited_by(child);
+        assert_eq!(config.status, Some(410));
+        assert_eq!(config.description.as_deref(), Some("child"));
+        assert_eq!(config.tags, ["docs", "api"]);
+        assert_eq!(
+            config.x_robots_tag,
+            [XRobotsDirective::NoIndex, XRobotsDirective::NoFollow]
+        );
+        assert_eq!(
+            config.head.html(),
+            "<meta name=\"parent\"><meta name=\"child\">"
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{Method, SsrMode, SsrModePrecedence};
+use std::{collections::HashSet, sync::Arc};
+
+/// The options of a set of route definitions that apply to each of its routes
+/// that does not set its own, which are passed down to the routes while they
+/// are [generated](crate::MatchNestedRoutes::generate_routes).
+///
+/// They are set on the [`RouteDefs`](crate::RouteDefs), with
+/// [`RouteDefs::default_methods`](crate::RouteDefs::default_methods) and
+/// [`RouteDefs::ssr_mode_precedence`](crate::RouteDefs::ssr_mode_precedence).
+/// The `Default` value is the one route definitions start with.
+#[derive(Debug, Clone)]
+pub struct RouteDefaults {
+    methods: Arc<HashSet<Method>>,
+    ssr_mode_precedence: SsrModePrecedence,
+}
+
+impl Default for RouteDefaults {
+    fn default() -> Self {
+        Self {
+            methods: Arc::new([Method::Get].into()),
+            ssr_mode_precedence: SsrMode::resolve,
+        }
+    }
+}
+
+impl RouteDefaults {
+    /// The methods handled by the routes that do not
+    /// [set their own](crate::NestedRoute::methods), which are only `GET` by
+    /// default.
+    pub fn methods(&self) -> &HashSet<Method> {
+        &self.methods
+    }
+
+    /// The mode of a child route under `parent`, with the
+    /// [precedence](SsrModePrecedence) of the router, which is
+    /// [`SsrMode::resolve`] by default.
+    pub fn resolve_ssr_mode(
+        &self,
+        parent: &SsrMode,
+        child: &SsrMode,
+    ) -> SsrMode {
+        (self.ssr_mode_precedence)(parent, child)
+    }
+
+    pub(crate) fn with_methods(
+        mut self,
+        methods: impl IntoIterator<Item = Method>,
+    ) -> Self {
+        self.methods = Arc::new(methods.into_iter().collect());
+        self
+    }
+
+    pub(crate) fn with_ssr_mode_precedence(
+        mut self,
+        precedence: SsrModePrecedence,
+    ) -> Self {
+        self.ssr_mode_precedence = precedence;
+        self
+    }
+}
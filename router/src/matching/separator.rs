@@ -0,0 +1,55 @@
+/// Sets the character that separates the segments of the paths a router
+/// matches.
+///
+/// By default this is `/`, as in a URL. Other separators are useful for routing
+/// things that are not URLs, like `.` for the commands of a CLI
+/// (`remote.add.origin`).
+///
+/// Used with [`RouteDefs::separator`](crate::RouteDefs::separator). The
+/// segments of the routes are still defined the same way, and the paths they
+/// generate still use `/`: only the way paths are split while matching changes.
+/// A path does not need to start with the separator, so `a.b.c` is matched in
+/// the same way as `.a.b.c`.
+///
+/// ```rust
+/// use leptos_router::{
+///     NestedRoute, ParamSegment, RouteDefs, SeparatorConfig, StaticSegment,
+/// };
+///
+/// let routes = RouteDefs::<_>::new(NestedRoute::new(
+///     (StaticSegment("a"), ParamSegment("x"), StaticSegment("c")),
+///     || (),
+/// ))
+/// .separator(SeparatorConfig::new('.'));
+///
+/// assert!(routes.match_route("a.b.c").is_some());
+/// assert!(routes.match_route("/a/b/c").is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SeparatorConfig {
+    separator: char,
+}
+
+impl Default for SeparatorConfig {
+    fn default() -> Self {
+        Self { separator: '/' }
+    }
+}
+
+impl SeparatorConfig {
+    /// Splits paths on `separator`.
+    pub fn new(separator: char) -> Self {
+        Self { separator }
+    }
+
+    /// The character that paths are split on.
+    pub fn separator(&self) -> char {
+        self.separator
+    }
+
+    /// Whether nothing but an optional separator is left of `path`, so that it
+    /// is fully matched.
+    pub(crate) fn is_empty_path(&self, path: &str) -> bool {
+        path.is_empty() || path.strip_prefix(self.separator) == Some("")
+    }
+}
@@ -0,0 +1,198 @@
+use super::{
+    MatchInterface, MatchNestedRoutes, MatchRequest, PathSegment,
+    RouteDefaults, RouteMatchId,
+};
+use std::collections::{HashMap, HashSet};
+
+/// The fully-static paths in a set of route definitions, with the
+/// [`branch`](super::GeneratedRouteData::branch) of the route that matches each
+/// of them.
+#[derive(Debug, Default)]
+pub(crate) struct StaticPaths(HashMap<String, Vec<usize>>);
+
+impl StaticPaths {
+    /// Collects the paths of the routes whose segments are all static.
+    ///
+    /// A path is only included if it matches the same route as it would when
+    /// walking the whole tree, so that looking it up can never return a
+    /// different match (for example, when an earlier route with a param would
+    /// match the same path).
+    pub(crate) fn compile<Children>(
+        children: &Children,
+        defaults: &RouteDefaults,
+    ) -> Self
+    where
+        Children: MatchNestedRoutes,
+    {
+        let mut paths = HashMap::new();
+        let mut seen = HashSet::new();
+        // as long as every earlier route is fully static, a path can only be
+        // matched by the first route with exactly that path, so the tree walk
+        // only needs to be checked once a route with params has been seen
+        let mut after_dynamic = false;
+        for route in children.generate_routes(defaults) {
+            let Some(path) = static_path(&route.segments) else {
+                after_dynamic = true;
+                continue;
+            };
+            for candidate in [format!("{path}/"), path] {
+                if !seen.insert(candidate.clone()) {
+                    continue;
+                }
+                let branch = complete(children.match_branch(
+                    &route.branch,
+                    MatchRequest::new(&candidate),
+                ));
+                let same_as_walk = !after_dynamic
+                    || complete(
+                        children.match_request(MatchRequest::new(&candidate)),
+                    ) == branch;
+                if branch.is_some() && same_as_walk {
+                    paths.insert(candidate, route.branch.clone());
+                }
+            }
+        }
+        Self(paths)
+    }
+
+    /// The branch of the route that matches `path`, if it is one of the static
+    /// paths.
+    pub(crate) fn get(&self, path: &str) -> Option<&[usize]> {
+        self.0.get(path).map(Vec::as_slice)
+    }
+}
+
+/// The path of a route, if every one of its segments is static.
+fn static_path(segments: &[PathSegment]) -> Option<String> {
+    let mut path = String::new();
+    for segment in segments {
+        match segment {
+            PathSegment::Unit => {}
+            PathSegment::Static(segment) => {
+                let segment = segment.trim_matches('/');
+                if !segment.is_empty() {
+                    path.push('/');
+                    path.push_str(segment);
+                }
+            }
+            _ => return None,
+        }
+    }
+    Some(path)
+}
+
+/// The IDs of every route in a complete match, from the outermost to the
+/// innermost.
+fn complete<M>(
+    (matched, remaining): (Option<(RouteMatchId, M)>, &str),
+) -> Option<Vec<RouteMatchId>>
+where
+    M: MatchInterface,
+{
+    let (_, matched) = matched?;
+    (remaining.is_empty() || remaining == "/").then(|| match_chain(matched))
+}
+
+fn match_chain<M>(matched: M) -> Vec<RouteMatchId>
+where
+    M: MatchInterface,
+{
+    let id = matched.as_id();
+    let mut chain = match matched.into_view_and_child().1 {
+        Some(child) => match_chain(child),
+        None => Vec::new(),
+    };
+    chain.insert(0, id);
+    chain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::match_chain;
+    use crate::{
+        MatchNestedRoutes, MatchParams, NestedRoute, ParamSegment, RouteDefs,
+        RouteMatchId, StaticSegment,
+    };
+    use std::borrow::Cow;
+    use tachys::view::iterators::StaticVec;
+
+    type Resolved = (Vec<RouteMatchId>, Vec<(Cow<'static, str>, String)>);
+
+    fn resolve<Children: MatchNestedRoutes>(
+        routes: &RouteDefs<Children>,
+        path: &str,
+    ) -> Option<Resolved> {
+        routes.match_route(path).map(|matched| {
+            let params = matched.to_params();
+            (match_chain(matched), params)
+        })
+    }
+
+    #[test]
+    fn compiled_routes_match_like_tree_walk() {
+        let routes = RouteDefs::<_>::new_with_base(
+            (
+                NestedRoute::new(StaticSegment(""), || ()),
+                NestedRoute::new(StaticSegment("about"), || ()),
+                NestedRoute::new(ParamSegment("page"), || ()),
+                // shadowed by the param route above
+                NestedRoute::new(StaticSegment("contact"), || ()),
+                NestedRoute::new(StaticSegment("users"), || ()).child((
+                    NestedRoute::new(StaticSegment("settings"), || ()),
+                    NestedRoute::new(ParamSegment("id"), || ()),
+                )),
+            ),
+            "/app",
+        );
+        let compiled = routes.clone().compile();
+        let static_paths = compiled.static_paths.as_ref().unwrap();
+        assert!(static_paths.get("/about").is_some());
+        assert!(static_paths.get("/users/settings/").is_some());
+        assert!(static_paths.get("/contact").is_none());
+
+        for path in [
+            "/app",
+            "/app/",
+            "/app/about",
+            "/app/about/",
+            "/app/contact",
+            "/app/users/settings",
+            "/app/users/settings/",
+            "/app/users/42",
+            "/app/missing/page",
+            "/other/about",
+        ] {
+            assert_eq!(
+                resolve(&compiled, path),
+                resolve(&routes, path),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn compiled_routes_match_among_many_static_routes() {
+        let routes = RouteDefs::<_>::new(StaticVec::from(
+            (0..2000)
+                .map(|i| {
+                    let path: &'static str = format!("r{i}").leak();
+                    NestedRoute::new(StaticSegment(path), || ())
+                })
+                .collect::<Vec<_>>(),
+        ));
+        let compiled = routes.clone().compile();
+        assert!(compiled
+            .static_paths
+            .as_ref()
+            .unwrap()
+            .get("/r1999")
+            .is_some());
+        for path in ["/r0", "/r1999", "/r1999/", "/r2000"] {
+            assert_eq!(
+                resolve(&compiled, path),
+                resolve(&routes, path),
+                "{path}"
+            );
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use super::{GeneratedRouteData, PathSegment};
+use crate::SsrMode;
+use std::hash::{Hash, Hasher};
+
+/// Hashes the base and generated routes of a set of route definitions,
+/// independent of the order in which the routes were generated.
+pub(crate) fn structure_hash(
+    base: Option<&str>,
+    routes: impl IntoIterator<Item = GeneratedRouteData>,
+) -> u64 {
+    let mut table = routes
+        .into_iter()
+        .map(|route| {
+            let segments = route
+                .segments
+                .iter()
+                .map(|segment| {
+                    (segment_kind(segment), segment.as_raw_str().to_string())
+                })
+                .collect::<Vec<_>>();
+            let mut methods = route
+                .methods
+                .iter()
+                .map(|method| method.as_str().to_string())
+                .collect::<Vec<_>>();
+            methods.sort();
+            (segments, methods, ssr_mode_name(&route.ssr_mode))
+        })
+        .collect::<Vec<_>>();
+    table.sort();
+
+    let mut hasher = Fnv1a::default();
+    base.hash(&mut hasher);
+    table.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn segment_kind(segment: &PathSegment) -> u8 {
+    match segment {
+        PathSegment::Unit => 0,
+        PathSegment::Static(_) => 1,
+        PathSegment::Param(_) => 2,
+        PathSegment::OptionalParam(_) => 3,
+        PathSegment::Splat(_) => 4,
+    }
+}
+
+pub(crate) fn ssr_mode_name(mode: &SsrMode) -> &'static str {
+    match mode {
+        SsrMode::OutOfOrder => "OutOfOrder",
+        SsrMode::PartiallyBlocked => "PartiallyBlocked",
+        SsrMode::InOrder => "InOrder",
+        SsrMode::Async => "Async",
+        SsrMode::Static(_) => "Static",
+    }
+}
+
+/// 64-bit FNV-1a, which (unlike the standard library's default hasher) is
+/// specified, so the hash is the same across runs, builds and compiler
+/// versions.
+pub(crate) struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+        }
+    }
+
+    // lengths are hashed as `usize`, so use the same width on every platform
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Method, NestedRoute, ParamSegment, RouteDefs, StaticSegment};
+
+    #[test]
+    fn structure_hash_ignores_route_order() {
+        let a = RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment("about"), || ()),
+            NestedRoute::new(
+                (StaticSegment("users"), ParamSegment("id")),
+                || (),
+            ),
+        ));
+        let b = RouteDefs::<_>::new((
+            NestedRoute::new(
+                (StaticSegment("users"), ParamSegment("id")),
+                || (),
+            ),
+            NestedRoute::new(StaticSegment("about"), || ()),
+        ));
+        assert_eq!(a.structure_hash(), b.structure_hash());
+    }
+
+    #[test]
+    fn structure_hash_changes_with_routes() {
+        let base = RouteDefs::<_>::new(NestedRoute::new(
+            StaticSegment("users"),
+            || (),
+        ))
+        .structure_hash();
+        let param =
+            RouteDefs::<_>::new(NestedRoute::new(ParamSegment("users"), || ()))
+                .structure_hash();
+        let method = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("users"), || ())
+                .custom_method(Method::Post.as_str().to_string()),
+        )
+        .structure_hash();
+        assert_ne!(base, param);
+        assert_ne!(base, method);
+        assert_eq!(
+            base,
+            RouteDefs::<_>::new(NestedRoute::new(
+                StaticSegment("users"),
+                || ()
+            ))
+            .structure_hash()
+        );
+    }
+}
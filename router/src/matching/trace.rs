@@ -0,0 +1,214 @@
+//! Spans and events for matching requests, with the `tracing` feature.
+//!
+//! Without the feature, these do nothing and compile away.
+
+use super::{MatchError, MatchRequest, MatchSuccess};
+
+#[cfg(feature = "tracing")]
+thread_local! {
+    /// How many routes have been tried by the request that is being resolved on
+    /// this thread.
+    static ATTEMPTS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Runs `resolve` in a span for resolving `req`, which records the ID of the
+/// route that matched and how many routes were tried to find it.
+///
+/// The span records the path without its query, because the query often carries
+/// data like tokens or email addresses. The query is only recorded if
+/// `with_query` is `true`.
+#[cfg(feature = "tracing")]
+pub(crate) fn resolve_span<M>(
+    req: &MatchRequest<'_>,
+    with_query: bool,
+    resolve: impl FnOnce() -> Result<MatchSuccess<M>, MatchError>,
+) -> Result<MatchSuccess<M>, MatchError> {
+    use tracing::field;
+
+    let path = req.as_path();
+    let path = path.split_once('?').map_or(path, |(path, _)| path);
+    let span = tracing::debug_span!(
+        "resolve_route",
+        path,
+        query = field::Empty,
+        matched = field::Empty,
+        attempts = field::Empty,
+    );
+    if with_query {
+        if let Some(query) = req.as_query() {
+            span.record("query", query);
+        }
+    }
+    let _entered = span.enter();
+
+    let outer = ATTEMPTS.with(|attempts| attempts.replace(0));
+    let result = resolve();
+    let attempts = ATTEMPTS.with(|attempts| attempts.replace(outer));
+
+    span.record("attempts", attempts);
+    match &result {
+        Ok(success) => {
+            span.record("matched", success.id().0);
+            tracing::debug!(
+                matched = success.id().0,
+                attempts,
+                "matched route"
+            );
+        }
+        Err(error) => tracing::debug!(%error, attempts, "no route matched"),
+    }
+    result
+}
+
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub(crate) fn resolve_span<M>(
+    _req: &MatchRequest<'_>,
+    _with_query: bool,
+    resolve: impl FnOnce() -> Result<MatchSuccess<M>, MatchError>,
+) -> Result<MatchSuccess<M>, MatchError> {
+    resolve()
+}
+
+/// Records that the route with the given ID was tried, and whether its own
+/// segments matched.
+#[cfg(feature = "tracing")]
+pub(crate) fn route_tried(id: u16, matched: bool) {
+    ATTEMPTS.with(|attempts| attempts.set(attempts.get() + 1));
+    tracing::trace!(route = id, matched, "tried route");
+}
+
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub(crate) fn route_tried(_id: u16, _matched: bool) {}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use crate::{MatchRequest, NestedRoute, RouteDefs, StaticSegment};
+    use std::{
+        fmt,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    /// Records the fields of the `resolve_route` span, and how many routes were
+    /// tried.
+    #[derive(Default)]
+    struct Recorded {
+        span_fields: Vec<(String, String)>,
+        tried: usize,
+    }
+
+    struct FieldsVisitor<'a>(&'a mut Vec<(String, String)>);
+
+    impl Visit for FieldsVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0
+                .push((field.name().to_string(), format!("{value:?}")));
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Recorder {
+        recorded: Arc<Mutex<Recorded>>,
+        next_id: Arc<AtomicU64>,
+    }
+
+    impl Recorder {
+        fn field(&self, name: &str) -> Option<String> {
+            let recorded = self.recorded.lock().unwrap();
+            recorded
+                .span_fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value.clone())
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+            if span.metadata().name() == "resolve_route" {
+                let mut recorded = self.recorded.lock().unwrap();
+                span.record(&mut FieldsVisitor(&mut recorded.span_fields));
+            }
+            span::Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+        }
+
+        fn record(&self, _span: &span::Id, values: &span::Record<'_>) {
+            let mut recorded = self.recorded.lock().unwrap();
+            values.record(&mut FieldsVisitor(&mut recorded.span_fields));
+        }
+
+        fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Vec::new();
+            event.record(&mut FieldsVisitor(&mut fields));
+            if fields.contains(&("message".into(), "tried route".into())) {
+                self.recorded.lock().unwrap().tried += 1;
+            }
+        }
+
+        fn enter(&self, _span: &span::Id) {}
+
+        fn exit(&self, _span: &span::Id) {}
+    }
+
+    fn routes() -> RouteDefs<impl crate::MatchNestedRoutes + Clone> {
+        RouteDefs::new((
+            NestedRoute::new(StaticSegment("about"), || "About"),
+            NestedRoute::new(StaticSegment("users"), || "Users"),
+        ))
+    }
+
+    #[test]
+    fn resolving_a_request_records_the_matched_route_and_attempts() {
+        let recorder = Recorder::default();
+        let routes = routes();
+        let matched =
+            tracing::subscriber::with_default(recorder.clone(), || {
+                routes
+                    .resolve_request(
+                        MatchRequest::new("/users").query("token=secret"),
+                    )
+                    .unwrap()
+            });
+
+        assert_eq!(recorder.field("path").as_deref(), Some("\"/users\""));
+        assert_eq!(recorder.field("matched"), Some(matched.id().0.to_string()));
+        assert_eq!(recorder.field("attempts").as_deref(), Some("2"));
+        assert_eq!(recorder.recorded.lock().unwrap().tried, 2);
+        // the query is left out unless it is opted into
+        assert_eq!(recorder.field("query"), None);
+    }
+
+    #[test]
+    fn the_query_is_only_recorded_once_opted_into() {
+        let recorder = Recorder::default();
+        let routes = routes().trace_query(true);
+        tracing::subscriber::with_default(recorder.clone(), || {
+            routes
+                .resolve_request(
+                    MatchRequest::new("/about").query("token=secret"),
+                )
+                .unwrap();
+        });
+
+        assert_eq!(recorder.field("path").as_deref(), Some("\"/about\""));
+        assert_eq!(
+            recorder.field("query").as_deref(),
+            Some("\"token=secret\"")
+        );
+        assert_eq!(recorder.field("attempts").as_deref(), Some("1"));
+    }
+}
@@ -0,0 +1,385 @@
+use super::{GeneratedRouteData, PathSegment, RouteMatchId};
+use std::{collections::HashSet, fmt};
+
+/// A problem detected in a set of route definitions by
+/// [`RouteDefs::validate`](super::RouteDefs::validate).
+///
+/// Route chains are formatted as paths, with `:name` for params, `:name?` for
+/// optional params, and `*name` for wildcards. The [`Display`](fmt::Display)
+/// output is stable, so it can be matched on in tests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteWarning {
+    /// A route can never be matched, because every path it matches is matched
+    /// first by an earlier route.
+    Shadowed {
+        /// The route that can never be matched.
+        route: String,
+        /// The earlier route that matches first.
+        shadowed_by: String,
+    },
+    /// A param name appears more than once in a single chain of nested routes.
+    DuplicateParam {
+        /// The route chain.
+        route: String,
+        /// The repeated param name.
+        param: String,
+    },
+    /// A route has segments after a wildcard segment, which consumes the whole
+    /// remaining path.
+    AfterWildcard {
+        /// The route chain.
+        route: String,
+        /// The name of the wildcard segment.
+        wildcard: String,
+    },
+}
+
+impl fmt::Display for RouteWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteWarning::Shadowed { route, shadowed_by } => write!(
+                f,
+                "route `{route}` is unreachable: shadowed by `{shadowed_by}`"
+            ),
+            RouteWarning::DuplicateParam { route, param } => write!(
+                f,
+                "route `{route}` declares param `{param}` more than once"
+            ),
+            RouteWarning::AfterWildcard { route, wildcard } => write!(
+                f,
+                "route `{route}` has segments after wildcard `*{wildcard}`"
+            ),
+        }
+    }
+}
+
+/// A route that can never be matched, because an earlier route with a wildcard
+/// segment matches every one of its paths first, found by
+/// [`RouteDefs::check_conflicts`](super::RouteDefs::check_conflicts).
+///
+/// This usually means that the routes are declared in the wrong order: since
+/// routes are tried in the order they are declared, a wildcard route should
+/// come after the more specific routes it overlaps with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteConflict {
+    /// The ID of the route that can never be matched.
+    pub route: Option<RouteMatchId>,
+    /// The path pattern of the route that can never be matched.
+    pub route_path: String,
+    /// The ID of the earlier route with the wildcard.
+    pub wildcard: Option<RouteMatchId>,
+    /// The path pattern of the earlier route with the wildcard.
+    pub wildcard_path: String,
+}
+
+impl fmt::Display for RouteConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "route `{}` is unreachable: wildcard route `{}` matches first",
+            self.route_path, self.wildcard_path
+        )
+    }
+}
+
+/// Formats a chain of segments as a path pattern.
+pub fn segments_to_pattern(segments: &[PathSegment]) -> String {
+    let mut pattern = String::new();
+    for segment in normalize_segments(segments) {
+        pattern.push('/');
+        match segment {
+            PathSegment::Unit => {}
+            PathSegment::Static(s) => pattern.push_str(&s),
+            PathSegment::Param(s) => {
+                pattern.push(':');
+                pattern.push_str(&s);
+            }
+            PathSegment::OptionalParam(s) => {
+                pattern.push(':');
+                pattern.push_str(&s);
+                pattern.push('?');
+            }
+            PathSegment::Splat(s) => {
+                pattern.push('*');
+                pattern.push_str(&s);
+            }
+        }
+    }
+    if pattern.is_empty() {
+        pattern.push('/');
+    }
+    pattern
+}
+
+/// Splits static segments on `/` and removes empty and unit segments, so that
+/// chains built from differently-nested definitions can be compared segment by
+/// segment.
+pub(crate) fn normalize_segments(segments: &[PathSegment]) -> Vec<PathSegment> {
+    segments
+        .iter()
+        .flat_map(|segment| match segment {
+            PathSegment::Unit => Vec::new(),
+            PathSegment::Static(s) => s
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .map(|s| PathSegment::Static(s.to_string().into()))
+                .collect(),
+            other => vec![other.clone()],
+        })
+        .collect()
+}
+
+/// Whether every path matched by `earlier` is also matched by `later`'s
+/// pattern, so that `later` can never be reached.
+///
+/// This is deliberately conservative: optional params in the earlier route are
+/// never treated as shadowing, because whether they match depends on what
+/// follows them.
+fn shadows(earlier: &[PathSegment], later: &[PathSegment]) -> bool {
+    for (idx, segment) in earlier.iter().enumerate() {
+        match (segment, later.get(idx)) {
+            (PathSegment::Splat(_), _) => return true,
+            (_, None) => return false,
+            (PathSegment::Static(a), Some(PathSegment::Static(b))) => {
+                if a != b {
+                    return false;
+                }
+            }
+            (
+                PathSegment::Param(_),
+                Some(PathSegment::Static(_) | PathSegment::Param(_)),
+            ) => {}
+            _ => return false,
+        }
+    }
+    earlier.len() == later.len()
+}
+
+pub(crate) fn validate_routes(
+    routes: impl IntoIterator<Item = GeneratedRouteData>,
+) -> Vec<RouteWarning> {
+    let mut warnings = Vec::new();
+    let mut seen: Vec<Vec<PathSegment>> = Vec::new();
+
+    for route in routes {
+        let segments = normalize_segments(&route.segments);
+        let pattern = segments_to_pattern(&segments);
+
+        let mut names = HashSet::new();
+        for segment in &segments {
+            if let PathSegment::Param(name)
+            | PathSegment::OptionalParam(name)
+            | PathSegment::Splat(name) = segment
+            {
+                if !names.insert(name.clone()) {
+                    warnings.push(RouteWarning::DuplicateParam {
+                        route: pattern.clone(),
+                        param: name.to_string(),
+                    });
+                }
+            }
+        }
+
+        if let Some(idx) = segments
+            .iter()
+            .position(|segment| matches!(segment, PathSegment::Splat(_)))
+        {
+            if idx + 1 < segments.len() {
+                warnings.push(RouteWarning::AfterWildcard {
+                    route: pattern.clone(),
+                    wildcard: segments[idx].as_raw_str().to_string(),
+                });
+            }
+        }
+
+        if let Some(earlier) =
+            seen.iter().find(|earlier| shadows(earlier, &segments))
+        {
+            warnings.push(RouteWarning::Shadowed {
+                route: pattern,
+                shadowed_by: segments_to_pattern(earlier),
+            });
+        }
+
+        seen.push(segments);
+    }
+
+    warnings
+}
+
+pub(crate) fn check_conflicts(
+    routes: impl IntoIterator<Item = GeneratedRouteData>,
+) -> Vec<RouteConflict> {
+    let mut conflicts = Vec::new();
+    let mut wildcards: Vec<(Vec<PathSegment>, Option<RouteMatchId>)> =
+        Vec::new();
+
+    for route in routes {
+        let segments = normalize_segments(&route.segments);
+        if let Some((wildcard, wildcard_id)) = wildcards
+            .iter()
+            .find(|(wildcard, _)| shadows(wildcard, &segments))
+        {
+            conflicts.push(RouteConflict {
+                route: route.id,
+                route_path: segments_to_pattern(&segments),
+                wildcard: *wildcard_id,
+                wildcard_path: segments_to_pattern(wildcard),
+            });
+        }
+
+        if segments
+            .iter()
+            .any(|segment| matches!(segment, PathSegment::Splat(_)))
+        {
+            wildcards.push((segments, route.id));
+        }
+    }
+
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        NestedRoute, OptionalParamSegment, ParamSegment, RouteDefs,
+        StaticSegment, WildcardSegment,
+    };
+
+    #[test]
+    fn valid_routes_have_no_warnings() {
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment(""), || ()),
+            NestedRoute::new(StaticSegment("users"), || ()).child((
+                NestedRoute::new(StaticSegment("new"), || ()),
+                NestedRoute::new(ParamSegment("id"), || ()),
+            )),
+            NestedRoute::new(
+                (StaticSegment("posts"), OptionalParamSegment("id")),
+                || (),
+            ),
+            NestedRoute::new(WildcardSegment("any"), || ()),
+        ));
+        assert!(routes.validate().is_empty());
+    }
+
+    #[test]
+    fn detects_route_shadowed_by_wildcard() {
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(
+                (StaticSegment("users"), WildcardSegment("rest")),
+                || (),
+            ),
+            NestedRoute::new(
+                (StaticSegment("users"), StaticSegment("settings")),
+                || (),
+            ),
+        ));
+        let warnings = routes
+            .validate()
+            .into_iter()
+            .map(|w| w.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            vec![
+                "route `/users/settings` is unreachable: shadowed by \
+                 `/users/*rest`"
+            ]
+        );
+    }
+
+    #[test]
+    fn conflicts_identify_routes_shadowed_by_wildcard() {
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment("docs"), || ()).child((
+                NestedRoute::new(WildcardSegment("page"), || ()),
+                NestedRoute::new(StaticSegment("search"), || ()),
+            )),
+            // only shadowed by a param route, which is not a conflict
+            NestedRoute::new(ParamSegment("page"), || ()),
+            NestedRoute::new(StaticSegment("about"), || ()),
+            NestedRoute::new(WildcardSegment("any"), || ()),
+            NestedRoute::new((StaticSegment("a"), StaticSegment("b")), || ()),
+        ));
+        let generated =
+            routes.generate_routes().1.into_iter().collect::<Vec<_>>();
+        let conflicts = routes.check_conflicts();
+
+        assert_eq!(
+            conflicts
+                .iter()
+                .map(|conflict| conflict.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "route `/docs/search` is unreachable: wildcard route \
+                 `/docs/*page` matches first",
+                "route `/a/b` is unreachable: wildcard route `/*any` matches \
+                 first",
+            ]
+        );
+        assert_eq!(conflicts[0].route, generated[1].id);
+        assert_eq!(conflicts[0].wildcard, generated[0].id);
+        assert_eq!(conflicts[1].route, generated[5].id);
+        assert_eq!(conflicts[1].wildcard, generated[4].id);
+        assert_ne!(conflicts[0].route, conflicts[0].wildcard);
+    }
+
+    #[test]
+    fn detects_static_shadowed_by_param() {
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("/users"), || ()).child((
+                NestedRoute::new(ParamSegment("id"), || ()),
+                NestedRoute::new(StaticSegment("new"), || ()),
+            )),
+        );
+        let warnings = routes
+            .validate()
+            .into_iter()
+            .map(|w| w.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            vec!["route `/users/new` is unreachable: shadowed by `/users/:id`"]
+        );
+    }
+
+    #[test]
+    fn detects_duplicate_params_in_chain() {
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(ParamSegment("id"), || ())
+                .child(NestedRoute::new(ParamSegment("id"), || ())),
+        );
+        let warnings = routes
+            .validate()
+            .into_iter()
+            .map(|w| w.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            vec!["route `/:id/:id` declares param `id` more than once"]
+        );
+    }
+
+    #[test]
+    fn detects_children_under_wildcard() {
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(
+                (StaticSegment("files"), WildcardSegment("path")),
+                || (),
+            )
+            .child(NestedRoute::new(StaticSegment("edit"), || ())),
+        );
+        let warnings = routes
+            .validate()
+            .into_iter()
+            .map(|w| w.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            vec![
+                "route `/files/*path/edit` has segments after wildcard `*path`"
+            ]
+        );
+    }
+}
@@ -1,5 +1,14 @@
+use std::{
+    borrow::Cow,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
 /// Represents an HTTP method that can be handled by this route.
-#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+///
+/// Methods are compared by name, so a [`Method::Custom`] with a standard name
+/// (like `Custom("GET")`) is equal to the corresponding standard variant.
+#[derive(Clone, Debug, Default)]
 pub enum Method {
     /// The [`GET`](https://developer.mozilla.org/en-US/docs/Web/HTTP/Methods/GET) method
     /// requests a representation of the specified resource.
@@ -18,4 +27,156 @@ pub enum Method {
     /// The [`PATCH`](https://developer.mozilla.org/en-US/docs/Web/HTTP/Methods/PATCH) method
     /// applies partial modifications to a resource.
     Patch,
+    /// A non-standard method, like `PURGE` or `SEARCH`. HTTP method names are
+    /// case-sensitive.
+    Custom(Cow<'static, str>),
+}
+
+impl Method {
+    /// Creates a method from its name, using a standard variant if there is
+    /// one.
+    pub fn from_name(name: impl Into<Cow<'static, str>>) -> Self {
+        let name = name.into();
+        match name.as_ref() {
+            "GET" => Method::Get,
+            "POST" => Method::Post,
+            "PUT" => Method::Put,
+            "DELETE" => Method::Delete,
+            "PATCH" => Method::Patch,
+            _ => Method::Custom(name),
+        }
+    }
+
+    /// The name of the method, as it appears in an HTTP request.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+            Method::Patch => "PATCH",
+            Method::Custom(name) => name,
+        }
+    }
+
+    /// Whether the name of the method is a valid HTTP token, which every
+    /// method sent in a request is, so that a route can handle it.
+    ///
+    /// The standard methods are always valid. The name of a custom method
+    /// must not be empty, and may only contain letters, digits and
+    /// ``!#$%&'*+-.^_`|~``.
+    pub fn is_valid(&self) -> bool {
+        let name = self.as_str();
+        !name.is_empty()
+            && name.bytes().all(|byte| {
+                byte.is_ascii_alphanumeric()
+                    || b"!#$%&'*+-.^_`|~".contains(&byte)
+            })
+    }
+}
+
+/// The value of the
+/// [`Allow`](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Allow)
+/// header for a route that handles the given methods.
+///
+/// The server integrations answer `HEAD` requests to any route that handles
+/// `GET`, and `OPTIONS` requests to any route that does not handle `OPTIONS`
+/// itself, so both are included.
+pub fn allow_header<'a>(
+    methods: impl IntoIterator<Item = &'a Method>,
+) -> String {
+    const ORDER: [&str; 7] =
+        ["GET", "HEAD", "POST", "PUT", "DELETE", "PATCH", "OPTIONS"];
+
+    let mut names = methods.into_iter().map(Method::as_str).collect::<Vec<_>>();
+    if names.contains(&"GET") {
+        names.push("HEAD");
+    }
+    names.push("OPTIONS");
+    names.sort_by_key(|name| {
+        (
+            ORDER
+                .iter()
+                .position(|standard| standard == name)
+                .unwrap_or(usize::MAX),
+            *name,
+        )
+    });
+    names.dedup();
+    names.join(", ")
+}
+
+impl PartialEq for Method {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Method {}
+
+impl Hash for Method {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&'static str> for Method {
+    fn from(value: &'static str) -> Self {
+        Method::from_name(value)
+    }
+}
+
+impl From<String> for Method {
+    fn from(value: String) -> Self {
+        Method::from_name(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{allow_header, Method};
+    use std::collections::HashSet;
+
+    #[test]
+    fn custom_methods_compare_by_name() {
+        assert_eq!(Method::from_name("GET"), Method::Get);
+        assert_eq!(Method::Custom("GET".into()), Method::Get);
+        assert_eq!(Method::from_name("PURGE"), Method::Custom("PURGE".into()));
+        assert_ne!(Method::from_name("purge"), Method::from_name("PURGE"));
+
+        let methods: HashSet<Method> =
+            [Method::Get, Method::Custom("GET".into()), "SEARCH".into()].into();
+        assert_eq!(methods.len(), 2);
+        assert!(methods.contains(&Method::Custom("SEARCH".into())));
+    }
+
+    #[test]
+    fn custom_method_names_must_be_tokens() {
+        assert!(Method::Get.is_valid());
+        assert!(Method::from_name("PURGE").is_valid());
+        assert!(Method::from_name("M-SEARCH").is_valid());
+        assert!(!Method::from_name("").is_valid());
+        assert!(!Method::from_name("GET STUFF").is_valid());
+        assert!(!Method::from_name("PURGE\r\n").is_valid());
+        assert!(!Method::from_name("(PURGE)").is_valid());
+    }
+
+    #[test]
+    fn allow_header_includes_implied_methods() {
+        assert_eq!(
+            allow_header(&[Method::Post, "SEARCH".into(), Method::Get]),
+            "GET, HEAD, POST, OPTIONS, SEARCH"
+        );
+        assert_eq!(allow_header(&[Method::Delete]), "DELETE, OPTIONS");
+        assert_eq!(
+            allow_header(&[Method::from_name("OPTIONS"), Method::Put]),
+            "PUT, OPTIONS"
+        );
+    }
 }
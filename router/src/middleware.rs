@@ -0,0 +1,183 @@
+use leptos::server_fn::middleware::{BoxedService, Layer};
+use std::{any::Any, fmt, sync::Arc};
+
+/// The middleware of a route, set with
+/// [`NestedRoute::middleware`](crate::NestedRoute::middleware), which the
+/// server integrations apply to the requests to its path.
+///
+/// Like the middleware of a server function, each layer is specific to the
+/// request and response types of a server integration, like
+/// `http::Request<axum::body::Body>` and `http::Response<axum::body::Body>` for
+/// `leptos_axum`, or `ActixRequest` and `ActixResponse` for `leptos_actix`, and
+/// an integration only applies the layers for its own types.
+#[derive(Clone, Default)]
+pub struct RouteMiddleware(Vec<Arc<dyn Any + Send + Sync>>);
+
+impl RouteMiddleware {
+    /// Adds `layer`, inside the layers that were added before it.
+    pub fn push<Req: 'static, Res: 'static>(
+        &mut self,
+        layer: Arc<dyn Layer<Req, Res>>,
+    ) {
+        self.0.push(Arc::new(layer));
+    }
+
+    /// The layers for the request type `Req` and the response type `Res`, from
+    /// the outermost, which runs first, to the innermost.
+    pub fn layers<Req: 'static, Res: 'static>(
+        &self,
+    ) -> impl Iterator<Item = &Arc<dyn Layer<Req, Res>>> {
+        self.0.iter().filter_map(|layer| layer.downcast_ref())
+    }
+
+    /// Wraps `service` in the layers for its request and response types, so
+    /// that the first layer that was added sees the request first.
+    pub fn apply<Req: 'static, Res: 'static>(
+        &self,
+        service: BoxedService<Req, Res>,
+    ) -> BoxedService<Req, Res> {
+        let layers = self.layers::<Req, Res>().collect::<Vec<_>>();
+        layers
+            .into_iter()
+            .rev()
+            .fold(service, |service, layer| layer.layer(service))
+    }
+
+    /// Whether there are no layers, for any integration.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The middleware of a child route, inside the middleware of its parent.
+    pub(crate) fn inherit(
+        parent: Option<&RouteMiddleware>,
+        child: Option<RouteMiddleware>,
+    ) -> Option<RouteMiddleware> {
+        match (parent, child) {
+            (Some(parent), Some(child)) => {
+                let mut middleware = parent.clone();
+                middleware.0.extend(child.0);
+                Some(middleware)
+            }
+            (parent, child) => child.or_else(|| parent.cloned()),
+        }
+    }
+}
+
+impl fmt::Debug for RouteMiddleware {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RouteMiddleware")
+            .field(&self.0.len())
+            .finish()
+    }
+}
+
+impl PartialEq for RouteMiddleware {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self.0.iter().zip(&other.0).all(|(a, b)| Arc::ptr_eq(a, b))
+    }
+}
+
+impl Eq for RouteMiddleware {}
+
+#[cfg(test)]
+mod tests {
+    use super::RouteMiddleware;
+    use leptos::server_fn::{
+        error::ServerFnErrorErr,
+        middleware::{BoxedService, Layer, Service},
+        Bytes,
+    };
+    use std::{future::Future, pin::Pin, sync::Arc};
+
+    type Trace = Vec<&'static str>;
+
+    /// Records its name in the request on the way in, and in the response on
+    /// the way out.
+    struct Named(&'static str);
+
+    struct NamedService(&'static str, BoxedService<Trace, Trace>);
+
+    impl Layer<Trace, Trace> for Named {
+        fn layer(
+            &self,
+            inner: BoxedService<Trace, Trace>,
+        ) -> BoxedService<Trace, Trace> {
+            BoxedService::new(inner.ser, NamedService(self.0, inner))
+        }
+    }
+
+    impl Service<Trace, Trace> for NamedService {
+        fn run(
+            &mut self,
+            mut req: Trace,
+            _ser: fn(ServerFnErrorErr) -> Bytes,
+        ) -> Pin<Box<dyn Future<Output = Trace> + Send>> {
+            let name = self.0;
+            req.push(name);
+            let res = self.1.run(req);
+            Box::pin(async move {
+                let mut res = res.await;
+                res.push(name);
+                res
+            })
+        }
+    }
+
+    struct Handler;
+
+    impl Service<Trace, Trace> for Handler {
+        fn run(
+            &mut self,
+            mut req: Trace,
+            _ser: fn(ServerFnErrorErr) -> Bytes,
+        ) -> Pin<Box<dyn Future<Output = Trace> + Send>> {
+            req.push("handler");
+            Box::pin(async move { req })
+        }
+    }
+
+    fn middleware(names: &[&'static str]) -> RouteMiddleware {
+        let mut middleware = RouteMiddleware::default();
+        for name in names {
+            middleware.push::<Trace, Trace>(Arc::new(Named(name)));
+        }
+        middleware
+    }
+
+    #[test]
+    fn the_parent_middleware_runs_around_the_child_middleware() {
+        let middleware = RouteMiddleware::inherit(
+            Some(&middleware(&["auth", "audit"])),
+            Some(middleware(&["admin"])),
+        )
+        .unwrap();
+        let mut service = middleware.apply(BoxedService::new(
+            |err| Bytes::from(err.to_string()),
+            Handler,
+        ));
+        assert_eq!(
+            futures::executor::block_on(service.run(vec![])),
+            ["auth", "audit", "admin", "handler", "admin", "audit", "auth"]
+        );
+
+        // the layers for another integration are left out
+        let mut other = RouteMiddleware::default();
+        other.push::<String, String>(Arc::new(OtherLayer));
+        assert!(!other.is_empty());
+        assert_eq!(other.layers::<Trace, Trace>().count(), 0);
+        assert_eq!(RouteMiddleware::inherit(None, None), None);
+    }
+
+    struct OtherLayer;
+
+    impl Layer<String, String> for OtherLayer {
+        fn layer(
+            &self,
+            inner: BoxedService<String, String>,
+        ) -> BoxedService<String, String> {
+            inner
+        }
+    }
+}
@@ -0,0 +1,401 @@
+/// Which optimizations are applied to the HTML of a route that
+/// [minifies](crate::NestedRoute::minify_html) its responses.
+///
+/// The minifier itself is only included with the `minify_html` feature; without
+/// it, the server integrations send the HTML as it was rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HtmlMinifyConfig {
+    /// Removes HTML comments, except for the markers that hydration relies on.
+    pub remove_comments: bool,
+    /// Collapses each run of whitespace in text to a single space, except in
+    /// `<pre>` and `<textarea>`.
+    pub collapse_whitespace: bool,
+    /// Removes closing tags that the HTML parser implies, like `</li>` and
+    /// `</td>`.
+    pub remove_optional_tags: bool,
+    /// Removes comments and unneeded whitespace from `<style>` elements.
+    pub minify_css: bool,
+    /// Removes indentation and blank lines from `<script>` elements.
+    pub minify_js: bool,
+}
+
+impl HtmlMinifyConfig {
+    /// Applies every optimization.
+    pub fn all() -> Self {
+        Self {
+            remove_comments: true,
+            collapse_whitespace: true,
+            remove_optional_tags: true,
+            minify_css: true,
+            minify_js: true,
+        }
+    }
+}
+
+/// Minifies a whole HTML document, or fragment, with `config`.
+#[cfg(feature = "minify_html")]
+pub fn minify_html(config: HtmlMinifyConfig, html: &str) -> String {
+    let mut minifier = HtmlMinifier::new(config);
+    let mut minified = minifier.push(html.as_bytes());
+    minified.extend(minifier.finish());
+    String::from_utf8_lossy(&minified).into_owned()
+}
+
+/// Closing tags that can be left out, because the parser closes the element
+/// when it meets the next sibling or the end of the parent anyway.
+#[cfg(feature = "minify_html")]
+const OPTIONAL_END_TAGS: [&[u8]; 12] = [
+    b"li",
+    b"dt",
+    b"dd",
+    b"option",
+    b"optgroup",
+    b"thead",
+    b"tbody",
+    b"tfoot",
+    b"tr",
+    b"td",
+    b"th",
+    b"colgroup",
+];
+
+/// The prefixes of the comments that are hydration markers, rather than
+/// authored comments.
+#[cfg(feature = "minify_html")]
+const MARKER_COMMENTS: [&[u8]; 4] = [b"bo-", b"bc-", b"s-", b"hot-reload|"];
+
+/// Minifies HTML as it is streamed, a chunk at a time.
+///
+/// A chunk can end anywhere, even in the middle of a tag: what cannot be
+/// minified yet is kept until the next chunk, or until
+/// [`finish`](HtmlMinifier::finish).
+#[cfg(feature = "minify_html")]
+#[derive(Debug)]
+pub struct HtmlMinifier {
+    config: HtmlMinifyConfig,
+    pending: Vec<u8>,
+    raw_text: Option<RawText>,
+    in_whitespace: bool,
+}
+
+/// An element whose content is not parsed as HTML, or that keeps its
+/// whitespace.
+#[cfg(feature = "minify_html")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RawText {
+    Pre,
+    Script,
+    Style,
+    Textarea,
+}
+
+#[cfg(feature = "minify_html")]
+impl RawText {
+    fn from_name(name: &[u8]) -> Option<Self> {
+        match name {
+            b"pre" => Some(Self::Pre),
+            b"script" => Some(Self::Script),
+            b"style" => Some(Self::Style),
+            b"textarea" => Some(Self::Textarea),
+            _ => None,
+        }
+    }
+
+    fn close(self) -> &'static [u8] {
+        match self {
+            Self::Pre => b"</pre",
+            Self::Script => b"</script",
+            Self::Style => b"</style",
+            Self::Textarea => b"</textarea",
+        }
+    }
+}
+
+#[cfg(feature = "minify_html")]
+impl HtmlMinifier {
+    /// Creates a minifier for a new response.
+    pub fn new(config: HtmlMinifyConfig) -> Self {
+        Self {
+            config,
+            pending: Vec::new(),
+            raw_text: None,
+            in_whitespace: false,
+        }
+    }
+
+    /// Minifies the next chunk of the response, returning as much of it as can
+    /// be minified.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<u8> {
+        self.pending.extend_from_slice(chunk);
+        self.minify(false)
+    }
+
+    /// Returns the rest of the response, once every chunk has been pushed.
+    pub fn finish(&mut self) -> Vec<u8> {
+        self.minify(true)
+    }
+
+    fn minify(&mut self, at_end: bool) -> Vec<u8> {
+        let html = std::mem::take(&mut self.pending);
+        let mut out = Vec::with_capacity(html.len());
+        let mut index = 0;
+        while index < html.len() {
+            let rest = &html[index..];
+            let len = if let Some(raw_text) = self.raw_text {
+                match find_ignore_case(rest, raw_text.close()) {
+                    Some(end) => {
+                        self.write_raw_text(raw_text, &rest[..end], &mut out);
+                        self.raw_text = None;
+                        end
+                    }
+                    None if at_end => {
+                        out.extend_from_slice(rest);
+                        rest.len()
+                    }
+                    // the whole content is needed to minify scripts and styles
+                    None => break,
+                }
+            } else if rest[0] == b'<' {
+                match tag_end(rest) {
+                    Some(end) => {
+                        self.write_tag(&rest[..end], &mut out);
+                        end
+                    }
+                    None if at_end => {
+                        out.extend_from_slice(rest);
+                        rest.len()
+                    }
+                    None => break,
+                }
+            } else {
+                let end = rest
+                    .iter()
+                    .position(|&byte| byte == b'<')
+                    .unwrap_or(rest.len());
+                self.write_text(&rest[..end], &mut out);
+                end
+            };
+            index += len;
+        }
+        self.pending = html[index..].to_vec();
+        out
+    }
+
+    fn write_text(&mut self, text: &[u8], out: &mut Vec<u8>) {
+        if !self.config.collapse_whitespace {
+            out.extend_from_slice(text);
+            return;
+        }
+        for &byte in text {
+            if !byte.is_ascii_whitespace() {
+                out.push(byte);
+                self.in_whitespace = false;
+            } else if !self.in_whitespace {
+                out.push(b' ');
+                self.in_whitespace = true;
+            }
+        }
+    }
+
+    fn write_tag(&mut self, tag: &[u8], out: &mut Vec<u8>) {
+        if let Some(comment) = tag.strip_prefix(b"<!--") {
+            let is_marker = comment == b"-->"
+                || MARKER_COMMENTS
+                    .iter()
+                    .any(|prefix| comment.starts_with(prefix));
+            if self.config.remove_comments && !is_marker {
+                return;
+            }
+        } else if let Some(closing) = tag.strip_prefix(b"</") {
+            let name = tag_name(closing);
+            if self.config.remove_optional_tags
+                && OPTIONAL_END_TAGS.contains(&name.as_slice())
+            {
+                return;
+            }
+        } else if !tag.ends_with(b"/>") {
+            self.raw_text = RawText::from_name(&tag_name(&tag[1..]));
+        }
+        out.extend_from_slice(tag);
+        self.in_whitespace = false;
+    }
+
+    fn write_raw_text(
+        &mut self,
+        raw_text: RawText,
+        content: &[u8],
+        out: &mut Vec<u8>,
+    ) {
+        match raw_text {
+            RawText::Script if self.config.minify_js => {
+                out.extend(minify_js(content))
+            }
+            RawText::Style if self.config.minify_css => {
+                out.extend(minify_css(content))
+            }
+            _ => out.extend_from_slice(content),
+        }
+    }
+}
+
+/// The length of the tag or comment at the start of `html`, skipping over any
+/// `>` in quoted attributes, or `None` if it has not ended yet.
+#[cfg(feature = "minify_html")]
+fn tag_end(html: &[u8]) -> Option<usize> {
+    if html.starts_with(b"<!--") {
+        return find(&html[4..], b"-->").map(|end| end + 7);
+    }
+    // it may still turn out to be a comment
+    if b"<!--".starts_with(html) {
+        return None;
+    }
+    let mut quote = None;
+    for (index, &byte) in html.iter().enumerate().skip(1) {
+        match (quote, byte) {
+            (None, b'"' | b'\'') => quote = Some(byte),
+            (Some(open), _) if open == byte => quote = None,
+            (None, b'>') => return Some(index + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(feature = "minify_html")]
+fn tag_name(tag: &[u8]) -> Vec<u8> {
+    tag.iter()
+        .take_while(|byte| byte.is_ascii_alphanumeric() || **byte == b'-')
+        .map(u8::to_ascii_lowercase)
+        .collect()
+}
+
+#[cfg(feature = "minify_html")]
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(feature = "minify_html")]
+fn find_ignore_case(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle))
+}
+
+/// Removes the indentation and the blank lines of a script.
+///
+/// The line breaks are kept, so that automatic semicolon insertion still
+/// applies, and scripts with template literals or line continuations, whose
+/// strings could span lines, are kept as they are.
+#[cfg(feature = "minify_html")]
+fn minify_js(script: &[u8]) -> Vec<u8> {
+    if script.contains(&b'`') || find(script, b"\\\n").is_some() {
+        return script.to_vec();
+    }
+    let lines = script
+        .split(|&byte| byte == b'\n')
+        .map(<[u8]>::trim_ascii)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    lines.join(&b'\n')
+}
+
+/// Removes the comments of a stylesheet, and the whitespace that does not
+/// separate tokens.
+#[cfg(feature = "minify_html")]
+fn minify_css(style: &[u8]) -> Vec<u8> {
+    // a space is not needed after these, nor before the ones that are not `:`,
+    // which would change the meaning of a selector like `a :hover`
+    const PUNCTUATION: &[u8] = b"{};,>:";
+
+    let mut out = Vec::with_capacity(style.len());
+    let mut in_whitespace = false;
+    let mut index = 0;
+    while index < style.len() {
+        let byte = style[index];
+        if style[index..].starts_with(b"/*") {
+            index = find(&style[index + 2..], b"*/")
+                .map_or(style.len(), |end| index + end + 4);
+            continue;
+        }
+        if byte.is_ascii_whitespace() {
+            in_whitespace = true;
+            index += 1;
+            continue;
+        }
+        if in_whitespace
+            && out.last().is_some_and(|last| !PUNCTUATION.contains(last))
+            && (byte == b':' || !PUNCTUATION.contains(&byte))
+        {
+            out.push(b' ');
+        }
+        in_whitespace = false;
+        if matches!(byte, b'"' | b'\'') {
+            let end = string_end(&style[index..]);
+            out.extend_from_slice(&style[index..index + end]);
+            index += end;
+            continue;
+        }
+        if byte == b'}' && out.last() == Some(&b';') {
+            out.pop();
+        }
+        out.push(byte);
+        index += 1;
+    }
+    out
+}
+
+/// The length of the quoted string at the start of `css`, skipping over escaped
+/// quotes.
+#[cfg(feature = "minify_html")]
+fn string_end(css: &[u8]) -> usize {
+    let quote = css[0];
+    let mut index = 1;
+    while index < css.len() {
+        match css[index] {
+            b'\\' => index += 2,
+            byte if byte == quote => return index + 1,
+            _ => index += 1,
+        }
+    }
+    css.len()
+}
+
+#[cfg(all(test, feature = "minify_html"))]
+mod tests {
+    use super::{minify_html, HtmlMinifier, HtmlMinifyConfig};
+
+    #[test]
+    fn minifies_html() {
+        let html = "<ul>\n  <!-- the items -->\n  <li>One</li>\n  \
+                    <li>Two<!--bo-1--><!--bc-1--></li>\n</ul>\n<pre>  keep\n  \
+                    this  </pre><style>\n  a :hover , p {\n    color: red ;\n  \
+                    }\n  /* done */\n</style><script>\n  let a = 1;\n\n  \
+                    let b = \"</p>\";\n</script>";
+        assert_eq!(
+            minify_html(HtmlMinifyConfig::all(), html),
+            "<ul> <li>One <li>Two<!--bo-1--><!--bc-1--> </ul> <pre>  keep\n  \
+             this  </pre><style>a :hover,p{color:red}</style><script>let a = \
+             1;\nlet b = \"</p>\";</script>"
+        );
+        assert_eq!(minify_html(HtmlMinifyConfig::default(), html), html);
+    }
+
+    #[test]
+    fn minifies_streamed_chunks_like_the_whole_response() {
+        let html =
+            "<div class=\"a > b\">\n  Hello,   world!  <!-- note -->\n  \
+                    <em>hi</em>\n</div><style>p { color: red; }</style>";
+        let expected = minify_html(HtmlMinifyConfig::all(), html);
+        for size in 1..html.len() {
+            let mut minifier = HtmlMinifier::new(HtmlMinifyConfig::all());
+            let mut minified = Vec::new();
+            for chunk in html.as_bytes().chunks(size) {
+                minified.extend(minifier.push(chunk));
+            }
+            minified.extend(minifier.finish());
+            assert_eq!(String::from_utf8(minified).unwrap(), expected);
+        }
+    }
+}
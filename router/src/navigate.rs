@@ -1,4 +1,9 @@
-use crate::location::State;
+use crate::location::{State, Url};
+use any_spawner::Executor;
+use futures::channel::oneshot;
+use leptos::callback::{Callable, Callback};
+use std::borrow::Cow;
+use tachys::dom::document;
 
 /// Options that can be used to configure a navigation. Used with [use_navigate](crate::hooks::use_navigate).
 #[derive(Clone, Debug)]
@@ -14,6 +19,10 @@ pub struct NavigateOptions {
     /// [State](https://developer.mozilla.org/en-US/docs/Web/API/History/state) that should be pushed
     /// onto the history stack during navigation.
     pub state: State,
+    /// Where to move focus once the new route has loaded. Defaults to `None`,
+    /// which uses the `focus_behavior` of the
+    /// [`Router`](crate::components::Router).
+    pub focus: Option<FocusBehavior>,
 }
 
 impl Default for NavigateOptions {
@@ -23,6 +32,141 @@ impl Default for NavigateOptions {
             replace: false,
             scroll: true,
             state: State::new(None),
+            focus: None,
         }
     }
 }
+
+/// Where the router moves focus after a client-side navigation, so that
+/// keyboard and screen reader users start at the content of the new route
+/// rather than at the link they activated.
+///
+/// Focus is only moved once the new route, and any `<Suspense/>` within it, has
+/// loaded. It is never moved on the initial page load, or on navigations that
+/// only change the query string or hash.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum FocusBehavior {
+    /// Focuses the first element with an `autofocus` or `data-router-focus`
+    /// attribute, falling back to the `<main>` element (or `[role=main]`), and
+    /// then to the `<body>`.
+    #[default]
+    Auto,
+    /// Focuses the first element matching this CSS selector, with the same
+    /// fallbacks as [`FocusBehavior::Auto`].
+    Selector(Cow<'static, str>),
+    /// Leaves focus where it is.
+    Preserve,
+}
+
+/// A client-side navigation that has settled, passed to the `on_navigation_end`
+/// callback of the [`Router`](crate::components::Router).
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavigationEnd {
+    /// The URL that was navigated to.
+    pub url: Url,
+    /// The title of the document, as set by the new route.
+    pub title: String,
+}
+
+/// Calls `callback`, if there is one, at the end of the navigation to `url`.
+///
+/// The DOM of the new route has been swapped in by then, but the effects that
+/// it queued, like those that set the title and meta tags of the document, only
+/// run on a later tick: so the callback is deferred until they have run, and it
+/// sees the new title.
+pub(crate) fn end_navigation(
+    callback: Option<Callback<(NavigationEnd,)>>,
+    url: Url,
+) {
+    end_navigation_with(callback, url, || document().title());
+}
+
+fn end_navigation_with(
+    callback: Option<Callback<(NavigationEnd,)>>,
+    url: Url,
+    title: impl FnOnce() -> String + 'static,
+) {
+    let Some(callback) = callback else {
+        return;
+    };
+    Executor::spawn_local(async move {
+        // spawned after the effects that the navigation queued, so they run
+        // during this yield
+        let (tx, rx) = oneshot::channel();
+        Executor::spawn_local(async move {
+            _ = tx.send(());
+        });
+        _ = rx.await;
+        callback.run((NavigationEnd {
+            url,
+            title: title(),
+        },));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{end_navigation_with, NavigationEnd};
+    use crate::location::Url;
+    use any_spawner::Executor;
+    use futures::{
+        channel::{mpsc, oneshot},
+        StreamExt,
+    };
+    use leptos::callback::Callback;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn navigation_ends_after_the_title_is_updated() {
+        _ = Executor::init_futures_executor();
+
+        // like the render effect of a <Title/>, which reruns on a later tick
+        // when the resource that its text reads changes
+        let resource = Arc::new(Mutex::new("Home"));
+        let title = Arc::new(Mutex::new(String::from("Home")));
+        let (notify, mut effect) = mpsc::unbounded::<()>();
+        Executor::spawn_local({
+            let resource = Arc::clone(&resource);
+            let title = Arc::clone(&title);
+            async move {
+                while effect.next().await.is_some() {
+                    *title.lock().unwrap() =
+                        format!("{} | Docs", resource.lock().unwrap());
+                }
+            }
+        });
+
+        let ended = Arc::new(Mutex::new(None));
+        let callback = Callback::new({
+            let ended = Arc::clone(&ended);
+            move |(end,): (NavigationEnd,)| {
+                *ended.lock().unwrap() = Some(end.title);
+            }
+        });
+
+        // the navigation blocks on the resource, and settles as soon as it has
+        // resolved, in the same tick as the title's effect is notified
+        let (resolve, resolved) = oneshot::channel();
+        Executor::spawn_local({
+            let title = Arc::clone(&title);
+            async move {
+                *resource.lock().unwrap() = resolved.await.unwrap();
+                notify.unbounded_send(()).unwrap();
+                end_navigation_with(
+                    Some(callback),
+                    Url::default(),
+                    move || title.lock().unwrap().clone(),
+                );
+            }
+        });
+        Executor::poll_local();
+        assert_eq!(*ended.lock().unwrap(), None);
+
+        resolve.send("Getting Started").unwrap();
+        Executor::poll_local();
+        assert_eq!(
+            ended.lock().unwrap().as_deref(),
+            Some("Getting Started | Docs")
+        );
+    }
+}
@@ -1,8 +1,10 @@
 use crate::{
     flat_router::MatchedRoute,
+    guard::NavigationGuard,
     hooks::Matched,
     location::{LocationProvider, Url},
     matching::RouteDefs,
+    navigate::{end_navigation, NavigationEnd},
     params::ParamsMap,
     view_transition::start_view_transition,
     ChooseView, MatchInterface, MatchNestedRoutes, MatchParams, PathSegment,
@@ -11,25 +13,31 @@ use crate::{
 use any_spawner::Executor;
 use either_of::{Either, EitherOf3};
 use futures::{channel::oneshot, future::join_all, FutureExt};
-use leptos::{attr::any_attribute::AnyAttribute, component, oco::Oco};
+use leptos::{
+    attr::any_attribute::AnyAttribute, callback::Callback, component, oco::Oco,
+};
 use or_poisoned::OrPoisoned;
 use reactive_graph::{
     computed::{ArcMemo, ScopedFuture},
     owner::{provide_context, use_context, Owner},
     signal::{ArcRwSignal, ArcTrigger},
-    traits::{Get, GetUntracked, Notify, ReadUntracked, Set, Track},
+    traits::{
+        Get, GetUntracked, Notify, ReadUntracked, Set, Track, WithUntracked,
+    },
     transition::AsyncTransition,
     wrappers::write::SignalSetter,
 };
 use send_wrapper::SendWrapper;
 use std::{
+    any::{Any, TypeId},
     cell::RefCell,
+    collections::HashMap,
     fmt::Debug,
     future::Future,
     iter, mem,
     pin::Pin,
     rc::Rc,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, RwLock},
 };
 use tachys::{
     hydration::Cursor,
@@ -51,6 +59,7 @@ pub(crate) struct NestedRoutesView<Loc, Defs, FalFn> {
     pub base: Option<Oco<'static, str>>,
     pub fallback: FalFn,
     pub set_is_routing: Option<SignalSetter<bool>>,
+    pub on_navigation_end: Option<Callback<(NavigationEnd,)>>,
     pub transition: bool,
 }
 
@@ -71,7 +80,7 @@ impl<Loc, Defs, FalFn, Fal> Render for NestedRoutesView<Loc, Defs, FalFn>
 where
     Loc: LocationProvider,
     Defs: MatchNestedRoutes,
-    FalFn: FnOnce() -> Fal,
+    FalFn: FnOnce() -> Fal + 'static,
     Fal: Render + 'static,
 {
     // TODO support fallback while loading
@@ -93,13 +102,16 @@ where
         let path = url.path().to_string();
 
         // match the route
-        let new_match = routes.match_route(url.path());
+        let new_match = routes.match_url(&url);
+        let guard = new_match
+            .as_ref()
+            .map(|route| NavigationGuard::new(route, &outer_owner));
 
         // start with an empty view because we'll be loading routes async
         let view = EitherOf3::A(()).build();
         let view = Rc::new(RefCell::new(view));
         let matched_view = match new_match {
-            None => EitherOf3::B(fallback()),
+            None => None,
             Some(route) => {
                 route.build_nested_route(
                     &url,
@@ -109,7 +121,7 @@ where
                     &outer_owner,
                 );
                 drop(url);
-                outer_owner.with(|| EitherOf3::C(Outlet().into_any()))
+                Some(outer_owner.with(|| Outlet().into_any()))
             }
         };
 
@@ -118,9 +130,19 @@ where
             let loaders = mem::take(&mut loaders);
             ScopedFuture::new(async move {
                 let triggers = join_all(loaders).await;
-                for trigger in triggers {
-                    trigger.notify();
-                }
+                let allowed = match guard {
+                    Some(guard) => guard.allows().await,
+                    None => true,
+                };
+                let matched_view = match matched_view {
+                    Some(outlet) if allowed => {
+                        for trigger in triggers {
+                            trigger.notify();
+                        }
+                        EitherOf3::C(outlet)
+                    }
+                    _ => EitherOf3::B(fallback()),
+                };
                 matched_view.rebuild(&mut *view.borrow_mut());
             })
         });
@@ -136,11 +158,28 @@ where
     fn rebuild(self, state: &mut Self::State) {
         let url_snapshot = self.current_url.get_untracked();
 
-        // if the path is the same, we do not need to re-route
-        // we can just update the search query and go about our day
-        if url_snapshot.path() == state.path {
-            for outlet in &state.outlets {
-                outlet.url.set(url_snapshot.to_owned());
+        // if the path is the same, and the query does not change which routes
+        // match it, we do not need to re-route: we can just update the search
+        // query and go about our day
+        if url_snapshot.path() == state.path
+            && matches_outlets(
+                self.routes.match_url(&url_snapshot),
+                &state.outlets,
+            )
+        {
+            // if only the hash has changed, this is not a new navigation at
+            // all: the routes only need to see the new URL
+            let hash_only = state.outlets.first().is_some_and(|outlet| {
+                outlet.url.with_untracked(|url| {
+                    url.differs_only_in_hash(&url_snapshot)
+                })
+            });
+            update_outlet_urls(&state.outlets, &url_snapshot);
+            if let Some(loc) = self.location {
+                loc.ready_to_complete();
+            }
+            if !hash_only {
+                end_navigation(self.on_navigation_end, url_snapshot);
             }
             return;
         }
@@ -148,9 +187,9 @@ where
         state.path.clear();
         state.path.push_str(url_snapshot.path());
 
-        let new_match = self.routes.match_route(url_snapshot.path());
+        let new_match = self.routes.match_url(&url_snapshot);
 
-        state.current_url.set(url_snapshot);
+        state.current_url.set(url_snapshot.clone());
 
         match new_match {
             None => {
@@ -160,12 +199,14 @@ where
                 if let Some(loc) = self.location {
                     loc.ready_to_complete();
                 }
+                end_navigation(self.on_navigation_end, url_snapshot);
             }
             Some(route) => {
                 if let Some(set_is_routing) = self.set_is_routing {
                     set_is_routing.set(true);
                 }
 
+                let guard = NavigationGuard::new(&route, &self.outer_owner);
                 let mut preloaders = Vec::new();
                 let mut full_loaders = Vec::new();
                 let different_level = route.rebuild_nested_route(
@@ -185,8 +226,17 @@ where
                     .as_ref()
                     .map(|nav| nav.is_back().get_untracked())
                     .unwrap_or(false);
+                let view = Rc::clone(&state.view);
+                let fallback = self.fallback;
                 Executor::spawn_local(async move {
                     let triggers = join_all(preloaders).await;
+                    // the views of routes that a guard does not allow are never
+                    // shown
+                    if !guard.allows().await {
+                        EitherOf3::<(), Fal, AnyView>::B(fallback())
+                            .rebuild(&mut view.borrow_mut());
+                        return;
+                    }
                     // tell each one of the outlet triggers that it's ready
                     let notify = move || {
                         for trigger in triggers {
@@ -208,6 +258,7 @@ where
                     if let Some(loc) = location {
                         loc.ready_to_complete();
                     }
+                    end_navigation(self.on_navigation_end, url_snapshot);
                 });
 
                 // if it was on the fallback, show the view instead
@@ -289,12 +340,18 @@ where
                         })
                         .chain(data.segments)
                         .collect::<Vec<_>>();
+                    let mut config = data.config;
+                    config.deduplication = config
+                        .deduplication
+                        .map(|config| config.for_route(data.id));
                     RouteListing::new(
                         path,
                         data.ssr_mode,
                         data.methods,
                         data.regenerate,
                     )
+                    .with_config(config)
+                    .with_language_redirect(data.language_redirect)
                 })
                 .collect::<Vec<_>>();
 
@@ -328,7 +385,7 @@ where
             let current_url = current_url.read_untracked();
 
             let mut outlets = Vec::new();
-            let new_match = routes.match_route(current_url.path());
+            let new_match = routes.match_url(&current_url);
             let view = match new_match {
                 None => Either::Left(fallback()),
                 Some(route) => {
@@ -382,7 +439,7 @@ where
         let current_url = current_url.read_untracked();
 
         let mut outlets = Vec::new();
-        let new_match = routes.match_route(current_url.path());
+        let new_match = routes.match_url(&current_url);
         let view = match new_match {
             None => Either::Left(fallback()),
             Some(route) => {
@@ -434,7 +491,7 @@ where
         let path = url.path().to_string();
 
         // match the route
-        let new_match = routes.match_route(url.path());
+        let new_match = routes.match_url(&url);
 
         // start with an empty view because we'll be loading routes async
         let view = Rc::new(RefCell::new(
@@ -473,8 +530,89 @@ where
     }
 }
 
+/// Updates the URL signal of each matched route, without re-routing.
+///
+/// This is used when only the query or hash has changed (for example, after a
+/// `GET` submission of a `<Form/>` to the current path): every route keeps its
+/// owner and its view, and only readers of the URL like
+/// [`use_query`](crate::hooks::use_query) are notified. Outlets whose URL is
+/// already up to date are not notified at all.
+fn update_outlet_urls(outlets: &[RouteContext], url: &Url) {
+    for outlet in outlets {
+        if outlet.url.with_untracked(|current| current != url) {
+            outlet.url.set(url.to_owned());
+        }
+    }
+}
+
 type OutletViewFn = Box<dyn FnMut(Owner) -> Suspend<AnyView> + Send>;
 
+/// The values that a route's view passes to the view rendered in its
+/// `<Outlet/>`, by type.
+///
+/// The store belongs to the parent route, not to the child, so that it is kept
+/// when only the child changes (and the parent view is not rendered again).
+#[derive(Clone, Default)]
+pub(crate) struct OutletContext(
+    Arc<RwLock<HashMap<TypeId, Box<dyn Any + Send + Sync>>>>,
+);
+
+impl OutletContext {
+    pub(crate) fn insert<T: Send + Sync + 'static>(&self, value: T) {
+        self.0
+            .write()
+            .or_poisoned()
+            .insert(TypeId::of::<T>(), Box::new(value));
+    }
+
+    pub(crate) fn get<T: Clone + 'static>(&self) -> Option<T> {
+        self.0
+            .read()
+            .or_poisoned()
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+            .cloned()
+    }
+}
+
+impl Debug for OutletContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OutletContext").finish_non_exhaustive()
+    }
+}
+
+/// Provided via context to each matched route: the store its view passes values
+/// to its outlet with, and the store of its parent route, which it reads them
+/// from.
+///
+/// Because each route provides its own, the values a route passes to its outlet
+/// are seen by its child route, but not by the routes nested further down.
+#[derive(Debug, Clone)]
+pub(crate) struct OutletContexts {
+    pub(crate) own: OutletContext,
+    pub(crate) parent: Option<OutletContext>,
+}
+
+/// Whether `matched` is a match of the same chain of routes as the ones that
+/// `outlets` render.
+fn matches_outlets(
+    matched: Option<impl MatchInterface>,
+    outlets: &[RouteContext],
+) -> bool {
+    fn push_ids(matched: impl MatchInterface, ids: &mut Vec<RouteMatchId>) {
+        ids.push(matched.as_id());
+        if let Some(child) = matched.into_view_and_child().1 {
+            push_ids(child, ids);
+        }
+    }
+
+    let mut ids = Vec::new();
+    if let Some(matched) = matched {
+        push_ids(matched, &mut ids);
+    }
+    ids.iter().eq(outlets.iter().map(|outlet| &outlet.id))
+}
+
 pub(crate) struct RouteContext {
     id: RouteMatchId,
     trigger: ArcTrigger,
@@ -484,6 +622,7 @@ pub(crate) struct RouteContext {
     pub matched: ArcRwSignal<String>,
     base: Option<Oco<'static, str>>,
     view_fn: Arc<Mutex<OutletViewFn>>,
+    outlet_context: OutletContext,
 }
 
 impl Debug for RouteContext {
@@ -517,6 +656,7 @@ impl Clone for RouteContext {
             matched: self.matched.clone(),
             base: self.base.clone(),
             view_fn: Arc::clone(&self.view_fn),
+            outlet_context: self.outlet_context.clone(),
         }
     }
 }
@@ -616,6 +756,11 @@ where
         // require that we can clone it out
         let trigger = ArcTrigger::new();
 
+        let contexts = OutletContexts {
+            own: OutletContext::default(),
+            parent: outlets.last().map(|outlet| outlet.outlet_context.clone()),
+        };
+
         // add this outlet to the end of the outlet stack used for diffing
         let outlet = RouteContext {
             id: self.as_id(),
@@ -628,9 +773,17 @@ where
                 Suspend::new(Box::pin(async { ().into_any() }))
             }))),
             base: base.clone(),
+            outlet_context: contexts.own.clone(),
         };
         outlets.push(outlet.clone());
 
+        // provide any context values from the route definition, so that they
+        // are available to this route's view and to its children
+        owner.with(|| {
+            provide_context(contexts);
+            self.provide_route_contexts();
+        });
+
         // send the initial view through the channel, and recurse through the children
         let (view, child) = self.into_view_and_child();
 
@@ -708,6 +861,10 @@ where
             .take(*items)
             .map(|route| (route.params.clone(), route.matched.clone()))
             .unzip();
+        let parent_outlet_context = (*items > 0)
+            .then(|| outlets.get(*items - 1))
+            .flatten()
+            .map(|outlet| outlet.outlet_context.clone());
         let current = outlets.get_mut(*items);
         match current {
             // if there's nothing currently in the routes at this point, build from here
@@ -726,6 +883,25 @@ where
                     self.to_params().into_iter().collect::<ParamsMap>();
                 let new_match = self.as_matched().to_owned();
 
+                // a different route gets a new owner (see below), which needs
+                // the context values from the route definition before this
+                // match is consumed it also gets a new store for the values its
+                // view passes to its outlet, but keeps reading those of its
+                // parent, which has not changed
+                let new_owner = (id != current.id).then(|| {
+                    let owner = parent.child();
+                    current.outlet_context = OutletContext::default();
+                    let contexts = OutletContexts {
+                        own: current.outlet_context.clone(),
+                        parent: parent_outlet_context,
+                    };
+                    owner.with(|| {
+                        provide_context(contexts);
+                        self.provide_route_contexts();
+                    });
+                    owner
+                });
+
                 let (view, child) = self.into_view_and_child();
 
                 // if the IDs don't match, everything below in the tree needs to be swapped:
@@ -734,7 +910,7 @@ where
                 // 2) remove other outlets that are lower down in the match tree
                 // 3) build the rest of the list of matched routes, rather than rebuilding,
                 //    as all lower outlets needs to be replaced
-                if id != current.id {
+                if let Some(new_owner) = new_owner {
                     // update the ID of the match at this depth, so that futures rebuilds diff
                     // against the new ID, not the original one
                     current.id = id;
@@ -780,7 +956,7 @@ where
                     // assign a new owner, so that contexts and signals owned by the previous route
                     // in this outlet can be dropped
                     let mut old_owner =
-                        Some(mem::replace(&mut current.owner, parent.child()));
+                        Some(mem::replace(&mut current.owner, new_owner));
                     let owner = current.owner.clone();
                     let (full_tx, full_rx) = oneshot::channel();
                     let full_tx = Mutex::new(Some(full_tx));
@@ -928,3 +1104,269 @@ where
         view_fn(Owner::current().unwrap())
     }
 }
+
+// parsing the query string of a URL requires `ssr` outside the browser
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{
+        AddNestedRoute, NestedRouteViewState, NestedRoutesView, RouteContext,
+    };
+    use crate::{
+        hooks::{provide_outlet_context, use_outlet_context},
+        location::{BrowserUrl, RequestUrl, Url},
+        MatchNestedRoutes, NestedRoute, RouteDefs, RouteMatchId, StaticSegment,
+    };
+    use any_spawner::Executor;
+    use either_of::EitherOf3;
+    use reactive_graph::{
+        computed::ArcMemo,
+        owner::{use_context, Owner},
+        signal::ArcRwSignal,
+        traits::{Get, Set, With, WithUntracked},
+    };
+    use std::{cell::RefCell, rc::Rc};
+    use tachys::view::{either::EitherOf3State, Render};
+
+    /// A fallback that renders nothing, so that the state of the routes can be
+    /// built outside the browser.
+    struct NoFallback;
+
+    impl Render for NoFallback {
+        type State = ();
+
+        fn build(self) -> Self::State {}
+
+        fn rebuild(self, _state: &mut Self::State) {}
+    }
+
+    /// A `<Routes/>` for `routes` that shows the page at `url`.
+    fn routes_view<Defs: Clone>(
+        routes: &RouteDefs<Defs>,
+        url: &ArcRwSignal<Url>,
+        owner: &Owner,
+    ) -> NestedRoutesView<BrowserUrl, Defs, fn() -> NoFallback> {
+        NestedRoutesView {
+            location: None,
+            routes: routes.clone(),
+            outer_owner: owner.clone(),
+            current_url: url.clone(),
+            base: None,
+            fallback: || NoFallback,
+            set_is_routing: None,
+            on_navigation_end: None,
+            transition: false,
+        }
+    }
+
+    /// The state of the routes showing the page at `url`, like the one `build`
+    /// returns.
+    ///
+    /// Building the view of the matched routes needs the DOM, so the view is
+    /// left on the fallback, until a navigation to other routes replaces it
+    /// with their outlet.
+    fn routes_state<Defs>(
+        routes: &RouteDefs<Defs>,
+        url: &ArcRwSignal<Url>,
+        owner: &Owner,
+    ) -> NestedRouteViewState<NoFallback>
+    where
+        Defs: MatchNestedRoutes,
+        Defs::Match: AddNestedRoute,
+    {
+        let mut outlets = Vec::new();
+        let path = url.with_untracked(|url| {
+            routes.match_url(url).unwrap().build_nested_route(
+                url,
+                None,
+                &mut Vec::new(),
+                &mut outlets,
+                owner,
+            );
+            url.path().to_string()
+        });
+        NestedRouteViewState {
+            path,
+            current_url: url.clone(),
+            outlets,
+            view: Rc::new(RefCell::new(EitherOf3State {
+                state: EitherOf3::B(()),
+            })),
+        }
+    }
+
+    fn outlet_ids(
+        state: &NestedRouteViewState<NoFallback>,
+    ) -> Vec<(RouteMatchId, Owner)> {
+        state
+            .outlets
+            .iter()
+            .map(|outlet| (outlet.id, outlet.owner.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn query_only_change_keeps_nested_routes() {
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("shop"), || ()).child(
+                NestedRoute::new(StaticSegment("products"), || ())
+                    .child(NestedRoute::new(StaticSegment("search"), || ())),
+            ),
+        );
+        let url = ArcRwSignal::new(
+            RequestUrl::new("/shop/products/search?q=shoes")
+                .parse()
+                .unwrap(),
+        );
+        let root = Owner::new();
+        let mut state = routes_state(&routes, &url, &root);
+        assert_eq!(state.outlets.len(), 3);
+        let before = outlet_ids(&state);
+
+        // like `use_query_map`, read the query from the innermost route's URL
+        // signal
+        let query = {
+            let url = state.outlets[2].url.clone();
+            ArcMemo::new(move |_| url.with(|url| url.search_params().get("q")))
+        };
+        assert_eq!(query.get().as_deref(), Some("shoes"));
+
+        let new_url = RequestUrl::new("/shop/products/search?q=boots")
+            .parse()
+            .unwrap();
+        url.set(new_url.clone());
+        routes_view(&routes, &url, &root).rebuild(&mut state);
+
+        assert_eq!(outlet_ids(&state), before);
+        assert_eq!(query.get().as_deref(), Some("boots"));
+        assert!(state
+            .outlets
+            .iter()
+            .all(|outlet| outlet.url.with(|url| url == &new_url)));
+    }
+
+    #[test]
+    fn hash_only_change_does_not_rebuild_the_view() {
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("docs"), || ())
+                .child(NestedRoute::new(StaticSegment("routing"), || ())),
+        );
+        let with_hash = |hash: &str| {
+            let mut url = RequestUrl::new("/docs/routing").parse().unwrap();
+            hash.clone_into(url.hash_mut());
+            url
+        };
+        let url = ArcRwSignal::new(with_hash("#nesting"));
+        let root = Owner::new();
+        let mut state = routes_state(&routes, &url, &root);
+        let before = outlet_ids(&state);
+
+        url.set(with_hash("#params"));
+        routes_view(&routes, &url, &root).rebuild(&mut state);
+
+        assert_eq!(outlet_ids(&state), before);
+        assert!(matches!(state.view.borrow().state, EitherOf3::B(())));
+        assert!(state
+            .outlets
+            .iter()
+            .all(|outlet| outlet.url.with(|url| url.hash() == "#params")));
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Config(&'static str);
+
+    #[test]
+    fn provided_contexts_are_scoped_to_the_matched_route() {
+        // a navigation to other routes spawns the loading of their data
+        _ = Executor::init_futures_executor();
+        let routes = RouteDefs::<_>::new((
+            NestedRoute::new(StaticSegment("settings"), || ())
+                .provide(Config("settings"))
+                .child((
+                    NestedRoute::new(StaticSegment("profile"), || ()),
+                    NestedRoute::new(StaticSegment("account"), || ()),
+                )),
+            NestedRoute::new(StaticSegment("about"), || ()),
+        ));
+        let url = ArcRwSignal::new(
+            RequestUrl::new("/settings/profile").parse().unwrap(),
+        );
+        let root = Owner::new();
+        let mut state = routes_state(&routes, &url, &root);
+        let seen = |state: &NestedRouteViewState<NoFallback>| {
+            state
+                .outlets
+                .iter()
+                .map(|outlet| outlet.owner.with(use_context::<Config>))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(seen(&state), [Some(Config("settings")); 2]);
+
+        let navigate = |state: &mut NestedRouteViewState<NoFallback>, path| {
+            url.set(RequestUrl::new(path).parse().unwrap());
+            routes_view(&routes, &url, &root).rebuild(state);
+        };
+        // the new child route sees the value of the route that is still matched
+        navigate(&mut state, "/settings/account");
+        assert_eq!(seen(&state), [Some(Config("settings")); 2]);
+
+        navigate(&mut state, "/about");
+        assert_eq!(seen(&state), [None]);
+        assert_eq!(root.with(use_context::<Config>), None);
+
+        navigate(&mut state, "/settings/profile");
+        assert_eq!(seen(&state), [Some(Config("settings")); 2]);
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Org(&'static str);
+
+    #[test]
+    fn outlet_context_is_only_seen_by_the_child_route() {
+        let routes = RouteDefs::<_>::new(
+            NestedRoute::new(StaticSegment("org"), || ()).child((
+                NestedRoute::new(StaticSegment("members"), || ())
+                    .child(NestedRoute::new(StaticSegment("list"), || ())),
+                NestedRoute::new(StaticSegment("settings"), || ()),
+            )),
+        );
+        let url = RequestUrl::new("/org/members/list").parse().unwrap();
+
+        let root = Owner::new();
+        let mut outlets = Vec::<RouteContext>::new();
+        routes.match_url(&url).unwrap().build_nested_route(
+            &url,
+            None,
+            &mut Vec::new(),
+            &mut outlets,
+            &root,
+        );
+        outlets[0]
+            .owner
+            .with(|| provide_outlet_context(Org("acme")));
+        let seen = |outlets: &[RouteContext]| {
+            outlets
+                .iter()
+                .map(|outlet| outlet.owner.with(use_outlet_context::<Org>))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(seen(&outlets), [None, Some(Org("acme")), None]);
+
+        // the parent view is not rendered again when only its child changes
+        let url = RequestUrl::new("/org/settings").parse().unwrap();
+        routes
+            .match_route(url.path())
+            .unwrap()
+            .rebuild_nested_route(
+                &url,
+                None,
+                &mut 0,
+                &mut Vec::new(),
+                &mut Vec::new(),
+                &mut outlets,
+                &root,
+                false,
+                0,
+            );
+        assert_eq!(seen(&outlets), [None, Some(Org("acme"))]);
+    }
+}
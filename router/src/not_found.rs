@@ -0,0 +1,67 @@
+use serde_json::{json, Value};
+
+/// The body a [JSON not-found route](crate::NestedRoute::not_found_json)
+/// responds with, with `404 Not Found`, instead of rendering a view.
+///
+/// ```rust
+/// use leptos_router::NotFoundResponse;
+/// use serde_json::json;
+///
+/// let response = NotFoundResponse::problem("There is no such order.");
+/// assert_eq!(response.content_type(), "application/problem+json");
+/// assert_eq!(
+///     serde_json::from_str::<serde_json::Value>(response.body()).unwrap(),
+///     json!({
+///         "type": "about:blank",
+///         "title": "Not Found",
+///         "status": 404,
+///         "detail": "There is no such order.",
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotFoundResponse {
+    content_type: &'static str,
+    body: String,
+}
+
+impl NotFoundResponse {
+    /// Responds with `payload`, as `application/json`.
+    pub fn json(payload: &Value) -> Self {
+        Self {
+            content_type: "application/json",
+            body: payload.to_string(),
+        }
+    }
+
+    /// Responds with the [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807)
+    /// problem details of a `404 Not Found`, with `detail` as the explanation
+    /// that is specific to this route, as `application/problem+json`.
+    pub fn problem(detail: &str) -> Self {
+        let problem = json!({
+            "type": "about:blank",
+            "title": "Not Found",
+            "status": 404,
+            "detail": detail,
+        });
+        Self {
+            content_type: "application/problem+json",
+            body: problem.to_string(),
+        }
+    }
+
+    /// The HTTP status code of the response, which is always `404`.
+    pub fn status(&self) -> u16 {
+        404
+    }
+
+    /// The value of the `Content-Type` header of the response.
+    pub fn content_type(&self) -> &'static str {
+        self.content_type
+    }
+
+    /// The body of the response.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{
+    matching::normalize_segments, ExpandOptionals, GeneratedRouteData,
+    MatchNestedRoutes, PathSegment, RouteDefs,
+};
+use serde_json::{json, Map, Value};
+
+/// The methods that an OpenAPI path item can have operations for.
+const OPENAPI_METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+impl<Children> RouteDefs<Children>
+where
+    Children: MatchNestedRoutes,
+{
+    /// Assembles the
+    /// [paths object](https://spec.openapis.org/oas/v3.1.0#paths-object) of an
+    /// OpenAPI document that describes these routes, with the base path before
+    /// each of them.
+    ///
+    /// Each route has an operation for each of its methods, with its
+    /// [description](crate::NestedRoute::description), its
+    /// [tags](crate::NestedRoute::tag), a required string parameter for each
+    /// param in its path, and a response with the route's
+    /// [status](crate::NestedRoute::status) or `200`, as well as one for each
+    /// of its [response schemas](crate::NestedRoute::response_schema). OpenAPI
+    /// has no optional path params, so a route with an optional param is listed
+    /// both with and without it. Methods that OpenAPI does not have operations
+    /// for, like `PURGE`, are left out.
+    ///
+    /// ```rust
+    /// use leptos_router::{
+    ///     NestedRoute, ParamSegment, RouteDefs, StaticSegment,
+    /// };
+    ///
+    /// let routes = RouteDefs::<_>::new(
+    ///     NestedRoute::new(
+    ///         (StaticSegment("users"), ParamSegment("id")),
+    ///         || (),
+    ///     )
+    ///     .description("A user's profile.")
+    ///     .tag("users"),
+    /// );
+    /// let paths = routes.to_openapi_paths();
+    /// let get = &paths["/users/{id}"]["get"];
+    /// assert_eq!(get["description"], "A user's profile.");
+    /// assert_eq!(get["parameters"][0]["name"], "id");
+    /// ```
+    pub fn to_openapi_paths(&self) -> Value {
+        let (base, routes) = self.generate_routes();
+        let base = base.unwrap_or_default().trim_end_matches('/');
+        let mut paths = Map::new();
+        for route in routes {
+            for segments in
+                normalize_segments(&route.segments).expand_optionals()
+            {
+                let path = match format!("{base}{}", openapi_path(&segments)) {
+                    path if path.is_empty() => "/".to_string(),
+                    path => path,
+                };
+                let path_item = paths
+                    .entry(path)
+                    .or_insert_with(|| Value::Object(Map::new()));
+                if let Value::Object(path_item) = path_item {
+                    for method in &route.methods {
+                        let method = method.as_str().to_ascii_lowercase();
+                        if OPENAPI_METHODS.contains(&method.as_str()) {
+                            path_item.insert(
+                                method,
+                                openapi_operation(&route, &segments),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        Value::Object(paths)
+    }
+}
+
+/// Formats segments as an OpenAPI path template, like `/users/{id}`.
+fn openapi_path(segments: &[PathSegment]) -> String {
+    let mut path = String::new();
+    for segment in segments {
+        match segment {
+            PathSegment::Unit => continue,
+            PathSegment::Static(s) => {
+                path.push('/');
+                path.push_str(s);
+            }
+            PathSegment::Param(s)
+            | PathSegment::OptionalParam(s)
+            | PathSegment::Splat(s) => {
+                path.push_str("/{");
+                path.push_str(s);
+                path.push('}');
+            }
+        }
+    }
+    path
+}
+
+fn openapi_operation(
+    route: &GeneratedRouteData,
+    segments: &[PathSegment],
+) -> Value {
+    let mut operation = Map::new();
+    if let Some(description) = &route.description {
+        operation.insert("description".into(), description.as_str().into());
+    }
+    if !route.tags.is_empty() {
+        operation.insert("tags".into(), route.tags.clone().into());
+    }
+    let parameters = segments
+        .iter()
+        .filter_map(|segment| match segment {
+            PathSegment::Param(name)
+            | PathSegment::OptionalParam(name)
+            | PathSegment::Splat(name) => Some(json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string" },
+            })),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if !parameters.is_empty() {
+        operation.insert("parameters".into(), parameters.into());
+    }
+    let status = route.status.unwrap_or(200);
+    let mut responses = Map::new();
+    responses.insert(
+        status.to_string(),
+        json!({ "description": "The rendered route." }),
+    );
+    for (code, schema) in &route.response_schemas {
+        let description = if *code == status {
+            "The rendered route.".to_string()
+        } else {
+            format!("A `{code}` response.")
+        };
+        responses.insert(
+            code.to_string(),
+            json!({
+                "description": description,
+                "content": { "application/json": { "schema": schema } },
+            }),
+        );
+    }
+    operation.insert("responses".into(), Value::Object(responses));
+    Value::Object(operation)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Method, NestedRoute, OptionalParamSegment, ParamSegment, RouteDefs,
+        StaticSegment,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn openapi_paths_describe_each_route() {
+        let routes = RouteDefs::<_>::new_with_base(
+            (
+                NestedRoute::new(StaticSegment(""), || ()),
+                NestedRoute::new(StaticSegment("users"), || ())
+                    .description("Every user.")
+                    .tag("users")
+                    .child(
+                        NestedRoute::new(
+                            (ParamSegment("id"), OptionalParamSegment("tab")),
+                            || (),
+                        )
+                        .custom_method(Method::Delete.as_str().to_string())
+                        .custom_method("PURGE")
+                        .status(202)
+                        .response_schema(404, json!({ "type": "null" }))
+                        .tag("admin"),
+                    ),
+            ),
+            "/app",
+        );
+
+        let user = json!({
+            "description": "Every user.",
+            "tags": ["users", "admin"],
+            "parameters": [{
+                "name": "id",
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string" },
+            }],
+            "responses": {
+                "202": { "description": "The rendered route." },
+                "404": {
+                    "description": "A `404` response.",
+                    "content": {
+                        "application/json": { "schema": { "type": "null" } }
+                    },
+                },
+            },
+        });
+        let mut user_tab = user.clone();
+        user_tab["parameters"].as_array_mut().unwrap().push(json!({
+            "name": "tab",
+            "in": "path",
+            "required": true,
+            "schema": { "type": "string" },
+        }));
+
+        assert_eq!(
+            routes.to_openapi_paths(),
+            json!({
+                "/app": {
+                    "get": {
+                        "responses": {
+                            "200": { "description": "The rendered route." }
+                        },
+                    },
+                },
+                "/app/users/{id}": { "get": user, "delete": user },
+                "/app/users/{id}/{tab}": { "get": user_tab, "delete": user_tab },
+            })
+        );
+    }
+}
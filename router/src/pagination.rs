@@ -0,0 +1,178 @@
+use thiserror::Error;
+
+/// What to do with a request for more items per page than
+/// [`max_per_page`](PaginationConfig::max_per_page) allows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PerPageOverflow {
+    /// Use `max_per_page` instead.
+    #[default]
+    Clamp,
+    /// Reject the request with [`TooManyPerPage`], which the route answers with
+    /// `400 Bad Request`.
+    Reject,
+}
+
+/// A request for more items per page than a paginated route allows, when its
+/// [`overflow`](PaginationConfig::overflow) is [`PerPageOverflow::Reject`].
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[error(
+    "{requested} items per page were requested, but at most {max} are allowed"
+)]
+pub struct TooManyPerPage {
+    /// The number of items per page the request asked for.
+    pub requested: u32,
+    /// The largest number of items per page the route allows.
+    pub max: u32,
+}
+
+/// Defines how a paginated route reads its page parameters from the query
+/// string.
+///
+/// Used with
+/// [`NestedRoute::with_pagination`](crate::NestedRoute::with_pagination).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PaginationConfig {
+    /// The query parameter that contains the (1-based) page number.
+    pub page_param: &'static str,
+    /// The query parameter that contains the number of items per page.
+    pub per_page_param: &'static str,
+    /// The number of items per page if the request does not specify one.
+    pub default_per_page: usize,
+    /// The largest number of items per page that can be requested.
+    pub max_per_page: usize,
+    /// What to do with a request for more than `max_per_page` items per page.
+    pub overflow: PerPageOverflow,
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self {
+            page_param: "page",
+            per_page_param: "per_page",
+            default_per_page: 20,
+            max_per_page: 100,
+            overflow: PerPageOverflow::Clamp,
+        }
+    }
+}
+
+impl PaginationConfig {
+    /// Reads the page parameters from a query string (without the leading `?`).
+    ///
+    /// Parameters that are missing or are not valid numbers use their defaults.
+    /// If the query string is unknown (`None`), every parameter uses its
+    /// default. Returns an error if the request should be rejected, because it
+    /// asks for too many items per page.
+    pub fn params(
+        &self,
+        query: Option<&str>,
+    ) -> Result<PaginationParams, TooManyPerPage> {
+        let param = |name: &str| {
+            query?
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| *key == name)
+                .and_then(|(_, value)| value.parse::<u32>().ok())
+        };
+
+        let max_per_page = saturating_u32(self.max_per_page);
+        let per_page = match param(self.per_page_param) {
+            Some(per_page) if per_page > max_per_page => match self.overflow {
+                PerPageOverflow::Clamp => max_per_page,
+                PerPageOverflow::Reject => {
+                    return Err(TooManyPerPage {
+                        requested: per_page,
+                        max: max_per_page,
+                    })
+                }
+            },
+            Some(per_page) if per_page > 0 => per_page,
+            _ => saturating_u32(self.default_per_page).min(max_per_page),
+        };
+        let page = param(self.page_param).unwrap_or(1).max(1);
+
+        Ok(PaginationParams {
+            page,
+            per_page,
+            offset: (page - 1).saturating_mul(per_page),
+        })
+    }
+}
+
+fn saturating_u32(value: usize) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
+
+/// The page a request to a paginated route asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PaginationParams {
+    /// The 1-based page number.
+    pub page: u32,
+    /// The number of items per page.
+    pub per_page: u32,
+    /// The number of items before this page.
+    pub offset: u32,
+}
+
+impl Default for PaginationParams {
+    fn default() -> Self {
+        PaginationConfig::default()
+            .params(None)
+            .expect("the default pagination config never rejects")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        PaginationConfig, PaginationParams, PerPageOverflow, TooManyPerPage,
+    };
+
+    #[test]
+    fn reads_params_from_query() {
+        let config = PaginationConfig::default();
+        assert_eq!(
+            config.params(Some("q=shoes&page=3&per_page=50")),
+            Ok(PaginationParams {
+                page: 3,
+                per_page: 50,
+                offset: 100
+            })
+        );
+        assert_eq!(
+            config.params(Some("page=oops&per_page=0")),
+            Ok(PaginationParams {
+                page: 1,
+                per_page: 20,
+                offset: 0
+            })
+        );
+        assert_eq!(config.params(None), Ok(PaginationParams::default()));
+    }
+
+    #[test]
+    fn clamps_or_rejects_too_many_per_page() {
+        let config = PaginationConfig {
+            page_param: "p",
+            per_page_param: "n",
+            default_per_page: 10,
+            max_per_page: 25,
+            overflow: PerPageOverflow::Clamp,
+        };
+        assert_eq!(config.params(Some("p=2&n=500")).unwrap().per_page, 25);
+        assert_eq!(config.params(Some("p=2&n=500")).unwrap().offset, 25);
+
+        let config = PaginationConfig {
+            overflow: PerPageOverflow::Reject,
+            ..config
+        };
+        assert_eq!(
+            config.params(Some("p=2&n=500")),
+            Err(TooManyPerPage {
+                requested: 500,
+                max: 25
+            })
+        );
+        assert_eq!(config.params(Some("p=2&n=25")).unwrap().per_page, 25);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::location::Url;
-use std::{borrow::Cow, str::FromStr, sync::Arc};
+use std::{borrow::Cow, fmt::Display, str::FromStr, sync::Arc};
 use thiserror::Error;
 
 type ParamsMapInner = Vec<(Cow<'static, str>, Vec<String>)>;
@@ -170,6 +170,27 @@ impl Params for () {
     }
 }
 
+/// The inverse of [`Params`], which serializes a typed data structure into a
+/// key-value map, e.g., to build the query of a link with the same rules it is
+/// parsed with.
+pub trait ToParams {
+    /// Serializes the value into a map.
+    fn to_map(&self) -> ParamsMap;
+
+    /// Serializes the value into a query string, like `?page=2&tag=a&tag=b`,
+    /// which is empty if there are no params.
+    fn to_query_string(&self) -> String {
+        self.to_map().to_query_string()
+    }
+}
+
+impl ToParams for () {
+    #[inline(always)]
+    fn to_map(&self) -> ParamsMap {
+        ParamsMap::new()
+    }
+}
+
 /// Converts some parameter value from the URL into a typed parameter with the given name.
 pub trait IntoParam
 where
@@ -178,6 +199,22 @@ where
     /// Converts the param.
     fn into_param(value: Option<&str>, name: &str)
         -> Result<Self, ParamsError>;
+
+    /// Converts the param with the given name in `map`, from its most recent
+    /// value unless the param can have several, like a `Vec`.
+    fn into_param_from_map(
+        map: &ParamsMap,
+        name: &str,
+    ) -> Result<Self, ParamsError> {
+        Self::into_param(map.get_str(name), name)
+    }
+}
+
+/// The inverse of [`IntoParam`], which adds a typed parameter to a map with the
+/// given name.
+pub trait ToParam {
+    /// Adds the param to `map`.
+    fn to_param(&self, name: &'static str, map: &mut ParamsMap);
 }
 
 impl<T> IntoParam for Option<T>
@@ -199,14 +236,71 @@ where
     }
 }
 
+/// A param that is left out of the map when it is `None`.
+impl<T> ToParam for Option<T>
+where
+    T: Display,
+{
+    fn to_param(&self, name: &'static str, map: &mut ParamsMap) {
+        if let Some(value) = self {
+            map.insert(name, value.to_string());
+        }
+    }
+}
+
+/// A param that is repeated once for each of its values, like `?tag=a&tag=b`,
+/// which is empty if it is missing.
+impl<T> IntoParam for Vec<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+{
+    fn into_param(
+        value: Option<&str>,
+        _name: &str,
+    ) -> Result<Self, ParamsError> {
+        value
+            .into_iter()
+            .map(|value| {
+                T::from_str(value).map_err(|e| ParamsError::Params(Arc::new(e)))
+            })
+            .collect()
+    }
+
+    fn into_param_from_map(
+        map: &ParamsMap,
+        name: &str,
+    ) -> Result<Self, ParamsError> {
+        map.get_all(name)
+            .unwrap_or_default()
+            .iter()
+            .map(|value| {
+                T::from_str(value).map_err(|e| ParamsError::Params(Arc::new(e)))
+            })
+            .collect()
+    }
+}
+
+impl<T> ToParam for Vec<T>
+where
+    T: Display,
+{
+    fn to_param(&self, name: &'static str, map: &mut ParamsMap) {
+        for value in self {
+            map.insert(name, value.to_string());
+        }
+    }
+}
+
 // TODO can we support Option<T> and T in a non-nightly way?
 #[cfg(all(feature = "nightly", rustc_nightly))]
 mod option_param {
-    use super::{IntoParam, ParamsError};
-    use std::{str::FromStr, sync::Arc};
+    use super::{IntoParam, ParamsError, ParamsMap, ToParam};
+    use std::{fmt::Display, str::FromStr, sync::Arc};
 
     auto trait NotOption {}
     impl<T> !NotOption for Option<T> {}
+    impl<T, A: std::alloc::Allocator> !NotOption for Vec<T, A> {}
 
     impl<T> IntoParam for T
     where
@@ -222,6 +316,15 @@ mod option_param {
             Self::from_str(value).map_err(|e| ParamsError::Params(Arc::new(e)))
         }
     }
+
+    impl<T> ToParam for T
+    where
+        T: Display + NotOption,
+    {
+        fn to_param(&self, name: &'static str, map: &mut ParamsMap) {
+            map.insert(name, self.to_string());
+        }
+    }
 }
 
 /// Errors that can occur while parsing params using [`Params`].
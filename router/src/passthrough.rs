@@ -0,0 +1,455 @@
+use futures::{future::BoxFuture, stream::BoxStream};
+use std::{borrow::Cow, fmt, net::IpAddr, sync::Arc};
+use thiserror::Error;
+
+/// The body of a request forwarded by a [`UpstreamProxy`], or of its response,
+/// as a stream of chunks.
+pub type ProxyBody = BoxStream<'static, Result<Vec<u8>, ProxyError>>;
+
+/// A request to a [passthrough route](crate::NestedRoute::passthrough), as the
+/// server integrations hand it to its [`UpstreamProxy`].
+pub struct ProxyRequest {
+    /// The method of the request, like `GET`.
+    pub method: String,
+    /// The path of the request, with its query, like
+    /// `/legacy/report?year=2024`.
+    pub path_and_query: String,
+    /// The headers of the request, except those whose value is not valid UTF-8.
+    pub headers: Vec<(String, String)>,
+    /// The address of the client that sent the request, if the server knows it.
+    pub client_ip: Option<IpAddr>,
+    /// The body of the request.
+    pub body: ProxyBody,
+}
+
+impl fmt::Debug for ProxyRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyRequest")
+            .field("method", &self.method)
+            .field("path_and_query", &self.path_and_query)
+            .field("headers", &self.headers)
+            .field("client_ip", &self.client_ip)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The response of the upstream to a [`ProxyRequest`], which the server
+/// integrations stream back to the client.
+pub struct ProxyResponse {
+    /// The status code of the response.
+    pub status: u16,
+    /// The headers of the response.
+    pub headers: Vec<(String, String)>,
+    /// The body of the response.
+    pub body: ProxyBody,
+}
+
+impl fmt::Debug for ProxyResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Why a request could not be forwarded to the upstream.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ProxyError {
+    /// The upstream could not be reached.
+    #[error("could not connect to the upstream: {0}")]
+    Connect(String),
+    /// The upstream did not respond in time.
+    #[error("the upstream did not respond in time")]
+    Timeout,
+    /// The request or its response failed for another reason.
+    #[error("the upstream request failed: {0}")]
+    Request(String),
+}
+
+impl ProxyError {
+    /// The status code of the response to the client, which is
+    /// `504 Gateway Timeout` if the upstream timed out, and `502 Bad Gateway`
+    /// otherwise.
+    pub fn status(&self) -> u16 {
+        match self {
+            ProxyError::Timeout => 504,
+            _ => 502,
+        }
+    }
+}
+
+/// Forwards the requests to a
+/// [passthrough route](crate::NestedRoute::passthrough) to another server, like
+/// a legacy backend that Leptos is deployed alongside.
+pub trait UpstreamProxy: Send + Sync {
+    /// Sends `request` to the upstream, resolving to its response once its
+    /// headers have arrived, while its body is still streamed.
+    fn forward(
+        &self,
+        request: ProxyRequest,
+    ) -> BoxFuture<'static, Result<ProxyResponse, ProxyError>>;
+}
+
+/// The [`UpstreamProxy`] of a
+/// [passthrough route](crate::NestedRoute::passthrough).
+#[derive(Clone)]
+pub struct Passthrough(Arc<dyn UpstreamProxy>);
+
+impl Passthrough {
+    /// Forwards requests with `upstream`.
+    pub fn new(upstream: Arc<dyn UpstreamProxy>) -> Self {
+        Self(upstream)
+    }
+
+    /// Sends `request` to the upstream.
+    pub fn forward(
+        &self,
+        request: ProxyRequest,
+    ) -> BoxFuture<'static, Result<ProxyResponse, ProxyError>> {
+        self.0.forward(request)
+    }
+}
+
+impl fmt::Debug for Passthrough {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Passthrough(..)")
+    }
+}
+
+impl PartialEq for Passthrough {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Passthrough {}
+
+/// The headers that only apply to a single connection, which a proxy must not
+/// forward.
+const HOP_BY_HOP: [&str; 8] = [
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "proxy-connection",
+    "te",
+    "trailer",
+    "transfer-encoding",
+];
+
+/// Removes the headers that only apply to a single connection, including those
+/// named in the `Connection` header, which a proxy must not forward in either
+/// direction.
+pub fn strip_hop_by_hop_headers(headers: &mut Vec<(String, String)>) {
+    let named = headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("connection"))
+        .flat_map(|(_, value)| value.split(','))
+        .map(|name| name.trim().to_ascii_lowercase())
+        .collect::<Vec<_>>();
+    headers.retain(|(name, _)| {
+        let name = name.to_ascii_lowercase();
+        !HOP_BY_HOP.contains(&name.as_str())
+            && name != "upgrade"
+            && !named.contains(&name)
+    });
+}
+
+/// Which headers an [`UpstreamProxy`] adds to, or removes from, the requests it
+/// forwards.
+///
+/// By default, the address of the client is appended to `X-Forwarded-For` and
+/// sent in `X-Real-IP`, so that the upstream can tell who sent each request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyHeaders {
+    forwarded_for: bool,
+    real_ip: bool,
+    strip: Vec<Cow<'static, str>>,
+    add: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+}
+
+impl Default for ProxyHeaders {
+    fn default() -> Self {
+        Self {
+            forwarded_for: true,
+            real_ip: true,
+            strip: Vec::new(),
+            add: Vec::new(),
+        }
+    }
+}
+
+impl ProxyHeaders {
+    /// Appends the address of the client to the `X-Forwarded-For` header.
+    pub fn forwarded_for(mut self, forwarded_for: bool) -> Self {
+        self.forwarded_for = forwarded_for;
+        self
+    }
+
+    /// Sends the address of the client in the `X-Real-IP` header.
+    pub fn real_ip(mut self, real_ip: bool) -> Self {
+        self.real_ip = real_ip;
+        self
+    }
+
+    /// Removes the header `name` from the forwarded requests, like a session
+    /// cookie that the upstream should not see.
+    pub fn strip(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.strip.push(name.into());
+        self
+    }
+
+    /// Adds the header `name` to the forwarded requests, replacing it if the
+    /// client sent it.
+    pub fn add(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        value: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.add.push((name.into(), value.into()));
+        self
+    }
+
+    /// Applies these to the `headers` of a request from `client_ip`, after
+    /// removing the [hop-by-hop headers](strip_hop_by_hop_headers).
+    pub fn apply(
+        &self,
+        headers: &mut Vec<(String, String)>,
+        client_ip: Option<IpAddr>,
+    ) {
+        strip_hop_by_hop_headers(headers);
+        let remove = |headers: &mut Vec<(String, String)>, name: &str| {
+            headers.retain(|(header, _)| !header.eq_ignore_ascii_case(name));
+        };
+
+        if let Some(ip) = client_ip {
+            if self.forwarded_for {
+                let mut forwarded = headers
+                    .iter()
+                    .filter(|(name, _)| {
+                        name.eq_ignore_ascii_case("x-forwarded-for")
+                    })
+                    .map(|(_, value)| value.clone())
+                    .collect::<Vec<_>>();
+                forwarded.push(ip.to_string());
+                remove(headers, "x-forwarded-for");
+                headers.push((
+                    "x-forwarded-for".to_string(),
+                    forwarded.join(", "),
+                ));
+            }
+            if self.real_ip {
+                remove(headers, "x-real-ip");
+                headers.push(("x-real-ip".to_string(), ip.to_string()));
+            }
+        }
+
+        for name in self
+            .strip
+            .iter()
+            .chain(self.add.iter().map(|(name, _)| name))
+        {
+            remove(headers, name);
+        }
+        headers.extend(
+            self.add
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string())),
+        );
+    }
+}
+
+#[cfg(all(feature = "passthrough", not(target_arch = "wasm32")))]
+pub use http_client::HttpClientProxy;
+
+#[cfg(all(feature = "passthrough", not(target_arch = "wasm32")))]
+mod http_client {
+    use super::{
+        strip_hop_by_hop_headers, ProxyError, ProxyHeaders, ProxyRequest,
+        ProxyResponse, UpstreamProxy,
+    };
+    use futures::{future::BoxFuture, StreamExt};
+    use std::{sync::OnceLock, time::Duration};
+
+    /// An [`UpstreamProxy`] that forwards requests to an HTTP server with
+    /// [`reqwest`], keeping a pool of connections to it.
+    ///
+    /// ```rust
+    /// use leptos_router::{HttpClientProxy, NestedRoute, ProxyHeaders};
+    /// use std::{sync::Arc, time::Duration};
+    ///
+    /// let legacy = HttpClientProxy::new("http://127.0.0.1:8080")
+    ///     .connect_timeout(Duration::from_secs(2))
+    ///     .pool_size(16)
+    ///     .headers(ProxyHeaders::default().strip("cookie"));
+    /// let route = NestedRoute::passthrough(Arc::new(legacy));
+    /// ```
+    #[derive(Debug)]
+    pub struct HttpClientProxy {
+        upstream: String,
+        connect_timeout: Duration,
+        pool_size: usize,
+        headers: ProxyHeaders,
+        client: OnceLock<Result<reqwest::Client, ProxyError>>,
+    }
+
+    impl HttpClientProxy {
+        /// Forwards requests to the server at `upstream`, like
+        /// `http://127.0.0.1:8080`, with the path and query of each request
+        /// appended to it.
+        pub fn new(upstream: impl Into<String>) -> Self {
+            Self {
+                upstream: upstream.into().trim_end_matches('/').to_string(),
+                connect_timeout: Duration::from_secs(10),
+                pool_size: 32,
+                headers: ProxyHeaders::default(),
+                client: OnceLock::new(),
+            }
+        }
+
+        /// Sets how long to wait for a connection to the upstream, which is 10
+        /// seconds by default.
+        pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+            self.connect_timeout = timeout;
+            self
+        }
+
+        /// Sets how many idle connections to the upstream are kept open, which
+        /// is 32 by default.
+        pub fn pool_size(mut self, size: usize) -> Self {
+            self.pool_size = size;
+            self
+        }
+
+        /// Sets which headers are added to, or removed from, the forwarded
+        /// requests.
+        pub fn headers(mut self, headers: ProxyHeaders) -> Self {
+            self.headers = headers;
+            self
+        }
+
+        fn client(&self) -> Result<reqwest::Client, ProxyError> {
+            self.client
+                .get_or_init(|| {
+                    reqwest::Client::builder()
+                        .connect_timeout(self.connect_timeout)
+                        .pool_max_idle_per_host(self.pool_size)
+                        .redirect(reqwest::redirect::Policy::none())
+                        .build()
+                        .map_err(|error| ProxyError::Connect(error.to_string()))
+                })
+                .clone()
+        }
+    }
+
+    fn proxy_error(error: reqwest::Error) -> ProxyError {
+        if error.is_timeout() {
+            ProxyError::Timeout
+        } else if error.is_connect() {
+            ProxyError::Connect(error.to_string())
+        } else {
+            ProxyError::Request(error.to_string())
+        }
+    }
+
+    impl UpstreamProxy for HttpClientProxy {
+        fn forward(
+            &self,
+            request: ProxyRequest,
+        ) -> BoxFuture<'static, Result<ProxyResponse, ProxyError>> {
+            let ProxyRequest {
+                method,
+                path_and_query,
+                mut headers,
+                client_ip,
+                body,
+            } = request;
+            // a request without either of these has no body, which must not be
+            // sent chunked
+            let has_body = headers.iter().any(|(name, _)| {
+                name.eq_ignore_ascii_case("content-length")
+                    || name.eq_ignore_ascii_case("transfer-encoding")
+            });
+            self.headers.apply(&mut headers, client_ip);
+            headers.retain(|(name, _)| !name.eq_ignore_ascii_case("host"));
+
+            let url = format!("{}{path_and_query}", self.upstream);
+            let client = self.client();
+            Box::pin(async move {
+                let method = reqwest::Method::from_bytes(method.as_bytes())
+                    .map_err(|error| ProxyError::Request(error.to_string()))?;
+                let mut upstream = client?.request(method, url);
+                for (name, value) in headers {
+                    upstream = upstream.header(name, value);
+                }
+                if has_body {
+                    upstream = upstream.body(reqwest::Body::wrap_stream(body));
+                }
+
+                let response = upstream.send().await.map_err(proxy_error)?;
+                let mut headers = response
+                    .headers()
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        Some((
+                            name.to_string(),
+                            value.to_str().ok()?.to_string(),
+                        ))
+                    })
+                    .collect();
+                strip_hop_by_hop_headers(&mut headers);
+                Ok(ProxyResponse {
+                    status: response.status().as_u16(),
+                    headers,
+                    body: response
+                        .bytes_stream()
+                        .map(|chunk| {
+                            chunk
+                                .map(|bytes| bytes.to_vec())
+                                .map_err(proxy_error)
+                        })
+                        .boxed(),
+                })
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProxyHeaders;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn forwarded_requests_identify_the_client() {
+        let mut headers = [
+            ("Host", "example.com"),
+            ("Connection", "keep-alive, x-trace"),
+            ("X-Trace", "1"),
+            ("X-Forwarded-For", "10.0.0.1"),
+            ("Cookie", "session=secret"),
+            ("Accept", "text/html"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .to_vec();
+        ProxyHeaders::default()
+            .strip("cookie")
+            .add("x-app", "leptos")
+            .apply(
+                &mut headers,
+                Some(IpAddr::V4(Ipv4Addr::new(203, 0, 113, 7))),
+            );
+        assert_eq!(
+            headers,
+            [
+                ("Host", "example.com"),
+                ("Accept", "text/html"),
+                ("x-forwarded-for", "10.0.0.1, 203.0.113.7"),
+                ("x-real-ip", "203.0.113.7"),
+                ("x-app", "leptos"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+    }
+}
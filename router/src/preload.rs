@@ -0,0 +1,152 @@
+use std::{borrow::Cow, fmt};
+
+/// What kind of asset a [`PreloadLink`] is, which is its `as` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PreloadAs {
+    /// A JavaScript or WebAssembly module.
+    Script,
+    /// A stylesheet.
+    Style,
+    /// A font, which is always loaded in CORS mode.
+    Font,
+    /// An image.
+    Image,
+    /// Data loaded with `fetch()`, which is always loaded in CORS mode.
+    Fetch,
+}
+
+impl PreloadAs {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PreloadAs::Script => "script",
+            PreloadAs::Style => "style",
+            PreloadAs::Font => "font",
+            PreloadAs::Image => "image",
+            PreloadAs::Fetch => "fetch",
+        }
+    }
+
+    /// Browsers only use preloaded fonts and fetches that were requested in
+    /// CORS mode.
+    fn is_cross_origin(&self) -> bool {
+        matches!(self, PreloadAs::Font | PreloadAs::Fetch)
+    }
+}
+
+/// An asset that a route needs, which the browser can start loading before it
+/// has parsed the page, set with
+/// [`NestedRoute::preload`](crate::NestedRoute::preload).
+///
+/// The server integrations send the assets of the matched route in a `Link`
+/// header, which browsers act on as soon as they receive the headers, and which
+/// proxies and CDNs can send ahead of the response as `103 Early Hints`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PreloadLink {
+    href: Cow<'static, str>,
+    kind: PreloadAs,
+}
+
+impl PreloadLink {
+    /// Preloads the asset at `href`, which is of the given kind.
+    pub fn new(href: impl Into<Cow<'static, str>>, kind: PreloadAs) -> Self {
+        Self {
+            href: href.into(),
+            kind,
+        }
+    }
+
+    /// Preloads the script at `href`.
+    pub fn script(href: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(href, PreloadAs::Script)
+    }
+
+    /// Preloads the stylesheet at `href`.
+    pub fn style(href: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(href, PreloadAs::Style)
+    }
+
+    /// Preloads the font at `href`.
+    pub fn font(href: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(href, PreloadAs::Font)
+    }
+
+    /// Preloads the image at `href`.
+    pub fn image(href: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(href, PreloadAs::Image)
+    }
+
+    /// The URL of the asset.
+    pub fn href(&self) -> &str {
+        &self.href
+    }
+
+    /// What kind of asset this is.
+    pub fn kind(&self) -> PreloadAs {
+        self.kind
+    }
+
+    /// The equivalent `<link rel="preload">` tag, for the document itself.
+    pub fn to_html(&self) -> String {
+        let href = self.href.replace('&', "&amp;").replace('"', "&quot;");
+        format!(
+            r#"<link rel="preload" href="{href}" as="{}"{}>"#,
+            self.kind.as_str(),
+            if self.kind.is_cross_origin() {
+                " crossorigin"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+impl fmt::Display for PreloadLink {
+    /// Formats this as one link of a `Link` header.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}>; rel=preload; as={}", self.href, self.kind.as_str())?;
+        if self.kind.is_cross_origin() {
+            f.write_str("; crossorigin")?;
+        }
+        Ok(())
+    }
+}
+
+/// The value of the `Link` header that preloads the given assets, or `None` if
+/// there are none.
+pub fn preload_link_header(links: &[PreloadLink]) -> Option<String> {
+    if links.is_empty() {
+        return None;
+    }
+    Some(
+        links
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{preload_link_header, PreloadLink};
+
+    #[test]
+    fn links_are_joined_into_one_header() {
+        assert_eq!(preload_link_header(&[]), None);
+        assert_eq!(
+            preload_link_header(&[
+                PreloadLink::style("/pkg/app.css"),
+                PreloadLink::font("/fonts/inter.woff2"),
+            ])
+            .as_deref(),
+            Some(
+                "</pkg/app.css>; rel=preload; as=style, \
+                 </fonts/inter.woff2>; rel=preload; as=font; crossorigin"
+            )
+        );
+        assert_eq!(
+            PreloadLink::image("/hero.webp?w=1&h=2").to_html(),
+            r#"<link rel="preload" href="/hero.webp?w=1&amp;h=2" as="image">"#
+        );
+    }
+}
@@ -0,0 +1,419 @@
+use std::{collections::HashMap, fmt};
+use thiserror::Error;
+
+/// The type that the value of a query param must have, in a [`QuerySchema`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueryType {
+    /// Any string.
+    String,
+    /// An integer between `min` and `max`, inclusive.
+    Integer {
+        /// The smallest allowed value.
+        min: i64,
+        /// The largest allowed value.
+        max: i64,
+    },
+    /// One of the given strings.
+    Enum(&'static [&'static str]),
+}
+
+/// Describes the query params that requests to a route can have.
+///
+/// Used with
+/// [`NestedRoute::validate_query`](crate::NestedRoute::validate_query).
+///
+/// ```rust
+/// use leptos_router::{QuerySchema, QueryType};
+///
+/// let schema = QuerySchema::new()
+///     .required("search", QueryType::String)
+///     .optional("sort", QueryType::Enum(&["asc", "desc"]))
+///     .optional("limit", QueryType::Integer { min: 1, max: 100 });
+///
+/// let query = schema.validate("search=shoes&limit=20").unwrap();
+/// assert_eq!(query.get_str("search"), Some("shoes"));
+/// assert_eq!(query.get_integer("limit"), Some(20));
+/// assert_eq!(query.get_str("sort"), None);
+///
+/// assert!(schema.validate("sort=up").is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuerySchema {
+    params: Vec<QueryParam>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct QueryParam {
+    name: &'static str,
+    ty: QueryType,
+    required: bool,
+}
+
+impl QuerySchema {
+    /// Creates a schema that allows any query.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires every request to have the query param `name`, with a value of
+    /// type `ty`.
+    pub fn required(self, name: &'static str, ty: QueryType) -> Self {
+        self.param(name, ty, true)
+    }
+
+    /// Allows requests to have the query param `name`, with a value of type
+    /// `ty`.
+    pub fn optional(self, name: &'static str, ty: QueryType) -> Self {
+        self.param(name, ty, false)
+    }
+
+    fn param(
+        mut self,
+        name: &'static str,
+        ty: QueryType,
+        required: bool,
+    ) -> Self {
+        let param = QueryParam { name, ty, required };
+        match self.params.iter_mut().find(|param| param.name == name) {
+            Some(existing) => *existing = param,
+            None => self.params.push(param),
+        }
+        self
+    }
+
+    /// Requires every request to have the query param `name`, keeping its type
+    /// if it is already in the schema, or allowing any string otherwise.
+    pub(crate) fn require(mut self, name: &'static str) -> Self {
+        match self.params.iter_mut().find(|param| param.name == name) {
+            Some(existing) => {
+                existing.required = true;
+                self
+            }
+            None => self.required(name, QueryType::String),
+        }
+    }
+
+    /// Requires every param that `other` requires, as
+    /// [`require`](Self::require) does.
+    pub(crate) fn require_all(self, other: &QuerySchema) -> Self {
+        other
+            .params
+            .iter()
+            .filter(|param| param.required)
+            .fold(self, |schema, param| schema.require(param.name))
+    }
+
+    /// Adds the params of `other` to this schema, replacing any params with the
+    /// same name.
+    pub(crate) fn extend(&mut self, other: QuerySchema) {
+        for QueryParam { name, ty, required } in other.params {
+            *self = std::mem::take(self).param(name, ty, required);
+        }
+    }
+
+    /// Validates a query string (without the leading `?`) against this schema.
+    ///
+    /// A param that appears more than once has its first value, and a param
+    /// with an empty value is treated as missing. Params that are not in the
+    /// schema are ignored. Returns every error in the query, rather than just
+    /// the first.
+    pub fn validate(&self, query: &str) -> Result<ValidatedQuery, QueryErrors> {
+        let pairs = query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .collect::<Vec<_>>();
+
+        let mut values = HashMap::new();
+        let mut errors = Vec::new();
+        for QueryParam { name, ty, required } in &self.params {
+            let param = *name;
+            let value = pairs
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| decode(value))
+                .filter(|value| !value.is_empty());
+            let Some(value) = value else {
+                if *required {
+                    errors.push(QueryError::Missing { param });
+                }
+                continue;
+            };
+
+            let value = match *ty {
+                QueryType::String => Ok(QueryValue::String(value)),
+                QueryType::Integer { min, max } => match value.parse::<i64>() {
+                    Ok(n) if (min..=max).contains(&n) => {
+                        Ok(QueryValue::Integer(n))
+                    }
+                    Ok(_) => Err(QueryError::OutOfRange { param, min, max }),
+                    Err(_) => Err(QueryError::NotAnInteger { param }),
+                },
+                QueryType::Enum(allowed) => {
+                    if allowed.contains(&value.as_str()) {
+                        Ok(QueryValue::String(value))
+                    } else {
+                        Err(QueryError::NotAllowed { param, allowed })
+                    }
+                }
+            };
+            match value {
+                Ok(value) => {
+                    values.insert(param, value);
+                }
+                Err(error) => errors.push(error),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(ValidatedQuery(values))
+        } else {
+            Err(QueryErrors(errors))
+        }
+    }
+}
+
+fn decode(value: &str) -> String {
+    let value = value.replace('+', " ");
+    if !value.contains('%') {
+        return value;
+    }
+
+    #[cfg(feature = "ssr")]
+    {
+        percent_encoding::percent_decode_str(&value)
+            .decode_utf8_lossy()
+            .into_owned()
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        crate::location::Url::unescape(&value)
+    }
+}
+
+/// The value of a query param that has been validated against a
+/// [`QuerySchema`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum QueryValue {
+    /// The value of a [`QueryType::String`] or [`QueryType::Enum`] param.
+    String(String),
+    /// The value of a [`QueryType::Integer`] param.
+    Integer(i64),
+}
+
+/// The query params of a request that matched a [`QuerySchema`].
+///
+/// This is provided via context to the views of a route with
+/// [`NestedRoute::validate_query`](crate::NestedRoute::validate_query), and its
+/// nested routes. It only contains the params that are in the schema, and that
+/// the request has.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidatedQuery(HashMap<&'static str, QueryValue>);
+
+impl ValidatedQuery {
+    /// The value of the param `name`, if it is present.
+    pub fn get(&self, name: &str) -> Option<&QueryValue> {
+        self.0.get(name)
+    }
+
+    /// The value of the string or enum param `name`, if it is present.
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        match self.get(name)? {
+            QueryValue::String(value) => Some(value),
+            QueryValue::Integer(_) => None,
+        }
+    }
+
+    /// The value of the integer param `name`, if it is present.
+    pub fn get_integer(&self, name: &str) -> Option<i64> {
+        match self.get(name)? {
+            QueryValue::Integer(value) => Some(*value),
+            QueryValue::String(_) => None,
+        }
+    }
+}
+
+/// A way in which a query param does not match a [`QuerySchema`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    /// A required param is missing, or empty.
+    #[error("missing required query param `{param}`")]
+    Missing {
+        /// The name of the param.
+        param: &'static str,
+    },
+    /// The value of an integer param is not an integer.
+    #[error("query param `{param}` must be an integer")]
+    NotAnInteger {
+        /// The name of the param.
+        param: &'static str,
+    },
+    /// The value of an integer param is outside of its range.
+    #[error("query param `{param}` must be between {min} and {max}")]
+    OutOfRange {
+        /// The name of the param.
+        param: &'static str,
+        /// The smallest allowed value.
+        min: i64,
+        /// The largest allowed value.
+        max: i64,
+    },
+    /// The value of an enum param is not one of its allowed values.
+    #[error("query param `{param}` must be one of: {}", allowed.join(", "))]
+    NotAllowed {
+        /// The name of the param.
+        param: &'static str,
+        /// The allowed values.
+        allowed: &'static [&'static str],
+    },
+}
+
+impl QueryError {
+    /// The name of the param this error is about.
+    pub fn param(&self) -> &'static str {
+        match self {
+            QueryError::Missing { param }
+            | QueryError::NotAnInteger { param }
+            | QueryError::OutOfRange { param, .. }
+            | QueryError::NotAllowed { param, .. } => param,
+        }
+    }
+}
+
+/// Every way in which a query does not match a [`QuerySchema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryErrors(pub Vec<QueryError>);
+
+impl QueryErrors {
+    /// The body of the `400 Bad Request` response to a request whose query is
+    /// invalid, like
+    /// `{"errors":[{"param":"limit","message":"query param `limit` must be an integer"}]}`.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from(r#"{"errors":["#);
+        for (idx, error) in self.0.iter().enumerate() {
+            if idx > 0 {
+                json.push(',');
+            }
+            json.push_str(r#"{"param":"#);
+            push_json_string(&mut json, error.param());
+            json.push_str(r#","message":"#);
+            push_json_string(&mut json, &error.to_string());
+            json.push('}');
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+fn push_json_string(json: &mut String, s: &str) {
+    json.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => json.push_str(r#"\""#),
+            '\\' => json.push_str(r"\\"),
+            '\n' => json.push_str(r"\n"),
+            '\r' => json.push_str(r"\r"),
+            '\t' => json.push_str(r"\t"),
+            ch if ch.is_control() => {
+                json.push_str(&format!(r"\u{:04x}", ch as u32))
+            }
+            ch => json.push(ch),
+        }
+    }
+    json.push('"');
+}
+
+impl fmt::Display for QueryErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, error) in self.0.iter().enumerate() {
+            if idx > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for QueryErrors {}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueryError, QueryErrors, QuerySchema, QueryType, QueryValue};
+
+    fn schema() -> QuerySchema {
+        QuerySchema::new()
+            .required("search", QueryType::String)
+            .optional("sort", QueryType::Enum(&["asc", "desc"]))
+            .optional("limit", QueryType::Integer { min: 1, max: 100 })
+    }
+
+    #[test]
+    fn validates_query() {
+        let query = schema()
+            .validate("search=red+shoes&sort=desc&limit=50&other=1")
+            .unwrap();
+        assert_eq!(query.get_str("search"), Some("red shoes"));
+        assert_eq!(query.get_str("sort"), Some("desc"));
+        assert_eq!(query.get("limit"), Some(&QueryValue::Integer(50)));
+        assert_eq!(query.get_str("limit"), None);
+        assert_eq!(query.get("other"), None);
+
+        let query = schema().validate("search=shoes&search=hats").unwrap();
+        assert_eq!(query.get_str("search"), Some("shoes"));
+        assert_eq!(query.get_integer("limit"), None);
+    }
+
+    #[test]
+    fn reports_every_error() {
+        assert_eq!(
+            schema().validate("search=&sort=up&limit=500"),
+            Err(QueryErrors(vec![
+                QueryError::Missing { param: "search" },
+                QueryError::NotAllowed {
+                    param: "sort",
+                    allowed: &["asc", "desc"]
+                },
+                QueryError::OutOfRange {
+                    param: "limit",
+                    min: 1,
+                    max: 100
+                },
+            ]))
+        );
+        assert_eq!(
+            schema().validate("search=shoes&limit=ten"),
+            Err(QueryErrors(vec![QueryError::NotAnInteger {
+                param: "limit"
+            }]))
+        );
+    }
+
+    #[test]
+    fn errors_serialize_to_json() {
+        let errors = schema().validate("limit=ten").unwrap_err();
+        assert_eq!(
+            errors.to_json(),
+            r#"{"errors":[{"param":"search","message":"missing required query param `search`"},{"param":"limit","message":"query param `limit` must be an integer"}]}"#
+        );
+    }
+
+    #[test]
+    fn later_params_replace_earlier_ones() {
+        let mut schema = schema().optional("search", QueryType::String);
+        assert!(schema.validate("").is_ok());
+
+        schema.extend(
+            QuerySchema::new()
+                .required("limit", QueryType::Integer { min: 1, max: 10 }),
+        );
+        assert_eq!(
+            schema.validate("limit=50"),
+            Err(QueryErrors(vec![QueryError::OutOfRange {
+                param: "limit",
+                min: 1,
+                max: 10
+            }]))
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use crate::MatchRequest;
+use std::{collections::HashMap, fmt, ops::Deref, sync::Arc};
+
+/// A region of a geo-distributed deployment, like `"us-east-1"` or `"iad"`.
+///
+/// The [region hint](crate::NestedRoute::with_region_hint) of a route provides
+/// the region of each request via context to its view and to all of its nested
+/// routes, so that their data loaders can scope their queries to the replica in
+/// that region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Region(pub &'static str);
+
+impl Region {
+    /// The name of the region.
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl Deref for Region {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// Finds the region a request should be served from, in an app that is deployed
+/// to several.
+///
+/// Used with
+/// [`NestedRoute::with_region_hint`](crate::NestedRoute::with_region_hint). Any
+/// function that takes a [`MatchRequest`] and returns an `Option<Region>` is a
+/// hint, and [`CfWorkerRegionHint`] and [`FlyRegionHint`] read the headers set
+/// by Cloudflare and Fly.io.
+pub trait RegionHint: Send + Sync {
+    /// The region of the request, or `None` if it is not known.
+    fn region(&self, req: &MatchRequest<'_>) -> Option<Region>;
+}
+
+impl<F> RegionHint for F
+where
+    F: Fn(&MatchRequest<'_>) -> Option<Region> + Send + Sync,
+{
+    fn region(&self, req: &MatchRequest<'_>) -> Option<Region> {
+        self(req)
+    }
+}
+
+/// A [`RegionHint`] for Cloudflare, which finds the region of a request from
+/// the country of its client, in the `CF-IPCountry` header.
+///
+/// ```rust
+/// use leptos_router::{CfWorkerRegionHint, MatchRequest, Region, RegionHint};
+///
+/// let hint = CfWorkerRegionHint::new(Region("us-east-1"))
+///     .country("DE", Region("eu-central-1"))
+///     .country("FR", Region("eu-central-1"));
+///
+/// let headers = [("cf-ipcountry", "DE")];
+/// let req = MatchRequest::new("/").headers(&headers);
+/// assert_eq!(hint.region(&req), Some(Region("eu-central-1")));
+///
+/// let headers = [("cf-ipcountry", "BR")];
+/// let req = MatchRequest::new("/").headers(&headers);
+/// assert_eq!(hint.region(&req), Some(Region("us-east-1")));
+///
+/// assert_eq!(hint.region(&MatchRequest::new("/")), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CfWorkerRegionHint {
+    countries: HashMap<String, Region>,
+    fallback: Region,
+}
+
+impl CfWorkerRegionHint {
+    /// The header that Cloudflare sends the country code of the client in.
+    pub const HEADER: &'static str = "cf-ipcountry";
+
+    /// Serves requests from `fallback`, unless their country has a region of
+    /// its own.
+    pub fn new(fallback: Region) -> Self {
+        Self {
+            countries: HashMap::new(),
+            fallback,
+        }
+    }
+
+    /// Serves requests from the country with the ISO 3166-1 alpha-2 `code`,
+    /// like `DE`, from `region`.
+    pub fn country(mut self, code: &str, region: Region) -> Self {
+        self.countries.insert(code.to_ascii_uppercase(), region);
+        self
+    }
+}
+
+impl RegionHint for CfWorkerRegionHint {
+    fn region(&self, req: &MatchRequest<'_>) -> Option<Region> {
+        let country = req.header(Self::HEADER)?.trim().to_ascii_uppercase();
+        // `XX` is an unknown country, and `T1` a Tor exit node, which use the
+        // fallback
+        Some(
+            self.countries
+                .get(&country)
+                .copied()
+                .unwrap_or(self.fallback),
+        )
+    }
+}
+
+/// A [`RegionHint`] for Fly.io, which finds the region of a request from the
+/// region of the edge that received it, in the `Fly-Region` header.
+///
+/// Only the `regions` the app is deployed to are used, so that a request
+/// received in another region has no hint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlyRegionHint {
+    regions: Vec<Region>,
+}
+
+impl FlyRegionHint {
+    /// The header that Fly.io sends the region of the edge in.
+    pub const HEADER: &'static str = "fly-region";
+
+    /// Finds the regions in `regions`, like `Region("iad")`.
+    pub fn new(regions: impl IntoIterator<Item = Region>) -> Self {
+        Self {
+            regions: regions.into_iter().collect(),
+        }
+    }
+}
+
+impl RegionHint for FlyRegionHint {
+    fn region(&self, req: &MatchRequest<'_>) -> Option<Region> {
+        let region = req.header(Self::HEADER)?.trim();
+        self.regions
+            .iter()
+            .find(|known| known.eq_ignore_ascii_case(region))
+            .copied()
+    }
+}
+
+/// How a route finds the region of its requests.
+#[derive(Clone)]
+pub(crate) struct RegionHintConfig(Arc<dyn RegionHint>);
+
+impl RegionHintConfig {
+    pub(crate) fn new(hint: Arc<dyn RegionHint>) -> Self {
+        Self(hint)
+    }
+
+    pub(crate) fn region(&self, req: &MatchRequest<'_>) -> Option<Region> {
+        self.0.region(req)
+    }
+}
+
+impl fmt::Debug for RegionHintConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RegionHintConfig")
+    }
+}
+
+impl PartialEq for RegionHintConfig {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for RegionHintConfig {}
+
+#[cfg(test)]
+mod tests {
+    use super::{FlyRegionHint, Region, RegionHint};
+    use crate::MatchRequest;
+
+    #[test]
+    fn fly_hints_only_name_known_regions() {
+        let hint = FlyRegionHint::new([Region("iad"), Region("lhr")]);
+        let region = |value: &str| {
+            let headers = [("Fly-Region", value)];
+            hint.region(&MatchRequest::new("/").headers(&headers))
+        };
+        assert_eq!(region("lhr"), Some(Region("lhr")));
+        assert_eq!(region(" IAD "), Some(Region("iad")));
+        assert_eq!(region("syd"), None);
+        assert_eq!(hint.region(&MatchRequest::new("/")), None);
+    }
+}
@@ -0,0 +1,255 @@
+use serde_json::Value;
+use std::fmt;
+
+/// A way in which a JSON body does not match a schema, like the one documented
+/// with [`NestedRoute::response_schema`](crate::NestedRoute::response_schema).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaMismatch {
+    path: String,
+    expected: String,
+}
+
+impl SchemaMismatch {
+    /// Where in the body the mismatch is, as a JSON pointer like `/items/0/id`,
+    /// which is empty for the whole body.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// What the schema expected to find there.
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+}
+
+impl fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "at `{path}`: expected {}", self.expected)
+    }
+}
+
+/// Checks `value` against a JSON `schema`, returning every way in which it does
+/// not match.
+///
+/// This understands the keywords that describe the shape of a response: `type`
+/// (a type or a list of them), `enum`, `const`, `properties`, `required`,
+/// `additionalProperties` and `items`. Other keywords, like `$ref` or `format`,
+/// are ignored, so a value is only ever reported as a mismatch if it is
+/// certainly wrong.
+///
+/// ```rust
+/// use leptos_router::validate_response_schema;
+/// use serde_json::json;
+///
+/// let schema = json!({
+///     "type": "object",
+///     "properties": { "id": { "type": "integer" } },
+///     "required": ["id", "name"],
+/// });
+/// let mismatches =
+///     validate_response_schema(&schema, &json!({ "id": "1" }));
+/// let mismatches =
+///     mismatches.iter().map(ToString::to_string).collect::<Vec<_>>();
+/// assert_eq!(
+///     mismatches,
+///     [
+///         "at `/`: expected a property `name`",
+///         "at `/id`: expected a value of type integer",
+///     ]
+/// );
+/// ```
+pub fn validate_response_schema(
+    schema: &Value,
+    value: &Value,
+) -> Vec<SchemaMismatch> {
+    let mut mismatches = Vec::new();
+    validate_at(schema, value, &mut String::new(), &mut mismatches);
+    mismatches
+}
+
+fn validate_at(
+    schema: &Value,
+    value: &Value,
+    path: &mut String,
+    mismatches: &mut Vec<SchemaMismatch>,
+) {
+    let Value::Object(schema) = schema else {
+        // `true` and `{}` allow anything; `false` allows nothing
+        if schema == &Value::Bool(false) {
+            mismatches.push(mismatch(path, "nothing".to_string()));
+        }
+        return;
+    };
+    let mut mismatch_at = |expected: String| {
+        mismatches.push(mismatch(path, expected));
+    };
+
+    if let Some(ty) = schema.get("type") {
+        let types = match ty {
+            Value::Array(types) => {
+                types.iter().filter_map(Value::as_str).collect()
+            }
+            ty => ty.as_str().into_iter().collect::<Vec<_>>(),
+        };
+        if !types.is_empty() && !types.iter().any(|ty| has_type(value, ty)) {
+            mismatch_at(format!("a value of type {}", types.join(" or ")));
+            // the other keywords would only repeat the same mismatch
+            return;
+        }
+    }
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            mismatch_at(format!("one of {}", Value::Array(allowed.clone())));
+        }
+    }
+    if let Some(allowed) = schema.get("const") {
+        if allowed != value {
+            mismatch_at(allowed.to_string());
+        }
+    }
+
+    if let Value::Object(object) = value {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    mismatch_at(format!("a property `{name}`"));
+                }
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (name, property) in object {
+            let schema = match properties.and_then(|props| props.get(name)) {
+                Some(schema) => schema,
+                None => match schema.get("additionalProperties") {
+                    Some(schema) => schema,
+                    None => continue,
+                },
+            };
+            let len = path.len();
+            path.push('/');
+            push_pointer_token(path, name);
+            validate_at(schema, property, path, mismatches);
+            path.truncate(len);
+        }
+    } else if let (Value::Array(items), Some(schema)) =
+        (value, schema.get("items"))
+    {
+        for (idx, item) in items.iter().enumerate() {
+            let len = path.len();
+            path.push('/');
+            path.push_str(&idx.to_string());
+            validate_at(schema, item, path, mismatches);
+            path.truncate(len);
+        }
+    }
+}
+
+fn mismatch(path: &str, expected: String) -> SchemaMismatch {
+    SchemaMismatch {
+        path: path.to_string(),
+        expected,
+    }
+}
+
+fn has_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64()
+                || value.is_u64()
+                || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        // a type this does not know about cannot be checked
+        _ => true,
+    }
+}
+
+/// Escapes `name` as a JSON pointer token.
+fn push_pointer_token(path: &mut String, name: &str) {
+    for c in name.chars() {
+        match c {
+            '~' => path.push_str("~0"),
+            '/' => path.push_str("~1"),
+            c => path.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_response_schema;
+    use serde_json::json;
+
+    fn mismatches(
+        schema: serde_json::Value,
+        value: serde_json::Value,
+    ) -> Vec<(String, String)> {
+        validate_response_schema(&schema, &value)
+            .into_iter()
+            .map(|m| (m.path().to_string(), m.expected().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn nested_mismatches_are_reported_with_their_paths() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "items": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "id": { "type": "integer" },
+                            "status": { "enum": ["open", "closed"] },
+                        },
+                        "required": ["id"],
+                    },
+                },
+                "next": { "type": ["string", "null"] },
+            },
+            "additionalProperties": false,
+        });
+
+        assert_eq!(
+            mismatches(
+                schema.clone(),
+                json!({ "items": [{ "id": 1.0, "status": "open" }], "next": null })
+            ),
+            []
+        );
+        assert_eq!(
+            mismatches(
+                schema,
+                json!({
+                    "items": [{ "id": 1 }, { "status": "lost" }, 3],
+                    "next": 2,
+                    "a/b": true,
+                })
+            ),
+            [
+                ("/a~1b".to_string(), "nothing".to_string()),
+                ("/items/1".to_string(), "a property `id`".to_string()),
+                (
+                    "/items/1/status".to_string(),
+                    r#"one of ["open","closed"]"#.to_string()
+                ),
+                ("/items/2".to_string(), "a value of type object".to_string()),
+                (
+                    "/next".to_string(),
+                    "a value of type string or null".to_string()
+                ),
+            ]
+        );
+    }
+}
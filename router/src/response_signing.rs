@@ -0,0 +1,309 @@
+use crate::Secret;
+use secrecy::ExposeSecret;
+use std::fmt;
+use thiserror::Error;
+
+/// The response header that the server integrations send the signature of a
+/// response in.
+pub const SIGNATURE_HEADER: &str = "x-signature";
+
+/// The path that the server integrations serve the JSON Web Key Set of the
+/// public keys that responses are signed with at.
+pub const JWKS_PATH: &str = "/.well-known/jwks.json";
+
+/// The algorithm used to sign the responses of a route, named as in
+/// [JWA](https://www.rfc-editor.org/rfc/rfc7518#section-3.1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SigningAlgorithm {
+    /// HMAC-SHA256 (`HS256`), with a secret shared with the clients.
+    HmacSha256,
+    /// HMAC-SHA512 (`HS512`), with a secret shared with the clients.
+    HmacSha512,
+    /// RSASSA-PKCS1-v1_5 with SHA-256 (`RS256`), with a PKCS#8 RSA private key.
+    RsaSha256,
+    /// ECDSA on the P-256 curve with SHA-256 (`ES256`), with a PKCS#8 private
+    /// key.
+    EcdsaP256Sha256,
+}
+
+impl SigningAlgorithm {
+    /// The JWA name of the algorithm, like `ES256`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SigningAlgorithm::HmacSha256 => "HS256",
+            SigningAlgorithm::HmacSha512 => "HS512",
+            SigningAlgorithm::RsaSha256 => "RS256",
+            SigningAlgorithm::EcdsaP256Sha256 => "ES256",
+        }
+    }
+
+    /// Whether the algorithm signs with a private key, whose public key can be
+    /// published.
+    pub fn is_asymmetric(&self) -> bool {
+        matches!(
+            self,
+            SigningAlgorithm::RsaSha256 | SigningAlgorithm::EcdsaP256Sha256
+        )
+    }
+}
+
+/// Defines how the responses of a route are signed, set with
+/// [`NestedRoute::sign_response`](crate::NestedRoute::sign_response).
+///
+/// The server integrations sign the whole body of each response, and send the
+/// signature in the [`X-Signature`](SIGNATURE_HEADER) header, as
+/// `keyid="<key_id>", algorithm="<algorithm>", signature="<signature>"`, with
+/// the signature encoded as unpadded base64url. The public keys of the routes
+/// that are signed with RSA or ECDSA are served as a JSON Web Key Set at
+/// [`/.well-known/jwks.json`](JWKS_PATH).
+pub struct ResponseSigningConfig {
+    /// The algorithm used to sign the responses.
+    pub algorithm: SigningAlgorithm,
+    /// The ID of the key, which clients use to find its public key in the key
+    /// set.
+    pub key_id: &'static str,
+    /// The secret of an HMAC algorithm, or the DER-encoded PKCS#8 private key
+    /// of an RSA or ECDSA algorithm.
+    pub key: Secret<Vec<u8>>,
+}
+
+// `Secret<Vec<u8>>` is not `Clone`, so that secrets are not copied by accident
+impl Clone for ResponseSigningConfig {
+    fn clone(&self) -> Self {
+        Self {
+            algorithm: self.algorithm,
+            key_id: self.key_id,
+            key: Secret::new(self.key.expose_secret().clone()),
+        }
+    }
+}
+
+impl fmt::Debug for ResponseSigningConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseSigningConfig")
+            .field("algorithm", &self.algorithm)
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for ResponseSigningConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.algorithm == other.algorithm
+            && self.key_id == other.key_id
+            && self.key.expose_secret() == other.key.expose_secret()
+    }
+}
+
+impl Eq for ResponseSigningConfig {}
+
+/// Why a response could not be signed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SigningError {
+    /// The key is not a valid key for the algorithm.
+    #[error("the key `{0}` is not a valid key for its algorithm")]
+    InvalidKey(&'static str),
+    /// The signature could not be computed.
+    #[error("the response could not be signed with the key `{0}`")]
+    Failed(&'static str),
+}
+
+#[cfg(feature = "response_signing")]
+impl ResponseSigningConfig {
+    /// The value of the `X-Signature` header for a response with `body`.
+    pub fn sign(&self, body: &[u8]) -> Result<String, SigningError> {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+        let signature = self.signature(body)?;
+        Ok(format!(
+            "keyid=\"{}\", algorithm=\"{}\", signature=\"{}\"",
+            self.key_id,
+            self.algorithm.as_str(),
+            URL_SAFE_NO_PAD.encode(signature)
+        ))
+    }
+
+    /// The raw signature of `body`.
+    pub fn signature(&self, body: &[u8]) -> Result<Vec<u8>, SigningError> {
+        use hmac::{Hmac, Mac};
+        use ring::{rand::SystemRandom, signature};
+
+        let key = self.key.expose_secret();
+        let rng = SystemRandom::new();
+        match self.algorithm {
+            SigningAlgorithm::HmacSha256 => {
+                let mut mac = Hmac::<sha2::Sha256>::new_from_slice(key)
+                    .expect("HMAC can take a key of any size");
+                mac.update(body);
+                Ok(mac.finalize().into_bytes().to_vec())
+            }
+            SigningAlgorithm::HmacSha512 => {
+                let mut mac = Hmac::<sha2::Sha512>::new_from_slice(key)
+                    .expect("HMAC can take a key of any size");
+                mac.update(body);
+                Ok(mac.finalize().into_bytes().to_vec())
+            }
+            SigningAlgorithm::RsaSha256 => {
+                let key_pair = signature::RsaKeyPair::from_pkcs8(key)
+                    .map_err(|_| SigningError::InvalidKey(self.key_id))?;
+                let mut signature = vec![0; key_pair.public().modulus_len()];
+                key_pair
+                    .sign(
+                        &signature::RSA_PKCS1_SHA256,
+                        &rng,
+                        body,
+                        &mut signature,
+                    )
+                    .map_err(|_| SigningError::Failed(self.key_id))?;
+                Ok(signature)
+            }
+            SigningAlgorithm::EcdsaP256Sha256 => {
+                let key_pair = signature::EcdsaKeyPair::from_pkcs8(
+                    &signature::ECDSA_P256_SHA256_FIXED_SIGNING,
+                    key,
+                    &rng,
+                )
+                .map_err(|_| SigningError::InvalidKey(self.key_id))?;
+                key_pair
+                    .sign(&rng, body)
+                    .map(|signature| signature.as_ref().to_vec())
+                    .map_err(|_| SigningError::Failed(self.key_id))
+            }
+        }
+    }
+
+    /// The public key of an RSA or ECDSA key, as a JSON Web Key, or `None` for
+    /// the secret of an HMAC algorithm, which is never published.
+    pub fn jwk(&self) -> Result<Option<serde_json::Value>, SigningError> {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+        use ring::{
+            rand::SystemRandom,
+            rsa::PublicKeyComponents,
+            signature::{self, KeyPair},
+        };
+        use serde_json::json;
+
+        let key = self.key.expose_secret();
+        let mut jwk = match self.algorithm {
+            SigningAlgorithm::HmacSha256 | SigningAlgorithm::HmacSha512 => {
+                return Ok(None)
+            }
+            SigningAlgorithm::RsaSha256 => {
+                let key_pair = signature::RsaKeyPair::from_pkcs8(key)
+                    .map_err(|_| SigningError::InvalidKey(self.key_id))?;
+                let public =
+                    PublicKeyComponents::<Vec<u8>>::from(key_pair.public());
+                json!({
+                    "kty": "RSA",
+                    "n": URL_SAFE_NO_PAD.encode(public.n),
+                    "e": URL_SAFE_NO_PAD.encode(public.e),
+                })
+            }
+            SigningAlgorithm::EcdsaP256Sha256 => {
+                let key_pair = signature::EcdsaKeyPair::from_pkcs8(
+                    &signature::ECDSA_P256_SHA256_FIXED_SIGNING,
+                    key,
+                    &SystemRandom::new(),
+                )
+                .map_err(|_| SigningError::InvalidKey(self.key_id))?;
+                // an uncompressed point: `0x04`, then the coordinates
+                let (x, y) = key_pair.public_key().as_ref()[1..].split_at(32);
+                json!({
+                    "kty": "EC",
+                    "crv": "P-256",
+                    "x": URL_SAFE_NO_PAD.encode(x),
+                    "y": URL_SAFE_NO_PAD.encode(y),
+                })
+            }
+        };
+        jwk["kid"] = self.key_id.into();
+        jwk["alg"] = self.algorithm.as_str().into();
+        jwk["use"] = "sig".into();
+        Ok(Some(jwk))
+    }
+}
+
+/// The JSON Web Key Set of the public keys in `configs`, served by the server
+/// integrations at [`JWKS_PATH`], with one key for each key ID.
+///
+/// Keys that are not valid are left out, since the responses cannot be signed
+/// with them either.
+#[cfg(feature = "response_signing")]
+pub fn jwks<'a>(
+    configs: impl IntoIterator<Item = &'a ResponseSigningConfig>,
+) -> serde_json::Value {
+    let mut key_ids = Vec::new();
+    let keys = configs
+        .into_iter()
+        .filter(|config| {
+            let new = !key_ids.contains(&config.key_id);
+            key_ids.push(config.key_id);
+            new
+        })
+        .filter_map(|config| config.jwk().ok().flatten())
+        .collect::<Vec<_>>();
+    serde_json::json!({ "keys": keys })
+}
+
+#[cfg(all(test, feature = "response_signing"))]
+mod tests {
+    use super::{jwks, ResponseSigningConfig, Secret, SigningAlgorithm};
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use ring::{rand::SystemRandom, signature};
+
+    #[test]
+    fn ecdsa_signatures_verify_with_the_published_key() {
+        let pkcs8 = signature::EcdsaKeyPair::generate_pkcs8(
+            &signature::ECDSA_P256_SHA256_FIXED_SIGNING,
+            &SystemRandom::new(),
+        )
+        .unwrap();
+        let config = ResponseSigningConfig {
+            algorithm: SigningAlgorithm::EcdsaP256Sha256,
+            key_id: "primary",
+            key: Secret::new(pkcs8.as_ref().to_vec()),
+        };
+        let header = config.sign(b"{\"ok\":true}").unwrap();
+        let signature = header
+            .strip_prefix(r#"keyid="primary", algorithm="ES256", signature=""#)
+            .and_then(|rest| rest.strip_suffix('"'))
+            .unwrap();
+
+        let jwks = jwks([&config, &config]);
+        let keys = jwks["keys"].as_array().unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0]["kid"], "primary");
+        let coordinate = |name: &str| {
+            URL_SAFE_NO_PAD
+                .decode(keys[0][name].as_str().unwrap())
+                .unwrap()
+        };
+        let point = [vec![4], coordinate("x"), coordinate("y")].concat();
+        let public = signature::UnparsedPublicKey::new(
+            &signature::ECDSA_P256_SHA256_FIXED,
+            point,
+        );
+        let signature = URL_SAFE_NO_PAD.decode(signature).unwrap();
+        assert!(public.verify(b"{\"ok\":true}", &signature).is_ok());
+        assert!(public.verify(b"{\"ok\":false}", &signature).is_err());
+    }
+
+    #[test]
+    fn hmac_secrets_are_not_published() {
+        let config = ResponseSigningConfig {
+            algorithm: SigningAlgorithm::HmacSha256,
+            key_id: "shared",
+            key: Secret::new(b"It's a Secret to Everybody".to_vec()),
+        };
+        assert_eq!(config.jwk(), Ok(None));
+        // the example from GitHub's documentation on validating webhook
+        // deliveries
+        assert_eq!(
+            config.signature(b"Hello, World!").unwrap(),
+            crate::webhook::decode_hex(
+                "757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17"
+            )
+            .unwrap()
+        );
+    }
+}
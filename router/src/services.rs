@@ -0,0 +1,186 @@
+use reactive_graph::owner::use_context;
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt,
+    sync::Arc,
+};
+
+/// Resolves the services of an app, like database pools or API clients, by
+/// their type, so that views can use them without global statics, set with
+/// [`NestedRoute::with_service_locator`](crate::NestedRoute::with_service_locator)
+/// and read with [`use_service`].
+///
+/// A locator is looked up through a `dyn` pointer, so the method to implement
+/// is [`resolve_any`](ServiceLocator::resolve_any), which takes the [`TypeId`]
+/// of the service; [`resolve`](ServiceLocator::resolve) is the typed version of
+/// it.
+///
+/// [`ServiceMap`] is a locator for services that are created up front. A
+/// dependency injection container like [`shaku`](https://docs.rs/shaku) can be
+/// wrapped in a locator that answers for the interfaces it provides:
+///
+/// ```rust,ignore
+/// use leptos_router::ServiceLocator;
+/// use shaku::HasComponent;
+/// use std::any::{Any, TypeId};
+/// use std::sync::Arc;
+///
+/// struct Container(Arc<AppModule>);
+///
+/// impl ServiceLocator for Container {
+///     fn resolve_any(
+///         &self,
+///         ty: TypeId,
+///     ) -> Option<Arc<dyn Any + Send + Sync>> {
+///         if ty == TypeId::of::<Arc<dyn Database>>() {
+///             let db: Arc<dyn Database> = self.0.resolve();
+///             return Some(Arc::new(db));
+///         }
+///         None
+///     }
+/// }
+///
+/// // in a view
+/// let db = use_service::<Arc<dyn Database>>().expect("no database");
+/// ```
+pub trait ServiceLocator: Send + Sync + 'static {
+    /// The service of the type with the given ID, or `None` if this locator
+    /// does not provide one.
+    ///
+    /// The service must be an `Arc<T>` (behind the `dyn Any`) for the
+    /// [`TypeId`] of `T`.
+    fn resolve_any(&self, ty: TypeId) -> Option<Arc<dyn Any + Send + Sync>>;
+
+    /// The service of type `T`, or `None` if this locator does not provide one.
+    fn resolve<T>(&self) -> Option<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+        Self: Sized,
+    {
+        self.resolve_any(TypeId::of::<T>())?.downcast().ok()
+    }
+}
+
+impl<L> ServiceLocator for Arc<L>
+where
+    L: ServiceLocator + ?Sized,
+{
+    fn resolve_any(&self, ty: TypeId) -> Option<Arc<dyn Any + Send + Sync>> {
+        (**self).resolve_any(ty)
+    }
+}
+
+/// A [`ServiceLocator`] that holds one service of each type, which are created
+/// before they are inserted.
+///
+/// ```rust
+/// use leptos_router::{ServiceLocator, ServiceMap};
+/// use std::sync::Arc;
+///
+/// struct DbPool {
+///     url: String,
+/// }
+///
+/// let services = ServiceMap::new().with(DbPool {
+///     url: "postgres://localhost/app".into(),
+/// });
+/// let pool = services.resolve::<DbPool>().unwrap();
+/// assert_eq!(pool.url, "postgres://localhost/app");
+/// assert!(services.resolve::<String>().is_none());
+/// ```
+#[derive(Clone, Default)]
+pub struct ServiceMap(HashMap<TypeId, Arc<dyn Any + Send + Sync>>);
+
+impl ServiceMap {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `service`, replacing any service of the same type.
+    pub fn with<T>(self, service: T) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.with_arc(Arc::new(service))
+    }
+
+    /// Adds a service that is already shared, replacing any service of the same
+    /// type.
+    pub fn with_arc<T>(mut self, service: Arc<T>) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.0.insert(TypeId::of::<T>(), service);
+        self
+    }
+}
+
+impl ServiceLocator for ServiceMap {
+    fn resolve_any(&self, ty: TypeId) -> Option<Arc<dyn Any + Send + Sync>> {
+        self.0.get(&ty).cloned()
+    }
+}
+
+impl fmt::Debug for ServiceMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ServiceMap").field(&self.0.len()).finish()
+    }
+}
+
+/// The [`ServiceLocator`] of the closest route that set one, provided via
+/// context to its view and to all of its nested routes.
+#[derive(Clone)]
+pub(crate) struct RouteServices(pub(crate) Arc<dyn ServiceLocator>);
+
+/// The service of type `T` from the [`ServiceLocator`] of the closest route
+/// that [set one](crate::NestedRoute::with_service_locator), or `None` if there
+/// is no such route or its locator does not provide the service.
+pub fn use_service<T>() -> Option<Arc<T>>
+where
+    T: Send + Sync + 'static,
+{
+    use_context::<RouteServices>()?
+        .0
+        .resolve_any(TypeId::of::<T>())?
+        .downcast()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{use_service, RouteServices, ServiceMap};
+    use reactive_graph::owner::{provide_context, Owner};
+    use std::sync::Arc;
+
+    fn provide_services(services: ServiceMap) {
+        provide_context(RouteServices(Arc::new(services)));
+    }
+
+    #[test]
+    fn nested_locators_shadow_their_parents() {
+        let parent = Owner::new();
+        parent.with(|| {
+            provide_services(ServiceMap::new().with("parent").with(1_u32));
+            let child = parent.child();
+            child.with(|| {
+                provide_services(ServiceMap::new().with("child"));
+                assert_eq!(*use_service::<&str>().unwrap(), "child");
+                // the locator of the parent is not asked for what the child
+                // does not provide
+                assert!(use_service::<u32>().is_none());
+            });
+            assert_eq!(*use_service::<&str>().unwrap(), "parent");
+            assert_eq!(*use_service::<u32>().unwrap(), 1);
+        });
+    }
+
+    #[test]
+    fn there_are_no_services_outside_a_locator() {
+        Owner::new().with(|| {
+            assert!(use_service::<&str>().is_none());
+        });
+        assert!(use_service::<&str>().is_none());
+    }
+}
@@ -0,0 +1,121 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use tokio::sync::watch;
+
+/// Stops the server integrations from accepting new requests to a route once
+/// `signal` is set, so that the server can exit without cutting off a response
+/// in the middle.
+///
+/// Used with
+/// [`NestedRoute::graceful_shutdown`](crate::NestedRoute::graceful_shutdown).
+/// Once the signal is set, new requests to the route are answered with
+/// `503 Service Unavailable`, instead of being accepted and then failing
+/// mid-stream. The requests that were accepted before are left to complete, and
+/// are counted as in flight until their response bodies have been sent in full,
+/// so that the server can wait for them with [`drained`](Self::drained), or
+/// with the `drain_routes` of its integration.
+///
+/// ```rust
+/// use leptos_router::ShutdownSignal;
+/// use std::sync::{
+///     atomic::{AtomicBool, Ordering},
+///     Arc,
+/// };
+///
+/// let signal = Arc::new(AtomicBool::new(false));
+/// let shutdown = ShutdownSignal::new(Arc::clone(&signal));
+/// let request = shutdown.track_request();
+/// assert_eq!(shutdown.in_flight(), 1);
+///
+/// signal.store(true, Ordering::Relaxed);
+/// assert!(shutdown.is_shutting_down());
+/// drop(request);
+/// assert_eq!(shutdown.in_flight(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ShutdownSignal {
+    signal: Arc<AtomicBool>,
+    in_flight: Arc<watch::Sender<usize>>,
+}
+
+impl ShutdownSignal {
+    /// Shuts down the routes that use it once `signal` is set.
+    pub fn new(signal: Arc<AtomicBool>) -> Self {
+        Self {
+            signal,
+            in_flight: Arc::new(watch::Sender::new(0)),
+        }
+    }
+
+    /// Whether the signal is set, so that the routes should not accept new
+    /// requests.
+    pub fn is_shutting_down(&self) -> bool {
+        self.signal.load(Ordering::Relaxed)
+    }
+
+    /// The number of requests to the routes that have been accepted, and whose
+    /// responses have not been sent in full yet.
+    pub fn in_flight(&self) -> usize {
+        *self.in_flight.borrow()
+    }
+
+    /// Counts a request as in flight until the returned guard is dropped, which
+    /// the server integrations do once its response body has been sent.
+    pub fn track_request(&self) -> TrackedRequest {
+        self.in_flight.send_modify(|in_flight| *in_flight += 1);
+        TrackedRequest(Arc::clone(&self.in_flight))
+    }
+
+    /// Resolves once no request to the routes is in flight.
+    pub async fn drained(&self) {
+        let mut in_flight = self.in_flight.subscribe();
+        // the sender is kept alive by `self`, so this can only return once it
+        // is zero
+        _ = in_flight.wait_for(|in_flight| *in_flight == 0).await;
+    }
+}
+
+impl PartialEq for ShutdownSignal {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.signal, &other.signal)
+            && Arc::ptr_eq(&self.in_flight, &other.in_flight)
+    }
+}
+
+impl Eq for ShutdownSignal {}
+
+/// A request that is [in flight](ShutdownSignal::in_flight) until this is
+/// dropped.
+#[derive(Debug)]
+pub struct TrackedRequest(Arc<watch::Sender<usize>>);
+
+impl Drop for TrackedRequest {
+    fn drop(&mut self) {
+        self.0.send_modify(|in_flight| *in_flight -= 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ShutdownSignal;
+    use futures::{executor::block_on, FutureExt};
+    use std::sync::{atomic::AtomicBool, Arc};
+
+    #[test]
+    fn drained_waits_for_the_requests_in_flight() {
+        let shutdown = ShutdownSignal::new(Arc::new(AtomicBool::new(false)));
+        let first = shutdown.track_request();
+        let second = shutdown.clone().track_request();
+        assert_eq!(shutdown.in_flight(), 2);
+
+        let mut drained = Box::pin(shutdown.drained());
+        assert!((&mut drained).now_or_never().is_none());
+        drop(first);
+        assert!((&mut drained).now_or_never().is_none());
+        drop(second);
+        block_on(drained);
+        assert_eq!(shutdown.in_flight(), 0);
+    }
+}
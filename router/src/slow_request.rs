@@ -0,0 +1,139 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+/// The upper bounds of the buckets that [`SlowRequestConfig::histogram_bucket`]
+/// reports, in seconds, which are the default buckets of Prometheus histograms.
+const BUCKETS: [(f64, &str); 11] = [
+    (0.005, "0.005"),
+    (0.01, "0.01"),
+    (0.025, "0.025"),
+    (0.05, "0.05"),
+    (0.1, "0.1"),
+    (0.25, "0.25"),
+    (0.5, "0.5"),
+    (1.0, "1"),
+    (2.5, "2.5"),
+    (5.0, "5"),
+    (10.0, "10"),
+];
+
+/// When the server integrations warn that a request to a route was slow, set
+/// with [`slow_request_warning`](crate::NestedRoute::slow_request_warning).
+///
+/// The integrations measure the time from when a request is matched to a route
+/// until its response has been sent, and emit a warning with the route, the
+/// path and params of the request, the time it took, and its
+/// [histogram bucket](Self::histogram_bucket) if it took longer than the
+/// `threshold`.
+///
+/// ```rust
+/// use leptos_router::SlowRequestConfig;
+/// use std::time::Duration;
+///
+/// let config = SlowRequestConfig::new(Duration::from_millis(200));
+/// assert!(config.should_warn(Duration::from_millis(350)));
+/// assert!(!config.should_warn(Duration::from_millis(150)));
+/// assert_eq!(
+///     SlowRequestConfig::histogram_bucket(Duration::from_millis(350)),
+///     "0.5"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SlowRequestConfig {
+    /// How long a request can take before it is slow.
+    pub threshold: Duration,
+    /// The fraction of slow requests to warn about, from `0.0` to `1.0`, to
+    /// reduce the volume of the logs of routes that are often slow.
+    pub sample_rate: f32,
+}
+
+impl SlowRequestConfig {
+    /// Warns about every request that takes longer than `threshold`.
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            sample_rate: 1.0,
+        }
+    }
+
+    /// Whether a request that took `elapsed` is slow.
+    pub fn is_slow(&self, elapsed: Duration) -> bool {
+        elapsed > self.threshold
+    }
+
+    /// Whether to warn about a request that took `elapsed`: if it is slow, and
+    /// it is in the [sample](Self::sample_rate) of the slow requests, which is
+    /// chosen at random.
+    pub fn should_warn(&self, elapsed: Duration) -> bool {
+        self.is_slow(elapsed)
+            && (self.sample_rate >= 1.0
+                || (self.sample_rate > 0.0
+                    && random_fraction() < f64::from(self.sample_rate)))
+    }
+
+    /// The upper bound, in seconds, of the bucket of the default Prometheus
+    /// histogram buckets that a request that took `elapsed` falls into, like
+    /// `"0.5"`, or `"+Inf"` if it took longer than all of them, to aggregate
+    /// the warnings with the request duration metrics.
+    pub fn histogram_bucket(elapsed: Duration) -> &'static str {
+        let secs = elapsed.as_secs_f64();
+        BUCKETS
+            .iter()
+            .find(|(bound, _)| secs <= *bound)
+            .map_or("+Inf", |(_, label)| label)
+    }
+}
+
+impl PartialEq for SlowRequestConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.threshold == other.threshold
+            && self.sample_rate.to_bits() == other.sample_rate.to_bits()
+    }
+}
+
+impl Eq for SlowRequestConfig {}
+
+/// A random number from `0.0` to `1.0`, from the random keys of the standard
+/// library's hasher, which are different for each `RandomState`.
+fn random_fraction() -> f64 {
+    RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SlowRequestConfig;
+    use std::time::Duration;
+
+    #[test]
+    fn requests_are_sampled_at_the_sample_rate() {
+        let slow = Duration::from_secs(2);
+        let config = SlowRequestConfig {
+            threshold: Duration::from_secs(1),
+            sample_rate: 0.0,
+        };
+        assert!(config.is_slow(slow));
+        assert!(!config.should_warn(slow));
+
+        let config = SlowRequestConfig {
+            sample_rate: 0.5,
+            ..config
+        };
+        let warned = (0..1_000).filter(|_| config.should_warn(slow)).count();
+        assert!((300..700).contains(&warned), "{warned}");
+    }
+
+    #[test]
+    fn durations_fall_into_the_smallest_bucket_above_them() {
+        let bucket = |millis| {
+            SlowRequestConfig::histogram_bucket(Duration::from_millis(millis))
+        };
+        assert_eq!(bucket(0), "0.005");
+        assert_eq!(bucket(100), "0.1");
+        assert_eq!(bucket(101), "0.25");
+        assert_eq!(bucket(10_000), "10");
+        assert_eq!(bucket(10_001), "+Inf");
+    }
+}
@@ -26,6 +26,15 @@ use crate::static_routes::StaticRoute;
 /// The mode defaults to out-of-order streaming. For a path that includes multiple nested routes, the most
 /// restrictive mode will be used: i.e., if even a single nested route asks for `Async` rendering, the whole initial
 /// request will be rendered `Async`. (`Async` is the most restricted requirement, followed by `InOrder`, `PartiallyBlocked`, and `OutOfOrder`.)
+///
+/// `Static` is above every streaming mode, so a static route stays static under
+/// an `Async` parent, and the children of a static route are static too, unless
+/// they opt out with [`NestedRoute::no_static`](crate::NestedRoute::no_static).
+/// When both a parent and its child are static, the [`StaticRoute`] of the
+/// child is used, because it describes the paths of the child.
+/// [`SsrMode::resolve`] implements this, and can be replaced for a set of
+/// routes with
+/// [`RouteDefs::ssr_mode_precedence`](crate::RouteDefs::ssr_mode_precedence).
 #[derive(Default, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SsrMode {
     /// **Out-of-order streaming** (`OutOfOrder`, the default): Serve an HTML shell that includes `fallback` for any `Suspense`. Load data on the **server**, streaming it down to the client as it resolves, and streaming down HTML for `Suspense` nodes.
@@ -50,3 +59,88 @@ pub enum SsrMode {
     ///    configuration provided by a [`StaticRoute`].
     Static(StaticRoute),
 }
+
+/// Combines the [`SsrMode`] of a parent route with that of its child, to find
+/// the mode of the child, as set with
+/// [`RouteDefs::ssr_mode_precedence`](crate::RouteDefs::ssr_mode_precedence).
+pub type SsrModePrecedence = fn(parent: &SsrMode, child: &SsrMode) -> SsrMode;
+
+impl SsrMode {
+    /// The mode of a route whose parent uses `parent`, and which asks for
+    /// `child`: the more restrictive of the two, or the mode of the child if
+    /// they are equally restrictive.
+    ///
+    /// ```rust
+    /// use leptos_router::{static_routes::StaticRoute, SsrMode};
+    ///
+    /// assert_eq!(
+    ///     SsrMode::resolve(&SsrMode::InOrder, &SsrMode::PartiallyBlocked),
+    ///     SsrMode::InOrder
+    /// );
+    /// assert!(matches!(
+    ///     SsrMode::resolve(
+    ///         &SsrMode::Async,
+    ///         &SsrMode::Static(StaticRoute::new())
+    ///     ),
+    ///     SsrMode::Static(_)
+    /// ));
+    /// ```
+    pub fn resolve(parent: &SsrMode, child: &SsrMode) -> SsrMode {
+        if child >= parent {
+            child.clone()
+        } else {
+            parent.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SsrMode;
+    use crate::static_routes::StaticRoute;
+
+    fn mode(label: char, static_route: &StaticRoute) -> SsrMode {
+        match label {
+            'O' => SsrMode::OutOfOrder,
+            'P' => SsrMode::PartiallyBlocked,
+            'I' => SsrMode::InOrder,
+            'A' => SsrMode::Async,
+            _ => SsrMode::Static(static_route.clone()),
+        }
+    }
+
+    #[test]
+    fn the_more_restrictive_mode_wins() {
+        // the parent static route is not incremental, and the child one is, so
+        // that the table shows which of the two a static child ends up with
+        let parent_static = StaticRoute::new();
+        let child_static = StaticRoute::new().incremental(true);
+        // each row is a parent mode, and the modes its children get, for
+        // children asking for `OutOfOrder`, `PartiallyBlocked`, `InOrder`,
+        // `Async` and `Static`
+        let table = [
+            ('O', "OPIAS"),
+            ('P', "PPIAS"),
+            ('I', "IIIAS"),
+            ('A', "AAAAS"),
+            ('S', "SSSSS"),
+        ];
+        for (parent, resolved) in table {
+            for (child, expected) in "OPIAS".chars().zip(resolved.chars()) {
+                let parent_mode = mode(parent, &parent_static);
+                let child_mode = mode(child, &child_static);
+                // a static child keeps its own configuration
+                let expected_static = if child == 'S' {
+                    &child_static
+                } else {
+                    &parent_static
+                };
+                assert_eq!(
+                    SsrMode::resolve(&parent_mode, &child_mode),
+                    mode(expected, expected_static),
+                    "{parent} parent, {child} child"
+                );
+            }
+        }
+    }
+}
@@ -1,13 +1,19 @@
 use crate::{hooks::RawParamsMap, params::ParamsMap, PathSegment};
-use futures::{channel::oneshot, stream, Stream, StreamExt};
+use futures::{
+    channel::oneshot,
+    future::{FutureExt, Shared},
+    stream, Stream, StreamExt,
+};
 use leptos::task::spawn;
+use or_poisoned::OrPoisoned;
 use reactive_graph::{owner::Owner, traits::GetUntracked};
 use std::{
+    collections::HashMap,
     fmt::{Debug, Display},
     future::Future,
     ops::Deref,
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, LazyLock, Mutex},
 };
 
 type PinnedFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
@@ -47,11 +53,50 @@ impl PartialEq for RegenerationFn {
     }
 }
 
+/// A function that defines when the set of params for a statically-generated
+/// route should be generated again.
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct RefreshParamsFn(Arc<dyn Fn() -> PinnedStream<()> + Send + Sync>);
+
+impl Debug for RefreshParamsFn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RefreshParamsFn").finish_non_exhaustive()
+    }
+}
+
+impl Deref for RefreshParamsFn {
+    type Target = dyn Fn() -> PinnedStream<()> + Send + Sync;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl PartialEq for RefreshParamsFn {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
 /// Defines how a static route should be generated.
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct StaticRoute {
     pub(crate) prerender_params: Option<StaticParams>,
     pub(crate) regenerate: Option<RegenerationFn>,
+    pub(crate) refresh_params: Option<RefreshParamsFn>,
+    pub(crate) incremental: bool,
+}
+
+impl Default for StaticRoute {
+    fn default() -> Self {
+        Self {
+            prerender_params: None,
+            regenerate: None,
+            refresh_params: None,
+            incremental: true,
+        }
+    }
 }
 
 impl StaticRoute {
@@ -87,6 +132,41 @@ impl StaticRoute {
         self
     }
 
+    /// Defines when the [prerendered params](Self::prerender_params) should be
+    /// generated again.
+    ///
+    /// Each time the stream yields, the params function is called again, and
+    /// any paths for new params (for example, a product that was added after
+    /// the server started) are rendered.
+    pub fn refresh_params<St>(
+        mut self,
+        refresh: impl Fn() -> St + Send + Sync + 'static,
+    ) -> Self
+    where
+        St: Stream<Item = ()> + Send + 'static,
+    {
+        self.refresh_params =
+            Some(RefreshParamsFn(Arc::new(move || Box::pin(refresh()))));
+        self
+    }
+
+    /// Defines whether paths that were not prerendered are rendered and written
+    /// as static files on their first request (incremental static
+    /// regeneration).
+    ///
+    /// This is `true` by default. If it is `false`, those paths are rendered on
+    /// every request, and never written.
+    pub fn incremental(mut self, incremental: bool) -> Self {
+        self.incremental = incremental;
+        self
+    }
+
+    /// Whether paths that were not prerendered are written as static files on
+    /// their first request.
+    pub fn is_incremental(&self) -> bool {
+        self.incremental
+    }
+
     /// Returns a set of params that should be prerendered.
     pub async fn to_prerendered_params(&self) -> Option<StaticParamsMap> {
         match &self.prerender_params {
@@ -122,7 +202,10 @@ impl PartialEq for StaticRoute {
             (None, Some(_)) | (Some(_), None) => false,
             (Some(this), Some(that)) => Arc::ptr_eq(this, that),
         };
-        prerender && (self.regenerate == other.regenerate)
+        prerender
+            && self.regenerate == other.regenerate
+            && self.refresh_params == other.refresh_params
+            && self.incremental == other.incremental
     }
 }
 
@@ -291,8 +374,64 @@ impl Display for ResolvedStaticPath {
     }
 }
 
+/// Deduplicates concurrent async work by key, so that callers that arrive while
+/// the work for a key is in progress wait for the same result, rather than
+/// doing the work again.
+pub(crate) struct SingleFlight<T> {
+    #[allow(clippy::type_complexity)]
+    in_flight: Mutex<HashMap<String, Shared<PinnedFuture<T>>>>,
+}
+
+impl<T> Default for SingleFlight<T> {
+    fn default() -> Self {
+        Self {
+            in_flight: Default::default(),
+        }
+    }
+}
+
+impl<T> SingleFlight<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    pub(crate) async fn run<Fut>(
+        &self,
+        key: &str,
+        work: impl FnOnce() -> Fut,
+    ) -> T
+    where
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let shared = self
+            .in_flight
+            .lock()
+            .or_poisoned()
+            .entry(key.to_string())
+            .or_insert_with(|| (Box::pin(work()) as PinnedFuture<T>).shared())
+            .clone();
+        let value = shared.clone().await;
+
+        let mut in_flight = self.in_flight.lock().or_poisoned();
+        if in_flight
+            .get(key)
+            .is_some_and(|current| current.ptr_eq(&shared))
+        {
+            in_flight.remove(key);
+        }
+        value
+    }
+}
+
+static STATIC_BUILDS: LazyLock<SingleFlight<(Owner, Option<String>)>> =
+    LazyLock::new(Default::default);
+
 impl ResolvedStaticPath {
     /// Builds the page that corresponds to this path.
+    ///
+    /// If this path is already being built (for example, because several
+    /// requests for a page that has not been generated yet arrived at the same
+    /// time), this waits for that build to finish rather than rendering the
+    /// page again.
     pub async fn build<Fut, WriterFut>(
         self,
         render_fn: impl Fn(&ResolvedStaticPath) -> Fut + Send + Clone + 'static,
@@ -303,6 +442,28 @@ impl ResolvedStaticPath {
         was_404: impl Fn(&Owner) -> bool + Send + Clone + 'static,
         regenerate: Vec<RegenerationFn>,
     ) -> (Owner, Option<String>)
+    where
+        Fut: Future<Output = (Owner, String)> + Send + 'static,
+        WriterFut: Future<Output = Result<(), std::io::Error>> + Send + 'static,
+    {
+        let key = self.path.clone();
+        STATIC_BUILDS
+            .run(&key, move || {
+                self.build_uncached(render_fn, writer, was_404, regenerate)
+            })
+            .await
+    }
+
+    async fn build_uncached<Fut, WriterFut>(
+        self,
+        render_fn: impl Fn(&ResolvedStaticPath) -> Fut + Send + Clone + 'static,
+        writer: impl Fn(&ResolvedStaticPath, &Owner, String) -> WriterFut
+            + Send
+            + Clone
+            + 'static,
+        was_404: impl Fn(&Owner) -> bool + Send + Clone + 'static,
+        regenerate: Vec<RegenerationFn>,
+    ) -> (Owner, Option<String>)
     where
         Fut: Future<Output = (Owner, String)> + Send + 'static,
         WriterFut: Future<Output = Result<(), std::io::Error>> + Send + 'static,
@@ -440,4 +601,34 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn single_flight_runs_concurrent_work_once() {
+        use futures::{executor::block_on, future::join3};
+
+        let flight = SingleFlight::<usize>::default();
+        let (tx, rx) = oneshot::channel::<()>();
+
+        // the second call arrives while the first is still waiting, so shares
+        // its result
+        let (first, second, _) = block_on(join3(
+            flight.run("/post/1", move || async move {
+                rx.await.unwrap();
+                1
+            }),
+            flight.run("/post/1", || async { 2 }),
+            async move { tx.send(()).unwrap() },
+        ));
+        assert_eq!((first, second), (1, 1));
+        assert!(flight.in_flight.lock().unwrap().is_empty());
+
+        // once the work is done, the key runs again
+        assert_eq!(block_on(flight.run("/post/1", || async { 3 })), 3);
+    }
+
+    #[test]
+    fn static_route_is_incremental_by_default() {
+        assert!(StaticRoute::new().is_incremental());
+        assert!(!StaticRoute::new().incremental(false).is_incremental());
+    }
 }
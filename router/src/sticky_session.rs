@@ -0,0 +1,83 @@
+use std::{
+    sync::OnceLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// How a load balancer can keep routing a client's requests to the same server
+/// instance, for routes set with
+/// [`NestedRoute::sticky_session`](crate::NestedRoute::sticky_session), like
+/// WebSocket or server-sent event routes with long-running connections.
+///
+/// The server integrations identify the instance, with its
+/// [`instance_id`](Self::instance_id), in the first response to a client that
+/// does not send the cookie yet, so that a load balancer configured with the
+/// same cookie or header can send its later requests to the same instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StickySessionPolicy {
+    /// The name of the session affinity cookie, which is set to the ID of the
+    /// instance.
+    pub cookie: &'static str,
+    /// The name of the response header that is set to the ID of the instance,
+    /// along with the cookie.
+    pub header: &'static str,
+}
+
+impl StickySessionPolicy {
+    /// The ID of this server instance: the `LEPTOS_INSTANCE_ID` environment
+    /// variable, or else `HOSTNAME` (which is the name of the pod in
+    /// Kubernetes), or else an ID for this process.
+    pub fn instance_id() -> &'static str {
+        static INSTANCE_ID: OnceLock<String> = OnceLock::new();
+        INSTANCE_ID.get_or_init(|| {
+            std::env::var("LEPTOS_INSTANCE_ID")
+                .or_else(|_| std::env::var("HOSTNAME"))
+                .ok()
+                .filter(|id| !id.is_empty())
+                .unwrap_or_else(|| {
+                    let started = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map_or(0, |since| since.as_nanos());
+                    format!("{:x}-{started:x}", std::process::id())
+                })
+        })
+    }
+
+    /// Whether the value of a request's `Cookie` header already has the session
+    /// affinity cookie, in which case the instance does not need to be
+    /// identified again.
+    pub fn is_set_in(&self, cookie_header: &str) -> bool {
+        cookie_header.split(';').any(|pair| {
+            pair.split_once('=')
+                .is_some_and(|(name, _)| name.trim() == self.cookie)
+        })
+    }
+
+    /// The value of the `Set-Cookie` header that identifies this instance.
+    pub fn set_cookie(&self) -> String {
+        format!(
+            "{}={}; Path=/; HttpOnly; SameSite=Lax",
+            self.cookie,
+            Self::instance_id()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StickySessionPolicy;
+
+    #[test]
+    fn finds_the_affinity_cookie_among_others() {
+        let policy = StickySessionPolicy {
+            cookie: "backend",
+            header: "x-backend",
+        };
+        assert!(policy.is_set_in("theme=dark; backend=pod-1"));
+        assert!(!policy.is_set_in("theme=dark; my-backend=pod-1"));
+        assert!(!policy.is_set_in(""));
+        assert!(policy.set_cookie().starts_with(&format!(
+            "backend={};",
+            StickySessionPolicy::instance_id()
+        )));
+    }
+}
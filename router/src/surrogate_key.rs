@@ -0,0 +1,111 @@
+use crate::params::ParamsMap;
+use std::{fmt, sync::Arc};
+
+/// Returns the surrogate keys of a response, from the params of the route, for
+/// [`NestedRoute::surrogate_key`](crate::NestedRoute::surrogate_key).
+pub type SurrogateKeyFn = Arc<dyn Fn(&ParamsMap) -> Vec<String> + Send + Sync>;
+
+/// The surrogate keys (or cache tags) of the responses from a route, which a
+/// CDN can use to invalidate every cached response that has a key, like all the
+/// pages that show a post.
+///
+/// These are the functions of the route and of its ancestors, which the server
+/// integrations call with the params of each request, and send the keys they
+/// return in the `Surrogate-Key` header of Fastly and the `Cache-Tag` header of
+/// Cloudflare.
+///
+/// ```rust
+/// use leptos_router::{params::ParamsMap, SurrogateKeys};
+///
+/// let mut keys = SurrogateKeys::default();
+/// keys.push(|_: &ParamsMap| vec!["posts".to_string(), "feed".to_string()]);
+/// keys.push(|params: &ParamsMap| {
+///     params
+///         .get_str("slug")
+///         .map(|slug| format!("post:{slug}"))
+///         .into_iter()
+///         .chain(["posts".to_string()])
+///         .collect()
+/// });
+///
+/// // for `/posts`, without a slug
+/// let params = ParamsMap::new();
+/// assert_eq!(keys.resolve(&params), ["posts", "feed"]);
+/// assert_eq!(
+///     keys.headers(&params),
+///     [
+///         ("surrogate-key", "posts feed".to_string()),
+///         ("cache-tag", "posts,feed".to_string()),
+///     ]
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct SurrogateKeys(Vec<SurrogateKeyFn>);
+
+impl SurrogateKeys {
+    /// Adds the keys returned by `keys`, after those of the functions that were
+    /// added before it.
+    pub fn push(
+        &mut self,
+        keys: impl Fn(&ParamsMap) -> Vec<String> + Send + Sync + 'static,
+    ) {
+        self.0.push(Arc::new(keys));
+    }
+
+    /// The keys for a request with `params`, in order and without duplicates or
+    /// empty keys.
+    pub fn resolve(&self, params: &ParamsMap) -> Vec<String> {
+        let mut resolved = Vec::new();
+        for key in self.0.iter().flat_map(|keys| keys(params)) {
+            // keys are separated by spaces or commas in the headers
+            let key = key.replace([' ', ','], "-");
+            if !key.is_empty() && !resolved.contains(&key) {
+                resolved.push(key);
+            }
+        }
+        resolved
+    }
+
+    /// The `Surrogate-Key` and `Cache-Tag` headers of a response to a request
+    /// with `params`, which are left out if there are no keys.
+    pub fn headers(&self, params: &ParamsMap) -> Vec<(&'static str, String)> {
+        let keys = self.resolve(params);
+        if keys.is_empty() {
+            return Vec::new();
+        }
+        vec![
+            ("surrogate-key", keys.join(" ")),
+            ("cache-tag", keys.join(",")),
+        ]
+    }
+
+    /// The keys of a child route, after those of its parent.
+    pub(crate) fn inherit(
+        parent: Option<&SurrogateKeys>,
+        child: Option<SurrogateKeys>,
+    ) -> Option<SurrogateKeys> {
+        match (parent, child) {
+            (Some(parent), Some(child)) => {
+                let mut keys = parent.clone();
+                keys.0.extend(child.0);
+                Some(keys)
+            }
+            (parent, child) => child.or_else(|| parent.cloned()),
+        }
+    }
+}
+
+impl fmt::Debug for SurrogateKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SurrogateKeys").field(&self.0.len()).finish()
+    }
+}
+
+impl PartialEq for SurrogateKeys {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self.0.iter().zip(&other.0).all(|(a, b)| Arc::ptr_eq(a, b))
+    }
+}
+
+impl Eq for SurrogateKeys {}
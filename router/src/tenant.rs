@@ -0,0 +1,113 @@
+use crate::{matching::AnyChooseView, MatchRequest};
+use std::{collections::HashMap, fmt, ops::Deref, sync::Arc};
+
+/// Finds the tenant a request is for, in an app that serves several tenants.
+///
+/// Used with [`NestedRoute::multi_tenant`](crate::NestedRoute::multi_tenant).
+/// Any function that takes a [`MatchRequest`] and returns an `Option<String>`
+/// is a resolver, so the tenant can be read from the host, the path, the query
+/// or the cookies of the request.
+pub trait TenantResolver: Send + Sync {
+    /// The ID of the tenant the request is for, or `None` if it is not for a
+    /// known tenant.
+    fn resolve(&self, req: &MatchRequest<'_>) -> Option<String>;
+}
+
+impl<F> TenantResolver for F
+where
+    F: Fn(&MatchRequest<'_>) -> Option<String> + Send + Sync,
+{
+    fn resolve(&self, req: &MatchRequest<'_>) -> Option<String> {
+        self(req)
+    }
+}
+
+/// The ID of the tenant the current route is rendered for, provided via context
+/// by a [multi-tenant route](crate::NestedRoute::multi_tenant) to its view and
+/// to all of its nested routes, so that their data loaders can scope their
+/// queries to the tenant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TenantId(String);
+
+impl TenantId {
+    /// Wraps a tenant ID.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// The ID of the tenant.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for TenantId {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// How a multi-tenant route finds its tenant, and the views it renders for
+/// specific tenants.
+#[derive(Clone)]
+pub(crate) struct TenantConfig {
+    resolver: Arc<dyn TenantResolver>,
+    views: HashMap<String, AnyChooseView>,
+}
+
+impl TenantConfig {
+    pub(crate) fn new(
+        resolver: Arc<dyn TenantResolver>,
+        views: HashMap<String, AnyChooseView>,
+    ) -> Self {
+        Self { resolver, views }
+    }
+
+    /// Resolves the tenant of `req`, along with its view, if it has one.
+    pub(crate) fn resolve(
+        &self,
+        req: &MatchRequest<'_>,
+    ) -> Option<TenantMatch> {
+        let id = self.resolver.resolve(req)?;
+        let view = self.views.get(&id).cloned();
+        Some(TenantMatch { id, view })
+    }
+}
+
+impl fmt::Debug for TenantConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TenantConfig")
+            .field("tenants", &self.views.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for TenantConfig {
+    fn eq(&self, other: &Self) -> bool {
+        // views cannot be compared, so only the tenants that have one are
+        Arc::ptr_eq(&self.resolver, &other.resolver)
+            && self.views.len() == other.views.len()
+            && self.views.keys().all(|id| other.views.contains_key(id))
+    }
+}
+
+impl Eq for TenantConfig {}
+
+/// The tenant that a multi-tenant route was matched for.
+#[derive(Clone)]
+pub(crate) struct TenantMatch {
+    pub(crate) id: String,
+    /// The tenant's own view, if it has one.
+    pub(crate) view: Option<AnyChooseView>,
+}
+
+// the view is determined by the tenant, for a given route
+impl PartialEq for TenantMatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for TenantMatch {}
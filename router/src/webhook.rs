@@ -0,0 +1,161 @@
+use secrecy::ExposeSecret;
+pub use secrecy::Secret;
+use std::{fmt, sync::Arc};
+
+/// The hash function used to compute a webhook's HMAC signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HmacAlgorithm {
+    /// HMAC-SHA256.
+    Sha256,
+    /// HMAC-SHA512.
+    Sha512,
+}
+
+impl HmacAlgorithm {
+    /// The lowercase name of the algorithm, as used in signature prefixes like
+    /// `sha256=`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HmacAlgorithm::Sha256 => "sha256",
+            HmacAlgorithm::Sha512 => "sha512",
+        }
+    }
+}
+
+/// Defines how requests to a webhook route are authenticated.
+///
+/// The server integrations compute the HMAC of the raw request body with
+/// `secret`, and compare it to the hex-encoded signature in the `header`
+/// request header. Requests with a missing or invalid signature are rejected
+/// with `401 Unauthorized`.
+#[derive(Clone)]
+pub struct WebhookSignatureConfig {
+    /// The name of the request header that contains the signature, like
+    /// `X-Hub-Signature-256`.
+    pub header: &'static str,
+    /// The secret shared with the sender of the webhook.
+    pub secret: Secret<String>,
+    /// The hash function used to compute the signature.
+    pub algorithm: HmacAlgorithm,
+}
+
+impl fmt::Debug for WebhookSignatureConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookSignatureConfig")
+            .field("header", &self.header)
+            .field("secret", &self.secret)
+            .field("algorithm", &self.algorithm)
+            .finish()
+    }
+}
+
+impl PartialEq for WebhookSignatureConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.header == other.header
+            && self.algorithm == other.algorithm
+            && self.secret.expose_secret() == other.secret.expose_secret()
+    }
+}
+
+impl Eq for WebhookSignatureConfig {}
+
+impl WebhookSignatureConfig {
+    /// Whether `signature` is a valid signature of `body`.
+    ///
+    /// The signature is hex-encoded, and may be prefixed with the algorithm
+    /// name (like `sha256=...`). It is compared in constant time.
+    #[cfg(feature = "ssr")]
+    pub fn verify(&self, body: &[u8], signature: &str) -> bool {
+        use hmac::{Hmac, Mac};
+
+        let signature = signature.trim();
+        let signature = signature
+            .strip_prefix(self.algorithm.as_str())
+            .and_then(|sig| sig.strip_prefix('='))
+            .unwrap_or(signature);
+        let Some(signature) = decode_hex(signature) else {
+            return false;
+        };
+        let key = self.secret.expose_secret().as_bytes();
+
+        // `Hmac::new_from_slice` accepts keys of any length
+        match self.algorithm {
+            HmacAlgorithm::Sha256 => {
+                let mut mac = Hmac::<sha2::Sha256>::new_from_slice(key)
+                    .expect("HMAC can take a key of any size");
+                mac.update(body);
+                mac.verify_slice(&signature).is_ok()
+            }
+            HmacAlgorithm::Sha512 => {
+                let mut mac = Hmac::<sha2::Sha512>::new_from_slice(key)
+                    .expect("HMAC can take a key of any size");
+                mac.update(body);
+                mac.verify_slice(&signature).is_ok()
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub(crate) fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|idx| u8::from_str_radix(hex.get(idx..idx + 2)?, 16).ok())
+        .collect()
+}
+
+/// The raw body of a webhook request, after its signature has been validated.
+///
+/// The server integrations provide this via context when handling a route with
+/// a [`WebhookSignatureConfig`], because the body has already been read to
+/// validate it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookBody(Arc<[u8]>);
+
+impl WebhookBody {
+    /// Wraps the raw bytes of a request body.
+    pub fn new(body: impl Into<Arc<[u8]>>) -> Self {
+        Self(body.into())
+    }
+
+    /// The raw bytes of the request body.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{HmacAlgorithm, Secret, WebhookSignatureConfig};
+
+    fn config(algorithm: HmacAlgorithm) -> WebhookSignatureConfig {
+        WebhookSignatureConfig {
+            header: "X-Signature",
+            secret: Secret::new("It's a Secret to Everybody".to_string()),
+            algorithm,
+        }
+    }
+
+    // the example from GitHub's documentation on validating webhook deliveries
+    const SHA256: &str =
+        "757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+    #[test]
+    fn verifies_sha256_signature() {
+        let config = config(HmacAlgorithm::Sha256);
+        assert!(config.verify(b"Hello, World!", SHA256));
+        assert!(config.verify(b"Hello, World!", &format!("sha256={SHA256}")));
+        assert!(!config.verify(b"Hello, World?", SHA256));
+        assert!(!config.verify(b"Hello, World!", "not hex"));
+        assert!(!config.verify(b"Hello, World!", ""));
+    }
+
+    #[test]
+    fn sha512_does_not_accept_sha256_signature() {
+        let config = config(HmacAlgorithm::Sha512);
+        assert!(!config.verify(b"Hello, World!", SHA256));
+    }
+}
@@ -0,0 +1,80 @@
+use futures::{
+    stream::{BoxStream, Stream},
+    StreamExt,
+};
+use std::{fmt, sync::Arc};
+
+/// A message sent over the connection to a
+/// [WebSocket route](crate::NestedRoute::websocket).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WebSocketMessage {
+    /// A text message.
+    Text(String),
+    /// A binary message.
+    Binary(Vec<u8>),
+}
+
+type HandlerFn = dyn Fn(
+        BoxStream<'static, WebSocketMessage>,
+    ) -> BoxStream<'static, WebSocketMessage>
+    + Send
+    + Sync;
+
+/// Handles the connections to a
+/// [WebSocket route](crate::NestedRoute::websocket).
+///
+/// The handler is called once for each connection, with the stream of messages
+/// received from the client, and returns the stream of messages to send to it.
+/// The server integrations close the connection when either stream ends.
+///
+/// ```rust
+/// use futures::StreamExt;
+/// use leptos_router::{WebSocketHandler, WebSocketMessage};
+///
+/// // responds to each text message with the same message
+/// let echo = WebSocketHandler::new(|incoming| {
+///     incoming.filter(|message| {
+///         futures::future::ready(matches!(message, WebSocketMessage::Text(_)))
+///     })
+/// });
+/// ```
+#[derive(Clone)]
+pub struct WebSocketHandler(Arc<HandlerFn>);
+
+impl WebSocketHandler {
+    /// Creates a handler that responds to the messages of each connection with
+    /// the stream returned by `handler`.
+    pub fn new<F, S>(handler: F) -> Self
+    where
+        F: Fn(BoxStream<'static, WebSocketMessage>) -> S
+            + Send
+            + Sync
+            + 'static,
+        S: Stream<Item = WebSocketMessage> + Send + 'static,
+    {
+        Self(Arc::new(move |incoming| handler(incoming).boxed()))
+    }
+
+    /// Handles a new connection, given the messages received from the client,
+    /// returning the messages to send to it.
+    pub fn handle(
+        &self,
+        incoming: impl Stream<Item = WebSocketMessage> + Send + 'static,
+    ) -> BoxStream<'static, WebSocketMessage> {
+        (self.0)(incoming.boxed())
+    }
+}
+
+impl fmt::Debug for WebSocketHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WebSocketHandler(..)")
+    }
+}
+
+impl PartialEq for WebSocketHandler {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for WebSocketHandler {}
@@ -0,0 +1,89 @@
+use std::{fmt, time::SystemTime};
+
+/// A directive of the
+/// [`X-Robots-Tag`](https://developers.google.com/search/docs/crawling-indexing/robots-meta-tag#xrobotstag)
+/// response header, set with
+/// [`NestedRoute::x_robots_tag`](crate::NestedRoute::x_robots_tag).
+///
+/// Unlike a `<meta name="robots">` tag, the header also applies to responses
+/// that are not HTML, like those of API or file routes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum XRobotsDirective {
+    /// Do not show this response in search results.
+    NoIndex,
+    /// Do not follow the links in this response.
+    NoFollow,
+    /// Do not show a text snippet or video preview of this response in search
+    /// results.
+    NoSnippet,
+    /// Do not index the images in this response.
+    NoImageIndex,
+    /// Do not offer a translation of this response in search results.
+    NoTranslate,
+    /// Do not show this response in search results after the given time.
+    UnavailableAfter(SystemTime),
+    /// Any other directive, like `max-snippet: 20` or one for a specific
+    /// crawler, like `googlebot: nofollow`.
+    Custom(String),
+}
+
+impl fmt::Display for XRobotsDirective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XRobotsDirective::NoIndex => f.write_str("noindex"),
+            XRobotsDirective::NoFollow => f.write_str("nofollow"),
+            XRobotsDirective::NoSnippet => f.write_str("nosnippet"),
+            XRobotsDirective::NoImageIndex => f.write_str("noimageindex"),
+            XRobotsDirective::NoTranslate => f.write_str("notranslate"),
+            XRobotsDirective::UnavailableAfter(time) => {
+                write!(
+                    f,
+                    "unavailable_after: {}",
+                    httpdate::fmt_http_date(*time)
+                )
+            }
+            XRobotsDirective::Custom(directive) => f.write_str(directive),
+        }
+    }
+}
+
+/// The value of the `X-Robots-Tag` header with the given directives, or `None`
+/// if there are none.
+pub fn x_robots_tag_header(directives: &[XRobotsDirective]) -> Option<String> {
+    if directives.is_empty() {
+        return None;
+    }
+    Some(
+        directives
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{x_robots_tag_header, XRobotsDirective};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn directives_are_joined_into_one_header() {
+        assert_eq!(x_robots_tag_header(&[]), None);
+        assert_eq!(
+            x_robots_tag_header(&[
+                XRobotsDirective::NoIndex,
+                XRobotsDirective::NoImageIndex,
+                XRobotsDirective::UnavailableAfter(
+                    UNIX_EPOCH + Duration::from_secs(1_750_000_000)
+                ),
+                XRobotsDirective::Custom("max-snippet: 20".into()),
+            ])
+            .as_deref(),
+            Some(
+                "noindex, noimageindex, unavailable_after: Sun, 15 Jun 2025 \
+                 15:06:40 GMT, max-snippet: 20"
+            )
+        );
+    }
+}
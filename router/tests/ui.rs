@@ -0,0 +1,8 @@
+#[cfg(not(erase_components))]
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/builder_after_child.rs");
+    t.compile_fail("tests/ui/child_twice.rs");
+    t.compile_fail("tests/ui/not_a_view.rs");
+}
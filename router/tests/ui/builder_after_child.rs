@@ -0,0 +1,8 @@
+use leptos_router::{NestedRoute, SsrMode, StaticSegment};
+
+// the settings of a route are only available before its children are set
+fn main() {
+    let _ = NestedRoute::new(StaticSegment("users"), || ())
+        .child(NestedRoute::new(StaticSegment("settings"), || ()))
+        .ssr_mode(SsrMode::Async);
+}
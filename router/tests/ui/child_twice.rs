@@ -0,0 +1,8 @@
+use leptos_router::{NestedRoute, StaticSegment};
+
+// the children of a route are set at once, as a tuple
+fn main() {
+    let _ = NestedRoute::new(StaticSegment("users"), || ())
+        .child(NestedRoute::new(StaticSegment("settings"), || ()))
+        .child(NestedRoute::new(StaticSegment("profile"), || ()));
+}
@@ -0,0 +1,6 @@
+use leptos_router::{NestedRoute, StaticSegment};
+
+// the view of a route is a function that creates it, not the view itself
+fn main() {
+    let _ = NestedRoute::new(StaticSegment("users"), "Users");
+}
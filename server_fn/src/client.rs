@@ -1,10 +1,18 @@
 use crate::{request::ClientReq, response::ClientRes};
 use bytes::Bytes;
 use futures::{Sink, Stream};
-use std::{future::Future, sync::OnceLock};
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
 
 static ROOT_URL: OnceLock<&'static str> = OnceLock::new();
 
+static RESPECT_DOCUMENT_BASE: AtomicBool = AtomicBool::new(false);
+
 /// Set the root server URL that all server function paths are relative to for the client.
 ///
 /// If this is not set, it defaults to the origin.
@@ -17,6 +25,60 @@ pub fn get_server_url() -> &'static str {
     ROOT_URL.get().copied().unwrap_or("")
 }
 
+/// How relative URLs are resolved in the browser when the document sets a
+/// [`<base href>`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/base),
+/// as it often does when an app is deployed under a subdirectory.
+///
+/// This applies to server function URLs, which are relative if the server URL
+/// and the prefix of the server function are. The router applies the same
+/// setting to the URLs it builds for links, forms and redirects, so that they
+/// all agree with each other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DocumentBase {
+    /// Relative URLs are made absolute before they are used, so the
+    /// `<base href>` has no effect on them.
+    #[default]
+    Ignore,
+    /// Relative URLs are left to the browser, which resolves them against the
+    /// `<base href>`.
+    Respect,
+}
+
+/// Sets how relative URLs are resolved when the document sets a `<base href>`.
+pub fn set_document_base(document_base: DocumentBase) {
+    RESPECT_DOCUMENT_BASE
+        .store(document_base == DocumentBase::Respect, Ordering::Relaxed);
+}
+
+/// Returns how relative URLs are resolved when the document sets a
+/// `<base href>`.
+pub fn get_document_base() -> DocumentBase {
+    if RESPECT_DOCUMENT_BASE.load(Ordering::Relaxed) {
+        DocumentBase::Respect
+    } else {
+        DocumentBase::Ignore
+    }
+}
+
+/// The URL a server function at `path` is sent to, which is the
+/// [server URL](get_server_url) followed by the path.
+///
+/// If that is relative, it is made absolute from the root of the origin, unless
+/// the [document base](DocumentBase) is respected.
+pub fn server_fn_url(path: &str) -> String {
+    let server_url = get_server_url();
+    let mut url = String::with_capacity(server_url.len() + path.len() + 1);
+    url.push_str(server_url);
+    url.push_str(path);
+    if get_document_base() == DocumentBase::Ignore
+        && !url.starts_with('/')
+        && !url.contains("://")
+    {
+        url.insert(0, '/');
+    }
+    url
+}
+
 /// A client defines a pair of request/response types and the logic to send
 /// and receive them.
 ///
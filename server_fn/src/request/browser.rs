@@ -1,6 +1,6 @@
 use super::ClientReq;
 use crate::{
-    client::get_server_url,
+    client::server_fn_url,
     error::{FromServerFnError, ServerFnErrorErr},
 };
 use bytes::Bytes;
@@ -101,12 +101,7 @@ where
         method: http::Method,
     ) -> Result<Self, E> {
         let (abort_ctrl, abort_signal) = abort_signal();
-        let server_url = get_server_url();
-        let mut url = String::with_capacity(
-            server_url.len() + path.len() + 1 + query.len(),
-        );
-        url.push_str(server_url);
-        url.push_str(path);
+        let mut url = server_fn_url(path);
         url.push('?');
         url.push_str(query);
         Ok(Self(SendWrapper::new(RequestInner {
@@ -145,10 +140,7 @@ where
         method: Method,
     ) -> Result<Self, E> {
         let (abort_ctrl, abort_signal) = abort_signal();
-        let server_url = get_server_url();
-        let mut url = String::with_capacity(server_url.len() + path.len());
-        url.push_str(server_url);
-        url.push_str(path);
+        let url = server_fn_url(path);
         Ok(Self(SendWrapper::new(RequestInner {
             request: match method {
                 Method::POST => Request::post(&url),
@@ -183,10 +175,7 @@ where
         method: Method,
     ) -> Result<Self, E> {
         let (abort_ctrl, abort_signal) = abort_signal();
-        let server_url = get_server_url();
-        let mut url = String::with_capacity(server_url.len() + path.len());
-        url.push_str(server_url);
-        url.push_str(path);
+        let url = server_fn_url(path);
         let body: &[u8] = &body;
         let body = Uint8Array::from(body).buffer();
         Ok(Self(SendWrapper::new(RequestInner {
@@ -222,10 +211,7 @@ where
         method: Method,
     ) -> Result<Self, E> {
         let (abort_ctrl, abort_signal) = abort_signal();
-        let server_url = get_server_url();
-        let mut url = String::with_capacity(server_url.len() + path.len());
-        url.push_str(server_url);
-        url.push_str(path);
+        let url = server_fn_url(path);
         Ok(Self(SendWrapper::new(RequestInner {
             request: match method {
                 Method::POST => Request::post(&url),
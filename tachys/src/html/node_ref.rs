@@ -22,6 +22,37 @@ where
 {
     /// Fills the container with the element.
     fn load(self, el: &crate::renderer::types::Element);
+
+    /// Empties the container, if it still holds the element, once the element
+    /// is removed.
+    ///
+    /// By default, this does nothing.
+    fn unload(self, el: &crate::renderer::types::Element) {
+        _ = el;
+    }
+}
+
+/// The state of a [`NodeRefAttr`], which [unloads](NodeRefContainer::unload)
+/// the element from its container when it is dropped, because the element has
+/// been removed.
+pub struct NodeRefState<E, C>
+where
+    E: ElementType,
+    C: NodeRefContainer<E>,
+{
+    el: crate::renderer::types::Element,
+    container: C,
+    ty: PhantomData<E>,
+}
+
+impl<E, C> Drop for NodeRefState<E, C>
+where
+    E: ElementType,
+    C: NodeRefContainer<E>,
+{
+    fn drop(&mut self) {
+        self.container.clone().unload(&self.el);
+    }
 }
 
 /// An [`Attribute`] that will fill a [`NodeRefContainer`] with an HTML element.
@@ -64,7 +95,7 @@ where
 {
     const MIN_LENGTH: usize = 0;
     type AsyncOutput = Self;
-    type State = crate::renderer::types::Element;
+    type State = NodeRefState<E, C>;
     type Cloneable = Self;
     type CloneableOwned = Self;
 
@@ -86,17 +117,21 @@ where
         self,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
-        self.container.load(el);
-        el.to_owned()
+        self.build(el)
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        self.container.load(el);
-        el.to_owned()
+        self.container.clone().load(el);
+        NodeRefState {
+            el: el.to_owned(),
+            container: self.container,
+            ty: PhantomData,
+        }
     }
 
     fn rebuild(self, state: &mut Self::State) {
-        self.container.load(state);
+        self.container.clone().load(&state.el);
+        state.container = self.container;
     }
 
     fn into_cloneable(self) -> Self::Cloneable {
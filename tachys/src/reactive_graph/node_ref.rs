@@ -7,12 +7,13 @@ use reactive_graph::{
         RwSignal,
     },
     traits::{
-        DefinedAt, Get, Notify, ReadUntracked, Set, Track, UntrackableGuard,
-        Write,
+        DefinedAt, Get, GetUntracked, Notify, ReadUntracked, Set, Track,
+        UntrackableGuard, With, WithUntracked, Write,
     },
+    wrappers::read::Signal,
 };
 use send_wrapper::SendWrapper;
-use std::{cell::Cell, ops::DerefMut};
+use std::ops::DerefMut;
 use wasm_bindgen::JsCast;
 
 /// A reactive reference to a DOM node that can be used with the `node_ref` attribute.
@@ -33,29 +34,106 @@ where
         Self(RwSignal::new(None))
     }
 
-    /// Runs the provided closure when the `NodeRef` has been connected
-    /// with its element.
+    /// Runs the provided closure when the `NodeRef` has been connected with its
+    /// element, or right away if it already has been.
+    ///
+    /// The closure runs once for each element: if the `NodeRef` is connected
+    /// with another element, because the one it was connected with has been
+    /// replaced, it runs again with the new one. It never runs during
+    /// server-side rendering, and runs after the element is claimed during
+    /// hydration.
     #[inline(always)]
-    pub fn on_load<F>(self, f: F)
+    pub fn on_load<F>(self, mut f: F)
     where
         E: 'static,
-        F: FnOnce(E::Output) + 'static,
+        F: FnMut(E::Output) + 'static,
         E: ElementType,
         E::Output: JsCast + Clone + 'static,
     {
-        let f = Cell::new(Some(f));
+        let loaded = self.get_untracked();
+        if let Some(el) = loaded.clone() {
+            f(el);
+        }
+        self.on_change(loaded, move |_, new| {
+            if let Some(el) = new {
+                f(el);
+            }
+        });
+    }
+
+    /// Runs the provided closure with the element the `NodeRef` was connected
+    /// with, once it has been removed, or replaced with another element, which
+    /// [`on_load`](Self::on_load) is called for.
+    #[inline(always)]
+    pub fn on_unload<F>(self, mut f: F)
+    where
+        E: 'static,
+        F: FnMut(E::Output) + 'static,
+        E: ElementType,
+        E::Output: JsCast + Clone + 'static,
+    {
+        self.on_change(self.get_untracked(), move |old, _| {
+            if let Some(el) = old {
+                f(el);
+            }
+        });
+    }
+
+    /// Whether the `NodeRef` is connected with an element that has not been
+    /// removed.
+    ///
+    /// This is always `false` during server-side rendering.
+    pub fn mounted(self) -> Signal<bool>
+    where
+        E::Output: JsCast + 'static,
+    {
+        let inner = self.0;
+        Signal::derive(move || inner.with(Option::is_some))
+    }
 
+    /// Runs `f` with the element the `NodeRef` was connected with and the one
+    /// it is connected with now, each time it is connected with a different
+    /// element, starting from `loaded`.
+    fn on_change(
+        self,
+        loaded: Option<E::Output>,
+        mut f: impl FnMut(Option<E::Output>, Option<E::Output>) + 'static,
+    ) where
+        E::Output: JsCast + Clone + 'static,
+    {
+        let mut connected = Connected(loaded);
         Effect::new(move |_| {
-            if let Some(node_ref) = self.get() {
-                let f = f.take().unwrap();
-                untrack(move || {
-                    f(node_ref);
-                });
+            let new = self.get();
+            let old =
+                connected.replace(new.clone(), |a, b| a.as_ref() == b.as_ref());
+            if let Some(old) = old {
+                untrack(|| f(old, new));
             }
         });
     }
 }
 
+/// The element a [`NodeRef`] was last seen to be connected with, by one of its
+/// callbacks.
+struct Connected<T>(Option<T>);
+
+impl<T> Connected<T> {
+    /// Replaces the element with `new`, if it is a different one by `same`,
+    /// returning the element it was connected with before.
+    fn replace(
+        &mut self,
+        new: Option<T>,
+        same: impl Fn(&T, &T) -> bool,
+    ) -> Option<Option<T>> {
+        let unchanged = match (&self.0, &new) {
+            (Some(a), Some(b)) => same(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        (!unchanged).then(|| std::mem::replace(&mut self.0, new))
+    }
+}
+
 impl<E> Default for NodeRef<E>
 where
     E: ElementType,
@@ -94,6 +172,20 @@ where
         self.0
             .set(Some(SendWrapper::new(el.clone().unchecked_into())));
     }
+
+    fn unload(self, el: &crate::renderer::types::Element) {
+        // the element may already have been replaced with the one that was
+        // loaded after it
+        let loaded = self.0.with_untracked(|loaded| {
+            loaded.as_ref().is_some_and(|loaded| {
+                AsRef::<wasm_bindgen::JsValue>::as_ref(&**loaded)
+                    == AsRef::<wasm_bindgen::JsValue>::as_ref(el)
+            })
+        });
+        if loaded {
+            self.0.try_set(None);
+        }
+    }
 }
 
 impl<E> DefinedAt for NodeRef<E>
@@ -170,3 +262,48 @@ where
 {
     NodeRef::new()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Connected, NodeRef};
+    use crate::html::element::Div;
+    use reactive_graph::{
+        owner::Owner,
+        traits::{GetUntracked, Set},
+    };
+    use send_wrapper::SendWrapper;
+    use wasm_bindgen::{JsCast, JsValue};
+
+    #[test]
+    fn callbacks_run_once_for_each_element() {
+        let same = |a: &&str, b: &&str| a == b;
+        let mut connected = Connected(None);
+        // the element is loaded, and rendered again without being replaced
+        assert_eq!(connected.replace(Some("first"), same), Some(None));
+        assert_eq!(connected.replace(Some("first"), same), None);
+        // the element is swapped for another one, so the first one is unloaded
+        assert_eq!(
+            connected.replace(Some("second"), same),
+            Some(Some("first"))
+        );
+        // and then removed
+        assert_eq!(connected.replace(None, same), Some(Some("second")));
+        assert_eq!(connected.replace(None, same), None);
+    }
+
+    #[test]
+    fn mounted_follows_the_connected_element() {
+        let owner = Owner::new();
+        owner.set();
+        let node_ref = NodeRef::<Div>::new();
+        let mounted = node_ref.mounted();
+        assert!(!mounted.get_untracked());
+
+        node_ref
+            .0
+            .set(Some(SendWrapper::new(JsValue::NULL.unchecked_into())));
+        assert!(mounted.get_untracked());
+        node_ref.0.set(None);
+        assert!(!mounted.get_untracked());
+    }
+}
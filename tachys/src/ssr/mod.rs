@@ -24,6 +24,28 @@ pub struct StreamBuilder {
 }
 
 type PinnedFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+/// The global that the script of each out-of-order chunk sets to the number of
+/// `Suspense` fragments that are still showing their fallbacks, once it has
+/// swapped in its own.
+///
+/// After hydration, the router keeps it up to date with the `Suspense`
+/// boundaries that are pending, so it can drive the same loading indicator
+/// during client-side navigation.
+pub const PENDING_FRAGMENTS_GLOBAL: &str = "__LEPTOS_PENDING_FRAGMENTS";
+
+/// The event that is dispatched on the `document` each time
+/// [`PENDING_FRAGMENTS_GLOBAL`] is updated.
+pub const PENDING_FRAGMENTS_EVENT: &str = "leptos:pending-fragments";
+
+/// A `<style>` and `<script>` for the `<head>` of the document, which show a
+/// loading bar at the top of the page while [`PENDING_FRAGMENTS_GLOBAL`] is
+/// more than zero, without waiting for WASM to load.
+///
+/// The bar has the ID `leptos-streaming-progress`, so its styles can be
+/// overridden.
+pub const STREAMING_PROGRESS_SNIPPET: &str =
+    include_str!("streaming_progress.html");
 type ChunkFuture = PinnedFuture<VecDeque<StreamChunk>>;
 
 impl StreamBuilder {
@@ -312,24 +334,37 @@ impl OooChunk {
         buf.push_str(
             "\";let open = undefined;let close = undefined;let walker = \
              document.createTreeWalker(document.body, \
-             NodeFilter.SHOW_COMMENT);while(walker.nextNode()) \
+             NodeFilter.SHOW_COMMENT);let opening = /^s-(\\d+-)+o$/;let \
+             pending = 0;while(walker.nextNode()) \
              {if(walker.currentNode.textContent == `s-${id}o`){ \
              open=walker.currentNode; } else \
              if(walker.currentNode.textContent == `s-${id}c`) { close = \
-             walker.currentNode;}}let range = new Range(); \
+             walker.currentNode;} else \
+             if(opening.test(walker.currentNode.textContent)) { \
+             pending++; }}let range = new Range(); \
              range.setStartBefore(open); range.setEndBefore(close);",
         );
         if replace {
+            // the fragment may have fallbacks of its own, which are pending
+            // until their fragments arrive
             buf.push_str(
                 "range.deleteContents(); let tpl = \
-                 document.getElementById(`${id}f`); \
+                 document.getElementById(`${id}f`); let inner = \
+                 document.createTreeWalker(tpl.content, \
+                 NodeFilter.SHOW_COMMENT);while(inner.nextNode()) \
+                 {if(opening.test(inner.currentNode.textContent)) { \
+                 pending++; }} \
                  close.parentNode.insertBefore(tpl.content.cloneNode(true), \
                  close);close.remove();",
             );
         } else {
             buf.push_str("close.remove();open.remove();");
         }
-        buf.push_str("})()</script>");
+        buf.push_str("window.");
+        buf.push_str(PENDING_FRAGMENTS_GLOBAL);
+        buf.push_str(" = pending;document.dispatchEvent(new Event(\"");
+        buf.push_str(PENDING_FRAGMENTS_EVENT);
+        buf.push_str("\"));})()</script>");
     }
 
     /// Consumes this structure and returns its inner chunks of the stream.
@@ -728,3 +763,51 @@ mod tests {
     }
 }
 */
+
+#[cfg(test)]
+mod pending_fragments_tests {
+    use super::{
+        OooChunk, StreamBuilder, PENDING_FRAGMENTS_EVENT,
+        PENDING_FRAGMENTS_GLOBAL, STREAMING_PROGRESS_SNIPPET,
+    };
+
+    #[test]
+    fn each_fragment_reports_how_many_are_still_pending() {
+        let report = format!(
+            "window.{PENDING_FRAGMENTS_GLOBAL} = \
+             pending;document.dispatchEvent(new \
+             Event(\"{PENDING_FRAGMENTS_EVENT}\"));}})()</script>"
+        );
+        for replace in [true, false] {
+            let mut buf = String::new();
+            OooChunk::push_end(replace, "1-", &mut buf);
+            assert!(buf.ends_with(&report), "{buf}");
+            // a fragment that replaces its fallback may bring fallbacks of its
+            // own
+            assert_eq!(buf.contains("tpl.content, NodeFilter"), replace);
+        }
+    }
+
+    #[test]
+    fn the_markers_of_the_stream_are_counted_as_pending() {
+        let mut builder = StreamBuilder::new(Some(vec![0, 12]));
+        builder.write_chunk_marker(true);
+        builder.write_chunk_marker(false);
+        assert_eq!(builder.sync_buf, "<!--s-0-12-o--><!--s-0-12-c-->");
+
+        // the script counts the comments that match the opening marker, like
+        // `s-0-12-o`
+        let mut script = String::new();
+        OooChunk::push_end(false, "0-12-", &mut script);
+        assert!(script.contains(r"let opening = /^s-(\d+-)+o$/;"));
+    }
+
+    #[test]
+    fn the_streaming_progress_snippet_follows_the_pending_fragments() {
+        assert!(STREAMING_PROGRESS_SNIPPET
+            .contains(&format!("window.{PENDING_FRAGMENTS_GLOBAL} > 0")));
+        assert!(STREAMING_PROGRESS_SNIPPET.contains(&format!(
+            "document.addEventListener(\"{PENDING_FRAGMENTS_EVENT}\", update)"
+        )));
+    }
+}
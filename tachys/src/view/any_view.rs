@@ -15,7 +15,12 @@ use crate::{
     ssr::StreamBuilder,
 };
 use futures::future::{join, join_all};
-use std::{any::TypeId, fmt::Debug};
+use std::{
+    any::TypeId,
+    collections::hash_map::DefaultHasher,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+};
 #[cfg(feature = "ssr")]
 use std::{future::Future, pin::Pin};
 
@@ -29,8 +34,13 @@ use std::{future::Future, pin::Pin};
 /// Generally speaking, using `AnyView` restricts the amount of information available to the
 /// compiler and should be limited to situations in which it is necessary to preserve the maximum
 /// amount of type information possible.
+///
+/// An `AnyView` created with [`IntoAny::into_any_keyed`] is only rebuilt if its
+/// key has changed, so a list of views can be set again without re-rendering
+/// the ones that are the same.
 pub struct AnyView {
     type_id: TypeId,
+    key: Option<u64>,
     value: Erased,
     build: fn(Erased) -> AnyViewState,
     rebuild: fn(Erased, &mut AnyViewState),
@@ -75,12 +85,59 @@ impl Debug for AnyView {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AnyView")
             .field("type_id", &self.type_id)
+            .field("key", &self.key)
             .finish_non_exhaustive()
     }
 }
+
+impl AnyView {
+    /// The [`TypeId`] of the view that was erased, which is its
+    /// [`Owned`](RenderHtml::Owned) type.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Whether the view that was erased is a `T`.
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+
+    /// Returns a reference to the view that was erased, if it is a `T`.
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.is::<T>().then(|| self.value.get_ref::<T>())
+    }
+
+    /// Returns the view that was erased, if it is a `T`, or this view
+    /// otherwise.
+    ///
+    /// ```rust
+    /// # use tachys::view::any_view::IntoAny;
+    /// let view = "hello".to_string().into_any();
+    /// assert!(!view.is::<&str>());
+    /// assert_eq!(view.downcast::<String>().unwrap(), "hello");
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn downcast<T: 'static>(self) -> Result<T, AnyView> {
+        if self.is::<T>() {
+            Ok(self.value.into_inner::<T>())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+fn hash_key<K: Hash + 'static>(key: &K) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    // keys of different types are never the same, even if they hash the same
+    // way
+    TypeId::of::<K>().hash(&mut hasher);
+    key.hash(&mut hasher);
+    hasher.finish()
+}
 /// Retained view state for [`AnyView`].
 pub struct AnyViewState {
     type_id: TypeId,
+    key: Option<u64>,
     state: ErasedLocal,
     unmount: fn(&mut ErasedLocal),
     mount: fn(
@@ -96,6 +153,7 @@ impl Debug for AnyViewState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AnyViewState")
             .field("type_id", &self.type_id)
+            .field("key", &self.key)
             .field("state", &"")
             .field("unmount", &self.unmount)
             .field("mount", &self.mount)
@@ -108,6 +166,33 @@ impl Debug for AnyViewState {
 pub trait IntoAny {
     /// Converts the view into a type-erased [`AnyView`].
     fn into_any(self) -> AnyView;
+
+    /// Converts the view into a type-erased [`AnyView`] that is identified by
+    /// `key`.
+    ///
+    /// When a keyed `AnyView` is rendered in place of one with the same type
+    /// and key, it is not rebuilt, and the view that is already rendered is
+    /// kept. This lets a list of erased views, like `Vec<AnyView>`, be set
+    /// again without re-rendering the entries that have not changed, including
+    /// inside the rows of a keyed list like `<For/>`.
+    ///
+    /// ```rust
+    /// # use tachys::view::any_view::{AnyView, IntoAny};
+    /// let notifications: Vec<AnyView> = vec![
+    ///     "Saved".into_any_keyed(1),
+    ///     "Uploaded".into_any_keyed(2),
+    /// ];
+    /// # _ = notifications;
+    /// ```
+    fn into_any_keyed<K>(self, key: K) -> AnyView
+    where
+        Self: Sized,
+        K: Hash + 'static,
+    {
+        let mut view = self.into_any();
+        view.key = Some(hash_key(&key));
+        view
+    }
 }
 
 /// A more general version of [`IntoAny`] that allows into [`AnyView`],
@@ -256,6 +341,7 @@ where
             let state = ErasedLocal::new(value.into_inner::<T>().build());
             AnyViewState {
                 type_id: TypeId::of::<T>(),
+                key: None,
                 state,
                 mount: mount_any::<T>,
                 unmount: unmount_any::<T>,
@@ -275,6 +361,7 @@ where
             );
             AnyViewState {
                 type_id: TypeId::of::<T>(),
+                key: None,
                 state,
                 mount: mount_any::<T>,
                 unmount: unmount_any::<T>,
@@ -294,6 +381,7 @@ where
         let value = self.into_owned();
         AnyView {
             type_id: TypeId::of::<T::Owned>(),
+            key: None,
             build: build::<T::Owned>,
             rebuild: rebuild::<T::Owned>,
             #[cfg(feature = "ssr")]
@@ -319,11 +407,18 @@ impl Render for AnyView {
     type State = AnyViewState;
 
     fn build(self) -> Self::State {
-        (self.build)(self.value)
+        let mut state = (self.build)(self.value);
+        state.key = self.key;
+        state
     }
 
     fn rebuild(self, state: &mut Self::State) {
         if self.type_id == state.type_id {
+            if self.key.is_some() && self.key == state.key {
+                // the same keyed view is already rendered
+                return;
+            }
+            state.key = self.key;
             (self.rebuild)(self.value, state)
         } else {
             let mut new = self.build();
@@ -371,7 +466,9 @@ impl RenderHtml for AnyView {
     async fn resolve(self) -> Self::AsyncOutput {
         #[cfg(feature = "ssr")]
         {
-            (self.resolve)(self.value).await
+            let mut view = (self.resolve)(self.value).await;
+            view.key = self.key;
+            view
         }
         #[cfg(not(feature = "ssr"))]
         panic!(
@@ -512,8 +609,9 @@ impl RenderHtml for AnyView {
                 if cfg!(feature = "mark_branches") {
                     cursor.advance_to_placeholder(position);
                 }
-                let state =
+                let mut state =
                     (self.hydrate_from_server)(self.value, cursor, position);
+                state.key = self.key;
                 if cfg!(feature = "mark_branches") {
                     cursor.advance_to_placeholder(position);
                 }
@@ -754,3 +852,174 @@ mod tests {
     }
 }
  */
+
+#[cfg(test)]
+mod rebuild_tests {
+    use super::{AnyViewState, IntoAny};
+    use crate::{
+        html::attribute::{any_attribute::AnyAttribute, Attribute},
+        hydration::Cursor,
+        view::{
+            add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
+            RenderHtml,
+        },
+    };
+    use std::any::TypeId;
+
+    /// A view that renders nothing, so that it can be built without a DOM.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Counter(u32);
+
+    /// The state of a [`Counter`], which counts how many times it has been
+    /// rebuilt.
+    #[derive(Debug)]
+    struct CounterState {
+        value: u32,
+        rebuilds: usize,
+        mounted: bool,
+    }
+
+    impl Render for Counter {
+        type State = CounterState;
+
+        fn build(self) -> Self::State {
+            CounterState {
+                value: self.0,
+                rebuilds: 0,
+                mounted: true,
+            }
+        }
+
+        fn rebuild(self, state: &mut Self::State) {
+            state.value = self.0;
+            state.rebuilds += 1;
+        }
+    }
+
+    impl AddAnyAttr for Counter {
+        type Output<SomeNewAttr: Attribute> = Counter;
+
+        fn add_any_attr<NewAttr: Attribute>(
+            self,
+            _attr: NewAttr,
+        ) -> Self::Output<NewAttr>
+        where
+            Self::Output<NewAttr>: RenderHtml,
+        {
+            self
+        }
+    }
+
+    impl RenderHtml for Counter {
+        type AsyncOutput = Self;
+        type Owned = Self;
+
+        const MIN_LENGTH: usize = 0;
+
+        fn dry_resolve(&mut self) {}
+
+        async fn resolve(self) -> Self::AsyncOutput {
+            self
+        }
+
+        fn to_html_with_buf(
+            self,
+            _buf: &mut String,
+            _position: &mut Position,
+            _escape: bool,
+            _mark_branches: bool,
+            _extra_attrs: Vec<AnyAttribute>,
+        ) {
+        }
+
+        fn hydrate<const FROM_SERVER: bool>(
+            self,
+            _cursor: &Cursor,
+            _position: &PositionState,
+        ) -> Self::State {
+            self.build()
+        }
+
+        fn into_owned(self) -> Self::Owned {
+            self
+        }
+    }
+
+    impl Mountable for CounterState {
+        fn unmount(&mut self) {
+            self.mounted = false;
+        }
+
+        fn mount(
+            &mut self,
+            _parent: &crate::renderer::types::Element,
+            _marker: Option<&crate::renderer::types::Node>,
+        ) {
+            self.mounted = true;
+        }
+
+        fn insert_before_this(&self, _child: &mut dyn Mountable) -> bool {
+            false
+        }
+
+        fn elements(&self) -> Vec<crate::renderer::types::Element> {
+            vec![]
+        }
+    }
+
+    fn counter(state: &AnyViewState) -> &CounterState {
+        state.state.get_ref::<CounterState>()
+    }
+
+    #[test]
+    fn keyed_view_with_the_same_key_keeps_its_state() {
+        let mut state = Counter(1).into_any_keyed("a").build();
+        Counter(2).into_any_keyed("a").rebuild(&mut state);
+        assert_eq!(counter(&state).value, 1);
+        assert_eq!(counter(&state).rebuilds, 0);
+        assert!(counter(&state).mounted);
+    }
+
+    #[test]
+    fn keyed_view_with_another_key_is_rebuilt() {
+        let mut state = Counter(1).into_any_keyed("a").build();
+        Counter(2).into_any_keyed("b").rebuild(&mut state);
+        assert_eq!(counter(&state).value, 2);
+        assert_eq!(counter(&state).rebuilds, 1);
+
+        // the new key is kept, so the next view with it is not rebuilt
+        Counter(3).into_any_keyed("b").rebuild(&mut state);
+        assert_eq!(counter(&state).value, 2);
+
+        // keys of different types are different keys
+        Counter(4)
+            .into_any_keyed(String::from("b"))
+            .rebuild(&mut state);
+        assert_eq!(counter(&state).value, 4);
+    }
+
+    #[test]
+    fn unkeyed_views_are_always_rebuilt() {
+        let mut state = Counter(1).into_any().build();
+        Counter(1).into_any().rebuild(&mut state);
+        Counter(2).into_any().rebuild(&mut state);
+        assert_eq!(counter(&state).value, 2);
+        assert_eq!(counter(&state).rebuilds, 2);
+
+        // nor is a keyed view that replaces an unkeyed one kept as it is
+        Counter(3).into_any_keyed("a").rebuild(&mut state);
+        assert_eq!(counter(&state).value, 3);
+        Counter(4).into_any().rebuild(&mut state);
+        assert_eq!(counter(&state).value, 4);
+    }
+
+    #[test]
+    fn keyed_view_of_another_type_replaces_the_view() {
+        let mut state = Counter(1).into_any_keyed("a").build();
+        (Counter(2),).into_any_keyed("a").rebuild(&mut state);
+        assert_eq!(state.type_id, TypeId::of::<(Counter,)>());
+        // a new state is built for it, rather than rebuilding the old one
+        assert_eq!(counter(&state).value, 2);
+        assert_eq!(counter(&state).rebuilds, 0);
+    }
+}
@@ -893,3 +893,42 @@ mod tests {
     }
 }
 */
+
+#[cfg(test)]
+mod diff_tests {
+    use super::{diff, DiffOpAdd, DiffOpAddMode, DiffOpRemove, FxIndexSet};
+
+    fn keys(keys: &[u32]) -> FxIndexSet<u32> {
+        keys.iter().copied().collect()
+    }
+
+    // only the rows that a diff adds are created by the view function of a
+    // keyed list like `<For/>`, while the others keep the views they already
+    // rendered
+    #[test]
+    fn rows_with_the_same_keys_are_not_rebuilt() {
+        let same = diff(&keys(&[1, 2, 3]), &keys(&[1, 2, 3]));
+        assert!(same.added.is_empty());
+        assert!(same.removed.is_empty());
+        assert!(same.moved.is_empty());
+        assert!(!same.clear);
+
+        let reordered = diff(&keys(&[1, 2, 3]), &keys(&[3, 1, 2]));
+        assert!(reordered.added.is_empty());
+        assert!(reordered.removed.is_empty());
+        assert!(!reordered.moved.is_empty());
+    }
+
+    #[test]
+    fn only_rows_with_new_keys_are_built() {
+        let replaced = diff(&keys(&[1, 2, 3]), &keys(&[1, 4, 3]));
+        assert_eq!(
+            replaced.added,
+            [DiffOpAdd {
+                at: 1,
+                mode: DiffOpAddMode::Normal,
+            }]
+        );
+        assert_eq!(replaced.removed, [DiffOpRemove { at: 1 }]);
+    }
+}